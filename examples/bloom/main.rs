@@ -1,6 +1,6 @@
 extern crate sdl2;
 
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, path::Path, rc::Rc};
 
 use cairo::{
     app::{
@@ -10,6 +10,7 @@ use cairo::{
     buffer::framebuffer::Framebuffer,
     color::Color,
     device::{game_controller::GameControllerState, keyboard::KeyboardState, mouse::MouseState},
+    fs::resolver::AssetResolver,
     matrix::Mat4,
     render::{
         options::{
@@ -32,10 +33,14 @@ use cairo::{
     vec::vec3::Vec3,
 };
 
+use sdl2::keyboard::Keycode;
+
 use scene::make_scene;
 
 mod scene;
 
+static TOGGLE_BLOOM_MIPMAPS_KEYCODE: Keycode = Keycode::F3;
+
 fn main() -> Result<(), String> {
     let mut window_info = AppWindowInfo {
         title: "examples/bloom".to_string(),
@@ -117,8 +122,14 @@ fn main() -> Result<(), String> {
         let bloom_dirt_mask_handle = {
             let mut texture_u8_arena = scene_context.resources.texture_u8.borrow_mut();
 
+            // Looks for assets under `CAIRO_ASSET_PATH` first, if it's set.
+
+            let mut resolver = AssetResolver::from_env("CAIRO_ASSET_PATH");
+
+            resolver.add_root(Path::new("./examples/bloom/assets"));
+
             let mut map = TextureMap::new(
-                "./examples/bloom/assets/dirt_mask.png",
+                &resolver.resolve("dirt_mask.png")?.display().to_string(),
                 TextureMapStorageFormat::RGB24,
             );
 
@@ -197,6 +208,15 @@ fn main() -> Result<(), String> {
         }
     };
 
+    // Optionally draws the bloom pass's (tone-mapped) mipmaps over the frame.
+
+    let draw_bloom_mipmaps_rc = RefCell::new(false);
+
+    println!(
+        "Press {} to show the bloom mipmaps.",
+        TOGGLE_BLOOM_MIPMAPS_KEYCODE
+    );
+
     let mut update = |app: &mut App,
                       keyboard_state: &mut KeyboardState,
                       mouse_state: &mut MouseState,
@@ -230,6 +250,15 @@ fn main() -> Result<(), String> {
 
         renderer.shader_options.update(keyboard_state);
 
+        if keyboard_state
+            .newly_pressed_keycodes
+            .contains(&TOGGLE_BLOOM_MIPMAPS_KEYCODE)
+        {
+            let mut draw_bloom_mipmaps = draw_bloom_mipmaps_rc.borrow_mut();
+
+            *draw_bloom_mipmaps = !*draw_bloom_mipmaps;
+        }
+
         Ok(())
     };
 
@@ -272,10 +301,12 @@ fn main() -> Result<(), String> {
 
                 color_buffer.copy_to(canvas);
 
-                if false {
+                if *draw_bloom_mipmaps_rc.borrow() {
                     let bloom_texture_map = bloom_texture_map_rc.borrow();
 
-                    blit_bloom_mipmaps_to_canvas(&bloom_texture_map, canvas);
+                    let tone_mapping = renderer_rc.borrow().options.tone_mapping;
+
+                    blit_bloom_mipmaps_to_canvas(&bloom_texture_map, tone_mapping, canvas);
                 }
 
                 Ok(())
@@ -289,7 +320,11 @@ fn main() -> Result<(), String> {
     Ok(())
 }
 
-fn blit_bloom_mipmaps_to_canvas(bloom_texture_map: &TextureMap<Vec3>, canvas: &mut [u8]) {
+fn blit_bloom_mipmaps_to_canvas(
+    bloom_texture_map: &TextureMap<Vec3>,
+    tone_mapping: ToneMappingOperator,
+    canvas: &mut [u8],
+) {
     let width = bloom_texture_map.levels[0].0.width;
 
     let (mut thumbnail_start_x, thumbnail_start_y) = (0, 0);
@@ -304,11 +339,7 @@ fn blit_bloom_mipmaps_to_canvas(bloom_texture_map: &TextureMap<Vec3>, canvas: &m
 
                 let bloom_color_hdr = mipmap.get(x, y);
 
-                static TONE_MAPPING_OPERATOR: ToneMappingOperator =
-                    ToneMappingOperator::Exposure(1.0);
-
-                let mut bloom_color_tone_mapped_linear =
-                    TONE_MAPPING_OPERATOR.map(*bloom_color_hdr);
+                let mut bloom_color_tone_mapped_linear = tone_mapping.map(*bloom_color_hdr);
 
                 bloom_color_tone_mapped_linear.linear_to_srgb();
 
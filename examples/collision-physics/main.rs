@@ -1,6 +1,6 @@
 extern crate sdl2;
 
-use std::{cell::RefCell, cmp::Ordering, f32::consts::TAU, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, f32::consts::TAU, path::Path, rc::Rc};
 
 use sdl2::keyboard::Keycode;
 
@@ -19,7 +19,7 @@ use cairo::{
     },
     matrix::Mat4,
     mesh::{
-        obj::load::{load_obj, LoadObjResult, ProcessGeometryFlag},
+        obj::load::{load_obj_cached, LoadObjResult, ProcessGeometryFlag},
         Mesh,
     },
     render::{options::RenderOptions, Renderer},
@@ -84,8 +84,9 @@ fn main() -> Result<(), String> {
         let mut material_arena = resources.material.borrow_mut();
         let mut texture_u8_arena = resources.texture_u8.borrow_mut();
 
-        let LoadObjResult(_level_geometry, level_meshes) = load_obj(
+        let LoadObjResult(_level_geometry, level_meshes) = load_obj_cached(
             "./data/blender/collision-level/collision-level_004.obj",
+            Path::new("./cache/meshes"),
             &mut material_arena,
             &mut texture_u8_arena,
             Some(ProcessGeometryFlag::Null | ProcessGeometryFlag::Center),
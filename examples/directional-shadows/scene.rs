@@ -11,7 +11,9 @@ use cairo::{
         context::utils::make_cube_scene,
         environment::Environment,
         graph::SceneGraph,
-        light::{ambient_light::AmbientLight, directional_light::DirectionalLight},
+        light::{
+            ambient_light::AmbientLight, directional_light::DirectionalLight, shadow::ShadowFilter,
+        },
         node::{SceneNode, SceneNodeType},
         resources::SceneResources,
     },
@@ -72,6 +74,8 @@ pub(crate) fn make_scene(
             directional_light.intensities = vec3::ONES * 0.6;
 
             directional_light.enable_shadow_maps(1024, 100.0, resources.clone());
+
+            directional_light.shadow_filter = ShadowFilter::Pcf { kernel: 3 };
         }
     }
 
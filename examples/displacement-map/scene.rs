@@ -67,6 +67,10 @@ pub fn make_scene(
 
     brick_material.displacement_scale = 0.05;
 
+    // Used by parallax occlusion mapping (cycle parallax modes with `0`).
+
+    brick_material.parallax_steps = 32;
+
     brick_material.load_all_maps(texture_u8_arena, rendering_context)?;
 
     // Box material
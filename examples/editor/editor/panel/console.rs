@@ -1,23 +1,243 @@
+use std::{cell::RefCell, rc::Rc};
+
 use serde::{Deserialize, Serialize};
 
 use cairo::{
+    color::{self, Color},
+    command::{CommandArgument, CommandHistory, CommandRegistry},
+    log::{self, LogBuffer, LogCategory, LogLevel, GLOBAL_LOGGER, LOG_LEVELS},
     serde::PostDeserialize,
-    ui::{fastpath::text::text, ui_box::tree::UIBoxTree},
+    ui::{
+        context::GLOBAL_UI_CONTEXT,
+        fastpath::{
+            radio::{radio_group, RadioOption},
+            text::text,
+            text_input::{text_input_with_submit, TextInputNavigation},
+        },
+        ui_box::tree::UIBoxTree,
+    },
 };
 
 use super::PanelInstance;
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize)]
-pub struct ConsolePanel {}
+static CONSOLE_HISTORY_LENGTH: usize = 32;
+
+static CONSOLE_COMMAND_HISTORY_LENGTH: usize = 64;
+
+static LOG_LEVEL_NAMES: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+
+thread_local! {
+    static CONSOLE_LOG_BUFFER: Rc<RefCell<LogBuffer>> =
+        Rc::new(RefCell::new(LogBuffer::new(CONSOLE_HISTORY_LENGTH)));
+
+    static CONSOLE_COMMANDS: RefCell<CommandRegistry<ConsolePanel>> =
+        RefCell::new(make_console_command_registry());
+}
+
+// Routes the global logger's records into the console's buffer.
+pub fn subscribe_console_to_log() {
+    CONSOLE_LOG_BUFFER.with(|buffer| {
+        GLOBAL_LOGGER.with(|logger| {
+            logger.borrow_mut().subscribe_buffer(buffer.clone());
+        });
+    });
+}
+
+fn make_console_command_registry() -> CommandRegistry<ConsolePanel> {
+    let mut registry = CommandRegistry::default();
+
+    registry.register(
+        "clear",
+        "Clears the console.",
+        vec![],
+        Box::new(|_panel: &mut ConsolePanel, _args| {
+            CONSOLE_LOG_BUFFER.with(|buffer| buffer.borrow_mut().clear());
+
+            Ok(())
+        }),
+    );
+
+    registry.register(
+        "filter",
+        "Hides console messages below a level.",
+        vec![CommandArgument::with_completions("level", &LOG_LEVEL_NAMES)],
+        Box::new(|panel: &mut ConsolePanel, args| {
+            panel.minimum_level = args.parse::<LogLevel>(0)?;
+
+            Ok(())
+        }),
+    );
+
+    registry.register(
+        "log_level",
+        "Sets the level of the engine's logger.",
+        vec![CommandArgument::with_completions("level", &LOG_LEVEL_NAMES)],
+        Box::new(|_panel: &mut ConsolePanel, args| {
+            let level = args.parse::<LogLevel>(0)?;
+
+            GLOBAL_LOGGER.with(|logger| logger.borrow_mut().set_level(level));
+
+            Ok(())
+        }),
+    );
+
+    registry
+}
+
+fn get_log_level_color(level: LogLevel) -> Color {
+    match level {
+        LogLevel::Trace | LogLevel::Debug => color::LIGHT_GRAY,
+        LogLevel::Info => color::WHITE,
+        LogLevel::Warn => color::YELLOW,
+        LogLevel::Error => color::RED,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsolePanel {
+    // Records below this level are hidden.
+    #[serde(default)]
+    minimum_level: LogLevel,
+    #[serde(skip)]
+    command_input: String,
+    #[serde(skip, default = "make_command_history")]
+    command_history: CommandHistory,
+}
+
+fn make_command_history() -> CommandHistory {
+    CommandHistory::new(CONSOLE_COMMAND_HISTORY_LENGTH)
+}
+
+impl Default for ConsolePanel {
+    fn default() -> Self {
+        Self {
+            minimum_level: Default::default(),
+            command_input: Default::default(),
+            command_history: make_command_history(),
+        }
+    }
+}
 
 impl PostDeserialize for ConsolePanel {
     fn post_deserialize(&mut self) {}
 }
 
+impl ConsolePanel {
+    fn submit_command(&mut self) {
+        let input = std::mem::take(&mut self.command_input);
+
+        if input.trim().is_empty() {
+            return;
+        }
+
+        self.command_history.push(&input);
+
+        log::info(LogCategory::Console, &format!("> {}", input));
+
+        let result = CONSOLE_COMMANDS.with(|registry| registry.borrow_mut().execute(self, &input));
+
+        if let Err(err) = result {
+            log::error(LogCategory::Console, &err);
+        }
+    }
+}
+
 impl PanelInstance for ConsolePanel {
     fn render(&mut self, tree: &mut UIBoxTree) -> Result<(), String> {
         tree.push(text(String::new(), "Console".to_string()))?;
 
+        // Level filter
+
+        let level_options: Vec<RadioOption> = LOG_LEVELS
+            .iter()
+            .map(|level| RadioOption {
+                label: level.to_string(),
+            })
+            .collect();
+
+        let selected_index = LOG_LEVELS
+            .iter()
+            .position(|level| *level == self.minimum_level)
+            .unwrap_or(0);
+
+        if let Some(index) = radio_group(
+            "ConsolePanel.minimum_level".to_string(),
+            &level_options,
+            selected_index,
+            tree,
+        )? {
+            self.minimum_level = LOG_LEVELS[index];
+        }
+
+        // Records, color-coded by level.
+
+        CONSOLE_LOG_BUFFER.with(|buffer| -> Result<(), String> {
+            for record in buffer.borrow().iter_at_level(self.minimum_level) {
+                GLOBAL_UI_CONTEXT.with(|ctx| {
+                    ctx.styles
+                        .borrow_mut()
+                        .text_color
+                        .push(get_log_level_color(record.level));
+                });
+
+                let line = text(String::new(), record.to_string());
+
+                GLOBAL_UI_CONTEXT.with(|ctx| {
+                    ctx.styles.borrow_mut().text_color.pop();
+                });
+
+                tree.push(line)?;
+            }
+
+            Ok(())
+        })?;
+
+        // Command input, with suggested completions.
+
+        let input_result = text_input_with_submit(
+            "ConsolePanel.command_input".to_string(),
+            &self.command_input,
+            tree,
+        )?;
+
+        if let Some(new_value) = input_result.value {
+            self.command_input = new_value;
+        }
+
+        match input_result.navigation {
+            Some(TextInputNavigation::Complete) => {
+                if let Some(completed) = CONSOLE_COMMANDS
+                    .with(|registry| registry.borrow().complete_line(&self.command_input))
+                {
+                    self.command_input = completed;
+                }
+            }
+            Some(TextInputNavigation::Previous) => {
+                if let Some(line) = self.command_history.get_previous() {
+                    self.command_input = line.to_string();
+                }
+            }
+            Some(TextInputNavigation::Next) => {
+                self.command_input = self
+                    .command_history
+                    .get_next()
+                    .unwrap_or_default()
+                    .to_string();
+            }
+            None => (),
+        }
+
+        if input_result.was_submitted {
+            self.submit_command();
+        } else if !self.command_input.is_empty() {
+            let completions =
+                CONSOLE_COMMANDS.with(|registry| registry.borrow().complete(&self.command_input));
+
+            if !completions.is_empty() {
+                tree.push(text(String::new(), completions.join("  ")))?;
+            }
+        }
+
         Ok(())
     }
 }
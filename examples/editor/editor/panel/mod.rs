@@ -73,6 +73,7 @@ pub fn build_floating_window_panel_tree<'a>(
         resizable: true,
         alpha_split: 1.0,
         instance_data: Some(panel_instance_data),
+        tabs: vec![],
         layout_direction: UILayoutDirection::TopToBottom,
     }))
 }
@@ -89,6 +90,7 @@ pub fn build_main_window_panel_tree<'a>(
         resizable: true,
         alpha_split: 1.0,
         instance_data: None,
+        tabs: vec![],
         layout_direction: UILayoutDirection::LeftToRight,
     });
 
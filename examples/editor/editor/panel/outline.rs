@@ -2,13 +2,21 @@ use serde::{Deserialize, Serialize};
 
 use cairo::{
     serde::PostDeserialize,
-    ui::{fastpath::text::text, ui_box::tree::UIBoxTree},
+    ui::{
+        context_menu::{do_context_menu, ContextMenuItem},
+        fastpath::text::text,
+        ui_box::{tree::UIBoxTree, UIBoxFeatureFlag},
+    },
 };
 
 use super::PanelInstance;
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
-pub struct OutlinePanel {}
+pub struct OutlinePanel {
+    // Label of the last action chosen from the panel's context menu.
+    #[serde(skip)]
+    last_action: Option<String>,
+}
 
 impl PostDeserialize for OutlinePanel {
     fn post_deserialize(&mut self) {}
@@ -16,7 +24,44 @@ impl PostDeserialize for OutlinePanel {
 
 impl PanelInstance for OutlinePanel {
     fn render(&mut self, tree: &mut UIBoxTree) -> Result<(), String> {
-        tree.push(text(String::new(), "Outline".to_string()))?;
+        let label = match &self.last_action {
+            Some(action) => format!("Outline ({})", action),
+            None => "Outline".to_string(),
+        };
+
+        let mut label_box = text("OutlinePanelLabel".to_string(), label);
+
+        label_box.features |= UIBoxFeatureFlag::Hoverable;
+
+        let key = label_box.key.clone();
+
+        let interaction = tree.push(label_box)?;
+
+        let items = vec![
+            ContextMenuItem::Submenu(
+                "Add".to_string(),
+                vec![
+                    ContextMenuItem::Action("Cube".to_string()),
+                    ContextMenuItem::Action("Sphere".to_string()),
+                ],
+            ),
+            ContextMenuItem::Separator,
+            ContextMenuItem::Action("Collapse all".to_string()),
+        ];
+
+        let result = do_context_menu(&key, &interaction, &items);
+
+        if let Some(path) = result.selected {
+            let mut chosen = &items[path[0]];
+
+            for index in &path[1..] {
+                if let ContextMenuItem::Submenu(_, children) = chosen {
+                    chosen = &children[*index];
+                }
+            }
+
+            self.last_action = chosen.label().map(|label| label.to_string());
+        }
 
         Ok(())
     }
@@ -3,6 +3,7 @@ use cairo::ui::{
     fastpath::{
         button::button,
         container::{container, greedy_container},
+        debug_info::debug_info,
         spacer::spacer,
     },
     ui_box::{tree::UIBoxTree, UIBox, UIBoxFeatureFlag, UIBoxFeatureMask, UILayoutDirection},
@@ -159,7 +160,7 @@ pub fn build_toolbar_ui(_ctx: &UIContext, tree: &mut UIBoxTree) -> Result<(), St
 
         // Button.
 
-        tree.push(button(
+        let mut button_box = button(
             format!("ToolbarItemList_Item{}", button_index),
             button_label.to_string(),
             Some([
@@ -172,10 +173,20 @@ pub fn build_toolbar_ui(_ctx: &UIContext, tree: &mut UIBoxTree) -> Result<(), St
                     strictness: 1.0,
                 },
             ]),
-        ))?;
+        );
+
+        button_box.tooltip = Some(format!("Toolbar action {}", button_index + 1));
+
+        tree.push(button_box)?;
     }
 
     tree.pop_parent()?;
 
+    // Frame rate and CPU capabilities.
+
+    tree.push(spacer(16))?;
+
+    debug_info("ToolbarDebugInfo".to_string(), tree)?;
+
     tree.pop_parent()
 }
@@ -1,6 +1,6 @@
 extern crate sdl2;
 
-use std::{cell::RefCell, env, rc::Rc};
+use std::{cell::RefCell, env, path::Path, rc::Rc, time::Duration};
 
 use current_platform::CURRENT_PLATFORM;
 
@@ -18,6 +18,8 @@ use cairo::{
         mouse::{self, MouseState},
     },
     font::cache::FontCache,
+    fs::watcher::DirectoryWatcher,
+    log::{self, LogCategory},
     resource::handle::Handle,
     scene::{
         context::{utils::make_cube_scene, SceneContext},
@@ -39,8 +41,8 @@ use cairo::{
 };
 
 use editor::panel::{
-    build_floating_window_panel_tree, EditorPanelRenderCallbacks, PanelInstance,
-    EDITOR_PANEL_ARENAS,
+    build_floating_window_panel_tree, console::subscribe_console_to_log,
+    EditorPanelRenderCallbacks, PanelInstance, EDITOR_PANEL_ARENAS,
 };
 
 pub mod editor;
@@ -332,6 +334,7 @@ fn main() -> Result<(), String> {
                         with_titlebar: true,
                         size: (300, 225),
                         position: (100 + i * 100, 100 + i * 100),
+                        ..Default::default()
                     },
                     None,
                     floating_window_panel_tree,
@@ -382,7 +385,7 @@ fn main() -> Result<(), String> {
 
             // Rebuild the UI tree based on the new window (root) resolution.
 
-            window_list.rebuild_ui_trees(resolution);
+            window_list.rebuild_ui_trees(resolution)?;
         }
 
         framebuffer.clear(None);
@@ -432,6 +435,22 @@ fn main() -> Result<(), String> {
         }
     });
 
+    subscribe_console_to_log();
+
+    // Watches the assets directory (if any) so that changed files can be
+    // hot-reloaded.
+
+    let assets_path = Path::new("./assets");
+
+    let mut assets_watcher = if assets_path.is_dir() {
+        Some(DirectoryWatcher::new(
+            assets_path,
+            Duration::from_millis(500),
+        )?)
+    } else {
+        None
+    };
+
     let mut update = |app: &mut App,
                       keyboard_state: &mut KeyboardState,
                       mouse_state: &mut MouseState,
@@ -452,7 +471,23 @@ fn main() -> Result<(), String> {
 
         let mut window_list = window_list_rc.borrow_mut();
 
-        window_list.rebuild_ui_trees(resolution);
+        window_list.rebuild_ui_trees(resolution)?;
+
+        // A failed scan is retried on the next poll.
+
+        if let Some(watcher) = assets_watcher.as_mut() {
+            match watcher.poll() {
+                Ok(events) => {
+                    for event in events {
+                        log::info(
+                            LogCategory::IO,
+                            &format!("{} asset: {}", event.kind, event.path.display()),
+                        );
+                    }
+                }
+                Err(err) => log::error(LogCategory::IO, &err),
+            }
+        }
 
         Ok(())
     };
@@ -102,7 +102,7 @@ pub fn make_sphere_grid_scene(
         None,
     );
 
-    let mesh = meshes[1].to_owned();
+    let mesh = meshes[0].to_owned();
     let mesh_handle = mesh_arena.insert(mesh);
 
     // Generate a grid of mesh instances.
@@ -5,9 +5,10 @@ use uuid::Uuid;
 use cairo::{
     buffer::Buffer2D,
     color::{self, Color},
-    device::{keyboard::KeyboardState, mouse::MouseState},
+    device::{cpu::CpuCapabilities, keyboard::KeyboardState, mouse::MouseState},
     texture::map::TextureMap,
     time::TimingInfo,
+    ui::plot::{LinePlotOptions as PlotOptions, PlotRange},
 };
 
 use super::ui::{
@@ -20,9 +21,11 @@ use super::ui::{
         item::{ItemLayoutHorizontalAlignment, ItemLayoutOptions, ItemTextAlignment},
         UILayoutContext,
     },
+    plot::{do_line_plot, LinePlotOptions},
     separator::{do_separator, SeparatorOptions},
     slider::{do_slider, NumberSliderOptions},
     text::{do_text, TextOptions},
+    textarea::{do_textarea, TextareaOptions},
     textbox::{do_textbox, TextboxOptions},
 };
 
@@ -38,6 +41,7 @@ pub fn draw_sample_panel_contents(
     textboxes_model: &mut HashMap<String, String>,
     checkboxes_model: &mut HashMap<String, bool>,
     timing_info: &TimingInfo,
+    cpu_capabilities: &CpuCapabilities,
     wojak_texture: &mut TextureMap,
 ) {
     // Draw a bordered button.
@@ -45,6 +49,7 @@ pub fn draw_sample_panel_contents(
     let button_options = ButtonOptions {
         label: "Bordered button".to_string(),
         with_border: true,
+        tooltip: Some("Prints a message when clicked".to_string()),
         ..Default::default()
     };
 
@@ -171,6 +176,17 @@ pub fn draw_sample_panel_contents(
         },
     );
 
+    do_text(
+        ctx,
+        layout,
+        parent_buffer,
+        &TextOptions {
+            text: cpu_capabilities.to_string(),
+            color: color::WHITE,
+            ..Default::default()
+        },
+    );
+
     // Draw a separator.
 
     do_separator(
@@ -242,12 +258,71 @@ pub fn draw_sample_panel_contents(
         println!("You edited a Textbox ({})!", textbox_id);
     }
 
+    // Draw a textarea.
+
+    let textarea_options = TextareaOptions {
+        label: format!("Textarea {}", panel_id.item).to_string(),
+        rows: 4,
+        ..Default::default()
+    };
+
+    // Draw a line plot (e.g., a flickering light's intensity over time).
+
+    let intensities: Vec<f32> = (0..64)
+        .map(|i| {
+            let t = timing_info.uptime_seconds - i as f32 * 0.05;
+
+            (t * 3.0).sin() * 0.5 + (t * 7.0).sin() * 0.25
+        })
+        .rev()
+        .collect();
+
+    do_line_plot(
+        ctx,
+        layout,
+        parent_buffer,
+        &intensities,
+        &LinePlotOptions {
+            label: "Light intensity".to_string(),
+            plot: PlotOptions {
+                range: PlotRange::Fixed {
+                    min: -1.0,
+                    max: 1.0,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let textarea_model_key = format!("{}_textarea", panel_uuid);
+
+    textboxes_model
+        .entry(textarea_model_key.clone())
+        .or_default();
+
+    if do_textarea(
+        ctx,
+        layout,
+        parent_buffer,
+        timing_info.uptime_seconds,
+        keyboard_state,
+        mouse_state,
+        &textarea_options,
+        textboxes_model.entry(textarea_model_key),
+    )
+    .did_edit
+    {
+        println!("You edited a Textarea!");
+    }
+
     // Draw a number slider.
 
     let slider_options = NumberSliderOptions {
         label: format!("Slider {}", panel_id.item).to_string(),
         min: Some(-1.0 * panel_id.item as f32),
         max: Some(1.0 * panel_id.item as f32),
+        tooltip: Some("Click to type; shift-drag for fine control; ctrl-drag to snap".to_string()),
         ..Default::default()
     };
 
@@ -265,6 +340,7 @@ pub fn draw_sample_panel_contents(
         ctx,
         layout,
         parent_buffer,
+        keyboard_state,
         mouse_state,
         &slider_options,
         slider_model_entry,
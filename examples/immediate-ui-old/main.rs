@@ -20,14 +20,17 @@ use cairo::{
     device::{game_controller::GameControllerState, keyboard::KeyboardState, mouse::MouseState},
     font::{cache::FontCache, FontInfo},
     texture::map::{TextureMap, TextureMapStorageFormat},
+    ui::{context_menu::ContextMenuItem, text_edit::SystemClipboard},
 };
 
 mod ui;
 
 use ui::{
     context::{UIContext, UIID},
+    context_menu::{do_context_menu, ContextMenuOptions},
     layout::{UILayoutContext, UILayoutDirection, UILayoutExtent},
     panel::{do_panel, PanelOptions, PanelTitlebarOptions},
+    tooltip::do_tooltip,
 };
 
 mod draw_sample_panel_contents;
@@ -114,6 +117,10 @@ fn main() -> Result<(), String> {
             Box::leak(Box::new(RefCell::new(Default::default()))),
         ))));
 
+    global_ui_context.borrow_mut().clipboard = Box::new(SystemClipboard::new(
+        app.context.sdl_context.video()?.clipboard(),
+    ));
+
     // UI panel extents
 
     let root_id = UIID {
@@ -183,6 +190,23 @@ fn main() -> Result<(), String> {
 
     wojak_texture.load(rendering_context).unwrap();
 
+    // Context menu (right-click)
+
+    let context_menu_options = ContextMenuOptions {
+        items: vec![
+            ContextMenuItem::Action("Toggle layout direction".to_string()),
+            ContextMenuItem::Separator,
+            ContextMenuItem::Submenu(
+                "Add".to_string(),
+                vec![
+                    ContextMenuItem::Action("Cube".to_string()),
+                    ContextMenuItem::Action("Sphere".to_string()),
+                ],
+            ),
+        ],
+        ..Default::default()
+    };
+
     let layout_direction = UILayoutDirection::TopToBottom;
 
     let layout_direction_rc = RefCell::new(layout_direction);
@@ -269,6 +293,7 @@ fn main() -> Result<(), String> {
                                 &mut textboxes_model,
                                 &mut checkboxes_model,
                                 &app.timing_info,
+                                &app.cpu_capabilities,
                                 &mut wojak_texture,
                             );
                         },
@@ -335,6 +360,38 @@ fn main() -> Result<(), String> {
                     }
                 }
             }
+
+            // Draw the context menu above all panels.
+
+            let context_menu_result = do_context_menu(
+                &mut ctx,
+                &mut color_buffer,
+                mouse_state,
+                &root_extent,
+                &context_menu_options,
+            );
+
+            if let Some(selected) = context_menu_result.selected {
+                println!("Selected context menu item {:?}.", selected);
+
+                if selected == [0] {
+                    let mut layout_direction = layout_direction_rc.borrow_mut();
+
+                    *layout_direction = match *layout_direction {
+                        UILayoutDirection::LeftToRight => UILayoutDirection::TopToBottom,
+                        UILayoutDirection::TopToBottom => UILayoutDirection::LeftToRight,
+                    }
+                }
+            }
+
+            // Draw the hovered item's tooltip (if any) above everything else.
+
+            do_tooltip(
+                &mut ctx,
+                &mut color_buffer,
+                mouse_state,
+                app.timing_info.uptime_seconds,
+            );
         }
 
         Ok(())
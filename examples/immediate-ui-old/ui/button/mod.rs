@@ -16,6 +16,7 @@ use super::{
     context::{UIContext, UIID},
     get_mouse_result,
     layout::{item::ItemLayoutOptions, UILayoutContext},
+    request_tooltip,
 };
 
 static BORDERED_BUTTON_LABEL_PADDING_VERTICAL: u32 = 4;
@@ -26,6 +27,7 @@ pub struct ButtonOptions {
     pub layout_options: ItemLayoutOptions,
     pub label: String,
     pub with_border: bool,
+    pub tooltip: Option<String>,
 }
 
 #[derive(Default, Debug)]
@@ -104,6 +106,8 @@ pub fn do_button(
         item_height,
     );
 
+    request_tooltip(ctx, &id, &options.tooltip);
+
     let result = DoButtonResult {
         is_down,
         was_released,
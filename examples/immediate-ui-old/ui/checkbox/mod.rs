@@ -16,6 +16,7 @@ use super::{
     context::{UIContext, UIID},
     get_mouse_result,
     layout::{item::ItemLayoutOptions, UILayoutContext},
+    request_tooltip,
 };
 
 static CHECKBOX_LABEL_PADDING: u32 = 8;
@@ -24,6 +25,7 @@ static CHECKBOX_LABEL_PADDING: u32 = 8;
 pub struct CheckboxOptions {
     pub layout_options: ItemLayoutOptions,
     pub label: String,
+    pub tooltip: Option<String>,
 }
 
 #[derive(Default, Debug)]
@@ -98,6 +100,8 @@ pub fn do_checkbox(
         item_height,
     );
 
+    request_tooltip(ctx, &id, &options.tooltip);
+
     // Updates the state of our checkbox model, if needed.
 
     let mut is_checked = match &model_entry {
@@ -1,17 +1,23 @@
 use std::{
     cell::RefCell,
+    collections::HashMap,
     fmt::{Display, Formatter},
 };
 
 use cairo::{
     font::{cache::FontCache, FontInfo},
     graphics::text::cache::TextCache,
+    ui::{
+        context_menu::ContextMenuState,
+        text_edit::{Clipboard, InMemoryClipboard, TextEditState},
+        tooltip::TooltipState,
+    },
 };
 
 use super::theme::{UITheme, DEFAULT_UI_THEME};
 
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct UIID {
     pub item: u32,
 }
@@ -22,6 +28,14 @@ impl Display for UIID {
     }
 }
 
+// An in-progress typed value for a number slider.
+#[derive(Debug, Clone)]
+pub struct SliderEntry {
+    pub id: UIID,
+    pub text: String,
+    pub edit_state: TextEditState,
+}
+
 #[derive(Debug)]
 pub struct UIContext<'a> {
     pub font_cache: &'a mut RefCell<FontCache<'a>>,
@@ -32,6 +46,14 @@ pub struct UIContext<'a> {
     is_focus_target_open: bool,
     theme: Option<&'a UITheme>,
     next_id: u32,
+    text_edit_states: HashMap<UIID, TextEditState>,
+    scroll_offsets: HashMap<UIID, usize>,
+    pub clipboard: Box<dyn Clipboard>,
+    pub slider_entry: Option<SliderEntry>,
+    pub slider_did_drag: bool,
+    pub slider_drag_value: Option<f32>,
+    pub context_menu: Option<ContextMenuState>,
+    pub tooltip: TooltipState<UIID>,
 }
 
 impl<'a> UIContext<'a> {
@@ -49,6 +71,14 @@ impl<'a> UIContext<'a> {
             is_focus_target_open: false,
             theme: Some(&DEFAULT_UI_THEME),
             next_id: 0,
+            text_edit_states: Default::default(),
+            scroll_offsets: Default::default(),
+            clipboard: Box::<InMemoryClipboard>::default(),
+            slider_entry: None,
+            slider_did_drag: false,
+            slider_drag_value: None,
+            context_menu: None,
+            tooltip: Default::default(),
         }
     }
 
@@ -102,4 +132,20 @@ impl<'a> UIContext<'a> {
     pub fn set_focus_target_open(&mut self, is_open: bool) {
         self.is_focus_target_open = is_open;
     }
+
+    pub fn get_text_edit_state(&self, id: &UIID) -> Option<TextEditState> {
+        self.text_edit_states.get(id).cloned()
+    }
+
+    pub fn set_text_edit_state(&mut self, id: &UIID, state: TextEditState) {
+        self.text_edit_states.insert(*id, state);
+    }
+
+    pub fn get_scroll_offset(&self, id: &UIID) -> usize {
+        self.scroll_offsets.get(id).cloned().unwrap_or(0)
+    }
+
+    pub fn set_scroll_offset(&mut self, id: &UIID, offset: usize) {
+        self.scroll_offsets.insert(*id, offset);
+    }
 }
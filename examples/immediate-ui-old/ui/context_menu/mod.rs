@@ -0,0 +1,194 @@
+use std::cell::RefMut;
+
+use cairo::{
+    buffer::Buffer2D,
+    device::mouse::MouseState,
+    graphics::{
+        text::{
+            cache::{cache_text, TextCacheKey},
+            TextOperation,
+        },
+        Graphics,
+    },
+    ui::context_menu::{
+        update_context_menu, ContextMenuItem, ContextMenuMetrics, ContextMenuState,
+    },
+};
+
+use super::{context::UIContext, layout::UILayoutExtent};
+
+static CONTEXT_MENU_ITEM_HORIZONTAL_PADDING: u32 = 8;
+static CONTEXT_MENU_SUBMENU_INDICATOR: &str = ">";
+
+#[derive(Default, Debug)]
+pub struct ContextMenuOptions {
+    pub items: Vec<ContextMenuItem>,
+    pub metrics: ContextMenuMetrics,
+}
+
+#[derive(Default, Debug)]
+pub struct DoContextMenuResult {
+    pub selected: Option<Vec<usize>>,
+}
+
+// Opens a context menu when the user right-clicks inside of `trigger_extent`.
+// Should be called after other UI items are drawn, so the menu renders on top.
+pub fn do_context_menu(
+    ctx: &mut RefMut<'_, UIContext>,
+    parent_buffer: &mut Buffer2D,
+    mouse_state: &MouseState,
+    trigger_extent: &UILayoutExtent,
+    options: &ContextMenuOptions,
+) -> DoContextMenuResult {
+    let (mouse_x, mouse_y) = mouse_state.position;
+
+    let is_trigger_hovered = mouse_x >= trigger_extent.left as i32
+        && mouse_x <= trigger_extent.right as i32
+        && mouse_y >= trigger_extent.top as i32
+        && mouse_y <= trigger_extent.bottom as i32;
+
+    let result = update_context_menu(
+        &mut ctx.context_menu,
+        &options.items,
+        &options.metrics,
+        mouse_state,
+        is_trigger_hovered,
+    );
+
+    if let Some(state) = ctx.context_menu.clone() {
+        draw_context_menu(ctx, &state, mouse_state, options, parent_buffer);
+    }
+
+    DoContextMenuResult {
+        selected: result.selected,
+    }
+}
+
+fn draw_context_menu(
+    ctx: &mut RefMut<'_, UIContext>,
+    state: &ContextMenuState,
+    mouse_state: &MouseState,
+    options: &ContextMenuOptions,
+    parent_buffer: &mut Buffer2D,
+) {
+    let metrics = &options.metrics;
+
+    let theme = ctx.get_theme();
+
+    for panel in state.get_panels(&options.items, metrics) {
+        let (panel_x, panel_y) = (
+            panel.position.0.max(0) as u32,
+            panel.position.1.max(0) as u32,
+        );
+
+        let panel_height: u32 = panel
+            .items
+            .iter()
+            .map(|item| metrics.get_height(item))
+            .sum();
+
+        Graphics::rectangle(
+            parent_buffer,
+            panel_x,
+            panel_y,
+            metrics.item_width,
+            panel_height,
+            Some(theme.dropdown_background.to_u32()),
+            Some(theme.panel_border.to_u32()),
+        );
+
+        let hovered_index = panel.get_item_at(mouse_state.position, metrics);
+
+        for (index, item) in panel.items.iter().enumerate() {
+            let item_y = panel.get_item_top(index, metrics).max(0) as u32;
+
+            let label = match item {
+                ContextMenuItem::Separator => {
+                    // Draw a separator line through the middle of its row.
+
+                    let separator_y = (item_y + metrics.separator_height / 2) as i32;
+
+                    Graphics::line(
+                        parent_buffer,
+                        (panel_x + CONTEXT_MENU_ITEM_HORIZONTAL_PADDING) as i32,
+                        separator_y,
+                        (panel_x + metrics.item_width - CONTEXT_MENU_ITEM_HORIZONTAL_PADDING)
+                            as i32,
+                        separator_y,
+                        theme.separator.to_u32(),
+                    );
+
+                    continue;
+                }
+                ContextMenuItem::Action(label) | ContextMenuItem::Submenu(label, _) => label,
+            };
+
+            // Highlight the hovered item, or the item whose submenu is open.
+
+            if hovered_index == Some(index) || panel.open_index == Some(index) {
+                Graphics::rectangle(
+                    parent_buffer,
+                    panel_x + 1,
+                    item_y + 1,
+                    metrics.item_width - 2,
+                    metrics.item_height - 2,
+                    Some(theme.input_background.to_u32()),
+                    None,
+                );
+            }
+
+            draw_context_menu_text(
+                ctx,
+                label,
+                panel_x + CONTEXT_MENU_ITEM_HORIZONTAL_PADDING,
+                item_y,
+                parent_buffer,
+            );
+
+            if let ContextMenuItem::Submenu(_, _) = item {
+                draw_context_menu_text(
+                    ctx,
+                    CONTEXT_MENU_SUBMENU_INDICATOR,
+                    panel_x + metrics.item_width - CONTEXT_MENU_ITEM_HORIZONTAL_PADDING * 2,
+                    item_y,
+                    parent_buffer,
+                );
+            }
+        }
+    }
+}
+
+fn draw_context_menu_text(
+    ctx: &UIContext,
+    text: &str,
+    x: u32,
+    y: u32,
+    parent_buffer: &mut Buffer2D,
+) {
+    {
+        let mut font_cache = ctx.font_cache.borrow_mut();
+        let mut text_cache = ctx.text_cache.borrow_mut();
+
+        cache_text(&mut font_cache, &mut text_cache, &ctx.font_info, text);
+    }
+
+    let text_cache_key = TextCacheKey {
+        font_info: ctx.font_info.clone(),
+        text: text.to_string(),
+    };
+
+    let text = &text_cache_key.text;
+
+    let text_cache = ctx.text_cache.borrow();
+
+    let texture = text_cache.get(&text_cache_key).unwrap();
+
+    let op = TextOperation {
+        text,
+        x,
+        y: y + 1,
+        color: ctx.get_theme().text,
+    };
+
+    Graphics::blit_text_from_mask(texture, &op, parent_buffer, None)
+}
@@ -18,6 +18,7 @@ use super::{
     context::{UIContext, UIID},
     get_mouse_result,
     layout::{item::ItemLayoutOptions, UILayoutContext},
+    request_tooltip,
 };
 
 static DROPDOWN_WIDTH: u32 = 200;
@@ -30,6 +31,7 @@ pub struct DropdownOptions {
     pub layout_options: ItemLayoutOptions,
     pub label: String,
     pub items: Vec<String>,
+    pub tooltip: Option<String>,
 }
 
 #[derive(Default, Debug)]
@@ -115,6 +117,8 @@ pub fn do_dropdown(
         item_height,
     );
 
+    request_tooltip(ctx, &id, &options.tooltip);
+
     if was_released && ctx.is_focused(&id) {
         // Toggle the open vs. closed state of our menu.
 
@@ -12,15 +12,19 @@ use self::{
 pub mod button;
 pub mod checkbox;
 pub mod context;
+pub mod context_menu;
 pub mod dropdown;
 pub mod image;
 pub mod layout;
 pub mod panel;
+pub mod plot;
 pub mod separator;
 pub mod slider;
 pub mod text;
+pub mod textarea;
 pub mod textbox;
 pub mod theme;
+pub mod tooltip;
 
 #[allow(clippy::too_many_arguments)]
 pub fn get_mouse_result(
@@ -119,3 +123,12 @@ pub fn get_mouse_result(
 
     (is_down, was_released)
 }
+
+// Registers this item's tooltip (if any) while the item is hovered.
+pub fn request_tooltip(ctx: &mut RefMut<'_, UIContext>, id: &UIID, tooltip: &Option<String>) {
+    if let Some(text) = tooltip {
+        if ctx.is_hovered(id) {
+            ctx.tooltip.request(*id, text);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::cell::RefMut;
+
+use cairo::{
+    buffer::Buffer2D,
+    graphics::{
+        text::{
+            cache::{cache_text, TextCacheKey},
+            TextOperation,
+        },
+        Graphics,
+    },
+    ui::{
+        extent::ScreenExtent,
+        plot::{draw_line_plot, LinePlotOptions as PlotOptions},
+    },
+};
+
+use super::{
+    context::UIContext,
+    layout::{item::ItemLayoutOptions, UILayoutContext},
+};
+
+static LINE_PLOT_LABEL_PADDING: u32 = 8;
+
+#[derive(Debug)]
+pub struct LinePlotOptions {
+    pub layout_options: ItemLayoutOptions,
+    pub label: String,
+    pub width: u32,
+    pub height: u32,
+    pub plot: PlotOptions,
+}
+
+impl Default for LinePlotOptions {
+    fn default() -> Self {
+        Self {
+            layout_options: Default::default(),
+            label: Default::default(),
+            width: 200,
+            height: 60,
+            plot: Default::default(),
+        }
+    }
+}
+
+pub fn do_line_plot(
+    ctx: &mut RefMut<'_, UIContext>,
+    layout: &mut UILayoutContext,
+    parent_buffer: &mut Buffer2D,
+    values: &[f32],
+    options: &LinePlotOptions,
+) {
+    {
+        let mut font_cache = ctx.font_cache.borrow_mut();
+        let mut text_cache = ctx.text_cache.borrow_mut();
+
+        cache_text(
+            &mut font_cache,
+            &mut text_cache,
+            &ctx.font_info,
+            &options.label,
+        );
+    }
+
+    let text_cache_key = TextCacheKey {
+        font_info: ctx.font_info.clone(),
+        text: options.label.clone(),
+    };
+
+    let label_texture_width = ctx
+        .text_cache
+        .borrow()
+        .get(&text_cache_key)
+        .unwrap()
+        .0
+        .width;
+
+    let (layout_offset_x, layout_offset_y) = options
+        .layout_options
+        .get_layout_offset(layout, options.width);
+
+    let item_width = options.width + LINE_PLOT_LABEL_PADDING + label_texture_width;
+    let item_height = options.height;
+
+    // Render a line plot.
+
+    layout.prepare_cursor(item_width, item_height);
+
+    draw_line_plot_item(
+        ctx,
+        layout,
+        layout_offset_x,
+        layout_offset_y,
+        &text_cache_key,
+        values,
+        options,
+        parent_buffer,
+    );
+
+    layout.advance_cursor(item_width, item_height);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_line_plot_item(
+    ctx: &mut RefMut<'_, UIContext>,
+    layout: &UILayoutContext,
+    layout_offset_x: u32,
+    layout_offset_y: u32,
+    text_cache_key: &TextCacheKey,
+    values: &[f32],
+    options: &LinePlotOptions,
+    parent_buffer: &mut Buffer2D,
+) {
+    let cursor = layout.get_cursor();
+
+    let theme = ctx.get_theme();
+
+    let (plot_x, plot_y) = (cursor.x + layout_offset_x, cursor.y + layout_offset_y);
+
+    // Draw the plot background.
+
+    Graphics::rectangle(
+        parent_buffer,
+        plot_x,
+        plot_y,
+        options.width,
+        options.height,
+        Some(theme.dropdown_background.to_u32()),
+        None,
+    );
+
+    // Draw the grid, axes and data.
+
+    let extent = ScreenExtent::new((plot_x, plot_y), (options.width - 1, options.height - 1));
+
+    draw_line_plot(parent_buffer, &extent, values, &options.plot);
+
+    // Draw the plot label.
+
+    let text_cache = ctx.text_cache.borrow();
+
+    let label_texture = text_cache.get(text_cache_key).unwrap();
+
+    let op = TextOperation {
+        text: &text_cache_key.text,
+        x: plot_x + options.width - 1 + LINE_PLOT_LABEL_PADDING,
+        y: plot_y,
+        color: theme.text,
+    };
+
+    Graphics::blit_text_from_mask(label_texture, &op, parent_buffer, None)
+}
@@ -1,10 +1,10 @@
 use std::{cell::RefMut, collections::hash_map::Entry};
 
-use sdl2::mouse::MouseButton;
+use sdl2::{keyboard::Keycode, mouse::MouseButton};
 
 use cairo::{
     buffer::Buffer2D,
-    device::mouse::MouseState,
+    device::{keyboard::KeyboardState, mouse::MouseState},
     graphics::{
         text::{
             cache::{cache_text, TextCacheKey},
@@ -12,12 +12,20 @@ use cairo::{
         },
         Graphics,
     },
+    ui::{
+        fastpath::slider::{
+            get_committed_value, get_dragged_value, parse_slider_entry, SliderDragMode,
+            SliderOptions,
+        },
+        text_edit::TextEditState,
+    },
 };
 
 use super::{
-    context::{UIContext, UIID},
+    context::{SliderEntry, UIContext, UIID},
     get_mouse_result,
     layout::{item::ItemLayoutOptions, UILayoutContext},
+    request_tooltip,
 };
 
 static NUMBER_SLIDER_WIDTH: u32 = 200;
@@ -31,6 +39,8 @@ pub struct NumberSliderOptions {
     pub label: String,
     pub min: Option<f32>,
     pub max: Option<f32>,
+    pub step: Option<f32>,
+    pub tooltip: Option<String>,
 }
 
 #[derive(Default, Debug)]
@@ -42,6 +52,7 @@ pub fn do_slider(
     ctx: &mut RefMut<'_, UIContext>,
     layout: &mut UILayoutContext,
     parent_buffer: &mut Buffer2D,
+    keyboard_state: &KeyboardState,
     mouse_state: &MouseState,
     options: &NumberSliderOptions,
     mut model_entry: Entry<'_, String, String>,
@@ -88,7 +99,7 @@ pub fn do_slider(
     let item_width = NUMBER_SLIDER_WIDTH + NUMBER_SLIDER_LABEL_PADDING + label_texture_width;
     let item_height = label_texture_height;
 
-    let (_is_down, _was_released) = get_mouse_result(
+    let (_is_down, was_released) = get_mouse_result(
         ctx,
         &id,
         layout,
@@ -99,104 +110,132 @@ pub fn do_slider(
         item_height,
     );
 
+    request_tooltip(ctx, &id, &options.tooltip);
+
     // Updates the state of our slider model, if needed.
 
+    let slider_options = SliderOptions {
+        min: options.min.unwrap_or(f32::MIN),
+        max: options.max.unwrap_or(f32::MAX),
+        step: options.step,
+        ..Default::default()
+    };
+
     let mut did_edit = false;
 
-    if ctx.is_focused(&id) {
-        match mouse_state.buttons_down.get(&MouseButton::Left) {
-            Some(_) => {
-                match &mut model_entry {
-                    Entry::Occupied(o) => {
-                        let x_motion = mouse_state.relative_motion.0;
+    let is_entering = matches!(&ctx.slider_entry, Some(entry) if entry.id == id);
 
-                        let delta = x_motion as f32 / NUMBER_SLIDER_WIDTH as f32;
+    if is_entering {
+        // Route keystrokes to the typed entry; Enter (or clicking away)
+        // commits it, and Escape cancels it.
 
-                        let parse_result = o.get().parse::<f32>();
+        let mut entry = ctx.slider_entry.take().unwrap();
 
-                        let min = match options.min {
-                            Some(min) => min,
-                            None => f32::MIN,
-                        };
+        let mut should_commit = !ctx.is_focused(&id);
+        let mut should_cancel = false;
 
-                        let max = match options.max {
-                            Some(max) => max,
-                            None => f32::MAX,
-                        };
+        for keycode in &keyboard_state.newly_pressed_keycodes {
+            match *keycode {
+                Keycode::RETURN | Keycode::KP_ENTER => should_commit = true,
+                Keycode::ESCAPE => should_cancel = true,
+                _ => (),
+            }
+        }
 
-                        let scaling_factor = max - min;
+        if !should_commit && !should_cancel {
+            entry.edit_state.handle_keyboard(
+                &mut entry.text,
+                keyboard_state,
+                ctx.clipboard.as_mut(),
+            );
+        }
 
-                        if let Ok(value) = parse_result {
-                            let adjusted = (value + delta * scaling_factor).clamp(min, max);
+        if should_cancel {
+            // Discard the entry.
+        } else if should_commit {
+            if let (Entry::Occupied(o), Some(value)) = (
+                &mut model_entry,
+                parse_slider_entry(&entry.text, &slider_options),
+            ) {
+                let value_str = value.to_string();
 
-                            let adjusted_str = adjusted.to_string();
+                if *o.get() != value_str {
+                    did_edit = true;
 
-                            if *o.get() != adjusted_str {
-                                did_edit = true;
+                    *o.get_mut() = value_str;
+                }
+            }
+        } else {
+            ctx.slider_entry.replace(entry);
+        }
+    } else if ctx.is_focused(&id) {
+        if mouse_state.buttons_down.contains(&MouseButton::Left) {
+            let x_motion = mouse_state.relative_motion.0;
+
+            if x_motion != 0 {
+                ctx.slider_did_drag = true;
+
+                if let Entry::Occupied(o) = &mut model_entry {
+                    if let Ok(value) = o.get().parse::<f32>() {
+                        // Shift-drag for fine adjustment; ctrl-drag to snap.
+
+                        let mode = SliderDragMode::from_modifiers(keyboard_state.modifiers);
+
+                        let drag_alpha = x_motion as f32 / NUMBER_SLIDER_WIDTH as f32;
+
+                        let drag_value = get_dragged_value(
+                            ctx.slider_drag_value.unwrap_or(value),
+                            drag_alpha,
+                            &slider_options,
+                            mode,
+                        );
 
-                                *o.get_mut() = adjusted.to_string();
-                            }
+                        ctx.slider_drag_value.replace(drag_value);
+
+                        let adjusted = get_committed_value(drag_value, &slider_options, mode);
+
+                        let adjusted_str = adjusted.to_string();
+
+                        if *o.get() != adjusted_str {
+                            did_edit = true;
+
+                            *o.get_mut() = adjusted_str;
                         }
                     }
-                    Entry::Vacant(_v) => {
-                        // Ignore this mouse-drag.
-                    }
                 }
             }
-            None => {
-                // Do nothing
+        }
+
+        if was_released {
+            // A click without dragging begins typed entry.
+
+            if !ctx.slider_did_drag {
+                if let Entry::Occupied(o) = &model_entry {
+                    let text = o.get().clone();
+
+                    let mut edit_state = TextEditState::at_end(&text);
+
+                    edit_state.select_all(&text);
+
+                    ctx.slider_entry.replace(SliderEntry {
+                        id,
+                        text,
+                        edit_state,
+                    });
+                }
             }
+
+            ctx.slider_did_drag = false;
+
+            ctx.slider_drag_value.take();
         }
     }
 
-    // match ctx.get_focus_target() {
-    //     Some(target_id) => {
-    //         if target_id == id {
-    //             for code in &keyboard_state.keys_pressed {
-    //                 match code {
-    //                     (Keycode::Backspace | Keycode::Delete, _) => {
-    //                         // Remove one character from the model value, if possible.
-
-    //                         match &mut model_entry {
-    //                             Entry::Occupied(o) => {
-    //                                 (*o.get_mut()).pop();
-
-    //                                 did_edit = true;
-    //                             }
-    //                             Entry::Vacant(v) => {
-    //                                 // Ignore this keypress.
-    //                             }
-    //                         }
-    //                     }
-    //                     _ => {
-    //                         match get_alpha_numeric(code) {
-    //                             Some(char) => {
-    //                                 // Add this character to the model value (string).
-
-    //                                 match &mut model_entry {
-    //                                     Entry::Occupied(o) => {
-    //                                         *o.get_mut() += char;
-
-    //                                         did_edit = true;
-    //                                     }
-    //                                     Entry::Vacant(_v) => {
-    //                                         // No model value exists at this entry.
-
-    //                                         // Ignore this keypress.
-    //                                     }
-    //                                 }
-    //                             }
-    //                             None => {
-    //                                 // Ignore this keypress.
-    //                             }
-    //                         }
-    //                     }
-    //                 }
-    //             }
-    //         }
-    //     }
-    //     None => (),
-    // }
+    let entry_text = ctx
+        .slider_entry
+        .as_ref()
+        .filter(|entry| entry.id == id)
+        .map(|entry| entry.text.clone());
 
     let result = DoNumberSliderResult { did_edit };
 
@@ -213,6 +252,7 @@ pub fn do_slider(
         &text_cache_key,
         options,
         &mut model_entry,
+        entry_text.as_deref(),
         parent_buffer,
     );
 
@@ -231,6 +271,7 @@ fn draw_slider(
     text_cache_key: &TextCacheKey,
     options: &NumberSliderOptions,
     model: &mut Entry<'_, String, String>,
+    entry_text: Option<&str>,
     parent_buffer: &mut Buffer2D,
 ) {
     let cursor = layout.get_cursor();
@@ -305,11 +346,13 @@ fn draw_slider(
             let text = o.get();
 
             if !text.is_empty() {
-                // Draw the slider value text (formatted).
-
-                let text_parsed = text.parse::<f32>().unwrap();
+                // Draw the slider value text (formatted), or the value being
+                // typed.
 
-                let text_formatted = format!("{:.*}", 2, text_parsed);
+                let text_formatted = match entry_text {
+                    Some(entry_text) => format!("{}_", entry_text),
+                    None => format!("{:.*}", 2, text.parse::<f32>().unwrap()),
+                };
 
                 let mut font_cache = ctx.font_cache.borrow_mut();
 
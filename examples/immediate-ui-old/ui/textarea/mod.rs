@@ -0,0 +1,359 @@
+use std::{cell::RefMut, collections::hash_map::Entry, f32::consts::TAU};
+
+use sdl2::mouse::MouseWheelDirection;
+
+use cairo::{
+    buffer::Buffer2D,
+    device::{keyboard::KeyboardState, mouse::MouseState},
+    graphics::{
+        text::{
+            cache::{cache_text, TextCacheKey},
+            TextOperation,
+        },
+        Graphics,
+    },
+    ui::text_edit::{get_line_index, wrap_lines, TextEditState, TextLine},
+};
+
+use super::{
+    context::{UIContext, UIID},
+    get_mouse_result,
+    layout::{item::ItemLayoutOptions, UILayoutContext},
+    request_tooltip,
+};
+
+static TEXTAREA_WIDTH: u32 = 200;
+static TEXTAREA_LABEL_PADDING: u32 = 8;
+static TEXTAREA_TEXT_PADDING: u32 = 4;
+static TEXTAREA_CURSOR_PADDING: u32 = 2;
+
+#[derive(Debug)]
+pub struct TextareaOptions {
+    pub layout_options: ItemLayoutOptions,
+    pub label: String,
+    pub rows: usize,
+    pub tooltip: Option<String>,
+}
+
+impl Default for TextareaOptions {
+    fn default() -> Self {
+        Self {
+            layout_options: Default::default(),
+            label: Default::default(),
+            rows: 4,
+            tooltip: None,
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct DoTextareaResult {
+    pub did_edit: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn do_textarea(
+    ctx: &mut RefMut<'_, UIContext>,
+    layout: &mut UILayoutContext,
+    parent_buffer: &mut Buffer2D,
+    uptime_seconds: f32,
+    keyboard_state: &KeyboardState,
+    mouse_state: &MouseState,
+    options: &TextareaOptions,
+    mut model_entry: Entry<'_, String, String>,
+) -> DoTextareaResult {
+    let id = UIID {
+        item: ctx.next_id(),
+    };
+
+    {
+        let mut font_cache = ctx.font_cache.borrow_mut();
+        let mut text_cache = ctx.text_cache.borrow_mut();
+
+        cache_text(
+            &mut font_cache,
+            &mut text_cache,
+            &ctx.font_info,
+            &options.label,
+        );
+    }
+
+    let label_texture_width: u32;
+    let line_height: u32;
+
+    let text_cache_key = TextCacheKey {
+        font_info: ctx.font_info.clone(),
+        text: options.label.clone(),
+    };
+
+    {
+        let text_cache = ctx.text_cache.borrow();
+
+        let label_texture = text_cache.get(&text_cache_key).unwrap();
+
+        label_texture_width = label_texture.0.width;
+        line_height = label_texture.0.height;
+    }
+
+    // Check whether a mouse event occurred inside this textarea.
+
+    let (layout_offset_x, layout_offset_y) = options
+        .layout_options
+        .get_layout_offset(layout, TEXTAREA_WIDTH);
+
+    let rows = options.rows.max(1);
+
+    let item_width = TEXTAREA_WIDTH + TEXTAREA_LABEL_PADDING + label_texture_width;
+    let item_height = line_height * rows as u32;
+
+    let (_is_down, _was_released) = get_mouse_result(
+        ctx,
+        &id,
+        layout,
+        mouse_state,
+        layout_offset_x,
+        layout_offset_y,
+        item_width,
+        item_height,
+    );
+
+    request_tooltip(ctx, &id, &options.tooltip);
+
+    // Updates the state of our textarea model, if needed.
+
+    let mut did_edit = false;
+
+    let mut edit_state = None;
+
+    if ctx.is_focused(&id) {
+        if let Entry::Occupied(o) = &mut model_entry {
+            let text = o.get_mut();
+
+            let mut state = ctx
+                .get_text_edit_state(&id)
+                .unwrap_or_else(|| TextEditState::at_end(text));
+
+            did_edit =
+                state.handle_keyboard_multiline(text, keyboard_state, ctx.clipboard.as_mut());
+
+            ctx.set_text_edit_state(&id, state);
+
+            edit_state.replace(state);
+        }
+    }
+
+    // Wrap the model value to the textarea's inner width.
+
+    let lines = match &model_entry {
+        Entry::Occupied(o) => {
+            let mut font_cache = ctx.font_cache.borrow_mut();
+
+            let font = font_cache.load(&ctx.font_info).unwrap();
+
+            wrap_lines(o.get(), TEXTAREA_WIDTH - TEXTAREA_TEXT_PADDING * 2, |s| {
+                font.size_of(s).map(|(width, _)| width).unwrap_or(0)
+            })
+        }
+        Entry::Vacant(_v) => vec![],
+    };
+
+    // Scroll with the mouse wheel while hovered, then keep the caret visible.
+
+    let max_scroll_offset = lines.len().saturating_sub(rows);
+
+    let mut scroll_offset = ctx.get_scroll_offset(&id);
+
+    if ctx.is_hovered(&id) {
+        if let Some(event) = &mouse_state.wheel_event {
+            scroll_offset = match event.direction {
+                MouseWheelDirection::Flipped => {
+                    (scroll_offset as i32 + event.delta).max(0) as usize
+                }
+                _ => (scroll_offset as i32 - event.delta).max(0) as usize,
+            };
+        }
+    }
+
+    if let Some(state) = &edit_state {
+        if did_edit || !keyboard_state.newly_pressed_keycodes.is_empty() {
+            let caret_line = get_line_index(&lines, state.cursor);
+
+            if caret_line < scroll_offset {
+                scroll_offset = caret_line;
+            } else if caret_line >= scroll_offset + rows {
+                scroll_offset = caret_line + 1 - rows;
+            }
+        }
+    }
+
+    scroll_offset = scroll_offset.min(max_scroll_offset);
+
+    ctx.set_scroll_offset(&id, scroll_offset);
+
+    let result = DoTextareaResult { did_edit };
+
+    // Render a textarea.
+
+    layout.prepare_cursor(item_width, item_height);
+
+    draw_textarea(
+        ctx,
+        &id,
+        layout,
+        layout_offset_x,
+        layout_offset_y,
+        &text_cache_key,
+        &model_entry,
+        &lines,
+        scroll_offset,
+        rows,
+        uptime_seconds,
+        parent_buffer,
+    );
+
+    layout.advance_cursor(item_width, item_height);
+
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_textarea(
+    ctx: &mut RefMut<'_, UIContext>,
+    id: &UIID,
+    layout: &UILayoutContext,
+    layout_offset_x: u32,
+    layout_offset_y: u32,
+    text_cache_key: &TextCacheKey,
+    model: &Entry<'_, String, String>,
+    lines: &[TextLine],
+    scroll_offset: usize,
+    rows: usize,
+    uptime_seconds: f32,
+    parent_buffer: &mut Buffer2D,
+) {
+    let cursor = layout.get_cursor();
+
+    let text_cache = ctx.text_cache.borrow();
+
+    let label_texture = text_cache.get(text_cache_key).unwrap();
+
+    let line_height = label_texture.0.height;
+
+    let textarea_height = line_height * rows as u32;
+
+    let theme = ctx.get_theme();
+
+    let label_color = if ctx.is_focused(id) {
+        theme.text_focus
+    } else if ctx.is_hovered(id) {
+        theme.text_hover
+    } else {
+        theme.text
+    };
+
+    // Draw the textarea background.
+
+    let (textarea_x, textarea_y) = (cursor.x + layout_offset_x, cursor.y + layout_offset_y);
+
+    Graphics::rectangle(
+        parent_buffer,
+        textarea_x,
+        textarea_y,
+        TEXTAREA_WIDTH,
+        textarea_height,
+        Some(theme.input_background.to_u32()),
+        None,
+    );
+
+    // Draw the visible lines of the model value (text).
+
+    if let Entry::Occupied(o) = model {
+        let text = o.get();
+
+        let chars: Vec<char> = text.chars().collect();
+
+        let mut font_cache = ctx.font_cache.borrow_mut();
+
+        let font = font_cache.load(&ctx.font_info).unwrap();
+
+        let max_width = TEXTAREA_WIDTH - TEXTAREA_TEXT_PADDING;
+
+        for (row, line) in lines.iter().skip(scroll_offset).take(rows).enumerate() {
+            let line_text: String = chars[line.start..line.end].iter().collect();
+
+            if line_text.is_empty() {
+                continue;
+            }
+
+            let (_width, _height, line_texture) =
+                Graphics::make_text_mask(font.as_ref(), &line_text).unwrap();
+
+            Graphics::blit_text_from_mask(
+                &line_texture,
+                &TextOperation {
+                    text: &line_text,
+                    x: textarea_x + TEXTAREA_TEXT_PADDING,
+                    y: textarea_y + 1 + row as u32 * line_height,
+                    color: theme.input_text,
+                },
+                parent_buffer,
+                Some(max_width),
+            );
+        }
+
+        // Draw the text cursor.
+
+        let with_cursor = (uptime_seconds * TAU).sin() > 0.0;
+
+        if ctx.is_focused(id) && with_cursor {
+            let edit_state = ctx
+                .get_text_edit_state(id)
+                .unwrap_or_else(|| TextEditState::at_end(text));
+
+            let caret_line = get_line_index(lines, edit_state.cursor);
+
+            if caret_line >= scroll_offset && caret_line < scroll_offset + rows {
+                let line_start = lines.get(caret_line).map(|l| l.start).unwrap_or(0);
+
+                let prefix: String = chars[line_start..edit_state.cursor.min(chars.len())]
+                    .iter()
+                    .collect();
+
+                let prefix_width = if prefix.is_empty() {
+                    0
+                } else {
+                    font.size_of(&prefix).map(|(width, _)| width).unwrap_or(0)
+                };
+
+                let blinking_text_cursor_x = textarea_x
+                    + TEXTAREA_TEXT_PADDING
+                    + prefix_width.min(max_width - TEXTAREA_CURSOR_PADDING)
+                    + TEXTAREA_CURSOR_PADDING;
+
+                let blinking_text_cursor_y =
+                    textarea_y + 2 + (caret_line - scroll_offset) as u32 * line_height;
+
+                Graphics::rectangle(
+                    parent_buffer,
+                    blinking_text_cursor_x,
+                    blinking_text_cursor_y,
+                    2,
+                    line_height - 2 - 2,
+                    None,
+                    Some(theme.input_cursor.to_u32()),
+                );
+            }
+        }
+    }
+
+    // Draw the textarea label.
+
+    let op = TextOperation {
+        text: &text_cache_key.text,
+        x: textarea_x + TEXTAREA_WIDTH - 1 + TEXTAREA_LABEL_PADDING,
+        y: textarea_y,
+        color: label_color,
+    };
+
+    Graphics::blit_text_from_mask(label_texture, &op, parent_buffer, None)
+}
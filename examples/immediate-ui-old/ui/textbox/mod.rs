@@ -1,13 +1,8 @@
 use std::{cell::RefMut, collections::hash_map::Entry, f32::consts::TAU};
 
-use sdl2::keyboard::Keycode;
-
 use cairo::{
     buffer::Buffer2D,
-    device::{
-        keyboard::{keycode::to_ascii_char, KeyboardState},
-        mouse::MouseState,
-    },
+    device::{keyboard::KeyboardState, mouse::MouseState},
     graphics::{
         text::{
             cache::{cache_text, TextCacheKey},
@@ -15,6 +10,7 @@ use cairo::{
         },
         Graphics,
     },
+    ui::text_edit::{get_byte_offset, get_display_text, TextEditState},
 };
 
 use super::{
@@ -24,6 +20,7 @@ use super::{
         item::{ItemLayoutOptions, ItemTextAlignment},
         UILayoutContext,
     },
+    request_tooltip,
 };
 
 static TEXTBOX_WIDTH: u32 = 200;
@@ -36,6 +33,8 @@ pub struct TextboxOptions {
     pub layout_options: ItemLayoutOptions,
     pub label: String,
     pub input_text_alignment: ItemTextAlignment,
+    pub masked: bool,
+    pub tooltip: Option<String>,
 }
 
 #[derive(Default, Debug)]
@@ -107,55 +106,27 @@ pub fn do_textbox(
         item_height,
     );
 
+    request_tooltip(ctx, &id, &options.tooltip);
+
     // Updates the state of our textbox model, if needed.
 
     let mut did_edit = false;
 
-    if let Some(target_id) = ctx.get_focus_target() {
-        if target_id == id {
-            for keycode in &keyboard_state.newly_pressed_keycodes {
-                match *keycode {
-                    Keycode::Backspace | Keycode::Delete => {
-                        // Remove one character from the model value, if possible.
-
-                        match &mut model_entry {
-                            Entry::Occupied(o) => {
-                                (*o.get_mut()).pop();
-
-                                did_edit = true;
-                            }
-                            Entry::Vacant(_v) => {
-                                // Ignore this keypress.
-                            }
-                        }
-                    }
-                    _ => {
-                        match to_ascii_char(keycode, &keyboard_state.newly_pressed_keycodes) {
-                            Some(char) => {
-                                // Add this character to the model value (string).
-
-                                match &mut model_entry {
-                                    Entry::Occupied(o) => {
-                                        let s = o.get_mut();
-
-                                        *s += char.to_string().as_str();
-
-                                        did_edit = true;
-                                    }
-                                    Entry::Vacant(_v) => {
-                                        // No model value exists at this entry.
-
-                                        // Ignore this keypress.
-                                    }
-                                }
-                            }
-                            None => {
-                                // Ignore this keypress.
-                            }
-                        }
-                    }
-                }
-            }
+    if ctx.is_focused(&id) {
+        if let Entry::Occupied(o) = &mut model_entry {
+            let text = o.get_mut();
+
+            let mut edit_state = ctx
+                .get_text_edit_state(&id)
+                .unwrap_or_else(|| TextEditState::at_end(text));
+
+            did_edit = if options.masked {
+                edit_state.handle_keyboard_masked(text, keyboard_state, ctx.clipboard.as_mut())
+            } else {
+                edit_state.handle_keyboard(text, keyboard_state, ctx.clipboard.as_mut())
+            };
+
+            ctx.set_text_edit_state(&id, edit_state);
         }
     }
 
@@ -235,7 +206,11 @@ fn draw_textbox(
 
     match model {
         Entry::Occupied(o) => {
-            let text = o.get();
+            // Masked textboxes draw one bullet per model character.
+
+            let display_text = get_display_text(o.get(), options.masked);
+
+            let text = &display_text;
 
             if !text.is_empty() {
                 // Draw the input value text.
@@ -280,12 +255,28 @@ fn draw_textbox(
                 let with_cursor = (uptime_second * TAU).sin() > 0.0;
 
                 if ctx.is_focused(id) && with_cursor {
+                    // Measure the text preceding the edit cursor.
+
+                    let edit_state = ctx
+                        .get_text_edit_state(id)
+                        .unwrap_or_else(|| TextEditState::at_end(text));
+
+                    let prefix_width = match text
+                        .get(..get_byte_offset(text, edit_state.cursor))
+                        .filter(|prefix| !prefix.is_empty())
+                    {
+                        Some(prefix) => {
+                            let (prefix_width, _, _) =
+                                Graphics::make_text_mask(font.as_ref(), prefix).unwrap();
+
+                            prefix_width
+                        }
+                        None => 0,
+                    };
+
                     let blinking_text_cursor_x = textbox_top_left.0
                         + TEXTBOX_TEXT_PADDING
-                        + model_value_texture
-                            .0
-                            .width
-                            .min(max_width - TEXTBOX_CURSOR_PADDING)
+                        + prefix_width.min(max_width - TEXTBOX_CURSOR_PADDING)
                         + TEXTBOX_CURSOR_PADDING;
 
                     let blinking_text_cursor_y = textbox_top_right.1 + 2;
@@ -0,0 +1,86 @@
+use std::cell::RefMut;
+
+use cairo::{
+    buffer::Buffer2D,
+    device::mouse::MouseState,
+    graphics::{
+        text::{
+            cache::{cache_text, TextCacheKey},
+            TextOperation,
+        },
+        Graphics,
+    },
+};
+
+use super::context::UIContext;
+
+static TOOLTIP_PADDING: u32 = 4;
+static TOOLTIP_CURSOR_OFFSET: u32 = 16;
+
+// Shows the hovered item's tooltip near the mouse cursor, once the hover delay
+// has elapsed. Should be called once per frame, after all other UI items are
+// drawn, so the tooltip renders on top.
+pub fn do_tooltip(
+    ctx: &mut RefMut<'_, UIContext>,
+    parent_buffer: &mut Buffer2D,
+    mouse_state: &MouseState,
+    uptime_seconds: f32,
+) {
+    ctx.tooltip.update(uptime_seconds);
+
+    let text = match ctx.tooltip.get_visible() {
+        Some((_id, text)) => text.to_string(),
+        None => return,
+    };
+
+    {
+        let mut font_cache = ctx.font_cache.borrow_mut();
+        let mut text_cache = ctx.text_cache.borrow_mut();
+
+        cache_text(&mut font_cache, &mut text_cache, &ctx.font_info, &text);
+    }
+
+    let text_cache_key = TextCacheKey {
+        font_info: ctx.font_info.clone(),
+        text,
+    };
+
+    let text_cache = ctx.text_cache.borrow();
+
+    let texture = text_cache.get(&text_cache_key).unwrap();
+
+    let theme = ctx.get_theme();
+
+    let (width, height) = (
+        texture.0.width + TOOLTIP_PADDING * 2,
+        texture.0.height + TOOLTIP_PADDING * 2,
+    );
+
+    // Position the tooltip below and to the right of the cursor, keeping it
+    // inside of the parent buffer.
+
+    let x = (mouse_state.position.0.max(0) as u32 + TOOLTIP_CURSOR_OFFSET)
+        .min(parent_buffer.width.saturating_sub(width));
+
+    let y = (mouse_state.position.1.max(0) as u32 + TOOLTIP_CURSOR_OFFSET)
+        .min(parent_buffer.height.saturating_sub(height));
+
+    Graphics::rectangle(
+        parent_buffer,
+        x,
+        y,
+        width,
+        height,
+        Some(theme.dropdown_background.to_u32()),
+        Some(theme.panel_border.to_u32()),
+    );
+
+    let op = TextOperation {
+        text: &text_cache_key.text,
+        x: x + TOOLTIP_PADDING,
+        y: y + TOOLTIP_PADDING,
+        color: theme.text,
+    };
+
+    Graphics::blit_text_from_mask(texture, &op, parent_buffer, None)
+}
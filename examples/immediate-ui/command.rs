@@ -1,4 +1,4 @@
-use std::{any::TypeId, cell::RefCell, fmt::Display, str::FromStr};
+use std::{any::TypeId, cell::RefCell, str::FromStr};
 
 use uuid::Uuid;
 
@@ -7,37 +7,20 @@ use cairo::{
         resolution::{Resolution, RESOLUTIONS_16X9},
         window::{AppWindowingMode, APP_WINDOWING_MODES},
     },
+    command::{CommandArgument, CommandRegistry},
     mem::linked_list::LinkedList,
     render::{
         culling::{FACE_CULLING_REJECT, FACE_CULLING_WINDING_ORDER},
         options::{tone_mapping::TONE_MAPPING_OPERATORS, RenderPassFlag},
     },
     resource::handle::Handle,
-    scene::camera::{CameraProjectionKind, CAMERA_PROJECTION_KINDS},
+    scene::camera::{CameraProjectionKind, CAMERA_PROJECTION_KINDS, DEFAULT_CAMERA_FIELD_OF_VIEW},
     software_renderer::zbuffer::DEPTH_TEST_METHODS,
     vec::vec3::Vec3,
 };
 
 use crate::{SCENE_CONTEXT, SETTINGS};
 
-pub struct Command<'a> {
-    pub kind: &'a String,
-    pub args: &'a [String],
-    pub is_undo: bool,
-}
-
-impl<'a> Display for Command<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{} {}{}",
-            self.kind,
-            self.args.join(" "),
-            if self.is_undo { " (undo)" } else { "" }
-        )
-    }
-}
-
 pub type PendingCommand = (String, bool);
 
 #[derive(Default, Debug, Clone)]
@@ -67,531 +50,658 @@ fn parse_or_map_err<T: 'static + FromStr>(arg: &String) -> Result<T, String> {
 type ProcessCommandResult =
     Result<(Option<String>, Option<Resolution>, Option<AppWindowingMode>), String>;
 
-fn process_command(command: Command) -> ProcessCommandResult {
-    match command.kind.as_str() {
-        "set" => {
-            let (setting_key, value_str) = (&command.args[0], &command.args[1]);
+static SETTING_KEYS: [&str; 41] = [
+    "windowing_mode",
+    "resolution",
+    "brightness",
+    "gamma",
+    "vsync",
+    "hdr",
+    "render.fragment_shader",
+    "render_options.do_rasterization",
+    "render_options.rasterizer_options.face_culling_strategy.winding_order",
+    "render_options.rasterizer_options.face_culling_strategy.reject",
+    "render_options.do_lighting",
+    "render_options.do_deferred_lighting",
+    "render_options.do_bloom",
+    "render_options.draw_wireframe",
+    "render_options.wireframe_color",
+    "render_options.wireframe_depth_tested",
+    "render_options.draw_normals",
+    "render_options.draw_tangents",
+    "render_options.draw_bitangents",
+    "render_options.draw_normals_scale",
+    "render_options.fog",
+    "render.tone_mapping",
+    "depth_test_method",
+    "shader_options.texture_filtering",
+    "shader_options.albedo_color_maps",
+    "shader_options.ambient_occlusion_maps",
+    "shader_options.roughness_maps",
+    "shader_options.metallic_maps",
+    "shader_options.normal_maps",
+    "shader_options.displacement_maps",
+    "shader_options.parallax_occlusion",
+    "shader_options.parallax_self_shadowing",
+    "shader_options.specular_maps",
+    "shader_options.emissive_maps",
+    "postprocessing.effects.outline",
+    "postprocessing.effects.invert",
+    "postprocessing.effects.grayscale",
+    "postprocessing.effects.sharpen_kernel",
+    "postprocessing.effects.blur_kernel",
+    "postprocessing.effects.edge_detection_kernel",
+    "camera",
+];
+
+// Receives the outcome of a command, for undo and for applying app changes.
+#[derive(Default, Debug, Clone)]
+struct CommandContext {
+    prev_value: Option<String>,
+    new_resolution: Option<Resolution>,
+    new_windowing_mode: Option<AppWindowingMode>,
+}
 
-            let mut prev_value_str: Option<String> = None;
-            let mut new_resolution: Option<Resolution> = None;
-            let mut new_windowing_mode: Option<AppWindowingMode> = None;
+fn make_command_registry() -> CommandRegistry<CommandContext> {
+    let mut registry = CommandRegistry::default();
 
-            SETTINGS.with(|settings_rc| -> Result<(), String> {
-                let mut current_settings = settings_rc.borrow_mut();
+    registry.register(
+        "set",
+        "Changes a setting.",
+        vec![
+            CommandArgument::with_completions("key", &SETTING_KEYS),
+            CommandArgument::new("value"),
+        ],
+        Box::new(|context: &mut CommandContext, args| {
+            let (prev_value, new_resolution, new_windowing_mode) = process_set_command(&args.0)?;
 
-                match setting_key.as_str() {
-                    "windowing_mode" => {
-                        let value = parse_or_map_err::<usize>(value_str)?;
+            context.prev_value = prev_value;
+            context.new_resolution = new_resolution;
+            context.new_windowing_mode = new_windowing_mode;
 
-                        let requested_mode = APP_WINDOWING_MODES[value];
+            Ok(())
+        }),
+    );
 
-                        if requested_mode != current_settings.windowing_mode {
-                            prev_value_str
-                                .replace((current_settings.windowing_mode as usize).to_string());
+    registry
+}
 
-                            current_settings.windowing_mode = requested_mode;
+thread_local! {
+    static COMMAND_REGISTRY: RefCell<CommandRegistry<CommandContext>> =
+        RefCell::new(make_command_registry());
+}
 
-                            new_windowing_mode.replace(current_settings.windowing_mode);
-                        }
+fn process_set_command(args: &[String]) -> ProcessCommandResult {
+    let (setting_key, value_str) = (&args[0], &args[1]);
 
-                        Ok(())
-                    }
-                    "resolution" => {
-                        let value = parse_or_map_err::<usize>(value_str)?;
+    let mut prev_value_str: Option<String> = None;
+    let mut new_resolution: Option<Resolution> = None;
+    let mut new_windowing_mode: Option<AppWindowingMode> = None;
 
-                        let requested_resolution = value;
+    SETTINGS.with(|settings_rc| -> Result<(), String> {
+        let mut current_settings = settings_rc.borrow_mut();
 
-                        if requested_resolution != current_settings.resolution {
-                            prev_value_str.replace(current_settings.resolution.to_string());
+        match setting_key.as_str() {
+            "windowing_mode" => {
+                let value = parse_or_map_err::<usize>(value_str)?;
 
-                            current_settings.resolution = requested_resolution;
+                let requested_mode = APP_WINDOWING_MODES[value];
 
-                            new_resolution.replace(RESOLUTIONS_16X9[requested_resolution]);
-                        }
+                if requested_mode != current_settings.windowing_mode {
+                    prev_value_str.replace((current_settings.windowing_mode as usize).to_string());
 
-                        Ok(())
-                    }
-                    "brightness" => {
-                        prev_value_str.replace(current_settings.brightness.to_string());
+                    current_settings.windowing_mode = requested_mode;
 
-                        current_settings.brightness = parse_or_map_err::<f32>(value_str)?;
+                    new_windowing_mode.replace(current_settings.windowing_mode);
+                }
 
-                        Ok(())
-                    }
-                    "gamma" => {
-                        prev_value_str.replace(current_settings.gamma.to_string());
+                Ok(())
+            }
+            "resolution" => {
+                let value = parse_or_map_err::<usize>(value_str)?;
 
-                        current_settings.gamma = parse_or_map_err::<f32>(value_str)?;
+                let requested_resolution = value;
 
-                        Ok(())
-                    }
-                    "vsync" => {
-                        prev_value_str.replace(current_settings.vsync.to_string());
+                if requested_resolution != current_settings.resolution {
+                    prev_value_str.replace(current_settings.resolution.to_string());
 
-                        current_settings.vsync = parse_or_map_err::<bool>(value_str)?;
+                    current_settings.resolution = requested_resolution;
 
-                        Ok(())
-                    }
-                    "hdr" => {
-                        prev_value_str.replace(current_settings.hdr.to_string());
+                    new_resolution.replace(RESOLUTIONS_16X9[requested_resolution]);
+                }
 
-                        current_settings.hdr = parse_or_map_err::<bool>(value_str)?;
+                Ok(())
+            }
+            "brightness" => {
+                prev_value_str.replace(current_settings.brightness.to_string());
 
-                        Ok(())
-                    }
-                    "render.fragment_shader" => {
-                        prev_value_str.replace(current_settings.fragment_shader.to_string());
+                current_settings.brightness = parse_or_map_err::<f32>(value_str)?;
 
-                        current_settings.fragment_shader = parse_or_map_err::<usize>(value_str)?;
+                Ok(())
+            }
+            "gamma" => {
+                prev_value_str.replace(current_settings.gamma.to_string());
 
-                        Ok(())
-                    }
-                    "render_options.do_rasterization" => {
-                        let was_enabled = current_settings
-                            .render_options
-                            .render_pass_flags
-                            .contains(RenderPassFlag::Rasterization);
-
-                        prev_value_str.replace(was_enabled.to_string());
-
-                        let is_enabled = parse_or_map_err::<bool>(value_str)?;
-
-                        if is_enabled {
-                            current_settings.render_options.render_pass_flags ^=
-                                RenderPassFlag::Rasterization;
-                        } else {
-                            current_settings.render_options.render_pass_flags |=
-                                RenderPassFlag::Rasterization;
-                        }
+                current_settings.gamma = parse_or_map_err::<f32>(value_str)?;
 
-                        Ok(())
-                    }
-                    "render_options.rasterizer_options.face_culling_strategy.winding_order" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .render_options
-                                .rasterizer_options
-                                .face_culling_strategy
-                                .winding_order
-                                .to_string(),
-                        );
-
-                        let new_index = parse_or_map_err::<usize>(value_str)?;
-
-                        current_settings
-                            .render_options
-                            .rasterizer_options
-                            .face_culling_strategy
-                            .winding_order = FACE_CULLING_WINDING_ORDER[new_index];
-
-                        Ok(())
-                    }
-                    "render_options.rasterizer_options.face_culling_strategy.reject" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .render_options
-                                .rasterizer_options
-                                .face_culling_strategy
-                                .reject
-                                .to_string(),
-                        );
-
-                        let new_index = parse_or_map_err::<usize>(value_str)?;
-
-                        current_settings
-                            .render_options
-                            .rasterizer_options
-                            .face_culling_strategy
-                            .reject = FACE_CULLING_REJECT[new_index];
-
-                        Ok(())
-                    }
-                    "render_options.do_lighting" => {
-                        let was_enabled = current_settings
-                            .render_options
-                            .render_pass_flags
-                            .contains(RenderPassFlag::Lighting);
-
-                        prev_value_str.replace(was_enabled.to_string());
-
-                        let is_enabled = parse_or_map_err::<bool>(value_str)?;
-
-                        if is_enabled {
-                            current_settings.render_options.render_pass_flags ^=
-                                RenderPassFlag::Lighting;
-                        } else {
-                            current_settings.render_options.render_pass_flags |=
-                                RenderPassFlag::Lighting;
-                        }
+                Ok(())
+            }
+            "vsync" => {
+                prev_value_str.replace(current_settings.vsync.to_string());
 
-                        Ok(())
-                    }
-                    "render_options.do_deferred_lighting" => {
-                        let was_enabled = current_settings
-                            .render_options
-                            .render_pass_flags
-                            .contains(RenderPassFlag::DeferredLighting);
-
-                        prev_value_str.replace(was_enabled.to_string());
-
-                        let is_enabled = parse_or_map_err::<bool>(value_str)?;
-
-                        if is_enabled {
-                            current_settings.render_options.render_pass_flags ^=
-                                RenderPassFlag::DeferredLighting;
-                        } else {
-                            current_settings.render_options.render_pass_flags |=
-                                RenderPassFlag::DeferredLighting;
-                        }
+                current_settings.vsync = parse_or_map_err::<bool>(value_str)?;
 
-                        Ok(())
-                    }
-                    "render_options.do_bloom" => {
-                        let was_enabled = current_settings
-                            .render_options
-                            .render_pass_flags
-                            .contains(RenderPassFlag::Bloom);
-
-                        prev_value_str.replace(was_enabled.to_string());
-
-                        let is_enabled = parse_or_map_err::<bool>(value_str)?;
-
-                        if is_enabled {
-                            current_settings.render_options.render_pass_flags ^=
-                                RenderPassFlag::Bloom;
-                        } else {
-                            current_settings.render_options.render_pass_flags |=
-                                RenderPassFlag::Bloom;
-                        }
+                Ok(())
+            }
+            "hdr" => {
+                prev_value_str.replace(current_settings.hdr.to_string());
 
-                        Ok(())
-                    }
-                    "render_options.draw_wireframe" => {
-                        prev_value_str
-                            .replace(current_settings.render_options.draw_wireframe.to_string());
+                current_settings.hdr = parse_or_map_err::<bool>(value_str)?;
 
-                        current_settings.render_options.draw_wireframe =
-                            parse_or_map_err::<bool>(value_str)?;
+                Ok(())
+            }
+            "render.fragment_shader" => {
+                prev_value_str.replace(current_settings.fragment_shader.to_string());
 
-                        Ok(())
-                    }
-                    "render_options.wireframe_color" => {
-                        prev_value_str
-                            .replace(current_settings.render_options.wireframe_color.to_string());
+                current_settings.fragment_shader = parse_or_map_err::<usize>(value_str)?;
 
-                        let wireframe_color = parse_or_map_err::<Vec3>(value_str)?;
+                Ok(())
+            }
+            "render_options.do_rasterization" => {
+                let was_enabled = current_settings
+                    .render_options
+                    .render_pass_flags
+                    .contains(RenderPassFlag::Rasterization);
+
+                prev_value_str.replace(was_enabled.to_string());
+
+                let is_enabled = parse_or_map_err::<bool>(value_str)?;
+
+                if is_enabled {
+                    current_settings.render_options.render_pass_flags ^=
+                        RenderPassFlag::Rasterization;
+                } else {
+                    current_settings.render_options.render_pass_flags |=
+                        RenderPassFlag::Rasterization;
+                }
 
-                        current_settings.render_options.wireframe_color = wireframe_color;
+                Ok(())
+            }
+            "render_options.rasterizer_options.face_culling_strategy.winding_order" => {
+                prev_value_str.replace(
+                    current_settings
+                        .render_options
+                        .rasterizer_options
+                        .face_culling_strategy
+                        .winding_order
+                        .to_string(),
+                );
+
+                let new_index = parse_or_map_err::<usize>(value_str)?;
+
+                current_settings
+                    .render_options
+                    .rasterizer_options
+                    .face_culling_strategy
+                    .winding_order = FACE_CULLING_WINDING_ORDER[new_index];
+
+                Ok(())
+            }
+            "render_options.rasterizer_options.face_culling_strategy.reject" => {
+                prev_value_str.replace(
+                    current_settings
+                        .render_options
+                        .rasterizer_options
+                        .face_culling_strategy
+                        .reject
+                        .to_string(),
+                );
+
+                let new_index = parse_or_map_err::<usize>(value_str)?;
+
+                current_settings
+                    .render_options
+                    .rasterizer_options
+                    .face_culling_strategy
+                    .reject = FACE_CULLING_REJECT[new_index];
+
+                Ok(())
+            }
+            "render_options.do_lighting" => {
+                let was_enabled = current_settings
+                    .render_options
+                    .render_pass_flags
+                    .contains(RenderPassFlag::Lighting);
 
-                        Ok(())
-                    }
-                    "render_options.draw_normals" => {
-                        prev_value_str
-                            .replace(current_settings.render_options.draw_normals.to_string());
+                prev_value_str.replace(was_enabled.to_string());
 
-                        current_settings.render_options.draw_normals =
-                            parse_or_map_err::<bool>(value_str)?;
+                let is_enabled = parse_or_map_err::<bool>(value_str)?;
 
-                        Ok(())
-                    }
-                    "render_options.draw_normals_scale" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .render_options
-                                .draw_normals_scale
-                                .to_string(),
-                        );
-
-                        current_settings.render_options.draw_normals_scale =
-                            parse_or_map_err::<f32>(value_str)?;
-
-                        Ok(())
-                    }
-                    "render.tone_mapping" => {
-                        prev_value_str
-                            .replace(current_settings.render_options.tone_mapping.to_string());
+                if is_enabled {
+                    current_settings.render_options.render_pass_flags ^= RenderPassFlag::Lighting;
+                } else {
+                    current_settings.render_options.render_pass_flags |= RenderPassFlag::Lighting;
+                }
 
-                        let new_index = parse_or_map_err::<usize>(value_str)?;
+                Ok(())
+            }
+            "render_options.do_deferred_lighting" => {
+                let was_enabled = current_settings
+                    .render_options
+                    .render_pass_flags
+                    .contains(RenderPassFlag::DeferredLighting);
+
+                prev_value_str.replace(was_enabled.to_string());
+
+                let is_enabled = parse_or_map_err::<bool>(value_str)?;
+
+                if is_enabled {
+                    current_settings.render_options.render_pass_flags ^=
+                        RenderPassFlag::DeferredLighting;
+                } else {
+                    current_settings.render_options.render_pass_flags |=
+                        RenderPassFlag::DeferredLighting;
+                }
 
-                        current_settings.render_options.tone_mapping =
-                            TONE_MAPPING_OPERATORS[new_index];
+                Ok(())
+            }
+            "render_options.do_bloom" => {
+                let was_enabled = current_settings
+                    .render_options
+                    .render_pass_flags
+                    .contains(RenderPassFlag::Bloom);
 
-                        Ok(())
-                    }
-                    "depth_test_method" => {
-                        prev_value_str.replace(current_settings.depth_test_method.to_string());
+                prev_value_str.replace(was_enabled.to_string());
 
-                        let new_index = parse_or_map_err::<usize>(value_str)?;
+                let is_enabled = parse_or_map_err::<bool>(value_str)?;
 
-                        current_settings.depth_test_method = DEPTH_TEST_METHODS[new_index];
+                if is_enabled {
+                    current_settings.render_options.render_pass_flags ^= RenderPassFlag::Bloom;
+                } else {
+                    current_settings.render_options.render_pass_flags |= RenderPassFlag::Bloom;
+                }
 
-                        Ok(())
-                    }
-                    "shader_options.texture_filtering" => {
-                        prev_value_str
-                            .replace(current_settings.shader_options.bilinear_active.to_string());
+                Ok(())
+            }
+            "render_options.draw_wireframe" => {
+                prev_value_str.replace(current_settings.render_options.draw_wireframe.to_string());
 
-                        current_settings.shader_options.bilinear_active = false;
-                        current_settings.shader_options.trilinear_active = false;
+                current_settings.render_options.draw_wireframe =
+                    parse_or_map_err::<bool>(value_str)?;
 
-                        let selected_index = parse_or_map_err::<usize>(value_str)?;
+                Ok(())
+            }
+            "render_options.wireframe_color" => {
+                prev_value_str.replace(current_settings.render_options.wireframe_color.to_string());
 
-                        match selected_index {
-                            0 => {}
-                            1 => {
-                                current_settings.shader_options.bilinear_active = true;
-                            }
-                            2 => {
-                                current_settings.shader_options.trilinear_active = true;
-                            }
-                            _ => (),
-                        }
+                let wireframe_color = parse_or_map_err::<Vec3>(value_str)?;
 
-                        Ok(())
-                    }
-                    "shader_options.albedo_color_maps" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .shader_options
-                                .albedo_mapping_active
-                                .to_string(),
-                        );
-
-                        current_settings.shader_options.albedo_mapping_active =
-                            parse_or_map_err::<bool>(value_str)?;
-
-                        Ok(())
-                    }
-                    "shader_options.ambient_occlusion_maps" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .shader_options
-                                .ambient_occlusion_mapping_active
-                                .to_string(),
-                        );
-
-                        current_settings
-                            .shader_options
-                            .ambient_occlusion_mapping_active =
-                            parse_or_map_err::<bool>(value_str)?;
-
-                        Ok(())
-                    }
-                    "shader_options.roughness_maps" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .shader_options
-                                .roughness_mapping_active
-                                .to_string(),
-                        );
-
-                        current_settings.shader_options.roughness_mapping_active =
-                            parse_or_map_err::<bool>(value_str)?;
-
-                        Ok(())
-                    }
-                    "shader_options.metallic_maps" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .shader_options
-                                .metallic_mapping_active
-                                .to_string(),
-                        );
-
-                        current_settings.shader_options.metallic_mapping_active =
-                            parse_or_map_err::<bool>(value_str)?;
-
-                        Ok(())
-                    }
-                    "shader_options.normal_maps" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .shader_options
-                                .normal_mapping_active
-                                .to_string(),
-                        );
-
-                        current_settings.shader_options.normal_mapping_active =
-                            parse_or_map_err::<bool>(value_str)?;
-
-                        Ok(())
-                    }
-                    "shader_options.displacement_maps" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .shader_options
-                                .displacement_mapping_active
-                                .to_string(),
-                        );
-
-                        current_settings.shader_options.displacement_mapping_active =
-                            parse_or_map_err::<bool>(value_str)?;
-
-                        Ok(())
-                    }
-                    "shader_options.specular_maps" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .shader_options
-                                .specular_exponent_mapping_active
-                                .to_string(),
-                        );
-
-                        current_settings
-                            .shader_options
-                            .specular_exponent_mapping_active =
-                            parse_or_map_err::<bool>(value_str)?;
-
-                        Ok(())
-                    }
-                    "shader_options.emissive_maps" => {
-                        prev_value_str.replace(
-                            current_settings
-                                .shader_options
-                                .emissive_color_mapping_active
-                                .to_string(),
-                        );
-
-                        current_settings
-                            .shader_options
-                            .emissive_color_mapping_active = parse_or_map_err::<bool>(value_str)?;
-
-                        Ok(())
-                    }
-                    "postprocessing.effects.outline" => {
-                        prev_value_str.replace(current_settings.effects.outline.to_string());
+                current_settings.render_options.wireframe_color = wireframe_color;
 
-                        current_settings.effects.outline = parse_or_map_err::<bool>(value_str)?;
+                Ok(())
+            }
+            "render_options.wireframe_depth_tested" => {
+                prev_value_str.replace(
+                    current_settings
+                        .render_options
+                        .wireframe_depth_tested
+                        .to_string(),
+                );
+
+                current_settings.render_options.wireframe_depth_tested =
+                    parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "render_options.draw_normals" => {
+                prev_value_str.replace(current_settings.render_options.draw_normals.to_string());
 
-                        Ok(())
-                    }
-                    "postprocessing.effects.invert" => {
-                        prev_value_str.replace(current_settings.effects.invert.to_string());
+                current_settings.render_options.draw_normals = parse_or_map_err::<bool>(value_str)?;
 
-                        current_settings.effects.invert = parse_or_map_err::<bool>(value_str)?;
+                Ok(())
+            }
+            "render_options.draw_tangents" => {
+                prev_value_str.replace(current_settings.render_options.draw_tangents.to_string());
 
-                        Ok(())
-                    }
-                    "postprocessing.effects.grayscale" => {
-                        prev_value_str.replace(current_settings.effects.grayscale.to_string());
+                current_settings.render_options.draw_tangents =
+                    parse_or_map_err::<bool>(value_str)?;
 
-                        current_settings.effects.grayscale = parse_or_map_err::<bool>(value_str)?;
+                Ok(())
+            }
+            "render_options.draw_bitangents" => {
+                prev_value_str.replace(current_settings.render_options.draw_bitangents.to_string());
 
-                        Ok(())
-                    }
-                    "postprocessing.effects.sharpen_kernel" => {
-                        prev_value_str.replace(current_settings.effects.sharpen_kernel.to_string());
+                current_settings.render_options.draw_bitangents =
+                    parse_or_map_err::<bool>(value_str)?;
 
-                        current_settings.effects.sharpen_kernel =
-                            parse_or_map_err::<bool>(value_str)?;
+                Ok(())
+            }
+            "render_options.draw_normals_scale" => {
+                prev_value_str.replace(
+                    current_settings
+                        .render_options
+                        .draw_normals_scale
+                        .to_string(),
+                );
+
+                current_settings.render_options.draw_normals_scale =
+                    parse_or_map_err::<f32>(value_str)?;
+
+                Ok(())
+            }
+            "render_options.fog" => {
+                prev_value_str.replace(current_settings.render_options.fog.is_some().to_string());
 
-                        Ok(())
-                    }
-                    "postprocessing.effects.blur_kernel" => {
-                        prev_value_str.replace(current_settings.effects.blur_kernel.to_string());
+                current_settings.render_options.fog = if parse_or_map_err::<bool>(value_str)? {
+                    Some(Default::default())
+                } else {
+                    None
+                };
 
-                        current_settings.effects.blur_kernel = parse_or_map_err::<bool>(value_str)?;
+                Ok(())
+            }
+            "render.tone_mapping" => {
+                prev_value_str.replace(current_settings.render_options.tone_mapping.to_string());
 
-                        Ok(())
-                    }
-                    "postprocessing.effects.edge_detection_kernel" => {
-                        prev_value_str
-                            .replace(current_settings.effects.edge_detection_kernel.to_string());
+                let new_index = parse_or_map_err::<usize>(value_str)?;
+
+                current_settings.render_options.tone_mapping = TONE_MAPPING_OPERATORS[new_index];
+
+                Ok(())
+            }
+            "depth_test_method" => {
+                prev_value_str.replace(current_settings.depth_test_method.to_string());
 
-                        current_settings.effects.edge_detection_kernel =
-                            parse_or_map_err::<bool>(value_str)?;
+                let new_index = parse_or_map_err::<usize>(value_str)?;
 
-                        Ok(())
+                current_settings.depth_test_method = DEPTH_TEST_METHODS[new_index];
+
+                Ok(())
+            }
+            "shader_options.texture_filtering" => {
+                prev_value_str.replace(current_settings.shader_options.bilinear_active.to_string());
+
+                current_settings.shader_options.bilinear_active = false;
+                current_settings.shader_options.trilinear_active = false;
+
+                let selected_index = parse_or_map_err::<usize>(value_str)?;
+
+                match selected_index {
+                    0 => {}
+                    1 => {
+                        current_settings.shader_options.bilinear_active = true;
                     }
-                    "camera" => {
-                        debug_assert!(command.args.len() >= 5);
+                    2 => {
+                        current_settings.shader_options.trilinear_active = true;
+                    }
+                    _ => (),
+                }
 
-                        let uuid = parse_or_map_err::<Uuid>(&command.args[1])?;
-                        let index = parse_or_map_err::<usize>(&command.args[2])?;
+                Ok(())
+            }
+            "shader_options.albedo_color_maps" => {
+                prev_value_str.replace(
+                    current_settings
+                        .shader_options
+                        .albedo_mapping_active
+                        .to_string(),
+                );
+
+                current_settings.shader_options.albedo_mapping_active =
+                    parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "shader_options.ambient_occlusion_maps" => {
+                prev_value_str.replace(
+                    current_settings
+                        .shader_options
+                        .ambient_occlusion_mapping_active
+                        .to_string(),
+                );
+
+                current_settings
+                    .shader_options
+                    .ambient_occlusion_mapping_active = parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "shader_options.roughness_maps" => {
+                prev_value_str.replace(
+                    current_settings
+                        .shader_options
+                        .roughness_mapping_active
+                        .to_string(),
+                );
+
+                current_settings.shader_options.roughness_mapping_active =
+                    parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "shader_options.metallic_maps" => {
+                prev_value_str.replace(
+                    current_settings
+                        .shader_options
+                        .metallic_mapping_active
+                        .to_string(),
+                );
+
+                current_settings.shader_options.metallic_mapping_active =
+                    parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "shader_options.normal_maps" => {
+                prev_value_str.replace(
+                    current_settings
+                        .shader_options
+                        .normal_mapping_active
+                        .to_string(),
+                );
+
+                current_settings.shader_options.normal_mapping_active =
+                    parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "shader_options.displacement_maps" => {
+                prev_value_str.replace(
+                    current_settings
+                        .shader_options
+                        .displacement_mapping_active
+                        .to_string(),
+                );
+
+                current_settings.shader_options.displacement_mapping_active =
+                    parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "shader_options.parallax_occlusion" => {
+                prev_value_str.replace(
+                    current_settings
+                        .shader_options
+                        .parallax_occlusion_active
+                        .to_string(),
+                );
+
+                current_settings.shader_options.parallax_occlusion_active =
+                    parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "shader_options.parallax_self_shadowing" => {
+                prev_value_str.replace(
+                    current_settings
+                        .shader_options
+                        .parallax_self_shadowing_active
+                        .to_string(),
+                );
+
+                current_settings
+                    .shader_options
+                    .parallax_self_shadowing_active = parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "shader_options.specular_maps" => {
+                prev_value_str.replace(
+                    current_settings
+                        .shader_options
+                        .specular_exponent_mapping_active
+                        .to_string(),
+                );
+
+                current_settings
+                    .shader_options
+                    .specular_exponent_mapping_active = parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "shader_options.emissive_maps" => {
+                prev_value_str.replace(
+                    current_settings
+                        .shader_options
+                        .emissive_color_mapping_active
+                        .to_string(),
+                );
+
+                current_settings
+                    .shader_options
+                    .emissive_color_mapping_active = parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "postprocessing.effects.outline" => {
+                prev_value_str.replace(current_settings.effects.outline.to_string());
 
-                        SCENE_CONTEXT.with(|ctx| -> Result<(), String> {
-                            let resources = &ctx.resources;
+                current_settings.effects.outline = parse_or_map_err::<bool>(value_str)?;
 
-                            let mut arena = resources.camera.borrow_mut();
+                Ok(())
+            }
+            "postprocessing.effects.invert" => {
+                prev_value_str.replace(current_settings.effects.invert.to_string());
 
-                            let camera_handle = Handle { index, uuid };
+                current_settings.effects.invert = parse_or_map_err::<bool>(value_str)?;
 
-                            match arena.get_mut(&camera_handle) {
-                                Ok(entry) => {
-                                    let camera = &mut entry.item;
+                Ok(())
+            }
+            "postprocessing.effects.grayscale" => {
+                prev_value_str.replace(current_settings.effects.grayscale.to_string());
 
-                                    let attribute = &command.args[3];
+                current_settings.effects.grayscale = parse_or_map_err::<bool>(value_str)?;
 
-                                    match attribute.as_str() {
-                                        "kind" => {
-                                            let new_kind_index =
-                                                parse_or_map_err::<usize>(&command.args[4])?;
+                Ok(())
+            }
+            "postprocessing.effects.sharpen_kernel" => {
+                prev_value_str.replace(current_settings.effects.sharpen_kernel.to_string());
 
-                                            let new_kind = CAMERA_PROJECTION_KINDS[new_kind_index];
+                current_settings.effects.sharpen_kernel = parse_or_map_err::<bool>(value_str)?;
 
-                                            match new_kind {
-                                                CameraProjectionKind::Perspective => {
-                                                    // TODO
-                                                    todo!()
-                                                }
-                                                CameraProjectionKind::Orthographic => {
-                                                    // TODO
-                                                    todo!()
-                                                }
-                                            }
-                                        }
-                                        "perspective.field_of_view" => {
-                                            let new_fov =
-                                                parse_or_map_err::<f32>(&command.args[4])?;
+                Ok(())
+            }
+            "postprocessing.effects.blur_kernel" => {
+                prev_value_str.replace(current_settings.effects.blur_kernel.to_string());
 
-                                            camera.set_field_of_view(Some(new_fov));
-                                        }
-                                        "projection_z_near" => {
-                                            let new_z_near =
-                                                parse_or_map_err::<f32>(&command.args[4])?;
+                current_settings.effects.blur_kernel = parse_or_map_err::<bool>(value_str)?;
 
-                                            camera.set_projection_z_near(
-                                                new_z_near.min(camera.get_projection_z_far()),
-                                            );
-                                        }
-                                        "projection_z_far" => {
-                                            let new_z_far =
-                                                parse_or_map_err::<f32>(&command.args[4])?;
+                Ok(())
+            }
+            "postprocessing.effects.edge_detection_kernel" => {
+                prev_value_str.replace(current_settings.effects.edge_detection_kernel.to_string());
 
-                                            camera.set_projection_z_far(
-                                                new_z_far.max(camera.get_projection_z_near()),
+                current_settings.effects.edge_detection_kernel =
+                    parse_or_map_err::<bool>(value_str)?;
+
+                Ok(())
+            }
+            "camera" => {
+                debug_assert!(args.len() >= 5);
+
+                let uuid = parse_or_map_err::<Uuid>(&args[1])?;
+                let index = parse_or_map_err::<usize>(&args[2])?;
+
+                SCENE_CONTEXT.with(|ctx| -> Result<(), String> {
+                    let resources = &ctx.resources;
+
+                    let mut arena = resources.camera.borrow_mut();
+
+                    let camera_handle = Handle { index, uuid };
+
+                    match arena.get_mut(&camera_handle) {
+                        Ok(entry) => {
+                            let camera = &mut entry.item;
+
+                            let attribute = &args[3];
+
+                            match attribute.as_str() {
+                                "kind" => {
+                                    let new_kind_index = parse_or_map_err::<usize>(&args[4])?;
+
+                                    let new_kind = CAMERA_PROJECTION_KINDS[new_kind_index];
+
+                                    match new_kind {
+                                        CameraProjectionKind::Perspective => {
+                                            camera.set_perspective(
+                                                camera
+                                                    .get_field_of_view()
+                                                    .unwrap_or(DEFAULT_CAMERA_FIELD_OF_VIEW),
                                             );
                                         }
-                                        _ => {
-                                            println!(
-                                                "Unrecognized camera attribute '{}'!",
-                                                attribute
-                                            );
+                                        CameraProjectionKind::Orthographic { height } => {
+                                            camera.set_orthographic(height);
                                         }
                                     }
+                                }
+                                "orthographic.height" => {
+                                    let new_height = parse_or_map_err::<f32>(&args[4])?;
+
+                                    camera.set_orthographic(new_height);
+                                }
+                                "perspective.field_of_view" => {
+                                    let new_fov = parse_or_map_err::<f32>(&args[4])?;
+
+                                    camera.set_field_of_view(Some(new_fov));
+                                }
+                                "projection_z_near" => {
+                                    let new_z_near = parse_or_map_err::<f32>(&args[4])?;
+
+                                    camera.set_projection_z_near(
+                                        new_z_near.min(camera.get_projection_z_far()),
+                                    );
+                                }
+                                "projection_z_far" => {
+                                    let new_z_far = parse_or_map_err::<f32>(&args[4])?;
 
-                                    Ok(())
+                                    camera.set_projection_z_far(
+                                        new_z_far.max(camera.get_projection_z_near()),
+                                    );
+                                }
+                                _ => {
+                                    println!("Unrecognized camera attribute '{}'!", attribute);
                                 }
-                                Err(_) => Err(format!(
-                                    "Camera not found for Handle {}!",
-                                    camera_handle.uuid
-                                )
-                                .to_string()),
                             }
-                        })
-                    }
-                    _ => {
-                        println!("Unknown settings key `{}`.", setting_key);
 
-                        Ok(())
+                            Ok(())
+                        }
+                        Err(_) => Err(format!(
+                            "Camera not found for Handle {}!",
+                            camera_handle.uuid
+                        )
+                        .to_string()),
                     }
-                }
-            })?;
+                })
+            }
+            _ => {
+                println!("Unknown settings key `{}`.", setting_key);
 
-            Ok((prev_value_str, new_resolution, new_windowing_mode))
+                Ok(())
+            }
         }
-        _ => Err(format!("Unknown command kind `{}`.", command.kind).to_string()),
-    }
+    })?;
+
+    Ok((prev_value_str, new_resolution, new_windowing_mode))
 }
 
 type ProcessCommandsResult = Result<(Option<Resolution>, Option<AppWindowingMode>), String>;
@@ -603,44 +713,25 @@ pub(crate) fn process_commands(
     let mut result: (Option<Resolution>, Option<AppWindowingMode>) = (None, None);
 
     while let Some((cmd, is_undo)) = pending_commands.pop_front() {
-        let components: Vec<String> = cmd.split(' ').map(|s| s.to_string()).collect();
+        let mut context = CommandContext::default();
 
-        if let Some((kind, args)) = components.split_first() {
-            let command = Command {
-                kind,
-                args,
-                is_undo,
-            };
+        let command =
+            COMMAND_REGISTRY.with(|registry| registry.borrow_mut().execute(&mut context, &cmd))?;
 
-            let process_command_result = process_command(command)?;
-
-            let prev_value = process_command_result.0;
-
-            let executed_command = ExecutedCommand {
-                kind: kind.to_string(),
-                args: args.iter().map(|s| s.to_string()).collect(),
-                prev_value,
-            };
+        if let Some(new_resolution) = context.new_resolution {
+            result.0.replace(new_resolution);
+        }
 
-            match process_command_result {
-                (_, Some(new_resolution), None) => {
-                    result.0.replace(new_resolution);
-                }
-                (_, None, Some(new_windowing_mode)) => {
-                    result.1.replace(new_windowing_mode);
-                }
-                (_, Some(new_resolution), Some(new_windowing_mode)) => {
-                    result.0.replace(new_resolution);
-                    result.1.replace(new_windowing_mode);
-                }
-                _ => (),
-            }
+        if let Some(new_windowing_mode) = context.new_windowing_mode {
+            result.1.replace(new_windowing_mode);
+        }
 
-            if !is_undo {
-                executed_commands.push_back(executed_command);
-            }
-        } else {
-            println!("Unrecognized command: '{}'", cmd);
+        if !is_undo {
+            executed_commands.push_back(ExecutedCommand {
+                kind: command.name,
+                args: command.args.0,
+                prev_value: context.prev_value,
+            });
         }
     }
 
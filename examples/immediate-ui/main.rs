@@ -80,7 +80,7 @@ fn resize_framebuffer(
     framebuffer_rc: &Rc<RefCell<Framebuffer>>,
     renderer: &mut SoftwareRenderer,
     window_list: &mut WindowList,
-) {
+) -> Result<(), String> {
     {
         // Resize our framebuffer to match the native window's new resolution.
 
@@ -115,7 +115,7 @@ fn resize_framebuffer(
     // Rebuild each window's UI tree(s), in response to the new (native
     // window) resolution.
 
-    window_list.rebuild_ui_trees(resolution);
+    window_list.rebuild_ui_trees(resolution)
 }
 
 fn main() -> Result<(), String> {
@@ -361,7 +361,7 @@ fn main() -> Result<(), String> {
         if let Some(resolution) = new_resolution {
             let mut renderer = renderer_rc.borrow_mut();
 
-            resize_framebuffer(resolution, &framebuffer_rc, &mut renderer, &mut window_list);
+            resize_framebuffer(resolution, &framebuffer_rc, &mut renderer, &mut window_list)?;
         } else {
             // Clear the framebuffer before rendering this frame.
             let mut framebuffer = framebuffer_rc.borrow_mut();
@@ -444,6 +444,14 @@ fn main() -> Result<(), String> {
                         effect.apply(&mut color_buffer);
                     }
                 });
+
+                // Encode the (possibly linear) scene for display, once, before
+                // the (sRGB) UI is drawn over it.
+
+                renderer_rc
+                    .borrow()
+                    .get_color_space()
+                    .present(&mut color_buffer);
             }
         }
 
@@ -569,7 +577,7 @@ fn main() -> Result<(), String> {
                     &framebuffer_rc,
                     &mut renderer,
                     &mut window_list,
-                );
+                )?;
             }
         }
 
@@ -717,7 +725,7 @@ fn main() -> Result<(), String> {
             let mut window_list = window_list_rc.borrow_mut();
 
             if let Some(resolution) = new_resolution {
-                resize_framebuffer(resolution, &framebuffer_rc, &mut renderer, &mut window_list);
+                resize_framebuffer(resolution, &framebuffer_rc, &mut renderer, &mut window_list)?;
 
                 app.resize_window(resolution)
             } else {
@@ -732,7 +740,7 @@ fn main() -> Result<(), String> {
                         &framebuffer_rc,
                         &mut renderer,
                         &mut window_list,
-                    );
+                    )?;
                 }
 
                 Ok(())
@@ -770,6 +778,20 @@ fn main() -> Result<(), String> {
                 renderer.options = current_settings.render_options;
                 renderer.shader_options = current_settings.shader_options;
 
+                // HDR output is tone-mapped (and gamma-encoded) by the renderer.
+
+                if current_settings.hdr {
+                    renderer
+                        .options
+                        .render_pass_flags
+                        .set(RenderPassFlag::ToneMapping);
+                } else {
+                    renderer
+                        .options
+                        .render_pass_flags
+                        .unset(RenderPassFlag::ToneMapping);
+                }
+
                 let shader = [
                     DEFAULT_FRAGMENT_SHADER,
                     AlbedoFragmentShader,
@@ -817,7 +839,7 @@ fn main() -> Result<(), String> {
 
         let mut window_list = window_list_rc.borrow_mut();
 
-        window_list.rebuild_ui_trees(current_resolution);
+        window_list.rebuild_ui_trees(current_resolution)?;
 
         Ok(())
     };
@@ -3,7 +3,7 @@ use std::fmt::{Debug, Display};
 use cairo::{
     mem::linked_list::LinkedList,
     resource::handle::Handle,
-    scene::camera::{Camera, CameraProjectionKind},
+    scene::camera::{Camera, CameraProjectionKind, CAMERA_PROJECTION_KINDS},
     serde::PostDeserialize,
     ui::{
         fastpath::{
@@ -72,21 +72,19 @@ impl CameraAttributesPanel {
 
         let current_projection_kind = camera.get_kind();
 
-        let projection_kind_options: Vec<RadioOption> = [
-            CameraProjectionKind::Orthographic.to_string(),
-            CameraProjectionKind::Perspective.to_string(),
-        ]
-        .into_iter()
-        .map(|label| RadioOption { label })
-        .collect();
-
-        let selected_projection_kind_index = [
-            CameraProjectionKind::Orthographic,
-            CameraProjectionKind::Perspective,
-        ]
-        .iter()
-        .position(|i| *i == current_projection_kind)
-        .unwrap();
+        let projection_kind_options: Vec<RadioOption> = CAMERA_PROJECTION_KINDS
+            .iter()
+            .map(|kind| RadioOption {
+                label: kind.to_string(),
+            })
+            .collect();
+
+        let selected_projection_kind_index = CAMERA_PROJECTION_KINDS
+            .iter()
+            .position(|kind| {
+                std::mem::discriminant(kind) == std::mem::discriminant(&current_projection_kind)
+            })
+            .unwrap();
 
         if let Some(new_selected_projection_kind_index) = radio_group(
             format!("{}.kind.radio_group", self.id).to_string(),
@@ -131,8 +129,31 @@ impl CameraAttributesPanel {
 
                 tree.push(spacer(18))?;
             }
-            CameraProjectionKind::Orthographic => {
-                // Extent
+            CameraProjectionKind::Orthographic { height } => {
+                // Height
+
+                tree.push(text(
+                    format!("CameraAttributesPanel{}.orthographic.height.label", self.id)
+                        .to_string(),
+                    "Height".to_string(),
+                ))?;
+
+                if let Some(new_height) = slider(
+                    format!("CameraAttributesPanel{}.orthographic.height", self.id),
+                    height,
+                    SliderOptions {
+                        min: 1.0,
+                        max: 100.0,
+                        ..Default::default()
+                    },
+                    tree,
+                )? {
+                    let cmd_str = self.make_command("orthographic.height", &new_height);
+
+                    pending_queue.push_back((cmd_str, false));
+                }
+
+                tree.push(spacer(18))?;
             }
         }
 
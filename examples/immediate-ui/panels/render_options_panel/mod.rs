@@ -350,6 +350,17 @@ impl PanelInstance for RenderOptionsPanel {
                 )?;
 
                 if draw_wireframe {
+                    // Wireframe depth testing.
+
+                    setting_checkbox(
+                        &format!("{}.debug.wireframeDepthTested", self.id),
+                        "render_options.wireframe_depth_tested",
+                        "Hide occluded edges",
+                        current_settings.render_options.wireframe_depth_tested,
+                        tree,
+                        &mut pending_queue,
+                    )?;
+
                     // Wireframe color.
 
                     let current_color = current_settings.render_options.wireframe_color;
@@ -394,7 +405,29 @@ impl PanelInstance for RenderOptionsPanel {
                     &mut pending_queue,
                 )?;
 
-                if draw_normals {
+                let draw_tangents = current_settings.render_options.draw_tangents;
+
+                setting_checkbox(
+                    &format!("{}.debug.drawTangents", self.id),
+                    "render_options.draw_tangents",
+                    "Draw tangents",
+                    draw_tangents,
+                    tree,
+                    &mut pending_queue,
+                )?;
+
+                let draw_bitangents = current_settings.render_options.draw_bitangents;
+
+                setting_checkbox(
+                    &format!("{}.debug.drawBitangents", self.id),
+                    "render_options.draw_bitangents",
+                    "Draw bitangents",
+                    draw_bitangents,
+                    tree,
+                    &mut pending_queue,
+                )?;
+
+                if draw_normals || draw_tangents || draw_bitangents {
                     // Draw normals scale
 
                     tree.push(text(
@@ -144,6 +144,18 @@ impl PanelInstance for ShaderOptionsPanel {
                                 "Displacement maps",
                                 current_settings.shader_options.displacement_mapping_active,
                             ),
+                            Checkbox::new(
+                                "shader_options.parallax_occlusion",
+                                "Parallax occlusion",
+                                current_settings.shader_options.parallax_occlusion_active,
+                            ),
+                            Checkbox::new(
+                                "shader_options.parallax_self_shadowing",
+                                "Parallax self-shadowing",
+                                current_settings
+                                    .shader_options
+                                    .parallax_self_shadowing_active,
+                            ),
                             Checkbox::new(
                                 "shader_options.specular_maps",
                                 "Specular maps",
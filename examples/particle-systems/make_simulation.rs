@@ -1,14 +1,10 @@
 use std::{cell::RefCell, f32::consts::PI, rc::Rc};
 
 use cairo::{
-    physics::simulation::{
-        force::ContactPoint,
-        particle::{
-            generator::{ParticleGenerator, ParticleGeneratorKind},
-            Particle,
-        },
-        physical_constants::EARTH_GRAVITY,
-        units::Newtons,
+    physics::simulation::particle::{
+        generator::{ParticleGenerator, ParticleGeneratorKind},
+        particlelist::{ParticleList, ParticleRecyclePolicy},
+        Particle,
     },
     random::sampler::RandomSampler,
     vec::vec3::Vec3,
@@ -16,37 +12,12 @@ use cairo::{
 
 use crate::{
     particle::{PARTICLE_MASS, PARTICLE_MAX_AGE_SECONDS},
-    simulation::{Operators, ParticleForce, Simulation},
-    state_vector::StateVector,
+    simulation::{Operators, Simulation},
 };
 
 pub(crate) const SEED_SIZE: usize = 2048;
 
-static GRAVITY: ParticleForce =
-    |_state: &StateVector, _i: usize, _current_time: f32| -> (Newtons, Option<ContactPoint>) {
-        let newtons = Vec3 {
-            x: 0.0,
-            y: -EARTH_GRAVITY,
-            z: 0.0,
-        };
-
-        (newtons, None)
-    };
-
-static AIR_RESISTANCE: ParticleForce =
-    |state: &StateVector, i: usize, _current_time: f32| -> (Newtons, Option<ContactPoint>) {
-        static D: f32 = 0.0;
-
-        static WIND: Vec3 = Vec3 {
-            x: 0.0,
-            y: 0.0,
-            z: 0.0,
-        };
-
-        let newtons = (WIND - state.data[i + state.len()]) * D;
-
-        (newtons, None)
-    };
+pub(crate) const PARTICLE_POOL_CAPACITY: usize = 2048;
 
 pub(crate) fn make_simulation<'a>(
     sampler: Rc<RefCell<RandomSampler<SEED_SIZE>>>,
@@ -325,8 +296,12 @@ pub(crate) fn make_simulation<'a>(
 
     Simulation {
         sampler,
-        pool: Default::default(),
-        forces: vec![&GRAVITY, &AIR_RESISTANCE],
+        pool: RefCell::new(ParticleList::new(
+            PARTICLE_POOL_CAPACITY,
+            ParticleRecyclePolicy::OldestFirst,
+        )),
+        environment: Default::default(),
+        forces: vec![],
         colliders: Default::default(),
         operators: RefCell::new(operators),
         generators: RefCell::new(vec![omnidirectional, directional_right, directional_up]),
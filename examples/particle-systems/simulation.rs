@@ -4,6 +4,7 @@ use physical_constants::NEWTONIAN_CONSTANT_OF_GRAVITATION;
 
 use cairo::{
     physics::simulation::{
+        environment::PhysicsEnvironment,
         force,
         particle::{
             generator::{ParticleGenerator, ParticleGeneratorKind},
@@ -28,6 +29,7 @@ static COMPONENTS_PER_PARTICLE: usize = 2;
 
 fn system_dynamics_function(
     current_state: &StateVector,
+    environment: &PhysicsEnvironment,
     quadtree: &Quadtree,
     forces: &[&ParticleForce],
     operators: &mut Operators,
@@ -37,8 +39,15 @@ fn system_dynamics_function(
     let n = current_state.len();
 
     // Compute new accelerations for derivative.
-    let mut derivative =
-        compute_accelerations(current_state, quadtree, forces, operators, current_time, h);
+    let mut derivative = compute_accelerations(
+        current_state,
+        environment,
+        quadtree,
+        forces,
+        operators,
+        current_time,
+        h,
+    );
 
     for i in 0..n {
         // Copy velocities from previous (current?) state.
@@ -50,6 +59,7 @@ fn system_dynamics_function(
 
 fn compute_accelerations(
     current_state: &StateVector,
+    environment: &PhysicsEnvironment,
     quadtree: &Quadtree,
     forces: &[&ParticleForce],
     operators: &mut Operators,
@@ -62,7 +72,8 @@ fn compute_accelerations(
 
     // Compute environmental accelerations acting on each particle.
     for i in 0..n {
-        let mut net_force_acceleration: Vec3 = Default::default();
+        let mut net_force_acceleration: Vec3 =
+            environment.get_acceleration(&current_state.data[i + n]);
 
         for force in forces {
             let (newtons, _contact_point) = force(current_state, i, current_time);
@@ -154,7 +165,8 @@ pub(crate) struct Operators {
 
 pub(crate) struct Simulation<'a, const N: usize> {
     pub sampler: Rc<RefCell<RandomSampler<N>>>,
-    pub pool: RefCell<ParticleList<N>>,
+    pub pool: RefCell<ParticleList>,
+    pub environment: PhysicsEnvironment,
     pub forces: Vec<&'a ParticleForce>,
     pub colliders: RefCell<Vec<LineSegmentCollider>>,
     pub operators: RefCell<Operators>,
@@ -251,6 +263,7 @@ impl<'a, const N: usize> Simulation<'a, N> {
 
         let derivative = system_dynamics_function(
             &state,
+            &self.environment,
             &quadtree,
             &self.forces,
             &mut operators,
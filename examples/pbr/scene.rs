@@ -93,7 +93,7 @@ pub fn make_sphere_grid_scene(
     let _geometry = result.0;
     let meshes = result.1;
 
-    let mesh = meshes[1].to_owned();
+    let mesh = meshes[0].to_owned();
 
     let mesh_handle = mesh_arena.insert(mesh);
 
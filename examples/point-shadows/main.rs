@@ -1,6 +1,6 @@
 extern crate sdl2;
 
-use std::{cell::RefCell, f32::consts::PI, rc::Rc};
+use std::{cell::RefCell, env, f32::consts::PI, rc::Rc};
 
 use cairo::{
     app::{
@@ -8,7 +8,10 @@ use cairo::{
         App, AppWindowInfo,
     },
     buffer::{framebuffer::Framebuffer, Buffer2D},
+    color,
+    debug::ruler::DistanceRuler,
     device::{game_controller::GameControllerState, keyboard::KeyboardState, mouse::MouseState},
+    font::{cache::FontCache, FontInfo},
     matrix::Mat4,
     render::Renderer,
     scene::{
@@ -118,6 +121,22 @@ fn main() -> Result<(), String> {
         RefCell::new(renderer)
     };
 
+    // Distance ruler (optionally labelled, given a path to a font)
+
+    let ruler_rc = RefCell::new(DistanceRuler::default());
+
+    let font_info = env::args().nth(1).map(|filepath| FontInfo {
+        filepath,
+        point_size: 14,
+    });
+
+    let font_cache_rc = RefCell::new(FontCache::new(app.context.ttf_context));
+
+    println!(
+        "Press {} to toggle the distance ruler, then click 2 points to measure.",
+        ruler_rc.borrow().toggle_keycode
+    );
+
     // App update and render callbacks
 
     let update_node = |_current_world_transform: &Mat4,
@@ -180,6 +199,34 @@ fn main() -> Result<(), String> {
             Some(update_node_rc),
         )?;
 
+        // Picks ruler points through the active camera.
+
+        {
+            let resources = &scene_context.resources;
+
+            let camera_arena = resources.camera.borrow();
+
+            let framebuffer = framebuffer_rc.borrow();
+
+            if let Some(camera) = camera_arena
+                .entries
+                .iter()
+                .flatten()
+                .map(|entry| &entry.item)
+                .find(|camera| camera.is_active)
+            {
+                ruler_rc.borrow_mut().update(
+                    keyboard_state,
+                    mouse_state,
+                    scene,
+                    resources,
+                    camera,
+                    framebuffer.width,
+                    framebuffer.height,
+                )?;
+            }
+        }
+
         renderer.options.update(keyboard_state);
 
         renderer.shader_options.update(keyboard_state);
@@ -218,6 +265,8 @@ fn main() -> Result<(), String> {
         {
             let mut renderer = renderer_rc.borrow_mut();
 
+            ruler_rc.borrow().render(&mut *renderer, color::YELLOW);
+
             renderer.end_frame();
         }
 
@@ -233,6 +282,15 @@ fn main() -> Result<(), String> {
                     draw_point_shadow_map_thumbnails(resources, &mut color_buffer);
                 }
 
+                if let Some(font_info) = font_info.as_ref() {
+                    ruler_rc.borrow().render_label(
+                        &mut color_buffer,
+                        &mut font_cache_rc.borrow_mut(),
+                        font_info,
+                        &shader_context_rc.borrow(),
+                    )?;
+                }
+
                 color_buffer.copy_to(canvas);
 
                 Ok(())
@@ -10,7 +10,8 @@ use cairo::{
     effect::Effect,
     effects::{
         dilation_effect::DilationEffect, grayscale_effect::GrayscaleEffect,
-        invert_effect::InvertEffect, kernel_effect::KernelEffect,
+        invert_effect::InvertEffect, kernel_effect::KernelEffect, palette_effect::PaletteEffect,
+        pixelate_effect::PixelateEffect,
     },
     matrix::Mat4,
     render::Renderer,
@@ -132,6 +133,22 @@ fn main() -> Result<(), String> {
     #[allow(unused)]
     let invert_effect = InvertEffect {};
 
+    #[allow(unused)]
+    let palette_effect = PaletteEffect::new(
+        &[
+            color::BLACK,
+            color::DARK_GRAY,
+            color::LIGHT_GRAY,
+            color::WHITE,
+            color::ORANGE,
+            color::SKY_BOX,
+        ],
+        true,
+    );
+
+    #[allow(unused)]
+    let pixelate_effect = PixelateEffect::new(4);
+
     #[allow(unused)]
     let sharpen_kernel_effect = KernelEffect::new([2, 2, 2, 2, -15, 2, 2, 2, 2], None);
 
@@ -145,6 +162,8 @@ fn main() -> Result<(), String> {
         // Box::new(RefCell::new(outline_effect)),
         // Box::new(RefCell::new(grayscale_effect)),
         // Box::new(RefCell::new(invert_effect)),
+        // Box::new(RefCell::new(pixelate_effect)),
+        // Box::new(RefCell::new(palette_effect)),
         // Box::new(RefCell::new(sharpen_kernel_effect)),
         // Box::new(RefCell::new(blur_kernel_effect)),
         Box::new(RefCell::new(edge_detection_kernel_effect)),
@@ -14,7 +14,9 @@ use cairo::{
     buffer::{framebuffer::Framebuffer, Buffer2D},
     color::Color,
     device::{game_controller::GameControllerState, keyboard::KeyboardState, mouse::MouseState},
+    log::{self, LogCategory},
     matrix::Mat4,
+    render::options::RenderPassFlag,
     resource::{arena::Arena, handle::Handle},
     scene::{
         context::SceneContext,
@@ -23,7 +25,10 @@ use cairo::{
         node::{SceneNode, SceneNodeType},
         resources::SceneResources,
     },
-    shader::context::ShaderContext,
+    shader::{
+        context::ShaderContext,
+        fog::{Fog, FogKind},
+    },
     shaders::{
         default_fragment_shader::DEFAULT_FRAGMENT_SHADER,
         default_vertex_shader::DEFAULT_VERTEX_SHADER,
@@ -43,6 +48,7 @@ mod scene;
 
 static USE_DEMO_CAMERA: bool = false;
 static DRAW_DIRECTIONAL_SHADOW_MAP_THUMBNAILS: bool = false;
+static ENTITY_CULLING_LOG_INTERVAL_FRAMES: u32 = 120;
 
 fn main() -> Result<(), String> {
     let mut window_info = AppWindowInfo {
@@ -141,6 +147,24 @@ fn main() -> Result<(), String> {
         Default::default(),
     );
 
+    // Shades each visible pixel once, despite the overdraw between primitives.
+
+    renderer.options.render_pass_flags |= RenderPassFlag::DepthPrepass;
+
+    // Fades the ground plane out into the distance.
+
+    renderer.options.fog = Some(Fog {
+        color: Vec3 {
+            x: 0.6,
+            y: 0.65,
+            z: 0.7,
+        },
+        kind: FogKind::Linear {
+            start: 20.0,
+            end: 90.0,
+        },
+    });
+
     renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
 
     let renderer_rc = RefCell::new(renderer);
@@ -399,7 +423,7 @@ fn main() -> Result<(), String> {
         Ok(())
     };
 
-    let render = |_frame_index: Option<u32>,
+    let render = |frame_index: Option<u32>,
                   _new_resolution: Option<Resolution>,
                   canvas: &mut [u8]|
      -> Result<(), String> {
@@ -422,6 +446,22 @@ fn main() -> Result<(), String> {
             }),
         ) {
             Ok(()) => {
+                // Reports how many entities were frustum-culled, periodically.
+
+                if let Some(index) = frame_index {
+                    if index.is_multiple_of(ENTITY_CULLING_LOG_INTERVAL_FRAMES) {
+                        let stats = renderer_rc.borrow().stats;
+
+                        log::info(
+                            LogCategory::Render,
+                            &format!(
+                                "Rendered {} entities ({} culled).",
+                                stats.entities_rendered, stats.entities_culled
+                            ),
+                        );
+                    }
+                }
+
                 // Write out.
 
                 let framebuffer = framebuffer_rc.borrow();
@@ -1,40 +1,18 @@
-use cairo::{
-    physics::simulation::{force::ContactPoint, physical_constants::EARTH_GRAVITY, units::Newtons},
-    vec::vec3::Vec3,
-};
+use cairo::physics::simulation::environment::PhysicsEnvironment;
 
-use crate::{
-    rigid_body::RigidBody,
-    rigid_body_simulation_state::RigidBodySimulationState,
-    simulation::{RigidBodyForce, Simulation},
-};
+use crate::{rigid_body::RigidBody, simulation::Simulation};
 
 pub fn make_simulation() -> Simulation {
-    #[allow(unused)]
-    let gravity_body_force: RigidBodyForce = Box::new(
-        |state: &RigidBodySimulationState,
-         _i: usize,
-         _current_time: f32|
-         -> (Newtons, Option<ContactPoint>) {
-            static BODY_FORCE: Vec3 = Vec3 {
-                x: 0.0,
-                y: -EARTH_GRAVITY,
-                z: 0.0,
-            };
+    // Gravity is off, leaving the body to the forces applied by the user; see
+    // `PhysicsEnvironment::default()` for Earth's gravity.
 
-            (BODY_FORCE / state.inverse_mass, None)
-        },
-    );
-
-    let forces = vec![
-        //
-        // gravity_body_force,
-    ];
+    let environment = PhysicsEnvironment::zero_gravity();
 
     let rigid_bodies = vec![RigidBody::circle(Default::default(), 5.0, 2.5)];
 
     Simulation {
-        forces,
+        environment,
+        forces: vec![],
         rigid_bodies,
     }
 }
@@ -2,6 +2,7 @@ use std::ops;
 
 use cairo::{
     matrix::Mat4,
+    physics::simulation::environment::PhysicsEnvironment,
     transform::quaternion::Quaternion,
     vec::{vec3::Vec3, vec4::Vec4},
 };
@@ -102,12 +103,20 @@ impl RigidBodySimulationState {
 
     pub fn accumulate_accelerations(
         &self,
+        environment: &PhysicsEnvironment,
         forces: &[RigidBodyForce],
         current_time: f32,
         derivative: &mut Self,
     ) {
         let position = self.position;
 
+        // Accumulate environmental (world-level) accelerations; static bodies
+        // are unaffected.
+
+        if self.inverse_mass > 0.0 {
+            derivative.linear_momentum += environment.get_acceleration(&self.velocity());
+        }
+
         for force in forces {
             let (f, point) = force(self, 0, current_time);
 
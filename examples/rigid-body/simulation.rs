@@ -1,4 +1,7 @@
-use cairo::{physics::simulation::force::BoxedForce, vec::vec3::Vec3};
+use cairo::{
+    physics::simulation::{environment::PhysicsEnvironment, force::BoxedForce},
+    vec::vec3::Vec3,
+};
 
 use crate::{
     rigid_body::RigidBody, rigid_body_simulation_state::RigidBodySimulationState,
@@ -8,6 +11,7 @@ use crate::{
 pub type RigidBodyForce = BoxedForce<RigidBodySimulationState>;
 
 pub struct Simulation {
+    pub environment: PhysicsEnvironment,
     pub forces: Vec<RigidBodyForce>,
     pub rigid_bodies: Vec<RigidBody>,
 }
@@ -23,7 +27,8 @@ impl Simulation {
         }
 
         let new_state = {
-            let derivative = system_dynamics_function(&state, &self.forces, current_time);
+            let derivative =
+                system_dynamics_function(&state, &self.environment, &self.forces, current_time);
 
             // Performs basic Euler integration over position and velocity.
 
@@ -41,6 +46,7 @@ impl Simulation {
 
 fn system_dynamics_function(
     state: &StateVector<RigidBodySimulationState>,
+    environment: &PhysicsEnvironment,
     forces: &[RigidBodyForce],
     current_time: f32,
 ) -> StateVector<RigidBodySimulationState> {
@@ -64,7 +70,7 @@ fn system_dynamics_function(
 
         // 3. Rate-of-change of linear and angular momenta.
 
-        body_state.accumulate_accelerations(forces, current_time, body_derivative);
+        body_state.accumulate_accelerations(environment, forces, current_time, body_derivative);
     }
 
     derivative
@@ -11,7 +11,7 @@ use cairo::{
     buffer::framebuffer::Framebuffer,
     device::{game_controller::GameControllerState, keyboard::KeyboardState, mouse::MouseState},
     matrix::Mat4,
-    render::Renderer,
+    render::{options::RenderOptions, Renderer},
     scene::{
         context::SceneContext,
         node::{SceneNode, SceneNodeType},
@@ -120,7 +120,10 @@ fn main() -> Result<(), String> {
         scene_context.resources.clone(),
         DEFAULT_VERTEX_SHADER,
         DEFAULT_FRAGMENT_SHADER,
-        Default::default(),
+        RenderOptions {
+            supersample_factor: 2,
+            ..Default::default()
+        },
     );
 
     let renderer_rc = RefCell::new(renderer);
@@ -1,13 +1,26 @@
 extern crate sdl2;
 
-use std::{cell::RefCell, f32::consts::TAU, rc::Rc};
+use std::{cell::RefCell, env, f32::consts::TAU, rc::Rc};
 
 use cairo::{
     app::{resolution::Resolution, App, AppWindowInfo},
-    buffer::framebuffer::Framebuffer,
-    device::{game_controller::GameControllerState, keyboard::KeyboardState, mouse::MouseState},
+    buffer::{framebuffer::Framebuffer, Buffer2D},
+    debug::key_bindings::KeyBindingOverlay,
+    device::{
+        game_controller::GameControllerState,
+        keyboard::{input_map::InputMap, KeyboardState},
+        mouse::MouseState,
+        recording::{InputPlayer, InputRecorder},
+    },
+    font::{cache::FontCache, FontInfo},
+    graphics::text::cache::TextCache,
     matrix::Mat4,
-    render::Renderer,
+    render::{
+        options::{shader::RenderShaderOptions, RenderOptions},
+        scale::{RenderScale, UpscaleFilter},
+        stats::{RenderStatsFormat, RenderStatsRecorder},
+        Renderer,
+    },
     scene::{
         context::SceneContext,
         node::{SceneNode, SceneNodeType},
@@ -92,12 +105,24 @@ fn main() -> Result<(), String> {
         ..Default::default()
     };
 
+    // Render the scene below the canvas resolution, adjusting the scale to
+    // target 60 frames per second.
+
+    let canvas_resolution = window_info.canvas_resolution;
+
+    let render_scale_rc = RefCell::new(RenderScale {
+        target_frame_seconds: Some(1.0 / 60.0),
+        filter: UpscaleFilter::Sharpen { sharpness: 0.5 },
+        ..RenderScale::new(0.5)
+    });
+
+    let pipeline_resolution = render_scale_rc
+        .borrow()
+        .get_scaled_resolution(canvas_resolution);
+
     // Pipeline framebuffer
 
-    let mut framebuffer = Framebuffer::new(
-        window_info.canvas_resolution.width,
-        window_info.canvas_resolution.height,
-    );
+    let mut framebuffer = Framebuffer::new(pipeline_resolution.width, pipeline_resolution.height);
 
     framebuffer.complete(0.3, 100.0);
 
@@ -160,6 +185,20 @@ fn main() -> Result<(), String> {
 
     let renderer_rc = RefCell::new(renderer);
 
+    // Canvas-sized buffer that the pipeline's output is upscaled into.
+
+    let canvas_buffer_rc = RefCell::new(Buffer2D::new(
+        canvas_resolution.width,
+        canvas_resolution.height,
+        None,
+    ));
+
+    // Optionally record render stats, exported (as CSV or JSON) on exit.
+
+    let render_stats_path = env::var("RENDER_STATS_PATH").ok();
+
+    let render_stats_recorder_rc = RefCell::new(RenderStatsRecorder::default());
+
     // Render callback
 
     let render_to_window_canvas = |_frame_index: Option<u32>,
@@ -167,7 +206,47 @@ fn main() -> Result<(), String> {
                                    _canvas: &mut [u8]|
      -> Result<(), String> { Ok(()) };
 
-    let (app, _event_watch) = App::new(&mut window_info, &render_to_window_canvas);
+    let (mut app, _event_watch) = App::new(&mut window_info, &render_to_window_canvas);
+
+    // Optionally record this session's input, or replay a previous recording.
+
+    if let Ok(path) = env::var("INPUT_RECORDING_PATH") {
+        app.input_recorder = Some(InputRecorder::new(&path));
+    }
+
+    if let Ok(path) = env::var("INPUT_PLAYBACK_PATH") {
+        app.input_player = Some(InputPlayer::load(&path)?);
+    }
+
+    // Optionally show an overlay of the key bindings, given a path to a font.
+
+    let font_info = env::args().nth(1).map(|filepath| FontInfo {
+        filepath,
+        point_size: 14,
+    });
+
+    let key_binding_overlay_rc = RefCell::new(KeyBindingOverlay::default());
+
+    match font_info.as_ref() {
+        Some(_) => println!(
+            "Press {} to show key bindings.",
+            key_binding_overlay_rc.borrow().toggle_keycode
+        ),
+        None => {
+            println!("Usage: cargo run --example spinning-cube [/path/to/your-font.fon]")
+        }
+    }
+
+    let font_cache_rc = RefCell::new(FontCache::new(app.context.ttf_context));
+
+    let text_cache_rc = RefCell::new(TextCache::new());
+
+    let mut input_map = InputMap::default();
+
+    RenderOptions::bind_keys(&mut input_map);
+    RenderShaderOptions::bind_keys(&mut input_map);
+
+    key_binding_overlay_rc.borrow().bind_keys(&mut input_map);
 
     // App update and render callbacks
 
@@ -200,9 +279,39 @@ fn main() -> Result<(), String> {
 
         let mut renderer = renderer_rc.borrow_mut();
 
-        renderer.options.update(keyboard_state);
+        if render_stats_path.is_some() {
+            // Record stats for the most recently rendered frame.
+
+            render_stats_recorder_rc.borrow_mut().record(
+                app.timing_info.current_frame_index,
+                app.timing_info.seconds_since_last_update,
+                renderer.stats,
+            );
+        }
+
+        renderer
+            .options
+            .update_with_input_map(keyboard_state, &input_map);
+
+        renderer
+            .shader_options
+            .update_with_input_map(keyboard_state, &input_map);
+
+        key_binding_overlay_rc.borrow_mut().update(keyboard_state);
 
-        renderer.shader_options.update(keyboard_state);
+        // Resize the pipeline framebuffer if our render scale changed.
+
+        let mut render_scale = render_scale_rc.borrow_mut();
+
+        if render_scale.update(app.timing_info.seconds_since_last_update) {
+            let resolution = render_scale.get_scaled_resolution(canvas_resolution);
+
+            framebuffer_rc
+                .borrow_mut()
+                .resize(resolution.width, resolution.height, true);
+
+            renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+        }
 
         Ok(())
     };
@@ -241,7 +350,24 @@ fn main() -> Result<(), String> {
             Some(color_buffer_lock) => {
                 let color_buffer = color_buffer_lock.borrow();
 
-                color_buffer.copy_to(canvas);
+                let mut canvas_buffer = canvas_buffer_rc.borrow_mut();
+
+                render_scale_rc
+                    .borrow()
+                    .upscale(&color_buffer, &mut canvas_buffer);
+
+                if let Some(font_info) = font_info.as_ref() {
+                    key_binding_overlay_rc.borrow().render(
+                        &mut canvas_buffer,
+                        &mut font_cache_rc.borrow_mut(),
+                        &mut text_cache_rc.borrow_mut(),
+                        font_info,
+                        (12, 12),
+                        &input_map,
+                    )?;
+                }
+
+                canvas_buffer.copy_to(canvas);
 
                 Ok(())
             }
@@ -251,5 +377,15 @@ fn main() -> Result<(), String> {
 
     app.run(&mut update, &render)?;
 
+    if let Some(path) = render_stats_path {
+        let format = if path.ends_with(".json") {
+            RenderStatsFormat::Json
+        } else {
+            RenderStatsFormat::Csv
+        };
+
+        render_stats_recorder_rc.borrow().export(&path, format)?;
+    }
+
     Ok(())
 }
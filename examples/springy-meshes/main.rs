@@ -73,7 +73,11 @@ fn main() -> Result<(), String> {
 
         framebuffer.clear(None);
 
-        draw_wind_velocity(&simulation.wind, &mut framebuffer, &framebuffer_center);
+        draw_wind_velocity(
+            &simulation.environment.wind,
+            &mut framebuffer,
+            &framebuffer_center,
+        );
 
         for mesh in &simulation.meshes {
             mesh.render(&mut framebuffer, &framebuffer_center);
@@ -112,7 +116,7 @@ fn main() -> Result<(), String> {
             &framebuffer_center,
         );
 
-        simulation.wind = cursor_world_space * 3.0;
+        simulation.environment.wind = cursor_world_space * 3.0;
 
         simulation.tick(uptime_seconds, h);
 
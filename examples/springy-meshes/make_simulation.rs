@@ -1,29 +1,14 @@
-use cairo::{
-    physics::simulation::{
-        physical_constants::EARTH_GRAVITY, state_vector::StateVector, units::Newtons,
-    },
-    vec::vec3::Vec3,
-};
+use cairo::vec::vec3::Vec3;
 
 use crate::{
-    simulation::{PointForce, Simulation},
-    springy_mesh::SpringyMesh,
+    simulation::Simulation, springy_mesh::SpringyMesh,
     static_line_segment_collider::StaticLineSegmentCollider,
 };
 
-static GRAVITY: PointForce =
-    |_state: &StateVector, _i: usize, _current_time: f32| -> (Newtons, Option<Vec3>) {
-        let newtons = Vec3 {
-            x: 0.0,
-            y: -EARTH_GRAVITY,
-            z: 0.0,
-        };
-
-        (newtons, None)
-    };
-
 pub fn make_simulation<'a>() -> Simulation<'a> {
-    let forces = vec![&GRAVITY];
+    // Gravity is applied through the simulation's (default) environment.
+
+    let forces = vec![];
 
     // let mesh = {
     //     static POINT_SPACING_METERS: f32 = 3.0;
@@ -102,7 +87,7 @@ pub fn make_simulation<'a>() -> Simulation<'a> {
 
     Simulation {
         forces,
-        wind: Default::default(),
+        environment: Default::default(),
         static_colliders: vec![StaticLineSegmentCollider::new(
             Vec3 {
                 x: -ground_plane_width_over_2,
@@ -1,5 +1,6 @@
 use cairo::{
     physics::simulation::{
+        environment::PhysicsEnvironment,
         force::Force,
         state_vector::{FromStateVector, StateVector, ToStateVector},
     },
@@ -15,7 +16,7 @@ pub type PointForce = Force<StateVector>;
 
 pub struct Simulation<'a> {
     pub forces: Vec<&'a PointForce>,
-    pub wind: Vec3,
+    pub environment: PhysicsEnvironment,
     pub static_colliders: Vec<StaticLineSegmentCollider>,
     pub meshes: Vec<SpringyMesh>,
 }
@@ -116,7 +117,9 @@ impl<'a> Simulation<'a> {
 
         // For each point, compute net environmental force acting on it.
         for i in 0..n {
-            let mut net_force_acceleration: Vec3 = Default::default();
+            let mut net_force_acceleration: Vec3 = self
+                .environment
+                .get_acceleration(&current_state.data[i + n]);
 
             for force in &self.forces {
                 let (newtons, _contact_point) = force(current_state, i, current_time);
@@ -136,7 +139,7 @@ impl<'a> Simulation<'a> {
                     &mut derivative,
                     mesh.state_index_offset,
                     n,
-                    &self.wind,
+                    &self.environment.wind,
                 );
             }
 
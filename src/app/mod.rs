@@ -1,30 +1,39 @@
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use sdl2::{
     event::{EventWatch, WindowEvent},
-    keyboard::Keycode,
+    keyboard::{Keycode, Mod},
     mouse::MouseButton,
+    rect::Rect,
     render::Canvas,
     video::{FullscreenType, Window},
     {event::Event, render::Texture},
 };
 
 use crate::{
+    buffer::Buffer2D,
     device::{
+        cpu::CpuCapabilities,
         game_controller::{GameController, GameControllerState},
         keyboard::KeyboardState,
         mouse::{MouseDragEvent, MouseEvent, MouseEventKind, MouseState, MouseWheelEvent},
+        recording::{InputPlayer, InputRecorder},
     },
     stats::CycleCounters,
+    thread_pool::ThreadPool,
     time::TimingInfo,
 };
 
 use context::{make_application_context, make_canvas_texture, ApplicationContext};
 use profile::AppCycleCounter;
 use resolution::{Resolution, DEFAULT_WINDOW_RESOLUTION};
-use window::AppWindowingMode;
+use window::{
+    secondary::{AppSecondaryWindow, AppWindowRegistry},
+    AppPresentMode, AppWindowingMode,
+};
 
 mod profile;
 
@@ -42,6 +51,10 @@ pub struct AppWindowInfo {
     pub relative_mouse_mode: bool,
     pub vertical_sync: bool,
     pub resizable: bool,
+    pub present_mode: AppPresentMode,
+    // Size of the app's shared thread pool; defaults to the number of
+    // available cores.
+    pub thread_count: Option<usize>,
 }
 
 impl Default for AppWindowInfo {
@@ -55,6 +68,8 @@ impl Default for AppWindowInfo {
             relative_mouse_mode: false,
             vertical_sync: false,
             resizable: false,
+            present_mode: Default::default(),
+            thread_count: None,
         }
     }
 }
@@ -64,8 +79,12 @@ pub struct App {
     pub is_resizing_self: Rc<RefCell<bool>>,
     pub context: ApplicationContext,
     pub canvas_texture: Rc<RefCell<Texture>>,
+    pub windows: Rc<RefCell<AppWindowRegistry>>,
+    pub thread_pool: Arc<ThreadPool>,
+    pub cpu_capabilities: CpuCapabilities,
     pub timing_info: TimingInfo,
-    are_updates_paused: bool,
+    pub input_recorder: Option<InputRecorder>,
+    pub input_player: Option<InputPlayer>,
     #[cfg(feature = "debug_cycle_counts")]
     pub cycle_counters: CycleCounters,
 }
@@ -95,6 +114,14 @@ impl App {
 
         let resizable = window_info.resizable;
 
+        let cpu_capabilities = *CpuCapabilities::get();
+
+        let thread_pool = Arc::new(ThreadPool::new(
+            window_info
+                .thread_count
+                .unwrap_or(cpu_capabilities.logical_cores),
+        ));
+
         let window_info_rc = Rc::new(RefCell::new(window_info));
         let window_info_rc_clone = window_info_rc.clone();
 
@@ -141,6 +168,7 @@ impl App {
                         render_and_present(
                             &mut canvas_window,
                             &mut canvas_texture,
+                            window_info.present_mode,
                             None,
                             None,
                             Some(new_resolution),
@@ -159,9 +187,13 @@ impl App {
             window_info: window_info_rc,
             context,
             canvas_texture: canvas_texture_rc,
+            windows: Default::default(),
+            thread_pool,
+            cpu_capabilities,
             is_resizing_self: is_resizing_self_rc,
             timing_info,
-            are_updates_paused: false,
+            input_recorder: None,
+            input_player: None,
             #[cfg(feature = "debug_cycle_counts")]
             cycle_counters: Default::default(),
         };
@@ -170,15 +202,20 @@ impl App {
     }
 
     pub fn pause_updates(&mut self) {
-        self.are_updates_paused = true;
+        self.timing_info.pause();
     }
 
     pub fn resume_updates(&mut self) {
-        self.are_updates_paused = false;
+        self.timing_info.resume();
     }
 
     pub fn toggle_updates(&mut self) {
-        self.are_updates_paused = !self.are_updates_paused;
+        self.timing_info.toggle_paused();
+    }
+
+    // Advances a single update while paused.
+    pub fn step_updates(&mut self) {
+        self.timing_info.step();
     }
 
     pub fn set_windowing_mode(&mut self, windowing_mode: AppWindowingMode) -> Result<(), String> {
@@ -213,6 +250,44 @@ impl App {
         Ok(())
     }
 
+    // Opens an additional OS window, with its own canvas and render callback;
+    // returns the new window's ID.
+    pub fn create_window<F>(&mut self, info: AppWindowInfo, render: F) -> Result<u32, String>
+    where
+        F: FnMut(&[Event], &mut Buffer2D) -> Result<(), String> + 'static,
+    {
+        let video_subsystem = self.context.sdl_context.video()?;
+
+        let mut window_builder = video_subsystem.window(
+            &info.title,
+            info.window_resolution.width,
+            info.window_resolution.height,
+        );
+
+        window_builder.position_centered();
+
+        if info.resizable {
+            window_builder.resizable();
+        }
+
+        let window = window_builder.build().map_err(|err| err.to_string())?;
+
+        let id = window.id();
+
+        let canvas = window
+            .into_canvas()
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        let canvas_texture =
+            make_canvas_texture(info.canvas_resolution, &canvas.texture_creator(), None)?;
+
+        let window =
+            AppSecondaryWindow::new(id, info, Box::new(render)).with_canvas(canvas, canvas_texture);
+
+        self.windows.borrow_mut().register(window)
+    }
+
     pub fn resize_window(&mut self, resolution: Resolution) -> Result<(), String> {
         let mut canvas = self.context.rendering_context.canvas.borrow_mut();
         let mut window_info = self.window_info.borrow_mut();
@@ -237,6 +312,29 @@ impl App {
     }
 
     pub fn run<U, R>(mut self, update: &mut U, render: &R) -> Result<(), String>
+    where
+        U: FnMut(
+            &mut Self,
+            &mut KeyboardState,
+            &mut MouseState,
+            &mut GameControllerState,
+        ) -> Result<(), String>,
+        R: Fn(Option<u32>, Option<Resolution>, &mut [u8]) -> Result<(), String>,
+    {
+        let result = self.run_main_loop(update, render);
+
+        // Saves the recording however the main loop exits, so that a session
+        // that ended in an error can still be replayed.
+
+        let saved = match self.input_recorder.as_ref() {
+            Some(recorder) => recorder.save(),
+            None => Ok(()),
+        };
+
+        result.and(saved)
+    }
+
+    fn run_main_loop<U, R>(&mut self, update: &mut U, render: &R) -> Result<(), String>
     where
         U: FnMut(
             &mut Self,
@@ -269,6 +367,11 @@ impl App {
 
         let mut last_update_tick = timer_subsystem.performance_counter();
 
+        // Once a secondary window exists, SDL no longer sends `Quit` when the
+        // main window is closed, so we watch for its `Close` event ourselves.
+
+        let main_window_id = self.context.rendering_context.canvas.borrow().window().id();
+
         // Main event loop
 
         'main: loop {
@@ -291,8 +394,6 @@ impl App {
 
             self.timing_info.milliseconds_slept = seconds_slept * 1000.0;
 
-            let mut should_update_step_forward = false;
-
             // #[cfg(feature = "print_timing_info")]
             // println!(
             //     "Slept for {} ticks, {}s, {}ms!",
@@ -322,6 +423,12 @@ impl App {
             }
 
             for event in events {
+                // Routes events targeting one of our secondary windows.
+
+                if self.windows.borrow_mut().route_event(&event) {
+                    continue;
+                }
+
                 match event {
                     Event::Quit { .. } => break 'main,
 
@@ -363,7 +470,7 @@ impl App {
 
                     Event::Window {
                         timestamp: _timestamp,
-                        window_id: _window_id,
+                        window_id,
                         win_event,
                     } => match &win_event {
                         WindowEvent::None => {
@@ -413,7 +520,11 @@ impl App {
                             // println!("(Window {}) {:?}", window_id, &win_event)
                         }
                         WindowEvent::Close => {
-                            // println!("(Window {}) {:?}", window_id, &win_event)
+                            if window_id == main_window_id {
+                                break 'main;
+                            }
+
+                            self.windows.borrow_mut().close(window_id);
                         }
                         _ => (),
                     },
@@ -436,8 +547,13 @@ impl App {
                         ..
                     } => match keycode {
                         Keycode::Escape => break 'main,
+                        Keycode::F6 => {
+                            self.timing_info.cycle_time_scale();
+
+                            println!("Time scale: {}", self.timing_info.time_scale);
+                        }
                         Keycode::F8 => self.toggle_updates(),
-                        Keycode::F9 => should_update_step_forward = true,
+                        Keycode::F9 => self.step_updates(),
                         Keycode::F11 => {
                             let windowing_mode = self.window_info.borrow().windowing_mode;
 
@@ -492,14 +608,31 @@ impl App {
 
             let sdl_keyboard_util = sdl_context.keyboard();
 
-            let modifiers = sdl_keyboard_util.mod_state();
+            // Secondary windows are sent their own (routed) input events, so
+            // the main window's keyboard and mouse state ignore any input
+            // while a secondary window holds the focus.
+
+            let is_focused = |focused_window_id: Option<u32>| {
+                focused_window_id.is_none_or(|id| id == main_window_id)
+            };
 
-            let sdl_keyboard_state = event_pump.keyboard_state();
+            let has_keyboard_focus = is_focused(sdl_keyboard_util.focused_window_id());
 
-            let pressed_keycodes = sdl_keyboard_state
-                .pressed_scancodes()
-                .filter_map(Keycode::from_scancode)
-                .collect();
+            let has_mouse_focus = is_focused(sdl_context.mouse().focused_window_id());
+
+            let (modifiers, pressed_keycodes) = if has_keyboard_focus {
+                let sdl_keyboard_state = event_pump.keyboard_state();
+
+                (
+                    sdl_keyboard_util.mod_state(),
+                    sdl_keyboard_state
+                        .pressed_scancodes()
+                        .filter_map(Keycode::from_scancode)
+                        .collect(),
+                )
+            } else {
+                (Mod::NOMOD, HashSet::new())
+            };
 
             let prev_pressed_keycodes = prev_keyboard_state.pressed_keycodes;
 
@@ -526,8 +659,11 @@ impl App {
 
             // Read any mouse click signals
 
-            let next_mouse_buttons_down: HashSet<MouseButton> =
-                current_mouse_state.pressed_mouse_buttons().collect();
+            let next_mouse_buttons_down: HashSet<MouseButton> = if has_mouse_focus {
+                current_mouse_state.pressed_mouse_buttons().collect()
+            } else {
+                HashSet::new()
+            };
 
             mouse_state
                 .prev_buttons_down
@@ -587,17 +723,32 @@ impl App {
             mouse_state.prev_position = prev_mouse_position;
             mouse_state.prev_ndc_position = prev_mouse_ndc_position;
 
-            mouse_state.position.0 = current_mouse_state.x();
-            mouse_state.position.1 = current_mouse_state.y();
-
-            {
+            if has_mouse_focus {
                 let window_info = self.window_info.borrow();
 
-                mouse_state.ndc_position.0 =
-                    mouse_state.position.0 as f32 / window_info.window_resolution.width as f32;
+                // Maps the window-space mouse position through the rect that
+                // the canvas is presented into (see `AppPresentMode`).
+
+                let window_position = (current_mouse_state.x(), current_mouse_state.y());
 
-                mouse_state.ndc_position.1 =
-                    mouse_state.position.1 as f32 / window_info.window_resolution.height as f32;
+                let (canvas, window) =
+                    (window_info.canvas_resolution, window_info.window_resolution);
+
+                mouse_state.position =
+                    window_info
+                        .present_mode
+                        .get_canvas_position(window_position, canvas, window);
+
+                mouse_state.ndc_position = window_info.present_mode.get_canvas_ndc_position(
+                    window_position,
+                    canvas,
+                    window,
+                );
+            } else {
+                // Holds the cursor's last position over the main window.
+
+                mouse_state.position = prev_mouse_position;
+                mouse_state.ndc_position = prev_mouse_ndc_position;
             }
 
             // Drag events.
@@ -647,14 +798,43 @@ impl App {
 
             // Update current scene
 
-            if !self.are_updates_paused || should_update_step_forward {
+            {
                 let ticks_since_last_update: u64 =
                     timer_subsystem.performance_counter() - last_update_tick;
 
-                self.timing_info.seconds_since_last_update =
-                    ticks_since_last_update as f32 / ticks_per_second as f32;
+                // While playing back a recording, the recorded input (and
+                // time delta) replaces whatever this frame actually saw.
 
-                self.timing_info.uptime_seconds += self.timing_info.seconds_since_last_update;
+                let replayed_frame = self
+                    .input_player
+                    .as_mut()
+                    .and_then(|player| player.next_frame());
+
+                match replayed_frame {
+                    Some(frame) => {
+                        frame.apply(
+                            &mut keyboard_state,
+                            &mut mouse_state,
+                            &mut game_controller.state,
+                        );
+
+                        self.timing_info
+                            .advance_exactly(frame.seconds_since_last_update);
+                    }
+                    None => {
+                        self.timing_info
+                            .advance(ticks_since_last_update as f32 / ticks_per_second as f32);
+                    }
+                }
+
+                if let Some(recorder) = self.input_recorder.as_mut() {
+                    recorder.record(
+                        &keyboard_state,
+                        &mouse_state,
+                        &game_controller.state,
+                        self.timing_info.seconds_since_last_update,
+                    );
+                }
             }
 
             #[cfg(feature = "debug_cycle_counts")]
@@ -663,7 +843,7 @@ impl App {
                 .start();
 
             update(
-                &mut self,
+                self,
                 &mut keyboard_state,
                 &mut mouse_state,
                 &mut game_controller.state,
@@ -696,9 +876,12 @@ impl App {
 
                 let current_frame_index = self.timing_info.current_frame_index;
 
+                let present_mode = self.window_info.borrow().present_mode;
+
                 render_and_present(
                     &mut canvas_window,
                     &mut canvas_texture,
+                    present_mode,
                     cycle_counters,
                     Some(current_frame_index),
                     None,
@@ -706,6 +889,10 @@ impl App {
                 )?;
             }
 
+            // Render any secondary windows.
+
+            self.windows.borrow_mut().render_and_present()?;
+
             frame_end = timer_subsystem.performance_counter();
 
             // Report framerate
@@ -858,6 +1045,7 @@ pub fn handle_set_window_mode_event(
 fn render_and_present(
     canvas_window: &mut Canvas<Window>,
     canvas_texture: &mut Texture,
+    present_mode: AppPresentMode,
     mut cycle_counters: Option<&mut CycleCounters>,
     current_frame_index: Option<u32>,
     new_resolution: Option<Resolution>,
@@ -898,7 +1086,29 @@ fn render_and_present(
     // Note that Canvas<Window>::copy() will automatically stretch our
     // window canvas to fit the current window size, if `dst` is `None`.
 
-    canvas_window.copy(canvas_texture, None, None)?;
+    match present_mode {
+        AppPresentMode::Stretch => {
+            canvas_window.copy(canvas_texture, None, None)?;
+        }
+        AppPresentMode::Fit | AppPresentMode::Fill => {
+            let texture_attrs = canvas_texture.query();
+
+            let canvas_resolution = Resolution::new((texture_attrs.width, texture_attrs.height));
+
+            let window_resolution = Resolution::new(canvas_window.output_size()?);
+
+            let (x, y, width, height) =
+                present_mode.get_destination_rect(canvas_resolution, window_resolution);
+
+            // Clears any letterbox bars to black.
+
+            canvas_window.set_draw_color(sdl2::pixels::Color::BLACK);
+
+            canvas_window.clear();
+
+            canvas_window.copy(canvas_texture, None, Some(Rect::new(x, y, width, height)))?;
+        }
+    }
 
     canvas_window.present();
 
@@ -1,5 +1,9 @@
 use std::fmt;
 
+use super::resolution::Resolution;
+
+pub mod secondary;
+
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum AppWindowingMode {
     #[default]
@@ -23,3 +27,165 @@ pub static APP_WINDOWING_MODES: [AppWindowingMode; 3] = [
     AppWindowingMode::FullScreen,
     AppWindowingMode::FullScreenWindowed,
 ];
+
+// How the canvas is presented when its aspect ratio differs from the window's.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AppPresentMode {
+    // Stretches the canvas to cover the window.
+    #[default]
+    Stretch = 0,
+    // Scales the canvas to fit inside the window, with black (letterbox) bars.
+    Fit = 1,
+    // Scales the canvas to cover the window, cropping any overflow.
+    Fill = 2,
+}
+
+impl fmt::Display for AppPresentMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppPresentMode::Stretch => write!(f, "Stretch"),
+            AppPresentMode::Fit => write!(f, "Fit"),
+            AppPresentMode::Fill => write!(f, "Fill"),
+        }
+    }
+}
+
+pub static APP_PRESENT_MODES: [AppPresentMode; 3] = [
+    AppPresentMode::Stretch,
+    AppPresentMode::Fit,
+    AppPresentMode::Fill,
+];
+
+impl AppPresentMode {
+    // Returns the window-space rect (x, y, width, height) that the canvas is
+    // copied into; for `Fill`, the rect may extend beyond the window.
+    pub fn get_destination_rect(
+        &self,
+        canvas: Resolution,
+        window: Resolution,
+    ) -> (i32, i32, u32, u32) {
+        let scale_x = window.width as f32 / canvas.width as f32;
+        let scale_y = window.height as f32 / canvas.height as f32;
+
+        let scale = match self {
+            AppPresentMode::Stretch => return (0, 0, window.width, window.height),
+            AppPresentMode::Fit => scale_x.min(scale_y),
+            AppPresentMode::Fill => scale_x.max(scale_y),
+        };
+
+        let width = (canvas.width as f32 * scale).round() as u32;
+        let height = (canvas.height as f32 * scale).round() as u32;
+
+        let x = (window.width as i32 - width as i32) / 2;
+        let y = (window.height as i32 - height as i32) / 2;
+
+        (x, y, width, height)
+    }
+
+    // Maps a window-space position to a normalized (0..1) position over the
+    // canvas, through the same destination rect that the canvas is copied into.
+    pub fn get_canvas_ndc_position(
+        &self,
+        position: (i32, i32),
+        canvas: Resolution,
+        window: Resolution,
+    ) -> (f32, f32) {
+        let (x, y, width, height) = self.get_destination_rect(canvas, window);
+
+        (
+            (position.0 - x) as f32 / width as f32,
+            (position.1 - y) as f32 / height as f32,
+        )
+    }
+
+    // Maps a window-space position to a canvas pixel position. `Stretch`
+    // leaves window-space positions as they are.
+    pub fn get_canvas_position(
+        &self,
+        position: (i32, i32),
+        canvas: Resolution,
+        window: Resolution,
+    ) -> (i32, i32) {
+        if *self == AppPresentMode::Stretch {
+            return position;
+        }
+
+        let ndc = self.get_canvas_ndc_position(position, canvas, window);
+
+        (
+            (ndc.0 * canvas.width as f32).floor() as i32,
+            (ndc.1 * canvas.height as f32).floor() as i32,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::resolution::Resolution;
+
+    use super::AppPresentMode;
+
+    #[test]
+    fn widescreen_canvas_is_letterboxed_in_a_4_3_window() {
+        let canvas = Resolution::new((1920, 1080));
+        let window = Resolution::new((1024, 768));
+
+        // Bars above and below the canvas.
+
+        assert_eq!(
+            AppPresentMode::Fit.get_destination_rect(canvas, window),
+            (0, 96, 1024, 576)
+        );
+
+        // Left and right edges cropped.
+
+        assert_eq!(
+            AppPresentMode::Fill.get_destination_rect(canvas, window),
+            (-170, 0, 1365, 768)
+        );
+
+        assert_eq!(
+            AppPresentMode::Stretch.get_destination_rect(canvas, window),
+            (0, 0, 1024, 768)
+        );
+    }
+
+    #[test]
+    fn window_positions_map_through_the_letterbox() {
+        let canvas = Resolution::new((1920, 1080));
+        let window = Resolution::new((1024, 768));
+
+        // The top-left corner of the letterboxed canvas.
+
+        assert_eq!(
+            AppPresentMode::Fit.get_canvas_position((0, 96), canvas, window),
+            (0, 0)
+        );
+
+        assert_eq!(
+            AppPresentMode::Fit.get_canvas_ndc_position((512, 384), canvas, window),
+            (0.5, 0.5)
+        );
+
+        // Positions over the top bar fall outside of the canvas.
+
+        assert!(
+            AppPresentMode::Fit
+                .get_canvas_position((512, 48), canvas, window)
+                .1
+                < 0
+        );
+
+        // The left edge of the window shows a cropped part of the canvas.
+
+        assert_eq!(
+            AppPresentMode::Fill.get_canvas_position((0, 0), canvas, window),
+            (239, 0)
+        );
+
+        assert_eq!(
+            AppPresentMode::Stretch.get_canvas_position((100, 50), canvas, window),
+            (100, 50)
+        );
+    }
+}
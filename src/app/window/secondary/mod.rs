@@ -0,0 +1,273 @@
+use std::fmt;
+
+use sdl2::{
+    event::{Event, WindowEvent},
+    render::{Canvas, Texture},
+    video::Window,
+};
+
+use crate::{
+    app::{resolution::Resolution, AppWindowInfo},
+    buffer::Buffer2D,
+};
+
+// Renders a secondary window's canvas, given any events routed to the window
+// since its last frame.
+pub type AppSecondaryWindowRenderFn = dyn FnMut(&[Event], &mut Buffer2D) -> Result<(), String>;
+
+// An additional OS window (e.g., a torn-off tool window), with its own canvas
+// and render callback.
+pub struct AppSecondaryWindow {
+    pub id: u32,
+    pub info: AppWindowInfo,
+    pub framebuffer: Buffer2D,
+    pub events: Vec<Event>,
+    pub has_focus: bool,
+    pub should_close: bool,
+    render: Box<AppSecondaryWindowRenderFn>,
+    canvas: Option<(Canvas<Window>, Texture)>,
+}
+
+impl fmt::Debug for AppSecondaryWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppSecondaryWindow")
+            .field("id", &self.id)
+            .field("info", &self.info)
+            .field("has_focus", &self.has_focus)
+            .field("should_close", &self.should_close)
+            .finish()
+    }
+}
+
+impl AppSecondaryWindow {
+    pub fn new(id: u32, info: AppWindowInfo, render: Box<AppSecondaryWindowRenderFn>) -> Self {
+        let framebuffer = Buffer2D::new(
+            info.canvas_resolution.width,
+            info.canvas_resolution.height,
+            None,
+        );
+
+        Self {
+            id,
+            info,
+            framebuffer,
+            events: vec![],
+            has_focus: false,
+            should_close: false,
+            render,
+            canvas: None,
+        }
+    }
+
+    pub fn with_canvas(mut self, canvas: Canvas<Window>, canvas_texture: Texture) -> Self {
+        self.canvas.replace((canvas, canvas_texture));
+
+        self
+    }
+
+    fn handle_window_event(&mut self, win_event: &WindowEvent) {
+        match win_event {
+            WindowEvent::Close => self.should_close = true,
+            WindowEvent::FocusGained => self.has_focus = true,
+            WindowEvent::FocusLost => self.has_focus = false,
+            WindowEvent::SizeChanged(width, height) => {
+                self.info.window_resolution = Resolution {
+                    width: *width as u32,
+                    height: *height as u32,
+                };
+            }
+            _ => (),
+        }
+    }
+
+    pub fn render_and_present(&mut self) -> Result<(), String> {
+        (self.render)(&self.events, &mut self.framebuffer)?;
+
+        self.events.clear();
+
+        if let Some((canvas, canvas_texture)) = self.canvas.as_mut() {
+            let framebuffer = &self.framebuffer;
+
+            canvas_texture.with_lock(None, |bytes, _pitch| {
+                framebuffer.copy_to(bytes);
+            })?;
+
+            canvas.copy(canvas_texture, None, None)?;
+
+            canvas.present();
+        }
+
+        Ok(())
+    }
+}
+
+// Tracks an app's secondary windows, keyed by their (SDL) window IDs.
+#[derive(Default, Debug)]
+pub struct AppWindowRegistry {
+    windows: Vec<AppSecondaryWindow>,
+}
+
+impl AppWindowRegistry {
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    pub fn get_ids(&self) -> Vec<u32> {
+        self.windows.iter().map(|window| window.id).collect()
+    }
+
+    pub fn get(&self, id: u32) -> Option<&AppSecondaryWindow> {
+        self.windows.iter().find(|window| window.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: u32) -> Option<&mut AppSecondaryWindow> {
+        self.windows.iter_mut().find(|window| window.id == id)
+    }
+
+    pub fn register(&mut self, window: AppSecondaryWindow) -> Result<u32, String> {
+        if self.get(window.id).is_some() {
+            return Err(format!(
+                "A secondary window with ID {} is already registered!",
+                window.id
+            ));
+        }
+
+        let id = window.id;
+
+        self.windows.push(window);
+
+        Ok(id)
+    }
+
+    pub fn remove(&mut self, id: u32) -> Option<AppSecondaryWindow> {
+        let index = self.windows.iter().position(|window| window.id == id)?;
+
+        Some(self.windows.remove(index))
+    }
+
+    // Marks a secondary window to be closed (before its next frame); returns
+    // `false` if no such window is registered.
+    pub fn close(&mut self, id: u32) -> bool {
+        match self.get_mut(id) {
+            Some(window) => {
+                window.should_close = true;
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Routes an event to the secondary window it targets (if any); returns
+    // `true` if the event was consumed.
+    pub fn route_event(&mut self, event: &Event) -> bool {
+        let Some(window) = event.get_window_id().and_then(|id| self.get_mut(id)) else {
+            return false;
+        };
+
+        if let Event::Window { win_event, .. } = event {
+            window.handle_window_event(win_event);
+        }
+
+        window.events.push(event.clone());
+
+        true
+    }
+
+    pub fn render_and_present(&mut self) -> Result<(), String> {
+        // Closing a window drops its canvas (and the underlying OS window).
+
+        self.windows.retain(|window| !window.should_close);
+
+        for window in self.windows.iter_mut() {
+            window.render_and_present()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sdl2::event::{Event, WindowEvent};
+
+    use crate::app::{resolution::Resolution, AppWindowInfo};
+
+    use super::{AppSecondaryWindow, AppWindowRegistry};
+
+    #[test]
+    fn secondary_windows_have_distinct_ids_and_framebuffers() {
+        let mut registry = AppWindowRegistry::default();
+
+        let make_window = |id: u32, resolution: Resolution| {
+            let info = AppWindowInfo {
+                title: format!("Tool window {}", id),
+                canvas_resolution: resolution,
+                window_resolution: resolution,
+                ..Default::default()
+            };
+
+            AppSecondaryWindow::new(
+                id,
+                info,
+                Box::new(move |_events, framebuffer| {
+                    framebuffer.clear(Some(id));
+
+                    Ok(())
+                }),
+            )
+        };
+
+        registry
+            .register(make_window(2, Resolution::new((320, 240))))
+            .unwrap();
+
+        registry
+            .register(make_window(3, Resolution::new((160, 120))))
+            .unwrap();
+
+        assert!(registry
+            .register(make_window(3, Resolution::new((160, 120))))
+            .is_err());
+
+        assert_eq!(registry.get_ids(), vec![2, 3]);
+
+        // Each window renders into its own framebuffer.
+
+        registry.render_and_present().unwrap();
+
+        let (a, b) = (registry.get(2).unwrap(), registry.get(3).unwrap());
+
+        assert_eq!((a.framebuffer.width, a.framebuffer.height), (320, 240));
+        assert_eq!((b.framebuffer.width, b.framebuffer.height), (160, 120));
+
+        assert!(a.framebuffer.data.iter().all(|pixel| *pixel == 2));
+        assert!(b.framebuffer.data.iter().all(|pixel| *pixel == 3));
+
+        // Events are routed by window ID.
+
+        let close = |window_id: u32| Event::Window {
+            timestamp: 0,
+            window_id,
+            win_event: WindowEvent::Close,
+        };
+
+        assert!(!registry.route_event(&close(1)));
+        assert!(registry.route_event(&close(3)));
+
+        registry.render_and_present().unwrap();
+
+        assert_eq!(registry.get_ids(), vec![2]);
+
+        assert!(!registry.close(3));
+        assert!(registry.close(2));
+
+        registry.render_and_present().unwrap();
+
+        assert!(registry.is_empty());
+    }
+}
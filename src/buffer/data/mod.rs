@@ -0,0 +1,113 @@
+use std::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+
+use crate::serde::PostDeserialize;
+
+// A large, typed, CPU-side buffer (akin to a shader storage buffer) that render
+// passes and simulations can read from and write to, e.g., light lists or
+// particle data.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct DataBuffer<T: Default + Copy + Debug> {
+    pub data: Vec<T>,
+}
+
+impl<T: Default + Copy + Debug> PostDeserialize for DataBuffer<T> {
+    fn post_deserialize(&mut self) {
+        // Nothing to do.
+    }
+}
+
+impl<T: Default + Copy + Debug> DataBuffer<T> {
+    pub fn new(len: usize) -> Self {
+        Self {
+            data: vec![Default::default(); len],
+        }
+    }
+
+    pub fn from_data(data: Vec<T>) -> Self {
+        Self { data }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.data.get(index)
+    }
+
+    pub fn set(&mut self, index: usize, value: T) -> Result<(), String> {
+        match self.data.get_mut(index) {
+            Some(element) => {
+                *element = value;
+
+                Ok(())
+            }
+            None => Err(format!(
+                "Called DataBuffer::set() with index {} on a buffer of length {}!",
+                index,
+                self.data.len()
+            )),
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.data.push(value);
+    }
+
+    pub fn resize(&mut self, len: usize) {
+        self.data.resize(len, Default::default());
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{scene::resources::SceneResources, vec::vec3::Vec3};
+
+    use super::DataBuffer;
+
+    #[test]
+    fn written_values_are_readable_through_the_arena_handle() {
+        let resources = SceneResources::default();
+
+        let handle = resources.data_vec3.borrow_mut().insert(DataBuffer::new(16));
+
+        {
+            let mut arena = resources.data_vec3.borrow_mut();
+
+            let buffer = &mut arena.get_mut(&handle).unwrap().item;
+
+            buffer.set(3, Vec3::ones()).unwrap();
+
+            buffer.as_mut_slice()[15].y = 2.0;
+
+            assert!(buffer.set(16, Vec3::ones()).is_err());
+        }
+
+        let arena = resources.data_vec3.borrow();
+
+        let buffer = &arena.get(&handle).unwrap().item;
+
+        assert_eq!(buffer.len(), 16);
+        assert_eq!(*buffer.get(3).unwrap(), Vec3::ones());
+        assert_eq!(buffer.get(15).unwrap().y, 2.0);
+        assert_eq!(*buffer.get(0).unwrap(), Vec3::default());
+    }
+}
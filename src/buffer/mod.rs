@@ -14,6 +14,7 @@ use crate::{
     vec::{vec2::Vec2, vec3::Vec3},
 };
 
+pub mod data;
 pub mod framebuffer;
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -8,6 +8,7 @@ use crate::{
 };
 
 pub mod blend;
+pub mod space;
 
 #[derive(Default, Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct Color {
@@ -0,0 +1,92 @@
+use crate::buffer::Buffer2D;
+
+use super::Color;
+
+// Describes how the values in a color buffer are encoded.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorSpace {
+    // Gamma-encoded values, ready for display.
+    #[default]
+    Srgb,
+    // Linear values (e.g., renderer output without tone mapping), which must
+    // be encoded before they're displayed.
+    Linear,
+}
+
+impl ColorSpace {
+    // Converts an sRGB-encoded color (e.g., a UI color) into this color space;
+    // the result isn't quantized, so that dark values survive a round trip.
+    pub fn from_srgb(&self, color: Color) -> Color {
+        match self {
+            ColorSpace::Srgb => color,
+            ColorSpace::Linear => map_rgb(color, |c| c * c),
+        }
+    }
+
+    // Converts a color in this color space into sRGB, quantizing (once) to
+    // whole 8-bit values.
+    pub fn to_srgb(&self, color: Color) -> Color {
+        match self {
+            ColorSpace::Srgb => color,
+            ColorSpace::Linear => map_rgb(color, |c| c.sqrt()).quantized(),
+        }
+    }
+
+    // Encodes a color buffer in this color space for display. This should
+    // happen once, before any (sRGB) UI is drawn over the buffer.
+    pub fn present(&self, buffer: &mut Buffer2D) {
+        if *self == ColorSpace::Srgb {
+            return;
+        }
+
+        for pixel in buffer.iter_mut() {
+            *pixel = self.to_srgb(Color::from_u32(*pixel)).to_u32();
+        }
+    }
+}
+
+// Note that we approximate the sRGB transfer function with a gamma of 2.0 (see
+// `Vec3::srgb_to_linear()`).
+fn map_rgb<F>(color: Color, f: F) -> Color
+where
+    F: Fn(f32) -> f32,
+{
+    Color {
+        r: f(color.r / 255.0) * 255.0,
+        g: f(color.g / 255.0) * 255.0,
+        b: f(color.b / 255.0) * 255.0,
+        a: color.a,
+    }
+}
+
+impl Color {
+    // Rounds to whole 8-bit values (see `Color::to_u32()`, which truncates).
+    fn quantized(self) -> Self {
+        Self {
+            r: self.r.round(),
+            g: self.g.round(),
+            b: self.b.round(),
+            a: self.a,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color::Color;
+
+    use super::ColorSpace;
+
+    #[test]
+    fn dark_srgb_values_round_trip_through_linear() {
+        for value in 1..=16 {
+            let srgb = Color::rgb(value, value, value);
+
+            let linear = ColorSpace::Linear.from_srgb(srgb);
+
+            let round_trip = ColorSpace::Linear.to_srgb(linear);
+
+            assert_eq!(round_trip.to_u32(), srgb.to_u32(), "sRGB value {value}");
+        }
+    }
+}
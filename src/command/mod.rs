@@ -0,0 +1,493 @@
+use std::{
+    any::type_name,
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    str::FromStr,
+};
+
+// Positional arguments of a parsed command.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CommandArgs(pub Vec<String>);
+
+impl CommandArgs {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Result<&str, String> {
+        self.0
+            .get(index)
+            .map(|arg| arg.as_str())
+            .ok_or_else(|| format!("Missing argument {}.", index))
+    }
+
+    pub fn parse<T: FromStr>(&self, index: usize) -> Result<T, String> {
+        let arg = self.get(index)?;
+
+        arg.parse::<T>().map_err(|_err| {
+            format!(
+                "Failed to parse a `{}` from argument string '{}'.",
+                type_name::<T>(),
+                arg
+            )
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ParsedCommand {
+    pub name: String,
+    pub args: CommandArgs,
+}
+
+impl fmt::Display for ParsedCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        for arg in &self.args.0 {
+            write!(f, " {}", arg)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Describes one positional argument, for usage strings and autocompletion.
+#[derive(Default, Debug, Clone)]
+pub struct CommandArgument {
+    pub name: String,
+    pub completions: Vec<String>,
+}
+
+impl CommandArgument {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            completions: vec![],
+        }
+    }
+
+    pub fn with_completions(name: &str, completions: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            completions: completions.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+}
+
+pub type CommandHandler<C> = Box<dyn FnMut(&mut C, &CommandArgs) -> Result<(), String>>;
+
+struct CommandDefinition<C> {
+    description: String,
+    arguments: Vec<CommandArgument>,
+    handler: CommandHandler<C>,
+}
+
+// Maps command names to handlers that operate on some context `C`; commands
+// are typed in as a name, followed by whitespace-separated arguments (which
+// may be quoted).
+pub struct CommandRegistry<C> {
+    commands: BTreeMap<String, CommandDefinition<C>>,
+}
+
+impl<C> Default for CommandRegistry<C> {
+    fn default() -> Self {
+        Self {
+            commands: Default::default(),
+        }
+    }
+}
+
+impl<C> fmt::Debug for CommandRegistry<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandRegistry")
+            .field("commands", &self.commands.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<C> CommandRegistry<C> {
+    pub fn register(
+        &mut self,
+        name: &str,
+        description: &str,
+        arguments: Vec<CommandArgument>,
+        handler: CommandHandler<C>,
+    ) {
+        self.commands.insert(
+            name.to_string(),
+            CommandDefinition {
+                description: description.to_string(),
+                arguments,
+                handler,
+            },
+        );
+    }
+
+    pub fn get_names(&self) -> impl Iterator<Item = &String> {
+        self.commands.keys()
+    }
+
+    pub fn get_usage(&self, name: &str) -> Option<String> {
+        self.commands.get(name).map(|command| {
+            let mut usage = name.to_string();
+
+            for argument in &command.arguments {
+                usage += &format!(" <{}>", argument.name);
+            }
+
+            if !command.description.is_empty() {
+                usage += &format!(" - {}", command.description);
+            }
+
+            usage
+        })
+    }
+
+    pub fn parse(&self, input: &str) -> Result<ParsedCommand, String> {
+        let mut tokens = tokenize(input)?.into_iter();
+
+        let name = tokens.next().ok_or_else(|| "Empty command.".to_string())?;
+
+        let command = self
+            .commands
+            .get(&name)
+            .ok_or_else(|| format!("Unknown command `{}`.", name))?;
+
+        let args = CommandArgs(tokens.collect());
+
+        // Handlers may accept trailing arguments beyond those described.
+
+        if args.len() < command.arguments.len() {
+            return Err(format!(
+                "Usage: {}",
+                self.get_usage(&name).unwrap_or_default()
+            ));
+        }
+
+        Ok(ParsedCommand { name, args })
+    }
+
+    pub fn call(&mut self, context: &mut C, command: &ParsedCommand) -> Result<(), String> {
+        let definition = self
+            .commands
+            .get_mut(&command.name)
+            .ok_or_else(|| format!("Unknown command `{}`.", command.name))?;
+
+        (definition.handler)(context, &command.args)
+    }
+
+    pub fn execute(&mut self, context: &mut C, input: &str) -> Result<ParsedCommand, String> {
+        let command = self.parse(input)?;
+
+        self.call(context, &command)?;
+
+        Ok(command)
+    }
+
+    // Returns the possible completions of a partially-typed command line.
+    pub fn complete(&self, input: &str) -> Vec<String> {
+        let tokens = match tokenize(input) {
+            Ok(tokens) => tokens,
+            Err(_) => return vec![],
+        };
+
+        // Whether the last token is still being typed.
+
+        let is_partial = !input.is_empty() && !input.ends_with(char::is_whitespace);
+
+        let (complete_tokens, prefix) = match (is_partial, tokens.split_last()) {
+            (true, Some((last, rest))) => (rest, last.as_str()),
+            _ => (tokens.as_slice(), ""),
+        };
+
+        let candidates: Vec<&String> = match complete_tokens.split_first() {
+            None => self.commands.keys().collect(),
+            Some((name, args)) => match self.commands.get(name) {
+                Some(command) => match command.arguments.get(args.len()) {
+                    Some(argument) => argument.completions.iter().collect(),
+                    None => vec![],
+                },
+                None => vec![],
+            },
+        };
+
+        candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(prefix))
+            .map(|candidate| {
+                let mut line = complete_tokens.to_vec();
+
+                line.push(candidate.clone());
+
+                line.join(" ")
+            })
+            .collect()
+    }
+
+    // Completes a partially-typed command line as far as is unambiguous (e.g.,
+    // when Tab is pressed); returns `None` if there's nothing to add.
+    pub fn complete_line(&self, input: &str) -> Option<String> {
+        let completions = self.complete(input);
+
+        let completed = match completions.as_slice() {
+            [] => return None,
+            [only] => format!("{} ", only),
+            [first, rest @ ..] => {
+                let mut prefix = first.as_str();
+
+                for completion in rest {
+                    let common_len = prefix
+                        .char_indices()
+                        .zip(completion.chars())
+                        .take_while(|((_, a), b)| a == b)
+                        .last()
+                        .map(|((index, c), _)| index + c.len_utf8())
+                        .unwrap_or(0);
+
+                    prefix = &prefix[..common_len];
+                }
+
+                prefix.to_string()
+            }
+        };
+
+        if completed.len() > input.len() {
+            Some(completed)
+        } else {
+            None
+        }
+    }
+}
+
+// Previously-entered command lines, recalled (newest first) with the up and
+// down arrow keys.
+#[derive(Default, Debug, Clone)]
+pub struct CommandHistory {
+    capacity: usize,
+    lines: VecDeque<String>,
+    // Index (from the newest line) of the line currently recalled, if any.
+    cursor: Option<usize>,
+}
+
+impl CommandHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            lines: VecDeque::with_capacity(capacity),
+            cursor: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn push(&mut self, line: &str) {
+        self.cursor = None;
+
+        if line.trim().is_empty() || self.lines.back().is_some_and(|last| last == line) {
+            return;
+        }
+
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+
+        if self.capacity > 0 {
+            self.lines.push_back(line.to_string());
+        }
+    }
+
+    // Steps back to an older line; stops at the oldest.
+    pub fn get_previous(&mut self) -> Option<&str> {
+        if self.lines.is_empty() {
+            return None;
+        }
+
+        let cursor = match self.cursor {
+            Some(cursor) => (cursor + 1).min(self.lines.len() - 1),
+            None => 0,
+        };
+
+        self.cursor = Some(cursor);
+
+        self.lines
+            .get(self.lines.len() - 1 - cursor)
+            .map(|line| line.as_str())
+    }
+
+    // Steps forward to a newer line; returns `None` after the newest line.
+    pub fn get_next(&mut self) -> Option<&str> {
+        match self.cursor {
+            Some(0) | None => {
+                self.cursor = None;
+
+                None
+            }
+            Some(cursor) => {
+                self.cursor = Some(cursor - 1);
+
+                self.lines
+                    .get(self.lines.len() - cursor)
+                    .map(|line| line.as_str())
+            }
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<String>, String> {
+    // Splits on whitespace, except within double quotes.
+
+    let mut tokens = vec![];
+
+    let mut current: Option<String> = None;
+
+    let mut is_quoted = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                is_quoted = !is_quoted;
+
+                current.get_or_insert_with(String::new);
+            }
+            c if c.is_whitespace() && !is_quoted => {
+                if let Some(token) = current.take() {
+                    tokens.push(token);
+                }
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    if is_quoted {
+        return Err("Unterminated quote in command.".to_string());
+    }
+
+    if let Some(token) = current.take() {
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CommandArgument, CommandHistory, CommandRegistry};
+
+    #[derive(Default)]
+    struct Settings {
+        vsync: bool,
+        gamma: f32,
+    }
+
+    fn make_registry() -> CommandRegistry<Settings> {
+        let mut registry = CommandRegistry::default();
+
+        registry.register(
+            "set",
+            "Changes a setting.",
+            vec![
+                CommandArgument::with_completions("key", &["vsync", "gamma"]),
+                CommandArgument::new("value"),
+            ],
+            Box::new(|settings: &mut Settings, args| match args.get(0)? {
+                "vsync" => {
+                    settings.vsync = args.parse::<bool>(1)?;
+
+                    Ok(())
+                }
+                "gamma" => {
+                    settings.gamma = args.parse::<f32>(1)?;
+
+                    Ok(())
+                }
+                key => Err(format!("Unknown setting `{}`.", key)),
+            }),
+        );
+
+        registry
+    }
+
+    #[test]
+    fn set_command_parses_typed_arguments() {
+        let mut registry = make_registry();
+
+        let mut settings = Settings::default();
+
+        let command = registry.execute(&mut settings, "set vsync true").unwrap();
+
+        assert_eq!(command.name, "set");
+        assert_eq!(command.args.0, vec!["vsync", "true"]);
+
+        assert!(settings.vsync);
+
+        registry.execute(&mut settings, "set gamma 2.2").unwrap();
+
+        assert_eq!(settings.gamma, 2.2);
+
+        // Invalid values, missing arguments, and unknown commands are errors.
+
+        assert!(registry.execute(&mut settings, "set vsync maybe").is_err());
+        assert!(registry.execute(&mut settings, "set vsync").is_err());
+        assert!(registry.execute(&mut settings, "reset vsync").is_err());
+
+        assert!(settings.vsync);
+    }
+
+    #[test]
+    fn partial_input_completes_names_and_arguments() {
+        let registry = make_registry();
+
+        assert_eq!(registry.complete("se"), vec!["set"]);
+        assert_eq!(registry.complete("set "), vec!["set vsync", "set gamma"]);
+        assert_eq!(registry.complete("set vs"), vec!["set vsync"]);
+        assert!(registry.complete("set vsync ").is_empty());
+    }
+
+    #[test]
+    fn tab_completion_matches_command_names_and_history_recalls_commands() {
+        let mut registry = make_registry();
+
+        for name in ["save", "help"] {
+            registry.register(name, "", vec![], Box::new(|_settings, _args| Ok(())));
+        }
+
+        assert_eq!(registry.complete("s"), vec!["save", "set"]);
+
+        // Ambiguous prefixes are completed as far as possible.
+
+        assert_eq!(registry.complete_line("s"), None);
+        assert_eq!(registry.complete_line("h"), Some("help ".to_string()));
+        assert_eq!(
+            registry.complete_line("set v"),
+            Some("set vsync ".to_string())
+        );
+
+        let mut history = CommandHistory::new(8);
+
+        history.push("set vsync true");
+        history.push("set gamma 2.2");
+
+        assert_eq!(history.get_previous(), Some("set gamma 2.2"));
+        assert_eq!(history.get_previous(), Some("set vsync true"));
+        assert_eq!(history.get_previous(), Some("set vsync true"));
+        assert_eq!(history.get_next(), Some("set gamma 2.2"));
+        assert_eq!(history.get_next(), None);
+
+        // Entering a command resets recall to the newest line.
+
+        history.push("help");
+
+        assert_eq!(history.get_previous(), Some("help"));
+    }
+}
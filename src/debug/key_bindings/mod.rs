@@ -0,0 +1,165 @@
+use sdl2::keyboard::Keycode;
+
+use crate::{
+    buffer::Buffer2D,
+    color,
+    device::keyboard::{
+        input_map::{InputMap, KeyBinding},
+        KeyboardState,
+    },
+    font::{cache::FontCache, FontInfo},
+    graphics::{
+        text::{cache::TextCache, TextOperation},
+        Graphics,
+    },
+};
+
+// An on-screen cheat-sheet listing the bindings in an `InputMap`.
+#[derive(Debug, Copy, Clone)]
+pub struct KeyBindingOverlay {
+    pub is_visible: bool,
+    pub toggle_keycode: Keycode,
+}
+
+impl Default for KeyBindingOverlay {
+    fn default() -> Self {
+        Self {
+            is_visible: false,
+            toggle_keycode: Keycode::F1,
+        }
+    }
+}
+
+impl KeyBindingOverlay {
+    pub fn bind_keys(&self, input_map: &mut InputMap) {
+        input_map.bind("Help", "Toggle key bindings", self.toggle_keycode);
+    }
+
+    pub fn update(&mut self, keyboard_state: &KeyboardState) {
+        if keyboard_state
+            .newly_pressed_keycodes
+            .contains(&self.toggle_keycode)
+        {
+            self.is_visible = !self.is_visible;
+        }
+    }
+
+    // Returns a heading for each category, followed by its bindings.
+    pub fn get_lines(input_map: &InputMap) -> Vec<String> {
+        let mut lines = vec![];
+
+        for (category, bindings) in input_map.get_bindings_by_category() {
+            lines.push(category.to_string());
+
+            for binding in bindings {
+                lines.push(format_binding(binding));
+            }
+        }
+
+        lines
+    }
+
+    pub fn render(
+        &self,
+        target: &mut Buffer2D,
+        font_cache: &mut FontCache,
+        text_cache: &mut TextCache,
+        font_info: &FontInfo,
+        position: (u32, u32),
+        input_map: &InputMap,
+    ) -> Result<(), String> {
+        if !self.is_visible {
+            return Ok(());
+        }
+
+        let line_height = (font_info.point_size as f32 * 1.5) as u32;
+
+        let mut y = position.1;
+
+        for (category, bindings) in input_map.get_bindings_by_category() {
+            let heading = category.to_string();
+
+            Graphics::text(
+                target,
+                font_cache,
+                Some(text_cache),
+                font_info,
+                &TextOperation {
+                    text: &heading,
+                    x: position.0,
+                    y,
+                    color: color::YELLOW,
+                },
+            )?;
+
+            y += line_height;
+
+            for binding in bindings {
+                let line = format_binding(binding);
+
+                Graphics::text(
+                    target,
+                    font_cache,
+                    Some(text_cache),
+                    font_info,
+                    &TextOperation {
+                        text: &line,
+                        x: position.0,
+                        y,
+                        color: color::WHITE,
+                    },
+                )?;
+
+                y += line_height;
+            }
+
+            // Spacing between categories.
+
+            y += line_height / 2;
+        }
+
+        Ok(())
+    }
+}
+
+fn format_binding(binding: &KeyBinding) -> String {
+    format!("  {}: {}", binding.keycode.name(), binding.action)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        device::keyboard::input_map::InputMap,
+        render::options::{shader::RenderShaderOptions, RenderOptions},
+    };
+
+    use super::KeyBindingOverlay;
+
+    #[test]
+    fn overlay_lists_every_bound_action() {
+        let mut input_map = InputMap::default();
+
+        RenderOptions::bind_keys(&mut input_map);
+        RenderShaderOptions::bind_keys(&mut input_map);
+
+        KeyBindingOverlay::default().bind_keys(&mut input_map);
+
+        let lines = KeyBindingOverlay::get_lines(&input_map);
+
+        for binding in input_map.iter() {
+            assert!(lines
+                .iter()
+                .any(|line| line.ends_with(&format!(": {}", binding.action))));
+        }
+
+        // Each category's heading precedes its bindings.
+
+        let categories = input_map.get_bindings_by_category();
+
+        assert_eq!(categories.len(), 3);
+
+        assert_eq!(lines.len(), input_map.len() + categories.len());
+
+        assert_eq!(lines[0], categories[0].0);
+    }
+}
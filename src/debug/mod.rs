@@ -1 +1,3 @@
+pub mod key_bindings;
 pub mod message;
+pub mod ruler;
@@ -0,0 +1,295 @@
+use sdl2::{keyboard::Keycode, mouse::MouseButton};
+
+use crate::{
+    buffer::Buffer2D,
+    color::{self, Color},
+    device::{
+        keyboard::KeyboardState,
+        mouse::{MouseEventKind, MouseState},
+    },
+    font::{cache::FontCache, FontInfo},
+    graphics::{text::TextOperation, Graphics},
+    render::Renderer,
+    scene::{camera::Camera, graph::SceneGraph, resources::SceneResources},
+    shader::context::ShaderContext,
+    vec::vec3::Vec3,
+};
+
+// Measures the world-space distance between 2 points picked in a viewport.
+#[derive(Debug, Clone)]
+pub struct DistanceRuler {
+    pub is_active: bool,
+    pub toggle_keycode: Keycode,
+    pub start: Option<Vec3>,
+    pub end: Option<Vec3>,
+}
+
+impl Default for DistanceRuler {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            toggle_keycode: Keycode::F2,
+            start: None,
+            end: None,
+        }
+    }
+}
+
+impl DistanceRuler {
+    // Adds a measurement point; a third point begins a new measurement.
+    pub fn add_point(&mut self, point: Vec3) {
+        match (self.start, self.end) {
+            (Some(_), None) => {
+                self.end.replace(point);
+            }
+            _ => {
+                self.start.replace(point);
+                self.end.take();
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.start.take();
+        self.end.take();
+    }
+
+    pub fn get_distance(&self) -> Option<f32> {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => Some((end - start).mag()),
+            _ => None,
+        }
+    }
+
+    pub fn get_label(&self) -> Option<String> {
+        self.get_distance()
+            .map(|distance| format!("{:.3}", distance))
+    }
+
+    // Toggles the ruler (clearing any measurement), and picks a point on the
+    // scene's geometry for each left-click inside the viewport (`width` x
+    // `height`, at the viewport's origin).
+    pub fn update(
+        &mut self,
+        keyboard_state: &KeyboardState,
+        mouse_state: &MouseState,
+        scene: &SceneGraph,
+        resources: &SceneResources,
+        camera: &Camera,
+        width: u32,
+        height: u32,
+    ) -> Result<(), String> {
+        if keyboard_state
+            .newly_pressed_keycodes
+            .contains(&self.toggle_keycode)
+        {
+            self.is_active = !self.is_active;
+
+            self.clear();
+        }
+
+        if !self.is_active {
+            return Ok(());
+        }
+
+        let was_clicked = matches!(
+            mouse_state.button_event,
+            Some(event) if matches!((event.button, event.kind), (MouseButton::Left, MouseEventKind::Up))
+        );
+
+        let (x, y) = mouse_state.position;
+
+        if !was_clicked || x < 0 || y < 0 || x as u32 >= width || y as u32 >= height {
+            return Ok(());
+        }
+
+        let mut ray = camera.get_pixel_ray(x as u32, y as u32, width, height);
+
+        if let Some(point) = scene.pick(resources, &mut ray)? {
+            self.add_point(point);
+        }
+
+        Ok(())
+    }
+
+    pub fn render(&self, renderer: &mut dyn Renderer, color: Color) {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => {
+                renderer.render_line(start, end, color);
+            }
+            (Some(start), None) => {
+                renderer.render_axes(Some(start), Some(0.25));
+            }
+            _ => (),
+        }
+    }
+
+    // Draws the measured distance at the (projected) midpoint of the ruler.
+    pub fn render_label(
+        &self,
+        target: &mut Buffer2D,
+        font_cache: &mut FontCache,
+        font_info: &FontInfo,
+        shader_context: &ShaderContext,
+    ) -> Result<(), String> {
+        let (start, end, label) = match (self.start, self.end, self.get_label()) {
+            (Some(start), Some(end), Some(label)) => (start, end, label),
+            _ => return Ok(()),
+        };
+
+        let midpoint_ndc_space = shader_context.to_ndc_space((start + end) / 2.0);
+
+        // Skips labels that fall behind the camera or off-screen.
+
+        if midpoint_ndc_space.z < 0.0
+            || !(0.0..1.0).contains(&midpoint_ndc_space.x)
+            || !(0.0..1.0).contains(&midpoint_ndc_space.y)
+        {
+            return Ok(());
+        }
+
+        let op = TextOperation {
+            text: &label,
+            x: (midpoint_ndc_space.x * target.width as f32) as u32,
+            y: (midpoint_ndc_space.y * target.height as f32) as u32,
+            color: color::YELLOW,
+        };
+
+        Graphics::text(target, font_cache, None, font_info, &op)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use crate::{
+        entity::Entity,
+        geometry::primitives::ray::Ray,
+        mesh::{face::PartialFace, mesh_geometry::MeshGeometry, Mesh},
+        scene::{
+            graph::SceneGraph,
+            node::{SceneNode, SceneNodeType},
+            resources::SceneResources,
+        },
+        transform::Transform3D,
+        vec::vec3::{self, Vec3},
+    };
+
+    use super::DistanceRuler;
+
+    #[test]
+    fn measures_the_distance_between_points_picked_on_a_transformed_quad() {
+        let resources = SceneResources::default();
+
+        // A 2x2 quad, facing +Z, at z = 0.
+
+        let geometry = MeshGeometry {
+            vertices: vec![
+                Vec3 {
+                    x: -1.0,
+                    y: -1.0,
+                    z: 0.0,
+                },
+                Vec3 {
+                    x: 1.0,
+                    y: -1.0,
+                    z: 0.0,
+                },
+                Vec3 {
+                    x: 1.0,
+                    y: 1.0,
+                    z: 0.0,
+                },
+                Vec3 {
+                    x: -1.0,
+                    y: 1.0,
+                    z: 0.0,
+                },
+            ]
+            .into_boxed_slice(),
+            normals: vec![vec3::FORWARD].into_boxed_slice(),
+            uvs: vec![Default::default()].into_boxed_slice(),
+        };
+
+        let faces = [[0, 1, 2], [0, 2, 3]]
+            .map(|vertices| PartialFace {
+                vertices,
+                normals: Some([0, 0, 0]),
+                uvs: Some([0, 0, 0]),
+            })
+            .to_vec();
+
+        let mesh = Mesh::new(Rc::new(geometry), faces, None);
+
+        let entity_handle = {
+            let mesh_handle = resources.mesh.borrow_mut().insert(mesh);
+
+            resources
+                .entity
+                .borrow_mut()
+                .insert(Entity::new(mesh_handle, None))
+        };
+
+        // Scales the quad to 20x20, and moves it to (1, 0, -2), so that it
+        // covers x in [-9, 11] and y in [-10, 10].
+
+        let mut transform = Transform3D::default();
+
+        transform.set_scale(Vec3 {
+            x: 10.0,
+            y: 10.0,
+            z: 10.0,
+        });
+
+        transform.set_translation(Vec3 {
+            x: 1.0,
+            y: 0.0,
+            z: -2.0,
+        });
+
+        let mut scene = SceneGraph::new();
+
+        scene
+            .root
+            .add_child(SceneNode::new(
+                SceneNodeType::Entity,
+                transform,
+                Some(entity_handle),
+            ))
+            .unwrap();
+
+        let pick_straight_down = |x: f32, y: f32| {
+            let mut ray = Ray::new(
+                Vec3 { x, y, z: 5.0 },
+                Vec3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: -1.0,
+                },
+            );
+
+            scene.pick(&resources, &mut ray).unwrap()
+        };
+
+        // Misses the quad.
+
+        assert!(pick_straight_down(-9.5, 0.0).is_none());
+        assert!(pick_straight_down(0.0, 10.5).is_none());
+
+        // Picks 2 points on the quad, 7 units apart.
+
+        let mut ruler = DistanceRuler::default();
+
+        for (x, y) in [(-3.0, 1.0), (4.0, 1.0)] {
+            let point = pick_straight_down(x, y).unwrap();
+
+            assert!((point - Vec3 { x, y, z: -2.0 }).mag() < 1e-5, "{:?}", point);
+
+            ruler.add_point(point);
+        }
+
+        assert!((ruler.get_distance().unwrap() - 7.0).abs() < 1e-5);
+        assert_eq!(ruler.get_label().as_deref(), Some("7.000"));
+    }
+}
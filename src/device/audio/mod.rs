@@ -0,0 +1,261 @@
+use std::{fmt, sync::Arc};
+
+use sdl2::{
+    audio::{AudioCallback, AudioDevice, AudioSpec, AudioSpecDesired},
+    Sdl,
+};
+
+use sound::Sound;
+use spatial::SpatialAudioParameters;
+
+pub mod sound;
+pub mod spatial;
+
+static DEFAULT_FREQUENCY: i32 = 44100;
+static DEFAULT_CHANNELS: u8 = 2;
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct VoiceId(u64);
+
+#[derive(Debug, Clone)]
+struct Voice {
+    id: VoiceId,
+    sound: Arc<Sound>,
+    // Position in the sound, in (fractional) sound frames.
+    cursor: f64,
+    volume: f32,
+    pan: f32,
+    looping: bool,
+}
+
+// Mixes all playing voices into the device's output buffer; runs on SDL's
+// audio thread.
+#[derive(Debug)]
+pub struct AudioMixer {
+    frequency: i32,
+    channels: u8,
+    master_volume: f32,
+    voices: Vec<Voice>,
+}
+
+impl AudioMixer {
+    pub fn new(frequency: i32, channels: u8) -> Self {
+        Self {
+            frequency,
+            channels,
+            master_volume: 1.0,
+            voices: vec![],
+        }
+    }
+
+    pub fn active_voices(&self) -> usize {
+        self.voices.len()
+    }
+
+    pub fn mix(&mut self, out: &mut [f32]) {
+        for sample in out.iter_mut() {
+            *sample = 0.0;
+        }
+
+        let output_channels = self.channels as usize;
+
+        let output_frames = out.len() / output_channels;
+
+        for voice in self.voices.iter_mut() {
+            let sound = &voice.sound;
+
+            let sound_frames = sound.frames();
+
+            if sound_frames == 0 {
+                voice.cursor = f64::MAX;
+
+                continue;
+            }
+
+            // Nearest-frame resampling from the sound's rate to the device's.
+
+            let step = sound.frequency as f64 / self.frequency as f64;
+
+            let (left_gain, right_gain) = get_pan_gains(voice.pan);
+
+            for frame in 0..output_frames {
+                let mut sound_frame = voice.cursor as usize;
+
+                if sound_frame >= sound_frames {
+                    if voice.looping {
+                        voice.cursor %= sound_frames as f64;
+
+                        sound_frame = voice.cursor as usize;
+                    } else {
+                        break;
+                    }
+                }
+
+                for channel in 0..output_channels {
+                    let pan_gain = match (output_channels, channel) {
+                        (2, 0) => left_gain,
+                        (2, 1) => right_gain,
+                        _ => 1.0,
+                    };
+
+                    out[frame * output_channels + channel] +=
+                        sound.sample(sound_frame, channel, output_channels)
+                            * voice.volume
+                            * pan_gain
+                            * self.master_volume;
+                }
+
+                voice.cursor += step;
+            }
+        }
+
+        // Retire any voices that finished playing.
+
+        self.voices
+            .retain(|voice| voice.looping || (voice.cursor as usize) < voice.sound.frames());
+
+        for sample in out.iter_mut() {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+    }
+}
+
+impl AudioCallback for AudioMixer {
+    type Channel = f32;
+
+    fn callback(&mut self, out: &mut [f32]) {
+        self.mix(out);
+    }
+}
+
+// Constant-power stereo panning, for a pan in [-1 (left), 1 (right)].
+fn get_pan_gains(pan: f32) -> (f32, f32) {
+    let angle = (pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+
+    (angle.cos(), angle.sin())
+}
+
+pub struct AudioPlaybackDevice {
+    device: AudioDevice<AudioMixer>,
+    next_voice_id: u64,
+}
+
+impl fmt::Debug for AudioPlaybackDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AudioPlaybackDevice")
+            .field("device", &"Unknown")
+            .field("next_voice_id", &self.next_voice_id)
+            .finish()
+    }
+}
+
+impl AudioPlaybackDevice {
+    pub fn new(sdl_context: &Sdl) -> Result<Self, String> {
+        let audio_subsystem = sdl_context.audio()?;
+
+        let desired_spec = AudioSpecDesired {
+            freq: Some(DEFAULT_FREQUENCY),
+            channels: Some(DEFAULT_CHANNELS),
+            samples: None,
+        };
+
+        let device = audio_subsystem.open_playback(None, &desired_spec, |spec: AudioSpec| {
+            AudioMixer::new(spec.freq, spec.channels)
+        })?;
+
+        device.resume();
+
+        Ok(Self {
+            device,
+            next_voice_id: 0,
+        })
+    }
+
+    pub fn play(&mut self, sound: &Arc<Sound>, volume: f32) -> VoiceId {
+        self.start_voice(sound, volume, false)
+    }
+
+    pub fn play_looping(&mut self, sound: &Arc<Sound>, volume: f32) -> VoiceId {
+        self.start_voice(sound, volume, true)
+    }
+
+    pub fn is_playing(&mut self, id: VoiceId) -> bool {
+        self.device.lock().voices.iter().any(|voice| voice.id == id)
+    }
+
+    pub fn stop(&mut self, id: VoiceId) {
+        self.device.lock().voices.retain(|voice| voice.id != id);
+    }
+
+    pub fn stop_all(&mut self) {
+        self.device.lock().voices.clear();
+    }
+
+    pub fn set_volume(&mut self, id: VoiceId, volume: f32) {
+        if let Some(voice) = self
+            .device
+            .lock()
+            .voices
+            .iter_mut()
+            .find(|voice| voice.id == id)
+        {
+            voice.volume = volume;
+        }
+    }
+
+    pub fn set_pan(&mut self, id: VoiceId, pan: f32) {
+        if let Some(voice) = self
+            .device
+            .lock()
+            .voices
+            .iter_mut()
+            .find(|voice| voice.id == id)
+        {
+            voice.pan = pan;
+        }
+    }
+
+    // Applies spatialized volume and pan to a playing voice, scaling the
+    // attenuated volume by `volume`.
+    pub fn set_spatial(&mut self, id: VoiceId, parameters: &SpatialAudioParameters, volume: f32) {
+        if let Some(voice) = self
+            .device
+            .lock()
+            .voices
+            .iter_mut()
+            .find(|voice| voice.id == id)
+        {
+            voice.volume = volume * parameters.volume;
+            voice.pan = parameters.pan;
+        }
+    }
+
+    pub fn set_master_volume(&mut self, volume: f32) {
+        self.device.lock().master_volume = volume;
+    }
+
+    pub fn pause(&self) {
+        self.device.pause();
+    }
+
+    pub fn resume(&self) {
+        self.device.resume();
+    }
+
+    fn start_voice(&mut self, sound: &Arc<Sound>, volume: f32, looping: bool) -> VoiceId {
+        let id = VoiceId(self.next_voice_id);
+
+        self.next_voice_id += 1;
+
+        self.device.lock().voices.push(Voice {
+            id,
+            sound: sound.clone(),
+            cursor: 0.0,
+            volume,
+            pan: 0.0,
+            looping,
+        });
+
+        id
+    }
+}
@@ -0,0 +1,146 @@
+use sdl2::{
+    audio::{AudioCVT, AudioFormat, AudioSpecWAV},
+    rwops::RWops,
+};
+
+// A decoded sound, stored as interleaved samples normalized to [-1, 1].
+#[derive(Debug, Clone)]
+pub struct Sound {
+    pub frequency: i32,
+    pub channels: u8,
+    // Sample format of the source data, before conversion.
+    pub format: AudioFormat,
+    pub samples: Vec<f32>,
+}
+
+impl Sound {
+    pub fn load_wav(filepath: &str) -> Result<Self, String> {
+        match AudioSpecWAV::load_wav(filepath) {
+            Ok(wav) => Self::from_wav_spec(&wav),
+            Err(err) => Err(format!("Failed to load WAV file '{}': {}", filepath, err)),
+        }
+    }
+
+    pub fn from_wav_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut source = RWops::from_bytes(bytes)?;
+
+        let wav = AudioSpecWAV::load_wav_rw(&mut source)?;
+
+        Self::from_wav_spec(&wav)
+    }
+
+    fn from_wav_spec(wav: &AudioSpecWAV) -> Result<Self, String> {
+        if wav.channels == 0 {
+            return Err("WAV file declares zero channels.".to_string());
+        }
+
+        // Converts the samples to (native-endian) floats, keeping the sound's
+        // own rate and channel count; the mixer resamples as it plays.
+
+        let converter = AudioCVT::new(
+            wav.format,
+            wav.channels,
+            wav.freq,
+            AudioFormat::f32_sys(),
+            wav.channels,
+            wav.freq,
+        )?;
+
+        let samples = converter
+            .convert(wav.buffer().to_vec())
+            .chunks_exact(4)
+            .map(|quad| f32::from_ne_bytes([quad[0], quad[1], quad[2], quad[3]]))
+            .collect();
+
+        Ok(Self {
+            frequency: wav.freq,
+            channels: wav.channels,
+            format: wav.format,
+            samples,
+        })
+    }
+
+    // Number of sample frames (one sample per channel).
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels as usize
+    }
+
+    pub fn duration_seconds(&self) -> f32 {
+        self.frames() as f32 / self.frequency as f32
+    }
+
+    // Returns the sample for the given frame and output channel, mapping
+    // between mono and multi-channel layouts as needed.
+    pub fn sample(&self, frame: usize, channel: usize, output_channels: usize) -> f32 {
+        let channels = self.channels as usize;
+
+        let base = frame * channels;
+
+        if channels == output_channels {
+            self.samples[base + channel]
+        } else if channels == 1 {
+            self.samples[base]
+        } else if output_channels == 1 {
+            self.samples[base..base + channels].iter().sum::<f32>() / channels as f32
+        } else {
+            self.samples[base + channel.min(channels - 1)]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sdl2::audio::AudioFormat;
+
+    use super::Sound;
+
+    fn encode_wav_s16(frequency: u32, channels: u16, samples: &[i16]) -> Vec<u8> {
+        let data_size = (samples.len() * 2) as u32;
+
+        let mut bytes = vec![];
+
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+        bytes.extend_from_slice(b"WAVE");
+
+        bytes.extend_from_slice(b"fmt ");
+        bytes.extend_from_slice(&16_u32.to_le_bytes());
+        bytes.extend_from_slice(&1_u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&frequency.to_le_bytes());
+        bytes.extend_from_slice(&(frequency * channels as u32 * 2).to_le_bytes());
+        bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16_u16.to_le_bytes());
+
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+
+        for sample in samples {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn load_wav_s16_stereo() {
+        let samples: Vec<i16> = (0..200).map(|i| (i * 100) as i16).collect();
+
+        let bytes = encode_wav_s16(22050, 2, &samples);
+
+        let sound = Sound::from_wav_bytes(&bytes).unwrap();
+
+        assert_eq!(sound.format, AudioFormat::S16LSB);
+        assert_eq!(sound.frequency, 22050);
+        assert_eq!(sound.channels, 2);
+        assert_eq!(sound.samples.len(), 200);
+        assert_eq!(sound.frames(), 100);
+
+        assert_eq!(sound.samples[1], 100.0 / 32768.0);
+    }
+
+    #[test]
+    fn rejects_non_wav() {
+        assert!(Sound::from_wav_bytes(b"not a wav file").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    scene::light::attenuation::LightAttenuation,
+    transform::Transform3D,
+    vec::vec3::{self, Vec3},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SpatialAudioParameters {
+    // Distance attenuation, in [0, 1].
+    pub volume: f32,
+    // Stereo pan, in [-1 (left), 1 (right)].
+    pub pan: f32,
+}
+
+impl Default for SpatialAudioParameters {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            pan: 0.0,
+        }
+    }
+}
+
+// Computes the volume and pan of a sound emitted at `emitter_position`, as
+// heard by a listener with the given transform. Distance attenuation follows
+// the same model used by point lights.
+pub fn get_spatial_parameters(
+    listener: &Transform3D,
+    emitter_position: &Vec3,
+    attenuation: &LightAttenuation,
+) -> SpatialAudioParameters {
+    let listener_to_emitter = *emitter_position - *listener.translation();
+
+    let distance = listener_to_emitter.mag();
+
+    let volume = attenuation.attenuate_for_distance(distance).min(1.0);
+
+    if distance < f32::EPSILON {
+        return SpatialAudioParameters { volume, pan: 0.0 };
+    }
+
+    let listener_right = (vec3::RIGHT * *listener.rotation().mat()).as_normal();
+
+    let pan = (listener_to_emitter / distance)
+        .dot(listener_right)
+        .clamp(-1.0, 1.0);
+
+    SpatialAudioParameters { volume, pan }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        scene::light::attenuation::LIGHT_ATTENUATION_RANGE_13_UNITS,
+        transform::Transform3D,
+        vec::vec3::{self, Vec3},
+    };
+
+    use super::get_spatial_parameters;
+
+    #[test]
+    fn left_of_listener_pans_left_and_attenuates() {
+        let listener = Transform3D::default();
+
+        let attenuation = &LIGHT_ATTENUATION_RANGE_13_UNITS;
+
+        let near = get_spatial_parameters(&listener, &(-vec3::RIGHT * 2.0), attenuation);
+        let far = get_spatial_parameters(&listener, &(-vec3::RIGHT * 8.0), attenuation);
+
+        assert_eq!(near.pan, -1.0);
+        assert_eq!(far.pan, -1.0);
+
+        assert!(near.volume < 1.0);
+        assert!(far.volume < near.volume);
+
+        let ahead = get_spatial_parameters(
+            &listener,
+            &Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 4.0,
+            },
+            attenuation,
+        );
+
+        assert!(ahead.pan.abs() < 0.0001);
+    }
+}
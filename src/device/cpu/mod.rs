@@ -0,0 +1,122 @@
+use std::{fmt, sync::OnceLock};
+
+use serde::Serialize;
+
+use crate::thread_pool::get_default_thread_count;
+
+// Ordered from least to most capable, so that code paths can be selected
+// with comparisons (e.g., `simd_level >= SimdLevel::Avx2`).
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+pub enum SimdLevel {
+    #[default]
+    None,
+    Neon,
+    Sse2,
+    Sse41,
+    Avx,
+    Avx2,
+    Avx512,
+}
+
+impl fmt::Display for SimdLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SimdLevel::None => "None",
+                SimdLevel::Neon => "NEON",
+                SimdLevel::Sse2 => "SSE2",
+                SimdLevel::Sse41 => "SSE4.1",
+                SimdLevel::Avx => "AVX",
+                SimdLevel::Avx2 => "AVX2",
+                SimdLevel::Avx512 => "AVX-512",
+            }
+        )
+    }
+}
+
+impl SimdLevel {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub fn detect() -> Self {
+        if is_x86_feature_detected!("avx512f") {
+            SimdLevel::Avx512
+        } else if is_x86_feature_detected!("avx2") {
+            SimdLevel::Avx2
+        } else if is_x86_feature_detected!("avx") {
+            SimdLevel::Avx
+        } else if is_x86_feature_detected!("sse4.1") {
+            SimdLevel::Sse41
+        } else if is_x86_feature_detected!("sse2") {
+            SimdLevel::Sse2
+        } else {
+            SimdLevel::None
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    pub fn detect() -> Self {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            SimdLevel::Neon
+        } else {
+            SimdLevel::None
+        }
+    }
+
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    pub fn detect() -> Self {
+        SimdLevel::None
+    }
+}
+
+static CPU_CAPABILITIES: OnceLock<CpuCapabilities> = OnceLock::new();
+
+// What the host CPU offers, detected once at startup; used to size thread
+// pools and to pick between scalar and SIMD math paths.
+#[derive(Default, Debug, Copy, Clone, Serialize)]
+pub struct CpuCapabilities {
+    pub logical_cores: usize,
+    pub simd_level: SimdLevel,
+}
+
+impl fmt::Display for CpuCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CPU: {} logical cores, SIMD: {}",
+            self.logical_cores, self.simd_level
+        )
+    }
+}
+
+impl CpuCapabilities {
+    pub fn detect() -> Self {
+        Self {
+            logical_cores: get_default_thread_count(),
+            simd_level: SimdLevel::detect(),
+        }
+    }
+
+    // Detects the host's capabilities on first use; later calls return the
+    // same report.
+    pub fn get() -> &'static Self {
+        CPU_CAPABILITIES.get_or_init(Self::detect)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CpuCapabilities;
+
+    #[test]
+    fn detected_capabilities_are_reportable() {
+        let capabilities = CpuCapabilities::detect();
+
+        assert!(capabilities.logical_cores >= 1);
+
+        let serialized = serde_json::to_string(&capabilities).unwrap();
+
+        assert!(serialized.contains("\"logical_cores\""));
+        assert!(serialized.contains("\"simd_level\""));
+    }
+}
@@ -1,5 +1,7 @@
 use core::fmt;
 
+use serde::{Deserialize, Serialize};
+
 use sdl2::{
     controller::{Axis, Button},
     haptic::Haptic,
@@ -165,7 +167,7 @@ impl GameController {
     }
 }
 
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameControllerStateButtons {
     pub a: bool,
     pub b: bool,
@@ -184,17 +186,17 @@ pub struct GameControllerStateButtons {
     pub dpad_right: bool,
 }
 
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameControllerStateTrigger {
     pub activation: i16,
 }
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameControllerStateTriggers {
     pub left: GameControllerStateTrigger,
     pub right: GameControllerStateTrigger,
 }
 
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameControllerStateJoystickPosition {
     pub x: i16,
     pub y: i16,
@@ -210,17 +212,17 @@ impl fmt::Display for GameControllerStateJoystickPosition {
     }
 }
 
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameControllerStateJoystick {
     pub position: GameControllerStateJoystickPosition,
 }
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameControllerStateJoysticks {
     pub left: GameControllerStateJoystick,
     pub right: GameControllerStateJoystick,
 }
 
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameControllerState {
     pub is_initialized: bool,
     pub axis_dead_zone: i16,
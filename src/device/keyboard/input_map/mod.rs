@@ -0,0 +1,88 @@
+use sdl2::keyboard::Keycode;
+
+use super::KeyboardState;
+
+#[derive(Debug, Clone)]
+pub struct KeyBinding {
+    pub category: String,
+    pub action: String,
+    pub keycode: Keycode,
+}
+
+// Maps named actions to keys, grouped by category (e.g., "Render").
+#[derive(Default, Debug, Clone)]
+pub struct InputMap {
+    bindings: Vec<KeyBinding>,
+}
+
+impl InputMap {
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    // Binding an action that is already bound replaces its key.
+    pub fn bind(&mut self, category: &str, action: &str, keycode: Keycode) {
+        match self
+            .bindings
+            .iter_mut()
+            .find(|binding| binding.category == category && binding.action == action)
+        {
+            Some(binding) => {
+                binding.keycode = keycode;
+            }
+            None => self.bindings.push(KeyBinding {
+                category: category.to_string(),
+                action: action.to_string(),
+                keycode,
+            }),
+        }
+    }
+
+    pub fn get_keycode(&self, action: &str) -> Option<Keycode> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.action == action)
+            .map(|binding| binding.keycode)
+    }
+
+    // Returns the action (if any) bound to `keycode` within `category`.
+    pub fn get_action(&self, category: &str, keycode: Keycode) -> Option<&str> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.category == category && binding.keycode == keycode)
+            .map(|binding| binding.action.as_str())
+    }
+
+    pub fn was_pressed(&self, action: &str, keyboard_state: &KeyboardState) -> bool {
+        match self.get_keycode(action) {
+            Some(keycode) => keyboard_state.newly_pressed_keycodes.contains(&keycode),
+            None => false,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &KeyBinding> {
+        self.bindings.iter()
+    }
+
+    // Returns each category's bindings, with categories in the order that
+    // they were first bound.
+    pub fn get_bindings_by_category(&self) -> Vec<(&str, Vec<&KeyBinding>)> {
+        let mut categories: Vec<(&str, Vec<&KeyBinding>)> = vec![];
+
+        for binding in &self.bindings {
+            match categories
+                .iter_mut()
+                .find(|(category, _)| *category == binding.category)
+            {
+                Some((_, bindings)) => bindings.push(binding),
+                None => categories.push((&binding.category, vec![binding])),
+            }
+        }
+
+        categories
+    }
+}
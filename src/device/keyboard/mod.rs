@@ -2,6 +2,7 @@ use std::collections::HashSet;
 
 use sdl2::keyboard::{Keycode, Mod};
 
+pub mod input_map;
 pub mod keycode;
 
 #[derive(Debug, Clone)]
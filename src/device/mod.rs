@@ -1,3 +1,6 @@
+pub mod audio;
+pub mod cpu;
 pub mod game_controller;
 pub mod keyboard;
 pub mod mouse;
+pub mod recording;
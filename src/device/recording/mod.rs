@@ -0,0 +1,392 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use sdl2::{
+    keyboard::{Keycode, Mod},
+    mouse::{MouseButton, MouseWheelDirection},
+};
+
+use super::{
+    game_controller::GameControllerState,
+    keyboard::KeyboardState,
+    mouse::{MouseDragEvent, MouseEvent, MouseEventKind, MouseState, MouseWheelEvent},
+};
+
+// SDL's input types aren't serializable, so we record their raw values.
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecordedKeyboardState {
+    modifiers: u16,
+    pressed_keycodes: Vec<i32>,
+    newly_pressed_keycodes: Vec<i32>,
+}
+
+impl From<&KeyboardState> for RecordedKeyboardState {
+    fn from(state: &KeyboardState) -> Self {
+        let to_sorted = |keycodes: &mut dyn Iterator<Item = &Keycode>| {
+            let mut keycodes: Vec<i32> = keycodes.map(|keycode| keycode.into_i32()).collect();
+
+            keycodes.sort();
+
+            keycodes
+        };
+
+        Self {
+            modifiers: state.modifiers.bits(),
+            pressed_keycodes: to_sorted(&mut state.pressed_keycodes.iter()),
+            newly_pressed_keycodes: to_sorted(&mut state.newly_pressed_keycodes.iter()),
+        }
+    }
+}
+
+impl From<&RecordedKeyboardState> for KeyboardState {
+    fn from(recorded: &RecordedKeyboardState) -> Self {
+        Self {
+            modifiers: Mod::from_bits_truncate(recorded.modifiers),
+            pressed_keycodes: recorded
+                .pressed_keycodes
+                .iter()
+                .filter_map(|keycode| Keycode::from_i32(*keycode))
+                .collect(),
+            newly_pressed_keycodes: recorded
+                .newly_pressed_keycodes
+                .iter()
+                .filter_map(|keycode| Keycode::from_i32(*keycode))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecordedMouseState {
+    buttons_down: Vec<u8>,
+    prev_buttons_down: Vec<u8>,
+    // Button, and whether it was pressed (or released).
+    button_event: Option<(u8, bool)>,
+    position: (i32, i32),
+    prev_position: (i32, i32),
+    ndc_position: (f32, f32),
+    prev_ndc_position: (f32, f32),
+    // Direction, and delta.
+    wheel_event: Option<(u32, i32)>,
+    relative_motion: (i32, i32),
+    drag_events: Vec<(u8, (i32, i32))>,
+}
+
+impl From<&MouseState> for RecordedMouseState {
+    fn from(state: &MouseState) -> Self {
+        let to_sorted = |buttons: &mut dyn Iterator<Item = &MouseButton>| {
+            let mut buttons: Vec<u8> = buttons.map(|button| *button as u8).collect();
+
+            buttons.sort();
+
+            buttons
+        };
+
+        let mut drag_events: Vec<(u8, (i32, i32))> = state
+            .drag_events
+            .iter()
+            .map(|(button, event)| (*button as u8, event.delta))
+            .collect();
+
+        drag_events.sort();
+
+        Self {
+            buttons_down: to_sorted(&mut state.buttons_down.iter()),
+            prev_buttons_down: to_sorted(&mut state.prev_buttons_down.iter()),
+            button_event: state.button_event.map(|event| {
+                (
+                    event.button as u8,
+                    matches!(event.kind, MouseEventKind::Down),
+                )
+            }),
+            position: state.position,
+            prev_position: state.prev_position,
+            ndc_position: state.ndc_position,
+            prev_ndc_position: state.prev_ndc_position,
+            wheel_event: state
+                .wheel_event
+                .as_ref()
+                .map(|event| (event.direction.to_ll(), event.delta)),
+            relative_motion: state.relative_motion,
+            drag_events,
+        }
+    }
+}
+
+impl From<&RecordedMouseState> for MouseState {
+    fn from(recorded: &RecordedMouseState) -> Self {
+        Self {
+            buttons_down: recorded
+                .buttons_down
+                .iter()
+                .map(|button| MouseButton::from_ll(*button))
+                .collect(),
+            prev_buttons_down: recorded
+                .prev_buttons_down
+                .iter()
+                .map(|button| MouseButton::from_ll(*button))
+                .collect(),
+            button_event: recorded.button_event.map(|(button, is_down)| MouseEvent {
+                button: MouseButton::from_ll(button),
+                kind: if is_down {
+                    MouseEventKind::Down
+                } else {
+                    MouseEventKind::Up
+                },
+            }),
+            position: recorded.position,
+            prev_position: recorded.prev_position,
+            ndc_position: recorded.ndc_position,
+            prev_ndc_position: recorded.prev_ndc_position,
+            wheel_event: recorded
+                .wheel_event
+                .map(|(direction, delta)| MouseWheelEvent {
+                    direction: MouseWheelDirection::from_ll(direction),
+                    delta,
+                }),
+            relative_motion: recorded.relative_motion,
+            drag_events: recorded
+                .drag_events
+                .iter()
+                .map(|(button, delta)| {
+                    (
+                        MouseButton::from_ll(*button),
+                        MouseDragEvent { delta: *delta },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+// The input (and elapsed time) seen by a single update.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputFrame {
+    keyboard: RecordedKeyboardState,
+    mouse: RecordedMouseState,
+    game_controller: GameControllerState,
+    pub seconds_since_last_update: f32,
+}
+
+impl InputFrame {
+    pub fn new(
+        keyboard_state: &KeyboardState,
+        mouse_state: &MouseState,
+        game_controller_state: &GameControllerState,
+        seconds_since_last_update: f32,
+    ) -> Self {
+        Self {
+            keyboard: keyboard_state.into(),
+            mouse: mouse_state.into(),
+            game_controller: *game_controller_state,
+            seconds_since_last_update,
+        }
+    }
+
+    // Overwrites the given device states with this frame's input.
+    pub fn apply(
+        &self,
+        keyboard_state: &mut KeyboardState,
+        mouse_state: &mut MouseState,
+        game_controller_state: &mut GameControllerState,
+    ) {
+        *keyboard_state = (&self.keyboard).into();
+        *mouse_state = (&self.mouse).into();
+        *game_controller_state = self.game_controller;
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct InputRecording {
+    pub frames: Vec<InputFrame>,
+}
+
+impl InputRecording {
+    pub fn load(filepath: &str) -> Result<Self, String> {
+        let json = fs::read_to_string(filepath)
+            .map_err(|err| format!("Failed to read input recording {}: {}", filepath, err))?;
+
+        serde_json::from_str(&json)
+            .map_err(|err| format!("Failed to parse input recording {}: {}", filepath, err))
+    }
+
+    pub fn save(&self, filepath: &str) -> Result<(), String> {
+        let json = serde_json::to_string(self).map_err(|err| err.to_string())?;
+
+        fs::write(filepath, json)
+            .map_err(|err| format!("Failed to write input recording {}: {}", filepath, err))
+    }
+}
+
+// Records the input for each update, to be saved when the app exits.
+#[derive(Default, Debug, Clone)]
+pub struct InputRecorder {
+    pub filepath: String,
+    pub recording: InputRecording,
+}
+
+impl InputRecorder {
+    pub fn new(filepath: &str) -> Self {
+        Self {
+            filepath: filepath.to_string(),
+            recording: Default::default(),
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        keyboard_state: &KeyboardState,
+        mouse_state: &MouseState,
+        game_controller_state: &GameControllerState,
+        seconds_since_last_update: f32,
+    ) {
+        self.recording.frames.push(InputFrame::new(
+            keyboard_state,
+            mouse_state,
+            game_controller_state,
+            seconds_since_last_update,
+        ));
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        self.recording.save(&self.filepath)
+    }
+}
+
+// Replays a recording's frames, one per update.
+#[derive(Default, Debug, Clone)]
+pub struct InputPlayer {
+    recording: InputRecording,
+    next_frame_index: usize,
+}
+
+impl InputPlayer {
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            recording,
+            next_frame_index: 0,
+        }
+    }
+
+    pub fn load(filepath: &str) -> Result<Self, String> {
+        Ok(Self::new(InputRecording::load(filepath)?))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_frame_index >= self.recording.frames.len()
+    }
+
+    pub fn next_frame(&mut self) -> Option<&InputFrame> {
+        let frame = self.recording.frames.get(self.next_frame_index)?;
+
+        self.next_frame_index += 1;
+
+        Some(frame)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sdl2::{
+        keyboard::{Keycode, Mod},
+        mouse::{MouseButton, MouseWheelDirection},
+    };
+
+    use crate::{
+        device::{
+            game_controller::GameControllerState,
+            keyboard::KeyboardState,
+            mouse::{MouseDragEvent, MouseState, MouseWheelEvent},
+        },
+        testing::TempPath,
+    };
+
+    use super::{InputFrame, InputPlayer, InputRecorder};
+
+    #[test]
+    fn recorded_input_replays_identically() {
+        let temp_path = TempPath::new("input.json");
+
+        let filepath = temp_path.to_str().unwrap();
+
+        let mut recorder = InputRecorder::new(filepath);
+
+        let mut keyboard_state = KeyboardState::default();
+        let mut mouse_state = MouseState::default();
+        let mut game_controller_state = GameControllerState::default();
+
+        let mut recorded = vec![];
+
+        for frame_index in 0..3 {
+            keyboard_state.modifiers = Mod::LSHIFTMOD;
+            keyboard_state.pressed_keycodes.insert(Keycode::W);
+            keyboard_state.newly_pressed_keycodes = [Keycode::Num1].into();
+
+            mouse_state.position = (frame_index * 10, 20);
+            mouse_state.buttons_down.insert(MouseButton::Left);
+            mouse_state.drag_events.insert(
+                MouseButton::Left,
+                MouseDragEvent {
+                    delta: (frame_index, -frame_index),
+                },
+            );
+            mouse_state.wheel_event.replace(MouseWheelEvent {
+                direction: MouseWheelDirection::Flipped,
+                delta: frame_index,
+            });
+
+            game_controller_state.buttons.a = frame_index % 2 == 0;
+            game_controller_state.joysticks.left.position.x = frame_index as i16 * 100;
+
+            let seconds_since_last_update = 0.016 + frame_index as f32 * 0.001;
+
+            recorder.record(
+                &keyboard_state,
+                &mouse_state,
+                &game_controller_state,
+                seconds_since_last_update,
+            );
+
+            recorded.push(InputFrame::new(
+                &keyboard_state,
+                &mouse_state,
+                &game_controller_state,
+                seconds_since_last_update,
+            ));
+        }
+
+        recorder.save().unwrap();
+
+        let mut player = InputPlayer::load(filepath).unwrap();
+
+        // Replays each frame into fresh device states.
+
+        for expected in &recorded {
+            let frame = player.next_frame().unwrap().clone();
+
+            let mut keyboard_state = KeyboardState::default();
+            let mut mouse_state = MouseState::default();
+            let mut game_controller_state = GameControllerState::default();
+
+            frame.apply(
+                &mut keyboard_state,
+                &mut mouse_state,
+                &mut game_controller_state,
+            );
+
+            let replayed = InputFrame::new(
+                &keyboard_state,
+                &mouse_state,
+                &game_controller_state,
+                frame.seconds_since_last_update,
+            );
+
+            assert_eq!(&replayed, expected);
+        }
+
+        assert!(player.is_finished());
+        assert!(player.next_frame().is_none());
+    }
+}
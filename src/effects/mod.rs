@@ -3,3 +3,5 @@ pub mod gaussian_blur;
 pub mod grayscale_effect;
 pub mod invert_effect;
 pub mod kernel_effect;
+pub mod palette_effect;
+pub mod pixelate_effect;
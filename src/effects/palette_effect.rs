@@ -0,0 +1,161 @@
+use std::fs;
+
+use crate::{buffer::Buffer2D, color::Color, effect::Effect};
+
+// 4x4 Bayer matrix, for ordered dithering.
+static BAYER_4X4: [[f32; 4]; 4] = [
+    [0.0, 8.0, 2.0, 10.0],
+    [12.0, 4.0, 14.0, 6.0],
+    [3.0, 11.0, 1.0, 9.0],
+    [15.0, 7.0, 13.0, 5.0],
+];
+
+// Strength of the dithering offset, in 8-bit color units.
+static DITHER_SPREAD: f32 = 32.0;
+
+#[derive(Default)]
+pub struct PaletteEffect {
+    palette: Vec<Color>,
+    dithering: bool,
+}
+
+impl PaletteEffect {
+    pub fn new(palette: &[Color], dithering: bool) -> Self {
+        Self {
+            palette: palette.to_vec(),
+            dithering,
+        }
+    }
+
+    pub fn from_file(path: &str, dithering: bool) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read palette file '{}': {}", path, err))?;
+
+        let palette = parse_palette(&text)?;
+
+        Ok(Self::new(&palette, dithering))
+    }
+
+    fn get_nearest_color(&self, r: f32, g: f32, b: f32) -> Color {
+        let mut nearest = self.palette[0];
+        let mut nearest_distance_squared = f32::MAX;
+
+        for color in &self.palette {
+            let (dr, dg, db) = (color.r - r, color.g - g, color.b - b);
+
+            let distance_squared = dr * dr + dg * dg + db * db;
+
+            if distance_squared < nearest_distance_squared {
+                nearest = *color;
+                nearest_distance_squared = distance_squared;
+            }
+        }
+
+        nearest
+    }
+}
+
+impl Effect for PaletteEffect {
+    fn apply(&mut self, buffer: &mut Buffer2D) {
+        if self.palette.is_empty() {
+            return;
+        }
+
+        let width = buffer.width as usize;
+
+        for (index, color_u32) in buffer.data.iter_mut().enumerate() {
+            let color = Color::from_u32(*color_u32);
+
+            // Offsets the source color by a per-pixel threshold, if dithering.
+
+            let offset = if self.dithering {
+                let (x, y) = (index % width, index / width);
+
+                (BAYER_4X4[y % 4][x % 4] / 16.0 - 0.5) * DITHER_SPREAD
+            } else {
+                0.0
+            };
+
+            let mut nearest =
+                self.get_nearest_color(color.r + offset, color.g + offset, color.b + offset);
+
+            nearest.a = color.a;
+
+            *color_u32 = nearest.to_u32();
+        }
+    }
+}
+
+// Parses a palette with one hex color (e.g., `ff8000` or `#ff8000`) per line;
+// blank lines and lines beginning with `;` are skipped.
+pub fn parse_palette(text: &str) -> Result<Vec<Color>, String> {
+    let mut palette = vec![];
+
+    for line in text.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+
+        let hex = line.strip_prefix('#').unwrap_or(line);
+
+        let rgb = match (hex.len(), u32::from_str_radix(hex, 16)) {
+            (6, Ok(rgb)) => rgb,
+            _ => return Err(format!("Invalid palette color '{}'.", line)),
+        };
+
+        palette.push(Color::rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8));
+    }
+
+    Ok(palette)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::Buffer2D,
+        color::{self, Color},
+        effect::Effect,
+    };
+
+    use super::{parse_palette, PaletteEffect};
+
+    #[test]
+    fn pixels_snap_to_nearest_palette_entry() {
+        let mut buffer = Buffer2D::new(2, 1, None);
+
+        buffer.set(0, 0, Color::rgb(230, 20, 10).to_u32());
+        buffer.set(1, 0, Color::rgb(15, 30, 200).to_u32());
+
+        let mut effect = PaletteEffect::new(&[color::BLACK, color::RED, color::BLUE], false);
+
+        effect.apply(&mut buffer);
+
+        assert_eq!(*buffer.get(0, 0), color::RED.to_u32());
+        assert_eq!(*buffer.get(1, 0), color::BLUE.to_u32());
+    }
+
+    #[test]
+    fn empty_palette_is_a_no_op() {
+        let original = Color::rgb(12, 34, 56).to_u32();
+
+        let mut buffer = Buffer2D::new(2, 2, Some(original));
+
+        let mut effect = PaletteEffect::new(&[], true);
+
+        effect.apply(&mut buffer);
+
+        assert!(buffer.data.iter().all(|color| *color == original));
+    }
+
+    #[test]
+    fn parses_hex_palette() {
+        let palette = parse_palette("; comment\n#ff0000\n\n0000ff\n").unwrap();
+
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette[1].to_u32(), color::BLUE.to_u32());
+
+        assert!(parse_palette("nope").is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{buffer::Buffer2D, color::Color, effect::Effect};
+
+pub struct PixelateEffect {
+    pixel_size: u32,
+}
+
+impl PixelateEffect {
+    pub fn new(pixel_size: u32) -> Self {
+        Self {
+            pixel_size: pixel_size.max(1),
+        }
+    }
+}
+
+impl Effect for PixelateEffect {
+    fn apply(&mut self, buffer: &mut Buffer2D) {
+        let size = self.pixel_size;
+
+        if size == 1 {
+            return;
+        }
+
+        for block_y in (0..buffer.height).step_by(size as usize) {
+            for block_x in (0..buffer.width).step_by(size as usize) {
+                // Blocks along the right and bottom edges may be partial.
+
+                let x_end = (block_x + size).min(buffer.width);
+                let y_end = (block_y + size).min(buffer.height);
+
+                // Downsample the block (box filter).
+
+                let mut sum = [0.0_f32; 4];
+
+                for y in block_y..y_end {
+                    for x in block_x..x_end {
+                        let color = Color::from_u32(*buffer.get(x, y));
+
+                        sum[0] += color.r;
+                        sum[1] += color.g;
+                        sum[2] += color.b;
+                        sum[3] += color.a;
+                    }
+                }
+
+                let count = ((x_end - block_x) * (y_end - block_y)) as f32;
+
+                let average = Color {
+                    r: (sum[0] / count).round(),
+                    g: (sum[1] / count).round(),
+                    b: (sum[2] / count).round(),
+                    a: (sum[3] / count).round(),
+                }
+                .to_u32();
+
+                // Upsample the block (nearest-neighbor).
+
+                for y in block_y..y_end {
+                    for x in block_x..x_end {
+                        buffer.set(x, y, average);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{buffer::Buffer2D, color::Color, effect::Effect};
+
+    use super::PixelateEffect;
+
+    #[test]
+    fn pixel_size_of_4_makes_each_block_uniform() {
+        let mut buffer = Buffer2D::new(8, 8, None);
+
+        for y in 0..8 {
+            for x in 0..8 {
+                buffer.set(x, y, Color::rgb((x * 30) as u8, (y * 30) as u8, 0).to_u32());
+            }
+        }
+
+        PixelateEffect::new(4).apply(&mut buffer);
+
+        for block_y in [0, 4] {
+            for block_x in [0, 4] {
+                let block_color = *buffer.get(block_x, block_y);
+
+                for y in block_y..block_y + 4 {
+                    for x in block_x..block_x + 4 {
+                        assert_eq!(*buffer.get(x, y), block_color);
+                    }
+                }
+            }
+        }
+
+        // Each block holds the average of its source pixels.
+
+        assert_eq!(*buffer.get(0, 0), Color::rgb(45, 45, 0).to_u32());
+    }
+}
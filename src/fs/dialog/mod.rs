@@ -0,0 +1,254 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+// Native open/save dialogs, shown by the platform's own dialog tool (PowerShell
+// on Windows, AppleScript on macOS, and zenity elsewhere).
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FileDialogKind {
+    Open,
+    Save,
+}
+
+impl fmt::Display for FileDialogKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                FileDialogKind::Open => "Open",
+                FileDialogKind::Save => "Save",
+            }
+        )
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct FileDialogFilter {
+    pub name: String,
+    pub extensions: Vec<String>,
+}
+
+impl FileDialogFilter {
+    pub fn new(name: &str, extensions: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+        }
+    }
+
+    fn get_patterns(&self, separator: &str) -> String {
+        self.extensions
+            .iter()
+            .map(|ext| format!("*.{}", ext))
+            .collect::<Vec<String>>()
+            .join(separator)
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct FileDialogOptions {
+    pub title: String,
+    pub directory: Option<PathBuf>,
+    pub file_name: Option<String>,
+    pub filters: Vec<FileDialogFilter>,
+}
+
+pub fn open_file_dialog(options: &FileDialogOptions) -> Result<Option<PathBuf>, String> {
+    show_file_dialog(FileDialogKind::Open, options)
+}
+
+pub fn save_file_dialog(options: &FileDialogOptions) -> Result<Option<PathBuf>, String> {
+    show_file_dialog(FileDialogKind::Save, options)
+}
+
+fn show_file_dialog(
+    kind: FileDialogKind,
+    options: &FileDialogOptions,
+) -> Result<Option<PathBuf>, String> {
+    let mut command = get_dialog_command(kind, options);
+
+    let output = command
+        .output()
+        .map_err(|err| format!("Failed to show {} file dialog: {}", kind, err))?;
+
+    // Dialog tools exit with a non-zero status when the user cancels.
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let path = stdout.trim();
+
+    if path.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(PathBuf::from(path)))
+    }
+}
+
+fn get_dialog_command(kind: FileDialogKind, options: &FileDialogOptions) -> Command {
+    let (program, args) = if cfg!(target_os = "windows") {
+        ("powershell", get_powershell_args(kind, options))
+    } else if cfg!(target_os = "macos") {
+        ("osascript", get_osascript_args(kind, options))
+    } else {
+        ("zenity", get_zenity_args(kind, options))
+    };
+
+    let mut command = Command::new(program);
+
+    command.args(args);
+
+    command
+}
+
+fn get_initial_path(options: &FileDialogOptions) -> Option<PathBuf> {
+    match (&options.directory, &options.file_name) {
+        (Some(directory), Some(file_name)) => Some(directory.join(file_name)),
+        (Some(directory), None) => Some(directory.clone()),
+        (None, Some(file_name)) => Some(Path::new(file_name).to_path_buf()),
+        (None, None) => None,
+    }
+}
+
+fn get_zenity_args(kind: FileDialogKind, options: &FileDialogOptions) -> Vec<String> {
+    let mut args = vec![
+        "--file-selection".to_string(),
+        format!("--title={}", options.title),
+    ];
+
+    if kind == FileDialogKind::Save {
+        args.push("--save".to_string());
+        args.push("--confirm-overwrite".to_string());
+    }
+
+    if let Some(path) = get_initial_path(options) {
+        // Zenity treats a trailing separator as "start in this directory".
+
+        let mut path = path.display().to_string();
+
+        if options.file_name.is_none() && !path.ends_with('/') {
+            path.push('/');
+        }
+
+        args.push(format!("--filename={}", path));
+    }
+
+    for filter in &options.filters {
+        args.push(format!(
+            "--file-filter={} | {}",
+            filter.name,
+            filter.get_patterns(" ")
+        ));
+    }
+
+    args
+}
+
+fn get_osascript_args(kind: FileDialogKind, options: &FileDialogOptions) -> Vec<String> {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+
+    let mut script = match kind {
+        FileDialogKind::Open => format!("choose file with prompt \"{}\"", escape(&options.title)),
+        FileDialogKind::Save => format!(
+            "choose file name with prompt \"{}\"",
+            escape(&options.title)
+        ),
+    };
+
+    if let (FileDialogKind::Save, Some(file_name)) = (kind, &options.file_name) {
+        script += &format!(" default name \"{}\"", escape(file_name));
+    }
+
+    if let Some(directory) = &options.directory {
+        script += &format!(
+            " default location POSIX file \"{}\"",
+            escape(&directory.display().to_string())
+        );
+    }
+
+    vec!["-e".to_string(), format!("POSIX path of ({})", script)]
+}
+
+fn get_powershell_args(kind: FileDialogKind, options: &FileDialogOptions) -> Vec<String> {
+    let escape = |s: &str| s.replace('\'', "''");
+
+    let mut script = format!(
+        "Add-Type -AssemblyName System.Windows.Forms; \
+         $dialog = New-Object System.Windows.Forms.{}FileDialog; \
+         $dialog.Title = '{}'; ",
+        kind,
+        escape(&options.title)
+    );
+
+    if !options.filters.is_empty() {
+        let filter = options
+            .filters
+            .iter()
+            .map(|filter| format!("{}|{}", filter.name, filter.get_patterns(";")))
+            .collect::<Vec<String>>()
+            .join("|");
+
+        script += &format!("$dialog.Filter = '{}'; ", escape(&filter));
+    }
+
+    if let Some(directory) = &options.directory {
+        script += &format!(
+            "$dialog.InitialDirectory = '{}'; ",
+            escape(&directory.display().to_string())
+        );
+    }
+
+    if let Some(file_name) = &options.file_name {
+        script += &format!("$dialog.FileName = '{}'; ", escape(file_name));
+    }
+
+    script += "if ($dialog.ShowDialog() -eq 'OK') { $dialog.FileName } else { exit 1 }";
+
+    vec!["-NoProfile".to_string(), "-Command".to_string(), script]
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::{
+        get_zenity_args, open_file_dialog, save_file_dialog, FileDialogFilter, FileDialogKind,
+        FileDialogOptions,
+    };
+
+    #[test]
+    fn save_dialog_arguments_include_filters_and_initial_path() {
+        // Checks the public dialog API surface without showing a dialog.
+
+        let _open: fn(&FileDialogOptions) -> Result<Option<PathBuf>, String> = open_file_dialog;
+        let _save: fn(&FileDialogOptions) -> Result<Option<PathBuf>, String> = save_file_dialog;
+
+        let options = FileDialogOptions {
+            title: "Save Scene".to_string(),
+            directory: Some(PathBuf::from("/tmp/scenes")),
+            file_name: Some("untitled.json".to_string()),
+            filters: vec![FileDialogFilter::new("Scene", &["json", "scene"])],
+        };
+
+        let args = get_zenity_args(FileDialogKind::Save, &options);
+
+        assert_eq!(
+            args,
+            vec![
+                "--file-selection",
+                "--title=Save Scene",
+                "--save",
+                "--confirm-overwrite",
+                "--filename=/tmp/scenes/untitled.json",
+                "--file-filter=Scene | *.json *.scene",
+            ]
+        );
+    }
+}
@@ -2,6 +2,10 @@ use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
 
+pub mod dialog;
+pub mod resolver;
+pub mod watcher;
+
 pub fn read_lines(filepath: &Path) -> io::Result<io::Lines<io::BufReader<File>>> {
     let path_display = filepath.display();
 
@@ -0,0 +1,93 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+// Resolves logical asset paths (e.g., "textures/foo.png") against an ordered
+// list of search roots, so that assets can be relocated without code changes.
+#[derive(Default, Debug, Clone)]
+pub struct AssetResolver {
+    roots: Vec<PathBuf>,
+}
+
+impl AssetResolver {
+    pub fn new(roots: &[&Path]) -> Self {
+        Self {
+            roots: roots.iter().map(|root| root.to_path_buf()).collect(),
+        }
+    }
+
+    pub fn from_env(variable: &str) -> Self {
+        // Reads a list of roots, separated as in the platform's `PATH`.
+
+        match env::var_os(variable) {
+            Some(value) => Self {
+                roots: env::split_paths(&value).collect(),
+            },
+            None => Default::default(),
+        }
+    }
+
+    pub fn get_roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    pub fn add_root(&mut self, root: &Path) {
+        self.roots.push(root.to_path_buf());
+    }
+
+    pub fn resolve(&self, logical_path: &str) -> Result<PathBuf, String> {
+        let path = Path::new(logical_path);
+
+        if path.is_absolute() {
+            return if path.exists() {
+                Ok(path.to_path_buf())
+            } else {
+                Err(format!("Asset {} does not exist.", path.display()))
+            };
+        }
+
+        // Earlier roots take precedence over later ones.
+
+        self.roots
+            .iter()
+            .map(|root| root.join(path))
+            .find(|candidate| candidate.exists())
+            .ok_or(format!(
+                "Asset {} was not found in any of the roots {:?}.",
+                logical_path, self.roots
+            ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::testing::TempPath;
+
+    use super::AssetResolver;
+
+    #[test]
+    fn logical_path_resolves_to_the_first_existing_root() {
+        let base = TempPath::new_dir("resolver");
+
+        let (empty, first, second) = (base.join("empty"), base.join("first"), base.join("second"));
+
+        for root in [&empty, &first, &second] {
+            fs::create_dir_all(root.join("textures")).unwrap();
+        }
+
+        fs::write(first.join("textures/foo.png"), "first").unwrap();
+        fs::write(second.join("textures/foo.png"), "second").unwrap();
+
+        let resolver = AssetResolver::new(&[&empty, &first, &second]);
+
+        let resolved = resolver.resolve("textures/foo.png");
+
+        let missing = resolver.resolve("textures/bar.png");
+
+        assert_eq!(resolved, Ok(first.join("textures/foo.png")));
+        assert!(missing.is_err());
+    }
+}
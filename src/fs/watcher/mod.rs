@@ -0,0 +1,235 @@
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+impl fmt::Display for FileChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                FileChangeKind::Created => "Created",
+                FileChangeKind::Modified => "Modified",
+                FileChangeKind::Removed => "Removed",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChangeEvent {
+    pub path: PathBuf,
+    pub kind: FileChangeKind,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct FileSnapshot {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+// Watches a directory tree for changes by periodically re-scanning it. Meant
+// to be polled once per frame; scans are throttled to `interval`.
+#[derive(Debug, Clone)]
+pub struct DirectoryWatcher {
+    root: PathBuf,
+    interval: Duration,
+    last_scan: Option<Instant>,
+    snapshots: HashMap<PathBuf, FileSnapshot>,
+}
+
+impl DirectoryWatcher {
+    pub fn new(root: &Path, interval: Duration) -> Result<Self, String> {
+        if !root.is_dir() {
+            return Err(format!("Cannot watch {}: not a directory.", root.display()));
+        }
+
+        let mut watcher = Self {
+            root: root.to_path_buf(),
+            interval,
+            last_scan: None,
+            snapshots: Default::default(),
+        };
+
+        // Records the initial state of the tree, so that only subsequent
+        // changes are reported.
+
+        watcher.snapshots = watcher.scan()?;
+        watcher.last_scan.replace(Instant::now());
+
+        Ok(watcher)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn poll(&mut self) -> Result<Vec<FileChangeEvent>, String> {
+        if let Some(last_scan) = self.last_scan {
+            if last_scan.elapsed() < self.interval {
+                return Ok(vec![]);
+            }
+        }
+
+        self.last_scan.replace(Instant::now());
+
+        let snapshots = self.scan()?;
+
+        let mut events = vec![];
+
+        for (path, snapshot) in &snapshots {
+            match self.snapshots.get(path) {
+                Some(previous) => {
+                    if previous != snapshot {
+                        events.push(FileChangeEvent {
+                            path: path.clone(),
+                            kind: FileChangeKind::Modified,
+                        });
+                    }
+                }
+                None => events.push(FileChangeEvent {
+                    path: path.clone(),
+                    kind: FileChangeKind::Created,
+                }),
+            }
+        }
+
+        for path in self.snapshots.keys() {
+            if !snapshots.contains_key(path) {
+                events.push(FileChangeEvent {
+                    path: path.clone(),
+                    kind: FileChangeKind::Removed,
+                });
+            }
+        }
+
+        // Reports changes in a stable order.
+
+        events.sort_by(|a, b| a.path.cmp(&b.path));
+
+        self.snapshots = snapshots;
+
+        Ok(events)
+    }
+
+    fn scan(&self) -> Result<HashMap<PathBuf, FileSnapshot>, String> {
+        let mut snapshots = HashMap::new();
+
+        let mut directories = vec![self.root.clone()];
+
+        while let Some(directory) = directories.pop() {
+            // Directories (including the root) that no longer exist are
+            // skipped, so that their files are reported as removed.
+
+            let entries = match fs::read_dir(&directory) {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    return Err(format!("Failed to read {}: {}", directory.display(), err));
+                }
+            };
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+
+                // Skips entries that vanish mid-scan; the next scan will
+                // report them as removed.
+
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+
+                if metadata.is_dir() {
+                    directories.push(path);
+                } else {
+                    snapshots.insert(
+                        path,
+                        FileSnapshot {
+                            modified: metadata.modified().ok(),
+                            len: metadata.len(),
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(snapshots)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, time::Duration};
+
+    use crate::testing::TempPath;
+
+    use super::{DirectoryWatcher, FileChangeEvent, FileChangeKind};
+
+    #[test]
+    fn touching_a_watched_file_reports_its_path() {
+        let root = TempPath::new_dir("watcher");
+
+        let nested = root.join("textures");
+
+        fs::create_dir_all(&nested).unwrap();
+
+        let path = nested.join("albedo.png");
+
+        fs::write(&path, "a").unwrap();
+
+        let mut watcher = DirectoryWatcher::new(&root, Duration::ZERO).unwrap();
+
+        assert!(watcher.poll().unwrap().is_empty());
+
+        fs::write(&path, "ab").unwrap();
+
+        let events = watcher.poll().unwrap();
+
+        assert_eq!(
+            events,
+            vec![FileChangeEvent {
+                path,
+                kind: FileChangeKind::Modified,
+            }]
+        );
+    }
+
+    #[test]
+    fn removing_a_watched_directory_reports_its_files_as_removed() {
+        let root = TempPath::new_dir("watcher");
+
+        let nested = root.join("textures");
+
+        fs::create_dir_all(&nested).unwrap();
+
+        let path = nested.join("albedo.png");
+
+        fs::write(&path, "a").unwrap();
+
+        let mut watcher = DirectoryWatcher::new(&root, Duration::ZERO).unwrap();
+
+        fs::remove_dir_all(&*root).unwrap();
+
+        let events = watcher.poll().unwrap();
+
+        assert_eq!(
+            events,
+            vec![FileChangeEvent {
+                path,
+                kind: FileChangeKind::Removed,
+            }]
+        );
+
+        assert!(watcher.poll().unwrap().is_empty());
+    }
+}
@@ -1,6 +1,12 @@
 use std::mem;
 
-use crate::vec::vec3::{Vec3, Vec3A};
+use crate::{
+    matrix::Mat4,
+    vec::{
+        vec3::{Vec3, Vec3A},
+        vec4::Vec4,
+    },
+};
 
 use super::{
     accelerator::static_triangle_bvh::StaticTriangleBVH,
@@ -60,6 +66,41 @@ pub fn intersect_ray_triangle(
     }
 }
 
+// Intersects a world-space ray with a mesh's (object-space) BVH, placed in the
+// world by `world_transform`; returns the closest hit position, if any.
+pub fn intersect_ray_transformed_bvh(
+    ray: &mut Ray,
+    bvh: &StaticTriangleBVH,
+    world_transform: &Mat4,
+) -> Option<Vec3> {
+    let to_object_space = world_transform.affine_inverse()?;
+
+    // The direction isn't re-normalized, so that `t` means the same distance in
+    // both spaces.
+
+    let mut object_space_ray = Ray::new(
+        (Vec4::new(ray.origin, 1.0) * to_object_space).to_vec3(),
+        (Vec4::new(ray.direction, 0.0) * to_object_space).to_vec3(),
+    );
+
+    // Only hits closer than `ray.t` are reported.
+
+    object_space_ray.t = ray.t;
+
+    intersect_ray_bvh(&mut object_space_ray, bvh);
+
+    match object_space_ray.triangle {
+        Some(triangle) => {
+            ray.t = object_space_ray.t;
+
+            ray.triangle.replace(triangle);
+
+            Some(ray.origin + ray.direction * ray.t)
+        }
+        None => None,
+    }
+}
+
 pub fn test_ray_aabb(ray: &Ray, aabb: &AABB) -> f32 {
     let min = &aabb.min;
     let max = &aabb.max;
@@ -2,5 +2,6 @@ mod circle;
 pub mod line;
 mod rectangle;
 pub mod text;
+mod triangle;
 
 pub struct Graphics {}
@@ -0,0 +1,122 @@
+use super::Graphics;
+
+// Sub-pixel precision used when snapping floating-point points to the
+// fixed-point grid.
+static TRIANGLE_SUBPIXEL_PRECISION_BITS: u32 = 8;
+
+impl Graphics {
+    // Visits each pixel (of a `width` x `height` target) whose center lies
+    // inside the triangle; pixels centered on an edge shared by two triangles
+    // are visited for only one of them.
+    pub fn for_each_triangle_pixel<C>(
+        width: u32,
+        height: u32,
+        points: [(f32, f32); 3],
+        mut callback: C,
+    ) where
+        C: FnMut(u32, u32),
+    {
+        let scale = (1_i64 << TRIANGLE_SUBPIXEL_PRECISION_BITS) as f32;
+
+        let points = points.map(|(x, y)| ((x * scale).round() as i64, (y * scale).round() as i64));
+
+        Graphics::for_each_fixed_point_triangle_pixel(
+            width,
+            height,
+            points,
+            TRIANGLE_SUBPIXEL_PRECISION_BITS,
+            |x, y, _weights, _area| callback(x, y),
+        );
+    }
+
+    // Like `for_each_triangle_pixel()`, for points given in fixed-point
+    // (`subpixel_precision_bits`) coordinates; also passes the pixel's
+    // (unnormalized) barycentric weights for each point, in order, along with
+    // their sum (i.e., twice the triangle's area).
+    pub fn for_each_fixed_point_triangle_pixel<C>(
+        width: u32,
+        height: u32,
+        points: [(i64, i64); 3],
+        subpixel_precision_bits: u32,
+        mut callback: C,
+    ) where
+        C: FnMut(u32, u32, [i64; 3], i64),
+    {
+        let bits = subpixel_precision_bits;
+
+        let [p0, mut p1, mut p2] = points;
+
+        // Orients the triangle so that interior samples lie on the positive
+        // side of each edge.
+
+        let mut area = edge_function(p0, p1, p2);
+
+        if area == 0 || width == 0 || height == 0 {
+            return;
+        }
+
+        let is_flipped = area < 0;
+
+        if is_flipped {
+            std::mem::swap(&mut p1, &mut p2);
+
+            area = -area;
+        }
+
+        // Bounding box, in whole pixels, clamped to the target.
+
+        let to_pixel = |fixed: i64| (fixed >> bits).max(0);
+
+        let x_min = to_pixel(p0.0.min(p1.0).min(p2.0));
+        let y_min = to_pixel(p0.1.min(p1.1).min(p2.1));
+
+        let x_max = to_pixel(p0.0.max(p1.0).max(p2.0)).min(width as i64 - 1);
+        let y_max = to_pixel(p0.1.max(p1.1).max(p2.1)).min(height as i64 - 1);
+
+        let half_pixel = 1_i64 << bits >> 1;
+
+        let edges = [(p1, p2), (p2, p0), (p0, p1)];
+
+        for y in y_min..=y_max {
+            for x in x_min..=x_max {
+                // Samples each pixel at its center.
+
+                let sample = ((x << bits) + half_pixel, (y << bits) + half_pixel);
+
+                let mut weights = [0_i64; 3];
+
+                let is_covered = edges.iter().zip(weights.iter_mut()).all(|(edge, weight)| {
+                    *weight = edge_function(edge.0, edge.1, sample);
+
+                    *weight > 0 || (*weight == 0 && is_top_left_edge(edge.0, edge.1))
+                });
+
+                if !is_covered {
+                    continue;
+                }
+
+                if is_flipped {
+                    weights.swap(1, 2);
+                }
+
+                callback(x as u32, y as u32, weights, area);
+            }
+        }
+    }
+}
+
+// Twice the signed area of the (fixed-point) triangle `a`, `b`, `c`; positive
+// when `c` lies to the right of the edge from `a` to `b` (in screen space).
+fn edge_function(a: (i64, i64), b: (i64, i64), c: (i64, i64)) -> i64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+// Samples lying exactly on an edge belong to the triangle only if it's a left
+// edge, or a horizontal top edge; an edge shared by two triangles is
+// oriented oppositely in each, so exactly one of them covers such samples.
+fn is_top_left_edge(a: (i64, i64), b: (i64, i64)) -> bool {
+    let is_left = b.1 < a.1;
+    let is_top = a.1 == b.1 && b.0 > a.0;
+
+    is_left || is_top
+}
@@ -9,6 +9,7 @@ pub mod app;
 pub mod buffer;
 pub mod collections;
 pub mod color;
+pub mod command;
 pub mod debug;
 pub mod device;
 pub mod effect;
@@ -19,6 +20,7 @@ pub mod fs;
 pub mod geometry;
 pub mod graphics;
 pub mod hdr;
+pub mod log;
 pub mod material;
 pub mod matrix;
 pub mod mem;
@@ -33,7 +35,9 @@ pub mod shader;
 pub mod shaders;
 pub mod software_renderer;
 pub mod stats;
+pub mod testing;
 pub mod texture;
+pub mod thread_pool;
 pub mod time;
 pub mod transform;
 pub mod ui;
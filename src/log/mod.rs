@@ -0,0 +1,371 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    fmt,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::Path,
+    rc::Rc,
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Default, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
+)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+pub static LOG_LEVELS: [LogLevel; 5] = [
+    LogLevel::Trace,
+    LogLevel::Debug,
+    LogLevel::Info,
+    LogLevel::Warn,
+    LogLevel::Error,
+];
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                LogLevel::Trace => "TRACE",
+                LogLevel::Debug => "DEBUG",
+                LogLevel::Info => "INFO",
+                LogLevel::Warn => "WARN",
+                LogLevel::Error => "ERROR",
+            }
+        )
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LOG_LEVELS
+            .iter()
+            .find(|level| level.to_string().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| format!("Unknown log level `{}`.", s))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum LogCategory {
+    Render,
+    Scene,
+    IO,
+    Console,
+}
+
+impl fmt::Display for LogCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                LogCategory::Render => "render",
+                LogCategory::Scene => "scene",
+                LogCategory::IO => "io",
+                LogCategory::Console => "console",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub level: LogLevel,
+    pub category: LogCategory,
+    pub message: String,
+}
+
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] [{}] {}", self.level, self.category, self.message)
+    }
+}
+
+pub type LogSubscriber = Box<dyn FnMut(&LogRecord)>;
+
+// Keeps the most recent records (up to some capacity), e.g., for display in
+// an in-app console.
+#[derive(Default, Debug, Clone)]
+pub struct LogBuffer {
+    capacity: usize,
+    records: VecDeque<LogRecord>,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    pub fn push(&mut self, record: LogRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+
+        self.records.push_back(record);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LogRecord> {
+        self.records.iter()
+    }
+
+    // Iterates over records at or above the given level, oldest first.
+    pub fn iter_at_level(&self, level: LogLevel) -> impl Iterator<Item = &LogRecord> {
+        self.records
+            .iter()
+            .filter(move |record| record.level >= level)
+    }
+}
+
+// Filters records by level (optionally per category), then writes them to
+// stdout, an optional log file, and any subscribers (e.g., a console panel).
+pub struct Logger {
+    level: LogLevel,
+    category_levels: HashMap<LogCategory, LogLevel>,
+    pub write_to_stdout: bool,
+    file: Option<File>,
+    subscribers: Vec<LogSubscriber>,
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self {
+            level: Default::default(),
+            category_levels: Default::default(),
+            write_to_stdout: true,
+            file: None,
+            subscribers: vec![],
+        }
+    }
+}
+
+impl fmt::Debug for Logger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Logger")
+            .field("level", &self.level)
+            .field("category_levels", &self.category_levels)
+            .field("write_to_stdout", &self.write_to_stdout)
+            .field("file", &self.file)
+            .field("subscribers", &self.subscribers.len())
+            .finish()
+    }
+}
+
+impl Logger {
+    pub fn new(level: LogLevel) -> Self {
+        Self {
+            level,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_level(&mut self, level: LogLevel) {
+        self.level = level;
+    }
+
+    // Overrides the logger's level for one category.
+    pub fn set_category_level(&mut self, category: LogCategory, level: LogLevel) {
+        self.category_levels.insert(category, level);
+    }
+
+    pub fn get_level(&self, category: LogCategory) -> LogLevel {
+        self.category_levels
+            .get(&category)
+            .copied()
+            .unwrap_or(self.level)
+    }
+
+    pub fn is_enabled(&self, level: LogLevel, category: LogCategory) -> bool {
+        level >= self.get_level(category)
+    }
+
+    pub fn set_file(&mut self, path: &Path) -> Result<(), String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| format!("Failed to open log file {}: {}", path.display(), err))?;
+
+        self.file = Some(file);
+
+        Ok(())
+    }
+
+    pub fn subscribe(&mut self, subscriber: LogSubscriber) {
+        self.subscribers.push(subscriber);
+    }
+
+    pub fn subscribe_buffer(&mut self, buffer: Rc<RefCell<LogBuffer>>) {
+        self.subscribe(Box::new(move |record| {
+            buffer.borrow_mut().push(record.clone());
+        }));
+    }
+
+    // Returns whether the message passed the level filter.
+    pub fn log(&mut self, level: LogLevel, category: LogCategory, message: &str) -> bool {
+        if !self.is_enabled(level, category) {
+            return false;
+        }
+
+        let record = LogRecord {
+            level,
+            category,
+            message: message.to_string(),
+        };
+
+        if self.write_to_stdout {
+            println!("{}", record);
+        }
+
+        if let Some(file) = self.file.as_mut() {
+            writeln!(file, "{}", record).ok();
+        }
+
+        for subscriber in self.subscribers.iter_mut() {
+            subscriber(&record);
+        }
+
+        true
+    }
+}
+
+thread_local! {
+    pub static GLOBAL_LOGGER: RefCell<Logger> = Default::default();
+}
+
+pub fn log(level: LogLevel, category: LogCategory, message: &str) {
+    GLOBAL_LOGGER.with(|logger| {
+        logger.borrow_mut().log(level, category, message);
+    });
+}
+
+pub fn trace(category: LogCategory, message: &str) {
+    log(LogLevel::Trace, category, message)
+}
+
+pub fn debug(category: LogCategory, message: &str) {
+    log(LogLevel::Debug, category, message)
+}
+
+pub fn info(category: LogCategory, message: &str) {
+    log(LogLevel::Info, category, message)
+}
+
+pub fn warn(category: LogCategory, message: &str) {
+    log(LogLevel::Warn, category, message)
+}
+
+pub fn error(category: LogCategory, message: &str) {
+    log(LogLevel::Error, category, message)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{LogBuffer, LogCategory, LogLevel, Logger};
+
+    #[test]
+    fn messages_below_the_configured_level_are_filtered_out() {
+        let mut logger = Logger::new(LogLevel::Warn);
+
+        logger.write_to_stdout = false;
+
+        logger.set_category_level(LogCategory::IO, LogLevel::Debug);
+
+        let emitted = Rc::new(RefCell::new(vec![]));
+
+        let emitted_clone = emitted.clone();
+
+        logger.subscribe(Box::new(move |record| {
+            emitted_clone.borrow_mut().push(record.to_string());
+        }));
+
+        assert!(!logger.log(LogLevel::Info, LogCategory::Render, "filtered"));
+        assert!(logger.log(LogLevel::Warn, LogCategory::Render, "at level"));
+        assert!(logger.log(LogLevel::Error, LogCategory::Scene, "above level"));
+        assert!(logger.log(LogLevel::Debug, LogCategory::IO, "category override"));
+        assert!(!logger.log(LogLevel::Trace, LogCategory::IO, "filtered"));
+
+        assert_eq!(
+            *emitted.borrow(),
+            vec![
+                "[WARN] [render] at level",
+                "[ERROR] [scene] above level",
+                "[DEBUG] [io] category override",
+            ]
+        );
+    }
+
+    #[test]
+    fn warnings_are_buffered_with_their_level() {
+        let mut logger = Logger::new(LogLevel::Info);
+
+        logger.write_to_stdout = false;
+
+        let buffer = Rc::new(RefCell::new(LogBuffer::new(2)));
+
+        logger.subscribe_buffer(buffer.clone());
+
+        logger.log(LogLevel::Debug, LogCategory::Scene, "filtered");
+        logger.log(LogLevel::Warn, LogCategory::Render, "Missing texture.");
+
+        {
+            let buffer = buffer.borrow();
+
+            assert_eq!(buffer.len(), 1);
+
+            let record = buffer.iter().next().unwrap();
+
+            assert_eq!(record.level, LogLevel::Warn);
+            assert_eq!(record.category, LogCategory::Render);
+            assert_eq!(record.message, "Missing texture.");
+        }
+
+        // Older records are dropped once the buffer is full.
+
+        logger.log(LogLevel::Info, LogCategory::IO, "Loaded scene.");
+        logger.log(LogLevel::Error, LogCategory::IO, "Failed to save scene.");
+
+        let buffer = buffer.borrow();
+
+        assert_eq!(
+            buffer.iter().map(|record| record.level).collect::<Vec<_>>(),
+            vec![LogLevel::Info, LogLevel::Error]
+        );
+
+        assert_eq!(buffer.iter_at_level(LogLevel::Warn).count(), 1);
+    }
+}
@@ -22,12 +22,20 @@ pub struct Material {
     pub alpha_map: Option<Handle>,
     pub transparency: f32,
     pub transparency_map: Option<Handle>,
+    // Fragments with an alpha below this threshold are discarded; the rest are
+    // rendered as opaque (i.e., alpha-tested rather than blended).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alpha_cutoff: Option<f32>,
     pub translucency: Vec3,
     pub index_of_refraction: f32,
     pub bump_map: Option<Handle>,
     pub normal_map: Option<Handle>,
     pub displacement_map: Option<Handle>,
     pub displacement_scale: f32,
+    // Number of layers to march through, for parallax occlusion mapping; 0
+    // picks a count based on the view angle.
+    #[serde(default)]
+    pub parallax_steps: u8,
     pub ambient_occlusion_map: Option<Handle>,
     // Blinn-Phong attributes
     pub ambient_color: Vec3,
@@ -50,8 +58,18 @@ pub struct Material {
     pub clearcoat_roughness: f32,
     pub anisotropy: f32,
     pub anisotropy_rotation: f32,
+    // Strength of screen-space reflections on this material's surfaces.
+    #[serde(default)]
+    pub reflectivity: f32,
     // Miscellaneous
     pub decal_map: Option<Handle>,
+    // Baked irradiance, sampled by the mesh's lightmap UVs (from the `Vec3`
+    // texture arena); see `bake_lightmap()`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lightmap: Option<Handle>,
+    // Name of a (registered) fragment shader to use instead of the renderer's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fragment_shader: Option<String>,
 }
 
 impl PostDeserialize for Material {
@@ -70,6 +88,12 @@ impl Material {
         }
     }
 
+    // Whether fragments of this material are blended over what's behind them
+    // (rather than rendered as opaque, or alpha-tested).
+    pub fn is_transparent(&self) -> bool {
+        self.alpha_cutoff.is_none() && (self.transparency > 0.0 || self.alpha_map.is_some())
+    }
+
     pub fn load_all_maps(
         &mut self,
         texture_arena: &mut Arena<TextureMap>,
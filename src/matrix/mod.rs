@@ -376,6 +376,56 @@ impl Mat4 {
 
         result
     }
+
+    // Inverts an affine transform (i.e., a 3x3 linear part plus a translation
+    // row); returns `None` if the linear part is singular.
+    pub fn affine_inverse(&self) -> Option<Self> {
+        let m = &self.elements;
+
+        let cofactor_00 = m[1][1] * m[2][2] - m[1][2] * m[2][1];
+        let cofactor_01 = m[1][2] * m[2][0] - m[1][0] * m[2][2];
+        let cofactor_02 = m[1][0] * m[2][1] - m[1][1] * m[2][0];
+
+        let determinant = m[0][0] * cofactor_00 + m[0][1] * cofactor_01 + m[0][2] * cofactor_02;
+
+        if determinant.abs() < f32::EPSILON {
+            return None;
+        }
+
+        let d = 1.0 / determinant;
+
+        let linear = [
+            [
+                cofactor_00 * d,
+                (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * d,
+                (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * d,
+            ],
+            [
+                cofactor_01 * d,
+                (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * d,
+                (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * d,
+            ],
+            [
+                cofactor_02 * d,
+                (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * d,
+                (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * d,
+            ],
+        ];
+
+        // Undoes the translation (row vectors, so it's applied last).
+
+        let (tx, ty, tz) = (m[3][0], m[3][1], m[3][2]);
+
+        let translation =
+            [0, 1, 2].map(|j| -(tx * linear[0][j] + ty * linear[1][j] + tz * linear[2][j]));
+
+        Some(Self::new_from_elements([
+            [linear[0][0], linear[0][1], linear[0][2], 0.0],
+            [linear[1][0], linear[1][1], linear[1][2], 0.0],
+            [linear[2][0], linear[2][1], linear[2][2], 0.0],
+            [translation[0], translation[1], translation[2], 1.0],
+        ]))
+    }
 }
 
 impl Default for Mat4 {
@@ -0,0 +1,529 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::{
+    material::{mtl::load_mtl, Material},
+    mesh::{face::Face, mesh_geometry::MeshGeometry, obj::load::find_material_handle, Mesh},
+    resource::arena::Arena,
+    serde::PostDeserialize,
+    texture::map::TextureMap,
+    vec::{vec2::Vec2, vec3::Vec3},
+};
+
+// A binary cache of processed meshes (geometry, faces, and their tangent
+// frames), written to a cache directory with a `.meshbin` extension.
+//
+// Meshes reference materials by name; materials are re-loaded from each
+// mesh's `material_source` when reading the cache. BVHs are not cached.
+
+static MESH_CACHE_MAGIC: &[u8; 8] = b"MESHBIN\0";
+
+static MESH_CACHE_VERSION: u32 = 2;
+
+// Cache files are named for their source file, and disambiguated by a hash of
+// its path.
+pub fn get_mesh_cache_path(source_path: &Path, cache_directory: &Path) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+
+    source_path.hash(&mut hasher);
+
+    let stem = source_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    cache_directory.join(format!("{}-{:016x}.meshbin", stem, hasher.finish()))
+}
+
+pub fn is_mesh_cache_fresh(source_path: &Path, cache_path: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|metadata| metadata.modified());
+
+    match (modified(source_path), modified(cache_path)) {
+        (Ok(source_modified), Ok(cache_modified)) => cache_modified >= source_modified,
+        _ => false,
+    }
+}
+
+pub fn write_mesh_cache(
+    cache_path: &Path,
+    geometry: &MeshGeometry,
+    meshes: &[Mesh],
+    flags: u32,
+    material_arena: &Arena<Material>,
+) -> Result<(), String> {
+    let mut writer = MeshCacheWriter::default();
+
+    writer.bytes.extend_from_slice(MESH_CACHE_MAGIC);
+    writer.write_u32(MESH_CACHE_VERSION);
+    writer.write_u32(flags);
+
+    // Geometry
+
+    writer.write_len(geometry.vertices.len());
+
+    for vertex in geometry.vertices.iter() {
+        writer.write_vec3(vertex);
+    }
+
+    writer.write_len(geometry.normals.len());
+
+    for normal in geometry.normals.iter() {
+        writer.write_vec3(normal);
+    }
+
+    writer.write_len(geometry.uvs.len());
+
+    for uv in geometry.uvs.iter() {
+        writer.write_f32(uv.x);
+        writer.write_f32(uv.y);
+        writer.write_f32(uv.z);
+    }
+
+    // Meshes
+
+    writer.write_len(meshes.len());
+
+    for mesh in meshes {
+        let material_name = match &mesh.material {
+            Some(handle) => Some(material_arena.get(handle)?.item.name.clone()),
+            None => None,
+        };
+
+        writer.write_string(&mesh.object_source);
+        writer.write_string(&mesh.object_name);
+        writer.write_string(&mesh.group_name);
+        writer.write_string(&mesh.material_source);
+        writer.write_string(&material_name);
+
+        writer.write_len(mesh.faces.len());
+
+        for face in &mesh.faces {
+            for indices in [&face.vertices, &face.normals, &face.uvs] {
+                for index in indices {
+                    writer.write_len(*index);
+                }
+            }
+
+            for vectors in [&face.tangents, &face.bitangents] {
+                for vector in vectors {
+                    writer.write_vec3(vector);
+                }
+            }
+
+            writer.write_u32(face.smoothing_group);
+        }
+    }
+
+    if let Some(cache_directory) = cache_path.parent() {
+        fs::create_dir_all(cache_directory).map_err(|err| {
+            format!(
+                "Failed to create mesh cache directory {}: {}",
+                cache_directory.display(),
+                err
+            )
+        })?;
+    }
+
+    fs::write(cache_path, &writer.bytes).map_err(|err| {
+        format!(
+            "Failed to write mesh cache {}: {}",
+            cache_path.display(),
+            err
+        )
+    })
+}
+
+pub fn read_mesh_cache(
+    cache_path: &Path,
+    flags: u32,
+    material_arena: &mut Arena<Material>,
+    texture_arena: &mut Arena<TextureMap>,
+) -> Result<(Rc<MeshGeometry>, Vec<Mesh>), String> {
+    let bytes = fs::read(cache_path).map_err(|err| {
+        format!(
+            "Failed to read mesh cache {}: {}",
+            cache_path.display(),
+            err
+        )
+    })?;
+
+    let mut reader = MeshCacheReader {
+        bytes: &bytes,
+        offset: 0,
+    };
+
+    if reader.read_bytes(MESH_CACHE_MAGIC.len())? != MESH_CACHE_MAGIC {
+        return Err(format!("{} is not a mesh cache.", cache_path.display()));
+    }
+
+    let version = reader.read_u32()?;
+
+    if version != MESH_CACHE_VERSION {
+        return Err(format!(
+            "Mesh cache version {} is not supported (expected {}).",
+            version, MESH_CACHE_VERSION
+        ));
+    }
+
+    if reader.read_u32()? != flags {
+        return Err("Mesh cache was written with different processing flags.".to_string());
+    }
+
+    // Geometry
+
+    let vertices = (0..reader.read_len()?)
+        .map(|_| reader.read_vec3())
+        .collect::<Result<Vec<Vec3>, String>>()?;
+
+    let normals = (0..reader.read_len()?)
+        .map(|_| reader.read_vec3())
+        .collect::<Result<Vec<Vec3>, String>>()?;
+
+    let uvs = (0..reader.read_len()?)
+        .map(|_| {
+            Ok(Vec2 {
+                x: reader.read_f32()?,
+                y: reader.read_f32()?,
+                z: reader.read_f32()?,
+            })
+        })
+        .collect::<Result<Vec<Vec2>, String>>()?;
+
+    let geometry = Rc::new(MeshGeometry {
+        vertices: vertices.into_boxed_slice(),
+        normals: normals.into_boxed_slice(),
+        uvs: uvs.into_boxed_slice(),
+    });
+
+    // Meshes
+
+    let mesh_count = reader.read_len()?;
+
+    // Counts come from the (possibly corrupt) cache file, so we don't reserve
+    // space for them up front.
+
+    let mut meshes = vec![];
+
+    let mut loaded_material_sources: Vec<String> = vec![];
+
+    for _ in 0..mesh_count {
+        let object_source = reader.read_string()?;
+        let object_name = reader.read_string()?;
+        let group_name = reader.read_string()?;
+        let material_source = reader.read_string()?;
+        let material_name = reader.read_string()?;
+
+        let face_count = reader.read_len()?;
+
+        let mut faces = vec![];
+
+        for _ in 0..face_count {
+            let mut face = Face::default();
+
+            for indices in [&mut face.vertices, &mut face.normals, &mut face.uvs] {
+                for index in indices.iter_mut() {
+                    *index = reader.read_len()?;
+                }
+            }
+
+            for vectors in [&mut face.tangents, &mut face.bitangents] {
+                for vector in vectors.iter_mut() {
+                    *vector = reader.read_vec3()?;
+                }
+            }
+
+            face.smoothing_group = reader.read_u32()?;
+
+            faces.push(face);
+        }
+
+        if let Some(src) = &material_source {
+            if !loaded_material_sources.contains(src) {
+                load_mtl(src, material_arena, texture_arena);
+
+                loaded_material_sources.push(src.clone());
+            }
+        }
+
+        let material = material_name
+            .as_ref()
+            .and_then(|name| find_material_handle(material_arena, name));
+
+        let mut mesh = Mesh {
+            object_source,
+            object_name,
+            group_name,
+            material_source,
+            material,
+            geometry: geometry.clone(),
+            faces,
+            aabb: Default::default(),
+            static_triangle_bvh: None,
+        };
+
+        mesh.post_deserialize();
+
+        meshes.push(mesh);
+    }
+
+    Ok((geometry, meshes))
+}
+
+#[derive(Default)]
+struct MeshCacheWriter {
+    bytes: Vec<u8>,
+}
+
+impl MeshCacheWriter {
+    fn write_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_len(&mut self, value: usize) {
+        self.write_u32(value as u32);
+    }
+
+    fn write_f32(&mut self, value: f32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_vec3(&mut self, value: &Vec3) {
+        self.write_f32(value.x);
+        self.write_f32(value.y);
+        self.write_f32(value.z);
+    }
+
+    fn write_string(&mut self, value: &Option<String>) {
+        // Writes a presence byte, followed by a length-prefixed UTF-8 string.
+
+        match value {
+            Some(string) => {
+                self.bytes.push(1);
+                self.write_len(string.len());
+                self.bytes.extend_from_slice(string.as_bytes());
+            }
+            None => self.bytes.push(0),
+        }
+    }
+}
+
+struct MeshCacheReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> MeshCacheReader<'a> {
+    fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], String> {
+        if self.offset + count > self.bytes.len() {
+            return Err("Unexpected end of mesh cache.".to_string());
+        }
+
+        let bytes = &self.bytes[self.offset..self.offset + count];
+
+        self.offset += count;
+
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, String> {
+        let bytes = self.read_bytes(4)?;
+
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_len(&mut self) -> Result<usize, String> {
+        self.read_u32().map(|value| value as usize)
+    }
+
+    fn read_f32(&mut self) -> Result<f32, String> {
+        self.read_u32().map(f32::from_bits)
+    }
+
+    fn read_vec3(&mut self) -> Result<Vec3, String> {
+        Ok(Vec3 {
+            x: self.read_f32()?,
+            y: self.read_f32()?,
+            z: self.read_f32()?,
+        })
+    }
+
+    fn read_string(&mut self) -> Result<Option<String>, String> {
+        match self.read_bytes(1)?[0] {
+            0 => Ok(None),
+            _ => {
+                let len = self.read_len()?;
+
+                let bytes = self.read_bytes(len)?;
+
+                String::from_utf8(bytes.to_vec())
+                    .map(Some)
+                    .map_err(|err| format!("Invalid string in mesh cache: {}", err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs,
+        rc::Rc,
+        time::{Duration, SystemTime},
+    };
+
+    use crate::{
+        mesh::obj::load::{load_obj_cached, ProcessGeometryFlag},
+        resource::arena::Arena,
+        testing::TempPath,
+        vec::vec3,
+    };
+
+    use super::{get_mesh_cache_path, is_mesh_cache_fresh};
+
+    static QUAD_OBJ: &str = "o Quad
+v -1.0 -1.0 0.0
+v 1.0 -1.0 0.0
+v 1.0 1.0 0.0
+v -1.0 1.0 0.0
+vt 0.0 0.0
+vt 1.0 0.0
+vt 1.0 1.0
+vt 0.0 1.0
+vn 0.0 0.0 1.0
+f 1/1/1 2/2/1 3/3/1
+f 1/1/1 3/3/1 4/4/1
+";
+
+    // Two meshes (split by material) sharing one geometry, without normals.
+    static SPLIT_QUAD_OBJ_WITHOUT_NORMALS: &str = "o Quad
+v -1.0 -1.0 0.0
+v 1.0 -1.0 0.0
+v 1.0 1.0 0.0
+v -1.0 1.0 0.0
+usemtl first
+f 1 2 3
+usemtl second
+f 1 3 4
+";
+
+    #[test]
+    fn cached_mesh_reloads_identically() {
+        let directory = TempPath::new_dir("meshbin");
+
+        let source_path = directory.join("quad.obj");
+
+        let cache_directory = directory.join("cache");
+
+        fs::write(&source_path, QUAD_OBJ).unwrap();
+
+        let filepath = source_path.to_str().unwrap();
+
+        let flags = Some(ProcessGeometryFlag::Null | ProcessGeometryFlag::Center);
+
+        let mut material_arena = Arena::new();
+        let mut texture_arena = Arena::new();
+
+        // The first load parses the source and writes the cache.
+
+        let parsed = load_obj_cached(
+            filepath,
+            &cache_directory,
+            &mut material_arena,
+            &mut texture_arena,
+            flags,
+        );
+
+        let cache_path = get_mesh_cache_path(&source_path, &cache_directory);
+
+        assert!(is_mesh_cache_fresh(&source_path, &cache_path));
+
+        // Replaces the source, keeping the cache fresh, so that a re-parse
+        // would load a different mesh.
+
+        fs::write(&source_path, SPLIT_QUAD_OBJ_WITHOUT_NORMALS).unwrap();
+
+        fs::File::options()
+            .write(true)
+            .open(&cache_path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+
+        // Subsequent loads read the cache.
+
+        let cached = load_obj_cached(
+            filepath,
+            &cache_directory,
+            &mut material_arena,
+            &mut texture_arena,
+            flags,
+        );
+
+        assert_eq!(
+            serde_json::to_string(&parsed.0).unwrap(),
+            serde_json::to_string(&cached.0).unwrap()
+        );
+
+        assert_eq!(
+            serde_json::to_string(&parsed.1).unwrap(),
+            serde_json::to_string(&cached.1).unwrap()
+        );
+    }
+
+    #[test]
+    fn generated_normals_survive_the_cache() {
+        let directory = TempPath::new_dir("meshbin");
+
+        let source_path = directory.join("split_quad.obj");
+
+        fs::write(&source_path, SPLIT_QUAD_OBJ_WITHOUT_NORMALS).unwrap();
+
+        let filepath = source_path.to_str().unwrap();
+
+        let mut material_arena = Arena::new();
+        let mut texture_arena = Arena::new();
+
+        let cache_directory = directory.join("cache");
+
+        let mut load = || {
+            load_obj_cached(
+                filepath,
+                &cache_directory,
+                &mut material_arena,
+                &mut texture_arena,
+                None,
+            )
+        };
+
+        let parsed = load();
+        let cached = load();
+
+        for (geometry, meshes) in [(&parsed.0, &parsed.1), (&cached.0, &cached.1)] {
+            let meshes: Vec<_> = meshes
+                .iter()
+                .filter(|mesh| !mesh.faces.is_empty())
+                .collect();
+
+            assert_eq!(meshes.len(), 2);
+
+            for mesh in meshes {
+                // Every mesh still shares (and indexes into) the one geometry.
+
+                assert!(Rc::ptr_eq(&mesh.geometry, geometry));
+
+                for face in &mesh.faces {
+                    for normal_index in face.normals {
+                        let normal = geometry.normals[normal_index];
+
+                        assert!((normal - vec3::FORWARD).mag() < 1e-5);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,6 @@
-use std::rc::Rc;
-
 use serde::{Deserialize, Serialize};
 
-use crate::vec::vec3::Vec3;
+use crate::vec::{vec2::Vec2, vec3::Vec3};
 
 use super::mesh_geometry::MeshGeometry;
 
@@ -20,21 +18,26 @@ pub struct Face {
     pub uvs: [usize; 3],
     pub tangents: [Vec3; 3],
     pub bitangents: [Vec3; 3],
+    // OBJ smoothing group; 0 means "off" (flat-shaded).
+    #[serde(default)]
+    pub smoothing_group: u32,
+    // Second UV channel, used for lightmaps; see `Mesh::generate_lightmap_uvs()`.
+    #[serde(default)]
+    pub lightmap_uvs: [Vec2; 3],
 }
 
 pub(in crate::mesh) fn get_processed_faces(
-    geometry: &Rc<MeshGeometry>,
+    geometry: &MeshGeometry,
     partial_faces: &Vec<PartialFace>,
 ) -> Vec<Face> {
     let mut faces: Vec<Face> = Vec::<Face>::with_capacity(partial_faces.len());
 
     for partial_face in partial_faces {
-        let normals = match partial_face.normals {
-            Some(face_normal_indices) => face_normal_indices,
-            None => {
-                todo!("Compute flat normals for this face, insert into Geometry.normals, and return indices.");
-            }
-        };
+        // Faces without normals get placeholder indices; see
+        // `MeshGeometry::generate_smooth_normals()` and
+        // `MeshGeometry::generate_flat_normals()`.
+
+        let normals = partial_face.normals.unwrap_or_default();
 
         let (uvs, tangent, bitangent) = match partial_face.uvs {
             Some(face_uv_indices) => {
@@ -92,6 +95,8 @@ pub(in crate::mesh) fn get_processed_faces(
             uvs,
             tangents: [tangent, tangent, tangent],
             bitangents: [bitangent, bitangent, bitangent],
+            smoothing_group: 0,
+            lightmap_uvs: Default::default(),
         });
     }
 
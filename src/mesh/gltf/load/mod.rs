@@ -0,0 +1,915 @@
+use std::{collections::HashMap, fs, path::Path, rc::Rc};
+
+use serde::Deserialize;
+
+use crate::{
+    material::Material,
+    mesh::{face::get_processed_faces, mesh_geometry::MeshGeometry, Mesh, PartialFace},
+    resource::{arena::Arena, handle::Handle},
+    texture::map::{TextureMap, TextureMapStorageFormat},
+    vec::{vec2::Vec2, vec3::Vec3},
+};
+
+// Meshes (one per glTF mesh primitive), followed by a handle for each of the
+// file's materials, in the order they're declared.
+pub struct LoadGltfResult(pub Vec<Mesh>, pub Vec<Handle>);
+
+static GLB_MAGIC: u32 = 0x46546C67;
+static GLB_CHUNK_TYPE_JSON: u32 = 0x4E4F534A;
+static GLB_CHUNK_TYPE_BIN: u32 = 0x004E4942;
+
+static GLTF_COMPONENT_TYPE_BYTE: u32 = 5120;
+static GLTF_COMPONENT_TYPE_UNSIGNED_BYTE: u32 = 5121;
+static GLTF_COMPONENT_TYPE_SHORT: u32 = 5122;
+static GLTF_COMPONENT_TYPE_UNSIGNED_SHORT: u32 = 5123;
+static GLTF_COMPONENT_TYPE_UNSIGNED_INT: u32 = 5125;
+static GLTF_COMPONENT_TYPE_FLOAT: u32 = 5126;
+
+static GLTF_PRIMITIVE_MODE_TRIANGLES: u32 = 4;
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GltfDocument {
+    #[serde(default)]
+    buffers: Vec<GltfBuffer>,
+    #[serde(default)]
+    buffer_views: Vec<GltfBufferView>,
+    #[serde(default)]
+    accessors: Vec<GltfAccessor>,
+    #[serde(default)]
+    meshes: Vec<GltfMesh>,
+    #[serde(default)]
+    materials: Vec<GltfMaterial>,
+    #[serde(default)]
+    textures: Vec<GltfTexture>,
+    #[serde(default)]
+    images: Vec<GltfImage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GltfBuffer {
+    uri: Option<String>,
+    byte_length: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GltfBufferView {
+    buffer: usize,
+    #[serde(default)]
+    byte_offset: usize,
+    byte_length: usize,
+    byte_stride: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GltfAccessor {
+    buffer_view: Option<usize>,
+    #[serde(default)]
+    byte_offset: usize,
+    component_type: u32,
+    #[serde(default)]
+    normalized: bool,
+    count: usize,
+    #[serde(rename = "type")]
+    element_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GltfMesh {
+    name: Option<String>,
+    primitives: Vec<GltfPrimitive>,
+}
+
+fn default_primitive_mode() -> u32 {
+    GLTF_PRIMITIVE_MODE_TRIANGLES
+}
+
+#[derive(Debug, Deserialize)]
+struct GltfPrimitive {
+    attributes: HashMap<String, usize>,
+    indices: Option<usize>,
+    material: Option<usize>,
+    #[serde(default = "default_primitive_mode")]
+    mode: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GltfMaterial {
+    name: Option<String>,
+    pbr_metallic_roughness: Option<GltfPbrMetallicRoughness>,
+    normal_texture: Option<GltfTextureInfo>,
+    occlusion_texture: Option<GltfTextureInfo>,
+    emissive_texture: Option<GltfTextureInfo>,
+    emissive_factor: Option<[f32; 3]>,
+    alpha_mode: Option<String>,
+    alpha_cutoff: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GltfPbrMetallicRoughness {
+    base_color_factor: Option<[f32; 4]>,
+    base_color_texture: Option<GltfTextureInfo>,
+    metallic_factor: Option<f32>,
+    roughness_factor: Option<f32>,
+    metallic_roughness_texture: Option<GltfTextureInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GltfTextureInfo {
+    index: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct GltfTexture {
+    source: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GltfImage {
+    uri: Option<String>,
+    buffer_view: Option<usize>,
+}
+
+pub fn load_gltf(
+    filepath: &str,
+    material_arena: &mut Arena<Material>,
+    texture_u8_arena: &mut Arena<TextureMap>,
+) -> Result<LoadGltfResult, String> {
+    let path = Path::new(filepath);
+
+    let parent_path = path.parent().unwrap_or(Path::new("."));
+
+    let bytes =
+        fs::read(path).map_err(|err| format!("Failed to open file {}: {}", path.display(), err))?;
+
+    // Binary (`.glb`) files embed the JSON document, along with an optional
+    // binary chunk that backs the first buffer.
+
+    let (json, glb_binary_chunk) = if read_u32(&bytes, 0) == Some(GLB_MAGIC) {
+        parse_glb(&bytes)?
+    } else {
+        (bytes.as_slice(), None)
+    };
+
+    let document: GltfDocument = serde_json::from_slice(json)
+        .map_err(|err| format!("Failed to parse glTF {}: {}", path.display(), err))?;
+
+    let buffers = document
+        .buffers
+        .iter()
+        .enumerate()
+        .map(|(index, buffer)| {
+            let data = match &buffer.uri {
+                Some(uri) => read_uri(uri, parent_path)?,
+                None => match (index, glb_binary_chunk) {
+                    (0, Some(chunk)) => chunk.to_vec(),
+                    _ => return Err(format!("glTF buffer {} has no data.", index)),
+                },
+            };
+
+            if data.len() < buffer.byte_length {
+                return Err(format!(
+                    "glTF buffer {} holds {} bytes (expected {}).",
+                    index,
+                    data.len(),
+                    buffer.byte_length
+                ));
+            }
+
+            Ok(data)
+        })
+        .collect::<Result<Vec<Vec<u8>>, String>>()?;
+
+    // Materials
+
+    let mut loader = GltfTextureLoader {
+        document: &document,
+        buffers: &buffers,
+        path,
+        parent_path,
+        texture_u8_arena,
+        loaded: Default::default(),
+    };
+
+    let file_stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut material_handles = Vec::with_capacity(document.materials.len());
+
+    for (index, gltf_material) in document.materials.iter().enumerate() {
+        let name = match &gltf_material.name {
+            Some(name) => name.clone(),
+            None => format!("{}_material_{}", file_stem, index),
+        };
+
+        let mut material = Material::new(name);
+
+        material.material_source = Some(filepath.to_string());
+
+        // Per the spec, metallic and roughness factors default to 1.
+
+        material.metallic = 1.0;
+        material.roughness = 1.0;
+
+        if let Some(pbr) = &gltf_material.pbr_metallic_roughness {
+            if let Some([r, g, b, _a]) = pbr.base_color_factor {
+                material.albedo = Vec3 { x: r, y: g, z: b };
+            }
+
+            if let Some(metallic) = pbr.metallic_factor {
+                material.metallic = metallic;
+            }
+
+            if let Some(roughness) = pbr.roughness_factor {
+                material.roughness = roughness;
+            }
+
+            if let Some(info) = &pbr.base_color_texture {
+                material.albedo_map = loader.get_texture_handle(info.index, None)?;
+            }
+
+            // Roughness is stored in the green channel; metalness in blue.
+
+            if let Some(info) = &pbr.metallic_roughness_texture {
+                material.roughness_map = loader.get_texture_handle(info.index, Some(1))?;
+                material.metallic_map = loader.get_texture_handle(info.index, Some(2))?;
+            }
+        }
+
+        if let Some(info) = &gltf_material.normal_texture {
+            material.normal_map = loader.get_texture_handle(info.index, None)?;
+        }
+
+        if let Some(info) = &gltf_material.occlusion_texture {
+            material.ambient_occlusion_map = loader.get_texture_handle(info.index, Some(0))?;
+        }
+
+        if let Some(info) = &gltf_material.emissive_texture {
+            material.emissive_color_map = loader.get_texture_handle(info.index, None)?;
+        }
+
+        if let Some([r, g, b]) = gltf_material.emissive_factor {
+            material.emissive_color = Vec3 { x: r, y: g, z: b };
+        }
+
+        if gltf_material.alpha_mode.as_deref() == Some("MASK") {
+            material.alpha_cutoff = Some(gltf_material.alpha_cutoff.unwrap_or(0.5));
+        }
+
+        material_handles.push(material_arena.insert(material));
+    }
+
+    // Meshes
+
+    let mut meshes = vec![];
+
+    for gltf_mesh in &document.meshes {
+        for primitive in &gltf_mesh.primitives {
+            if primitive.mode != GLTF_PRIMITIVE_MODE_TRIANGLES {
+                println!(
+                    "Skipping glTF primitive with unsupported mode {}.",
+                    primitive.mode
+                );
+
+                continue;
+            }
+
+            let mut mesh = load_primitive(&document, &buffers, primitive, &material_handles)?;
+
+            mesh.object_source = Some(filepath.to_string());
+            mesh.object_name.clone_from(&gltf_mesh.name);
+            mesh.material_source = Some(filepath.to_string());
+
+            meshes.push(mesh);
+        }
+    }
+
+    println!(
+        "Parsed {} meshes and {} materials.",
+        meshes.len(),
+        material_handles.len()
+    );
+
+    Ok(LoadGltfResult(meshes, material_handles))
+}
+
+fn load_primitive(
+    document: &GltfDocument,
+    buffers: &[Vec<u8>],
+    primitive: &GltfPrimitive,
+    material_handles: &[Handle],
+) -> Result<Mesh, String> {
+    let attribute = |name: &str| primitive.attributes.get(name).copied();
+
+    let position_accessor = attribute("POSITION")
+        .ok_or_else(|| "glTF primitive has no POSITION attribute.".to_string())?;
+
+    let vertices: Vec<Vec3> = read_accessor_floats(document, buffers, position_accessor)?
+        .chunks_exact(3)
+        .map(|v| Vec3 {
+            x: v[0],
+            y: v[1],
+            z: v[2],
+        })
+        .collect();
+
+    let normals: Vec<Vec3> = match attribute("NORMAL") {
+        Some(accessor) => read_accessor_floats(document, buffers, accessor)?
+            .chunks_exact(3)
+            .map(|n| Vec3 {
+                x: n[0],
+                y: n[1],
+                z: n[2],
+            })
+            .collect(),
+        None => vec![],
+    };
+
+    // glTF places the UV origin at the top-left of the image.
+
+    let uvs: Vec<Vec2> = match attribute("TEXCOORD_0") {
+        Some(accessor) => read_accessor_floats(document, buffers, accessor)?
+            .chunks_exact(2)
+            .map(|uv| Vec2 {
+                x: uv[0],
+                y: 1.0 - uv[1],
+                z: 0.0,
+            })
+            .collect(),
+        None => vec![],
+    };
+
+    let indices = match primitive.indices {
+        Some(accessor) => read_accessor_indices(document, buffers, accessor)?,
+        None => (0..vertices.len()).collect(),
+    };
+
+    let has_normals = !normals.is_empty();
+    let has_uvs = !uvs.is_empty();
+
+    let mut partial_faces = Vec::with_capacity(indices.len() / 3);
+
+    for triangle in indices.chunks_exact(3) {
+        let triangle = [triangle[0], triangle[1], triangle[2]];
+
+        if triangle.iter().any(|index| *index >= vertices.len()) {
+            return Err("glTF primitive index is out of bounds.".to_string());
+        }
+
+        partial_faces.push(PartialFace {
+            vertices: triangle,
+            normals: has_normals.then_some(triangle),
+            uvs: has_uvs.then_some(triangle),
+        });
+    }
+
+    let mut geometry = MeshGeometry {
+        vertices: vertices.into_boxed_slice(),
+        normals: normals.into_boxed_slice(),
+        uvs: uvs.into_boxed_slice(),
+    };
+
+    let mut faces = get_processed_faces(&geometry, &partial_faces);
+
+    if !has_normals {
+        // Per the spec, primitives without normals are flat-shaded.
+
+        geometry.generate_flat_normals(&mut faces);
+    }
+
+    let material = match primitive.material {
+        Some(index) => Some(
+            *material_handles
+                .get(index)
+                .ok_or_else(|| format!("glTF material {} does not exist.", index))?,
+        ),
+        None => None,
+    };
+
+    Ok(Mesh::from_faces(Rc::new(geometry), faces, material))
+}
+
+struct GltfTextureLoader<'a> {
+    document: &'a GltfDocument,
+    buffers: &'a [Vec<u8>],
+    path: &'a Path,
+    parent_path: &'a Path,
+    texture_u8_arena: &'a mut Arena<TextureMap>,
+    // Keyed by glTF texture index and (optional) single-channel index.
+    loaded: HashMap<(usize, Option<usize>), Handle>,
+}
+
+impl GltfTextureLoader<'_> {
+    fn get_texture_handle(
+        &mut self,
+        texture_index: usize,
+        channel: Option<usize>,
+    ) -> Result<Option<Handle>, String> {
+        if let Some(handle) = self.loaded.get(&(texture_index, channel)) {
+            return Ok(Some(*handle));
+        }
+
+        let image_index = match self
+            .document
+            .textures
+            .get(texture_index)
+            .and_then(|texture| texture.source)
+        {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        let image = self
+            .document
+            .images
+            .get(image_index)
+            .ok_or_else(|| format!("glTF image {} does not exist.", image_index))?;
+
+        let storage_format = match channel {
+            Some(channel) => TextureMapStorageFormat::Index8(channel),
+            None => TextureMapStorageFormat::RGB24,
+        };
+
+        let texture_map = self.get_texture_map(image_index, image, storage_format)?;
+
+        let handle = self.texture_u8_arena.insert(texture_map);
+
+        self.loaded.insert((texture_index, channel), handle);
+
+        Ok(Some(handle))
+    }
+
+    fn get_texture_map(
+        &self,
+        image_index: usize,
+        image: &GltfImage,
+        storage_format: TextureMapStorageFormat,
+    ) -> Result<TextureMap, String> {
+        if let Some(uri) = &image.uri {
+            if !uri.starts_with("data:") {
+                let image_path = self.parent_path.join(uri);
+
+                return Ok(TextureMap::new(
+                    image_path.to_str().unwrap(),
+                    storage_format,
+                ));
+            }
+        }
+
+        // Embedded images are kept in memory, and decoded when the texture
+        // map is loaded.
+
+        let data = match (&image.uri, image.buffer_view) {
+            (Some(uri), _) => read_uri(uri, self.parent_path)?,
+            (None, Some(view_index)) => {
+                get_buffer_view_bytes(self.document, self.buffers, view_index)?.to_vec()
+            }
+            (None, None) => return Err(format!("glTF image {} has no data.", image_index)),
+        };
+
+        let name = format!("{}#images/{}", self.path.display(), image_index);
+
+        Ok(TextureMap::from_encoded_bytes(&name, data, storage_format))
+    }
+}
+
+fn parse_glb(bytes: &[u8]) -> Result<(&[u8], Option<&[u8]>), String> {
+    let version = read_u32(bytes, 4).unwrap_or(0);
+
+    if version != 2 {
+        return Err(format!("GLB version {} is not supported.", version));
+    }
+
+    let mut json = None;
+    let mut binary = None;
+
+    // Chunks follow the 12-byte header; each begins with its length and type.
+
+    let mut offset = 12;
+
+    while let (Some(length), Some(chunk_type)) =
+        (read_u32(bytes, offset), read_u32(bytes, offset + 4))
+    {
+        let start = offset + 8;
+        let end = start + length as usize;
+
+        if end > bytes.len() {
+            return Err("Unexpected end of GLB chunk.".to_string());
+        }
+
+        if chunk_type == GLB_CHUNK_TYPE_JSON && json.is_none() {
+            json = Some(&bytes[start..end]);
+        } else if chunk_type == GLB_CHUNK_TYPE_BIN && binary.is_none() {
+            binary = Some(&bytes[start..end]);
+        }
+
+        offset = end;
+    }
+
+    match json {
+        Some(json) => Ok((json, binary)),
+        None => Err("GLB file has no JSON chunk.".to_string()),
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_uri(uri: &str, parent_path: &Path) -> Result<Vec<u8>, String> {
+    match uri.strip_prefix("data:") {
+        Some(data_uri) => match data_uri.split_once(";base64,") {
+            Some((_mime_type, encoded)) => decode_base64(encoded),
+            None => Err("Only base64-encoded glTF data URIs are supported.".to_string()),
+        },
+        None => {
+            let path = parent_path.join(uri);
+
+            fs::read(&path).map_err(|err| format!("Failed to read {}: {}", path.display(), err))
+        }
+    }
+}
+
+fn decode_base64(encoded: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 3 / 4);
+
+    let mut accumulator: u32 = 0;
+    let mut accumulated_bits = 0;
+
+    for c in encoded.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => break,
+            c if c.is_ascii_whitespace() => continue,
+            _ => return Err(format!("Invalid base64 character '{}'.", c as char)),
+        };
+
+        accumulator = (accumulator << 6) | value as u32;
+        accumulated_bits += 6;
+
+        if accumulated_bits >= 8 {
+            accumulated_bits -= 8;
+
+            bytes.push((accumulator >> accumulated_bits) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
+
+fn get_buffer_view_bytes<'a>(
+    document: &GltfDocument,
+    buffers: &'a [Vec<u8>],
+    view_index: usize,
+) -> Result<&'a [u8], String> {
+    let view = document
+        .buffer_views
+        .get(view_index)
+        .ok_or_else(|| format!("glTF buffer view {} does not exist.", view_index))?;
+
+    buffers
+        .get(view.buffer)
+        .and_then(|buffer| buffer.get(view.byte_offset..view.byte_offset + view.byte_length))
+        .ok_or_else(|| format!("glTF buffer view {} is out of bounds.", view_index))
+}
+
+fn get_component_size(component_type: u32) -> Result<usize, String> {
+    match component_type {
+        t if t == GLTF_COMPONENT_TYPE_BYTE || t == GLTF_COMPONENT_TYPE_UNSIGNED_BYTE => Ok(1),
+        t if t == GLTF_COMPONENT_TYPE_SHORT || t == GLTF_COMPONENT_TYPE_UNSIGNED_SHORT => Ok(2),
+        t if t == GLTF_COMPONENT_TYPE_UNSIGNED_INT || t == GLTF_COMPONENT_TYPE_FLOAT => Ok(4),
+        _ => Err(format!("Unknown glTF component type {}.", component_type)),
+    }
+}
+
+fn get_component_count(element_type: &str) -> Result<usize, String> {
+    match element_type {
+        "SCALAR" => Ok(1),
+        "VEC2" => Ok(2),
+        "VEC3" => Ok(3),
+        "VEC4" => Ok(4),
+        _ => Err(format!("Unsupported glTF accessor type {}.", element_type)),
+    }
+}
+
+// Calls `on_component` with the bytes of each component of each element, in
+// order; accessors without a buffer view read as zeros.
+fn visit_accessor_components(
+    document: &GltfDocument,
+    buffers: &[Vec<u8>],
+    accessor_index: usize,
+    on_component: &mut dyn FnMut(&[u8]),
+) -> Result<(), String> {
+    let accessor = document
+        .accessors
+        .get(accessor_index)
+        .ok_or_else(|| format!("glTF accessor {} does not exist.", accessor_index))?;
+
+    let component_size = get_component_size(accessor.component_type)?;
+    let component_count = get_component_count(&accessor.element_type)?;
+
+    let element_size = component_size * component_count;
+
+    let view_index = match accessor.buffer_view {
+        Some(index) => index,
+        None => {
+            let zeros = [0; 4];
+
+            for _ in 0..accessor.count * component_count {
+                on_component(&zeros[..component_size]);
+            }
+
+            return Ok(());
+        }
+    };
+
+    let view_bytes = get_buffer_view_bytes(document, buffers, view_index)?;
+
+    let stride = document.buffer_views[view_index]
+        .byte_stride
+        .unwrap_or(element_size);
+
+    for element in 0..accessor.count {
+        let start = accessor.byte_offset + element * stride;
+
+        let element_bytes = view_bytes
+            .get(start..start + element_size)
+            .ok_or_else(|| format!("glTF accessor {} is out of bounds.", accessor_index))?;
+
+        for component in element_bytes.chunks_exact(component_size) {
+            on_component(component);
+        }
+    }
+
+    Ok(())
+}
+
+fn read_accessor_floats(
+    document: &GltfDocument,
+    buffers: &[Vec<u8>],
+    accessor_index: usize,
+) -> Result<Vec<f32>, String> {
+    let accessor = document
+        .accessors
+        .get(accessor_index)
+        .ok_or_else(|| format!("glTF accessor {} does not exist.", accessor_index))?;
+
+    let (component_type, normalized) = (accessor.component_type, accessor.normalized);
+
+    let mut values = vec![];
+
+    visit_accessor_components(document, buffers, accessor_index, &mut |b| {
+        let value = match b.len() {
+            1 if component_type == GLTF_COMPONENT_TYPE_BYTE => {
+                let value = b[0] as i8 as f32;
+
+                if normalized {
+                    (value / 127.0).max(-1.0)
+                } else {
+                    value
+                }
+            }
+            1 => {
+                let value = b[0] as f32;
+
+                if normalized {
+                    value / 255.0
+                } else {
+                    value
+                }
+            }
+            2 if component_type == GLTF_COMPONENT_TYPE_SHORT => {
+                let value = i16::from_le_bytes([b[0], b[1]]) as f32;
+
+                if normalized {
+                    (value / 32767.0).max(-1.0)
+                } else {
+                    value
+                }
+            }
+            2 => {
+                let value = u16::from_le_bytes([b[0], b[1]]) as f32;
+
+                if normalized {
+                    value / 65535.0
+                } else {
+                    value
+                }
+            }
+            _ if component_type == GLTF_COMPONENT_TYPE_FLOAT => {
+                f32::from_le_bytes([b[0], b[1], b[2], b[3]])
+            }
+            _ => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32,
+        };
+
+        values.push(value);
+    })?;
+
+    Ok(values)
+}
+
+fn read_accessor_indices(
+    document: &GltfDocument,
+    buffers: &[Vec<u8>],
+    accessor_index: usize,
+) -> Result<Vec<usize>, String> {
+    let mut indices = vec![];
+
+    visit_accessor_components(document, buffers, accessor_index, &mut |b| {
+        let index = match b.len() {
+            1 => b[0] as usize,
+            2 => u16::from_le_bytes([b[0], b[1]]) as usize,
+            _ => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize,
+        };
+
+        indices.push(index);
+    })?;
+
+    Ok(indices)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::{resource::arena::Arena, testing::TempPath};
+
+    use super::{decode_base64, load_gltf};
+
+    fn make_single_triangle_glb() -> Vec<u8> {
+        // Positions (3 x VEC3 floats), followed by 3 unsigned short indices
+        // (padded to a 4-byte boundary).
+
+        let mut binary: Vec<u8> = vec![];
+
+        for position in [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]] {
+            for component in position {
+                binary.extend_from_slice(&component.to_le_bytes());
+            }
+        }
+
+        for index in [0u16, 1, 2] {
+            binary.extend_from_slice(&index.to_le_bytes());
+        }
+
+        binary.extend_from_slice(&[0, 0]);
+
+        let mut json = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 44 }],
+            "bufferViews": [
+                { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+                { "buffer": 0, "byteOffset": 36, "byteLength": 6 }
+            ],
+            "accessors": [
+                { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" },
+                { "bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR" }
+            ],
+            "materials": [{
+                "name": "Red",
+                "pbrMetallicRoughness": {
+                    "baseColorFactor": [1.0, 0.0, 0.0, 1.0],
+                    "metallicFactor": 0.0,
+                    "roughnessFactor": 0.5
+                }
+            }],
+            "meshes": [{
+                "name": "Triangle",
+                "primitives": [{
+                    "attributes": { "POSITION": 0 },
+                    "indices": 1,
+                    "material": 0
+                }]
+            }]
+        }"#
+        .as_bytes()
+        .to_vec();
+
+        while json.len() % 4 != 0 {
+            json.push(b' ');
+        }
+
+        let mut glb: Vec<u8> = vec![];
+
+        let total_length = 12 + 8 + json.len() + 8 + binary.len();
+
+        glb.extend_from_slice(b"glTF");
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&(total_length as u32).to_le_bytes());
+
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend_from_slice(&json);
+
+        glb.extend_from_slice(&(binary.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"BIN\0");
+        glb.extend_from_slice(&binary);
+
+        glb
+    }
+
+    #[test]
+    fn single_triangle_glb_loads_one_mesh_with_a_material() {
+        let directory = TempPath::new_dir("gltf");
+
+        let path = directory.join("triangle.glb");
+
+        fs::write(&path, make_single_triangle_glb()).unwrap();
+
+        let mut material_arena = Arena::new();
+        let mut texture_arena = Arena::new();
+
+        let result = load_gltf(
+            path.to_str().unwrap(),
+            &mut material_arena,
+            &mut texture_arena,
+        );
+
+        let result = result.unwrap();
+
+        let meshes = result.0;
+
+        assert_eq!(meshes.len(), 1);
+
+        let mesh = &meshes[0];
+
+        assert_eq!(mesh.object_name.as_deref(), Some("Triangle"));
+        assert_eq!(mesh.geometry.vertices.len(), 3);
+        assert_eq!(mesh.faces.len(), 1);
+        assert_eq!(mesh.faces[0].vertices, [0, 1, 2]);
+
+        let handle = mesh.material.unwrap();
+
+        assert_eq!(result.1, vec![handle]);
+
+        let material = &material_arena.get(&handle).unwrap().item;
+
+        assert_eq!(material.name, "Red");
+        assert_eq!((material.albedo.x, material.albedo.y), (1.0, 0.0));
+        assert_eq!(material.metallic, 0.0);
+        assert_eq!(material.roughness, 0.5);
+    }
+
+    #[test]
+    fn embedded_images_are_kept_in_memory() {
+        let directory = TempPath::new_dir("gltf");
+
+        let path = directory.join("textured.gltf");
+
+        fs::write(
+            &path,
+            r#"{
+                "asset": { "version": "2.0" },
+                "materials": [{
+                    "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } }
+                }],
+                "textures": [{ "source": 0 }],
+                "images": [{ "uri": "data:image/png;base64,aGVsbG8=" }]
+            }"#,
+        )
+        .unwrap();
+
+        let mut material_arena = Arena::new();
+        let mut texture_arena = Arena::new();
+
+        let result = load_gltf(
+            path.to_str().unwrap(),
+            &mut material_arena,
+            &mut texture_arena,
+        )
+        .unwrap();
+
+        let material = &material_arena.get(&result.1[0]).unwrap().item;
+
+        let albedo_map = &texture_arena
+            .get(&material.albedo_map.unwrap())
+            .unwrap()
+            .item;
+
+        assert_eq!(albedo_map.encoded_bytes.as_deref(), Some(&b"hello"[..]));
+
+        // Nothing was written alongside the file.
+
+        assert_eq!(fs::read_dir(&*directory).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn base64_data_decodes() {
+        assert_eq!(decode_base64("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode_base64("AAECAw==").unwrap(), vec![0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::vec::{vec2::Vec2, vec3::Vec3};
+
+use super::Mesh;
+
+// Empty texels kept on each side of a chart, so that filtering near its edges
+// doesn't pick up neighboring charts.
+static CHART_PADDING: u32 = 1;
+
+static MAX_PACKING_ATTEMPTS: usize = 200;
+
+// A face, laid flat (in its own plane), with its longest edge along the x-axis.
+#[derive(Debug, Copy, Clone)]
+struct Chart {
+    points: [(f32, f32); 3],
+    width: f32,
+    height: f32,
+}
+
+impl Chart {
+    fn from_triangle(vertices: [Vec3; 3]) -> Self {
+        let mut chart = Self {
+            points: Default::default(),
+            width: 0.0,
+            height: 0.0,
+        };
+
+        let (a, b) = [(0, 1), (1, 2), (2, 0)]
+            .into_iter()
+            .max_by(|(a0, b0), (a1, b1)| {
+                let length0 = (vertices[*b0] - vertices[*a0]).mag();
+                let length1 = (vertices[*b1] - vertices[*a1]).mag();
+
+                length0.total_cmp(&length1)
+            })
+            .unwrap();
+
+        let c = 3 - a - b;
+
+        let length = (vertices[b] - vertices[a]).mag();
+
+        if length == 0.0 {
+            return chart;
+        }
+
+        let edge_direction = (vertices[b] - vertices[a]) / length;
+
+        let a_to_c = vertices[c] - vertices[a];
+
+        // The third vertex projects onto the longest edge.
+
+        let along = a_to_c.dot(edge_direction).clamp(0.0, length);
+        let across = (a_to_c - edge_direction * along).mag();
+
+        chart.points[a] = (0.0, 0.0);
+        chart.points[b] = (length, 0.0);
+        chart.points[c] = (along, across);
+
+        chart.width = length;
+        chart.height = across;
+
+        chart
+    }
+
+    // Size of this chart's cell, in texels, including padding.
+    fn get_cell_size(&self, scale: f32) -> (u32, u32) {
+        (
+            (self.width * scale).ceil() as u32 + 2 * CHART_PADDING,
+            (self.height * scale).ceil() as u32 + 2 * CHART_PADDING,
+        )
+    }
+}
+
+// Maps a lightmap UV to (fractional) texel coordinates, where texel (x, y)
+// covers [x, x + 1) by [y, y + 1); agrees with the texture samplers, which
+// place texel centers at `uv * (size - 1)`.
+pub fn lightmap_uv_to_texel(uv: Vec2, resolution: u32) -> (f32, f32) {
+    let scale = (resolution - 1) as f32;
+
+    (uv.x * scale + 0.5, (1.0 - uv.y) * scale + 0.5)
+}
+
+fn texel_to_lightmap_uv(x: f32, y: f32, resolution: u32) -> Vec2 {
+    let scale = (resolution - 1) as f32;
+
+    Vec2 {
+        x: (x - 0.5) / scale,
+        y: 1.0 - (y - 0.5) / scale,
+        z: 0.0,
+    }
+}
+
+// Places each chart (in the given order) left-to-right, in rows, returning the
+// top-left texel of each chart's cell; returns `None` if the charts don't fit.
+fn pack_charts(
+    charts: &[Chart],
+    order: &[usize],
+    scale: f32,
+    resolution: u32,
+) -> Option<Vec<(u32, u32)>> {
+    let mut offsets = vec![(0, 0); charts.len()];
+
+    let (mut x, mut y, mut row_height) = (0, 0, 0);
+
+    for chart_index in order {
+        let (width, height) = charts[*chart_index].get_cell_size(scale);
+
+        if width > resolution {
+            return None;
+        }
+
+        if x + width > resolution {
+            x = 0;
+            y += row_height;
+            row_height = 0;
+        }
+
+        if y + height > resolution {
+            return None;
+        }
+
+        offsets[*chart_index] = (x, y);
+
+        x += width;
+        row_height = row_height.max(height);
+    }
+
+    Some(offsets)
+}
+
+impl Mesh {
+    pub fn generate_lightmap_uvs(&mut self, resolution: u32) -> Result<(), String> {
+        // Gives each face its own chart in a second (non-overlapping) UV
+        // channel, for a `resolution` x `resolution` lightmap; all charts
+        // share the same texel density.
+
+        if resolution < 2 {
+            return Err(format!("Invalid lightmap resolution {}.", resolution));
+        }
+
+        let vertices = &self.geometry.vertices;
+
+        let charts: Vec<Chart> = self
+            .faces
+            .iter()
+            .map(|face| Chart::from_triangle(face.vertices.map(|index| vertices[index])))
+            .collect();
+
+        // Tallest charts first, so that each row wastes less space.
+
+        let mut order: Vec<usize> = (0..charts.len()).collect();
+
+        order.sort_by(|a, b| charts[*b].height.total_cmp(&charts[*a].height));
+
+        // Starts from a density at which the charts' total area would fill the
+        // lightmap, and shrinks it until every chart fits.
+
+        let total_area: f32 = charts.iter().map(|chart| chart.width * chart.height).sum();
+
+        let mut scale = if total_area > 0.0 {
+            resolution as f32 / total_area.sqrt()
+        } else {
+            1.0
+        };
+
+        for _ in 0..MAX_PACKING_ATTEMPTS {
+            if let Some(offsets) = pack_charts(&charts, &order, scale, resolution) {
+                for ((face, chart), (x, y)) in self.faces.iter_mut().zip(&charts).zip(offsets) {
+                    face.lightmap_uvs = chart.points.map(|(u, v)| {
+                        texel_to_lightmap_uv(
+                            (x + CHART_PADDING) as f32 + u * scale,
+                            (y + CHART_PADDING) as f32 + v * scale,
+                            resolution,
+                        )
+                    });
+                }
+
+                return Ok(());
+            }
+
+            scale *= 0.9;
+        }
+
+        Err(format!(
+            "Failed to pack {} faces into a {}x{} lightmap.",
+            self.faces.len(),
+            resolution,
+            resolution
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{graphics::Graphics, mesh::primitive::cube};
+
+    use super::lightmap_uv_to_texel;
+
+    #[test]
+    fn lightmap_charts_do_not_overlap() {
+        let resolution = 64;
+
+        let mut mesh = cube::generate(2.0, 2.0, 2.0);
+
+        mesh.generate_lightmap_uvs(resolution).unwrap();
+
+        let mut owners: Vec<Option<usize>> = vec![None; (resolution * resolution) as usize];
+
+        for (face_index, face) in mesh.faces.iter().enumerate() {
+            let points = face
+                .lightmap_uvs
+                .map(|uv| lightmap_uv_to_texel(uv, resolution));
+
+            let mut texel_count = 0;
+
+            Graphics::for_each_triangle_pixel(resolution, resolution, points, |x, y| {
+                let owner = &mut owners[(y * resolution + x) as usize];
+
+                assert_eq!(*owner, None, "Face {} overlaps another face.", face_index);
+
+                owner.replace(face_index);
+
+                texel_count += 1;
+            });
+
+            assert!(texel_count > 0);
+        }
+    }
+}
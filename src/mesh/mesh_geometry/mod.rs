@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{collections::HashMap, fmt};
 
 use serde::{Deserialize, Serialize};
 
@@ -7,6 +7,8 @@ use crate::{
     vec::{vec2::Vec2, vec3::Vec3},
 };
 
+use super::face::Face;
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct MeshGeometry {
     pub vertices: Box<[Vec3]>,
@@ -39,4 +41,92 @@ impl MeshGeometry {
             vertex.z -= center.z;
         }
     }
+
+    // Normal generation appends to this geometry's normals, and re-points the
+    // faces at them; geometry shared by several meshes should have normals
+    // generated for each mesh's faces before it's shared (see `Rc`), so that
+    // every mesh indexes into the same normals.
+
+    pub fn generate_flat_normals(&mut self, faces: &mut [Face]) {
+        // Gives each face its own normal, shared by its 3 vertices.
+
+        let face_normals = self.get_face_normals(faces);
+
+        let mut normals = self.normals.to_vec();
+
+        for (face, normal) in faces.iter_mut().zip(face_normals) {
+            face.normals = [normals.len(); 3];
+
+            normals.push(if normal.is_zero() {
+                normal
+            } else {
+                normal.as_normal()
+            });
+        }
+
+        self.normals = normals.into_boxed_slice();
+    }
+
+    pub fn generate_smooth_normals(&mut self, faces: &mut [Face]) {
+        // Averages the (area-weighted) normals of all faces that share a
+        // vertex, within each smoothing group; faces outside of any smoothing
+        // group (group 0) are flat-shaded.
+
+        let face_normals = self.get_face_normals(faces);
+
+        let mut normal_index_per_vertex = HashMap::<(usize, u32), usize>::new();
+
+        let mut normals = self.normals.to_vec();
+
+        for (face, face_normal) in faces.iter_mut().zip(face_normals.iter()) {
+            if face.smoothing_group == 0 {
+                face.normals = [normals.len(); 3];
+
+                normals.push(*face_normal);
+
+                continue;
+            }
+
+            for (position, vertex_index) in face.vertices.iter().enumerate() {
+                let key = (*vertex_index, face.smoothing_group);
+
+                let normal_index = *normal_index_per_vertex.entry(key).or_insert_with(|| {
+                    normals.push(Default::default());
+
+                    normals.len() - 1
+                });
+
+                // Accumulates the unnormalized face normal, whose length is
+                // proportional to the face's area.
+
+                normals[normal_index] += *face_normal;
+
+                face.normals[position] = normal_index;
+            }
+        }
+
+        let generated_normals_start = self.normals.len();
+
+        for normal in normals.iter_mut().skip(generated_normals_start) {
+            // Leaves degenerate (zero-area) normals as-is.
+
+            if !normal.is_zero() {
+                *normal = normal.as_normal();
+            }
+        }
+
+        self.normals = normals.into_boxed_slice();
+    }
+
+    fn get_face_normals(&self, faces: &[Face]) -> Vec<Vec3> {
+        faces
+            .iter()
+            .map(|face| {
+                let (v0, v1, v2) =
+                    self.get_vertices(face.vertices[0], face.vertices[1], face.vertices[2]);
+
+                (*v1 - *v0).cross(*v2 - *v0)
+            })
+            .collect()
+    }
 }
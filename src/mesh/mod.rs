@@ -12,10 +12,14 @@ use crate::{
 use face::{get_processed_faces, Face, PartialFace};
 use mesh_geometry::MeshGeometry;
 
+pub mod cache;
 pub mod face;
+pub mod gltf;
+pub mod lightmap;
 pub mod mesh_geometry;
 pub mod obj;
 pub mod primitive;
+pub mod uv_layout;
 
 static TANGENT_BITANGENT_SMOOTHING_LIKENESS_THRESHOLD: f32 = 4.0;
 
@@ -106,6 +110,14 @@ impl Mesh {
     ) -> Self {
         let faces = get_processed_faces(&geometry, &partial_faces);
 
+        Self::from_faces(geometry, faces, material)
+    }
+
+    pub fn from_faces(
+        geometry: Rc<MeshGeometry>,
+        faces: Vec<Face>,
+        material: Option<Handle>,
+    ) -> Self {
         let mut mesh = Mesh {
             object_source: None,
             object_name: None,
@@ -125,6 +137,150 @@ impl Mesh {
         mesh
     }
 
+    // Regenerates this mesh's normals (see `MeshGeometry`); geometry that's
+    // shared with other meshes is first copied, so that it's left untouched
+    // for them.
+
+    pub fn generate_flat_normals(&mut self) {
+        Rc::make_mut(&mut self.geometry).generate_flat_normals(&mut self.faces);
+    }
+
+    pub fn generate_smooth_normals(&mut self) {
+        Rc::make_mut(&mut self.geometry).generate_smooth_normals(&mut self.faces);
+    }
+
+    pub fn generate_tangents(&mut self) -> Result<(), String> {
+        // Computes per-vertex tangents and bitangents from positions and UVs
+        // (Lengyel's method): accumulates each face's UV-space derivatives per
+        // vertex, then orthogonalizes the tangent against the vertex normal.
+
+        if self.geometry.uvs.is_empty() {
+            return Err("Cannot generate tangents for a mesh without UVs.".to_string());
+        }
+
+        let geometry = &self.geometry;
+
+        // A "vertex" is a unique (position, normal, UV) combination.
+
+        let mut accumulated = HashMap::<(usize, usize, usize), (Vec3, Vec3)>::new();
+
+        for face in &self.faces {
+            let (v0, v1, v2) = (
+                geometry.vertices[face.vertices[0]],
+                geometry.vertices[face.vertices[1]],
+                geometry.vertices[face.vertices[2]],
+            );
+
+            let (uv0, uv1, uv2) = (
+                geometry.uvs[face.uvs[0]],
+                geometry.uvs[face.uvs[1]],
+                geometry.uvs[face.uvs[2]],
+            );
+
+            let (edge0, edge1) = (v1 - v0, v2 - v0);
+
+            let (delta_uv0, delta_uv1) = (uv1 - uv0, uv2 - uv0);
+
+            let determinant = delta_uv0.x * delta_uv1.y - delta_uv1.x * delta_uv0.y;
+
+            if determinant.abs() < f32::EPSILON {
+                // Degenerate UV mapping; this face contributes nothing.
+                continue;
+            }
+
+            let r = 1.0 / determinant;
+
+            let tangent = (edge0 * delta_uv1.y - edge1 * delta_uv0.y) * r;
+            let bitangent = (edge1 * delta_uv0.x - edge0 * delta_uv1.x) * r;
+
+            for i in 0..3 {
+                let entry = accumulated
+                    .entry((face.vertices[i], face.normals[i], face.uvs[i]))
+                    .or_default();
+
+                entry.0 += tangent;
+                entry.1 += bitangent;
+            }
+        }
+
+        for face in self.faces.iter_mut() {
+            for i in 0..3 {
+                let key = (face.vertices[i], face.normals[i], face.uvs[i]);
+
+                let Some((tangent, bitangent)) = accumulated.get(&key) else {
+                    continue;
+                };
+
+                let normal = geometry.normals[face.normals[i]];
+
+                // Gram-Schmidt orthogonalization, preserving handedness.
+
+                let t = *tangent - normal * normal.dot(*tangent);
+
+                if t.is_zero() {
+                    continue;
+                }
+
+                let t = t.as_normal();
+
+                let handedness = if normal.cross(t).dot(*bitangent) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+
+                face.tangents[i] = t;
+                face.bitangents[i] = normal.cross(t) * handedness;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn weld_vertices(&mut self, epsilon: f32) -> usize {
+        // Collapses positions, normals, and UVs that lie within `epsilon` of
+        // one another (per component), and re-indexes faces accordingly;
+        // returns the number of positions removed.
+
+        let geometry = Rc::make_mut(&mut self.geometry);
+
+        let (vertices, vertex_remap) =
+            weld_points(&geometry.vertices, epsilon, |v| [v.x, v.y, v.z]);
+        let (normals, normal_remap) = weld_points(&geometry.normals, epsilon, |n| [n.x, n.y, n.z]);
+        let (uvs, uv_remap) = weld_points(&geometry.uvs, epsilon, |uv| [uv.x, uv.y, uv.z]);
+
+        let removed_count = geometry.vertices.len() - vertices.len();
+
+        geometry.vertices = vertices.into_boxed_slice();
+        geometry.normals = normals.into_boxed_slice();
+        geometry.uvs = uvs.into_boxed_slice();
+
+        // Faces without normals or UVs hold placeholder indices, which are
+        // left as-is.
+
+        let remap = |remap: &[usize], index: &mut usize| {
+            if let Some(new_index) = remap.get(*index) {
+                *index = *new_index;
+            }
+        };
+
+        for face in self.faces.iter_mut() {
+            for i in 0..3 {
+                remap(&vertex_remap, &mut face.vertices[i]);
+                remap(&normal_remap, &mut face.normals[i]);
+                remap(&uv_remap, &mut face.uvs[i]);
+            }
+        }
+
+        // Any existing BVH references the old geometry.
+
+        self.static_triangle_bvh = None;
+
+        self.post_deserialize();
+
+        removed_count
+    }
+
     fn post_process(&mut self) -> Result<(), String> {
         // Tangent and bitangent smoothing.
 
@@ -192,3 +348,217 @@ impl Mesh {
         Ok(())
     }
 }
+
+// Returns the welded points, along with the new index of each original point.
+fn weld_points<T: Copy>(
+    points: &[T],
+    epsilon: f32,
+    get_components: impl Fn(&T) -> [f32; 3],
+) -> (Vec<T>, Vec<usize>) {
+    // Buckets welded points into a grid of epsilon-sized cells, so that each
+    // point only needs to be compared against points in neighboring cells.
+
+    let cell_size = if epsilon > 0.0 { epsilon } else { 1.0 };
+
+    let mut cells = HashMap::<[i64; 3], Vec<usize>>::new();
+
+    let mut welded: Vec<T> = vec![];
+    let mut remap = Vec::with_capacity(points.len());
+
+    for point in points {
+        let components = get_components(point);
+
+        let cell = components.map(|c| (c / cell_size).floor() as i64);
+
+        let mut existing_index = None;
+
+        'search: for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let neighbor = [cell[0] + dx, cell[1] + dy, cell[2] + dz];
+
+                    for index in cells.get(&neighbor).into_iter().flatten() {
+                        let other = get_components(&welded[*index]);
+
+                        if components
+                            .iter()
+                            .zip(other)
+                            .all(|(a, b)| (a - b).abs() <= epsilon)
+                        {
+                            existing_index = Some(*index);
+
+                            break 'search;
+                        }
+                    }
+                }
+            }
+        }
+
+        let index = existing_index.unwrap_or_else(|| {
+            welded.push(*point);
+
+            cells.entry(cell).or_default().push(welded.len() - 1);
+
+            welded.len() - 1
+        });
+
+        remap.push(index);
+    }
+
+    (welded, remap)
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use crate::{
+        mesh::{mesh_geometry::MeshGeometry, Mesh, PartialFace},
+        vec::vec3::{self, Vec3},
+    };
+
+    use super::primitive::{cube, plane};
+
+    #[test]
+    fn generated_tangents_are_orthogonal_to_normals() {
+        let mut mesh = plane::generate(4.0, 2.0, 4, 2);
+
+        // Clears the tangents computed by `Mesh::new()`.
+
+        for face in mesh.faces.iter_mut() {
+            face.tangents = Default::default();
+            face.bitangents = Default::default();
+        }
+
+        mesh.generate_tangents().unwrap();
+
+        // The plane's U coordinate increases along +X, and its V coordinate
+        // along +Z.
+
+        for face in &mesh.faces {
+            for i in 0..3 {
+                let normal = mesh.geometry.normals[face.normals[i]];
+
+                let (tangent, bitangent) = (face.tangents[i], face.bitangents[i]);
+
+                assert!((tangent.mag() - 1.0).abs() < 1e-4);
+                assert!(normal.dot(tangent).abs() < 1e-4);
+                assert!(normal.dot(bitangent).abs() < 1e-4);
+
+                assert!((tangent - vec3::RIGHT).mag() < 1e-4);
+                assert!((bitangent - vec3::FORWARD).mag() < 1e-4);
+            }
+        }
+
+        // Meshes without UVs can't derive tangents.
+
+        let mut mesh_without_uvs = mesh.clone();
+
+        Rc::make_mut(&mut mesh_without_uvs.geometry).uvs = Default::default();
+
+        assert!(mesh_without_uvs.generate_tangents().is_err());
+    }
+
+    #[test]
+    fn generated_normals_follow_face_orientation() {
+        let mut mesh = cube::generate(2.0, 2.0, 2.0);
+
+        let expected: Vec<Vec3> = mesh
+            .faces
+            .iter()
+            .map(|face| {
+                let (v0, v1, v2) = mesh.geometry.get_vertices(
+                    face.vertices[0],
+                    face.vertices[1],
+                    face.vertices[2],
+                );
+
+                (*v1 - *v0).cross(*v2 - *v0).as_normal()
+            })
+            .collect();
+
+        mesh.generate_flat_normals();
+
+        for (face, expected) in mesh.faces.iter().zip(&expected) {
+            for i in 0..3 {
+                assert!((mesh.geometry.normals[face.normals[i]] - *expected).mag() < 1e-4);
+            }
+        }
+
+        // Without smoothing groups, smooth normals are flat, too.
+
+        mesh.generate_smooth_normals();
+
+        for (face, expected) in mesh.faces.iter().zip(&expected) {
+            for i in 0..3 {
+                assert!((mesh.geometry.normals[face.normals[i]] - *expected).mag() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn welding_a_triangle_soup_removes_duplicate_vertices() {
+        // Unrolls a cube into a "soup" where each face has its own vertices.
+
+        let cube = cube::generate(2.0, 2.0, 2.0);
+
+        let mut vertices = vec![];
+        let mut normals = vec![];
+        let mut uvs = vec![];
+
+        let mut partial_faces = vec![];
+
+        for face in &cube.faces {
+            let first = vertices.len();
+
+            for i in 0..3 {
+                vertices.push(cube.geometry.vertices[face.vertices[i]]);
+                normals.push(cube.geometry.normals[face.normals[i]]);
+                uvs.push(cube.geometry.uvs[face.uvs[i]]);
+            }
+
+            let indices = [first, first + 1, first + 2];
+
+            partial_faces.push(PartialFace {
+                vertices: indices,
+                normals: Some(indices),
+                uvs: Some(indices),
+            });
+        }
+
+        let geometry = MeshGeometry {
+            vertices: vertices.into_boxed_slice(),
+            normals: normals.into_boxed_slice(),
+            uvs: uvs.into_boxed_slice(),
+        };
+
+        let mut soup = Mesh::new(Rc::new(geometry), partial_faces, None);
+
+        let (vertex_count, aabb) = (soup.geometry.vertices.len(), soup.aabb);
+
+        let removed_count = soup.weld_vertices(1e-5);
+
+        assert!(removed_count > 0);
+        assert_eq!(soup.geometry.vertices.len(), vertex_count - removed_count);
+        assert_eq!(soup.geometry.vertices.len(), 8);
+
+        assert_eq!(soup.aabb.min, aabb.min);
+        assert_eq!(soup.aabb.max, aabb.max);
+
+        // Faces still describe the same triangles.
+
+        for (welded, original) in soup.faces.iter().zip(cube.faces.iter()) {
+            for i in 0..3 {
+                assert_eq!(
+                    soup.geometry.vertices[welded.vertices[i]],
+                    cube.geometry.vertices[original.vertices[i]]
+                );
+
+                assert_eq!(
+                    soup.geometry.uvs[welded.uvs[i]],
+                    cube.geometry.uvs[original.uvs[i]]
+                );
+            }
+        }
+    }
+}
@@ -1,20 +1,28 @@
-use std::{fmt, io::Error, mem, path::Path, rc::Rc};
+use std::{
+    fmt,
+    fs::File,
+    io::{BufRead, BufReader, ErrorKind},
+    mem,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
 use serde::{Deserialize, Serialize};
 
 use bitmask::bitmask;
 
 use crate::{
-    fs::read_lines,
     material::{mtl::load_mtl, Material},
     mesh::{
+        cache::{get_mesh_cache_path, is_mesh_cache_fresh, read_mesh_cache, write_mesh_cache},
+        face::{get_processed_faces, Face},
         mesh_geometry::MeshGeometry,
         obj::parse::{
             parse_face, parse_mtllib, parse_vertex, parse_vertex_normal, parse_vertex_uv,
         },
         Mesh, PartialFace,
     },
-    resource::arena::Arena,
+    resource::{arena::Arena, handle::Handle},
     texture::map::TextureMap,
     vec::{vec2::Vec2, vec3::Vec3},
 };
@@ -40,6 +48,8 @@ impl fmt::Display for LoadObjStats {
 
 struct PartialMesh {
     partial_faces: Vec<PartialFace>,
+    // Smoothing group of each partial face.
+    smoothing_groups: Vec<u32>,
     object_source: String,
     object_name: Option<String>,
     group_name: Option<String>,
@@ -54,180 +64,350 @@ bitmask! {
     }
 }
 
+// Progress of a streaming OBJ load, reported periodically while parsing.
+#[derive(Default, Debug, Copy, Clone)]
+pub struct LoadObjProgress {
+    pub bytes_read: u64,
+    pub total_bytes: u64,
+    pub lines_read: usize,
+}
+
+impl LoadObjProgress {
+    pub fn get_fraction(&self) -> f32 {
+        if self.total_bytes == 0 {
+            1.0
+        } else {
+            (self.bytes_read as f64 / self.total_bytes as f64) as f32
+        }
+    }
+}
+
+static LOAD_OBJ_PROGRESS_INTERVAL_LINES: usize = 4096;
+
+struct ObjParseState {
+    parent_path: PathBuf,
+    object_source: String,
+    material_source: Option<String>,
+    object_name: Option<String>,
+    group_name: Option<String>,
+    material_name: Option<String>,
+    smoothing_group: u32,
+    vertices: Vec<Vec3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<Vec2>,
+    partial_faces: Vec<PartialFace>,
+    smoothing_groups: Vec<u32>,
+    partial_meshes: Vec<PartialMesh>,
+    counts: LoadObjStats,
+}
+
+impl ObjParseState {
+    fn new(parent_path: &Path, object_source: String) -> Self {
+        Self {
+            parent_path: parent_path.to_path_buf(),
+            object_source,
+            material_source: None,
+            object_name: None,
+            group_name: None,
+            material_name: None,
+            smoothing_group: 0,
+            vertices: vec![],
+            normals: vec![],
+            uvs: vec![],
+            partial_faces: vec![],
+            smoothing_groups: vec![],
+            partial_meshes: vec![],
+            counts: Default::default(),
+        }
+    }
+
+    fn package_partial_mesh(&mut self) {
+        // If we were compiling a previous face group, package it into a Mesh
+        // (using the names in effect for its faces) before we continue.
+
+        if self.partial_faces.is_empty() {
+            return;
+        }
+
+        self.partial_meshes.push(PartialMesh {
+            partial_faces: mem::take(&mut self.partial_faces),
+            smoothing_groups: mem::take(&mut self.smoothing_groups),
+            object_source: self.object_source.clone(),
+            object_name: self.object_name.clone(),
+            group_name: self.group_name.clone(),
+            material_name: self.material_name.clone(),
+        });
+    }
+
+    fn parse_line(&mut self, line: &str) {
+        let mut line_tokens = line.split_whitespace();
+
+        let data_type = line_tokens.next();
+
+        match data_type {
+            None => (),
+            Some(dt) => {
+                match dt {
+                    // Comment
+                    "#" => {
+                        println!("# {}", line_tokens.next().unwrap_or_default());
+                    }
+                    // Geometric vertex, with (x, y, z, [w]) coordinates, w is optional and defaults to 1.0.
+                    "v" => {
+                        let vertex = parse_vertex(&mut line_tokens).unwrap();
+
+                        self.vertices.push(vertex);
+
+                        self.counts.vertex += 1;
+                    }
+                    // Texture (UV) coordinate, as (u, [v, w]), between 0 and 1. v, w are optional and default to 0.
+                    "vt" => {
+                        let uv = parse_vertex_uv(&mut line_tokens).unwrap();
+
+                        self.uvs.push(uv);
+
+                        self.counts.uv += 1;
+                    }
+                    // Vertex normal in (x,y,z) form; normal might not be a unit vector.
+                    "vn" => {
+                        let normal = parse_vertex_normal(&mut line_tokens).unwrap();
+
+                        self.normals.push(normal);
+
+                        self.counts.normal += 1;
+                    }
+                    // Parameter space vertex
+                    "vp" => {
+                        // TODO
+                    }
+                    // Polygonal face; quads and n-gons are split into triangles.
+                    "f" => {
+                        let partial_faces = parse_face(&mut line_tokens).unwrap();
+
+                        self.counts.face += partial_faces.len();
+
+                        self.smoothing_groups.resize(
+                            self.smoothing_groups.len() + partial_faces.len(),
+                            self.smoothing_group,
+                        );
+
+                        self.partial_faces.extend(partial_faces);
+                    }
+                    // Line element
+                    "l" => {
+                        // TODO
+                    }
+                    // External material reference
+                    "mtllib" => {
+                        let path = parse_mtllib(&mut line_tokens, &self.parent_path).unwrap();
+
+                        self.material_source = Some(path);
+
+                        // println!("mtllib {}", material_source.as_ref().unwrap());
+                    }
+                    // Material group
+                    "usemtl" => {
+                        // Each material span becomes its own Mesh.
+
+                        let next_material_name = Some(line_tokens.next().unwrap().to_string());
+
+                        if next_material_name != self.material_name {
+                            self.package_partial_mesh();
+                        }
+
+                        self.material_name = next_material_name;
+
+                        // println!(
+                        //     "\tusemtl {} for material group {}.",
+                        //     material_name.as_ref().unwrap(),
+                        //     group_name.as_ref().unwrap()
+                        // );
+
+                        self.counts.material_group += 1;
+                    }
+                    // Named object
+                    "o" => {
+                        self.package_partial_mesh();
+
+                        self.object_name = Some(line_tokens.next().unwrap().to_string());
+
+                        // println!("o {}", object_name.as_ref().unwrap());
+
+                        self.counts.object += 1;
+                    }
+                    // Named object group
+                    "g" => {
+                        self.package_partial_mesh();
+
+                        self.group_name = Some(line_tokens.next().unwrap().to_string());
+
+                        // println!("g {}", group_name.as_ref().unwrap());
+
+                        self.counts.group += 1;
+                    }
+                    // Smoothing group
+                    "s" => {
+                        // `s off` and `s 0` both disable smoothing.
+
+                        self.smoothing_group = match line_tokens.next() {
+                            Some("off") | None => 0,
+                            Some(group) => group.parse::<u32>().unwrap_or(0),
+                        };
+                    }
+                    other => {
+                        // Unrecognized prefix
+                        println!("Unrecognized data type: {}", other)
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub(in crate::mesh) fn find_material_handle(
+    material_arena: &Arena<Material>,
+    name: &str,
+) -> Option<Handle> {
+    let material_slot_index = material_arena.entries.iter().position(|slot| match slot {
+        Some(entry) => {
+            let material = &entry.item;
+
+            material.name == *name
+        }
+        None => false,
+    });
+
+    material_slot_index.map(|index| material_arena.get_handle(index).unwrap())
+}
+
 pub fn load_obj(
     filepath: &str,
     material_arena: &mut Arena<Material>,
     texture_arena: &mut Arena<TextureMap>,
     process_geometry_flags: Option<ProcessGeometryFlagMask>,
+) -> LoadObjResult {
+    load_obj_streaming(
+        filepath,
+        material_arena,
+        texture_arena,
+        process_geometry_flags,
+        &mut |_progress| {},
+    )
+}
+
+pub fn load_obj_cached(
+    filepath: &str,
+    cache_directory: &Path,
+    material_arena: &mut Arena<Material>,
+    texture_arena: &mut Arena<TextureMap>,
+    process_geometry_flags: Option<ProcessGeometryFlagMask>,
+) -> LoadObjResult {
+    // Reads processed meshes from a `.meshbin` cache (in `cache_directory`)
+    // when it's newer than the source; otherwise, parses the source and
+    // (re-)writes the cache.
+
+    let source_path = Path::new(filepath);
+
+    let cache_path = get_mesh_cache_path(source_path, cache_directory);
+
+    let flags = process_geometry_flags.map(|mask| *mask).unwrap_or(0);
+
+    if is_mesh_cache_fresh(source_path, &cache_path) {
+        match read_mesh_cache(&cache_path, flags, material_arena, texture_arena) {
+            Ok((geometry, meshes)) => return LoadObjResult(geometry, meshes),
+            Err(err) => println!("Ignoring mesh cache: {}", err),
+        }
+    }
+
+    let result = load_obj(
+        filepath,
+        material_arena,
+        texture_arena,
+        process_geometry_flags,
+    );
+
+    if let Err(err) = write_mesh_cache(&cache_path, &result.0, &result.1, flags, material_arena) {
+        println!("{}", err);
+    }
+
+    result
+}
+
+pub fn load_obj_streaming(
+    filepath: &str,
+    material_arena: &mut Arena<Material>,
+    texture_arena: &mut Arena<TextureMap>,
+    process_geometry_flags: Option<ProcessGeometryFlagMask>,
+    on_progress: &mut dyn FnMut(&LoadObjProgress),
 ) -> LoadObjResult {
     let path = Path::new(&filepath);
 
     let parent_path = path.parent().unwrap();
 
-    let lines = read_lines(path).unwrap();
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) => panic!("Failed to open file {}: {}", path.display(), err),
+    };
 
-    let object_source = Some(path.to_str().unwrap().to_string());
+    let mut progress = LoadObjProgress {
+        total_bytes: file.metadata().map(|metadata| metadata.len()).unwrap_or(0),
+        ..Default::default()
+    };
 
-    let mut material_source: Option<String> = None;
+    let mut reader = BufReader::new(file);
 
-    let mut object_name: Option<String> = None;
-    let mut group_name: Option<String> = None;
-    let mut material_name: Option<String> = None;
+    let mut state = ObjParseState::new(parent_path, path.to_str().unwrap().to_string());
 
-    let mut vertices: Vec<Vec3> = vec![];
-    let mut normals: Vec<Vec3> = vec![];
-    let mut uvs: Vec<Vec2> = vec![];
+    // Reads one line at a time into a reused buffer, so that memory used for
+    // reading stays bounded regardless of the file's size.
 
-    let mut partial_faces: Vec<PartialFace> = vec![];
-    let mut partial_meshes: Vec<PartialMesh> = vec![];
+    let mut line = String::new();
 
-    let mut counts: LoadObjStats = Default::default();
+    loop {
+        line.clear();
 
-    let end_signal: std::vec::IntoIter<Result<String, Error>> =
-        vec![(Ok("usemtl __sentinel__".to_string()))].into_iter();
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(bytes_read) => {
+                progress.bytes_read += bytes_read as u64;
+                progress.lines_read += 1;
 
-    let lines_safe = lines.chain(end_signal);
+                state.parse_line(&line);
 
-    for next_line in lines_safe {
-        match next_line {
+                if progress
+                    .lines_read
+                    .is_multiple_of(LOAD_OBJ_PROGRESS_INTERVAL_LINES)
+                {
+                    on_progress(&progress);
+                }
+            }
             Err(err) => {
                 println!("Error reading next line: {}", err);
 
-                continue;
-            }
-            Ok(line) => {
-                let mut line_tokens = line.split_whitespace();
-
-                let data_type = line_tokens.next();
-
-                match data_type {
-                    None => (),
-                    Some(dt) => {
-                        match dt {
-                            // Comment
-                            "#" => {
-                                println!("# {}", line_tokens.next().unwrap_or_default());
-                            }
-                            // Geometric vertex, with (x, y, z, [w]) coordinates, w is optional and defaults to 1.0.
-                            "v" => {
-                                let vertex = parse_vertex(&mut line_tokens).unwrap();
-
-                                vertices.push(vertex);
-
-                                counts.vertex += 1;
-                            }
-                            // Texture (UV) coordinate, as (u, [v, w]), between 0 and 1. v, w are optional and default to 0.
-                            "vt" => {
-                                let uv = parse_vertex_uv(&mut line_tokens).unwrap();
-
-                                uvs.push(uv);
-
-                                counts.uv += 1;
-                            }
-                            // Vertex normal in (x,y,z) form; normal might not be a unit vector.
-                            "vn" => {
-                                let normal = parse_vertex_normal(&mut line_tokens).unwrap();
-
-                                normals.push(normal);
-
-                                counts.normal += 1;
-                            }
-                            // Parameter space vertex
-                            "vp" => {
-                                // TODO
-                            }
-                            // Polygonal face
-                            "f" => {
-                                let partial_face = parse_face(&mut line_tokens).unwrap();
-
-                                partial_faces.push(partial_face);
-
-                                counts.face += 1;
-                            }
-                            // Line element
-                            "l" => {
-                                // TODO
-                            }
-                            // External material reference
-                            "mtllib" => {
-                                let path = parse_mtllib(&mut line_tokens, parent_path).unwrap();
-
-                                material_source = Some(path);
-
-                                // println!("mtllib {}", material_source.as_ref().unwrap());
-                            }
-                            // Material group
-                            "usemtl" => {
-                                // If we were compiling a previous face group,
-                                // package it into a Mesh before we continue.
-
-                                let next_material_name =
-                                    Some(line_tokens.next().unwrap().to_string());
-
-                                if next_material_name != material_name {
-                                    let mut partial_mesh = PartialMesh {
-                                        partial_faces: vec![],
-                                        object_source: object_source.as_ref().unwrap().clone(),
-                                        object_name: object_name.clone(),
-                                        group_name: group_name.clone(),
-                                        material_name,
-                                    };
-
-                                    mem::swap(&mut partial_faces, &mut partial_mesh.partial_faces);
-
-                                    partial_meshes.push(partial_mesh);
-                                }
-
-                                material_name = next_material_name;
-
-                                // println!(
-                                //     "\tusemtl {} for material group {}.",
-                                //     material_name.as_ref().unwrap(),
-                                //     group_name.as_ref().unwrap()
-                                // );
-
-                                counts.material_group += 1;
-                            }
-                            // Named object
-                            "o" => {
-                                object_name = Some(line_tokens.next().unwrap().to_string());
-
-                                // println!("o {}", object_name.as_ref().unwrap());
-
-                                counts.object += 1;
-                            }
-                            // Named object group
-                            "g" => {
-                                group_name = Some(line_tokens.next().unwrap().to_string());
-
-                                // println!("g {}", group_name.as_ref().unwrap());
-
-                                counts.group += 1;
-                            }
-                            // Smoothing group
-                            "s" => {
-                                // TODO
-                            }
-                            other => {
-                                // Unrecognized prefix
-                                println!("Unrecognized data type: {}", other)
-                            }
-                        }
-                    }
+                if err.kind() == ErrorKind::InvalidData {
+                    continue;
                 }
+
+                break;
             }
         }
     }
 
-    match &material_source {
+    // Packages the final face group.
+
+    state.parse_line("usemtl __sentinel__");
+
+    on_progress(&progress);
+
+    match &state.material_source {
         Some(src) => load_mtl(src, material_arena, texture_arena),
         None => (),
     }
 
     let mut geometry = MeshGeometry {
-        vertices: vertices.into_boxed_slice(),
-        normals: normals.into_boxed_slice(),
-        uvs: uvs.into_boxed_slice(),
+        vertices: state.vertices.into_boxed_slice(),
+        normals: state.normals.into_boxed_slice(),
+        uvs: state.uvs.into_boxed_slice(),
     };
 
     if let Some(mask) = process_geometry_flags {
@@ -236,37 +416,406 @@ pub fn load_obj(
         }
     }
 
-    let geometry_rc = Rc::new(geometry);
+    // Processes every mesh's faces before sharing the geometry, so that any
+    // normals we generate are written into the one (shared) geometry.
 
-    let mut meshes: Vec<Mesh> = vec![];
+    let mut faces_per_mesh = Vec::with_capacity(state.partial_meshes.len());
 
-    for partial_mesh in partial_meshes {
-        let material = partial_mesh.material_name.as_ref().and_then(|name| {
-            let material_slot_index = material_arena.entries.iter().position(|slot| match slot {
-                Some(entry) => {
-                    let material = &entry.item;
+    for partial_mesh in &state.partial_meshes {
+        let mut faces = get_processed_faces(&geometry, &partial_mesh.partial_faces);
 
-                    material.name == *name
-                }
-                None => false,
-            });
+        for (face, smoothing_group) in faces.iter_mut().zip(&partial_mesh.smoothing_groups) {
+            face.smoothing_group = *smoothing_group;
+        }
 
-            material_slot_index.map(|index| material_arena.get_handle(index).unwrap())
-        });
+        // Generates normals for those faces that the file doesn't provide them
+        // for, leaving any authored normals as-is.
+
+        let face_indices_missing_normals: Vec<usize> = partial_mesh
+            .partial_faces
+            .iter()
+            .enumerate()
+            .filter(|(_, partial_face)| partial_face.normals.is_none())
+            .map(|(face_index, _)| face_index)
+            .collect();
+
+        if !face_indices_missing_normals.is_empty() {
+            let mut faces_missing_normals: Vec<Face> = face_indices_missing_normals
+                .iter()
+                .map(|face_index| faces[*face_index])
+                .collect();
+
+            geometry.generate_smooth_normals(&mut faces_missing_normals);
+
+            for (face_index, face) in face_indices_missing_normals
+                .iter()
+                .zip(faces_missing_normals)
+            {
+                faces[*face_index] = face;
+            }
+        }
 
-        let mut mesh = Mesh::new(geometry_rc.clone(), partial_mesh.partial_faces, material);
+        faces_per_mesh.push(faces);
+    }
+
+    let geometry_rc = Rc::new(geometry);
+
+    let mut meshes: Vec<Mesh> = vec![];
+
+    for (partial_mesh, faces) in state.partial_meshes.into_iter().zip(faces_per_mesh) {
+        let material = partial_mesh
+            .material_name
+            .as_ref()
+            .and_then(|name| find_material_handle(material_arena, name));
+
+        let mut mesh = Mesh::from_faces(geometry_rc.clone(), faces, material);
 
         partial_mesh.object_name.clone_into(&mut mesh.object_name);
 
         mesh.object_source = Some(partial_mesh.object_source.to_owned());
 
+        state.material_source.clone_into(&mut mesh.material_source);
+
         partial_mesh.group_name.clone_into(&mut mesh.group_name);
 
         meshes.push(mesh);
     }
 
-    println!("{}", counts);
+    println!("{}", state.counts);
     println!("Parsed {} meshes.", meshes.len());
 
     LoadObjResult(geometry_rc, meshes)
 }
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::{mesh::Mesh, resource::arena::Arena, testing::TempPath};
+
+    use super::{load_obj, load_obj_streaming, ObjParseState, ProcessGeometryFlag};
+
+    static FIXTURE_OBJ: &str = "# Two quads in separate groups
+o Quads
+v -1.0 -1.0 0.0
+v 1.0 -1.0 0.0
+v 1.0 1.0 0.0
+v -1.0 1.0 0.0
+v -1.0 -1.0 -2.0
+v 1.0 -1.0 -2.0
+v 1.0 1.0 -2.0
+v -1.0 1.0 -2.0
+vt 0.0 0.0
+vt 1.0 0.0
+vt 1.0 1.0
+vt 0.0 1.0
+vn 0.0 0.0 1.0
+g Front
+usemtl front
+f 1/1/1 2/2/1 3/3/1
+f 1/1/1 3/3/1 4/4/1
+g Back
+usemtl back
+f 5/1/1 6/2/1 7/3/1
+f 5/1/1 7/3/1 8/4/1
+";
+
+    #[test]
+    fn quad_face_is_split_into_two_triangles_sharing_an_edge() {
+        let directory = TempPath::new_dir("obj");
+
+        let path = directory.join("quad.obj");
+
+        fs::write(
+            &path,
+            "v 0.0 0.0 0.0
+v 2.0 0.0 0.0
+v 2.0 2.0 0.0
+v 0.0 2.0 0.0
+vt 0.0 0.0
+vt 1.0 0.0
+vt 1.0 1.0
+vt 0.0 1.0
+vn 0.0 0.0 1.0
+f 1/1/1 2/2/1 3/3/1 4/4/1
+",
+        )
+        .unwrap();
+
+        let mut material_arena = Arena::new();
+        let mut texture_arena = Arena::new();
+
+        let result = load_obj(
+            path.to_str().unwrap(),
+            &mut material_arena,
+            &mut texture_arena,
+            Some(ProcessGeometryFlag::Null | ProcessGeometryFlag::Center),
+        );
+
+        let faces = result
+            .1
+            .iter()
+            .flat_map(|mesh| mesh.faces.iter())
+            .collect::<Vec<_>>();
+
+        assert_eq!(faces.len(), 2);
+
+        // Both triangles keep the quad's winding, and share the edge (0, 2).
+
+        assert_eq!(faces[0].vertices, [0, 1, 2]);
+        assert_eq!(faces[1].vertices, [0, 2, 3]);
+
+        assert_eq!(faces[0].uvs, [0, 1, 2]);
+        assert_eq!(faces[1].uvs, [0, 2, 3]);
+        assert_eq!(faces[1].normals, [0, 0, 0]);
+
+        // Centering still applies to the triangulated geometry.
+
+        assert_eq!(result.0.vertices[0].x, -1.0);
+        assert_eq!(result.0.vertices[2].y, 1.0);
+    }
+
+    // A unit cube without normals. Each quad is split along an edge of the
+    // cube's inscribed tetrahedron, so that every corner is weighted equally
+    // by its three faces.
+    static CUBE_OBJ_WITHOUT_NORMALS: &str = "o Cube
+v -1.0 -1.0 -1.0
+v 1.0 -1.0 -1.0
+v 1.0 1.0 -1.0
+v -1.0 1.0 -1.0
+v -1.0 -1.0 1.0
+v 1.0 -1.0 1.0
+v 1.0 1.0 1.0
+v -1.0 1.0 1.0
+s 1
+f 4 3 2 1
+f 5 6 7 8
+f 5 8 4 1
+f 2 3 7 6
+f 2 6 5 1
+f 4 8 7 3
+";
+
+    fn load_cube(obj: &str) -> Vec<Mesh> {
+        let directory = TempPath::new_dir("obj");
+
+        let path = directory.join("cube.obj");
+
+        fs::write(&path, obj).unwrap();
+
+        let mut material_arena = Arena::new();
+        let mut texture_arena = Arena::new();
+
+        let result = load_obj(
+            path.to_str().unwrap(),
+            &mut material_arena,
+            &mut texture_arena,
+            None,
+        );
+
+        result.1
+    }
+
+    #[test]
+    fn each_material_span_becomes_its_own_mesh() {
+        let directory = TempPath::new_dir("obj");
+
+        fs::write(
+            directory.join("cube.mtl"),
+            "newmtl red
+Kd 1.0 0.0 0.0
+newmtl blue
+Kd 0.0 0.0 1.0
+",
+        )
+        .unwrap();
+
+        let path = directory.join("cube.obj");
+
+        fs::write(
+            &path,
+            CUBE_OBJ_WITHOUT_NORMALS
+                .replacen("o Cube", "mtllib cube.mtl\no Cube", 1)
+                .replacen("f 4 3 2 1", "usemtl red\nf 4 3 2 1", 1)
+                .replacen("f 2 3 7 6", "usemtl blue\nf 2 3 7 6", 1),
+        )
+        .unwrap();
+
+        let mut material_arena = Arena::new();
+        let mut texture_arena = Arena::new();
+
+        let result = load_obj(
+            path.to_str().unwrap(),
+            &mut material_arena,
+            &mut texture_arena,
+            None,
+        );
+
+        let meshes = result.1;
+
+        assert_eq!(meshes.len(), 2);
+
+        let handles = meshes
+            .iter()
+            .map(|mesh| mesh.material.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_ne!(handles[0], handles[1]);
+
+        for (mesh, name) in meshes.iter().zip(["red", "blue"]) {
+            assert_eq!(mesh.faces.len(), 6);
+            assert_eq!(mesh.object_name.as_deref(), Some("Cube"));
+
+            let material = &material_arena.get(&mesh.material.unwrap()).unwrap().item;
+
+            assert_eq!(material.name, name);
+        }
+    }
+
+    #[test]
+    fn generated_smooth_normals_point_diagonally_outward() {
+        let meshes = load_cube(CUBE_OBJ_WITHOUT_NORMALS);
+
+        let mesh = meshes.iter().find(|mesh| !mesh.faces.is_empty()).unwrap();
+
+        assert_eq!(mesh.faces.len(), 12);
+
+        for face in &mesh.faces {
+            for (vertex_index, normal_index) in face.vertices.iter().zip(face.normals) {
+                let corner = mesh.geometry.vertices[*vertex_index];
+                let normal = mesh.geometry.normals[normal_index];
+
+                assert!((normal - corner.as_normal()).mag() < 1e-5);
+            }
+        }
+
+        // Corners shared by faces in different smoothing groups aren't
+        // averaged together; with smoothing off, every face is flat.
+
+        let flat_meshes = load_cube(&CUBE_OBJ_WITHOUT_NORMALS.replace("s 1", "s off"));
+
+        let flat_mesh = flat_meshes
+            .iter()
+            .find(|mesh| !mesh.faces.is_empty())
+            .unwrap();
+
+        for face in &flat_mesh.faces {
+            let normal = flat_mesh.geometry.normals[face.normals[0]];
+
+            assert!((normal.mag() - 1.0).abs() < 1e-5);
+
+            assert_eq!(
+                [normal.x.abs(), normal.y.abs(), normal.z.abs()]
+                    .iter()
+                    .filter(|component| **component > 0.5)
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn authored_normals_are_kept_alongside_generated_normals() {
+        // Only the first quad provides its normal.
+
+        let meshes = load_cube(
+            &CUBE_OBJ_WITHOUT_NORMALS
+                .replacen("s 1", "vn 0.0 0.0 -1.0\ns 1", 1)
+                .replacen("f 4 3 2 1", "f 4//1 3//1 2//1 1//1", 1),
+        );
+
+        let mesh = meshes.iter().find(|mesh| !mesh.faces.is_empty()).unwrap();
+
+        assert_eq!(mesh.faces.len(), 12);
+
+        for (face_index, face) in mesh.faces.iter().enumerate() {
+            for (vertex_index, normal_index) in face.vertices.iter().zip(face.normals) {
+                let normal = mesh.geometry.normals[normal_index];
+
+                if face_index < 2 {
+                    assert_eq!(normal_index, 0);
+                } else {
+                    // Generated normals still point away from the cube.
+
+                    let corner = mesh.geometry.vertices[*vertex_index];
+
+                    assert!((normal.mag() - 1.0).abs() < 1e-5);
+                    assert!(normal.dot(corner) > 0.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn streaming_parse_matches_in_memory_parse() {
+        let directory = TempPath::new_dir("obj");
+
+        let path = directory.join("quads.obj");
+
+        fs::write(&path, FIXTURE_OBJ).unwrap();
+
+        let filepath = path.to_str().unwrap();
+
+        let mut material_arena = Arena::new();
+        let mut texture_arena = Arena::new();
+
+        let mut reported = vec![];
+
+        let streamed = load_obj_streaming(
+            filepath,
+            &mut material_arena,
+            &mut texture_arena,
+            None,
+            &mut |progress| reported.push(*progress),
+        );
+
+        let buffered = load_obj(filepath, &mut material_arena, &mut texture_arena, None);
+
+        // Parses the whole file from memory, for comparison.
+
+        let mut state = ObjParseState::new(&directory, filepath.to_string());
+
+        for line in FIXTURE_OBJ.lines() {
+            state.parse_line(line);
+        }
+
+        state.parse_line("usemtl __sentinel__");
+
+        assert_eq!(streamed.0.vertices.as_ref(), state.vertices.as_slice());
+        assert_eq!(streamed.0.uvs.as_ref(), state.uvs.as_slice());
+        assert_eq!(streamed.0.normals.as_ref(), state.normals.as_slice());
+
+        assert_eq!(streamed.1.len(), 2);
+
+        assert_eq!(
+            streamed
+                .1
+                .iter()
+                .map(|mesh| mesh.group_name.as_deref())
+                .collect::<Vec<_>>(),
+            vec![Some("Front"), Some("Back")]
+        );
+        assert_eq!(
+            streamed
+                .1
+                .iter()
+                .map(|mesh| mesh.faces.len())
+                .collect::<Vec<usize>>(),
+            state
+                .partial_meshes
+                .iter()
+                .map(|partial_mesh| partial_mesh.partial_faces.len())
+                .collect::<Vec<usize>>(),
+        );
+
+        assert_eq!(
+            serde_json::to_string(&streamed.1).unwrap(),
+            serde_json::to_string(&buffered.1).unwrap()
+        );
+
+        let last = reported.last().unwrap();
+
+        assert_eq!(last.bytes_read, FIXTURE_OBJ.len() as u64);
+        assert_eq!(last.get_fraction(), 1.0);
+    }
+}
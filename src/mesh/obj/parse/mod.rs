@@ -50,7 +50,7 @@ pub fn parse_vertex_normal(tokens: &mut SplitWhitespace<'_>) -> Result<Vec3, Str
     Ok(Vec3 { x, y, z })
 }
 
-pub fn parse_face(tokens: &mut SplitWhitespace<'_>) -> Result<PartialFace, String> {
+pub fn parse_face(tokens: &mut SplitWhitespace<'_>) -> Result<Vec<PartialFace>, String> {
     // Vertex indices only:             f v1 v2 v3 ....
     // Vertex and UV indices:           f v1/uv1 v2/uv2 v3/uv3 ...
     // Vertex, UV, and normal indices:  f v1/uv1/n1 v2/uv2/n2 v3/uv3/n3 ...
@@ -64,50 +64,71 @@ pub fn parse_face(tokens: &mut SplitWhitespace<'_>) -> Result<PartialFace, Strin
     // `f 1004//1004 1003//1003 1002//1002` ({x,y,z}{vert_index, texture_index, vert_normal_index})
     // `f 1004//1004 1003//1003 1002//1002` ({x,y,z}{vert_index, texture_index, vert_normal_index})
 
-    let mut partial_face: PartialFace = Default::default();
+    let corners = tokens
+        .map(parse_face_vertex)
+        .collect::<Result<Vec<FaceVertex>, String>>()?;
 
-    let mut v1_iter = tokens.next().unwrap().split('/');
-    let mut v2_iter = tokens.next().unwrap().split('/');
-    let mut v3_iter = tokens.next().unwrap().split('/');
-
-    partial_face.vertices = [
-        v1_iter.next().unwrap().parse::<usize>().unwrap() - 1,
-        v2_iter.next().unwrap().parse::<usize>().unwrap() - 1,
-        v3_iter.next().unwrap().parse::<usize>().unwrap() - 1,
-    ];
+    if corners.len() < 3 {
+        return Err(format!(
+            "Face has {} vertices; expected at least 3.",
+            corners.len()
+        ));
+    }
 
-    let v1_uv_index = v1_iter.next();
-    let v2_uv_index = v2_iter.next();
-    let v3_uv_index = v3_iter.next();
+    // Fan-triangulates quads and n-gons around the first vertex, preserving
+    // the face's winding order: (0, 1, 2), (0, 2, 3), ...
+
+    let mut partial_faces = Vec::with_capacity(corners.len() - 2);
+
+    for i in 1..corners.len() - 1 {
+        let (v1, v2, v3) = (&corners[0], &corners[i], &corners[i + 1]);
+
+        partial_faces.push(PartialFace {
+            vertices: [v1.vertex, v2.vertex, v3.vertex],
+            uvs: match (v1.uv, v2.uv, v3.uv) {
+                (Some(uv1), Some(uv2), Some(uv3)) => Some([uv1, uv2, uv3]),
+                _ => None,
+            },
+            normals: match (v1.normal, v2.normal, v3.normal) {
+                (Some(n1), Some(n2), Some(n3)) => Some([n1, n2, n3]),
+                _ => None,
+            },
+        });
+    }
 
-    if let Some(index) = v1_uv_index {
-        if !index.is_empty() {
-            let v1_uv = v1_uv_index.unwrap().parse::<usize>().unwrap() - 1;
-            let v2_uv = v2_uv_index.unwrap().parse::<usize>().unwrap() - 1;
-            let v3_uv = v3_uv_index.unwrap().parse::<usize>().unwrap() - 1;
+    Ok(partial_faces)
+}
 
-            partial_face.uvs = Some([v1_uv, v2_uv, v3_uv]);
-        }
-    }
+struct FaceVertex {
+    vertex: usize,
+    uv: Option<usize>,
+    normal: Option<usize>,
+}
 
-    let v1_normal_index = v1_iter.next();
+fn parse_face_vertex(token: &str) -> Result<FaceVertex, String> {
+    // OBJ indices are 1-based; empty components (as in `v//n`) are omitted.
 
-    if v1_normal_index.is_some() {
-        let v2_normal_index = v2_iter.next();
-        let v3_normal_index = v3_iter.next();
+    let parse_index = |component: Option<&str>| -> Result<Option<usize>, String> {
+        match component {
+            None | Some("") => Ok(None),
+            Some(index) => match index.parse::<usize>() {
+                Ok(index) if index > 0 => Ok(Some(index - 1)),
+                _ => Err(format!("Invalid face index '{}' in '{}'.", index, token)),
+            },
+        }
+    };
 
-        let v1_n_raw = v1_normal_index.unwrap().parse::<usize>().unwrap();
-        let v2_n_raw = v2_normal_index.unwrap().parse::<usize>().unwrap();
-        let v3_n_raw = v3_normal_index.unwrap().parse::<usize>().unwrap();
+    let mut components = token.split('/');
 
-        let v1_n = v1_n_raw - 1;
-        let v2_n = v2_n_raw - 1;
-        let v3_n = v3_n_raw - 1;
+    let vertex = parse_index(components.next())?.ok_or(format!(
+        "Face vertex '{}' is missing a vertex index.",
+        token
+    ))?;
 
-        partial_face.normals = Some([v1_n, v2_n, v3_n]);
-    }
+    let uv = parse_index(components.next())?;
+    let normal = parse_index(components.next())?;
 
-    Ok(partial_face)
+    Ok(FaceVertex { vertex, uv, normal })
 }
 
 pub fn parse_mtllib(
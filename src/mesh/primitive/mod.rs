@@ -3,3 +3,4 @@ pub mod cone;
 pub mod cube;
 pub mod cylinder;
 pub mod plane;
+pub mod ribbon;
@@ -0,0 +1,182 @@
+use std::rc::Rc;
+
+use crate::{
+    mesh::{mesh_geometry::MeshGeometry, Mesh, PartialFace},
+    vec::{vec2::Vec2, vec3::Vec3},
+};
+
+#[derive(Debug, Copy, Clone)]
+pub struct RibbonOptions {
+    pub head_width: f32,
+    pub tail_width: f32,
+    pub head_alpha: f32,
+    pub tail_alpha: f32,
+}
+
+impl Default for RibbonOptions {
+    fn default() -> Self {
+        Self {
+            head_width: 1.0,
+            tail_width: 0.0,
+            head_alpha: 1.0,
+            tail_alpha: 0.0,
+        }
+    }
+}
+
+pub struct Ribbon {
+    pub mesh: Mesh,
+    // One alpha value per vertex in `mesh.geometry.vertices`.
+    pub alphas: Vec<f32>,
+}
+
+// Builds a strip of camera-facing quads connecting `points`, ordered from the
+// ribbon's head (`points[0]`) to its tail. Width and alpha are interpolated
+// linearly along the ribbon's length; the U texture coordinate runs from 0
+// (head) to 1 (tail).
+pub fn generate(points: &[Vec3], view_position: &Vec3, options: &RibbonOptions) -> Option<Ribbon> {
+    let num_points = points.len();
+
+    if num_points < 2 {
+        return None;
+    }
+
+    // Estimate the ribbon's direction of travel at each point. Where points
+    // coincide, reuse the previous direction (or, for leading points, the
+    // first valid one); a ribbon whose points all coincide has no direction.
+
+    let mut tangents: Vec<Option<Vec3>> = Vec::with_capacity(num_points);
+
+    let mut previous_tangent = None;
+
+    for i in 0..num_points {
+        let previous = points[i.saturating_sub(1)];
+        let next = points[(i + 1).min(num_points - 1)];
+
+        let direction = next - previous;
+
+        if direction.mag() > f32::EPSILON {
+            previous_tangent.replace(direction.as_normal());
+        }
+
+        tangents.push(previous_tangent);
+    }
+
+    let first_tangent = tangents.iter().find_map(|tangent| *tangent)?;
+
+    let mut vertices: Vec<Vec3> = Vec::with_capacity(num_points * 2);
+    let mut uvs: Vec<Vec2> = Vec::with_capacity(num_points * 2);
+    let mut normals: Vec<Vec3> = Vec::with_capacity(num_points);
+    let mut alphas: Vec<f32> = Vec::with_capacity(num_points * 2);
+
+    for (i, point) in points.iter().enumerate() {
+        let alpha = i as f32 / (num_points - 1) as f32;
+
+        let tangent = tangents[i].unwrap_or(first_tangent);
+
+        // Expand the ribbon perpendicular to both its direction and the view.
+
+        let to_view = (*view_position - *point).as_normal();
+
+        let half_width =
+            (options.head_width + (options.tail_width - options.head_width) * alpha) / 2.0;
+
+        let side = tangent.cross(to_view).as_normal() * half_width;
+
+        vertices.push(*point - side);
+        vertices.push(*point + side);
+
+        uvs.push(Vec2 {
+            x: alpha,
+            y: 0.0,
+            z: 0.0,
+        });
+
+        uvs.push(Vec2 {
+            x: alpha,
+            y: 1.0,
+            z: 0.0,
+        });
+
+        normals.push(to_view);
+
+        let vertex_alpha = options.head_alpha + (options.tail_alpha - options.head_alpha) * alpha;
+
+        alphas.push(vertex_alpha);
+        alphas.push(vertex_alpha);
+    }
+
+    let mut faces: Vec<PartialFace> = Vec::with_capacity((num_points - 1) * 2);
+
+    for i in 0..num_points - 1 {
+        let (near_left, near_right) = (i * 2, i * 2 + 1);
+        let (far_left, far_right) = (near_left + 2, near_right + 2);
+
+        faces.push(PartialFace {
+            vertices: [near_left, far_left, near_right],
+            uvs: Some([near_left, far_left, near_right]),
+            normals: Some([i, i + 1, i]),
+        });
+
+        faces.push(PartialFace {
+            vertices: [near_right, far_left, far_right],
+            uvs: Some([near_right, far_left, far_right]),
+            normals: Some([i, i + 1, i + 1]),
+        });
+    }
+
+    // Package the geometry.
+
+    let geometry = MeshGeometry {
+        vertices: vertices.into_boxed_slice(),
+        uvs: uvs.into_boxed_slice(),
+        normals: normals.into_boxed_slice(),
+    };
+
+    let mut mesh = Mesh::new(Rc::new(geometry), faces, None);
+
+    mesh.object_name = Some("ribbon".to_string());
+
+    Some(Ribbon { mesh, alphas })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::vec::vec3::Vec3;
+
+    use super::{generate, RibbonOptions};
+
+    #[test]
+    fn coincident_points_reuse_the_previous_direction() {
+        let point = |x: f32| Vec3 { x, y: 0.0, z: 0.0 };
+
+        let view_position = Vec3 {
+            x: 0.0,
+            y: 0.0,
+            z: -10.0,
+        };
+
+        let options = RibbonOptions::default();
+
+        // The first two points, and the last two points, coincide.
+
+        let points = [point(0.0), point(0.0), point(1.0), point(2.0), point(2.0)];
+
+        let ribbon = generate(&points, &view_position, &options).unwrap();
+
+        for vertex in ribbon.mesh.geometry.vertices.iter() {
+            assert!(vertex.x.is_finite() && vertex.y.is_finite() && vertex.z.is_finite());
+        }
+
+        // The head still spans the full head width, across the direction of travel.
+
+        let head_span = ribbon.mesh.geometry.vertices[1] - ribbon.mesh.geometry.vertices[0];
+
+        assert!((head_span.mag() - options.head_width).abs() < 1e-5);
+        assert!(head_span.x.abs() < 1e-5);
+
+        // A ribbon whose points all coincide has no direction.
+
+        assert!(generate(&[point(1.0); 3], &view_position, &options).is_none());
+    }
+}
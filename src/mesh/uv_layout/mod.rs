@@ -0,0 +1,219 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    buffer::Buffer2D,
+    color::{self, Color},
+    graphics::Graphics,
+    vec::vec2::Vec2,
+};
+
+use super::Mesh;
+
+static BACKGROUND_COLOR: Color = color::BLACK;
+static FILL_COLOR: Color = color::DARK_GRAY;
+static OVERLAP_COLOR: Color = color::RED;
+static EDGE_COLOR: Color = color::LIGHT_GRAY;
+static SEAM_COLOR: Color = color::YELLOW;
+
+// A mesh's faces, drawn in UV space, for spotting layout issues before baking
+// (AO, lightmaps, etc).
+#[derive(Debug, Clone)]
+pub struct UvLayout {
+    pub buffer: Buffer2D<u32>,
+    // Faces whose UV triangles overlap those of another face.
+    pub overlapping_faces: Vec<usize>,
+    // Edges (as pairs of vertex indices) shared by faces that map them to
+    // different UVs.
+    pub seams: Vec<(usize, usize)>,
+}
+
+impl UvLayout {
+    pub fn from_mesh(mesh: &Mesh, width: u32, height: u32) -> Result<Self, String> {
+        let uvs = &mesh.geometry.uvs;
+
+        if mesh
+            .faces
+            .iter()
+            .any(|face| face.uvs.iter().any(|index| *index >= uvs.len()))
+        {
+            return Err("Cannot draw the UV layout of a mesh without UVs.".to_string());
+        }
+
+        let to_pixel = |uv: &Vec2| (uv.x * width as f32, (1.0 - uv.y) * height as f32);
+
+        let mut buffer = Buffer2D::new(width, height, Some(BACKGROUND_COLOR.to_u32()));
+
+        // 1. Rasterize each face's UV triangle, noting the faces that cover
+        // each pixel.
+
+        let mut owners: Vec<Option<usize>> = vec![None; (width * height) as usize];
+
+        let mut overlapping_faces = BTreeSet::new();
+
+        for (face_index, face) in mesh.faces.iter().enumerate() {
+            let points = face.uvs.map(|index| to_pixel(&uvs[index]));
+
+            Graphics::for_each_triangle_pixel(width, height, points, |x, y| {
+                let owner = &mut owners[(y * width + x) as usize];
+
+                match owner {
+                    Some(other_face_index) => {
+                        overlapping_faces.insert(*other_face_index);
+                        overlapping_faces.insert(face_index);
+
+                        buffer.set(x, y, OVERLAP_COLOR.to_u32());
+                    }
+                    None => {
+                        owner.replace(face_index);
+
+                        buffer.set(x, y, FILL_COLOR.to_u32());
+                    }
+                }
+            });
+        }
+
+        // 2. Find seams, where faces sharing an edge disagree on its UVs.
+
+        let mut edge_uvs: HashMap<(usize, usize), (Vec2, Vec2)> = HashMap::new();
+
+        let mut seams = BTreeSet::new();
+
+        for face in &mesh.faces {
+            for i in 0..3 {
+                let j = (i + 1) % 3;
+
+                let (mut a, mut b) = (face.vertices[i], face.vertices[j]);
+                let (mut uv_a, mut uv_b) = (uvs[face.uvs[i]], uvs[face.uvs[j]]);
+
+                if a > b {
+                    (a, b) = (b, a);
+                    (uv_a, uv_b) = (uv_b, uv_a);
+                }
+
+                match edge_uvs.get(&(a, b)) {
+                    Some(other) => {
+                        if *other != (uv_a, uv_b) {
+                            seams.insert((a, b));
+                        }
+                    }
+                    None => {
+                        edge_uvs.insert((a, b), (uv_a, uv_b));
+                    }
+                }
+            }
+        }
+
+        // 3. Outline each face, highlighting its seam edges.
+
+        for face in &mesh.faces {
+            for i in 0..3 {
+                let j = (i + 1) % 3;
+
+                let (a, b) = (face.vertices[i], face.vertices[j]);
+
+                let is_seam = seams.contains(&(a.min(b), a.max(b)));
+
+                let (start, end) = (to_pixel(&uvs[face.uvs[i]]), to_pixel(&uvs[face.uvs[j]]));
+
+                Graphics::line(
+                    &mut buffer,
+                    start.0 as i32,
+                    start.1 as i32,
+                    end.0 as i32,
+                    end.1 as i32,
+                    if is_seam { SEAM_COLOR } else { EDGE_COLOR }.to_u32(),
+                );
+            }
+        }
+
+        Ok(Self {
+            buffer,
+            overlapping_faces: overlapping_faces.into_iter().collect(),
+            seams: seams.into_iter().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use crate::{
+        color,
+        mesh::{face::PartialFace, mesh_geometry::MeshGeometry, Mesh},
+        vec::{vec2::Vec2, vec3::Vec3},
+    };
+
+    use super::UvLayout;
+
+    fn uv(x: f32, y: f32) -> Vec2 {
+        Vec2 { x, y, z: 0.0 }
+    }
+
+    #[test]
+    fn overlapping_uv_triangles_are_flagged() {
+        let vertices = [(0, 0), (1, 0), (0, 1), (1, 1), (2, 0), (2, 1)]
+            .map(|(x, y)| Vec3 {
+                x: x as f32,
+                y: y as f32,
+                z: 0.0,
+            })
+            .to_vec();
+
+        let uvs = vec![
+            // Faces 0 and 1 overlap.
+            uv(0.1, 0.1),
+            uv(0.5, 0.1),
+            uv(0.1, 0.5),
+            uv(0.2, 0.2),
+            uv(0.6, 0.2),
+            uv(0.2, 0.6),
+            // Faces 2 and 3 form a quad, sharing a (diagonal) edge.
+            uv(0.65, 0.65),
+            uv(0.95, 0.65),
+            uv(0.65, 0.95),
+            uv(0.95, 0.95),
+        ];
+
+        let geometry = MeshGeometry {
+            vertices: vertices.into_boxed_slice(),
+            normals: vec![Vec3::default()].into_boxed_slice(),
+            uvs: uvs.into_boxed_slice(),
+        };
+
+        let partial_faces = [
+            ([0, 1, 2], [0, 1, 2]),
+            ([1, 3, 2], [3, 4, 5]),
+            ([1, 4, 3], [6, 7, 8]),
+            ([4, 5, 3], [7, 9, 8]),
+        ]
+        .map(|(vertices, uvs)| PartialFace {
+            vertices,
+            normals: None,
+            uvs: Some(uvs),
+        })
+        .to_vec();
+
+        let mesh = Mesh::new(Rc::new(geometry), partial_faces, None);
+
+        let layout = UvLayout::from_mesh(&mesh, 64, 64).unwrap();
+
+        assert_eq!(layout.overlapping_faces, vec![0, 1]);
+
+        // Where faces 0 and 1 overlap, in UV space.
+
+        assert_eq!(*layout.buffer.get(16, 47), color::RED.to_u32());
+
+        // Faces 1 and 2 are each mapped to separate islands.
+
+        assert_eq!(layout.seams, vec![(1, 2), (1, 3)]);
+
+        // Meshes without UVs have no layout to draw.
+
+        let mut mesh_without_uvs = mesh.clone();
+
+        Rc::make_mut(&mut mesh_without_uvs.geometry).uvs = Default::default();
+
+        assert!(UvLayout::from_mesh(&mesh_without_uvs, 64, 64).is_err());
+    }
+}
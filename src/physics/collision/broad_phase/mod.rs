@@ -0,0 +1,97 @@
+use std::cmp::Ordering;
+
+use crate::geometry::primitives::aabb::AABB;
+
+pub type CollisionPair = (usize, usize);
+
+// Returns all candidate pairs of (potentially) overlapping AABBs, using a
+// sweep-and-prune along the x-axis. Pairs are reported as indices into
+// `aabbs`, with the lower index first, sorted in ascending order.
+pub fn sweep_and_prune(aabbs: &[AABB]) -> Vec<CollisionPair> {
+    let mut pairs = vec![];
+
+    // Sort our AABB indices by each box's minimum extent along the sweep axis.
+
+    let mut sorted: Vec<usize> = (0..aabbs.len()).collect();
+
+    sorted.sort_by(|a, b| {
+        aabbs[*a]
+            .min
+            .x
+            .partial_cmp(&aabbs[*b].min.x)
+            .unwrap_or(Ordering::Equal)
+    });
+
+    // Sweep; maintain a list of "active" boxes whose x-interval may still
+    // overlap with subsequent boxes.
+
+    let mut active: Vec<usize> = vec![];
+
+    for index in sorted {
+        let aabb = &aabbs[index];
+
+        // Prune any active boxes that end before this box begins.
+
+        active.retain(|other| aabbs[*other].max.x >= aabb.min.x);
+
+        for other in &active {
+            if aabb.intersects(&aabbs[*other]) {
+                pairs.push((index.min(*other), index.max(*other)));
+            }
+        }
+
+        active.push(index);
+    }
+
+    pairs.sort();
+
+    pairs
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{geometry::primitives::aabb::AABB, vec::vec3::Vec3};
+
+    use super::sweep_and_prune;
+
+    fn unit_box(min: Vec3) -> AABB {
+        AABB::from_min_max(min, min + Vec3::ones())
+    }
+
+    #[test]
+    fn three_boxes_one_pair() {
+        let aabbs = [
+            unit_box(Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            }),
+            unit_box(Vec3 {
+                x: 0.5,
+                y: 0.5,
+                z: 0.5,
+            }),
+            unit_box(Vec3 {
+                x: 5.0,
+                y: 0.0,
+                z: 0.0,
+            }),
+        ];
+
+        assert_eq!(sweep_and_prune(&aabbs), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn overlapping_on_sweep_axis_only() {
+        let aabbs = [
+            unit_box(Default::default()),
+            unit_box(Vec3 {
+                x: 0.5,
+                y: 0.0,
+                z: 5.0,
+            }),
+        ];
+
+        assert!(sweep_and_prune(&aabbs).is_empty());
+    }
+}
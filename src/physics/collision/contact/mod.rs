@@ -0,0 +1,274 @@
+use crate::{
+    geometry::{
+        accelerator::static_triangle_bvh::StaticTriangleBVH,
+        primitives::{aabb::AABB, plane::Plane},
+    },
+    transform::quaternion::Quaternion,
+    vec::vec3::Vec3,
+};
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct Contact {
+    // World-space point of contact.
+    pub point: Vec3,
+    // Unit-length contact normal, pointing away from the static surface.
+    pub normal: Vec3,
+    // Depth of penetration along `normal` (always positive).
+    pub penetration: f32,
+}
+
+pub fn sphere_plane(center: &Vec3, radius: f32, plane: &Plane) -> Option<Contact> {
+    let distance = plane.get_signed_distance(center);
+
+    if distance >= radius {
+        return None;
+    }
+
+    Some(Contact {
+        point: *center - plane.normal * distance,
+        normal: plane.normal,
+        penetration: radius - distance,
+    })
+}
+
+pub fn box_plane(
+    center: &Vec3,
+    half_extents: &Vec3,
+    orientation: &Quaternion,
+    plane: &Plane,
+) -> Option<Contact> {
+    // Averages the contact point over all box corners found behind the plane.
+
+    let mut point = Vec3::default();
+    let mut penetration: f32 = 0.0;
+    let mut count = 0;
+
+    for corner in get_box_corners(center, half_extents, orientation) {
+        let distance = plane.get_signed_distance(&corner);
+
+        if distance < 0.0 {
+            point += corner;
+            penetration = penetration.max(-distance);
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        return None;
+    }
+
+    Some(Contact {
+        point: point / count as f32,
+        normal: plane.normal,
+        penetration,
+    })
+}
+
+pub fn sphere_triangle(
+    center: &Vec3,
+    radius: f32,
+    v0: &Vec3,
+    v1: &Vec3,
+    v2: &Vec3,
+) -> Option<Contact> {
+    let closest = closest_point_on_triangle(center, v0, v1, v2);
+
+    let delta = *center - closest;
+
+    let distance = delta.mag();
+
+    if distance >= radius {
+        return None;
+    }
+
+    let normal = if distance > f32::EPSILON {
+        delta / distance
+    } else {
+        (*v1 - *v0).cross(*v2 - *v0).as_normal()
+    };
+
+    Some(Contact {
+        point: closest,
+        normal,
+        penetration: radius - distance,
+    })
+}
+
+pub fn point_triangle(point: &Vec3, v0: &Vec3, v1: &Vec3, v2: &Vec3) -> Option<Contact> {
+    // Reports a contact when `point` lies behind the triangle's (front) face,
+    // and projects onto the triangle's interior.
+
+    let normal = (*v1 - *v0).cross(*v2 - *v0).as_normal();
+
+    let distance = normal.dot(*point - *v0);
+
+    if distance >= 0.0 {
+        return None;
+    }
+
+    let projected = *point - normal * distance;
+
+    if (closest_point_on_triangle(&projected, v0, v1, v2) - projected).mag() > f32::EPSILON {
+        return None;
+    }
+
+    Some(Contact {
+        point: projected,
+        normal,
+        penetration: -distance,
+    })
+}
+
+pub fn sphere_bvh(center: &Vec3, radius: f32, bvh: &StaticTriangleBVH) -> Vec<Contact> {
+    let extent = Vec3::ones() * radius;
+
+    let bounds = AABB::from_min_max(*center - extent, *center + extent);
+
+    let mut contacts = vec![];
+
+    for_each_triangle_in(bvh, &bounds, |v0, v1, v2| {
+        if let Some(contact) = sphere_triangle(center, radius, v0, v1, v2) {
+            contacts.push(contact);
+        }
+    });
+
+    contacts
+}
+
+pub fn box_bvh(
+    center: &Vec3,
+    half_extents: &Vec3,
+    orientation: &Quaternion,
+    bvh: &StaticTriangleBVH,
+) -> Vec<Contact> {
+    let corners = get_box_corners(center, half_extents, orientation);
+
+    let mut bounds = AABB::default();
+
+    for corner in &corners {
+        bounds.grow(corner);
+    }
+
+    let mut contacts = vec![];
+
+    for_each_triangle_in(bvh, &bounds, |v0, v1, v2| {
+        for corner in &corners {
+            if let Some(contact) = point_triangle(corner, v0, v1, v2) {
+                contacts.push(contact);
+            }
+        }
+    });
+
+    contacts
+}
+
+pub fn get_box_corners(center: &Vec3, half_extents: &Vec3, orientation: &Quaternion) -> [Vec3; 8] {
+    let local = AABB::from_min_max(-*half_extents, *half_extents).get_vertices();
+
+    local.map(|v| *center + v * *orientation.mat())
+}
+
+pub fn closest_point_on_triangle(p: &Vec3, a: &Vec3, b: &Vec3, c: &Vec3) -> Vec3 {
+    // See: Ericson, "Real-Time Collision Detection", Section 5.1.5.
+
+    let (p, a, b, c) = (*p, *a, *b, *c);
+
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+
+    if d1 <= 0.0 && d2 <= 0.0 {
+        // Vertex region A.
+        return a;
+    }
+
+    let bp = p - b;
+
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+
+    if d3 >= 0.0 && d4 <= d3 {
+        // Vertex region B.
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        // Edge region AB.
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+
+    if d6 >= 0.0 && d5 <= d6 {
+        // Vertex region C.
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        // Edge region AC.
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        // Edge region BC.
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    // Face region.
+
+    let denominator = 1.0 / (va + vb + vc);
+
+    let v = vb * denominator;
+    let w = vc * denominator;
+
+    a + ab * v + ac * w
+}
+
+fn for_each_triangle_in<F>(bvh: &StaticTriangleBVH, bounds: &AABB, mut callback: F)
+where
+    F: FnMut(&Vec3, &Vec3, &Vec3),
+{
+    if bvh.nodes_used == 0 {
+        return;
+    }
+
+    let mut stack = vec![0_usize];
+
+    while let Some(node_index) = stack.pop() {
+        let node = &bvh.nodes[node_index];
+
+        if !node.aabb.intersects(bounds) {
+            continue;
+        }
+
+        if node.is_leaf() {
+            let start = node.primitives_start_index as usize;
+            let end = start + node.primitives_count as usize;
+
+            for tri_index_index in start..end {
+                let tri = &bvh.tris[bvh.tri_indices[tri_index_index]];
+
+                let [v0, v1, v2] = tri.vertices;
+
+                let (v0, v1, v2) = bvh.geometry.get_vertices(v0, v1, v2);
+
+                callback(v0, v1, v2);
+            }
+        } else {
+            stack.push(node.left_child_index as usize);
+            stack.push(node.left_child_index as usize + 1);
+        }
+    }
+}
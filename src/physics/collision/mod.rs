@@ -0,0 +1,4 @@
+pub mod broad_phase;
+pub mod contact;
+pub mod response;
+pub mod spatial_hash;
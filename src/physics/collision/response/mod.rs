@@ -0,0 +1,104 @@
+use crate::{
+    geometry::{
+        accelerator::static_triangle_bvh::StaticTriangleBVH, intersect::intersect_ray_bvh,
+        primitives::ray::Ray,
+    },
+    vec::vec3::Vec3,
+};
+
+use super::contact::Contact;
+
+static DEFAULT_COEFFICIENT_OF_RESTITUTION: f32 = 0.75;
+static DEFAULT_COEFFICIENT_OF_FRICTION: f32 = 0.0;
+
+#[derive(Debug, Copy, Clone)]
+pub struct CollisionResponse {
+    pub restitution: f32,
+    pub friction: f32,
+}
+
+impl Default for CollisionResponse {
+    fn default() -> Self {
+        Self {
+            restitution: DEFAULT_COEFFICIENT_OF_RESTITUTION,
+            friction: DEFAULT_COEFFICIENT_OF_FRICTION,
+        }
+    }
+}
+
+impl CollisionResponse {
+    pub fn resolve(&self, position: &mut Vec3, velocity: &mut Vec3, contact: &Contact) {
+        let normal = contact.normal;
+
+        let normal_speed = velocity.dot(normal);
+
+        if normal_speed < 0.0 {
+            // Compute elasticity response (in the normal direction).
+
+            let velocity_normal = normal * normal_speed;
+
+            let response_velocity_normal = -velocity_normal * self.restitution;
+
+            // Compute friction response (in the tangent direction).
+
+            let velocity_tangent = *velocity - velocity_normal;
+
+            let response_velocity_tangent = if velocity_tangent.is_zero() {
+                velocity_tangent
+            } else {
+                let loss = (self.friction * velocity_normal.mag()).min(velocity_tangent.mag());
+
+                velocity_tangent - velocity_tangent.as_normal() * loss
+            };
+
+            *velocity = response_velocity_normal + response_velocity_tangent;
+        }
+
+        // Reflect any penetration back out of the surface, scaled by restitution.
+
+        *position += normal * (1.0 + self.restitution) * contact.penetration;
+    }
+}
+
+// Tests the line segment from `start` to `end` against the triangles of `bvh`,
+// returning a contact for the nearest crossing (if any). The contact normal
+// always faces back towards `start`.
+pub fn intersect_segment_bvh(start: &Vec3, end: &Vec3, bvh: &StaticTriangleBVH) -> Option<Contact> {
+    let delta = *end - *start;
+
+    let distance = delta.mag();
+
+    if distance < f32::EPSILON {
+        return None;
+    }
+
+    let direction = delta / distance;
+
+    let mut ray = Ray::new(*start, direction);
+
+    intersect_ray_bvh(&mut ray, bvh);
+
+    let tri_index = ray.triangle?;
+
+    if ray.t > distance {
+        return None;
+    }
+
+    let [v0, v1, v2] = bvh.tris[tri_index].vertices;
+
+    let (v0, v1, v2) = bvh.geometry.get_vertices(v0, v1, v2);
+
+    let mut normal = (*v1 - *v0).cross(*v2 - *v0).as_normal();
+
+    if normal.dot(direction) > 0.0 {
+        normal = -normal;
+    }
+
+    let point = *start + direction * ray.t;
+
+    Some(Contact {
+        point,
+        normal,
+        penetration: -normal.dot(*end - point),
+    })
+}
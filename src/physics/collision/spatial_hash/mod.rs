@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use crate::vec::vec3::Vec3;
+
+type CellKey = (i32, i32, i32);
+
+// A uniform grid, hashed by integer cell coordinates. Best suited to many
+// similarly-sized objects (i.e., particles) where `cell_size` is on the order
+// of the typical query radius.
+#[derive(Debug, Clone)]
+pub struct SpatialHashGrid {
+    cell_size: f32,
+    one_over_cell_size: f32,
+    cells: HashMap<CellKey, Vec<usize>>,
+    positions: HashMap<usize, Vec3>,
+}
+
+impl SpatialHashGrid {
+    pub fn new(cell_size: f32) -> Self {
+        assert!(
+            cell_size > 0.0,
+            "Called SpatialHashGrid::new() with a non-positive cell size!"
+        );
+
+        Self {
+            cell_size,
+            one_over_cell_size: 1.0 / cell_size,
+            cells: Default::default(),
+            positions: Default::default(),
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.positions.clear();
+    }
+
+    pub fn insert(&mut self, id: usize, position: Vec3) {
+        if self.positions.contains_key(&id) {
+            self.remove(id);
+        }
+
+        let key = self.get_cell_key(&position);
+
+        self.cells.entry(key).or_default().push(id);
+
+        self.positions.insert(id, position);
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<Vec3> {
+        let position = self.positions.remove(&id)?;
+
+        let key = self.get_cell_key(&position);
+
+        if let Some(cell) = self.cells.get_mut(&key) {
+            cell.retain(|other| *other != id);
+
+            if cell.is_empty() {
+                self.cells.remove(&key);
+            }
+        }
+
+        Some(position)
+    }
+
+    pub fn query_radius(&self, position: &Vec3, radius: f32) -> Vec<usize> {
+        let mut result = vec![];
+
+        let radius_squared = radius * radius;
+
+        let min = self.get_cell_key(&(*position - Vec3::ones() * radius));
+        let max = self.get_cell_key(&(*position + Vec3::ones() * radius));
+
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                for z in min.2..=max.2 {
+                    if let Some(cell) = self.cells.get(&(x, y, z)) {
+                        for id in cell {
+                            let delta = self.positions[id] - *position;
+
+                            if delta.dot(delta) <= radius_squared {
+                                result.push(*id);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    // Returns all other entries within `radius` of the entry `id`.
+    pub fn neighbors(&self, id: usize, radius: f32) -> Vec<usize> {
+        match self.positions.get(&id) {
+            Some(position) => self
+                .query_radius(position, radius)
+                .into_iter()
+                .filter(|other| *other != id)
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    fn get_cell_key(&self, position: &Vec3) -> CellKey {
+        (
+            (position.x * self.one_over_cell_size).floor() as i32,
+            (position.y * self.one_over_cell_size).floor() as i32,
+            (position.z * self.one_over_cell_size).floor() as i32,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::vec::vec3::Vec3;
+
+    use super::SpatialHashGrid;
+
+    #[test]
+    fn neighbors() {
+        let mut grid = SpatialHashGrid::new(1.0);
+
+        grid.insert(
+            0,
+            Vec3 {
+                x: 0.2,
+                y: 0.2,
+                z: 0.2,
+            },
+        );
+
+        grid.insert(
+            1,
+            Vec3 {
+                x: 0.6,
+                y: 0.4,
+                z: 0.3,
+            },
+        );
+
+        grid.insert(
+            2,
+            Vec3 {
+                x: 10.0,
+                y: -4.0,
+                z: 7.0,
+            },
+        );
+
+        assert_eq!(grid.neighbors(0, 1.0), vec![1]);
+        assert_eq!(grid.neighbors(1, 1.0), vec![0]);
+        assert!(grid.neighbors(2, 1.0).is_empty());
+    }
+
+    #[test]
+    fn reinsert_moves_entry() {
+        let mut grid = SpatialHashGrid::new(1.0);
+
+        grid.insert(0, Default::default());
+        grid.insert(
+            0,
+            Vec3 {
+                x: 5.0,
+                y: 0.0,
+                z: 0.0,
+            },
+        );
+
+        assert_eq!(grid.len(), 1);
+        assert!(grid.query_radius(&Default::default(), 0.5).is_empty());
+    }
+}
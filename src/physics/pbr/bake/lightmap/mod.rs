@@ -0,0 +1,431 @@
+use crate::{
+    buffer::Buffer2D,
+    geometry::{
+        accelerator::static_triangle_bvh::StaticTriangleBVH, intersect::intersect_ray_bvh,
+        primitives::ray::Ray,
+    },
+    graphics::Graphics,
+    mesh::{lightmap::lightmap_uv_to_texel, Mesh},
+    physics::pbr::sampling::cosine_sample_hemisphere,
+    random::sequence::hammersley_2d_sequence,
+    scene::light::{directional_light::DirectionalLight, point_light::PointLight},
+    texture::map::{TextureMap, TextureMapWrapping},
+    vec::vec3::{self, Vec3},
+};
+
+// Offsets ray origins from the surface, to avoid self-intersection.
+static SURFACE_BIAS: f32 = 0.001;
+
+#[derive(Debug, Copy, Clone)]
+pub struct LightmapBakeOptions {
+    pub resolution: u32,
+    // Number of (cosine-weighted) rays gathering bounced light, per texel.
+    pub bounce_samples: u32,
+    // Fraction of the incoming light that surfaces reflect back into the scene.
+    pub bounce_albedo: Vec3,
+}
+
+impl Default for LightmapBakeOptions {
+    fn default() -> Self {
+        Self {
+            resolution: 128,
+            bounce_samples: 64,
+            bounce_albedo: vec3::ONES * 0.5,
+        }
+    }
+}
+
+// Bakes the direct and (single-)bounced irradiance over the surface of `mesh`
+// into a lightmap, addressed by the mesh's lightmap UVs (see
+// `Mesh::generate_lightmap_uvs()`). Light is occluded and bounced by the
+// surfaces of `occluders` (e.g., the scene's static geometry, including
+// `mesh` itself); lights and occluders are given in the mesh's object space.
+pub fn bake_lightmap(
+    mesh: &Mesh,
+    occluders: &Mesh,
+    directional_lights: &[DirectionalLight],
+    point_lights: &[PointLight],
+    options: &LightmapBakeOptions,
+) -> Result<TextureMap<Vec3>, String> {
+    let resolution = options.resolution;
+
+    if resolution < 2 {
+        return Err(format!("Invalid lightmap resolution {}.", resolution));
+    }
+
+    let bvh = StaticTriangleBVH::new(occluders);
+
+    let face_normals = get_face_normals(mesh);
+
+    let occluder_face_normals = get_face_normals(occluders);
+
+    let mut buffer = Buffer2D::<Vec3>::new(resolution, resolution, None);
+
+    let mut is_baked = vec![false; (resolution * resolution) as usize];
+
+    let one_over_bounce_samples = 1.0 / options.bounce_samples.max(1) as f32;
+
+    for (face_index, face) in mesh.faces.iter().enumerate() {
+        let vertices = face.vertices.map(|index| mesh.geometry.vertices[index]);
+
+        let normals = face.normals.map(|index| {
+            mesh.geometry
+                .normals
+                .get(index)
+                .cloned()
+                .unwrap_or_default()
+        });
+
+        let points = face
+            .lightmap_uvs
+            .map(|uv| lightmap_uv_to_texel(uv, resolution));
+
+        Graphics::for_each_triangle_pixel(resolution, resolution, points, |x, y| {
+            let Some([b0, b1, b2]) =
+                get_barycentric_weights(points, (x as f32 + 0.5, y as f32 + 0.5))
+            else {
+                return;
+            };
+
+            let position = vertices[0] * b0 + vertices[1] * b1 + vertices[2] * b2;
+
+            let normal = {
+                let normal = normals[0] * b0 + normals[1] * b1 + normals[2] * b2;
+
+                if normal.is_zero() {
+                    face_normals[face_index]
+                } else {
+                    normal.as_normal()
+                }
+            };
+
+            // Light arriving directly from the lights.
+
+            let mut irradiance =
+                get_direct_irradiance(&bvh, position, normal, directional_lights, point_lights);
+
+            // Light reflected (once) off of the surrounding surfaces.
+
+            if options.bounce_samples > 0 {
+                let origin = position + normal * SURFACE_BIAS;
+
+                let mut bounced = Vec3::default();
+
+                for i in 0..options.bounce_samples {
+                    let direction = cosine_sample_hemisphere(
+                        hammersley_2d_sequence(i, one_over_bounce_samples),
+                        &normal,
+                    );
+
+                    let mut ray = Ray::new(origin, direction);
+
+                    intersect_ray_bvh(&mut ray, &bvh);
+
+                    if let Some(hit_face_index) = ray.triangle {
+                        let hit_position = origin + direction * ray.t;
+
+                        // Light leaves the side of the surface facing the ray.
+
+                        let hit_normal = {
+                            let normal = occluder_face_normals[hit_face_index];
+
+                            if normal.dot(direction) > 0.0 {
+                                -normal
+                            } else {
+                                normal
+                            }
+                        };
+
+                        bounced += get_direct_irradiance(
+                            &bvh,
+                            hit_position,
+                            hit_normal,
+                            directional_lights,
+                            point_lights,
+                        );
+                    }
+                }
+
+                irradiance += options.bounce_albedo * bounced * one_over_bounce_samples;
+            }
+
+            buffer.set(x, y, irradiance);
+
+            is_baked[(y * resolution + x) as usize] = true;
+        });
+    }
+
+    dilate(&mut buffer, &mut is_baked, 2);
+
+    let mut map = TextureMap::from_buffer(resolution, resolution, buffer);
+
+    map.sampling_options.wrapping = TextureMapWrapping::ClampToEdge;
+
+    Ok(map)
+}
+
+// Irradiance arriving at a point (and facing) on the surface of the BVH's mesh,
+// directly from the given lights.
+pub fn get_direct_irradiance(
+    bvh: &StaticTriangleBVH,
+    position: Vec3,
+    normal: Vec3,
+    directional_lights: &[DirectionalLight],
+    point_lights: &[PointLight],
+) -> Vec3 {
+    let origin = position + normal * SURFACE_BIAS;
+
+    let mut irradiance = Vec3::default();
+
+    for light in directional_lights {
+        let direction_to_light = (-light.get_direction().to_vec3()).as_normal();
+
+        let likeness = normal.dot(direction_to_light);
+
+        if likeness <= 0.0 {
+            continue;
+        }
+
+        let mut ray = Ray::new(origin, direction_to_light);
+
+        intersect_ray_bvh(&mut ray, bvh);
+
+        if ray.triangle.is_none() {
+            irradiance += light.intensities * likeness;
+        }
+    }
+
+    for light in point_lights {
+        let to_light = light.position - origin;
+
+        let distance = to_light.mag();
+
+        if distance == 0.0 {
+            continue;
+        }
+
+        let direction_to_light = to_light / distance;
+
+        let likeness = normal.dot(direction_to_light);
+
+        if likeness <= 0.0 {
+            continue;
+        }
+
+        let mut ray = Ray::new(origin, direction_to_light);
+
+        intersect_ray_bvh(&mut ray, bvh);
+
+        if ray.triangle.is_none() || ray.t >= distance {
+            let attenuation = light.get_attenuation().attenuate_for_distance(distance);
+
+            irradiance += light.intensities * attenuation * likeness;
+        }
+    }
+
+    irradiance
+}
+
+fn get_face_normals(mesh: &Mesh) -> Vec<Vec3> {
+    let vertices = &mesh.geometry.vertices;
+
+    mesh.faces
+        .iter()
+        .map(|face| {
+            let (v0, v1, v2) = (
+                vertices[face.vertices[0]],
+                vertices[face.vertices[1]],
+                vertices[face.vertices[2]],
+            );
+
+            let normal = (v1 - v0).cross(v2 - v0);
+
+            if normal.is_zero() {
+                normal
+            } else {
+                normal.as_normal()
+            }
+        })
+        .collect()
+}
+
+fn get_barycentric_weights(points: [(f32, f32); 3], p: (f32, f32)) -> Option<[f32; 3]> {
+    let [a, b, c] = points;
+
+    let area = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+
+    if area == 0.0 {
+        return None;
+    }
+
+    let w1 = ((p.0 - a.0) * (c.1 - a.1) - (p.1 - a.1) * (c.0 - a.0)) / area;
+    let w2 = ((b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)) / area;
+
+    Some([1.0 - w1 - w2, w1, w2])
+}
+
+// Grows the baked texels outward (into each chart's padding), so that
+// filtering near a chart's edges doesn't blend in unbaked (black) texels.
+fn dilate(buffer: &mut Buffer2D<Vec3>, is_baked: &mut [bool], passes: usize) {
+    let (width, height) = (buffer.width as i32, buffer.height as i32);
+
+    for _ in 0..passes {
+        let was_baked = is_baked.to_vec();
+
+        for y in 0..height {
+            for x in 0..width {
+                if was_baked[(y * width + x) as usize] {
+                    continue;
+                }
+
+                let mut sum = Vec3::default();
+                let mut count = 0;
+
+                for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                    let (nx, ny) = (x + dx, y + dy);
+
+                    if nx < 0 || ny < 0 || nx >= width || ny >= height {
+                        continue;
+                    }
+
+                    if was_baked[(ny * width + nx) as usize] {
+                        sum += *buffer.get(nx as u32, ny as u32);
+                        count += 1;
+                    }
+                }
+
+                if count > 0 {
+                    buffer.set(x as u32, y as u32, sum / count as f32);
+
+                    is_baked[(y * width + x) as usize] = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use crate::{
+        mesh::{
+            face::PartialFace, lightmap::lightmap_uv_to_texel, mesh_geometry::MeshGeometry, Mesh,
+        },
+        scene::light::point_light::PointLight,
+        vec::{vec2::Vec2, vec3::Vec3},
+    };
+
+    use super::{bake_lightmap, LightmapBakeOptions};
+
+    fn vec3(x: f32, y: f32, z: f32) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    #[test]
+    fn lit_texels_receive_more_energy_than_shadowed_texels() {
+        // A floor (facing up) with a small, floating blocker (facing down)
+        // between the light and the floor's left half.
+
+        let vertices = vec![
+            // Floor
+            vec3(-2.0, 0.0, -1.0),
+            vec3(2.0, 0.0, -1.0),
+            vec3(2.0, 0.0, 1.0),
+            vec3(-2.0, 0.0, 1.0),
+            // Blocker
+            vec3(-1.5, 1.0, -0.5),
+            vec3(-0.5, 1.0, -0.5),
+            vec3(-0.5, 1.0, 0.5),
+            vec3(-1.5, 1.0, 0.5),
+        ];
+
+        let normals = vec![vec3(0.0, 1.0, 0.0), vec3(0.0, -1.0, 0.0)];
+
+        let partial_faces = [
+            ([0, 2, 1], 0),
+            ([0, 3, 2], 0),
+            ([4, 5, 6], 1),
+            ([4, 6, 7], 1),
+        ]
+        .map(|(vertices, normal)| PartialFace {
+            vertices,
+            normals: Some([normal; 3]),
+            uvs: None,
+        })
+        .to_vec();
+
+        let geometry = MeshGeometry {
+            vertices: vertices.into_boxed_slice(),
+            normals: normals.into_boxed_slice(),
+            uvs: vec![Vec2::default()].into_boxed_slice(),
+        };
+
+        let mut mesh = Mesh::new(Rc::new(geometry), partial_faces, None);
+
+        let resolution = 64;
+
+        mesh.generate_lightmap_uvs(resolution).unwrap();
+
+        // A light directly above the blocker, and the floor's center.
+
+        let mut light = PointLight::new();
+
+        light.position = vec3(-1.0, 3.0, 0.0);
+
+        let lightmap = bake_lightmap(
+            &mesh,
+            &mesh,
+            &[],
+            &[light],
+            &LightmapBakeOptions {
+                resolution,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // Finds the texel (in the floor's charts) nearest a point on the floor.
+
+        let get_floor_energy = |x: f32, z: f32| -> f32 {
+            for face in &mesh.faces[0..2] {
+                let [a, b, c] = face.vertices.map(|index| mesh.geometry.vertices[index]);
+
+                // Solves for the point's barycentric weights, on the XZ plane.
+
+                let area = (b.x - a.x) * (c.z - a.z) - (b.z - a.z) * (c.x - a.x);
+
+                let w1 = ((x - a.x) * (c.z - a.z) - (z - a.z) * (c.x - a.x)) / area;
+                let w2 = ((b.x - a.x) * (z - a.z) - (b.z - a.z) * (x - a.x)) / area;
+                let w0 = 1.0 - w1 - w2;
+
+                if w0 < 0.0 || w1 < 0.0 || w2 < 0.0 {
+                    continue;
+                }
+
+                let uv = face.lightmap_uvs[0] * w0
+                    + face.lightmap_uvs[1] * w1
+                    + face.lightmap_uvs[2] * w2;
+
+                let (texel_x, texel_y) = lightmap_uv_to_texel(uv, resolution);
+
+                let irradiance = lightmap.levels[0].0.get(texel_x as u32, texel_y as u32);
+
+                return irradiance.x + irradiance.y + irradiance.z;
+            }
+
+            panic!("Point ({}, {}) is not on the floor.", x, z);
+        };
+
+        let shadowed = get_floor_energy(-1.0, 0.0);
+        let lit = get_floor_energy(1.0, 0.0);
+
+        assert!(lit > 0.0);
+        assert!(
+            lit > shadowed * 2.0,
+            "lit = {}, shadowed = {}",
+            lit,
+            shadowed
+        );
+    }
+}
@@ -28,6 +28,7 @@ use crate::{
 };
 
 pub mod brdf;
+pub mod lightmap;
 pub mod shaders;
 
 pub struct HDRBakeResult {
@@ -1,3 +1,4 @@
 pub mod bake;
 pub mod brdf;
 pub mod sampling;
+pub mod spherical_harmonics;
@@ -40,3 +40,43 @@ pub fn importance_sample_ggx(x_i: Vec2, normal: &Vec3, roughness: f32) -> Vec3 {
 
     biased_sample.as_normal()
 }
+
+// Maps a (uniform) 2D sample to a direction in the hemisphere around `normal`,
+// distributed proportionally to the cosine of its angle with `normal`.
+pub fn cosine_sample_hemisphere(x_i: Vec2, normal: &Vec3) -> Vec3 {
+    let phi = TAU * x_i.x;
+
+    let sin_theta = x_i.y.sqrt();
+
+    let cosine_theta = (1.0 - x_i.y).sqrt();
+
+    let up = if normal.y.abs() < 0.999 {
+        vec3::UP
+    } else {
+        vec3::RIGHT
+    };
+
+    let tangent = up.cross(*normal).as_normal();
+
+    let bitangent = normal.cross(tangent);
+
+    (tangent * (phi.cos() * sin_theta)
+        + bitangent * (phi.sin() * sin_theta)
+        + *normal * cosine_theta)
+        .as_normal()
+}
+
+// Maps a (uniform) 2D sample to a direction on the unit sphere, uniformly.
+pub fn uniform_sample_sphere(x_i: Vec2) -> Vec3 {
+    let z = 1.0 - 2.0 * x_i.x;
+
+    let r = (1.0 - z * z).max(0.0).sqrt();
+
+    let phi = TAU * x_i.y;
+
+    Vec3 {
+        x: r * phi.cos(),
+        y: r * phi.sin(),
+        z,
+    }
+}
@@ -0,0 +1,391 @@
+use std::{
+    f32::consts::{FRAC_1_SQRT_2, PI},
+    ops::{Add, Mul, Sub},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{matrix::Mat4, random::sequence::hammersley_2d_sequence, vec::vec3::Vec3};
+
+use super::sampling::uniform_sample_sphere;
+
+pub const SH_COEFFICIENT_COUNT: usize = 9;
+
+// Convolution of each band with a (clamped) cosine lobe, for irradiance.
+static BAND_IRRADIANCE_FACTORS: [f32; 3] = [PI, 2.0 * PI / 3.0, PI / 4.0];
+
+static COEFFICIENT_BANDS: [usize; SH_COEFFICIENT_COUNT] = [0, 1, 1, 1, 2, 2, 2, 2, 2];
+
+// Directions at which each band's (rotated) basis functions are matched, when
+// rotating; chosen so that the resulting linear systems are well-conditioned.
+
+static BAND_1_DIRECTIONS: [Vec3; 3] = [
+    Vec3 {
+        x: 1.0,
+        y: 0.0,
+        z: 0.0,
+    },
+    Vec3 {
+        x: 0.0,
+        y: 1.0,
+        z: 0.0,
+    },
+    Vec3 {
+        x: 0.0,
+        y: 0.0,
+        z: 1.0,
+    },
+];
+
+static BAND_2_DIRECTIONS: [Vec3; 5] = [
+    Vec3 {
+        x: 1.0,
+        y: 0.0,
+        z: 0.0,
+    },
+    Vec3 {
+        x: 0.0,
+        y: 0.0,
+        z: 1.0,
+    },
+    Vec3 {
+        x: FRAC_1_SQRT_2,
+        y: FRAC_1_SQRT_2,
+        z: 0.0,
+    },
+    Vec3 {
+        x: FRAC_1_SQRT_2,
+        y: 0.0,
+        z: FRAC_1_SQRT_2,
+    },
+    Vec3 {
+        x: 0.0,
+        y: FRAC_1_SQRT_2,
+        z: FRAC_1_SQRT_2,
+    },
+];
+
+// RGB radiance over the sphere, projected onto the first 3 bands (L0-L2) of the
+// real spherical harmonics basis.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SphericalHarmonics {
+    pub coefficients: [Vec3; SH_COEFFICIENT_COUNT],
+}
+
+impl SphericalHarmonics {
+    pub fn get_basis(direction: &Vec3) -> [f32; SH_COEFFICIENT_COUNT] {
+        let Vec3 { x, y, z } = *direction;
+
+        [
+            0.282_095,
+            0.488_603 * y,
+            0.488_603 * z,
+            0.488_603 * x,
+            1.092_548 * x * y,
+            1.092_548 * y * z,
+            0.315_392 * (3.0 * z * z - 1.0),
+            1.092_548 * x * z,
+            0.546_274 * (x * x - y * y),
+        ]
+    }
+
+    // Integrates `radiance` (a function of direction) over the sphere, using
+    // `sample_count` (uniformly distributed) directions.
+    pub fn project<F>(sample_count: u32, mut radiance: F) -> Self
+    where
+        F: FnMut(Vec3) -> Vec3,
+    {
+        let mut result = Self::default();
+
+        let one_over_n = 1.0 / sample_count.max(1) as f32;
+
+        // Each sample covers an equal share of the sphere's solid angle.
+
+        let solid_angle = 4.0 * PI * one_over_n;
+
+        for i in 0..sample_count {
+            let direction = uniform_sample_sphere(hammersley_2d_sequence(i, one_over_n));
+
+            result.add_sample(&direction, radiance(direction), solid_angle);
+        }
+
+        result
+    }
+
+    // Projects the radiance arriving from a single (unit) `direction`, covering
+    // `solid_angle` steradians, and accumulates it.
+    pub fn add_sample(&mut self, direction: &Vec3, radiance: Vec3, solid_angle: f32) {
+        let basis = Self::get_basis(direction);
+
+        for (coefficient, y) in self.coefficients.iter_mut().zip(basis) {
+            *coefficient += radiance * (y * solid_angle);
+        }
+    }
+
+    // Returns the projection of this radiance, rotated by `rotation`; i.e., the
+    // radiance arriving from `direction * rotation` is that which arrived from
+    // `direction` before.
+    pub fn rotated(&self, rotation: &Mat4) -> Self {
+        // Rotations don't mix bands, and leave band 0 (a constant) unchanged.
+
+        let inverse_rotation = rotation.transposed();
+
+        let mut result = *self;
+
+        self.rotate_band(&mut result, 1, &BAND_1_DIRECTIONS, &inverse_rotation);
+        self.rotate_band(&mut result, 2, &BAND_2_DIRECTIONS, &inverse_rotation);
+
+        result
+    }
+
+    fn rotate_band<const N: usize>(
+        &self,
+        result: &mut Self,
+        band: usize,
+        directions: &[Vec3; N],
+        inverse_rotation: &Mat4,
+    ) {
+        // Solves for the coefficients whose (unrotated) basis functions, at each
+        // direction, match the original band evaluated at that direction, in
+        // the original (unrotated) frame.
+
+        let offset = band * band;
+
+        let mut basis = [[0.0; N]; N];
+        let mut targets = [Vec3::default(); N];
+
+        for (k, direction) in directions.iter().enumerate() {
+            let unrotated_basis = Self::get_basis(direction);
+            let rotated_basis = Self::get_basis(&(*direction * *inverse_rotation));
+
+            for j in 0..N {
+                basis[k][j] = unrotated_basis[offset + j];
+
+                targets[k] += self.coefficients[offset + j] * rotated_basis[offset + j];
+            }
+        }
+
+        let coefficients = solve_linear_system(basis, targets);
+
+        result.coefficients[offset..offset + N].copy_from_slice(&coefficients);
+    }
+
+    // Reconstructs the radiance arriving from `direction`.
+    pub fn evaluate(&self, direction: &Vec3) -> Vec3 {
+        let basis = Self::get_basis(direction);
+
+        self.coefficients
+            .iter()
+            .zip(basis)
+            .fold(Vec3::default(), |sum, (coefficient, y)| {
+                sum + *coefficient * y
+            })
+    }
+
+    // Reconstructs the irradiance arriving at a surface facing `normal`.
+    pub fn evaluate_irradiance(&self, normal: &Vec3) -> Vec3 {
+        let basis = Self::get_basis(normal);
+
+        let mut irradiance = Vec3::default();
+
+        for (i, (coefficient, y)) in self.coefficients.iter().zip(basis).enumerate() {
+            irradiance += *coefficient * (BAND_IRRADIANCE_FACTORS[COEFFICIENT_BANDS[i]] * y);
+        }
+
+        irradiance.max(&Vec3::default())
+    }
+}
+
+// Gaussian elimination (with partial pivoting), for a small system `a * x = b`.
+fn solve_linear_system<const N: usize>(mut a: [[f32; N]; N], mut b: [Vec3; N]) -> [Vec3; N] {
+    for column in 0..N {
+        let pivot = (column..N)
+            .max_by(|i, j| a[*i][column].abs().total_cmp(&a[*j][column].abs()))
+            .unwrap();
+
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+
+        if a[column][column] == 0.0 {
+            continue;
+        }
+
+        let pivot_row = a[column];
+        let pivot_target = b[column];
+
+        for row in column + 1..N {
+            let factor = a[row][column] / pivot_row[column];
+
+            for (value, pivot_value) in a[row].iter_mut().zip(pivot_row).skip(column) {
+                *value -= pivot_value * factor;
+            }
+
+            b[row] -= pivot_target * factor;
+        }
+    }
+
+    // Back-substitution.
+
+    let mut x = [Vec3::default(); N];
+
+    for row in (0..N).rev() {
+        let mut sum = b[row];
+
+        for k in row + 1..N {
+            sum -= x[k] * a[row][k];
+        }
+
+        if a[row][row] != 0.0 {
+            x[row] = sum / a[row][row];
+        }
+    }
+
+    x
+}
+
+impl Add for SphericalHarmonics {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        for (a, b) in self.coefficients.iter_mut().zip(rhs.coefficients) {
+            *a += b;
+        }
+
+        self
+    }
+}
+
+impl Sub for SphericalHarmonics {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        for (a, b) in self.coefficients.iter_mut().zip(rhs.coefficients) {
+            *a -= b;
+        }
+
+        self
+    }
+}
+
+impl Mul<f32> for SphericalHarmonics {
+    type Output = Self;
+
+    fn mul(mut self, scalar: f32) -> Self::Output {
+        for coefficient in self.coefficients.iter_mut() {
+            *coefficient *= scalar;
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::PI;
+
+    use crate::{matrix::Mat4, random::sequence::hammersley_2d_sequence, vec::vec3::Vec3};
+
+    use super::{uniform_sample_sphere, SphericalHarmonics};
+
+    #[test]
+    fn projected_constant_radiance_reconstructs_to_that_constant() {
+        let ambient = Vec3 {
+            x: 0.2,
+            y: 0.5,
+            z: 1.0,
+        };
+
+        let sh = SphericalHarmonics::project(1024, |_direction| ambient);
+
+        for i in 0..32 {
+            let direction = uniform_sample_sphere(hammersley_2d_sequence(i, 1.0 / 32.0));
+
+            let radiance = sh.evaluate(&direction);
+
+            let irradiance = sh.evaluate_irradiance(&direction);
+
+            for (actual, expected) in [
+                (radiance, ambient),
+                // Integrated over the (cosine-weighted) hemisphere.
+                (irradiance, ambient * PI),
+            ] {
+                assert!(
+                    (actual - expected).mag() < 0.01 * expected.mag(),
+                    "{} != {}",
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn single_directional_sample_reconstructs_approximately() {
+        let direction = Vec3 {
+            x: 0.6,
+            y: 0.0,
+            z: 0.8,
+        };
+
+        let radiance = Vec3::ones();
+
+        let mut sh = SphericalHarmonics::default();
+
+        sh.add_sample(&direction, radiance, 1.0);
+
+        // A band-limited (L2) projection of a single sample peaks in the
+        // sample's direction, with a value of (L + 1)^2 / 4π.
+
+        let peak = sh.evaluate(&direction);
+
+        assert!((peak.x - 9.0 / (4.0 * PI)).abs() < 0.001, "{}", peak);
+
+        for i in 0..64 {
+            let other = uniform_sample_sphere(hammersley_2d_sequence(i, 1.0 / 64.0));
+
+            if other.dot(direction) < 0.9 {
+                assert!(sh.evaluate(&other).x < peak.x);
+            }
+        }
+
+        // Truncation "rings" back to (1 - 3 + 5) / 4π on the opposite side.
+
+        let opposite = sh.evaluate(&-direction);
+
+        assert!((opposite.x - peak.x / 3.0).abs() < 0.001, "{}", opposite);
+    }
+
+    #[test]
+    fn rotation_matches_projecting_the_rotated_sample() {
+        let direction = Vec3 {
+            x: 0.6,
+            y: 0.0,
+            z: 0.8,
+        };
+
+        let rotation = Mat4::rotation_z(0.7) * Mat4::rotation_x(-1.3);
+
+        let radiance = Vec3 {
+            x: 1.0,
+            y: 0.5,
+            z: 0.25,
+        };
+
+        let mut sh = SphericalHarmonics::default();
+        let mut expected = SphericalHarmonics::default();
+
+        sh.add_sample(&direction, radiance, 1.0);
+        expected.add_sample(&(direction * rotation), radiance, 1.0);
+
+        let rotated = sh.rotated(&rotation);
+
+        for (actual, expected) in rotated.coefficients.iter().zip(expected.coefficients) {
+            assert!(
+                (*actual - expected).mag() < 0.0001,
+                "{} != {}",
+                actual,
+                expected
+            );
+        }
+    }
+}
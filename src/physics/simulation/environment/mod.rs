@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::vec::vec3::Vec3;
+
+use super::{
+    physical_constants::EARTH_GRAVITY,
+    units::{Acceleration, Velocity},
+};
+
+// World-level forces, applied uniformly to every body in a simulation.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct PhysicsEnvironment {
+    pub gravity: Acceleration,
+    pub wind: Velocity,
+    // Linear drag, accelerating a body towards the wind's velocity in
+    // proportion to their difference.
+    pub drag: f32,
+}
+
+impl Default for PhysicsEnvironment {
+    fn default() -> Self {
+        Self {
+            gravity: Vec3 {
+                x: 0.0,
+                y: -EARTH_GRAVITY,
+                z: 0.0,
+            },
+            wind: Default::default(),
+            drag: 0.0,
+        }
+    }
+}
+
+impl PhysicsEnvironment {
+    pub fn zero_gravity() -> Self {
+        Self {
+            gravity: Default::default(),
+            ..Default::default()
+        }
+    }
+
+    pub fn get_acceleration(&self, velocity: &Velocity) -> Acceleration {
+        self.gravity + (self.wind - *velocity) * self.drag
+    }
+}
@@ -1,5 +1,7 @@
+pub mod environment;
 pub mod force;
 pub mod particle;
 pub mod physical_constants;
+pub mod rigid_body;
 pub mod state_vector;
 pub mod units;
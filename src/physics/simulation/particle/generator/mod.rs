@@ -3,7 +3,7 @@ use crate::{
     vec::vec3::Vec3,
 };
 
-use super::{particlelist::ParticleList, Particle};
+use super::{particlelist::ParticleList, sub_emitter::SubEmitter, Particle};
 
 #[derive(Debug, Copy, Clone)]
 pub enum ParticleGeneratorKind {
@@ -57,9 +57,13 @@ impl ParticleGenerator {
         }
     }
 
+    pub fn set_sub_emitter(&mut self, sub_emitter: Option<SubEmitter>) {
+        self.prototype.sub_emitter = sub_emitter;
+    }
+
     pub fn generate<const N: usize>(
         &mut self,
-        list: &mut ParticleList<N>,
+        list: &mut ParticleList,
         sampler: &mut RandomSampler<N>,
         h: f32,
     ) -> Result<(), String> {
@@ -83,6 +87,10 @@ impl ParticleGenerator {
 
         let num_particles_to_generate = num_particles_to_generate_fractional.floor() as usize;
 
+        // Limit our emission rate to what the pool can currently accommodate.
+
+        let num_particles_to_generate = num_particles_to_generate.min(list.available());
+
         for _ in 0..num_particles_to_generate {
             // 1. Determine an initial position for our particle.
 
@@ -1,11 +1,19 @@
 use core::f32;
 
-use crate::vec::vec3::Vec3;
+use crate::{
+    geometry::accelerator::static_triangle_bvh::StaticTriangleBVH,
+    physics::collision::response::{intersect_segment_bvh, CollisionResponse},
+    vec::vec3::Vec3,
+};
 
-use super::units::Velocity;
+use super::{environment::PhysicsEnvironment, units::Velocity};
+
+use sub_emitter::SubEmitter;
 
 pub mod generator;
 pub mod particlelist;
+pub mod sub_emitter;
+pub mod trail;
 
 #[derive(Debug, Copy, Clone)]
 pub struct Particle {
@@ -15,6 +23,9 @@ pub struct Particle {
     pub mass: f32,
     pub position: Vec3,
     pub velocity: Velocity,
+    pub depth: u8,
+    pub sub_emitter: Option<SubEmitter>,
+    pub sub_emitter_elapsed: f32,
 }
 
 impl Default for Particle {
@@ -26,6 +37,9 @@ impl Default for Particle {
             max_age: f32::MAX,
             position: Vec3::default(),
             velocity: Velocity::default(),
+            depth: 0,
+            sub_emitter: None,
+            sub_emitter_elapsed: 0.0,
         }
     }
 }
@@ -35,4 +49,113 @@ impl Particle {
         self.age += h;
         self.age > self.max_age
     }
+
+    // Advances this particle by `h`, under the environment's world forces.
+    pub fn integrate(&mut self, environment: &PhysicsEnvironment, h: f32) {
+        self.velocity += environment.get_acceleration(&self.velocity) * h;
+
+        self.position += self.velocity * h;
+    }
+
+    // Checks whether this particle crossed any triangle of `bvh` while moving
+    // from `previous_position` to its current position; if so, resolves the
+    // collision, updating the particle's position and velocity in place.
+    pub fn collide_bvh(
+        &mut self,
+        previous_position: &Vec3,
+        bvh: &StaticTriangleBVH,
+        response: &CollisionResponse,
+    ) -> bool {
+        match intersect_segment_bvh(previous_position, &self.position, bvh) {
+            Some(contact) => {
+                response.resolve(&mut self.position, &mut self.velocity, &contact);
+
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        geometry::accelerator::static_triangle_bvh::StaticTriangleBVH,
+        mesh::primitive::plane,
+        physics::{
+            collision::response::CollisionResponse, simulation::environment::PhysicsEnvironment,
+        },
+        vec::vec3::Vec3,
+    };
+
+    use super::Particle;
+
+    #[test]
+    fn particle_accelerates_along_custom_gravity() {
+        let gravity = Vec3 {
+            x: 3.0,
+            y: 0.0,
+            z: -4.0,
+        };
+
+        let environment = PhysicsEnvironment {
+            gravity,
+            ..PhysicsEnvironment::zero_gravity()
+        };
+
+        let mut particle = Particle::default();
+
+        for _ in 0..10 {
+            particle.integrate(&environment, 0.1);
+        }
+
+        // Velocity after 1 second should equal the gravity vector.
+
+        assert!((particle.velocity - gravity).mag() < 0.0001);
+
+        // Displacement should point in the direction of gravity.
+
+        assert!((particle.position.as_normal() - gravity.as_normal()).mag() < 0.0001);
+    }
+
+    #[test]
+    fn particle_bounces_off_floor() {
+        let floor = plane::generate(10.0, 10.0, 1, 1);
+
+        let bvh = StaticTriangleBVH::new(&floor);
+
+        let response = CollisionResponse {
+            restitution: 0.5,
+            friction: 0.0,
+        };
+
+        let previous_position = Vec3 {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+
+        let h = 0.2;
+
+        let mut particle = Particle {
+            position: previous_position,
+            velocity: Vec3 {
+                x: 2.0,
+                y: -10.0,
+                z: 0.0,
+            },
+            ..Default::default()
+        };
+
+        particle.position += particle.velocity * h;
+
+        assert!(particle.collide_bvh(&previous_position, &bvh, &response));
+
+        assert!((particle.velocity.y - 5.0).abs() < 0.0001);
+        assert!((particle.velocity.x - 2.0).abs() < 0.0001);
+
+        // Particle should no longer be below the floor.
+
+        assert!(particle.position.y >= 0.0);
+    }
 }
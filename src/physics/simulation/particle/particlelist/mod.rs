@@ -1,9 +1,25 @@
 use std::slice::{Iter, IterMut};
 
+use crate::random::sampler::RandomSampler;
+
 use super::Particle;
 
+static DEFAULT_PARTICLE_LIST_CAPACITY: usize = 1024;
+
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub enum ParticleRecyclePolicy {
+    // Only reuses slots belonging to dead particles; emission is limited (or
+    // refused) while the pool is full.
+    #[default]
+    DeadSlotReuse,
+
+    // When the pool is full, a new particle replaces the oldest living one.
+    OldestFirst,
+}
+
 #[derive(Debug, Clone)]
-pub struct ParticleList<const N: usize> {
+pub struct ParticleList {
+    pub policy: ParticleRecyclePolicy,
     // Stores all particles, including their current alive-or-dead state.
     pool: Vec<Particle>,
     // An array of indices into `pool`; a stack of all inactive cells.
@@ -12,39 +28,57 @@ pub struct ParticleList<const N: usize> {
     inactive_count: usize,
 }
 
-impl<const N: usize> Default for ParticleList<N> {
+impl Default for ParticleList {
     fn default() -> Self {
+        Self::new(DEFAULT_PARTICLE_LIST_CAPACITY, Default::default())
+    }
+}
+
+impl ParticleList {
+    pub fn new(capacity: usize, policy: ParticleRecyclePolicy) -> Self {
         let pool = vec![
             Particle {
                 alive: false,
                 ..Default::default()
             };
-            N
+            capacity
         ];
 
-        let inactive_stack = (0..N).collect();
+        let inactive_stack = (0..capacity).collect();
 
         Self {
+            policy,
             pool,
             inactive_stack,
-            inactive_count: N,
+            inactive_count: capacity,
         }
     }
-}
 
-impl<const N: usize> ParticleList<N> {
     pub fn capacity(&self) -> usize {
-        N
+        self.pool.len()
     }
 
     pub fn active(&self) -> usize {
-        self.pool.capacity() - self.inactive_count
+        self.pool.len() - self.inactive_count
     }
 
     pub fn inactive(&self) -> usize {
         self.inactive_count
     }
 
+    pub fn is_full(&self) -> bool {
+        self.inactive_count == 0
+    }
+
+    // Returns the number of new particles that may currently be activated,
+    // given the pool's occupancy and recycling policy.
+    pub fn available(&self) -> usize {
+        match self.policy {
+            ParticleRecyclePolicy::DeadSlotReuse => self.inactive_count,
+            ParticleRecyclePolicy::OldestFirst => self.pool.len(),
+        }
+    }
+
     pub fn iter(&self) -> Iter<'_, Particle> {
         self.pool.iter()
     }
@@ -70,27 +104,38 @@ impl<const N: usize> ParticleList<N> {
     }
 
     pub fn reset_inactive_stack(&mut self) {
-        self.inactive_stack = (0..N).collect();
+        self.inactive_stack = (0..self.pool.len()).collect();
 
-        self.inactive_count = N;
+        self.inactive_count = self.pool.len();
     }
 
     // Claims and activate a new particle in the pool, based on a description.
     pub fn activate(&mut self, particle: Particle) -> Result<(), String> {
         match self.inactive_stack.pop() {
             Some(index) => {
+                self.inactive_count -= 1;
+
                 self.pool[index] = Particle {
                     alive: true,
-                    ..Default::default()
+                    ..particle
                 };
 
-                self.inactive_count -= 1;
-
-                self.pool[index] = particle;
-
                 Ok(())
             }
-            None => Err("No pool memory.".to_string()),
+            None => match self.policy {
+                ParticleRecyclePolicy::DeadSlotReuse => Err("No pool memory.".to_string()),
+                ParticleRecyclePolicy::OldestFirst => match self.get_oldest() {
+                    Some(index) => {
+                        self.pool[index] = Particle {
+                            alive: true,
+                            ..particle
+                        };
+
+                        Ok(())
+                    }
+                    None => Err("No pool memory.".to_string()),
+                },
+            },
         }
     }
 
@@ -106,6 +151,47 @@ impl<const N: usize> ParticleList<N> {
         }
     }
 
+    // Like `test_and_deactivate()`, but also spawns the children of any
+    // particles whose sub-emitters trigger during this step.
+    pub fn test_and_deactivate_with_sub_emitters<const N: usize>(
+        &mut self,
+        sampler: &mut RandomSampler<N>,
+        h: f32,
+    ) -> Result<(), String> {
+        let mut emissions: Vec<(Particle, usize)> = vec![];
+
+        for (index, particle) in self.pool.iter_mut().enumerate() {
+            if !particle.alive {
+                continue;
+            }
+
+            let is_dying = particle.should_be_killed(h);
+
+            if let Some(sub_emitter) = particle.sub_emitter {
+                let count = sub_emitter.update(particle, is_dying, h);
+
+                if count > 0 {
+                    emissions.push((*particle, count));
+                }
+            }
+
+            if is_dying {
+                particle.alive = false;
+
+                self.inactive_stack.push(index);
+                self.inactive_count += 1;
+            }
+        }
+
+        for (parent, count) in emissions {
+            if let Some(sub_emitter) = parent.sub_emitter {
+                sub_emitter.emit(&parent, count, self, sampler)?;
+            }
+        }
+
+        Ok(())
+    }
+
     // Deactivates all particles.
     pub fn clear(&mut self) {
         for particle in &mut self.pool {
@@ -114,4 +200,55 @@ impl<const N: usize> ParticleList<N> {
 
         self.reset_inactive_stack();
     }
+
+    fn get_oldest(&self) -> Option<usize> {
+        self.pool
+            .iter()
+            .enumerate()
+            .filter(|(_, particle)| particle.alive)
+            .max_by(|(_, a), (_, b)| a.age.total_cmp(&b.age))
+            .map(|(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Particle, ParticleList, ParticleRecyclePolicy};
+
+    fn make_particle(age: f32) -> Particle {
+        Particle {
+            age,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn oldest_first_recycles_oldest() {
+        let mut list = ParticleList::new(2, ParticleRecyclePolicy::OldestFirst);
+
+        list.activate(make_particle(3.0)).unwrap();
+        list.activate(make_particle(1.0)).unwrap();
+
+        assert!(list.is_full());
+
+        assert!(list.activate(make_particle(0.0)).is_ok());
+
+        assert_eq!(list.active(), 2);
+
+        let mut ages: Vec<f32> = list.iter().filter(|p| p.alive).map(|p| p.age).collect();
+
+        ages.sort_by(f32::total_cmp);
+
+        assert_eq!(ages, vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn dead_slot_reuse_refuses_when_full() {
+        let mut list = ParticleList::new(1, ParticleRecyclePolicy::DeadSlotReuse);
+
+        list.activate(make_particle(0.0)).unwrap();
+
+        assert_eq!(list.available(), 0);
+        assert!(list.activate(make_particle(0.0)).is_err());
+    }
 }
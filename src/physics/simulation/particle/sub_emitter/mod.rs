@@ -0,0 +1,197 @@
+use crate::random::sampler::{DirectionSampler, RandomSampler, RangeSampler};
+
+use super::{particlelist::ParticleList, Particle};
+
+#[derive(Default, Debug, Copy, Clone)]
+pub enum SubEmitterTrigger {
+    // Emits once, when the parent particle dies.
+    #[default]
+    OnDeath,
+
+    // OnInterval(seconds)
+    // Emits repeatedly over the parent particle's lifetime; the interval must
+    // be positive and finite, or nothing is emitted.
+    OnInterval(f32),
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct SubEmitter {
+    pub trigger: SubEmitterTrigger,
+    // Number of child particles emitted per trigger.
+    pub count: usize,
+    // Maximum generation of particles that may themselves emit; a particle
+    // emitted directly by a generator has a depth of 0.
+    pub max_depth: u8,
+    // Fraction of the parent particle's velocity inherited by its children.
+    pub inherit_velocity: f32,
+    pub speed_mean: f32,
+    pub speed_range: f32,
+    pub mass: f32,
+    pub max_age: f32,
+}
+
+impl Default for SubEmitter {
+    fn default() -> Self {
+        Self {
+            trigger: Default::default(),
+            count: 8,
+            max_depth: 1,
+            inherit_velocity: 0.0,
+            speed_mean: 1.0,
+            speed_range: 0.0,
+            mass: 1.0,
+            max_age: 1.0,
+        }
+    }
+}
+
+impl SubEmitter {
+    // Returns the number of children that `parent` should emit this tick.
+    pub fn update(&self, parent: &mut Particle, is_dying: bool, h: f32) -> usize {
+        if parent.depth >= self.max_depth {
+            return 0;
+        }
+
+        match self.trigger {
+            SubEmitterTrigger::OnDeath => {
+                if is_dying {
+                    self.count
+                } else {
+                    0
+                }
+            }
+            SubEmitterTrigger::OnInterval(interval) => {
+                // A non-positive (or non-finite) interval would never be used up.
+
+                if !(interval > 0.0 && interval.is_finite()) {
+                    return 0;
+                }
+
+                parent.sub_emitter_elapsed += h;
+
+                let mut triggers = 0;
+
+                while parent.sub_emitter_elapsed >= interval {
+                    parent.sub_emitter_elapsed -= interval;
+
+                    triggers += 1;
+                }
+
+                triggers * self.count
+            }
+        }
+    }
+
+    pub fn emit<const N: usize>(
+        &self,
+        parent: &Particle,
+        count: usize,
+        list: &mut ParticleList,
+        sampler: &mut RandomSampler<N>,
+    ) -> Result<(), String> {
+        let count = count.min(list.available());
+
+        for _ in 0..count {
+            let direction = sampler.sample_direction_uniform();
+
+            let speed = sampler.sample_range_normal(self.speed_mean, self.speed_range / 3.0);
+
+            list.activate(Particle {
+                alive: true,
+                age: 0.0,
+                max_age: self.max_age,
+                mass: self.mass,
+                position: parent.position,
+                velocity: parent.velocity * self.inherit_velocity + direction * speed,
+                depth: parent.depth + 1,
+                sub_emitter: parent.sub_emitter,
+                sub_emitter_elapsed: 0.0,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        physics::simulation::particle::{
+            particlelist::{ParticleList, ParticleRecyclePolicy},
+            Particle,
+        },
+        random::sampler::RandomSampler,
+        vec::vec3::Vec3,
+    };
+
+    use super::{SubEmitter, SubEmitterTrigger};
+
+    #[test]
+    fn death_triggers_children() {
+        let mut list = ParticleList::new(16, ParticleRecyclePolicy::DeadSlotReuse);
+
+        let mut sampler: RandomSampler<64> = Default::default();
+
+        sampler.seed().unwrap();
+
+        let position = Vec3 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+
+        list.activate(Particle {
+            max_age: 1.0,
+            position,
+            sub_emitter: Some(SubEmitter {
+                trigger: SubEmitterTrigger::OnDeath,
+                count: 5,
+                max_depth: 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+
+        // Not yet dead.
+
+        list.test_and_deactivate_with_sub_emitters(&mut sampler, 0.5)
+            .unwrap();
+
+        assert_eq!(list.active(), 1);
+
+        // Parent dies, emitting its children.
+
+        list.test_and_deactivate_with_sub_emitters(&mut sampler, 1.0)
+            .unwrap();
+
+        assert_eq!(list.active(), 5);
+
+        for particle in list.iter().filter(|p| p.alive) {
+            assert_eq!(particle.position, position);
+            assert_eq!(particle.depth, 1);
+        }
+
+        // Children have reached the maximum depth, and emit nothing.
+
+        list.test_and_deactivate_with_sub_emitters(&mut sampler, 2.0)
+            .unwrap();
+
+        assert_eq!(list.active(), 0);
+    }
+
+    #[test]
+    fn invalid_interval_emits_nothing() {
+        for interval in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let sub_emitter = SubEmitter {
+                trigger: SubEmitterTrigger::OnInterval(interval),
+                max_depth: 1,
+                ..Default::default()
+            };
+
+            let mut parent = Particle::default();
+
+            assert_eq!(sub_emitter.update(&mut parent, false, 0.1), 0);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::vec::vec3::Vec3;
+
+use super::particlelist::ParticleList;
+
+// A fixed-size ring buffer of a particle's most recent positions.
+#[derive(Debug, Copy, Clone)]
+pub struct ParticleTrail<const N: usize> {
+    positions: [Vec3; N],
+    head: usize,
+    len: usize,
+    last_age: f32,
+}
+
+impl<const N: usize> Default for ParticleTrail<N> {
+    fn default() -> Self {
+        Self {
+            positions: [Default::default(); N],
+            head: 0,
+            len: 0,
+            last_age: 0.0,
+        }
+    }
+}
+
+impl<const N: usize> ParticleTrail<N> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.last_age = 0.0;
+    }
+
+    pub fn record(&mut self, position: Vec3) {
+        if N == 0 {
+            return;
+        }
+
+        self.head = (self.head + 1) % N;
+
+        self.positions[self.head] = position;
+
+        self.len = (self.len + 1).min(N);
+    }
+
+    // Returns the recorded positions, ordered from newest to oldest.
+    pub fn points(&self) -> Vec<Vec3> {
+        (0..self.len)
+            .map(|i| self.positions[(self.head + N - i) % N])
+            .collect()
+    }
+}
+
+// Records the current position of each living particle in `list` into its
+// corresponding trail; trails are reset whenever their particle dies or its
+// slot is recycled.
+pub fn record_trails<const N: usize>(list: &ParticleList, trails: &mut Vec<ParticleTrail<N>>) {
+    trails.resize(list.capacity(), Default::default());
+
+    for (particle, trail) in list.iter().zip(trails.iter_mut()) {
+        if !particle.alive {
+            if !trail.is_empty() {
+                trail.clear();
+            }
+
+            continue;
+        }
+
+        if particle.age < trail.last_age {
+            trail.clear();
+        }
+
+        trail.last_age = particle.age;
+
+        trail.record(particle.position);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        mesh::primitive::ribbon::{self, RibbonOptions},
+        physics::simulation::particle::{
+            particlelist::{ParticleList, ParticleRecyclePolicy},
+            Particle,
+        },
+        vec::vec3::Vec3,
+    };
+
+    use super::{record_trails, ParticleTrail};
+
+    #[test]
+    fn straight_line_ribbon() {
+        let mut list = ParticleList::new(1, ParticleRecyclePolicy::DeadSlotReuse);
+
+        list.activate(Particle {
+            velocity: Vec3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut trails: Vec<ParticleTrail<8>> = vec![];
+
+        let h = 0.25;
+
+        for _ in 0..6 {
+            for particle in list.iter_mut() {
+                particle.age += h;
+                particle.position += particle.velocity * h;
+            }
+
+            record_trails(&list, &mut trails);
+        }
+
+        let points = trails[0].points();
+
+        assert_eq!(points.len(), 6);
+
+        let view_position = Vec3 {
+            x: 0.5,
+            y: 0.0,
+            z: -10.0,
+        };
+
+        let width = 0.5;
+
+        let ribbon = ribbon::generate(
+            &points,
+            &view_position,
+            &RibbonOptions {
+                head_width: width,
+                tail_width: width,
+                head_alpha: 1.0,
+                tail_alpha: 0.0,
+            },
+        )
+        .unwrap();
+
+        let vertices = &ribbon.mesh.geometry.vertices;
+
+        assert_eq!(vertices.len(), points.len() * 2);
+
+        for pair in vertices.chunks(2) {
+            // Each pair of ribbon vertices straddles the particle's path.
+
+            let midpoint = (pair[0] + pair[1]) / 2.0;
+
+            assert!(midpoint.y.abs() < 0.0001 && midpoint.z.abs() < 0.0001);
+
+            // Expansion happens perpendicular to the path (and view).
+
+            assert!((pair[0].x - pair[1].x).abs() < 0.0001);
+            assert!(((pair[1] - pair[0]).mag() - width).abs() < 0.0001);
+        }
+
+        assert_eq!(ribbon.alphas[0], 1.0);
+        assert_eq!(ribbon.alphas[ribbon.alphas.len() - 1], 0.0);
+    }
+}
@@ -0,0 +1,449 @@
+use std::rc::Rc;
+
+use crate::{
+    geometry::{accelerator::static_triangle_bvh::StaticTriangleBVH, primitives::plane::Plane},
+    matrix::Mat4,
+    physics::collision::contact::{self, Contact},
+    transform::quaternion::Quaternion,
+    vec::vec3::Vec3,
+};
+
+use super::{environment::PhysicsEnvironment, units::Velocity};
+
+static DEFAULT_COEFFICIENT_OF_RESTITUTION: f32 = 0.5;
+static DEFAULT_COEFFICIENT_OF_FRICTION: f32 = 0.3;
+
+#[derive(Debug, Copy, Clone)]
+pub enum RigidBodyShape {
+    // Sphere(radius)
+    Sphere(f32),
+
+    // Box(half_extents)
+    Box(Vec3),
+}
+
+impl Default for RigidBodyShape {
+    fn default() -> Self {
+        Self::Sphere(1.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct RigidBody {
+    pub shape: RigidBodyShape,
+    pub position: Vec3,
+    pub orientation: Quaternion,
+    pub velocity: Velocity,
+    pub angular_velocity: Vec3,
+    pub restitution: f32,
+    pub friction: f32,
+    mass: f32,
+    inverse_mass: f32,
+    inverse_moment_of_inertia: Mat4,
+}
+
+impl Default for RigidBody {
+    fn default() -> Self {
+        Self::sphere(Default::default(), 1.0, 1.0)
+    }
+}
+
+impl RigidBody {
+    pub fn sphere(center: Vec3, radius: f32, mass: f32) -> Self {
+        // Solid sphere: I = (2/5) * m * r^2
+
+        let scale = 0.4 * mass * radius * radius;
+
+        Self::new(
+            RigidBodyShape::Sphere(radius),
+            center,
+            mass,
+            Vec3 {
+                x: scale,
+                y: scale,
+                z: scale,
+            },
+        )
+    }
+
+    pub fn cuboid(center: Vec3, half_extents: Vec3, mass: f32) -> Self {
+        // Solid cuboid: I_x = (1/12) * m * (h^2 + d^2), etc.
+
+        let extents = half_extents * 2.0;
+
+        let (x2, y2, z2) = (
+            extents.x * extents.x,
+            extents.y * extents.y,
+            extents.z * extents.z,
+        );
+
+        Self::new(
+            RigidBodyShape::Box(half_extents),
+            center,
+            mass,
+            Vec3 {
+                x: mass * (y2 + z2) / 12.0,
+                y: mass * (x2 + z2) / 12.0,
+                z: mass * (x2 + y2) / 12.0,
+            },
+        )
+    }
+
+    fn new(shape: RigidBodyShape, position: Vec3, mass: f32, moment_of_inertia: Vec3) -> Self {
+        let inverse_mass = if mass > 0.0 { 1.0 / mass } else { 0.0 };
+
+        let inverse_moment_of_inertia = if mass > 0.0 {
+            Mat4::scale([
+                1.0 / moment_of_inertia.x,
+                1.0 / moment_of_inertia.y,
+                1.0 / moment_of_inertia.z,
+                1.0,
+            ])
+        } else {
+            Mat4::scale([0.0, 0.0, 0.0, 1.0])
+        };
+
+        Self {
+            shape,
+            position,
+            orientation: Default::default(),
+            velocity: Default::default(),
+            angular_velocity: Default::default(),
+            restitution: DEFAULT_COEFFICIENT_OF_RESTITUTION,
+            friction: DEFAULT_COEFFICIENT_OF_FRICTION,
+            mass,
+            inverse_mass,
+            inverse_moment_of_inertia,
+        }
+    }
+
+    pub fn mass(&self) -> f32 {
+        self.mass
+    }
+
+    pub fn inverse_mass(&self) -> f32 {
+        self.inverse_mass
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.inverse_mass == 0.0
+    }
+
+    pub fn integrate(&mut self, acceleration: &Vec3, h: f32) {
+        if self.is_static() {
+            return;
+        }
+
+        // Semi-implicit Euler integration over linear state.
+
+        self.velocity += *acceleration * h;
+
+        self.position += self.velocity * h;
+
+        // First-order integration of orientation.
+        //
+        // See: https://www.ashwinnarayan.com/post/how-to-integrate-quaternions/
+
+        let spin = Quaternion::from_raw(0.0, self.angular_velocity);
+
+        self.orientation += (spin * self.orientation) * (0.5 * h);
+
+        self.orientation.renormalize();
+    }
+
+    pub fn get_contacts(&self, collider: &StaticCollider) -> Vec<Contact> {
+        match (&self.shape, collider) {
+            (RigidBodyShape::Sphere(radius), StaticCollider::Plane(plane)) => {
+                contact::sphere_plane(&self.position, *radius, plane)
+                    .into_iter()
+                    .collect()
+            }
+            (RigidBodyShape::Sphere(radius), StaticCollider::Mesh(bvh)) => {
+                contact::sphere_bvh(&self.position, *radius, bvh)
+            }
+            (RigidBodyShape::Box(half_extents), StaticCollider::Plane(plane)) => {
+                contact::box_plane(&self.position, half_extents, &self.orientation, plane)
+                    .into_iter()
+                    .collect()
+            }
+            (RigidBodyShape::Box(half_extents), StaticCollider::Mesh(bvh)) => {
+                contact::box_bvh(&self.position, half_extents, &self.orientation, bvh)
+            }
+        }
+    }
+
+    pub fn apply_impulse(&mut self, impulse: &Vec3, point: &Vec3) {
+        let r = *point - self.position;
+
+        self.velocity += *impulse * self.inverse_mass;
+
+        self.angular_velocity += self.apply_inverse_inertia(&r.cross(*impulse));
+    }
+
+    pub fn resolve_static_penetration(&mut self, contacts: &[Contact]) {
+        if self.is_static() {
+            return;
+        }
+
+        // Correct the body's position to prevent it from sinking into the
+        // surface; several contacts against one collider (e.g., the corners of a
+        // resting box) share a single correction, using the deepest contact.
+
+        let deepest = contacts
+            .iter()
+            .max_by(|a, b| a.penetration.total_cmp(&b.penetration));
+
+        if let Some(contact) = deepest {
+            self.position += contact.normal * contact.penetration;
+        }
+    }
+
+    pub fn resolve_static_contact(&mut self, contact: &Contact) {
+        if self.is_static() {
+            return;
+        }
+
+        let normal = contact.normal;
+
+        let r = contact.point - self.position;
+
+        let velocity_at_contact = self.velocity + self.angular_velocity.cross(r);
+
+        let normal_speed = velocity_at_contact.dot(normal);
+
+        if normal_speed >= 0.0 {
+            // Already separating.
+
+            return;
+        }
+
+        // Compute the normal (restitution) impulse.
+
+        let normal_impulse_magnitude =
+            -(1.0 + self.restitution) * normal_speed / self.get_effective_mass_inverse(&r, &normal);
+
+        self.apply_impulse(&(normal * normal_impulse_magnitude), &contact.point);
+
+        // Compute the tangential (friction) impulse, bounded by Coulomb's cone.
+
+        let tangent_velocity = velocity_at_contact - normal * normal_speed;
+
+        if tangent_velocity.is_zero() {
+            return;
+        }
+
+        let tangent = tangent_velocity.as_normal();
+
+        let tangent_impulse_magnitude = (-velocity_at_contact.dot(tangent)
+            / self.get_effective_mass_inverse(&r, &tangent))
+        .max(-self.friction * normal_impulse_magnitude);
+
+        self.apply_impulse(&(tangent * tangent_impulse_magnitude), &contact.point);
+    }
+
+    fn get_effective_mass_inverse(&self, r: &Vec3, direction: &Vec3) -> f32 {
+        // 1/m + (r x n) * I^-1 . (r x n)
+
+        let r_cross_direction = r.cross(*direction);
+
+        let angular = self.apply_inverse_inertia(&r_cross_direction);
+
+        self.inverse_mass + angular.dot(r_cross_direction)
+    }
+
+    fn apply_inverse_inertia(&self, v: &Vec3) -> Vec3 {
+        // With row vectors, `v * R` maps a body-space vector to world space, so
+        // the world-space inverse inertia is `R^T * I^-1 * R`.
+
+        let inverse_moment_of_inertia_world_space = {
+            let r = *self.orientation.mat();
+
+            r.transposed() * self.inverse_moment_of_inertia * r
+        };
+
+        *v * inverse_moment_of_inertia_world_space
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum StaticCollider {
+    Plane(Plane),
+    Mesh(Rc<StaticTriangleBVH>),
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct RigidBodySimulation {
+    pub environment: PhysicsEnvironment,
+    pub bodies: Vec<RigidBody>,
+    pub colliders: Vec<StaticCollider>,
+}
+
+impl RigidBodySimulation {
+    pub fn tick(&mut self, h: f32) {
+        for body in self.bodies.iter_mut() {
+            let acceleration = self.environment.get_acceleration(&body.velocity);
+
+            body.integrate(&acceleration, h);
+
+            for collider in &self.colliders {
+                let contacts = body.get_contacts(collider);
+
+                for contact in &contacts {
+                    body.resolve_static_contact(contact);
+                }
+
+                body.resolve_static_penetration(&contacts);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::FRAC_PI_4;
+
+    use crate::{geometry::primitives::plane::Plane, vec::vec3};
+
+    use super::*;
+
+    #[test]
+    fn sphere_bounces_off_plane() {
+        let restitution = 0.5;
+
+        let mut sphere = RigidBody::sphere(
+            Vec3 {
+                y: 1.05,
+                ..Default::default()
+            },
+            1.0,
+            2.0,
+        );
+
+        sphere.restitution = restitution;
+
+        sphere.velocity = Vec3 {
+            y: -10.0,
+            ..Default::default()
+        };
+
+        let mut simulation = RigidBodySimulation {
+            environment: PhysicsEnvironment::zero_gravity(),
+            bodies: vec![sphere],
+            colliders: vec![StaticCollider::Plane(Plane {
+                point: Default::default(),
+                normal: vec3::UP,
+            })],
+        };
+
+        simulation.tick(0.01);
+
+        let body = &simulation.bodies[0];
+
+        assert!((body.velocity.y - 10.0 * restitution).abs() < 0.0001);
+        assert!(body.velocity.x.abs() < 0.0001 && body.velocity.z.abs() < 0.0001);
+        assert!(body.angular_velocity.is_zero());
+
+        // Sphere should have been pushed back out of the plane.
+
+        assert!(body.position.y >= 1.0 - 0.0001);
+    }
+
+    #[test]
+    fn rotated_box_spins_about_its_long_axis() {
+        // A long box, rotated so that its world-space inertia isn't diagonal.
+
+        let mut body = RigidBody::cuboid(
+            Default::default(),
+            Vec3 {
+                x: 2.0,
+                y: 0.5,
+                z: 0.5,
+            },
+            3.0,
+        );
+
+        body.orientation = Quaternion::new(vec3::FORWARD, FRAC_PI_4);
+
+        let long_axis = (vec3::RIGHT * *body.orientation.mat()).as_normal();
+
+        // An impulse whose torque points along the long (world-space) axis.
+
+        let r = long_axis.cross(vec3::FORWARD).as_normal();
+
+        let impulse = long_axis.cross(r);
+
+        body.apply_impulse(&impulse, &r);
+
+        // Torque about a principal axis spins the body about that same axis,
+        // scaled by the inverse of its principal moment of inertia.
+
+        let extents_squared = 1.0 + 1.0;
+
+        let expected = long_axis * (12.0 / (3.0 * extents_squared));
+
+        assert!((body.angular_velocity - expected).mag() < 0.0001);
+
+        // The effective mass agrees: a unit impulse scaled by it changes the
+        // contact point's speed along the impulse direction by exactly 1.
+
+        let mut body = RigidBody::cuboid(
+            Default::default(),
+            Vec3 {
+                x: 2.0,
+                y: 0.5,
+                z: 0.5,
+            },
+            3.0,
+        );
+
+        body.orientation = Quaternion::new(
+            Vec3 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            }
+            .as_normal(),
+            0.7,
+        );
+
+        let point = Vec3 {
+            x: 1.5,
+            y: -0.25,
+            z: 0.5,
+        };
+
+        let direction = vec3::UP;
+
+        let k = body.get_effective_mass_inverse(&point, &direction);
+
+        body.apply_impulse(&(direction * (1.0 / k)), &point);
+
+        let speed = (body.velocity + body.angular_velocity.cross(point)).dot(direction);
+
+        assert!((speed - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn resting_box_corrects_penetration_once() {
+        let mut body = RigidBody::cuboid(Default::default(), vec3::ONES, 1.0);
+
+        // Four corner contacts against the same surface.
+
+        let contacts: Vec<Contact> = [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)]
+            .iter()
+            .map(|(x, z)| Contact {
+                point: Vec3 {
+                    x: *x,
+                    y: -1.0,
+                    z: *z,
+                },
+                normal: vec3::UP,
+                penetration: 0.1,
+            })
+            .collect();
+
+        body.resolve_static_penetration(&contacts);
+
+        assert!((body.position.y - 0.1).abs() < 0.0001);
+    }
+}
@@ -0,0 +1,36 @@
+use crate::vec::vec2::Vec2;
+
+// Returns a deterministic pseudo-random value in [0, 1) for the given 2D
+// coordinate (i.e., white noise).
+
+// See: https://thebookofshaders.com/10/
+pub fn hash_2d(coordinate: Vec2) -> f32 {
+    let dot = coordinate.x * 12.9898 + coordinate.y * 78.233;
+
+    let value = dot.sin() * 43_758.547;
+
+    value - value.floor()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::vec::vec2::Vec2;
+
+    use super::hash_2d;
+
+    #[test]
+    fn hash_is_deterministic_and_normalized() {
+        for i in 0..64 {
+            let coordinate = Vec2 {
+                x: i as f32 * 0.37,
+                y: i as f32 * 0.11,
+                z: 0.0,
+            };
+
+            let value = hash_2d(coordinate);
+
+            assert!((0.0..1.0).contains(&value));
+            assert_eq!(value, hash_2d(coordinate));
+        }
+    }
+}
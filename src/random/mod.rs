@@ -1,2 +1,5 @@
+pub mod hash;
+pub mod poisson_disk;
 pub mod sampler;
 pub mod sequence;
+pub mod stratified;
@@ -0,0 +1,153 @@
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use crate::vec::vec2::Vec2;
+
+static DEFAULT_MAX_ATTEMPTS: usize = 30;
+
+// Generates a set of points within the region [0, width) x [0, height), where
+// no two points lie closer than `min_distance` apart.
+//
+// See: Bridson, "Fast Poisson Disk Sampling in Arbitrary Dimensions" (2007).
+pub fn poisson_disk_2d<R: Rng>(
+    rng: &mut R,
+    width: f32,
+    height: f32,
+    min_distance: f32,
+    max_attempts: Option<usize>,
+) -> Vec<Vec2> {
+    assert!(width > 0.0 && height > 0.0 && min_distance > 0.0);
+
+    let max_attempts = max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+
+    // Background grid, sized such that each cell holds at most one sample.
+
+    let cell_size = min_distance / 2.0_f32.sqrt();
+
+    let columns = (width / cell_size).ceil() as usize;
+    let rows = (height / cell_size).ceil() as usize;
+
+    let mut grid: Vec<Option<usize>> = vec![None; columns * rows];
+
+    let get_cell = |point: &Vec2| -> (usize, usize) {
+        (
+            ((point.x / cell_size) as usize).min(columns - 1),
+            ((point.y / cell_size) as usize).min(rows - 1),
+        )
+    };
+
+    let mut samples: Vec<Vec2> = vec![];
+    let mut active: Vec<usize> = vec![];
+
+    let first = Vec2 {
+        x: rng.gen_range(0.0..width),
+        y: rng.gen_range(0.0..height),
+        z: 0.0,
+    };
+
+    let (x, y) = get_cell(&first);
+
+    grid[y * columns + x] = Some(0);
+
+    samples.push(first);
+    active.push(0);
+
+    let min_distance_squared = min_distance * min_distance;
+
+    while !active.is_empty() {
+        let active_index = rng.gen_range(0..active.len());
+
+        let origin = samples[active[active_index]];
+
+        let mut found = false;
+
+        for _ in 0..max_attempts {
+            // Sample uniformly from the annulus between r and 2r.
+
+            let angle = rng.gen_range(0.0..TAU);
+            let radius = rng.gen_range(min_distance..(min_distance * 2.0));
+
+            let candidate = Vec2 {
+                x: origin.x + radius * angle.cos(),
+                y: origin.y + radius * angle.sin(),
+                z: 0.0,
+            };
+
+            if candidate.x < 0.0
+                || candidate.x >= width
+                || candidate.y < 0.0
+                || candidate.y >= height
+            {
+                continue;
+            }
+
+            let (cell_x, cell_y) = get_cell(&candidate);
+
+            // Check neighboring cells for samples that are too close.
+
+            let mut is_valid = true;
+
+            'neighbors: for y in cell_y.saturating_sub(2)..(cell_y + 3).min(rows) {
+                for x in cell_x.saturating_sub(2)..(cell_x + 3).min(columns) {
+                    if let Some(other) = grid[y * columns + x] {
+                        let delta = samples[other] - candidate;
+
+                        if delta.x * delta.x + delta.y * delta.y < min_distance_squared {
+                            is_valid = false;
+
+                            break 'neighbors;
+                        }
+                    }
+                }
+            }
+
+            if is_valid {
+                let index = samples.len();
+
+                grid[cell_y * columns + cell_x] = Some(index);
+
+                samples.push(candidate);
+                active.push(index);
+
+                found = true;
+
+                break;
+            }
+        }
+
+        if !found {
+            active.swap_remove(active_index);
+        }
+    }
+
+    samples
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::poisson_disk_2d;
+
+    #[test]
+    fn samples_respect_min_distance() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let min_distance = 0.05;
+
+        let samples = poisson_disk_2d(&mut rng, 1.0, 1.0, min_distance, None);
+
+        assert!(samples.len() > 100);
+
+        for (i, a) in samples.iter().enumerate() {
+            assert!((0.0..1.0).contains(&a.x) && (0.0..1.0).contains(&a.y));
+
+            for b in samples.iter().skip(i + 1) {
+                let (dx, dy) = (a.x - b.x, a.y - b.y);
+
+                assert!((dx * dx + dy * dy).sqrt() >= min_distance);
+            }
+        }
+    }
+}
@@ -14,6 +14,14 @@ impl<const N: usize> Default for GeneratedDistribution<N> {
 }
 
 impl<const N: usize> GeneratedDistribution<N> {
+    pub fn cursor(&self) -> usize {
+        self.next
+    }
+
+    pub fn set_cursor(&mut self, cursor: usize) {
+        self.next = if N == 0 { 0 } else { cursor % N };
+    }
+
     pub fn sample(&mut self) -> f32 {
         if self.next == self.values.len() {
             self.next = 0;
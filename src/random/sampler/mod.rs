@@ -1,6 +1,9 @@
 use std::f32::consts::PI;
 
-use rand::rngs::ThreadRng;
+use rand::{
+    rngs::{StdRng, ThreadRng},
+    Rng, SeedableRng,
+};
 
 use rand_distr::{Distribution, Normal, NormalError, Uniform};
 
@@ -31,16 +34,41 @@ pub trait VectorDisplaceSampler {
     ) -> Result<Vec3, NormalError>;
 }
 
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct RandomSamplerSnapshot {
+    tick: u64,
+    uniform_cursor: usize,
+    normal_cursor: usize,
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct RandomSampler<const N: usize> {
     rng: ThreadRng,
     uniform_seed: GeneratedDistribution<N>,
     normal_seed: GeneratedDistribution<N>,
     is_seeded: bool,
+    // When set, each call to `tick()` moves the sampler to a fixed position
+    // in its sequence, regardless of how many samples were taken.
+    lockstep_samples_per_tick: Option<usize>,
+    tick: u64,
 }
 
 impl<const N: usize> RandomSampler<N> {
     pub fn seed(&mut self) -> Result<(), NormalError> {
+        let mut rng = self.rng.clone();
+
+        self.seed_from_rng(&mut rng)
+    }
+
+    // Seeds the sampler deterministically; two samplers seeded with the same
+    // value will produce identical sequences.
+    pub fn seed_from_u64(&mut self, seed: u64) -> Result<(), NormalError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        self.seed_from_rng(&mut rng)
+    }
+
+    fn seed_from_rng<R: Rng>(&mut self, rng: &mut R) -> Result<(), NormalError> {
         let uniform_sampler = Uniform::new_inclusive(0.0, 1.0);
 
         let normal_sampler = match Normal::new(0.0, 1.0) {
@@ -49,15 +77,59 @@ impl<const N: usize> RandomSampler<N> {
         };
 
         for i in 0..N {
-            self.uniform_seed.values[i] = uniform_sampler.sample(&mut self.rng);
-            self.normal_seed.values[i] = normal_sampler.sample(&mut self.rng);
+            self.uniform_seed.values[i] = uniform_sampler.sample(rng);
+            self.normal_seed.values[i] = normal_sampler.sample(rng);
         }
 
+        self.uniform_seed.set_cursor(0);
+        self.normal_seed.set_cursor(0);
+
+        self.tick = 0;
+
         self.is_seeded = true;
 
         Ok(())
     }
 
+    pub fn set_lockstep(&mut self, samples_per_tick: Option<usize>) {
+        self.lockstep_samples_per_tick = samples_per_tick;
+    }
+
+    pub fn is_lockstep(&self) -> bool {
+        self.lockstep_samples_per_tick.is_some()
+    }
+
+    pub fn current_tick(&self) -> u64 {
+        self.tick
+    }
+
+    // Signals the start of a new fixed-timestep tick.
+    pub fn tick(&mut self) {
+        self.tick += 1;
+
+        if let Some(samples_per_tick) = self.lockstep_samples_per_tick {
+            let cursor = ((self.tick * samples_per_tick as u64) % N.max(1) as u64) as usize;
+
+            self.uniform_seed.set_cursor(cursor);
+            self.normal_seed.set_cursor(cursor);
+        }
+    }
+
+    pub fn snapshot(&self) -> RandomSamplerSnapshot {
+        RandomSamplerSnapshot {
+            tick: self.tick,
+            uniform_cursor: self.uniform_seed.cursor(),
+            normal_cursor: self.normal_seed.cursor(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &RandomSamplerSnapshot) {
+        self.tick = snapshot.tick;
+
+        self.uniform_seed.set_cursor(snapshot.uniform_cursor);
+        self.normal_seed.set_cursor(snapshot.normal_cursor);
+    }
+
     // Returns a uniformly distributed random scalar in the range [min...max].
     fn _sample_range_uniform(&mut self, min: f32, max: f32) -> f32 {
         let sampler = Uniform::new_inclusive(min, max);
@@ -162,3 +234,57 @@ impl<const N: usize> VectorDisplaceSampler for RandomSampler<N> {
         Ok(self.sample_displacement(v, max_deflection_angle_radians, f.abs()))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{RandomSampler, RangeSampler};
+
+    fn take(sampler: &mut RandomSampler<256>, count: usize) -> Vec<f32> {
+        (0..count)
+            .map(|_| sampler.sample_range_uniform(0.0, 1.0))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a: RandomSampler<256> = Default::default();
+        let mut b: RandomSampler<256> = Default::default();
+
+        a.seed_from_u64(42).unwrap();
+        b.seed_from_u64(42).unwrap();
+
+        a.set_lockstep(Some(8));
+        b.set_lockstep(Some(8));
+
+        for tick in 0..10 {
+            // Consume a varying number of samples per tick from one sampler.
+
+            let a_samples = take(&mut a, 4);
+            let b_samples = take(&mut b, 1 + tick % 4);
+
+            assert_eq!(a_samples[..b_samples.len()], b_samples[..]);
+
+            a.tick();
+            b.tick();
+        }
+
+        assert_eq!(a.current_tick(), b.current_tick());
+    }
+
+    #[test]
+    fn snapshot_and_restore() {
+        let mut sampler: RandomSampler<256> = Default::default();
+
+        sampler.seed_from_u64(7).unwrap();
+
+        take(&mut sampler, 3);
+
+        let snapshot = sampler.snapshot();
+
+        let expected = take(&mut sampler, 5);
+
+        sampler.restore(&snapshot);
+
+        assert_eq!(take(&mut sampler, 5), expected);
+    }
+}
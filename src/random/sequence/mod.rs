@@ -19,3 +19,194 @@ pub fn hammersley_2d_sequence(i: u32, one_over_n: f32) -> Vec2 {
         z: 0.0,
     }
 }
+
+static HALTON_PRIMES: [u32; 16] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+// Returns the radical inverse of `index` in the given base; the `index`-th
+// value of the van der Corput sequence for that base. Panics if `base < 2`.
+pub fn radical_inverse(mut index: u32, base: u32) -> f32 {
+    assert!(
+        base >= 2,
+        "Called radical_inverse() with a base less than 2 (base={})!",
+        base
+    );
+
+    let one_over_base = 1.0 / base as f64;
+
+    let mut f = 1.0_f64;
+    let mut result = 0.0_f64;
+
+    while index > 0 {
+        f *= one_over_base;
+
+        result += f * (index % base) as f64;
+
+        index /= base;
+    }
+
+    result as f32
+}
+
+// Returns the `index`-th point of the Halton sequence in [0, 1)^d, using the
+// first `d` primes as bases. Note that index 0 maps to the origin.
+pub fn halton(index: u32, dimensions: usize) -> Vec<f32> {
+    assert!(
+        dimensions <= HALTON_PRIMES.len(),
+        "Called halton() with more than {} dimensions!",
+        HALTON_PRIMES.len()
+    );
+
+    HALTON_PRIMES[..dimensions]
+        .iter()
+        .map(|base| radical_inverse(index, *base))
+        .collect()
+}
+
+// Returns the `index`-th point of the Halton(2, 3) sequence.
+pub fn halton_2d_sequence(index: u32) -> Vec2 {
+    Vec2 {
+        x: radical_inverse(index, 2),
+        y: radical_inverse(index, 3),
+        z: 0.0,
+    }
+}
+
+// Primitive polynomials (degree, coefficients) and initial direction numbers
+// for Sobol dimensions 2 onward.
+//
+// See: https://web.maths.unsw.edu.au/~fkuo/sobol/ (new-joe-kuo-6.21201)
+static SOBOL_PARAMETERS: [(u32, u32, [u32; 5]); 7] = [
+    (1, 0, [1, 0, 0, 0, 0]),
+    (2, 1, [1, 3, 0, 0, 0]),
+    (3, 1, [1, 3, 1, 0, 0]),
+    (3, 2, [1, 1, 1, 0, 0]),
+    (4, 1, [1, 1, 3, 3, 0]),
+    (4, 4, [1, 3, 5, 13, 0]),
+    (5, 2, [1, 1, 5, 5, 17]),
+];
+
+pub static SOBOL_MAX_DIMENSIONS: usize = SOBOL_PARAMETERS.len() + 1;
+
+fn get_sobol_direction_numbers(dimension: usize) -> [u32; 32] {
+    let mut v = [0_u32; 32];
+
+    if dimension == 0 {
+        // The first dimension is the base-2 van der Corput sequence.
+
+        for (k, v_k) in v.iter_mut().enumerate() {
+            *v_k = 1 << (31 - k);
+        }
+
+        return v;
+    }
+
+    let (s, a, m) = SOBOL_PARAMETERS[dimension - 1];
+
+    let s = s as usize;
+
+    for k in 0..32 {
+        if k < s {
+            v[k] = m[k] << (31 - k);
+        } else {
+            let mut value = v[k - s] ^ (v[k - s] >> s);
+
+            for i in 1..s {
+                if (a >> (s - 1 - i)) & 1 == 1 {
+                    value ^= v[k - i];
+                }
+            }
+
+            v[k] = value;
+        }
+    }
+
+    v
+}
+
+// Returns the `index`-th point of the Sobol sequence in [0, 1)^d.
+pub fn sobol(index: u32, dimensions: usize) -> Vec<f32> {
+    assert!(
+        dimensions <= SOBOL_MAX_DIMENSIONS,
+        "Called sobol() with more than {} dimensions!",
+        SOBOL_MAX_DIMENSIONS
+    );
+
+    (0..dimensions)
+        .map(|dimension| {
+            let v = get_sobol_direction_numbers(dimension);
+
+            let mut result = 0_u32;
+
+            for (k, v_k) in v.iter().enumerate() {
+                if (index >> k) & 1 == 1 {
+                    result ^= v_k;
+                }
+            }
+
+            // Keeps only the 24 most significant bits, which an `f32` can
+            // represent exactly; rounding the full 32 bits could yield 1.0.
+
+            (result >> 8) as f32 / (1 << 24) as f32
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{halton, radical_inverse, sobol, SOBOL_MAX_DIMENSIONS};
+
+    #[test]
+    fn halton_base_2() {
+        let expected = [0.5, 0.25, 0.75, 0.125, 0.625, 0.375, 0.875];
+
+        for (i, value) in expected.iter().enumerate() {
+            assert_eq!(radical_inverse(i as u32 + 1, 2), *value);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn radical_inverse_rejects_base_1() {
+        radical_inverse(7, 1);
+    }
+
+    #[test]
+    fn halton_base_3() {
+        let expected = [1.0 / 3.0, 2.0 / 3.0, 1.0 / 9.0, 4.0 / 9.0, 7.0 / 9.0];
+
+        for (i, value) in expected.iter().enumerate() {
+            let sample = halton(i as u32 + 1, 2);
+
+            assert!((sample[1] - value).abs() < 0.00001);
+        }
+    }
+
+    #[test]
+    fn sobol_first_points() {
+        let expected = [
+            [0.0, 0.0],
+            [0.5, 0.5],
+            [0.25, 0.75],
+            [0.75, 0.25],
+            [0.125, 0.625],
+            [0.625, 0.125],
+        ];
+
+        for (i, point) in expected.iter().enumerate() {
+            let sample = sobol(i as u32, 2);
+
+            assert_eq!(sample[..], point[..]);
+        }
+    }
+
+    #[test]
+    fn sobol_points_lie_below_1() {
+        // The first dimension's bits are those of the index, reversed.
+
+        let sample = sobol(u32::MAX, SOBOL_MAX_DIMENSIONS);
+
+        assert_eq!(sample[0], 1.0 - f32::EPSILON / 2.0);
+
+        assert!(sample.iter().all(|value| (0.0..1.0).contains(value)));
+    }
+}
@@ -0,0 +1,44 @@
+use rand::Rng;
+
+use crate::vec::vec2::Vec2;
+
+// Returns one sample per cell of a `columns` x `rows` grid over [0, 1)^2. When
+// `jitter` is true, each sample is placed randomly within its cell; otherwise,
+// samples are placed at cell centers.
+pub fn stratified_2d<R: Rng>(rng: &mut R, columns: usize, rows: usize, jitter: bool) -> Vec<Vec2> {
+    let mut samples = Vec::with_capacity(columns * rows);
+
+    let one_over_columns = 1.0 / columns as f32;
+    let one_over_rows = 1.0 / rows as f32;
+
+    for y in 0..rows {
+        for x in 0..columns {
+            let (offset_x, offset_y) = if jitter {
+                (rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0))
+            } else {
+                (0.5, 0.5)
+            };
+
+            samples.push(Vec2 {
+                x: (x as f32 + offset_x) * one_over_columns,
+                y: (y as f32 + offset_y) * one_over_rows,
+                z: 0.0,
+            });
+        }
+    }
+
+    samples
+}
+
+// Returns `count` samples over [0, 1), one per equal-width stratum.
+pub fn stratified_1d<R: Rng>(rng: &mut R, count: usize, jitter: bool) -> Vec<f32> {
+    let one_over_count = 1.0 / count as f32;
+
+    (0..count)
+        .map(|i| {
+            let offset = if jitter { rng.gen_range(0.0..1.0) } else { 0.5 };
+
+            (i as f32 + offset) * one_over_count
+        })
+        .collect()
+}
@@ -0,0 +1,252 @@
+use crate::{
+    animation::lerp,
+    render::{options::rasterizer::MAX_SUBPIXEL_PRECISION_BITS, viewport::RenderViewport},
+    scene::camera::frustum::NdcPlane,
+    vertex::default_vertex_out::DefaultVertexOut,
+};
+
+// All 6 planes bounding the (projection-space) view frustum.
+pub static FRUSTUM_PLANES: [NdcPlane; 6] = [
+    NdcPlane::Near,
+    NdcPlane::Far,
+    NdcPlane::Left,
+    NdcPlane::Right,
+    NdcPlane::Top,
+    NdcPlane::Bottom,
+];
+
+// Planes clipped against when using a guard band; the far plane is left to the
+// depth test.
+pub static GUARD_BAND_PLANES: [NdcPlane; 5] = [
+    NdcPlane::Near,
+    NdcPlane::Left,
+    NdcPlane::Right,
+    NdcPlane::Top,
+    NdcPlane::Bottom,
+];
+
+// Signed distance of a (projection-space) vertex from a frustum plane; positive
+// distances lie inside of the frustum. The left, right, top, and bottom planes
+// are pushed outward by `side_scale` (1 for the view frustum itself).
+fn get_signed_distance(vertex: &DefaultVertexOut, ndc_plane: NdcPlane, side_scale: f32) -> f32 {
+    let position = &vertex.position_projection_space;
+
+    let side_w = position.w * side_scale;
+
+    match ndc_plane {
+        NdcPlane::Near => position.z + position.w,
+        NdcPlane::Far => position.w - position.z,
+        NdcPlane::Left => position.x + side_w,
+        NdcPlane::Right => side_w - position.x,
+        NdcPlane::Top => side_w - position.y,
+        NdcPlane::Bottom => position.y + side_w,
+    }
+}
+
+// Clips a convex polygon (given as projection-space vertices, in winding order)
+// against each of `frustum_planes`, in turn, interpolating the attributes of
+// any new vertices; the result keeps the polygon's winding order, and is empty
+// if the polygon lies entirely outside of the frustum.
+pub fn clip_polygon_against_frustum(
+    vertices: &[DefaultVertexOut],
+    frustum_planes: &[NdcPlane],
+) -> Vec<DefaultVertexOut> {
+    clip_polygon(vertices, frustum_planes, 1.0)
+}
+
+// Returns the largest guard band that keeps the fixed-point rasterizer's edge
+// functions within an `i64` for the given viewport.
+//
+// Coordinates within a guard band `g` differ by at most `g * width` (or
+// `g * height`) pixels, so each edge function is bounded by
+// `2 * g^2 * width * height * 2^(2 * bits)`; we keep that below 2^61, leaving
+// room for rounding.
+pub fn get_max_guard_band(viewport: &RenderViewport) -> f32 {
+    let area = (viewport.width.max(1) as f64) * (viewport.height.max(1) as f64);
+
+    let limit = 2.0_f64.powi(60 - 2 * MAX_SUBPIXEL_PRECISION_BITS as i32);
+
+    ((limit / area).sqrt() as f32).max(1.0)
+}
+
+// Clips a convex polygon against the near plane, and against a guard band that
+// extends `guard_band` times as far as the view frustum's sides (e.g., 2 for a
+// band twice the size of the viewport); the rasterizer is then responsible for
+// skipping any pixels that remain off-screen. The band is clamped to the range
+// `[1, get_max_guard_band(viewport)]`; a non-finite (e.g., NaN) band is
+// rejected, and the polygon is clipped against the view frustum instead.
+pub fn clip_polygon_against_guard_band(
+    vertices: &[DefaultVertexOut],
+    guard_band: f32,
+    viewport: &RenderViewport,
+) -> Vec<DefaultVertexOut> {
+    if !guard_band.is_finite() {
+        return clip_polygon_against_frustum(vertices, &FRUSTUM_PLANES);
+    }
+
+    let guard_band = guard_band.clamp(1.0, get_max_guard_band(viewport));
+
+    clip_polygon(vertices, &GUARD_BAND_PLANES, guard_band)
+}
+
+fn clip_polygon(
+    vertices: &[DefaultVertexOut],
+    frustum_planes: &[NdcPlane],
+    side_scale: f32,
+) -> Vec<DefaultVertexOut> {
+    let mut polygon = vertices.to_vec();
+
+    for ndc_plane in frustum_planes {
+        if polygon.is_empty() {
+            break;
+        }
+
+        polygon = clip_polygon_by_plane(&polygon, *ndc_plane, side_scale);
+    }
+
+    polygon
+}
+
+fn clip_polygon_by_plane(
+    polygon: &[DefaultVertexOut],
+    ndc_plane: NdcPlane,
+    side_scale: f32,
+) -> Vec<DefaultVertexOut> {
+    let distances: Vec<f32> = polygon
+        .iter()
+        .map(|vertex| get_signed_distance(vertex, ndc_plane, side_scale))
+        .collect();
+
+    if distances.iter().all(|distance| *distance > 0.0) {
+        return polygon.to_vec();
+    }
+
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+
+    for (index, current) in polygon.iter().enumerate() {
+        let next_index = (index + 1) % polygon.len();
+
+        let (current_distance, next_distance) = (distances[index], distances[next_index]);
+
+        if current_distance > 0.0 {
+            clipped.push(*current);
+        }
+
+        // Emit a new vertex wherever an edge crosses the plane.
+
+        if (current_distance > 0.0) != (next_distance > 0.0) {
+            let alpha = current_distance / (current_distance - next_distance);
+
+            clipped.push(lerp(*current, polygon[next_index], alpha));
+        }
+    }
+
+    clipped
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        render::viewport::RenderViewport,
+        scene::camera::frustum::NdcPlane,
+        vec::{vec2::Vec2, vec4::Vec4},
+        vertex::default_vertex_out::DefaultVertexOut,
+    };
+
+    use super::{clip_polygon_against_frustum, clip_polygon_against_guard_band, FRUSTUM_PLANES};
+
+    fn vertex(x: f32, y: f32, z: f32, u: f32) -> DefaultVertexOut {
+        let mut vertex = DefaultVertexOut::new();
+
+        vertex.position_projection_space = Vec4 { x, y, z, w: 1.0 };
+
+        vertex.uv = Vec2 {
+            x: u,
+            y: 0.0,
+            z: 0.0,
+        };
+
+        vertex
+    }
+
+    #[test]
+    fn triangle_spanning_the_near_plane_is_clipped_to_a_quad() {
+        // The first vertex lies in front of the near plane (at z = -w).
+
+        let triangle = [
+            vertex(0.0, 0.0, -2.0, 0.0),
+            vertex(0.5, 0.0, 0.0, 1.0),
+            vertex(0.0, 0.5, 0.0, 0.5),
+        ];
+
+        let polygon = clip_polygon_against_frustum(&triangle, &[NdcPlane::Near]);
+
+        let summary: Vec<(f32, f32, f32, f32)> = polygon
+            .iter()
+            .map(|v| {
+                let p = v.position_projection_space;
+
+                (p.x, p.y, p.z, v.uv.x)
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (0.25, 0.0, -1.0, 0.5),
+                (0.5, 0.0, 0.0, 1.0),
+                (0.0, 0.5, 0.0, 0.5),
+                (0.0, 0.25, -1.0, 0.25),
+            ]
+        );
+
+        // The remaining planes leave the (fully-inside) quad as it is.
+
+        let all_clipped = clip_polygon_against_frustum(&triangle, &FRUSTUM_PLANES);
+
+        assert_eq!(all_clipped.len(), 4);
+    }
+
+    #[test]
+    fn non_finite_guard_bands_fall_back_to_frustum_clipping() {
+        // Crosses the right edge of the screen, within a 2x guard band.
+
+        let triangle = [
+            vertex(0.5, 0.0, 0.0, 0.0),
+            vertex(1.5, 0.0, 0.0, 1.0),
+            vertex(0.5, 0.5, 0.0, 0.5),
+        ];
+
+        let viewport = RenderViewport {
+            width: 64,
+            width_over_2: 32.0,
+            height: 64,
+            height_over_2: 32.0,
+        };
+
+        let positions = |polygon: &[DefaultVertexOut]| -> Vec<Vec4> {
+            polygon
+                .iter()
+                .map(|vertex| vertex.position_projection_space)
+                .collect()
+        };
+
+        let frustum_clipped = positions(&clip_polygon_against_frustum(&triangle, &FRUSTUM_PLANES));
+
+        assert_eq!(
+            positions(&clip_polygon_against_guard_band(&triangle, 2.0, &viewport)),
+            positions(&triangle)
+        );
+
+        for guard_band in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert_eq!(
+                positions(&clip_polygon_against_guard_band(
+                    &triangle, guard_band, &viewport
+                )),
+                frustum_clipped
+            );
+        }
+
+        assert_ne!(frustum_clipped, positions(&triangle));
+    }
+}
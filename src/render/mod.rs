@@ -18,8 +18,11 @@ use crate::{
     vec::vec3::Vec3,
 };
 
+pub mod clip;
 pub mod culling;
 pub mod options;
+pub mod scale;
+pub mod stats;
 pub mod viewport;
 
 pub trait Renderer {
@@ -31,6 +34,13 @@ pub trait Renderer {
 
     fn end_frame(&mut self);
 
+    // While in a depth prepass, entities only write to the depth buffer; once
+    // it ends, re-submitted (opaque) entities are only shaded where they're
+    // visible.
+    fn begin_depth_prepass(&mut self);
+
+    fn end_depth_prepass(&mut self);
+
     fn render_point(
         &mut self,
         point_world_space: Vec3,
@@ -53,6 +63,8 @@ pub trait Renderer {
 
     fn render_camera(&mut self, camera: &Camera, color: Option<Color>);
 
+    fn render_camera_frustum(&mut self, camera: &Camera, color: Option<Color>);
+
     fn render_ambient_light(&mut self, transform: &Mat4, light: &AmbientLight);
 
     fn render_directional_light(&mut self, transform: &Mat4, light: &DirectionalLight);
@@ -1,3 +1,5 @@
+use std::sync::OnceLock;
+
 use bitmask::bitmask;
 
 use serde::{Deserialize, Serialize};
@@ -5,9 +7,10 @@ use serde::{Deserialize, Serialize};
 use sdl2::keyboard::Keycode;
 
 use crate::{
-    device::keyboard::KeyboardState,
+    device::keyboard::{input_map::InputMap, KeyboardState},
     render::culling::FaceCullingReject,
     resource::handle::Handle,
+    shader::fog::Fog,
     vec::vec3::{self, Vec3},
 };
 
@@ -18,6 +21,10 @@ pub mod rasterizer;
 pub mod shader;
 pub mod tone_mapping;
 
+pub static SUPERSAMPLE_FACTORS: [u32; 3] = [1, 2, 4];
+
+static DEFAULT_RENDER_INPUT_MAP: OnceLock<InputMap> = OnceLock::new();
+
 bitmask! {
     #[derive(Debug, Serialize, Deserialize)]
     pub mask RenderPassMask: u32 where flags RenderPassFlag {
@@ -29,6 +36,9 @@ bitmask! {
         Ssao = (1 << 4),
         SsaoBlur = (1 << 5),
         ToneMapping = (1 << 6),
+        DepthPrepass = (1 << 7),
+        ContactShadows = (1 << 8),
+        Ssr = (1 << 9),
     }
 }
 
@@ -45,12 +55,40 @@ impl Default for RenderPassMask {
 pub struct RenderOptions {
     pub render_pass_flags: RenderPassMask,
     pub bloom_dirt_mask_handle: Option<Handle>,
+    // Only the portion of a pixel's luminance above `bloom_threshold` blooms;
+    // the result is blended over the frame by `bloom_intensity`, after being
+    // blurred across `bloom_mip_count` mipmaps.
+    pub bloom_threshold: f32,
+    pub bloom_intensity: f32,
+    pub bloom_mip_count: u8,
     pub rasterizer_options: RasterizerOptions,
     pub tone_mapping: ToneMappingOperator,
+    // Skips entities whose (world-space) bounds lie outside of the view
+    // frustum, before any of their vertices are processed.
+    pub entity_frustum_culling: bool,
+    // Rasterizes at this multiple (1, 2, or 4) of the bound framebuffer's
+    // resolution, box-filtering the result down at the end of each frame.
+    pub supersample_factor: u32,
+    // Sorts triangles with transparent materials back-to-front and blends them
+    // over the opaque pass; otherwise, they're blended order-independently.
+    pub sorted_transparency: bool,
+    // Number of steps, and total (view-space) distance, marched towards the
+    // directional light by the contact shadow pass.
+    pub contact_shadow_steps: u32,
+    pub contact_shadow_length: f32,
+    // Copied into the shader context at the start of each frame; `None`
+    // disables fog.
+    pub fog: Option<Fog>,
     // User debug
     pub draw_wireframe: bool,
     pub wireframe_color: Vec3,
+    // Drops wireframe edges that are hidden behind the frame's geometry.
+    pub wireframe_depth_tested: bool,
+    // Draws each vertex's (world-space) normal, tangent, and bitangent as a
+    // line segment of length `draw_normals_scale`.
     pub draw_normals: bool,
+    pub draw_tangents: bool,
+    pub draw_bitangents: bool,
     pub draw_normals_scale: f32,
 }
 
@@ -59,23 +97,66 @@ impl Default for RenderOptions {
         Self {
             render_pass_flags: Default::default(),
             bloom_dirt_mask_handle: None,
+            bloom_threshold: 0.0,
+            bloom_intensity: 0.07,
+            bloom_mip_count: 4,
             rasterizer_options: Default::default(),
             tone_mapping: Default::default(),
+            entity_frustum_culling: true,
+            supersample_factor: 1,
+            sorted_transparency: true,
+            contact_shadow_steps: 8,
+            contact_shadow_length: 0.25,
+            fog: None,
             // User debug
             draw_wireframe: false,
             // User debug
             wireframe_color: vec3::ONES,
+            wireframe_depth_tested: false,
             draw_normals: false,
+            draw_tangents: false,
+            draw_bitangents: false,
             draw_normals_scale: 0.05,
         }
     }
 }
 
 impl RenderOptions {
+    // Describes the keys handled by `update()`.
+    pub fn bind_keys(input_map: &mut InputMap) {
+        for (action, keycode) in [
+            ("Toggle rasterization pass", Keycode::Num1),
+            ("Toggle lighting pass", Keycode::Num2),
+            ("Toggle deferred lighting pass", Keycode::Num3),
+            ("Toggle bloom pass", Keycode::Num4),
+            ("Cycle tone mapping operators", Keycode::Num5),
+            ("Cycle face culling", Keycode::Num6),
+            ("Toggle wireframe", Keycode::Num7),
+            ("Cycle normals / TBN frames", Keycode::Num8),
+            ("Cycle supersampling", Keycode::Num9),
+        ] {
+            input_map.bind("Render", action, keycode);
+        }
+    }
+
+    // Handles newly pressed keys, using the default key bindings.
     pub fn update(&mut self, keyboard_state: &KeyboardState) {
+        let input_map = DEFAULT_RENDER_INPUT_MAP.get_or_init(|| {
+            let mut input_map = InputMap::default();
+
+            Self::bind_keys(&mut input_map);
+
+            input_map
+        });
+
+        self.update_with_input_map(keyboard_state, input_map);
+    }
+
+    // Handles newly pressed keys, as bound (under "Render") in `input_map`.
+    pub fn update_with_input_map(&mut self, keyboard_state: &KeyboardState, input_map: &InputMap) {
         for keycode in keyboard_state.newly_pressed_keycodes.iter() {
-            match *keycode {
-                Keycode::Num1 => {
+            match input_map.get_action("Render", *keycode) {
+                Some("Toggle rasterization pass") => {
                     self.render_pass_flags ^= RenderPassFlag::Rasterization;
 
                     println!(
@@ -90,7 +171,7 @@ impl RenderOptions {
                         }
                     );
                 }
-                Keycode::Num2 => {
+                Some("Toggle lighting pass") => {
                     self.render_pass_flags ^= RenderPassFlag::Lighting;
 
                     println!(
@@ -102,7 +183,7 @@ impl RenderOptions {
                         }
                     );
                 }
-                Keycode::Num3 => {
+                Some("Toggle deferred lighting pass") => {
                     self.render_pass_flags ^= RenderPassFlag::DeferredLighting;
 
                     println!(
@@ -117,7 +198,7 @@ impl RenderOptions {
                         }
                     );
                 }
-                Keycode::Num4 => {
+                Some("Toggle bloom pass") => {
                     self.render_pass_flags ^= RenderPassFlag::Bloom;
 
                     println!(
@@ -129,7 +210,7 @@ impl RenderOptions {
                         }
                     );
                 }
-                Keycode::Num5 => {
+                Some("Cycle tone mapping operators") => {
                     // Cycle tone-mapping operators.
 
                     let current_index: usize = self.tone_mapping.try_into().unwrap();
@@ -140,7 +221,7 @@ impl RenderOptions {
 
                     println!("Tone mapping: {}", self.tone_mapping);
                 }
-                Keycode::Num6 => {
+                Some("Cycle face culling") => {
                     // Cycle culling reject settings.
 
                     self.rasterizer_options.face_culling_strategy.reject =
@@ -155,7 +236,7 @@ impl RenderOptions {
                         self.rasterizer_options.face_culling_strategy.reject
                     );
                 }
-                Keycode::Num7 => {
+                Some("Toggle wireframe") => {
                     self.draw_wireframe = !self.draw_wireframe;
 
                     println!(
@@ -163,16 +244,87 @@ impl RenderOptions {
                         if self.draw_wireframe { "On" } else { "Off" }
                     );
                 }
-                Keycode::Num8 => {
-                    self.draw_normals = !self.draw_normals;
+                Some("Cycle normals / TBN frames") => {
+                    // Cycle between no vertex frames, normals only, and full
+                    // TBN frames.
+
+                    (self.draw_normals, self.draw_tangents, self.draw_bitangents) =
+                        match (self.draw_normals, self.draw_tangents) {
+                            (false, _) => (true, false, false),
+                            (true, false) => (true, true, true),
+                            (true, true) => (false, false, false),
+                        };
 
                     println!(
-                        "Draw normals: {}",
-                        if self.draw_normals { "On" } else { "Off" }
+                        "Draw normals: {}, tangents: {}, bitangents: {}",
+                        if self.draw_normals { "On" } else { "Off" },
+                        if self.draw_tangents { "On" } else { "Off" },
+                        if self.draw_bitangents { "On" } else { "Off" }
                     );
                 }
+                Some("Cycle supersampling") => {
+                    // Cycle supersampling factors.
+
+                    let current_index = SUPERSAMPLE_FACTORS
+                        .iter()
+                        .position(|factor| *factor == self.supersample_factor)
+                        .unwrap_or(0);
+
+                    let new_index = (current_index + 1).rem_euclid(SUPERSAMPLE_FACTORS.len());
+
+                    self.supersample_factor = SUPERSAMPLE_FACTORS[new_index];
+
+                    println!("Supersampling: {}x", self.supersample_factor);
+                }
                 _ => {}
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use sdl2::keyboard::Keycode;
+
+    use crate::device::keyboard::{input_map::InputMap, KeyboardState};
+
+    use super::RenderOptions;
+
+    #[test]
+    fn update_dispatches_through_rebound_keys() {
+        let mut input_map = InputMap::default();
+
+        RenderOptions::bind_keys(&mut input_map);
+
+        input_map.bind("Render", "Toggle wireframe", Keycode::F12);
+
+        let mut options = RenderOptions::default();
+
+        let mut press = |keycode: Keycode, input_map: &InputMap| {
+            let mut keyboard_state = KeyboardState::default();
+
+            keyboard_state.newly_pressed_keycodes = [keycode].into();
+
+            options.update_with_input_map(&keyboard_state, input_map);
+
+            options.draw_wireframe
+        };
+
+        // The default key no longer toggles the wireframe.
+
+        assert!(!press(Keycode::Num7, &input_map));
+        assert!(press(Keycode::F12, &input_map));
+
+        // Without an input map, the default bindings apply.
+
+        let mut options = RenderOptions::default();
+
+        let mut keyboard_state = KeyboardState::default();
+
+        keyboard_state.newly_pressed_keycodes = [Keycode::Num7].into();
+
+        options.update(&keyboard_state);
+
+        assert!(options.draw_wireframe);
+    }
+}
@@ -1,6 +1,51 @@
 use crate::render::culling::FaceCullingStrategy;
 
-#[derive(Default, Debug, Copy, Clone)]
+// Keeps fixed-point viewport coordinates (and their products) well within the
+// range of an `i64`.
+pub static MAX_SUBPIXEL_PRECISION_BITS: u32 = 16;
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AttributeInterpolation {
+    #[default]
+    PerspectiveCorrect,
+    // Interpolates vertex attributes linearly in screen space, producing the
+    // characteristic texture warping of early 3D hardware.
+    Affine,
+}
+
+#[derive(Debug, Copy, Clone)]
 pub struct RasterizerOptions {
     pub face_culling_strategy: FaceCullingStrategy,
+    pub attribute_interpolation: AttributeInterpolation,
+    // When set, vertex screen positions are snapped to a grid of this
+    // (virtual) resolution; e.g., `Some((320, 240))`.
+    pub vertex_snap_resolution: Option<(u32, u32)>,
+    // When face culling is disabled, back-facing fragments are lit using a
+    // normal flipped toward the viewer.
+    pub two_sided_lighting: bool,
+    // When set, triangles are rasterized with integer edge functions, using
+    // vertex positions snapped to this many bits of sub-pixel precision; shared
+    // edges are then covered exactly once (i.e., without cracks or overdraw).
+    pub subpixel_precision_bits: Option<u32>,
+    // When set, triangles are clipped only against the near plane and a guard
+    // band this many times the size of the viewport (e.g., `Some(2.0)`),
+    // rather than against every side of the view frustum; the rasterizer skips
+    // any pixels that lie off-screen. The band is limited to
+    // `clip::get_max_guard_band()` for the viewport, so that fixed-point edge
+    // functions can't overflow (about 11 for a 1920x1080 viewport); a
+    // non-finite band falls back to clipping against the whole frustum.
+    pub guard_band: Option<f32>,
+}
+
+impl Default for RasterizerOptions {
+    fn default() -> Self {
+        Self {
+            face_culling_strategy: Default::default(),
+            attribute_interpolation: Default::default(),
+            vertex_snap_resolution: None,
+            two_sided_lighting: true,
+            subpixel_precision_bits: None,
+            guard_band: None,
+        }
+    }
 }
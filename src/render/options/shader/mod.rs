@@ -1,7 +1,11 @@
-use crate::device::keyboard::KeyboardState;
+use std::sync::OnceLock;
+
+use crate::device::keyboard::{input_map::InputMap, KeyboardState};
 
 use sdl2::keyboard::Keycode;
 
+static DEFAULT_SHADING_INPUT_MAP: OnceLock<InputMap> = OnceLock::new();
+
 #[derive(Debug, Copy, Clone)]
 pub struct RenderShaderOptions {
     pub bilinear_active: bool,
@@ -12,6 +16,9 @@ pub struct RenderShaderOptions {
     pub metallic_mapping_active: bool,
     pub normal_mapping_active: bool,
     pub displacement_mapping_active: bool,
+    // Ray-marches displacement maps, rather than offsetting by a single sample.
+    pub parallax_occlusion_active: bool,
+    pub parallax_self_shadowing_active: bool,
     pub specular_exponent_mapping_active: bool,
     pub emissive_color_mapping_active: bool,
 }
@@ -27,6 +34,8 @@ impl Default for RenderShaderOptions {
             metallic_mapping_active: false,
             normal_mapping_active: false,
             displacement_mapping_active: false,
+            parallax_occlusion_active: false,
+            parallax_self_shadowing_active: false,
             specular_exponent_mapping_active: false,
             emissive_color_mapping_active: false,
         }
@@ -34,20 +43,52 @@ impl Default for RenderShaderOptions {
 }
 
 impl RenderShaderOptions {
+    // Describes the keys handled by `update()`.
+    pub fn bind_keys(input_map: &mut InputMap) {
+        for (action, keycode) in [
+            ("Toggle bilinear filtering", Keycode::B),
+            ("Toggle trilinear filtering", Keycode::T),
+            ("Toggle ambient occlusion mapping", Keycode::O),
+            ("Toggle albedo color mapping", Keycode::P),
+            ("Toggle normal mapping", Keycode::N),
+            ("Toggle displacement mapping", Keycode::C),
+            ("Cycle parallax mapping mode", Keycode::J),
+            ("Toggle roughness mapping", Keycode::R),
+            ("Toggle metallic mapping", Keycode::M),
+            ("Toggle emissive color mapping", Keycode::K),
+        ] {
+            input_map.bind("Shading", action, keycode);
+        }
+    }
+
+    // Handles newly pressed keys, using the default key bindings.
     pub fn update(&mut self, keyboard_state: &KeyboardState) {
+        let input_map = DEFAULT_SHADING_INPUT_MAP.get_or_init(|| {
+            let mut input_map = InputMap::default();
+
+            Self::bind_keys(&mut input_map);
+
+            input_map
+        });
+
+        self.update_with_input_map(keyboard_state, input_map);
+    }
+
+    // Handles newly pressed keys, as bound (under "Shading") in `input_map`.
+    pub fn update_with_input_map(&mut self, keyboard_state: &KeyboardState, input_map: &InputMap) {
         for keycode in keyboard_state.newly_pressed_keycodes.iter() {
-            match *keycode {
-                Keycode::B => {
+            match input_map.get_action("Shading", *keycode) {
+                Some("Toggle bilinear filtering") => {
                     self.bilinear_active = !self.bilinear_active;
 
                     println!("Bilinear filtering: {}", self.bilinear_active)
                 }
-                Keycode::T => {
+                Some("Toggle trilinear filtering") => {
                     self.trilinear_active = !self.trilinear_active;
 
                     println!("Trilinear filtering: {}", self.trilinear_active)
                 }
-                Keycode::O => {
+                Some("Toggle ambient occlusion mapping") => {
                     self.ambient_occlusion_mapping_active = !self.ambient_occlusion_mapping_active;
 
                     println!(
@@ -55,7 +96,7 @@ impl RenderShaderOptions {
                         self.ambient_occlusion_mapping_active
                     )
                 }
-                Keycode::P => {
+                Some("Toggle albedo color mapping") => {
                     self.albedo_mapping_active = !self.albedo_mapping_active;
 
                     println!(
@@ -67,7 +108,7 @@ impl RenderShaderOptions {
                         }
                     )
                 }
-                Keycode::N => {
+                Some("Toggle normal mapping") => {
                     self.normal_mapping_active = !self.normal_mapping_active;
 
                     println!(
@@ -79,7 +120,7 @@ impl RenderShaderOptions {
                         }
                     )
                 }
-                Keycode::C => {
+                Some("Toggle displacement mapping") => {
                     self.displacement_mapping_active = !self.displacement_mapping_active;
 
                     println!(
@@ -91,7 +132,34 @@ impl RenderShaderOptions {
                         }
                     )
                 }
-                Keycode::R => {
+                Some("Cycle parallax mapping mode") => {
+                    // Offset -> Occlusion -> Occlusion (self-shadowed) -> ...
+
+                    (
+                        self.parallax_occlusion_active,
+                        self.parallax_self_shadowing_active,
+                    ) = match (
+                        self.parallax_occlusion_active,
+                        self.parallax_self_shadowing_active,
+                    ) {
+                        (false, _) => (true, false),
+                        (true, false) => (true, true),
+                        (true, true) => (false, false),
+                    };
+
+                    println!(
+                        "Parallax mapping: {}",
+                        match (
+                            self.parallax_occlusion_active,
+                            self.parallax_self_shadowing_active
+                        ) {
+                            (false, _) => "Offset",
+                            (true, false) => "Occlusion",
+                            (true, true) => "Occlusion (self-shadowed)",
+                        }
+                    )
+                }
+                Some("Toggle roughness mapping") => {
                     self.roughness_mapping_active = !self.roughness_mapping_active;
 
                     println!(
@@ -103,7 +171,7 @@ impl RenderShaderOptions {
                         }
                     )
                 }
-                Keycode::M => {
+                Some("Toggle metallic mapping") => {
                     self.metallic_mapping_active = !self.metallic_mapping_active;
 
                     println!(
@@ -115,7 +183,7 @@ impl RenderShaderOptions {
                         }
                     )
                 }
-                Keycode::K => {
+                Some("Toggle emissive color mapping") => {
                     self.emissive_color_mapping_active = !self.emissive_color_mapping_active;
 
                     println!(
@@ -115,6 +115,12 @@ fn reinhard_extended_luminance(hdr: Vec3, luminance_white_squared: f32) -> Vec3
 
     let old_luminance = hdr.luminance();
 
+    // Black can't be rescaled to a new luminance.
+
+    if old_luminance == 0.0 {
+        return hdr;
+    }
+
     let numerator = (1.0 + old_luminance / luminance_white_squared) * old_luminance;
 
     let denominator = 1.0 + old_luminance;
@@ -239,3 +245,93 @@ fn exposure_exponentiated(hdr: Vec3, exposure: f32) -> Vec3 {
             z: (-hdr.z * exposure).exp(),
         }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::vec::vec3::Vec3;
+
+    use super::{ToneMappingOperator, TONE_MAPPING_OPERATORS};
+
+    fn map_gray(operator: ToneMappingOperator, value: f32) -> f32 {
+        operator
+            .map(Vec3 {
+                x: value,
+                y: value,
+                z: value,
+            })
+            .x
+    }
+
+    fn assert_approx_eq(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-4,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn reinhard_maps_inputs_to_their_ratio_with_one_plus_the_input() {
+        let operator = ToneMappingOperator::Reinhard;
+
+        assert_approx_eq(map_gray(operator, 0.0), 0.0);
+        assert_approx_eq(map_gray(operator, 1.0), 0.5);
+        assert_approx_eq(map_gray(operator, 3.0), 0.75);
+    }
+
+    #[test]
+    fn reinhard_extended_maps_the_white_point_to_one() {
+        // Parameterized by the square of the white point.
+
+        let operator = ToneMappingOperator::ReinhardExtended(4.0);
+
+        assert_approx_eq(map_gray(operator, 0.0), 0.0);
+        assert_approx_eq(map_gray(operator, 1.0), 0.625);
+        assert_approx_eq(map_gray(operator, 2.0), 1.0);
+    }
+
+    #[test]
+    fn aces_maps_black_to_black_and_saturates_at_one() {
+        for operator in [
+            ToneMappingOperator::ACES,
+            ToneMappingOperator::ACESApproximate,
+        ] {
+            assert_approx_eq(map_gray(operator, 0.0), 0.0);
+
+            let mut previous = 0.0;
+
+            for value in [0.1, 0.5, 1.0, 4.0, 16.0, 1000.0] {
+                let mapped = map_gray(operator, value);
+
+                assert!(mapped >= previous, "{} is not monotonic", operator);
+                assert!(mapped <= 1.0, "{} exceeds 1.0", operator);
+
+                previous = mapped;
+            }
+
+            assert!(previous > 0.99);
+        }
+
+        assert_approx_eq(map_gray(ToneMappingOperator::ACESApproximate, 1.0), 0.6733);
+    }
+
+    #[test]
+    fn exposure_approaches_one_exponentially() {
+        let operator = ToneMappingOperator::Exposure(1.0);
+
+        assert_approx_eq(map_gray(operator, 0.0), 0.0);
+        assert_approx_eq(map_gray(operator, 1.0), 1.0 - (-1.0_f32).exp());
+        assert_approx_eq(
+            map_gray(ToneMappingOperator::Exposure(2.0), 1.0),
+            1.0 - (-2.0_f32).exp(),
+        );
+    }
+
+    #[test]
+    fn operators_map_black_to_black() {
+        for operator in TONE_MAPPING_OPERATORS {
+            assert_approx_eq(map_gray(operator, 0.0), 0.0);
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{app::resolution::Resolution, buffer::Buffer2D, color::Color};
+
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub enum UpscaleFilter {
+    Nearest,
+    #[default]
+    Bilinear,
+    // Bilinear, followed by an edge-adaptive sharpening pass (similar to
+    // AMD's FidelityFX CAS); `sharpness` is in the range [0, 1].
+    Sharpen {
+        sharpness: f32,
+    },
+}
+
+// Renders the scene at a fraction of the canvas resolution; optionally adjusts
+// that fraction to keep frame times near a target.
+#[derive(Debug, Copy, Clone)]
+pub struct RenderScale {
+    pub scale: f32,
+    pub min_scale: f32,
+    pub max_scale: f32,
+    pub target_frame_seconds: Option<f32>,
+    pub filter: UpscaleFilter,
+}
+
+impl Default for RenderScale {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            min_scale: 0.25,
+            max_scale: 1.0,
+            target_frame_seconds: None,
+            filter: Default::default(),
+        }
+    }
+}
+
+static DYNAMIC_SCALE_STEP: f32 = 0.05;
+
+impl RenderScale {
+    pub fn new(scale: f32) -> Self {
+        let mut render_scale = Self::default();
+
+        render_scale.set_scale(scale);
+
+        render_scale
+    }
+
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale.clamp(self.min_scale, self.max_scale);
+    }
+
+    pub fn get_scaled_resolution(&self, canvas_resolution: Resolution) -> Resolution {
+        let scaled = canvas_resolution * self.scale;
+
+        Resolution {
+            width: scaled.width.max(1),
+            height: scaled.height.max(1),
+        }
+    }
+
+    // Nudges the scale down when frames run long (and back up when there's
+    // headroom); returns `true` if the scale changed.
+    pub fn update(&mut self, seconds_since_last_update: f32) -> bool {
+        let target = match self.target_frame_seconds {
+            Some(seconds) => seconds,
+            None => return false,
+        };
+
+        let previous_scale = self.scale;
+
+        if seconds_since_last_update > target * 1.1 {
+            self.set_scale(self.scale - DYNAMIC_SCALE_STEP);
+        } else if seconds_since_last_update < target * 0.8 {
+            self.set_scale(self.scale + DYNAMIC_SCALE_STEP);
+        }
+
+        self.scale != previous_scale
+    }
+
+    pub fn upscale(&self, source: &Buffer2D, target: &mut Buffer2D) {
+        upscale(source, target, self.filter);
+    }
+}
+
+// Resamples `source` to fill `target`, using the given filter.
+pub fn upscale(source: &Buffer2D, target: &mut Buffer2D, filter: UpscaleFilter) {
+    match filter {
+        UpscaleFilter::Nearest => upscale_nearest(source, target),
+        UpscaleFilter::Bilinear => upscale_bilinear(source, target),
+        UpscaleFilter::Sharpen { sharpness } => {
+            upscale_bilinear(source, target);
+
+            sharpen(target, sharpness);
+        }
+    }
+}
+
+fn get_channels(color_u32: u32) -> [f32; 4] {
+    let color = Color::from_u32(color_u32);
+
+    [color.r, color.g, color.b, color.a]
+}
+
+// Resamples `source` to fill `target` (nearest-neighbor filtering).
+pub fn upscale_nearest(source: &Buffer2D, target: &mut Buffer2D) {
+    let scale_x = source.width as f32 / target.width as f32;
+    let scale_y = source.height as f32 / target.height as f32;
+
+    for y in 0..target.height {
+        let source_y = (((y as f32 + 0.5) * scale_y) as u32).min(source.height - 1);
+
+        for x in 0..target.width {
+            let source_x = (((x as f32 + 0.5) * scale_x) as u32).min(source.width - 1);
+
+            target.set(x, y, *source.get(source_x, source_y));
+        }
+    }
+}
+
+// Resamples `source` to fill `target` (bilinear filtering).
+pub fn upscale_bilinear(source: &Buffer2D, target: &mut Buffer2D) {
+    let scale_x = source.width as f32 / target.width as f32;
+    let scale_y = source.height as f32 / target.height as f32;
+
+    let (max_x, max_y) = (source.width - 1, source.height - 1);
+
+    for y in 0..target.height {
+        let source_y = ((y as f32 + 0.5) * scale_y - 0.5).clamp(0.0, max_y as f32);
+
+        let y0 = source_y as u32;
+        let y1 = (y0 + 1).min(max_y);
+        let alpha_y = source_y - y0 as f32;
+
+        for x in 0..target.width {
+            let source_x = ((x as f32 + 0.5) * scale_x - 0.5).clamp(0.0, max_x as f32);
+
+            let x0 = source_x as u32;
+            let x1 = (x0 + 1).min(max_x);
+            let alpha_x = source_x - x0 as f32;
+
+            let top_left = get_channels(*source.get(x0, y0));
+            let top_right = get_channels(*source.get(x1, y0));
+            let bottom_left = get_channels(*source.get(x0, y1));
+            let bottom_right = get_channels(*source.get(x1, y1));
+
+            let mut channels = [0.0; 4];
+
+            for i in 0..4 {
+                let top = top_left[i] + (top_right[i] - top_left[i]) * alpha_x;
+                let bottom = bottom_left[i] + (bottom_right[i] - bottom_left[i]) * alpha_x;
+
+                channels[i] = (top + (bottom - top) * alpha_y).round();
+            }
+
+            let color = Color {
+                r: channels[0],
+                g: channels[1],
+                b: channels[2],
+                a: channels[3],
+            };
+
+            target.set(x, y, color.to_u32());
+        }
+    }
+}
+
+// Sharpens `buffer` in place, weighting each pixel against its 4 neighbors;
+// areas that already have high local contrast are sharpened less, to avoid
+// ringing.
+pub fn sharpen(buffer: &mut Buffer2D, sharpness: f32) {
+    let source = buffer.clone();
+
+    let (max_x, max_y) = (source.width - 1, source.height - 1);
+
+    // Peak negative neighbor weight, per the CAS reference implementation.
+
+    let peak = -1.0 / (8.0 + (5.0 - 8.0) * sharpness.clamp(0.0, 1.0));
+
+    for y in 0..source.height {
+        for x in 0..source.width {
+            let center = get_channels(*source.get(x, y));
+
+            let neighbors = [
+                get_channels(*source.get(x, y.saturating_sub(1))),
+                get_channels(*source.get(x, (y + 1).min(max_y))),
+                get_channels(*source.get(x.saturating_sub(1), y)),
+                get_channels(*source.get((x + 1).min(max_x), y)),
+            ];
+
+            let mut channels = center;
+
+            // Sharpens color channels (not alpha).
+
+            for i in 0..3 {
+                let (mut min, mut max, mut sum) = (center[i], center[i], 0.0);
+
+                for neighbor in &neighbors {
+                    min = min.min(neighbor[i]);
+                    max = max.max(neighbor[i]);
+                    sum += neighbor[i];
+                }
+
+                let amplitude = (min.min(255.0 - max) / max.max(1.0)).clamp(0.0, 1.0).sqrt();
+
+                let weight = amplitude * peak;
+
+                channels[i] = ((sum * weight + center[i]) / (1.0 + 4.0 * weight))
+                    .clamp(0.0, 255.0)
+                    .round();
+            }
+
+            let color = Color {
+                r: channels[0],
+                g: channels[1],
+                b: channels[2],
+                a: channels[3],
+            };
+
+            buffer.set(x, y, color.to_u32());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        app::resolution::Resolution,
+        buffer::{framebuffer::Framebuffer, Buffer2D},
+        color::{self, Color},
+    };
+
+    use super::{sharpen, upscale, upscale_bilinear, RenderScale, UpscaleFilter};
+
+    #[test]
+    fn half_scale_renders_at_half_size_and_fills_canvas() {
+        let canvas_resolution = Resolution::new((640, 480));
+
+        let resolution = RenderScale::new(0.5).get_scaled_resolution(canvas_resolution);
+
+        let framebuffer = Framebuffer::new(resolution.width, resolution.height);
+
+        assert_eq!((framebuffer.width, framebuffer.height), (320, 240));
+
+        // Upscaling the pipeline's output covers every canvas pixel.
+
+        let color = color::ORANGE.to_u32();
+
+        let source = Buffer2D::new(resolution.width, resolution.height, Some(color));
+
+        let mut canvas = Buffer2D::new(canvas_resolution.width, canvas_resolution.height, None);
+
+        upscale_bilinear(&source, &mut canvas);
+
+        assert!(canvas.data.iter().all(|pixel| *pixel == color));
+    }
+
+    #[test]
+    fn nearest_and_bilinear_differ_between_source_pixels() {
+        let mut source = Buffer2D::new(2, 1, None);
+
+        source.set(0, 0, color::BLACK.to_u32());
+        source.set(1, 0, color::WHITE.to_u32());
+
+        let mut target = Buffer2D::new(4, 1, None);
+
+        // Target pixel 1 sits a quarter of the way between the 2 source pixels.
+
+        upscale(&source, &mut target, UpscaleFilter::Nearest);
+
+        assert_eq!(*target.get(1, 0), color::BLACK.to_u32());
+
+        upscale(&source, &mut target, UpscaleFilter::Bilinear);
+
+        assert_eq!(*target.get(1, 0), Color::rgb(64, 64, 64).to_u32());
+    }
+
+    #[test]
+    fn sharpen_increases_contrast_on_an_edge() {
+        let values = [64, 64, 96, 160, 192, 192];
+
+        let mut buffer = Buffer2D::new(values.len() as u32, 1, None);
+
+        for (x, value) in values.iter().enumerate() {
+            buffer.set(x as u32, 0, Color::rgb(*value, *value, *value).to_u32());
+        }
+
+        let get_contrast = |buffer: &Buffer2D| -> f32 {
+            Color::from_u32(*buffer.get(3, 0)).r - Color::from_u32(*buffer.get(2, 0)).r
+        };
+
+        let contrast_before = get_contrast(&buffer);
+
+        sharpen(&mut buffer, 1.0);
+
+        assert!(get_contrast(&buffer) > contrast_before);
+    }
+}
@@ -0,0 +1,160 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+// Per-frame counts, reset at the start of each frame.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenderStats {
+    pub entities_rendered: u32,
+    pub entities_culled: u32,
+    pub triangles_submitted: u32,
+    // Fragments that passed the depth test and were run through our geometry
+    // shader (and, when forward-rendering, our fragment shader).
+    #[serde(default)]
+    pub fragments_shaded: u32,
+    pub fragments_written: u32,
+}
+
+#[derive(Default, Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct RenderStatsFrame {
+    pub frame_index: u32,
+    pub frame_seconds: f32,
+    #[serde(flatten)]
+    pub stats: RenderStats,
+}
+
+static CSV_COLUMNS: [&str; 7] = [
+    "frame_index",
+    "frame_seconds",
+    "entities_rendered",
+    "entities_culled",
+    "triangles_submitted",
+    "fragments_shaded",
+    "fragments_written",
+];
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RenderStatsFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+// Records `RenderStats` over many frames, for export (e.g., when the app exits).
+#[derive(Default, Debug, Clone)]
+pub struct RenderStatsRecorder {
+    pub frames: Vec<RenderStatsFrame>,
+}
+
+impl RenderStatsRecorder {
+    pub fn record(&mut self, frame_index: u32, frame_seconds: f32, stats: RenderStats) {
+        self.frames.push(RenderStatsFrame {
+            frame_index,
+            frame_seconds,
+            stats,
+        });
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = CSV_COLUMNS.join(",");
+
+        csv.push('\n');
+
+        for frame in &self.frames {
+            let stats = &frame.stats;
+
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{}\n",
+                frame.frame_index,
+                frame.frame_seconds,
+                stats.entities_rendered,
+                stats.entities_culled,
+                stats.triangles_submitted,
+                stats.fragments_shaded,
+                stats.fragments_written
+            ));
+        }
+
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(&self.frames).map_err(|err| err.to_string())
+    }
+
+    pub fn export(&self, path: &str, format: RenderStatsFormat) -> Result<(), String> {
+        let contents = match format {
+            RenderStatsFormat::Csv => self.to_csv(),
+            RenderStatsFormat::Json => self.to_json()?,
+        };
+
+        fs::write(path, contents)
+            .map_err(|err| format!("Failed to write render stats to '{}': {}", path, err))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::testing::TempPath;
+
+    use super::{RenderStats, RenderStatsFormat, RenderStatsRecorder, CSV_COLUMNS};
+
+    #[test]
+    fn exported_stats_have_one_row_per_frame() {
+        let mut recorder = RenderStatsRecorder::default();
+
+        let frame_count = 5;
+
+        for frame_index in 0..frame_count {
+            let stats = RenderStats {
+                entities_rendered: frame_index,
+                triangles_submitted: frame_index * 12,
+                ..Default::default()
+            };
+
+            recorder.record(frame_index, 1.0 / 60.0, stats);
+        }
+
+        // CSV
+
+        let csv_path = TempPath::new("render-stats.csv");
+        let path = csv_path.to_str().unwrap();
+
+        recorder.export(path, RenderStatsFormat::Csv).unwrap();
+
+        let csv = fs::read_to_string(path).unwrap();
+
+        let mut lines = csv.lines();
+
+        assert_eq!(lines.next().unwrap(), CSV_COLUMNS.join(","));
+
+        let rows: Vec<&str> = lines.collect();
+
+        assert_eq!(rows.len(), frame_count as usize);
+        assert!(rows[2].starts_with("2,"));
+
+        for row in &rows {
+            assert_eq!(row.split(',').count(), CSV_COLUMNS.len());
+        }
+
+        // JSON
+
+        let json_path = TempPath::new("render-stats.json");
+        let path = json_path.to_str().unwrap();
+
+        recorder.export(path, RenderStatsFormat::Json).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+
+        let rows = json.as_array().unwrap();
+
+        assert_eq!(rows.len(), frame_count as usize);
+
+        for column in CSV_COLUMNS {
+            assert!(rows[0].get(column).is_some());
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    fmt,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+};
+
+use crate::thread_pool::ThreadPool;
+
+pub type ResourceLoadFn<T> = Box<dyn FnOnce() -> Result<T, String> + Send>;
+
+#[derive(Debug)]
+pub struct LoadedResource<T> {
+    pub job_id: u64,
+    pub priority: i32,
+    pub result: Result<T, String>,
+}
+
+struct PendingJob<T> {
+    id: u64,
+    priority: i32,
+    load: ResourceLoadFn<T>,
+}
+
+// Higher priorities pop first; jobs with equal priority pop in the order they
+// were enqueued.
+impl<T> Ord for PendingJob<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+impl<T> PartialOrd for PendingJob<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> PartialEq for PendingJob<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for PendingJob<T> {}
+
+struct ResourceLoaderState<T> {
+    pending: BinaryHeap<PendingJob<T>>,
+    is_shutting_down: bool,
+}
+
+type SharedResourceLoaderState<T> = Arc<Mutex<ResourceLoaderState<T>>>;
+
+// Loads resources on a (shared) thread pool, in priority order. Finished
+// resources are handed back to the main thread through `poll()`, which is meant
+// to be called between frames (e.g., to insert the results into scene arenas).
+pub struct ResourceLoaderQueue<T: Send + 'static> {
+    thread_pool: Arc<ThreadPool>,
+    state: SharedResourceLoaderState<T>,
+    sender: Sender<LoadedResource<T>>,
+    receiver: Receiver<LoadedResource<T>>,
+    next_job_id: u64,
+    in_flight: usize,
+}
+
+impl<T: Send + 'static> fmt::Debug for ResourceLoaderQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceLoaderQueue")
+            .field("thread_pool", &self.thread_pool)
+            .field("next_job_id", &self.next_job_id)
+            .field("in_flight", &self.in_flight)
+            .finish()
+    }
+}
+
+impl<T: Send + 'static> ResourceLoaderQueue<T> {
+    pub fn new(thread_pool: Arc<ThreadPool>) -> Self {
+        let state: SharedResourceLoaderState<T> = Arc::new(Mutex::new(ResourceLoaderState {
+            pending: BinaryHeap::new(),
+            is_shutting_down: false,
+        }));
+
+        let (sender, receiver) = channel();
+
+        Self {
+            thread_pool,
+            state,
+            sender,
+            receiver,
+            next_job_id: 0,
+            in_flight: 0,
+        }
+    }
+
+    pub fn enqueue(&mut self, priority: i32, load: ResourceLoadFn<T>) -> u64 {
+        let id = self.next_job_id;
+
+        self.next_job_id += 1;
+
+        self.state
+            .lock()
+            .unwrap()
+            .pending
+            .push(PendingJob { id, priority, load });
+
+        // Each pool job loads whichever pending resource has the highest
+        // priority once a worker picks it up, rather than this particular one.
+
+        let state = self.state.clone();
+        let sender = self.sender.clone();
+
+        self.thread_pool
+            .execute(move || run_next_job(state, sender));
+
+        self.in_flight += 1;
+
+        id
+    }
+
+    // Number of jobs that have been enqueued but not yet delivered.
+    pub fn get_in_flight_count(&self) -> usize {
+        self.in_flight
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.in_flight == 0
+    }
+
+    pub fn poll(&mut self) -> Vec<LoadedResource<T>> {
+        let delivered: Vec<LoadedResource<T>> = self.receiver.try_iter().collect();
+
+        self.in_flight -= delivered.len();
+
+        delivered
+    }
+}
+
+impl<T: Send + 'static> Drop for ResourceLoaderQueue<T> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+
+        // Discards any jobs that haven't started yet.
+
+        state.pending.clear();
+        state.is_shutting_down = true;
+    }
+}
+
+fn run_next_job<T>(state: SharedResourceLoaderState<T>, sender: Sender<LoadedResource<T>>) {
+    let job = {
+        let mut state = state.lock().unwrap();
+
+        if state.is_shutting_down {
+            return;
+        }
+
+        match state.pending.pop() {
+            Some(job) => job,
+            None => return,
+        }
+    };
+
+    let result = (job.load)();
+
+    let loaded = LoadedResource {
+        job_id: job.id,
+        priority: job.priority,
+        result,
+    };
+
+    // The queue may have been dropped.
+
+    sender.send(loaded).ok();
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::{mpsc::channel, Arc},
+        time::{Duration, Instant},
+    };
+
+    use crate::thread_pool::ThreadPool;
+
+    use super::ResourceLoaderQueue;
+
+    #[test]
+    fn higher_priority_jobs_are_delivered_first() {
+        let thread_pool = Arc::new(ThreadPool::new(1));
+
+        let mut queue: ResourceLoaderQueue<&'static str> = ResourceLoaderQueue::new(thread_pool);
+
+        // Occupies the single worker until the remaining jobs are enqueued.
+
+        let (release_sender, release_receiver) = channel::<()>();
+
+        queue.enqueue(
+            i32::MAX,
+            Box::new(move || {
+                release_receiver.recv().unwrap();
+
+                Ok("gate")
+            }),
+        );
+
+        queue.enqueue(0, Box::new(|| Ok("low")));
+        queue.enqueue(10, Box::new(|| Ok("high")));
+        queue.enqueue(5, Box::new(|| Ok("medium")));
+        queue.enqueue(5, Box::new(|| Ok("medium (later)")));
+
+        release_sender.send(()).unwrap();
+
+        let mut delivered = vec![];
+
+        let start = Instant::now();
+
+        while !queue.is_idle() && start.elapsed() < Duration::from_secs(5) {
+            for loaded in queue.poll() {
+                delivered.push(loaded.result.unwrap());
+            }
+
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        assert_eq!(
+            delivered,
+            vec!["gate", "high", "medium", "medium (later)", "low"]
+        );
+    }
+}
@@ -72,6 +72,35 @@ pub struct Frustum {
 }
 
 impl Frustum {
+    pub fn get_corners(&self) -> [Vec3; 8] {
+        [
+            self.near[0],
+            self.near[1],
+            self.near[2],
+            self.near[3],
+            self.far[0],
+            self.far[1],
+            self.far[2],
+            self.far[3],
+        ]
+    }
+
+    // Returns the frustum's 12 edges: 4 around the near plane, 4 around the far
+    // plane, then 4 connecting the 2 planes.
+    pub fn get_edges(&self) -> [(Vec3, Vec3); 12] {
+        let mut edges = [(Vec3::default(), Vec3::default()); 12];
+
+        for i in 0..4 {
+            let next = (i + 1) % 4;
+
+            edges[i] = (self.near[i], self.near[next]);
+            edges[4 + i] = (self.far[i], self.far[next]);
+            edges[8 + i] = (self.near[i], self.far[i]);
+        }
+
+        edges
+    }
+
     pub fn get_center(&self) -> Vec3 {
         let near_center = {
             let mut center: Vec3 = Default::default();
@@ -174,3 +203,59 @@ impl Frustum {
         [near, far, left, right, top, bottom]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        scene::camera::Camera,
+        vec::vec3::{self, Vec3},
+    };
+
+    #[test]
+    fn frustum_edges_connect_the_cameras_corners() {
+        let position = Vec3 {
+            x: 1.0,
+            y: 2.0,
+            z: -5.0,
+        };
+
+        let camera = Camera::from_perspective(position, vec3::ONES, 75.0, 16.0 / 9.0);
+
+        let frustum = camera.get_frustum();
+
+        let corners = frustum.get_corners();
+
+        // Near and far corners sit on their respective planes.
+
+        let forward = camera.look_vector.get_forward();
+
+        for (index, corner) in corners.iter().enumerate() {
+            let depth = (*corner - position).dot(forward);
+
+            let expected_depth = if index < 4 {
+                camera.get_projection_z_near()
+            } else {
+                camera.get_projection_z_far()
+            };
+
+            assert!((depth - expected_depth).abs() < 0.01 * expected_depth);
+        }
+
+        // Every drawn edge runs between 2 corners, and each corner joins 3 edges.
+
+        let edges = frustum.get_edges();
+
+        for corner in &corners {
+            let count = edges
+                .iter()
+                .filter(|(start, end)| start == corner || end == corner)
+                .count();
+
+            assert_eq!(count, 3);
+        }
+
+        for (start, end) in &edges {
+            assert!(corners.contains(start) && corners.contains(end));
+        }
+    }
+}
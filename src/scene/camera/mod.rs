@@ -3,7 +3,9 @@ use std::{f32::consts::PI, fmt};
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    animation::lerp,
     device::{game_controller::GameControllerState, keyboard::KeyboardState, mouse::MouseState},
+    geometry::primitives::{aabb::AABB, ray::Ray},
     matrix::Mat4,
     serde::PostDeserialize,
     shader::context::ShaderContext,
@@ -22,11 +24,15 @@ use self::frustum::{Frustum, FAR_PLANE_POINTS_CLIP_SPACE, NEAR_PLANE_POINTS_CLIP
 
 pub mod frustum;
 
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CameraProjectionKind {
     #[default]
     Perspective,
-    Orthographic,
+    // The height of the view volume, in world units; its width follows from
+    // the camera's aspect ratio.
+    Orthographic {
+        height: f32,
+    },
 }
 
 impl fmt::Display for CameraProjectionKind {
@@ -36,7 +42,7 @@ impl fmt::Display for CameraProjectionKind {
             "{}",
             match self {
                 CameraProjectionKind::Perspective => "Perspective",
-                CameraProjectionKind::Orthographic => "Orthographic",
+                CameraProjectionKind::Orthographic { .. } => "Orthographic",
             }
         )
     }
@@ -44,7 +50,9 @@ impl fmt::Display for CameraProjectionKind {
 
 pub static CAMERA_PROJECTION_KINDS: [CameraProjectionKind; 2] = [
     CameraProjectionKind::Perspective,
-    CameraProjectionKind::Orthographic,
+    CameraProjectionKind::Orthographic {
+        height: DEFAULT_CAMERA_ORTHOGRAPHIC_HEIGHT,
+    },
 ];
 
 #[derive(Default, Debug, Copy, Clone, Serialize, Deserialize)]
@@ -55,8 +63,29 @@ pub struct CameraOrthographicExtent {
     pub bottom: f32,
 }
 
-static DEFAULT_CAMERA_FIELD_OF_VIEW: f32 = 75.0;
-static DEFAULT_CAMERA_ASPECT_RATIO: f32 = 16.0 / 9.0;
+impl CameraOrthographicExtent {
+    pub fn from_height(height: f32, aspect_ratio: f32) -> Self {
+        let half_height = height / 2.0;
+        let half_width = half_height * aspect_ratio;
+
+        Self {
+            left: -half_width,
+            right: half_width,
+            top: half_height,
+            bottom: -half_height,
+        }
+    }
+}
+
+pub static DEFAULT_CAMERA_FIELD_OF_VIEW: f32 = 75.0;
+pub static DEFAULT_CAMERA_ASPECT_RATIO: f32 = 16.0 / 9.0;
+pub static DEFAULT_CAMERA_ORTHOGRAPHIC_HEIGHT: f32 = 10.0;
+
+// Limits for depth ranges fitted to scene bounds; the margin is a fraction of
+// each fitted plane's distance from the camera.
+static FITTED_PROJECTION_Z_NEAR_MIN: f32 = 0.05;
+static FITTED_PROJECTION_DEPTH_MIN: f32 = 1.0;
+static FITTED_PROJECTION_Z_MARGIN: f32 = 0.05;
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct Camera {
@@ -68,6 +97,9 @@ pub struct Camera {
     pub movement_speed: f32,
     projection_z_near: f32,
     projection_z_far: f32,
+    // Fits the near and far planes to the visible scene bounds, every frame.
+    #[serde(default)]
+    pub auto_fit_depth_range: bool,
     #[serde(skip)]
     projection_transform: Mat4,
     #[serde(skip)]
@@ -139,6 +171,7 @@ impl Camera {
             extent,
             projection_z_near,
             projection_z_far,
+            auto_fit_depth_range: false,
             movement_speed: 50.0,
             projection_transform: Default::default(),
             projection_inverse_transform: Default::default(),
@@ -160,7 +193,9 @@ impl Camera {
         extent: CameraOrthographicExtent,
     ) -> Self {
         Camera::new(
-            CameraProjectionKind::Orthographic,
+            CameraProjectionKind::Orthographic {
+                height: extent.top - extent.bottom,
+            },
             position,
             target,
             None,
@@ -197,6 +232,39 @@ impl Camera {
         self.recompute_world_space_frustum();
     }
 
+    pub fn set_perspective(&mut self, field_of_view: f32) {
+        self.kind = CameraProjectionKind::Perspective;
+
+        self.field_of_view = Some(field_of_view);
+
+        if self.aspect_ratio.is_none() {
+            self.aspect_ratio = Some(DEFAULT_CAMERA_ASPECT_RATIO);
+        }
+
+        self.recompute_projections();
+
+        self.recompute_world_space_frustum();
+    }
+
+    pub fn set_orthographic(&mut self, height: f32) {
+        self.kind = CameraProjectionKind::Orthographic { height };
+
+        // Derives the extent from the height and aspect ratio.
+
+        self.extent = None;
+
+        self.recompute_projections();
+
+        self.recompute_world_space_frustum();
+    }
+
+    pub fn get_orthographic_height(&self) -> Option<f32> {
+        match self.kind {
+            CameraProjectionKind::Perspective => None,
+            CameraProjectionKind::Orthographic { height } => Some(height),
+        }
+    }
+
     pub fn get_field_of_view(&self) -> Option<f32> {
         self.field_of_view
     }
@@ -214,20 +282,27 @@ impl Camera {
     }
 
     pub fn set_aspect_ratio(&mut self, aspect_ratio: f32) -> Result<(), String> {
-        match self.kind {
-            CameraProjectionKind::Perspective => {
-                self.aspect_ratio = Some(aspect_ratio);
+        if aspect_ratio <= 0.0 || !aspect_ratio.is_finite() {
+            return Err(format!(
+                "Called Camera::set_aspect_ratio() with an invalid aspect ratio ({})!",
+                aspect_ratio
+            ));
+        }
 
-                self.recompute_projections();
+        self.aspect_ratio = Some(aspect_ratio);
 
-                self.recompute_world_space_frustum();
+        // An orthographic camera's width follows its aspect ratio, replacing
+        // any explicit extent.
 
-                Ok(())
-            }
-            CameraProjectionKind::Orthographic => {
-                Err("Called Camera::set_aspect_ratio() on an orthographic camera!".to_string())
-            }
+        if let CameraProjectionKind::Orthographic { .. } = self.kind {
+            self.extent = None;
         }
+
+        self.recompute_projections();
+
+        self.recompute_world_space_frustum();
+
+        Ok(())
     }
 
     pub fn get_projection_z_near(&self) -> f32 {
@@ -254,6 +329,39 @@ impl Camera {
         self.recompute_world_space_frustum();
     }
 
+    // Moves the near and far planes as close as possible to a (world-space)
+    // bounding box, to maximize depth precision.
+    pub fn fit_projection_z_to_bounds(&mut self, aabb: &AABB) {
+        let view_inverse_transform = self.get_view_inverse_transform();
+
+        let (mut min_z, mut max_z) = (f32::MAX, f32::MIN);
+
+        for vertex in aabb.get_vertices() {
+            let z = (Vec4::new(vertex, 1.0) * view_inverse_transform).z;
+
+            min_z = min_z.min(z);
+            max_z = max_z.max(z);
+        }
+
+        // Nothing in front of the camera.
+
+        if max_z <= 0.0 {
+            return;
+        }
+
+        let near = (min_z * (1.0 - FITTED_PROJECTION_Z_MARGIN)).max(FITTED_PROJECTION_Z_NEAR_MIN);
+
+        let far =
+            (max_z * (1.0 + FITTED_PROJECTION_Z_MARGIN)).max(near + FITTED_PROJECTION_DEPTH_MIN);
+
+        self.projection_z_near = near;
+        self.projection_z_far = far;
+
+        self.recompute_projections();
+
+        self.recompute_world_space_frustum();
+    }
+
     pub fn get_frustum(&self) -> &Frustum {
         &self.frustum
     }
@@ -329,7 +437,7 @@ impl Camera {
 
                 (near_plane_points_world_space, far_plane_points_world_space)
             }
-            CameraProjectionKind::Orthographic => {
+            CameraProjectionKind::Orthographic { .. } => {
                 let near_plane_points_world_space = NEAR_PLANE_POINTS_CLIP_SPACE
                     .map(|coord| coord * self.get_projection_inverse() * self.get_view_transform())
                     .map(|coord| coord.to_vec3());
@@ -349,6 +457,27 @@ impl Camera {
         };
     }
 
+    // Returns a world-space ray passing through the center of the given pixel,
+    // from the near plane towards the far plane.
+    pub fn get_pixel_ray(&self, screen_x: u32, screen_y: u32, width: u32, height: u32) -> Ray {
+        let u = (screen_x as f32 + 0.5) / width as f32;
+        let v = (screen_y as f32 + 0.5) / height as f32;
+
+        // Frustum corners run clockwise from the top-left.
+
+        let get_plane_point = |corners: &[Vec3; 4]| -> Vec3 {
+            let top = lerp(corners[0], corners[1], u);
+            let bottom = lerp(corners[3], corners[2], u);
+
+            lerp(top, bottom, v)
+        };
+
+        let near = get_plane_point(&self.frustum.near);
+        let far = get_plane_point(&self.frustum.far);
+
+        Ray::new(near, (far - near).as_normal())
+    }
+
     pub fn get_near_plane_pixel_world_space_position(
         &self,
         screen_x: u32,
@@ -408,7 +537,7 @@ impl Camera {
                 keyboard_state,
                 match self.kind {
                     CameraProjectionKind::Perspective => Some(mouse_state),
-                    CameraProjectionKind::Orthographic => None,
+                    CameraProjectionKind::Orthographic { .. } => None,
                 },
                 game_controller_state,
                 self.movement_speed,
@@ -458,8 +587,14 @@ impl Camera {
                     self.projection_z_far,
                 );
             }
-            CameraProjectionKind::Orthographic => {
-                let extent = self.extent.unwrap();
+            CameraProjectionKind::Orthographic { height } => {
+                let extent = match self.extent {
+                    Some(extent) => extent,
+                    None => CameraOrthographicExtent::from_height(
+                        height,
+                        self.aspect_ratio.unwrap_or(DEFAULT_CAMERA_ASPECT_RATIO),
+                    ),
+                };
 
                 let (left, right, bottom, top, near, far) = (
                     extent.left,
@@ -478,3 +613,70 @@ impl Camera {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::vec::{
+        vec3::{self, Vec3},
+        vec4::Vec4,
+    };
+
+    use super::Camera;
+
+    fn project_to_ndc(camera: &Camera, point: Vec3) -> Vec3 {
+        let clip =
+            Vec4::new(point, 1.0) * camera.get_view_inverse_transform() * camera.get_projection();
+
+        clip.to_vec3() / clip.w
+    }
+
+    #[test]
+    fn known_points_project_to_expected_ndc_coordinates() {
+        let mut camera = Camera::from_perspective(Default::default(), vec3::FORWARD, 90.0, 1.0);
+
+        let point = Vec3 {
+            x: 1.0,
+            y: 0.5,
+            z: 2.0,
+        };
+
+        // With a 90-degree field of view, NDC coordinates are (x / z, y / z).
+
+        let ndc = project_to_ndc(&camera, point);
+
+        assert!((ndc.x - 0.5).abs() < 1e-5);
+        assert!((ndc.y - 0.25).abs() < 1e-5);
+
+        // An orthographic view volume, 4 units tall and 8 units wide.
+
+        camera.set_orthographic(4.0);
+        camera.set_aspect_ratio(2.0).unwrap();
+
+        let point = Vec3 {
+            x: 2.0,
+            y: 1.0,
+            z: 5.0,
+        };
+
+        let ndc = project_to_ndc(&camera, point);
+
+        assert!((ndc.x - 0.5).abs() < 1e-5);
+        assert!((ndc.y - 0.5).abs() < 1e-5);
+
+        // Narrowing the aspect ratio narrows the view volume.
+
+        camera.set_aspect_ratio(1.0).unwrap();
+
+        let ndc = project_to_ndc(&camera, point);
+
+        assert!((ndc.x - 1.0).abs() < 1e-5);
+        assert!((ndc.y - 0.5).abs() < 1e-5);
+
+        camera.set_perspective(90.0);
+
+        let ndc = project_to_ndc(&camera, point);
+
+        assert!((ndc.x - 0.4).abs() < 1e-5);
+        assert!((ndc.y - 0.2).abs() < 1e-5);
+    }
+}
@@ -10,11 +10,17 @@ use crate::{
     app::App,
     color,
     device::{game_controller::GameControllerState, keyboard::KeyboardState, mouse::MouseState},
+    geometry::{
+        accelerator::static_triangle_bvh::StaticTriangleBVH,
+        intersect::intersect_ray_transformed_bvh,
+        primitives::{aabb::AABB, ray::Ray},
+    },
     matrix::Mat4,
     render::{culling::FaceCullingReject, options::RenderPassFlag, Renderer},
     resource::handle::Handle,
     serde::PostDeserialize,
     shader::context::ShaderContext,
+    vec::{vec3::Vec3, vec4::Vec4},
 };
 
 use super::{
@@ -69,6 +75,10 @@ impl SceneGraph {
     ) -> Result<(), String> {
         shader_context.clear_lights();
 
+        shader_context.set_timing_info(&app.timing_info);
+
+        self.fit_camera_depth_ranges(resources)?;
+
         self.root.visit_mut(
             SceneNodeGlobalTraversalMethod::DepthFirst,
             Some(SceneNodeLocalTraversalMethod::PostOrder),
@@ -110,6 +120,136 @@ impl SceneGraph {
         Ok(())
     }
 
+    // Returns a world-space bounding box around every entity in the scene, if
+    // there are any.
+    pub fn get_world_space_aabb(&self, resources: &SceneResources) -> Result<Option<AABB>, String> {
+        let mut scene_aabb = AABB::default();
+
+        let mut has_entities = false;
+
+        let entity_arena = resources.entity.borrow();
+        let mesh_arena = resources.mesh.borrow();
+
+        self.root.visit(
+            SceneNodeGlobalTraversalMethod::DepthFirst,
+            None,
+            &mut |_current_depth: usize, current_world_transform: Mat4, node: &SceneNode| {
+                if let (SceneNodeType::Entity, Some(handle)) = (node.get_type(), node.get_handle())
+                {
+                    let entity = &entity_arena.get(handle)?.item;
+
+                    let mesh = &mesh_arena.get(&entity.mesh)?.item;
+
+                    for vertex in mesh.aabb.get_vertices() {
+                        let vertex_world_space =
+                            (Vec4::new(vertex, 1.0) * current_world_transform).to_vec3();
+
+                        scene_aabb.grow(&vertex_world_space);
+                    }
+
+                    has_entities = true;
+                }
+
+                Ok(())
+            },
+        )?;
+
+        Ok(has_entities.then(|| AABB::from_min_max(scene_aabb.min, scene_aabb.max)))
+    }
+
+    // Fits the depth range of every active camera that asks for it to the
+    // scene's current bounds.
+    pub fn fit_camera_depth_ranges(&self, resources: &SceneResources) -> Result<(), String> {
+        let mut camera_arena = resources.camera.borrow_mut();
+
+        let mut cameras = camera_arena
+            .entries
+            .iter_mut()
+            .flatten()
+            .map(|entry| &mut entry.item)
+            .filter(|camera| camera.is_active && camera.auto_fit_depth_range)
+            .peekable();
+
+        if cameras.peek().is_none() {
+            return Ok(());
+        }
+
+        if let Some(aabb) = self.get_world_space_aabb(resources)? {
+            for camera in cameras {
+                camera.fit_projection_z_to_bounds(&aabb);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Casts a world-space ray against every entity in the scene, using each
+    // mesh's BVH (built on first use); returns the closest hit position, if any.
+    pub fn pick(&self, resources: &SceneResources, ray: &mut Ray) -> Result<Option<Vec3>, String> {
+        let mut closest_hit: Option<Vec3> = None;
+
+        let entity_arena = resources.entity.borrow();
+        let mut mesh_arena = resources.mesh.borrow_mut();
+
+        self.root.visit(
+            SceneNodeGlobalTraversalMethod::DepthFirst,
+            None,
+            &mut |_current_depth: usize, current_world_transform: Mat4, node: &SceneNode| {
+                if let (SceneNodeType::Entity, Some(handle)) = (node.get_type(), node.get_handle())
+                {
+                    let entity = &entity_arena.get(handle)?.item;
+
+                    let mesh = &mut mesh_arena.get_mut(&entity.mesh)?.item;
+
+                    if mesh.faces.is_empty() {
+                        return Ok(());
+                    }
+
+                    if mesh.static_triangle_bvh.is_none() {
+                        let bvh = StaticTriangleBVH::new(mesh);
+
+                        mesh.static_triangle_bvh.replace(bvh);
+                    }
+
+                    // Only hits closer than `ray.t` are reported.
+
+                    if let Some(bvh) = &mesh.static_triangle_bvh {
+                        if let Some(hit) =
+                            intersect_ray_transformed_bvh(ray, bvh, &current_world_transform)
+                        {
+                            closest_hit.replace(hit);
+                        }
+                    }
+                }
+
+                Ok(())
+            },
+        )?;
+
+        Ok(closest_hit)
+    }
+
+    // Returns the world transform of every entity in the scene (e.g., to detect
+    // whether anything moved between frames).
+    pub fn get_entity_transforms(&self) -> Result<Vec<(Handle, Mat4)>, String> {
+        let mut transforms = vec![];
+
+        self.root.visit(
+            SceneNodeGlobalTraversalMethod::DepthFirst,
+            None,
+            &mut |_current_depth: usize, current_world_transform: Mat4, node: &SceneNode| {
+                if let (SceneNodeType::Entity, Some(handle)) = (node.get_type(), node.get_handle())
+                {
+                    transforms.push((*handle, current_world_transform));
+                }
+
+                Ok(())
+            },
+        )?;
+
+        Ok(transforms)
+    }
+
     pub fn render(
         &self,
         resources: &SceneResources,
@@ -652,6 +792,26 @@ impl SceneGraph {
             )?;
         }
 
+        // Optionally, render opaque entities' depth ahead of shading them.
+
+        let should_render_depth_prepass = renderer_rc
+            .borrow()
+            .get_options()
+            .render_pass_flags
+            .contains(RenderPassFlag::DepthPrepass);
+
+        if should_render_depth_prepass {
+            renderer_rc.borrow_mut().begin_depth_prepass();
+
+            self.root.visit(
+                SceneNodeGlobalTraversalMethod::DepthFirst,
+                Some(SceneNodeLocalTraversalMethod::PostOrder),
+                &mut render_opaque_entities,
+            )?;
+
+            renderer_rc.borrow_mut().end_depth_prepass();
+        }
+
         // Render opaque entities.
 
         self.root.visit(
@@ -802,3 +962,203 @@ impl Display for SceneGraph {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        buffer::framebuffer::Framebuffer,
+        entity::Entity,
+        material::Material,
+        mesh::primitive::cube,
+        render::{
+            options::{RenderPassFlag, RenderPassMask},
+            stats::RenderStats,
+            Renderer,
+        },
+        scene::{
+            camera::Camera,
+            node::{SceneNode, SceneNodeType},
+            resources::SceneResources,
+        },
+        shader::context::ShaderContext,
+        shaders::default_vertex_shader::DEFAULT_VERTEX_SHADER,
+        software_renderer::SoftwareRenderer,
+        transform::Transform3D,
+        vec::vec3::Vec3,
+    };
+
+    use super::SceneGraph;
+
+    #[test]
+    fn depth_prepass_output_matches_with_fewer_shaded_fragments() {
+        let resources: Rc<SceneResources> = Default::default();
+
+        let mut scene = SceneGraph::new();
+
+        // Two cubes, submitted back-to-front so that the nearer one overdraws
+        // the farther one.
+
+        let mesh_handle = resources
+            .mesh
+            .borrow_mut()
+            .insert(cube::generate(2.0, 2.0, 2.0));
+
+        for (z, albedo) in [(3.0, Vec3::ones()), (0.0, Vec3::ones() * 0.5)] {
+            let material_handle = {
+                let mut material = Material::new("cube".to_string());
+
+                material.albedo = albedo;
+
+                resources.material.borrow_mut().insert(material)
+            };
+
+            let entity_handle = resources
+                .entity
+                .borrow_mut()
+                .insert(Entity::new(mesh_handle, Some(material_handle)));
+
+            let mut transform = Transform3D::default();
+
+            transform.set_translation(Vec3 { x: 0.5, y: 0.0, z });
+
+            scene
+                .root
+                .add_child(SceneNode::new(
+                    SceneNodeType::Entity,
+                    transform,
+                    Some(entity_handle),
+                ))
+                .unwrap();
+        }
+
+        let camera = Camera::from_perspective(
+            Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            Default::default(),
+            75.0,
+            1.0,
+        );
+
+        let render = |render_pass_flags: RenderPassMask| -> (Vec<u32>, RenderStats) {
+            let shader_context: Rc<RefCell<ShaderContext>> = Default::default();
+
+            camera.update_shader_context(&mut shader_context.borrow_mut());
+
+            let mut renderer = SoftwareRenderer::new(
+                shader_context,
+                resources.clone(),
+                DEFAULT_VERTEX_SHADER,
+                |_, _, sample| sample.albedo,
+                Default::default(),
+            );
+
+            renderer.options.render_pass_flags = render_pass_flags;
+
+            let mut framebuffer = Framebuffer::new(32, 32);
+
+            framebuffer.complete(0.3, 100.0);
+
+            let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+            renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+            let renderer_rc = RefCell::new(renderer);
+
+            renderer_rc.borrow_mut().begin_frame();
+
+            scene.render(&resources, &renderer_rc, None).unwrap();
+
+            renderer_rc.borrow_mut().end_frame();
+
+            let framebuffer = framebuffer_rc.borrow();
+
+            let color_buffer = framebuffer.attachments.color.as_ref().unwrap().borrow();
+
+            let stats = renderer_rc.borrow().stats;
+
+            (color_buffer.get_all().clone(), stats)
+        };
+
+        let (color, stats) = render(RenderPassMask::default());
+
+        let (prepass_color, prepass_stats) =
+            render(RenderPassMask::default() | RenderPassFlag::DepthPrepass);
+
+        assert_eq!(prepass_color, color);
+
+        assert_eq!(prepass_stats.entities_rendered, stats.entities_rendered);
+
+        assert!(prepass_stats.fragments_shaded < stats.fragments_shaded);
+    }
+
+    #[test]
+    fn fitted_depth_range_encloses_the_scene_bounds() {
+        let resources: Rc<SceneResources> = Default::default();
+
+        let mut scene = SceneGraph::new();
+
+        // Two unit cubes, spanning view-space z=2 to z=50.
+
+        let mesh_handle = resources
+            .mesh
+            .borrow_mut()
+            .insert(cube::generate(1.0, 1.0, 1.0));
+
+        for z in [2.5, 49.5] {
+            let entity_handle = resources
+                .entity
+                .borrow_mut()
+                .insert(Entity::new(mesh_handle, None));
+
+            let mut transform = Transform3D::default();
+
+            transform.set_translation(Vec3 { x: 0.0, y: 0.0, z });
+
+            scene
+                .root
+                .add_child(SceneNode::new(
+                    SceneNodeType::Entity,
+                    transform,
+                    Some(entity_handle),
+                ))
+                .unwrap();
+        }
+
+        let camera_handle = {
+            let mut camera = Camera::from_perspective(
+                Default::default(),
+                Vec3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 1.0,
+                },
+                75.0,
+                1.0,
+            );
+
+            camera.is_active = true;
+            camera.auto_fit_depth_range = true;
+
+            resources.camera.borrow_mut().insert(camera)
+        };
+
+        scene.fit_camera_depth_ranges(&resources).unwrap();
+
+        let camera_arena = resources.camera.borrow();
+
+        let camera = &camera_arena.get(&camera_handle).unwrap().item;
+
+        let (near, far) = (
+            camera.get_projection_z_near(),
+            camera.get_projection_z_far(),
+        );
+
+        assert!((1.8..=2.0).contains(&near), "near = {}", near);
+        assert!((50.0..=55.0).contains(&far), "far = {}", far);
+    }
+}
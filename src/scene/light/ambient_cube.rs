@@ -0,0 +1,163 @@
+use std::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    geometry::accelerator::static_triangle_bvh::StaticTriangleBVH,
+    mesh::Mesh,
+    physics::pbr::sampling::uniform_sample_sphere,
+    random::sequence::hammersley_2d_sequence,
+    texture::cubemap::CubeMap,
+    vec::{
+        vec3::{self, Vec3},
+        vec4::Vec4,
+    },
+};
+
+use super::{
+    directional_light::DirectionalLight,
+    irradiance_probe_grid::{get_bounced_radiance, IrradianceProbeBakeOptions},
+    point_light::PointLight,
+};
+
+// Order of the cube's faces (and its colors).
+pub static AMBIENT_CUBE_AXES: [Vec3; 6] = [
+    // +X
+    vec3::RIGHT,
+    // -X
+    Vec3 {
+        x: -1.0,
+        y: 0.0,
+        z: 0.0,
+    },
+    // +Y
+    vec3::UP,
+    // -Y
+    Vec3 {
+        x: 0.0,
+        y: -1.0,
+        z: 0.0,
+    },
+    // +Z
+    vec3::FORWARD,
+    // -Z
+    Vec3 {
+        x: 0.0,
+        y: 0.0,
+        z: -1.0,
+    },
+];
+
+// The irradiance arriving at surfaces facing each of the 6 world axes; a
+// cheaper (and coarser) alternative to a spherical harmonics probe.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmbientCube {
+    pub colors: [Vec3; 6],
+}
+
+impl AmbientCube {
+    // Integrates `radiance` (a function of direction) over the hemisphere
+    // around each axis, using `sample_count` (uniformly distributed)
+    // directions.
+    pub fn project<F>(sample_count: u32, mut radiance: F) -> Self
+    where
+        F: FnMut(Vec3) -> Vec3,
+    {
+        let mut result = Self::default();
+
+        let one_over_n = 1.0 / sample_count.max(1) as f32;
+
+        let solid_angle = 4.0 * PI * one_over_n;
+
+        for i in 0..sample_count {
+            let direction = uniform_sample_sphere(hammersley_2d_sequence(i, one_over_n));
+
+            let radiance = radiance(direction);
+
+            for (color, axis) in result.colors.iter_mut().zip(AMBIENT_CUBE_AXES) {
+                let likeness = direction.dot(axis);
+
+                if likeness > 0.0 {
+                    *color += radiance * (likeness * solid_angle);
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn from_cubemap(cubemap: &CubeMap<Vec3>, sample_count: u32) -> Self {
+        Self::project(sample_count, |direction| {
+            cubemap.sample_nearest(&Vec4::new(direction, 0.0), None)
+        })
+    }
+
+    // Gathers the light arriving at `position`, reflected (once) off of the
+    // surfaces of `mesh`, or arriving from the sky; see
+    // `IrradianceProbeGrid::bake()`.
+    pub fn bake(
+        position: Vec3,
+        mesh: &Mesh,
+        directional_lights: &[DirectionalLight],
+        point_lights: &[PointLight],
+        options: &IrradianceProbeBakeOptions,
+    ) -> Self {
+        let bvh = StaticTriangleBVH::new(mesh);
+
+        Self::project(options.sample_count, |direction| {
+            get_bounced_radiance(
+                mesh,
+                &bvh,
+                position,
+                direction,
+                directional_lights,
+                point_lights,
+                options,
+            )
+        })
+    }
+
+    // Blends the 3 faces that `normal` leans towards, by the square of each
+    // of its components.
+    pub fn sample_irradiance(&self, normal: &Vec3) -> Vec3 {
+        let weights = *normal * *normal;
+
+        let x = if normal.x >= 0.0 { 0 } else { 1 };
+        let y = if normal.y >= 0.0 { 2 } else { 3 };
+        let z = if normal.z >= 0.0 { 4 } else { 5 };
+
+        self.colors[x] * weights.x + self.colors[y] * weights.y + self.colors[z] * weights.z
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::FRAC_1_SQRT_2;
+
+    use crate::vec::vec3::{self, Vec3};
+
+    use super::AmbientCube;
+
+    #[test]
+    fn sampling_blends_the_faces_a_normal_leans_towards() {
+        let mut cube = AmbientCube::default();
+
+        for (i, color) in cube.colors.iter_mut().enumerate() {
+            *color = vec3::ONES * (i + 1) as f32;
+        }
+
+        let (positive_x, positive_y) = (cube.colors[0], cube.colors[2]);
+
+        assert_eq!(cube.sample_irradiance(&vec3::UP), positive_y);
+
+        let normal = Vec3 {
+            x: FRAC_1_SQRT_2,
+            y: FRAC_1_SQRT_2,
+            z: 0.0,
+        };
+
+        let blended = cube.sample_irradiance(&normal);
+
+        assert!((blended - (positive_x + positive_y) / 2.0).mag() < 0.0001);
+    }
+}
@@ -38,7 +38,10 @@ use crate::{
 
 use super::{
     contribute_pbr_world_space,
-    shadow::{ShadowMapRenderingContext, SHADOW_MAP_CAMERA_NEAR},
+    shadow::{
+        get_dithered_shadow_tap_offset, ShadowFilter, ShadowMapDependencies,
+        ShadowMapRenderingContext, ShadowMapUpdatePolicy, SHADOW_MAP_CAMERA_NEAR,
+    },
 };
 
 pub const SHADOW_MAP_CAMERA_COUNT: usize = 3;
@@ -54,6 +57,15 @@ pub struct DirectionalLight {
     pub shadow_map_cameras: Option<Vec<(f32, Camera)>>,
     #[serde(skip)]
     pub shadow_map_rendering_context: Option<ShadowMapRenderingContext>,
+    #[serde(default)]
+    pub shadow_map_update_policy: ShadowMapUpdatePolicy,
+    // Takes a single, per-pixel dithered shadow map tap instead of filtering
+    // with `shadow_filter`; cheaper, but relies on temporal accumulation to
+    // smooth edges.
+    #[serde(default)]
+    pub dithered_shadows: bool,
+    #[serde(default)]
+    pub shadow_filter: ShadowFilter,
 }
 
 impl Default for DirectionalLight {
@@ -65,6 +77,9 @@ impl Default for DirectionalLight {
             shadow_maps: None,
             shadow_map_cameras: None,
             shadow_map_rendering_context: None,
+            shadow_map_update_policy: Default::default(),
+            dithered_shadows: false,
+            shadow_filter: Default::default(),
         };
 
         result.set_direction(Quaternion::new((vec3::RIGHT).as_normal(), -PI / 4.0));
@@ -116,15 +131,7 @@ impl DirectionalLight {
             scene_resources.clone(),
         );
 
-        let (width, height) = (shadow_map_size, shadow_map_size);
-
-        let mut blank_texture = TextureMap::<f32>::from_buffer(
-            width,
-            height,
-            Buffer2D::<f32>::new(width, height, None),
-        );
-
-        blank_texture.sampling_options.wrapping = TextureMapWrapping::ClampToEdge;
+        let blank_texture = make_blank_shadow_map(shadow_map_size);
 
         let mut handles: Vec<Handle> = vec![];
 
@@ -142,6 +149,44 @@ impl DirectionalLight {
             .replace(shadow_map_rendering_context);
     }
 
+    pub fn get_shadow_map_resolution(&self) -> Option<u32> {
+        self.shadow_map_rendering_context
+            .as_ref()
+            .map(|context| context.get_shadow_map_size())
+    }
+
+    // Re-allocates this light's (enabled) shadow maps at a new, power-of-two,
+    // resolution.
+    pub fn set_shadow_map_resolution(
+        &mut self,
+        resolution: u32,
+        scene_resources: &SceneResources,
+    ) -> Result<(), String> {
+        if !resolution.is_power_of_two() {
+            return Err(format!(
+                "Shadow map resolution must be a power of two (got {}).",
+                resolution
+            ));
+        }
+
+        let (Some(handles), Some(rendering_context)) = (
+            self.shadow_maps.as_ref(),
+            self.shadow_map_rendering_context.as_mut(),
+        ) else {
+            return Err("Shadow maps are not enabled for this light.".to_string());
+        };
+
+        let mut texture_f32_arena = scene_resources.texture_f32.borrow_mut();
+
+        for handle in handles {
+            texture_f32_arena.get_mut(handle)?.item = make_blank_shadow_map(resolution);
+        }
+
+        rendering_context.resize(resolution);
+
+        Ok(())
+    }
+
     pub fn update_shadow_maps(
         &mut self,
         resources: &SceneResources,
@@ -150,8 +195,24 @@ impl DirectionalLight {
         if let (Some(handles), Some(cameras), Some(rendering_context)) = (
             self.shadow_maps.as_ref(),
             self.shadow_map_cameras.as_ref(),
-            self.shadow_map_rendering_context.as_ref(),
+            self.shadow_map_rendering_context.as_mut(),
         ) {
+            // Reuse the current shadow maps, if our update policy allows it.
+
+            let dependencies = ShadowMapDependencies {
+                light_views: cameras
+                    .iter()
+                    .map(|(_far_z, camera)| {
+                        camera.get_view_inverse_transform() * camera.get_projection()
+                    })
+                    .collect(),
+                entity_transforms: scene.get_entity_transforms()?,
+            };
+
+            if !rendering_context.should_update(self.shadow_map_update_policy, dependencies) {
+                return Ok(());
+            }
+
             let mut texture_f32_arena = resources.texture_f32.borrow_mut();
 
             for (depth_index, (_far_z, camera)) in cameras.iter().enumerate() {
@@ -255,6 +316,8 @@ impl DirectionalLight {
 
         let frustum = view_camera.get_frustum();
 
+        let shadow_map_resolution = self.get_shadow_map_resolution();
+
         let subfrustum_cameras: Vec<(f32, Camera)> = (0..SHADOW_MAP_CAMERA_COUNT)
             .map(|subfrustum_index| {
                 let near_alpha = alpha_step * subfrustum_index as f32;
@@ -306,6 +369,20 @@ impl DirectionalLight {
                         max_z = max_z.max(view_space_coord.z);
                     }
 
+                    // Snaps the extent to whole shadow map texels, to keep
+                    // edges from shimmering as the view camera moves.
+
+                    if let Some(resolution) = shadow_map_resolution {
+                        let texel_size = (max_x - min_x).max(max_y - min_y) / resolution as f32;
+
+                        if texel_size > 0.0 {
+                            min_x = (min_x / texel_size).floor() * texel_size;
+                            max_x = (max_x / texel_size).ceil() * texel_size;
+                            min_y = (min_y / texel_size).floor() * texel_size;
+                            max_y = (max_y / texel_size).ceil() * texel_size;
+                        }
+                    }
+
                     CameraOrthographicExtent {
                         left: min_x,
                         right: max_x,
@@ -362,7 +439,8 @@ impl DirectionalLight {
             self.get_shadowing(sample, texture_f32_arena, context, maps)
         } else {
             0.0
-        };
+        }
+        .max(sample.contact_shadow);
 
         let intensity = self.intensities;
 
@@ -377,16 +455,45 @@ impl DirectionalLight {
         contribution * (1.0 - in_shadow)
     }
 
-    fn pcf_3x3(
+    // Returns 1.0 if the fragment lies behind the shadow map's depth at `uv`,
+    // or 0.0 otherwise.
+    fn shadow_tap(current_depth_ndc_space: f32, map: &TextureMap<f32>, uv: Vec2) -> f32 {
+        if uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0 {
+            return 0.0;
+        }
+
+        let closest_depth_ndc_space = sample_nearest_f32(uv, map);
+
+        if closest_depth_ndc_space == 0.0 {
+            return 0.0;
+        }
+
+        let bias = 0.0025;
+
+        if current_depth_ndc_space - bias > closest_depth_ndc_space {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    // Averages the shadow taps over a `kernel`-by-`kernel` neighborhood of
+    // texels around `uv`.
+    fn pcf(
         current_depth_ndc_space: f32,
         map: &TextureMap<f32>,
         texel_size: f32,
         uv: Vec2,
+        kernel: u8,
     ) -> f32 {
+        let kernel = kernel.max(1) as i32;
+
+        let (start, end) = (-(kernel / 2), (kernel - 1) / 2);
+
         let mut shadow = 0.0;
 
-        for y in -1..1 {
-            for x in -1..1 {
+        for y in start..=end {
+            for x in start..=end {
                 let perturbed_uv = uv
                     + Vec2 {
                         x: x as f32,
@@ -394,34 +501,16 @@ impl DirectionalLight {
                         z: 0.0,
                     } * texel_size;
 
-                if perturbed_uv.x < 0.0
-                    || perturbed_uv.x > 1.0
-                    || perturbed_uv.y < 0.0
-                    || perturbed_uv.y > 1.0
-                {
-                    continue;
-                }
-
-                let closest_depth_ndc_space = sample_nearest_f32(perturbed_uv, map);
-
-                if closest_depth_ndc_space == 0.0 {
-                    continue;
-                }
-
-                let bias = 0.0025;
-
-                let is_in_shadow = current_depth_ndc_space - bias > closest_depth_ndc_space;
-
-                if is_in_shadow {
-                    shadow += 1.0;
-                }
+                shadow += Self::shadow_tap(current_depth_ndc_space, map, perturbed_uv);
             }
         }
 
-        shadow / 9.0
+        shadow / (kernel * kernel) as f32
     }
 
-    fn poisson_3x3(
+    // Sums a (2x2) block of taps at each of 4 Poisson disk offsets, weighted
+    // as 3x3 blocks; kept as-is so that existing scenes keep their shadows.
+    fn poisson(
         current_depth_ndc_space: f32,
         map: &TextureMap<f32>,
         texel_size: f32,
@@ -455,15 +544,57 @@ impl DirectionalLight {
         for sample in &POISSON_DISK_SAMPLES {
             let poisson_uv = uv + (*sample / 700.0);
 
-            shadow += Self::pcf_3x3(current_depth_ndc_space, map, texel_size, poisson_uv);
+            for y in -1..1 {
+                for x in -1..1 {
+                    let perturbed_uv = poisson_uv
+                        + Vec2 {
+                            x: x as f32,
+                            y: y as f32,
+                            z: 0.0,
+                        } * texel_size;
+
+                    shadow += Self::shadow_tap(current_depth_ndc_space, map, perturbed_uv);
+                }
+            }
         }
 
-        shadow / POISSON_DISK_SAMPLES.len() as f32
+        shadow / (9.0 * POISSON_DISK_SAMPLES.len() as f32)
+    }
+
+    fn dithered_single_tap(
+        current_depth_ndc_space: f32,
+        map: &TextureMap<f32>,
+        texel_size: f32,
+        uv: Vec2,
+        screen_pixel: (u32, u32),
+        frame_index: u32,
+    ) -> f32 {
+        let (x, y) = screen_pixel;
+
+        let perturbed_uv = uv + get_dithered_shadow_tap_offset(x, y, frame_index) * texel_size;
+
+        Self::shadow_tap(current_depth_ndc_space, map, perturbed_uv)
+    }
+
+    fn get_screen_pixel(sample: &GeometrySample, context: &ShaderContext) -> (u32, u32) {
+        let position_projection_space = Vec4::new(sample.position_world_space, 1.0)
+            * context.view_inverse_transform
+            * context.get_projection();
+
+        let uv = (position_projection_space.to_vec3() / position_projection_space.w).ndc_to_uv();
+
+        let resolution = context.get_resolution();
+
+        (
+            (uv.x * resolution.x).max(0.0) as u32,
+            ((1.0 - uv.y) * resolution.y).max(0.0) as u32,
+        )
     }
 
     fn get_shadowing_for_map(
         &self,
         sample: &GeometrySample,
+        context: &ShaderContext,
         map: &TextureMap<f32>,
         transform: &Mat4,
     ) -> f32 {
@@ -475,11 +606,38 @@ impl DirectionalLight {
 
         let current_depth_ndc_space = sample_position_light_ndc_space.z;
 
-        let texel_size = 1.0 / map.width as f32;
+        // Matches the UV-to-texel mapping used by `sample_nearest_f32()`.
+
+        let texel_size = 1.0 / (map.width - 1).max(1) as f32;
 
         let uv = sample_position_light_ndc_space.ndc_to_uv();
 
-        Self::poisson_3x3(current_depth_ndc_space, map, texel_size, uv)
+        if self.dithered_shadows {
+            let screen_pixel = Self::get_screen_pixel(sample, context);
+
+            return Self::dithered_single_tap(
+                current_depth_ndc_space,
+                map,
+                texel_size,
+                uv,
+                screen_pixel,
+                context.frame_index,
+            );
+        }
+
+        match self.shadow_filter {
+            ShadowFilter::None => Self::shadow_tap(current_depth_ndc_space, map, uv),
+            ShadowFilter::Poisson => {
+                // Offsets by whole (not UV-mapped) texels, as it always has.
+
+                let texel_size = 1.0 / map.width as f32;
+
+                Self::poisson(current_depth_ndc_space, map, texel_size, uv)
+            }
+            ShadowFilter::Pcf { kernel } => {
+                Self::pcf(current_depth_ndc_space, map, texel_size, uv, kernel)
+            }
+        }
     }
 
     fn get_shadowing(
@@ -517,7 +675,7 @@ impl DirectionalLight {
 
                     let transform = &transforms[index].1;
 
-                    self.get_shadowing_for_map(sample, map, transform)
+                    self.get_shadowing_for_map(sample, context, map, transform)
                 } else {
                     0.0
                 }
@@ -526,3 +684,120 @@ impl DirectionalLight {
         }
     }
 }
+
+fn make_blank_shadow_map(shadow_map_size: u32) -> TextureMap<f32> {
+    let (width, height) = (shadow_map_size, shadow_map_size);
+
+    let mut blank_texture =
+        TextureMap::<f32>::from_buffer(width, height, Buffer2D::<f32>::new(width, height, None));
+
+    blank_texture.sampling_options.wrapping = TextureMapWrapping::ClampToEdge;
+
+    blank_texture
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use crate::{
+        buffer::Buffer2D, scene::resources::SceneResources, texture::map::TextureMap,
+        vec::vec2::Vec2,
+    };
+
+    use super::DirectionalLight;
+
+    #[test]
+    fn pcf_averages_a_kernel_sized_neighborhood_of_taps() {
+        // A 9x9 shadow map, with a single occluding texel at its center.
+
+        let mut buffer = Buffer2D::<f32>::new(9, 9, Some(1.0));
+
+        buffer.set(4, 4, 0.1);
+
+        let map = TextureMap::<f32>::from_buffer(9, 9, buffer);
+
+        let texel_size = 1.0 / 8.0;
+
+        let center_uv = Vec2 {
+            x: 0.5,
+            y: 0.5,
+            z: 0.0,
+        };
+
+        let shadow = |kernel: u8| DirectionalLight::pcf(0.5, &map, texel_size, center_uv, kernel);
+
+        // Exactly one of the kernel's taps is in shadow.
+
+        assert_eq!(shadow(1), 1.0);
+        assert_eq!(shadow(3), 1.0 / 9.0);
+        assert_eq!(shadow(5), 1.0 / 25.0);
+
+        // Off-center, the occluder falls outside of a 3x3 kernel.
+
+        let shadow_off_center = DirectionalLight::pcf(
+            0.5,
+            &map,
+            texel_size,
+            center_uv
+                + Vec2 {
+                    x: texel_size * 2.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            3,
+        );
+
+        assert_eq!(shadow_off_center, 0.0);
+    }
+
+    #[test]
+    fn shadow_maps_are_reallocated_at_a_new_resolution() {
+        let resources: Rc<SceneResources> = Default::default();
+
+        let mut light = DirectionalLight::default();
+
+        // Shadow maps must be enabled first.
+
+        assert!(light.set_shadow_map_resolution(512, &resources).is_err());
+
+        light.enable_shadow_maps(1024, 100.0, resources.clone());
+
+        let map_sizes = |light: &DirectionalLight| -> Vec<(u32, u32, usize)> {
+            let texture_f32_arena = resources.texture_f32.borrow();
+
+            light
+                .shadow_maps
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|handle| {
+                    let map = &texture_f32_arena.get(handle).unwrap().item;
+
+                    (map.width, map.height, map.levels[0].0.data.len())
+                })
+                .collect()
+        };
+
+        for resolution in [512, 2048] {
+            light
+                .set_shadow_map_resolution(resolution, &resources)
+                .unwrap();
+
+            for size in map_sizes(&light) {
+                assert_eq!(
+                    size,
+                    (resolution, resolution, (resolution * resolution) as usize)
+                );
+            }
+
+            assert_eq!(light.get_shadow_map_resolution(), Some(resolution));
+        }
+
+        // Non-power-of-two resolutions are rejected.
+
+        assert!(light.set_shadow_map_resolution(1000, &resources).is_err());
+
+        assert_eq!(light.get_shadow_map_resolution(), Some(2048));
+    }
+}
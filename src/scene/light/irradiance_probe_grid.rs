@@ -0,0 +1,265 @@
+use std::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    animation::lerp,
+    geometry::{
+        accelerator::static_triangle_bvh::StaticTriangleBVH,
+        intersect::intersect_ray_bvh,
+        primitives::{aabb::AABB, ray::Ray},
+    },
+    mesh::Mesh,
+    physics::pbr::{
+        bake::lightmap::get_direct_irradiance, spherical_harmonics::SphericalHarmonics,
+    },
+    vec::vec3::{self, Vec3},
+};
+
+use super::{directional_light::DirectionalLight, point_light::PointLight};
+
+#[derive(Debug, Copy, Clone)]
+pub struct IrradianceProbeBakeOptions {
+    // Number of rays gathering light, per probe.
+    pub sample_count: u32,
+    // Radiance arriving from directions that escape the scene.
+    pub sky_radiance: Vec3,
+    // Fraction of the incoming light that surfaces reflect towards the probes.
+    pub albedo: Vec3,
+}
+
+impl Default for IrradianceProbeBakeOptions {
+    fn default() -> Self {
+        Self {
+            sample_count: 256,
+            sky_radiance: Default::default(),
+            albedo: vec3::ONES * 0.5,
+        }
+    }
+}
+
+// A regular 3D grid of light probes, spanning `aabb`, each storing the radiance
+// arriving at its position (as spherical harmonics); dynamic entities, which
+// can't use lightmaps, interpolate between the nearest probes for their
+// ambient (indirect) light.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IrradianceProbeGrid {
+    pub aabb: AABB,
+    // Number of probes along each axis.
+    pub resolution: (u32, u32, u32),
+    pub probes: Vec<SphericalHarmonics>,
+}
+
+impl IrradianceProbeGrid {
+    pub fn new(aabb: AABB, resolution: (u32, u32, u32)) -> Self {
+        let resolution = (
+            resolution.0.max(1),
+            resolution.1.max(1),
+            resolution.2.max(1),
+        );
+
+        let probe_count = (resolution.0 * resolution.1 * resolution.2) as usize;
+
+        Self {
+            aabb,
+            resolution,
+            probes: vec![Default::default(); probe_count],
+        }
+    }
+
+    fn get_index(&self, x: u32, y: u32, z: u32) -> usize {
+        ((z * self.resolution.1 + y) * self.resolution.0 + x) as usize
+    }
+
+    pub fn get_probe_position(&self, x: u32, y: u32, z: u32) -> Vec3 {
+        let alpha = |index: u32, count: u32| {
+            if count > 1 {
+                index as f32 / (count - 1) as f32
+            } else {
+                0.5
+            }
+        };
+
+        let (min, max) = (self.aabb.min, self.aabb.max);
+
+        Vec3 {
+            x: lerp(min.x, max.x, alpha(x, self.resolution.0)),
+            y: lerp(min.y, max.y, alpha(y, self.resolution.1)),
+            z: lerp(min.z, max.z, alpha(z, self.resolution.2)),
+        }
+    }
+
+    // Trilinearly interpolates the 8 probes surrounding `position` (clamped to
+    // the grid's bounds).
+    pub fn get_interpolated(&self, position: &Vec3) -> SphericalHarmonics {
+        let (min, max) = (self.aabb.min, self.aabb.max);
+
+        // Fractional probe coordinates, and the lower probe index, per axis.
+
+        let get_cell = |p: f32, min: f32, max: f32, count: u32| -> (u32, f32) {
+            if count < 2 || max <= min {
+                return (0, 0.0);
+            }
+
+            let t = ((p - min) / (max - min)).clamp(0.0, 1.0) * (count - 1) as f32;
+
+            let index = (t.floor() as u32).min(count - 2);
+
+            (index, t - index as f32)
+        };
+
+        let (x, alpha_x) = get_cell(position.x, min.x, max.x, self.resolution.0);
+        let (y, alpha_y) = get_cell(position.y, min.y, max.y, self.resolution.1);
+        let (z, alpha_z) = get_cell(position.z, min.z, max.z, self.resolution.2);
+
+        let get_probe = |dx: u32, dy: u32, dz: u32| -> SphericalHarmonics {
+            let x = (x + dx).min(self.resolution.0 - 1);
+            let y = (y + dy).min(self.resolution.1 - 1);
+            let z = (z + dz).min(self.resolution.2 - 1);
+
+            self.probes[self.get_index(x, y, z)]
+        };
+
+        let lerp_x = |dy: u32, dz: u32| lerp(get_probe(0, dy, dz), get_probe(1, dy, dz), alpha_x);
+
+        let lerp_y = |dz: u32| lerp(lerp_x(0, dz), lerp_x(1, dz), alpha_y);
+
+        lerp(lerp_y(0), lerp_y(1), alpha_z)
+    }
+
+    pub fn sample_irradiance(&self, position: &Vec3, normal: &Vec3) -> Vec3 {
+        self.get_interpolated(position).evaluate_irradiance(normal)
+    }
+
+    // Gathers the light arriving at each probe, reflected (once) off of the
+    // surfaces of `mesh`, or arriving from the sky; the lights' direct
+    // contributions are left to the renderer. Positions are in world space.
+    pub fn bake(
+        &mut self,
+        mesh: &Mesh,
+        directional_lights: &[DirectionalLight],
+        point_lights: &[PointLight],
+        options: &IrradianceProbeBakeOptions,
+    ) {
+        let bvh = StaticTriangleBVH::new(mesh);
+
+        for z in 0..self.resolution.2 {
+            for y in 0..self.resolution.1 {
+                for x in 0..self.resolution.0 {
+                    let origin = self.get_probe_position(x, y, z);
+
+                    let probe = SphericalHarmonics::project(options.sample_count, |direction| {
+                        get_bounced_radiance(
+                            mesh,
+                            &bvh,
+                            origin,
+                            direction,
+                            directional_lights,
+                            point_lights,
+                            options,
+                        )
+                    });
+
+                    let index = self.get_index(x, y, z);
+
+                    self.probes[index] = probe;
+                }
+            }
+        }
+    }
+}
+
+// Radiance arriving at `origin` from `direction`, reflected (once) off of the
+// surfaces of `mesh`, or arriving from the sky.
+pub(in crate::scene::light) fn get_bounced_radiance(
+    mesh: &Mesh,
+    bvh: &StaticTriangleBVH,
+    origin: Vec3,
+    direction: Vec3,
+    directional_lights: &[DirectionalLight],
+    point_lights: &[PointLight],
+    options: &IrradianceProbeBakeOptions,
+) -> Vec3 {
+    let mut ray = Ray::new(origin, direction);
+
+    intersect_ray_bvh(&mut ray, bvh);
+
+    let Some(face_index) = ray.triangle else {
+        return options.sky_radiance;
+    };
+
+    let vertices = &mesh.geometry.vertices;
+
+    let face = &mesh.faces[face_index];
+
+    let (v0, v1, v2) = (
+        vertices[face.vertices[0]],
+        vertices[face.vertices[1]],
+        vertices[face.vertices[2]],
+    );
+
+    // Light leaves the side of the surface facing the origin.
+
+    let normal = {
+        let normal = (v1 - v0).cross(v2 - v0).as_normal();
+
+        if normal.dot(direction) > 0.0 {
+            -normal
+        } else {
+            normal
+        }
+    };
+
+    let hit_position = origin + direction * ray.t;
+
+    let irradiance =
+        get_direct_irradiance(bvh, hit_position, normal, directional_lights, point_lights);
+
+    // Diffuse (Lambertian) reflection.
+
+    options.albedo * irradiance / PI
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        geometry::primitives::aabb::AABB, physics::pbr::spherical_harmonics::SphericalHarmonics,
+        vec::vec3::Vec3,
+    };
+
+    use super::IrradianceProbeGrid;
+
+    #[test]
+    fn probes_are_interpolated_between_grid_points() {
+        let aabb = AABB::from_min_max(Vec3::default(), Vec3::ones() * 2.0);
+
+        let mut grid = IrradianceProbeGrid::new(aabb, (2, 2, 2));
+
+        let brightness = |x: u32| Vec3::ones() * (x as f32 + 1.0);
+
+        // Probes on the left see a dim ambient; those on the right, brighter.
+
+        for z in 0..2 {
+            for y in 0..2 {
+                for x in 0..2 {
+                    let index = grid.get_index(x, y, z);
+
+                    grid.probes[index] = SphericalHarmonics::project(256, |_| brightness(x));
+                }
+            }
+        }
+
+        let up = Vec3 {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+
+        let left = grid.sample_irradiance(&Vec3::default(), &up);
+        let middle = grid.sample_irradiance(&(aabb.max / 2.0), &up);
+        let right = grid.sample_irradiance(&aabb.max, &up);
+
+        assert!((middle - (left + right) / 2.0).mag() < 0.001);
+        assert!(left.x < middle.x && middle.x < right.x);
+    }
+}
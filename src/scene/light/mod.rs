@@ -9,9 +9,11 @@ use crate::{
     },
 };
 
+pub mod ambient_cube;
 pub mod ambient_light;
 pub mod attenuation;
 pub mod directional_light;
+pub mod irradiance_probe_grid;
 pub mod point_light;
 pub mod spot_light;
 
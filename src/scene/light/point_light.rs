@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     buffer::Buffer2D,
+    matrix::Mat4,
     render::{culling::FaceCullingReject, Renderer},
     resource::handle::Handle,
     scene::{
@@ -32,7 +33,10 @@ use crate::{
 use super::{
     attenuation::LightAttenuation,
     contribute_pbr_tangent_space,
-    shadow::{ShadowMapRenderingContext, SHADOW_MAP_CAMERA_NEAR},
+    shadow::{
+        ShadowMapDependencies, ShadowMapRenderingContext, ShadowMapUpdatePolicy,
+        SHADOW_MAP_CAMERA_NEAR,
+    },
 };
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +48,8 @@ pub struct PointLight {
     pub shadow_map: Option<Handle>,
     #[serde(skip)]
     pub shadow_map_rendering_context: Option<ShadowMapRenderingContext>,
+    #[serde(default)]
+    pub shadow_map_update_policy: ShadowMapUpdatePolicy,
     #[serde(skip)]
     pub influence_distance: f32,
 }
@@ -76,6 +82,7 @@ impl PointLight {
             attenuation: LightAttenuation::new(1.0, 0.35, 0.44),
             shadow_map: None,
             shadow_map_rendering_context: None,
+            shadow_map_update_policy: Default::default(),
             influence_distance: 0.0,
         };
 
@@ -144,9 +151,20 @@ impl PointLight {
         let rendering_context = if self.shadow_map_rendering_context.is_none() {
             return Err("Called PointLight::update_shadow_map() on a light with no shadow map rendering context created!".to_string());
         } else {
-            self.shadow_map_rendering_context.as_ref().unwrap()
+            self.shadow_map_rendering_context.as_mut().unwrap()
+        };
+
+        // Reuse the current shadow map, if our update policy allows it.
+
+        let dependencies = ShadowMapDependencies {
+            light_views: vec![Mat4::translation(self.position)],
+            entity_transforms: scene.get_entity_transforms()?,
         };
 
+        if !rendering_context.should_update(self.shadow_map_update_policy, dependencies) {
+            return Ok(());
+        }
+
         {
             let mut shader_context = rendering_context.shader_context.borrow_mut();
 
@@ -592,3 +610,92 @@ fn blit_hdr_attachment_to_cubemap_side(
         buffer.set_at(index, hdr_color.x);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use crate::{
+        entity::Entity,
+        mesh::primitive::cube,
+        scene::{
+            graph::SceneGraph,
+            light::shadow::ShadowMapUpdatePolicy,
+            node::{SceneNode, SceneNodeType},
+            resources::SceneResources,
+        },
+        vec::vec3::Vec3,
+    };
+
+    use super::PointLight;
+
+    #[test]
+    fn on_change_shadow_maps_are_reused_until_something_moves() {
+        let resources: Rc<SceneResources> = Default::default();
+
+        let entity_handle = {
+            let mesh_handle = resources
+                .mesh
+                .borrow_mut()
+                .insert(cube::generate(1.0, 1.0, 1.0));
+
+            resources
+                .entity
+                .borrow_mut()
+                .insert(Entity::new(mesh_handle, None))
+        };
+
+        let mut scene = SceneGraph::new();
+
+        scene
+            .root
+            .add_child(SceneNode::new(
+                SceneNodeType::Entity,
+                Default::default(),
+                Some(entity_handle),
+            ))
+            .unwrap();
+
+        let mut light = PointLight::new();
+
+        light.shadow_map_update_policy = ShadowMapUpdatePolicy::OnChange;
+
+        light.enable_shadow_maps(4, 20.0, resources.clone());
+
+        let update = |light: &mut PointLight, scene: &SceneGraph| -> u32 {
+            light.update_shadow_map(&resources, scene).unwrap();
+
+            light
+                .shadow_map_rendering_context
+                .as_ref()
+                .unwrap()
+                .get_frames_since_update()
+        };
+
+        // The first update always renders.
+
+        assert_eq!(update(&mut light, &scene), 0);
+
+        // Nothing moved, so the shadow map is reused.
+
+        assert_eq!(update(&mut light, &scene), 1);
+        assert_eq!(update(&mut light, &scene), 2);
+
+        // Moving an entity, or the light itself, re-renders the shadow map.
+
+        scene.root.children_mut().as_mut().unwrap()[0]
+            .get_transform_mut()
+            .set_translation(Vec3 {
+                x: 2.0,
+                y: 0.0,
+                z: 0.0,
+            });
+
+        assert_eq!(update(&mut light, &scene), 0);
+        assert_eq!(update(&mut light, &scene), 1);
+
+        light.position.x += 1.0;
+
+        assert_eq!(update(&mut light, &scene), 0);
+    }
+}
@@ -1,25 +1,92 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, f32::consts::TAU, rc::Rc};
+
+use serde::{Deserialize, Serialize};
 
 use crate::{
     buffer::framebuffer::Framebuffer,
+    matrix::Mat4,
     render::culling::FaceCullingReject,
+    resource::handle::Handle,
     scene::resources::SceneResources,
     shader::{
         context::ShaderContext, fragment::FragmentShaderFn, geometry::GeometryShaderFn,
         vertex::VertexShaderFn,
     },
     software_renderer::SoftwareRenderer,
+    vec::vec2::Vec2,
 };
 
 pub static SHADOW_MAP_CAMERA_NEAR: f32 = 0.05;
 pub static DEFAULT_SHADOW_MAP_CAMERA_FAR: f32 = 1000.0;
 
+// Ordered dither thresholds, in [0, 16), for a 4x4 block of screen pixels.
+pub static BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+// Radius (in shadow map texels) of the circle of dithered tap offsets.
+static DITHERED_SHADOW_TAP_RADIUS: f32 = 1.5;
+
+pub fn get_bayer_4x4_threshold(x: u32, y: u32) -> f32 {
+    (BAYER_4X4[(y % 4) as usize][(x % 4) as usize] as f32 + 0.5) / 16.0
+}
+
+// Offset (in texels) of the single shadow map tap taken for a screen pixel.
+// Each pixel in a 4x4 block takes its tap from a different point on a circle;
+// each frame rotates every pixel's point one step further around the circle,
+// so that temporal accumulation (e.g., TAA) averages all 16 into a soft edge.
+pub fn get_dithered_shadow_tap_offset(x: u32, y: u32, frame_index: u32) -> Vec2 {
+    let rotation = (frame_index % 16) as f32 / 16.0;
+
+    let theta = (get_bayer_4x4_threshold(x, y) + rotation) * TAU;
+
+    Vec2 {
+        x: theta.cos(),
+        y: theta.sin(),
+        z: 0.0,
+    } * DITHERED_SHADOW_TAP_RADIUS
+}
+
+// How often a light re-renders its shadow map(s).
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ShadowMapUpdatePolicy {
+    #[default]
+    EveryFrame,
+    // Only when the light, or an entity in the scene, has moved.
+    OnChange,
+    EveryNFrames(u32),
+}
+
+// How a light's shadow map is sampled for each shaded fragment.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ShadowFilter {
+    // A single (nearest) tap; hard, aliased edges.
+    #[default]
+    None,
+    // Small blocks of taps around 4 Poisson disk offsets; the soft (and
+    // partial) shadowing that lights had before filters were configurable.
+    Poisson,
+    // Percentage-closer filtering; averages the taps over a `kernel`-by-`kernel`
+    // neighborhood of shadow map texels.
+    Pcf {
+        kernel: u8,
+    },
+}
+
+// Everything that a light's shadow map(s) depend on: the light's view(s) of
+// the scene, and the world transform of every entity in it.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ShadowMapDependencies {
+    pub light_views: Vec<Mat4>,
+    pub entity_transforms: Vec<(Handle, Mat4)>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ShadowMapRenderingContext {
     pub projection_z_far: f32,
     pub framebuffer: Rc<RefCell<Framebuffer>>,
     pub shader_context: Rc<RefCell<ShaderContext>>,
     pub renderer: RefCell<SoftwareRenderer>,
+    frames_since_update: u32,
+    last_dependencies: Option<ShadowMapDependencies>,
 }
 
 impl ShadowMapRenderingContext {
@@ -79,6 +146,113 @@ impl ShadowMapRenderingContext {
             renderer,
             shader_context,
             framebuffer,
+            frames_since_update: 0,
+            last_dependencies: None,
+        }
+    }
+
+    pub fn get_shadow_map_size(&self) -> u32 {
+        self.framebuffer.borrow().width
+    }
+
+    // Re-allocates our framebuffer (and the renderer's attachments) at a new
+    // size; the next update always re-renders the shadow map(s).
+    pub fn resize(&mut self, shadow_map_size: u32) {
+        let mut framebuffer = Framebuffer::new(shadow_map_size, shadow_map_size);
+
+        framebuffer.complete(SHADOW_MAP_CAMERA_NEAR, self.projection_z_far);
+
+        *self.framebuffer.borrow_mut() = framebuffer;
+
+        self.renderer
+            .borrow_mut()
+            .bind_framebuffer(Some(self.framebuffer.clone()));
+
+        self.last_dependencies = None;
+    }
+
+    pub fn get_frames_since_update(&self) -> u32 {
+        self.frames_since_update
+    }
+
+    // Decides whether the shadow map(s) should be re-rendered this frame; if
+    // so, records `dependencies` as the state that we last rendered.
+    pub fn should_update(
+        &mut self,
+        policy: ShadowMapUpdatePolicy,
+        dependencies: ShadowMapDependencies,
+    ) -> bool {
+        let should_update = match (policy, &self.last_dependencies) {
+            (ShadowMapUpdatePolicy::EveryFrame, _) | (_, None) => true,
+            (ShadowMapUpdatePolicy::OnChange, Some(last)) => *last != dependencies,
+            (ShadowMapUpdatePolicy::EveryNFrames(n), Some(_)) => self.frames_since_update + 1 >= n,
+        };
+
+        if should_update {
+            self.frames_since_update = 0;
+
+            self.last_dependencies.replace(dependencies);
+        } else {
+            self.frames_since_update += 1;
+        }
+
+        should_update
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::TAU;
+
+    use super::get_dithered_shadow_tap_offset;
+
+    #[test]
+    fn dithered_tap_offsets_follow_the_bayer_sequence() {
+        // Pixel (0, 0) taps at 1/32 of a turn, 1.5 texels out.
+
+        let offset = get_dithered_shadow_tap_offset(0, 0, 0);
+
+        assert!((offset.x - 1.471_178).abs() < 1e-5, "{:?}", offset);
+        assert!((offset.y - 0.292_635).abs() < 1e-5, "{:?}", offset);
+
+        // Offsets trace a circle, in 16 steps.
+
+        let angle_index = |x: u32, y: u32, frame_index: u32| {
+            let offset = get_dithered_shadow_tap_offset(x, y, frame_index);
+
+            let theta = offset.y.atan2(offset.x).rem_euclid(TAU);
+
+            (theta / TAU * 16.0 - 0.5).round() as u8 % 16
+        };
+
+        let indices = |block_x: u32, block_y: u32, frame_index: u32| {
+            let mut indices = [[0; 4]; 4];
+
+            for (y, row) in indices.iter_mut().enumerate() {
+                for (x, index) in row.iter_mut().enumerate() {
+                    *index = angle_index(block_x + x as u32, block_y + y as u32, frame_index);
+                }
+            }
+
+            indices
+        };
+
+        // Repeats for every 4x4 block of pixels.
+
+        for (block_x, block_y) in [(0, 0), (4, 0), (0, 4), (8, 12)] {
+            assert_eq!(
+                indices(block_x, block_y, 0),
+                [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]]
+            );
+
+            // Each frame advances every pixel by one step, wrapping after 16.
+
+            assert_eq!(
+                indices(block_x, block_y, 1),
+                [[1, 9, 3, 11], [13, 5, 15, 7], [4, 12, 2, 10], [0, 8, 14, 6]]
+            );
+
+            assert_eq!(indices(block_x, block_y, 17), indices(block_x, block_y, 1));
         }
     }
 }
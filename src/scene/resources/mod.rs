@@ -3,6 +3,7 @@ use std::{cell::RefCell, rc::Rc};
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    buffer::data::DataBuffer,
     entity::Entity,
     material::Material,
     mesh::Mesh,
@@ -41,6 +42,12 @@ pub struct SceneResources {
     pub cubemap_u8: Rc<RefCell<Arena<CubeMap>>>,
     pub cubemap_f32: Rc<RefCell<Arena<CubeMap<f32>>>>,
     pub cubemap_vec3: Rc<RefCell<Arena<CubeMap<Vec3>>>>,
+    #[serde(default)]
+    pub data_u32: Rc<RefCell<Arena<DataBuffer<u32>>>>,
+    #[serde(default)]
+    pub data_f32: Rc<RefCell<Arena<DataBuffer<f32>>>>,
+    #[serde(default)]
+    pub data_vec3: Rc<RefCell<Arena<DataBuffer<Vec3>>>>,
 }
 
 impl PostDeserialize for SceneResources {
@@ -70,6 +77,9 @@ impl SceneResources {
         self.cubemap_u8.borrow_mut().post_deserialize();
         self.cubemap_f32.borrow_mut().post_deserialize();
         self.cubemap_vec3.borrow_mut().post_deserialize();
+        self.data_u32.borrow_mut().post_deserialize();
+        self.data_f32.borrow_mut().post_deserialize();
+        self.data_vec3.borrow_mut().post_deserialize();
     }
 
     pub fn post_deserialize_non_mut(&self) {
@@ -1,7 +1,18 @@
+use std::rc::Rc;
+
 use crate::{
+    geometry::primitives::plane::Plane,
     matrix::Mat4,
+    random::hash::hash_2d,
     resource::handle::Handle,
-    vec::{vec3::Vec3, vec4::Vec4},
+    scene::light::{ambient_cube::AmbientCube, irradiance_probe_grid::IrradianceProbeGrid},
+    time::TimingInfo,
+    vec::{vec2::Vec2, vec3::Vec3, vec4::Vec4},
+};
+
+use super::{
+    fog::Fog,
+    uniform::{ShaderUniforms, U_DISSOLVE, U_RESOLUTION, U_TIME, U_TIME_DELTA},
 };
 
 #[derive(Debug, Clone)]
@@ -23,11 +34,29 @@ pub struct ShaderContext {
     pub ambient_specular_brdf_integration_map: Option<Handle>,
     pub skybox_transform: Option<Mat4>,
     pub ambient_light: Option<Handle>,
+    // Ambient light for surfaces without a (baked) lightmap.
+    pub irradiance_probe_grid: Option<Rc<IrradianceProbeGrid>>,
+    // Cheaper fallback, for surfaces with neither a lightmap nor probes.
+    pub ambient_cube: Option<AmbientCube>,
     pub directional_light: Option<Handle>,
     pub directional_light_view_projections: Option<Vec<(f32, Mat4)>>,
     pub directional_light_view_projection_index: Option<usize>,
     pub point_lights: Vec<Handle>,
     pub spot_lights: Vec<Handle>,
+    // World-space plane; fragments behind it are discarded.
+    pub clip_plane: Option<Plane>,
+    // Blended over shaded fragments by their distance from the view position;
+    // copied from the renderer's `RenderOptions::fog` at the start of each
+    // frame.
+    pub fog: Option<Fog>,
+    // Index of the frame being rendered (e.g., to vary per-frame jitter).
+    pub frame_index: u32,
+    // Kept as fields (and mirrored into `uniforms`), as they're read for
+    // every fragment.
+    pub time: f32,
+    pub time_delta: f32,
+    pub dissolve: f32,
+    pub uniforms: ShaderUniforms,
 }
 
 impl Default for ShaderContext {
@@ -50,11 +79,20 @@ impl Default for ShaderContext {
             ambient_specular_brdf_integration_map: None,
             skybox_transform: None,
             ambient_light: None,
+            irradiance_probe_grid: None,
+            ambient_cube: None,
             directional_light: None,
             directional_light_view_projections: None,
             directional_light_view_projection_index: None,
             point_lights: vec![],
             spot_lights: vec![],
+            clip_plane: None,
+            fog: None,
+            frame_index: 0,
+            time: 0.0,
+            time_delta: 0.0,
+            dissolve: 0.0,
+            uniforms: Default::default(),
         }
     }
 }
@@ -117,6 +155,14 @@ impl ShaderContext {
         self.ambient_light = light;
     }
 
+    pub fn set_irradiance_probe_grid(&mut self, grid: Option<Rc<IrradianceProbeGrid>>) {
+        self.irradiance_probe_grid = grid;
+    }
+
+    pub fn set_ambient_cube(&mut self, cube: Option<AmbientCube>) {
+        self.ambient_cube = cube;
+    }
+
     pub fn set_directional_light(&mut self, light: Option<Handle>) {
         self.directional_light = light;
     }
@@ -183,6 +229,69 @@ impl ShaderContext {
         self.skybox_transform = optional_transform;
     }
 
+    pub fn get_time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn get_time_delta(&self) -> f32 {
+        self.time_delta
+    }
+
+    pub fn set_timing_info(&mut self, timing_info: &TimingInfo) {
+        self.frame_index = timing_info.current_frame_index;
+
+        self.time = timing_info.uptime_seconds;
+        self.time_delta = timing_info.seconds_since_last_update;
+
+        self.uniforms.set_f32(U_TIME, self.time);
+        self.uniforms.set_f32(U_TIME_DELTA, self.time_delta);
+    }
+
+    pub fn get_resolution(&self) -> Vec3 {
+        self.uniforms.get_vec3(U_RESOLUTION).unwrap_or_default()
+    }
+
+    pub fn set_resolution(&mut self, width: u32, height: u32) {
+        let resolution = Vec3 {
+            x: width as f32,
+            y: height as f32,
+            z: if height == 0 {
+                0.0
+            } else {
+                width as f32 / height as f32
+            },
+        };
+
+        self.uniforms.set_vec3(U_RESOLUTION, resolution);
+    }
+
+    pub fn set_clip_plane(&mut self, plane: Option<Plane>) {
+        self.clip_plane = plane;
+    }
+
+    pub fn is_clipped(&self, position_world_space: &Vec3) -> bool {
+        match &self.clip_plane {
+            Some(plane) => plane.get_signed_distance(position_world_space) < 0.0,
+            None => false,
+        }
+    }
+
+    pub fn get_dissolve(&self) -> f32 {
+        self.dissolve
+    }
+
+    pub fn set_dissolve(&mut self, dissolve: f32) {
+        self.dissolve = dissolve.clamp(0.0, 1.0);
+
+        self.uniforms.set_f32(U_DISSOLVE, self.dissolve);
+    }
+
+    pub fn is_dissolved(&self, uv: Vec2) -> bool {
+        let dissolve = self.get_dissolve();
+
+        dissolve > 0.0 && hash_2d(uv) < dissolve
+    }
+
     fn recompute_world_view_transform(&mut self) {
         self.world_view_transform = self.world_transform * self.view_inverse_transform;
     }
@@ -192,3 +301,38 @@ impl ShaderContext {
             self.world_view_transform * self.projection_transform;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::time::TimingInfo;
+
+    use super::ShaderContext;
+
+    #[test]
+    fn time_uniform_advances_by_frame_delta() {
+        let mut context = ShaderContext::default();
+
+        let mut timing_info = TimingInfo {
+            uptime_seconds: 1.0,
+            seconds_since_last_update: 0.016,
+            ..Default::default()
+        };
+
+        context.set_timing_info(&timing_info);
+
+        let previous_time = context.get_time();
+
+        // Simulates the next frame.
+
+        timing_info.seconds_since_last_update = 0.02;
+        timing_info.uptime_seconds += timing_info.seconds_since_last_update;
+
+        context.set_timing_info(&timing_info);
+
+        assert_eq!(context.get_time_delta(), 0.02);
+
+        assert!(
+            (context.get_time() - previous_time - context.get_time_delta()).abs() < f32::EPSILON
+        );
+    }
+}
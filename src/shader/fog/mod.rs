@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{animation::lerp, vec::vec3::Vec3};
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FogKind {
+    // Fades in between the `start` and `end` distances.
+    Linear { start: f32, end: f32 },
+    Exp { density: f32 },
+    Exp2 { density: f32 },
+}
+
+impl Default for FogKind {
+    fn default() -> Self {
+        Self::Linear {
+            start: 10.0,
+            end: 50.0,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fog {
+    pub color: Vec3,
+    pub kind: FogKind,
+}
+
+impl Default for Fog {
+    fn default() -> Self {
+        Self {
+            color: Vec3 {
+                x: 0.5,
+                y: 0.5,
+                z: 0.5,
+            },
+            kind: Default::default(),
+        }
+    }
+}
+
+impl Fog {
+    // How much of the fog color covers a surface at the given (view-space)
+    // distance, from 0 (none) to 1 (fully fogged).
+    pub fn get_factor(&self, distance: f32) -> f32 {
+        let factor = match self.kind {
+            FogKind::Linear { start, end } => {
+                if end <= start {
+                    return if distance < start { 0.0 } else { 1.0 };
+                }
+
+                (distance - start) / (end - start)
+            }
+            FogKind::Exp { density } => 1.0 - (-density * distance).exp(),
+            FogKind::Exp2 { density } => 1.0 - (-(density * distance).powi(2)).exp(),
+        };
+
+        factor.clamp(0.0, 1.0)
+    }
+
+    pub fn apply(&self, color: Vec3, distance: f32) -> Vec3 {
+        lerp(color, self.color, self.get_factor(distance))
+    }
+}
@@ -24,10 +24,17 @@ pub struct GeometrySample {
     pub emissive_color: Vec3,
     pub alpha: f32,
     pub ambient_factor: f32,
+    // Screen-space (contact) shadowing from the directional light, in [0, 1].
+    pub contact_shadow: f32,
+    // Irradiance baked into the material's lightmap, if any; replaces the
+    // ambient term, and the directional and point lights' terms.
+    pub baked_irradiance: Option<Vec3>,
     // PBR
     pub albedo: Vec3,
     pub roughness: f32,
     pub metallic: f32,
+    // Blends screen-space reflections over the lit sample, in [0, 1].
+    pub reflectivity: f32,
     // pub sheen: f32,
     // pub clearcoat_thickness: f32,
     // pub clearcoat_roughness: f32,
@@ -35,6 +42,15 @@ pub struct GeometrySample {
     // pub anisotropy_rotation: f32,
 }
 
+impl GeometrySample {
+    // Flips the sample's normal(s), e.g., to light the back of a surface.
+    pub fn flip_normal(&mut self) {
+        self.normal_world_space = -self.normal_world_space;
+
+        self.tangent_space_info.normal = -self.tangent_space_info.normal;
+    }
+}
+
 impl Add<GeometrySample> for GeometrySample {
     type Output = GeometrySample;
 
@@ -45,7 +61,13 @@ impl Add<GeometrySample> for GeometrySample {
             albedo: self.albedo + rhs.albedo,
             roughness: self.roughness + rhs.roughness,
             metallic: self.metallic + rhs.metallic,
+            reflectivity: self.reflectivity + rhs.reflectivity,
             ambient_factor: self.ambient_factor + rhs.ambient_factor,
+            contact_shadow: self.contact_shadow + rhs.contact_shadow,
+            baked_irradiance: self
+                .baked_irradiance
+                .zip(rhs.baked_irradiance)
+                .map(|(a, b)| a + b),
             position_world_space: self.position_world_space + rhs.position_world_space,
             position_view_space: self.position_view_space + rhs.position_view_space,
             normal_world_space: self.normal_world_space + rhs.normal_world_space,
@@ -74,7 +96,13 @@ impl Sub<GeometrySample> for GeometrySample {
             albedo: self.albedo - rhs.albedo,
             roughness: self.roughness - rhs.roughness,
             metallic: self.metallic - rhs.metallic,
+            reflectivity: self.reflectivity - rhs.reflectivity,
             ambient_factor: self.ambient_factor - rhs.ambient_factor,
+            contact_shadow: self.contact_shadow - rhs.contact_shadow,
+            baked_irradiance: self
+                .baked_irradiance
+                .zip(rhs.baked_irradiance)
+                .map(|(a, b)| a - b),
             position_world_space: self.position_world_space - rhs.position_world_space,
             position_view_space: self.position_view_space - rhs.position_view_space,
             normal_world_space: self.normal_world_space - rhs.normal_world_space,
@@ -103,7 +131,13 @@ impl Mul<GeometrySample> for GeometrySample {
             albedo: self.albedo * rhs.albedo,
             roughness: self.roughness * rhs.roughness,
             metallic: self.metallic * rhs.metallic,
+            reflectivity: self.reflectivity * rhs.reflectivity,
             ambient_factor: self.ambient_factor * rhs.ambient_factor,
+            contact_shadow: self.contact_shadow * rhs.contact_shadow,
+            baked_irradiance: self
+                .baked_irradiance
+                .zip(rhs.baked_irradiance)
+                .map(|(a, b)| a * b),
             position_world_space: self.position_world_space * rhs.position_world_space,
             position_view_space: self.position_view_space * rhs.position_view_space,
             normal_world_space: self.normal_world_space * rhs.normal_world_space,
@@ -132,7 +166,13 @@ impl Div<GeometrySample> for GeometrySample {
             albedo: self.albedo / rhs.albedo,
             roughness: self.roughness / rhs.roughness,
             metallic: self.metallic / rhs.metallic,
+            reflectivity: self.reflectivity / rhs.reflectivity,
             ambient_factor: self.ambient_factor / rhs.ambient_factor,
+            contact_shadow: self.contact_shadow / rhs.contact_shadow,
+            baked_irradiance: self
+                .baked_irradiance
+                .zip(rhs.baked_irradiance)
+                .map(|(a, b)| a / b),
             position_world_space: self.position_world_space / rhs.position_world_space,
             position_view_space: self.position_view_space / rhs.position_view_space,
             normal_world_space: self.normal_world_space / rhs.normal_world_space,
@@ -1,5 +1,9 @@
 pub mod alpha;
 pub mod context;
+pub mod fog;
 pub mod fragment;
 pub mod geometry;
+pub mod parallax;
+pub mod registry;
+pub mod uniform;
 pub mod vertex;
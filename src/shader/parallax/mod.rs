@@ -0,0 +1,246 @@
+use crate::{
+    texture::{map::TextureMap, sample::sample_nearest_u8},
+    vec::{
+        vec2::Vec2,
+        vec3::{self, Vec3},
+    },
+};
+
+static LAYER_COUNT_MIN: f32 = 8.0;
+static LAYER_COUNT_MAX: f32 = 32.0;
+
+static Z_FORWARD_TANGENT_SPACE: Vec3 = vec3::FORWARD;
+
+fn sample_depth(uv: Vec2, map: &TextureMap) -> f32 {
+    sample_nearest_u8(uv, map, None).0 as f32 / 255.0
+}
+
+// Uses more layers for views closer to grazing, unless a fixed number of
+// `steps` is given.
+fn get_layer_count(direction_tangent_space: Vec3, steps: u8) -> f32 {
+    if steps > 0 {
+        return steps as f32;
+    }
+
+    let alpha = Z_FORWARD_TANGENT_SPACE
+        .dot(direction_tangent_space)
+        .max(0.0);
+
+    (LAYER_COUNT_MAX - (LAYER_COUNT_MAX - LAYER_COUNT_MIN) * alpha).floor()
+}
+
+pub fn get_parallax_mapped_uv(
+    uv: Vec2,
+    fragment_to_view_direction_tangent_space: Vec3,
+    displacement: f32,
+    map: &TextureMap,
+    scale: f32,
+) -> Vec2 {
+    // Scale the view-direction vector (in tangent space) by the sampled
+    // displacement, modulated by a scaling factor.
+
+    let layer_count = get_layer_count(fragment_to_view_direction_tangent_space, 0);
+
+    let layer_depth: f32 = 1.0 / layer_count;
+
+    let p = Vec2 {
+        x: fragment_to_view_direction_tangent_space.x / fragment_to_view_direction_tangent_space.z,
+        y: fragment_to_view_direction_tangent_space.y / fragment_to_view_direction_tangent_space.z,
+        z: 1.0,
+    } * displacement
+        * scale;
+
+    let uv_step = p / layer_count;
+
+    let mut current_layer_depth = 0.0;
+    let mut current_uv = uv;
+    let mut current_sampled_displacement = displacement;
+
+    while current_layer_depth < current_sampled_displacement {
+        // Take a step along P.
+        current_uv -= uv_step;
+
+        // Re-sample the displacement map at this new UV coordinate.
+        current_sampled_displacement = sample_depth(current_uv, map);
+
+        // Update "current" layer depth for our next loop iteration.
+        current_layer_depth += layer_depth;
+    }
+
+    // Interpolate between the sampled displacements at the previous layer and
+    // the current layer.
+
+    let previous_uv = current_uv + uv_step;
+
+    let after_depth = current_sampled_displacement - current_layer_depth;
+
+    let before_depth = sample_depth(previous_uv, map) - current_layer_depth + layer_depth;
+
+    let alpha = after_depth / (after_depth - before_depth);
+
+    previous_uv * alpha + current_uv * (1.0 - alpha)
+}
+
+// Parallax occlusion mapping: marches the view ray down through the depth
+// (displacement) map, in tangent space, until it passes beneath the surface.
+pub fn get_parallax_occlusion_mapped_uv(
+    uv: Vec2,
+    fragment_to_view_direction_tangent_space: Vec3,
+    map: &TextureMap,
+    scale: f32,
+    steps: u8,
+) -> Vec2 {
+    let direction = fragment_to_view_direction_tangent_space;
+
+    if direction.z <= 0.0 {
+        return uv;
+    }
+
+    let layer_count = get_layer_count(direction, steps);
+
+    let layer_depth = 1.0 / layer_count;
+
+    // The full UV offset, for a ray reaching the deepest layer.
+
+    let p = Vec2 {
+        x: direction.x / direction.z,
+        y: direction.y / direction.z,
+        z: 1.0,
+    } * scale;
+
+    let uv_step = p / layer_count;
+
+    let mut current_layer_depth = 0.0;
+    let mut current_uv = uv;
+    let mut current_sampled_depth = sample_depth(current_uv, map);
+
+    for _ in 0..layer_count as usize {
+        if current_layer_depth >= current_sampled_depth {
+            break;
+        }
+
+        current_uv -= uv_step;
+
+        current_sampled_depth = sample_depth(current_uv, map);
+
+        current_layer_depth += layer_depth;
+    }
+
+    if current_layer_depth == 0.0 {
+        return uv;
+    }
+
+    // Interpolate between the last layer above the surface and the first layer
+    // below it.
+
+    let previous_uv = current_uv + uv_step;
+
+    let after_depth = current_sampled_depth - current_layer_depth;
+
+    let before_depth = sample_depth(previous_uv, map) - current_layer_depth + layer_depth;
+
+    let alpha = if after_depth == before_depth {
+        0.0
+    } else {
+        after_depth / (after_depth - before_depth)
+    };
+
+    previous_uv * alpha + current_uv * (1.0 - alpha)
+}
+
+// Marches from the (parallax-mapped) surface point towards the light, returning
+// 1 if the depth map occludes the light, or 0 otherwise.
+pub fn get_parallax_self_shadowing(
+    uv: Vec2,
+    fragment_to_light_direction_tangent_space: Vec3,
+    map: &TextureMap,
+    scale: f32,
+    steps: u8,
+) -> f32 {
+    let direction = fragment_to_light_direction_tangent_space;
+
+    if direction.z <= 0.0 {
+        return 0.0;
+    }
+
+    let initial_depth = sample_depth(uv, map);
+
+    if initial_depth == 0.0 {
+        return 0.0;
+    }
+
+    let layer_count = get_layer_count(direction, steps);
+
+    let layer_depth = initial_depth / layer_count;
+
+    let uv_step = Vec2 {
+        x: direction.x / direction.z,
+        y: direction.y / direction.z,
+        z: 1.0,
+    } * scale
+        * initial_depth
+        / layer_count;
+
+    let mut current_layer_depth = initial_depth - layer_depth;
+    let mut current_uv = uv + uv_step;
+
+    while current_layer_depth > 0.0 {
+        if sample_depth(current_uv, map) < current_layer_depth {
+            return 1.0;
+        }
+
+        current_uv += uv_step;
+
+        current_layer_depth -= layer_depth;
+    }
+
+    0.0
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::Buffer2D,
+        texture::map::TextureMap,
+        vec::{vec2::Vec2, vec3::Vec3},
+    };
+
+    use super::get_parallax_occlusion_mapped_uv;
+
+    #[test]
+    fn parallax_occlusion_offset_grows_with_view_angle() {
+        // A flat surface, sunk halfway into the depth map.
+
+        let map = TextureMap::from_buffer(4, 4, Buffer2D::new(4, 4, Some(128_u8)));
+
+        let uv = Vec2 {
+            x: 0.5,
+            y: 0.5,
+            z: 1.0,
+        };
+
+        let get_offset = |degrees_from_normal: f32| -> f32 {
+            let angle = degrees_from_normal.to_radians();
+
+            let direction_to_view = Vec3 {
+                x: angle.sin(),
+                y: 0.0,
+                z: angle.cos(),
+            };
+
+            let mapped_uv = get_parallax_occlusion_mapped_uv(uv, direction_to_view, &map, 0.1, 16);
+
+            // The view leans along the tangent, only.
+
+            (mapped_uv.x - uv.x).abs()
+        };
+
+        let offsets = [0.0, 30.0, 60.0, 75.0].map(get_offset);
+
+        assert_eq!(offsets[0], 0.0);
+
+        for pair in offsets.windows(2) {
+            assert!(pair[1] > pair[0], "{:?}", offsets);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::shaders::{
+    debug_shaders::{
+        albedo_fragment_shader::AlbedoFragmentShader, depth_fragment_shader::DepthFragmentShader,
+        emissive_fragment_shader::EmissiveFragmentShader,
+        metallic_fragment_shader::MetallicFragmentShader,
+        normal_fragment_shader::NormalFragmentShader,
+        roughness_fragment_shader::RoughnessFragmentShader,
+        specular_roughness_fragment_shader::SpecularRoughnessFragmentShader,
+        stencil_fragment_shader::StencilFragmentShader,
+        uv_test_fragment_shader::UvTestFragmentShader,
+    },
+    default_fragment_shader::DEFAULT_FRAGMENT_SHADER,
+    scrolling_uv_fragment_shader::ScrollingUvFragmentShader,
+};
+
+use super::fragment::FragmentShaderFn;
+
+pub static DEFAULT_FRAGMENT_SHADER_NAME: &str = "default";
+
+// Maps names to fragment shaders, so that a running app can swap shaders by
+// name (e.g., from a command). Each shader also keeps a stable index, which
+// the renderer can store per-sample.
+#[derive(Debug, Clone)]
+pub struct ShaderRegistry {
+    fragment_shaders: Vec<(String, FragmentShaderFn)>,
+}
+
+impl Default for ShaderRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            fragment_shaders: Default::default(),
+        };
+
+        // Registers the built-in shaders.
+
+        let built_in: [(&str, FragmentShaderFn); 11] = [
+            (DEFAULT_FRAGMENT_SHADER_NAME, DEFAULT_FRAGMENT_SHADER),
+            ("albedo", AlbedoFragmentShader),
+            ("depth", DepthFragmentShader),
+            ("emissive", EmissiveFragmentShader),
+            ("metallic", MetallicFragmentShader),
+            ("normal", NormalFragmentShader),
+            ("roughness", RoughnessFragmentShader),
+            ("scrolling_uv", ScrollingUvFragmentShader),
+            ("specular_roughness", SpecularRoughnessFragmentShader),
+            ("stencil", StencilFragmentShader),
+            ("uv_test", UvTestFragmentShader),
+        ];
+
+        for (name, shader) in built_in {
+            registry.register_fragment_shader(name, shader);
+        }
+
+        registry
+    }
+}
+
+impl ShaderRegistry {
+    // Registers (or replaces) the fragment shader with the given name.
+    pub fn register_fragment_shader(&mut self, name: &str, shader: FragmentShaderFn) {
+        match self.get_fragment_shader_index(name) {
+            Ok(index) => self.fragment_shaders[index].1 = shader,
+            Err(_) => self.fragment_shaders.push((name.to_string(), shader)),
+        }
+    }
+
+    pub fn get_fragment_shader_index(&self, name: &str) -> Result<usize, String> {
+        match self.fragment_shaders.iter().position(|(n, _)| n == name) {
+            Some(index) => Ok(index),
+            None => Err(format!("No fragment shader registered as '{}'!", name)),
+        }
+    }
+
+    pub fn get_fragment_shader_at(&self, index: usize) -> FragmentShaderFn {
+        self.fragment_shaders[index].1
+    }
+
+    pub fn get_fragment_shader(&self, name: &str) -> Result<FragmentShaderFn, String> {
+        let index = self.get_fragment_shader_index(name)?;
+
+        Ok(self.get_fragment_shader_at(index))
+    }
+
+    pub fn get_fragment_shader_names(&self) -> Vec<&String> {
+        self.fragment_shaders.iter().map(|(name, _)| name).collect()
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use crate::{matrix::Mat4, resource::handle::Handle, vec::vec3::Vec3};
+
+// Standard uniforms, kept up to date by the engine.
+
+// Seconds since the app started (f32).
+pub static U_TIME: &str = "u_time";
+
+// Seconds elapsed since the previous frame (f32).
+pub static U_TIME_DELTA: &str = "u_time_delta";
+
+// Framebuffer size, as (width, height, aspect ratio) (Vec3).
+pub static U_RESOLUTION: &str = "u_resolution";
+
+// Dissolve threshold in [0, 1]; a noise-based fraction of fragments to discard (f32).
+pub static U_DISSOLVE: &str = "u_dissolve";
+
+#[derive(Debug, Copy, Clone)]
+pub enum ShaderUniform {
+    Float(f32),
+    Vec3(Vec3),
+    Mat4(Mat4),
+    Handle(Handle),
+}
+
+// Named, typed parameters for custom shaders; set by user code (e.g., once per
+// frame) and read by shaders through the `ShaderContext`.
+#[derive(Default, Debug, Clone)]
+pub struct ShaderUniforms {
+    uniforms: HashMap<String, ShaderUniform>,
+}
+
+macro_rules! uniform_accessors {
+    ($get: ident, $set: ident, $variant: ident, $type: ty) => {
+        pub fn $get(&self, name: &str) -> Option<$type> {
+            match self.uniforms.get(name) {
+                Some(ShaderUniform::$variant(value)) => Some(*value),
+                _ => None,
+            }
+        }
+
+        pub fn $set(&mut self, name: &str, value: $type) {
+            self.set(name, ShaderUniform::$variant(value));
+        }
+    };
+}
+
+impl ShaderUniforms {
+    uniform_accessors!(get_f32, set_f32, Float, f32);
+    uniform_accessors!(get_vec3, set_vec3, Vec3, Vec3);
+    uniform_accessors!(get_mat4, set_mat4, Mat4, Mat4);
+    uniform_accessors!(get_handle, set_handle, Handle, Handle);
+
+    pub fn get(&self, name: &str) -> Option<&ShaderUniform> {
+        self.uniforms.get(name)
+    }
+
+    pub fn set(&mut self, name: &str, uniform: ShaderUniform) {
+        self.uniforms.insert(name.to_string(), uniform);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<ShaderUniform> {
+        self.uniforms.remove(name)
+    }
+
+    pub fn clear(&mut self) {
+        self.uniforms.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{shader::context::ShaderContext, vec::vec3::Vec3};
+
+    #[test]
+    fn set_and_read_back_vec3_uniform() {
+        let mut context = ShaderContext::default();
+
+        let tint = Vec3 {
+            x: 0.2,
+            y: 0.4,
+            z: 0.6,
+        };
+
+        context.uniforms.set_vec3("u_tint", tint);
+
+        assert_eq!(context.uniforms.get_vec3("u_tint"), Some(tint));
+
+        // Reading a uniform as the wrong type (or an unset name) gives `None`.
+
+        assert_eq!(context.uniforms.get_f32("u_tint"), None);
+        assert_eq!(context.uniforms.get_vec3("u_missing"), None);
+    }
+}
@@ -9,31 +9,48 @@ pub static DEFAULT_ALPHA_SHADER: AlphaShaderFn =
     |context: &ShaderContext, resources: &SceneResources, out: &DefaultVertexOut| -> bool {
         // Check if this fragment can be discarded.
 
+        if context.is_clipped(&out.position_world_space) || context.is_dissolved(out.uv) {
+            return false;
+        }
+
         match &context.active_material {
             Some(material_handle) => {
                 if let Ok(entry) = resources.material.borrow().get(material_handle) {
                     let material = &entry.item;
 
-                    if let Some(alpha_map_handle) = material.alpha_map {
-                        match resources.texture_u8.borrow().get(&alpha_map_handle) {
-                            Ok(entry) => {
-                                let map = &entry.item;
+                    let alpha = match material.alpha_map {
+                        Some(alpha_map_handle) => {
+                            match resources.texture_u8.borrow().get(&alpha_map_handle) {
+                                Ok(entry) => {
+                                    let map = &entry.item;
 
-                                // Read in a per-fragment normal, with components in the
-                                // range [0, 255].
+                                    // Read in a per-fragment alpha, in the
+                                    // range [0, 255].
 
-                                let (r, _g, _b) = sample_nearest_u8(out.uv, map, None);
+                                    let (r, _g, _b) = sample_nearest_u8(out.uv, map, None);
 
-                                if r < 4 {
-                                    return false;
+                                    if material.alpha_cutoff.is_none() && r < 4 {
+                                        return false;
+                                    }
+
+                                    r as f32 / 255.0
+                                }
+                                Err(err) => {
+                                    panic!(
+                                        "Failed to get TextureMap from Arena: {:?}: {}",
+                                        material_handle, err
+                                    )
                                 }
                             }
-                            Err(err) => {
-                                panic!(
-                                    "Failed to get TextureMap from Arena: {:?}: {}",
-                                    material_handle, err
-                                )
-                            }
+                        }
+                        None => 1.0 - material.transparency,
+                    };
+
+                    // Alpha-tested (cutout) materials.
+
+                    if let Some(cutoff) = material.alpha_cutoff {
+                        if alpha < cutoff {
+                            return false;
                         }
                     }
                 }
@@ -43,3 +60,88 @@ pub static DEFAULT_ALPHA_SHADER: AlphaShaderFn =
 
         true
     };
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        geometry::primitives::plane::Plane,
+        scene::resources::SceneResources,
+        shader::context::ShaderContext,
+        vec::{vec2::Vec2, vec3::Vec3},
+        vertex::default_vertex_out::DefaultVertexOut,
+    };
+
+    use super::DEFAULT_ALPHA_SHADER;
+
+    #[test]
+    fn fragments_behind_clip_plane_are_discarded() {
+        let resources = SceneResources::default();
+
+        let mut context = ShaderContext::default();
+
+        context.set_clip_plane(Some(Plane {
+            point: Default::default(),
+            normal: Vec3 {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+        }));
+
+        let fragment_at = |y: f32| DefaultVertexOut {
+            position_world_space: Vec3 { x: 0.0, y, z: 0.0 },
+            ..Default::default()
+        };
+
+        assert!(DEFAULT_ALPHA_SHADER(
+            &context,
+            &resources,
+            &fragment_at(1.0)
+        ));
+        assert!(!DEFAULT_ALPHA_SHADER(
+            &context,
+            &resources,
+            &fragment_at(-1.0)
+        ));
+    }
+
+    #[test]
+    fn raising_dissolve_discards_more_fragments() {
+        let resources = SceneResources::default();
+
+        let mut context = ShaderContext::default();
+
+        let mut count_discarded = |dissolve: f32| -> usize {
+            context.set_dissolve(dissolve);
+
+            let mut discarded = 0;
+
+            for y in 0..32 {
+                for x in 0..32 {
+                    let fragment = DefaultVertexOut {
+                        uv: Vec2 {
+                            x: x as f32 / 32.0,
+                            y: y as f32 / 32.0,
+                            z: 0.0,
+                        },
+                        ..Default::default()
+                    };
+
+                    if !DEFAULT_ALPHA_SHADER(&context, &resources, &fragment) {
+                        discarded += 1;
+                    }
+                }
+            }
+
+            discarded
+        };
+
+        let none = count_discarded(0.0);
+        let some = count_discarded(0.25);
+        let most = count_discarded(0.75);
+
+        assert_eq!(none, 0);
+        assert!(some > none);
+        assert!(most > some);
+    }
+}
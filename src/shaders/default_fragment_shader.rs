@@ -1,3 +1,5 @@
+use std::f32::consts::PI;
+
 use crate::{
     animation::lerp,
     physics::pbr::brdf::fresnel_schlick_indirect,
@@ -27,77 +29,103 @@ pub static DEFAULT_FRAGMENT_SHADER: FragmentShaderFn =
 
         let f0 = lerp(f0_dielectic, f0_metal, sample.metallic);
 
-        // Calculate ambient light contribution
-
-        let ambient_light_contribution = match (
-            &context.ambient_diffuse_irradiance_map,
-            &context.ambient_specular_prefiltered_environment_map,
-            &context.ambient_specular_brdf_integration_map,
-        ) {
-            (
-                Some(diffuse_irradiance_map_handle),
-                Some(specular_prefiltered_environment_map_handle),
-                Some(specular_brdf_integration_map_handle),
-            ) => {
-                match (
-                    resources
-                        .cubemap_vec3
-                        .borrow()
-                        .get(diffuse_irradiance_map_handle),
-                    resources
-                        .cubemap_vec3
-                        .borrow()
-                        .get(specular_prefiltered_environment_map_handle),
-                    resources
-                        .texture_vec2
-                        .borrow()
-                        .get(specular_brdf_integration_map_handle),
-                ) {
-                    (
-                        Ok(diffuse_irradiance_map_entry),
-                        Ok(specular_prefiltered_environment_map_entry),
-                        Ok(specular_brdf_integration_map_entry),
-                    ) => {
-                        let diffuse_irradiance_map = &diffuse_irradiance_map_entry.item;
-
-                        let specular_prefiltered_environment_map =
-                            &specular_prefiltered_environment_map_entry.item;
-
-                        let specular_brdf_integration_map =
-                            &specular_brdf_integration_map_entry.item;
-
-                        contribute_ambient_ibl(
-                            context,
-                            diffuse_irradiance_map,
-                            specular_prefiltered_environment_map,
-                            specular_brdf_integration_map,
-                            sample,
-                            &f0,
-                        )
+        // Calculate ambient light contribution (or use baked irradiance, from a
+        // lightmap, from nearby light probes, or from an ambient cube)
+
+        let baked_irradiance = sample
+            .baked_irradiance
+            .or_else(|| {
+                context.irradiance_probe_grid.as_ref().map(|grid| {
+                    grid.sample_irradiance(&sample.position_world_space, &sample.normal_world_space)
+                })
+            })
+            .or_else(|| {
+                context
+                    .ambient_cube
+                    .as_ref()
+                    .map(|cube| cube.sample_irradiance(&sample.normal_world_space))
+            });
+
+        // Baked irradiance is stored as-is (not divided by pi, unlike the IBL
+        // irradiance map), so the Lambertian BRDF (albedo / pi) applies here.
+
+        let ambient_light_contribution = match baked_irradiance {
+            Some(irradiance) => irradiance * sample.albedo * sample.ambient_factor / PI,
+            None => match (
+                &context.ambient_diffuse_irradiance_map,
+                &context.ambient_specular_prefiltered_environment_map,
+                &context.ambient_specular_brdf_integration_map,
+            ) {
+                (
+                    Some(diffuse_irradiance_map_handle),
+                    Some(specular_prefiltered_environment_map_handle),
+                    Some(specular_brdf_integration_map_handle),
+                ) => {
+                    match (
+                        resources
+                            .cubemap_vec3
+                            .borrow()
+                            .get(diffuse_irradiance_map_handle),
+                        resources
+                            .cubemap_vec3
+                            .borrow()
+                            .get(specular_prefiltered_environment_map_handle),
+                        resources
+                            .texture_vec2
+                            .borrow()
+                            .get(specular_brdf_integration_map_handle),
+                    ) {
+                        (
+                            Ok(diffuse_irradiance_map_entry),
+                            Ok(specular_prefiltered_environment_map_entry),
+                            Ok(specular_brdf_integration_map_entry),
+                        ) => {
+                            let diffuse_irradiance_map = &diffuse_irradiance_map_entry.item;
+
+                            let specular_prefiltered_environment_map =
+                                &specular_prefiltered_environment_map_entry.item;
+
+                            let specular_brdf_integration_map =
+                                &specular_brdf_integration_map_entry.item;
+
+                            contribute_ambient_ibl(
+                                context,
+                                diffuse_irradiance_map,
+                                specular_prefiltered_environment_map,
+                                specular_brdf_integration_map,
+                                sample,
+                                &f0,
+                            )
+                        }
+                        _ => panic!("Failed to get CubeMap from Arena."),
                     }
-                    _ => panic!("Failed to get CubeMap from Arena."),
                 }
-            }
-            _ => match &context.ambient_light {
-                Some(handle) => match resources.ambient_light.borrow().get(handle) {
-                    Ok(entry) => {
-                        let light = &entry.item;
+                _ => match &context.ambient_light {
+                    Some(handle) => match resources.ambient_light.borrow().get(handle) {
+                        Ok(entry) => {
+                            let light = &entry.item;
 
-                        light.contribute_pbr(sample)
-                    }
-                    Err(err) => panic!(
-                        "Failed to get AmbientLight from Arena: {:?}: {}",
-                        handle, err
-                    ),
+                            light.contribute_pbr(sample)
+                        }
+                        Err(err) => panic!(
+                            "Failed to get AmbientLight from Arena: {:?}: {}",
+                            handle, err
+                        ),
+                    },
+                    None => Default::default(),
                 },
-                None => Default::default(),
             },
         };
 
+        // A lightmap already holds the (direct and bounced) light from the
+        // scene's directional and point lights.
+
+        let is_lightmapped = sample.baked_irradiance.is_some();
+
         // Calculate directional light contribution
 
         let directional_light_contribution = match &context.directional_light {
-            Some(handle) => {
+            Some(handle) if !is_lightmapped => {
                 let texture_f32_arena = resources.texture_f32.borrow();
                 let directional_light_arena = resources.directional_light.borrow();
 
@@ -119,14 +147,20 @@ pub static DEFAULT_FRAGMENT_SHADER: FragmentShaderFn =
                     ),
                 }
             }
-            None => Default::default(),
+            _ => Default::default(),
         };
 
         // Calculate point light contributions (including specular)
 
         let mut point_light_contribution: Vec3 = Default::default();
 
-        for handle in &context.point_lights {
+        let point_lights = if is_lightmapped {
+            &[][..]
+        } else {
+            &context.point_lights[..]
+        };
+
+        for handle in point_lights {
             match resources.point_light.borrow().get(handle) {
                 Ok(entry) => {
                     let light = &entry.item;
@@ -170,11 +204,18 @@ pub static DEFAULT_FRAGMENT_SHADER: FragmentShaderFn =
 
         // Combine light intensities
 
-        ambient_light_contribution
+        let color = ambient_light_contribution
             + directional_light_contribution
             + point_light_contribution
             + spot_light_contribution
-            + emissive_light_contribution
+            + emissive_light_contribution;
+
+        // Fade towards the fog color with distance.
+
+        match &context.fog {
+            Some(fog) => fog.apply(color, sample.position_view_space.mag()),
+            None => color,
+        }
     };
 
 fn contribute_ambient_ibl(
@@ -261,3 +302,90 @@ fn contribute_ambient_ibl(
 
     (k_d * indirect_diffuse_irradiance + specular) * sample.ambient_factor
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        scene::resources::SceneResources,
+        shader::{
+            context::ShaderContext,
+            fog::{Fog, FogKind},
+            geometry::sample::GeometrySample,
+        },
+        vec::vec3::{self, Vec3},
+    };
+
+    use super::DEFAULT_FRAGMENT_SHADER;
+
+    fn shade_at_distance(context: &ShaderContext, distance: f32) -> Vec3 {
+        // An unlit, white surface.
+
+        let sample = GeometrySample {
+            emissive_color: vec3::ONES,
+            position_view_space: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: distance,
+            },
+            ..Default::default()
+        };
+
+        DEFAULT_FRAGMENT_SHADER(context, &SceneResources::default(), &sample)
+    }
+
+    #[test]
+    fn fog_blends_towards_the_fog_color_by_view_distance() {
+        let mut context = ShaderContext::default();
+
+        assert_eq!(shade_at_distance(&context, 100.0), vec3::ONES);
+
+        // Linear fog, fading in between 10 and 30 units.
+
+        context.fog = Some(Fog {
+            color: Vec3::default(),
+            kind: FogKind::Linear {
+                start: 10.0,
+                end: 30.0,
+            },
+        });
+
+        for (distance, expected) in [
+            (5.0, 1.0),
+            (10.0, 1.0),
+            (15.0, 0.75),
+            (20.0, 0.5),
+            (40.0, 0.0),
+        ] {
+            let color = shade_at_distance(&context, distance);
+
+            assert!(
+                (color.x - expected).abs() < 1e-5,
+                "{}: {:?}",
+                distance,
+                color
+            );
+        }
+
+        // Exponential fog.
+
+        for (kind, expected_factor) in [
+            (FogKind::Exp { density: 0.1 }, 1.0 - (-1.0_f32).exp()),
+            (FogKind::Exp2 { density: 0.1 }, 1.0 - (-1.0_f32).exp()),
+            (FogKind::Exp2 { density: 0.05 }, 1.0 - (-0.25_f32).exp()),
+        ] {
+            context.fog = Some(Fog {
+                color: Vec3::default(),
+                kind,
+            });
+
+            let color = shade_at_distance(&context, 10.0);
+
+            assert!(
+                (color.x - (1.0 - expected_factor)).abs() < 1e-5,
+                "{:?}: {:?}",
+                kind,
+                color
+            );
+        }
+    }
+}
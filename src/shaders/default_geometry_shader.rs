@@ -5,13 +5,15 @@ use crate::{
     shader::{
         context::ShaderContext,
         geometry::{sample::GeometrySample, GeometryShaderFn},
+        parallax::{
+            get_parallax_mapped_uv, get_parallax_occlusion_mapped_uv, get_parallax_self_shadowing,
+        },
     },
-    texture::sample::{sample_bilinear_u8, sample_nearest_u8},
-    vec::{
-        vec2::Vec2,
-        vec3::{self, Vec3},
-        vec4::Vec4,
+    texture::sample::{
+        get_mipmap_lod, sample_bilinear_u8, sample_bilinear_vec3, sample_nearest_u8,
+        sample_nearest_vec3, sample_trilinear_lod_u8,
     },
+    vec::{vec3, vec4::Vec4},
     vertex::default_vertex_out::DefaultVertexOut,
 };
 
@@ -30,8 +32,11 @@ pub static DEFAULT_GEOMETRY_SHADER: GeometryShaderFn = |context: &ShaderContext,
         depth: interpolant.depth,
         roughness: 1.0,
         metallic: 0.0,
+        reflectivity: 0.0,
         albedo: vec3::ONES,
         ambient_factor: 1.0,
+        contact_shadow: 0.0,
+        baked_irradiance: None,
         specular_color: vec3::ONES,
         specular_exponent: 8,
         emissive_color: Default::default(),
@@ -55,94 +60,65 @@ pub static DEFAULT_GEOMETRY_SHADER: GeometryShaderFn = |context: &ShaderContext,
 
                         let displacement = r as f32 / 255.0;
 
-                        // Modify sample UV based on height map, if
-                        // necessary, before proceeding.
-
-                        static LAYER_COUNT_MIN: f32 = 8.0;
-                        static LAYER_COUNT_MAX: f32 = 32.0;
-
-                        static Z_FORWARD_TANGENT_SPACE: Vec3 = vec3::FORWARD;
-
-                        let get_parallax_mapped_uv =
-                            |uv: Vec2,
-                             fragment_to_view_direction_tangent_space: Vec3,
-                             displacement: f32|
-                             -> Vec2 {
-                                // Scale the view-direction vector (in tangent
-                                // space) by the sampled displacement, modulated
-                                // by a scaling factor.
-
-                                let alpha = Z_FORWARD_TANGENT_SPACE
-                                    .dot(fragment_to_view_direction_tangent_space)
-                                    .max(0.0);
-
-                                let layer_count = (LAYER_COUNT_MAX
-                                    - (LAYER_COUNT_MAX - LAYER_COUNT_MIN) * alpha)
-                                    .floor();
-
-                                let layer_depth: f32 = 1.0 / layer_count;
-
-                                let p = Vec2 {
-                                    x: fragment_to_view_direction_tangent_space.x
-                                        / fragment_to_view_direction_tangent_space.z,
-                                    y: fragment_to_view_direction_tangent_space.y
-                                        / fragment_to_view_direction_tangent_space.z,
-                                    z: 1.0,
-                                } * displacement
-                                    * material.displacement_scale;
-
-                                let uv_step = p / layer_count;
-
-                                let mut current_layer_depth = 0.0;
-                                let mut current_uv = uv;
-                                let mut current_sampled_displacement = displacement;
-
-                                while current_layer_depth < current_sampled_displacement {
-                                    // Take a step along P.
-                                    current_uv -= uv_step;
-
-                                    // Re-sample the displacement map at this new UV coordinate.
-                                    current_sampled_displacement =
-                                        sample_nearest_u8(current_uv, map, None).0 as f32 / 255.0;
-
-                                    // Update "current" layer depth for our next loop iteration.
-                                    current_layer_depth += layer_depth;
-                                }
-
-                                // Interpolate between the sampled displacements
-                                // at the previous layer and the current layer.
-
-                                let previous_uv = current_uv + uv_step;
-
-                                let after_depth =
-                                    current_sampled_displacement - current_layer_depth;
-
-                                let before_depth =
-                                    (sample_nearest_u8(previous_uv, map, None).0 as f32 / 255.0)
-                                        - current_layer_depth
-                                        + layer_depth;
-
-                                let alpha = after_depth / (after_depth - before_depth);
-
-                                previous_uv * alpha + current_uv * (1.0 - alpha)
-                            };
-
                         if displacement != 0.0 {
                             let fragment_to_view_direction_tangent_space =
                                 (out.tangent_space_info.view_position
                                     - out.tangent_space_info.fragment_position)
                                     .as_normal();
 
-                            out.uv = get_parallax_mapped_uv(
-                                out.uv,
-                                fragment_to_view_direction_tangent_space,
-                                displacement,
-                            );
+                            // Modify sample UV based on height map, if
+                            // necessary, before proceeding.
+
+                            out.uv = if options.parallax_occlusion_active {
+                                get_parallax_occlusion_mapped_uv(
+                                    out.uv,
+                                    fragment_to_view_direction_tangent_space,
+                                    map,
+                                    material.displacement_scale,
+                                    material.parallax_steps,
+                                )
+                            } else {
+                                get_parallax_mapped_uv(
+                                    out.uv,
+                                    fragment_to_view_direction_tangent_space,
+                                    displacement,
+                                    map,
+                                    material.displacement_scale,
+                                )
+                            };
 
                             if out.uv.x < 0.0 || out.uv.x > 1.0 || out.uv.y < 0.0 || out.uv.y > 1.0
                             {
                                 return None;
                             }
+
+                            // Self-shadowing, from the directional light.
+
+                            if let (true, Some(handle)) = (
+                                options.parallax_occlusion_active
+                                    && options.parallax_self_shadowing_active,
+                                &context.directional_light,
+                            ) {
+                                if let Ok(entry) = resources.directional_light.borrow().get(handle)
+                                {
+                                    let light = &entry.item;
+
+                                    let fragment_to_light_direction_tangent_space = (*light
+                                        .get_direction()
+                                        * -1.0
+                                        * interpolant.tangent_space_info.tbn_inverse)
+                                        .to_vec3()
+                                        .as_normal();
+
+                                    out.contact_shadow = get_parallax_self_shadowing(
+                                        out.uv,
+                                        fragment_to_light_direction_tangent_space,
+                                        map,
+                                        material.displacement_scale,
+                                        material.parallax_steps,
+                                    );
+                                }
+                            }
                         }
                     }
                     Err(err) => {
@@ -323,6 +299,11 @@ pub static DEFAULT_GEOMETRY_SHADER: GeometryShaderFn = |context: &ShaderContext,
                 }
             }
 
+            // Cutout fragments that survived the alpha test are opaque.
+            if material.alpha_cutoff.is_some() {
+                out.alpha = 1.0;
+            }
+
             // Albedo color
             match (options.albedo_mapping_active, material.albedo_map) {
                 (true, Some(albedo_map_handle)) => {
@@ -330,7 +311,11 @@ pub static DEFAULT_GEOMETRY_SHADER: GeometryShaderFn = |context: &ShaderContext,
                         Ok(entry) => {
                             let map = &entry.item;
 
-                            let (r, g, b) = if options.bilinear_active {
+                            let (r, g, b) = if options.trilinear_active {
+                                let lod = get_mipmap_lod(interpolant.uv_footprint, map);
+
+                                sample_trilinear_lod_u8(out.uv, map, lod)
+                            } else if options.bilinear_active {
                                 sample_bilinear_u8(out.uv, map, None)
                             } else {
                                 sample_nearest_u8(out.uv, map, None)
@@ -411,6 +396,30 @@ pub static DEFAULT_GEOMETRY_SHADER: GeometryShaderFn = |context: &ShaderContext,
                 }
             }
 
+            // Reflectivity
+            out.reflectivity = material.reflectivity;
+
+            // Baked (lightmap) irradiance
+            if let Some(lightmap_handle) = &material.lightmap {
+                match resources.texture_vec3.borrow().get(lightmap_handle) {
+                    Ok(entry) => {
+                        let map = &entry.item;
+
+                        out.baked_irradiance = Some(if options.bilinear_active {
+                            sample_bilinear_vec3(interpolant.lightmap_uv, map, None)
+                        } else {
+                            sample_nearest_vec3(interpolant.lightmap_uv, map, None)
+                        });
+                    }
+                    Err(err) => {
+                        panic!(
+                            "Failed to get TextureMap from Arena: {:?}: {}",
+                            lightmap_handle, err
+                        )
+                    }
+                }
+            }
+
             // // Sheen
             // match material.sheen_map {
             //     Some(sheen_map_handle) => match resources.texture.borrow().get(&sheen_map_handle) {
@@ -64,6 +64,7 @@ pub static DEFAULT_VERTEX_SHADER: VertexShaderFn = |context: &ShaderContext,
 
     out.color = v.color;
     out.uv = v.uv;
+    out.lightmap_uv = v.lightmap_uv;
 
     out
 };
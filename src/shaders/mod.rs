@@ -9,4 +9,6 @@ pub mod directional_shadow_map_fragment_shader;
 pub mod directional_shadow_map_geometry_shader;
 pub mod directional_shadow_map_vertex_shader;
 
+pub mod scrolling_uv_fragment_shader;
+
 pub mod debug_shaders;
@@ -0,0 +1,68 @@
+#![allow(non_upper_case_globals)]
+
+use crate::{
+    color::Color,
+    scene::resources::SceneResources,
+    shader::{
+        context::ShaderContext, fragment::FragmentShaderFn, geometry::sample::GeometrySample,
+    },
+    texture::sample::sample_bilinear_u8,
+    vec::{vec2::Vec2, vec3::Vec3},
+};
+
+// Scroll speed, in UV units per second; may be overridden by user code.
+pub static U_SCROLL_SPEED: &str = "u_scroll_speed";
+
+static DEFAULT_SCROLL_SPEED: Vec3 = Vec3 {
+    x: 0.25,
+    y: 0.0,
+    z: 0.0,
+};
+
+pub static ScrollingUvFragmentShader: FragmentShaderFn =
+    |context: &ShaderContext, resources: &SceneResources, sample: &GeometrySample| -> Vec3 {
+        // Offsets this fragment's UV by the current time, wrapping to [0, 1).
+
+        let speed = context
+            .uniforms
+            .get_vec3(U_SCROLL_SPEED)
+            .unwrap_or(DEFAULT_SCROLL_SPEED);
+
+        let time = context.get_time();
+
+        let uv = Vec2 {
+            x: (sample.uv.x + speed.x * time).rem_euclid(1.0),
+            y: (sample.uv.y + speed.y * time).rem_euclid(1.0),
+            z: sample.uv.z,
+        };
+
+        // Samples the active material's albedo map at the scrolled coordinate;
+        // materials without an albedo map keep their (unscrolled) albedo.
+
+        let albedo_map_handle = context.active_material.and_then(|handle| {
+            resources
+                .material
+                .borrow()
+                .get(&handle)
+                .ok()
+                .and_then(|entry| entry.item.albedo_map)
+        });
+
+        match albedo_map_handle {
+            Some(handle) => match resources.texture_u8.borrow().get(&handle) {
+                Ok(entry) => {
+                    let map = &entry.item;
+
+                    let (r, g, b) = sample_bilinear_u8(uv, map, None);
+
+                    let mut color = Color::rgb(r, g, b).to_vec3() / 255.0;
+
+                    color.srgb_to_linear();
+
+                    color
+                }
+                Err(err) => panic!("Failed to get TextureMap from Arena: {:?}: {}", handle, err),
+            },
+            None => sample.albedo,
+        }
+    };
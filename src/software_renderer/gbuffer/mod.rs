@@ -1,13 +1,17 @@
 use crate::{buffer::Buffer2D, shader::geometry::sample::GeometrySample};
 
+// Also stores the fragment shader override (a shader registry index), if any,
+// for each sample.
 #[derive(Default, Debug, Clone)]
-pub struct GBuffer(pub Buffer2D<GeometrySample>);
+pub struct GBuffer(pub Buffer2D<GeometrySample>, pub Buffer2D<Option<usize>>);
 
 impl GBuffer {
     pub fn new(width: u32, height: u32) -> Self {
         let buffer = Buffer2D::new(width, height, None);
 
-        Self(buffer)
+        let fragment_shaders = Buffer2D::new(width, height, None);
+
+        Self(buffer, fragment_shaders)
     }
 
     pub fn clear(&mut self) {
@@ -22,12 +26,24 @@ impl GBuffer {
         self.0.get(x, y)
     }
 
-    pub fn set(&mut self, x: u32, y: u32, mut sample: GeometrySample) {
+    pub fn set(
+        &mut self,
+        x: u32,
+        y: u32,
+        mut sample: GeometrySample,
+        fragment_shader: Option<usize>,
+    ) {
         // Sets the `stencil` flag.
 
         sample.stencil = true;
 
         self.0.set(x, y, sample);
+
+        self.1.set(x, y, fragment_shader);
+    }
+
+    pub fn get_fragment_shader_at(&self, index: usize) -> Option<usize> {
+        *self.1.get_at(index)
     }
 
     pub fn iter(&self) -> std::slice::Iter<'_, GeometrySample> {
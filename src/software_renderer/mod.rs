@@ -5,12 +5,17 @@ use profile::SoftwareRendererCycleCounter;
 
 use crate::{
     buffer::{framebuffer::Framebuffer, Buffer2D},
-    color::Color,
+    color::{space::ColorSpace, Color},
     geometry::primitives::{aabb::AABB, ray::Ray},
     material::Material,
     matrix::Mat4,
     render::{
-        options::{shader::RenderShaderOptions, RenderOptions, RenderPassFlag},
+        culling::FaceCullingReject,
+        options::{
+            rasterizer::AttributeInterpolation, shader::RenderShaderOptions, RenderOptions,
+            RenderPassFlag,
+        },
+        stats::RenderStats,
         viewport::RenderViewport,
         Renderer,
     },
@@ -28,6 +33,7 @@ use crate::{
         context::ShaderContext,
         fragment::FragmentShaderFn,
         geometry::{sample::GeometrySample, GeometryShaderFn},
+        registry::ShaderRegistry,
         vertex::VertexShaderFn,
     },
     shaders::{
@@ -41,11 +47,14 @@ use crate::{
     vertex::default_vertex_out::DefaultVertexOut,
 };
 
-use self::gbuffer::GBuffer;
+use self::{gbuffer::GBuffer, zbuffer::DepthTestMethod};
 
 use super::{mesh::Mesh, vec::vec3::Vec3};
 
-use pass::ssao_pass::{make_4x4_tangent_space_rotations, make_hemisphere_kernel, KERNEL_SIZE};
+use pass::{
+    sorted_transparency_pass::TransparentTriangle,
+    ssao_pass::{make_4x4_tangent_space_rotations, make_hemisphere_kernel, KERNEL_SIZE},
+};
 
 mod gbuffer;
 mod pass;
@@ -54,12 +63,36 @@ mod profile;
 
 pub mod zbuffer;
 
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+enum DepthPrepassStage {
+    #[default]
+    Inactive,
+    // Entities write depth, without being shaded.
+    WritingDepth,
+    // Opaque entities are shaded where their depth equals the prepass depth.
+    Shading,
+}
+
 #[derive(Debug, Clone)]
 pub struct SoftwareRenderer {
     pub options: RenderOptions,
     pub cycle_counters: CycleCounters,
+    pub stats: RenderStats,
     pub shader_options: RenderShaderOptions,
+    // The framebuffer that we rasterize into.
     framebuffer: Option<Rc<RefCell<Framebuffer>>>,
+    // The framebuffer bound by the user; while supersampling, `framebuffer`
+    // is an internal, upscaled copy of it.
+    target_framebuffer: Option<Rc<RefCell<Framebuffer>>>,
+    supersample_factor: u32,
+    // Lines (in NDC space) drawn once the frame's geometry has been rasterized.
+    depth_tested_lines: Vec<(Vec3, Vec3, Color)>,
+    // Triangles with transparent materials, blended once the frame's opaque
+    // geometry has been shaded.
+    transparent_triangles: Vec<TransparentTriangle>,
+    is_active_material_transparent: bool,
+    is_rendering_transparent_triangles: bool,
+    depth_prepass_stage: DepthPrepassStage,
     viewport: RenderViewport,
     g_buffer: Option<GBuffer>,
     alpha_accumulation_buffer: Buffer2D<Vec4>,
@@ -74,6 +107,9 @@ pub struct SoftwareRenderer {
     alpha_shader: AlphaShaderFn,
     geometry_shader: GeometryShaderFn,
     fragment_shader: FragmentShaderFn,
+    fragment_shader_name: Option<String>,
+    fragment_shader_override: Option<usize>,
+    pub shader_registry: ShaderRegistry,
 }
 
 impl Renderer for SoftwareRenderer {
@@ -95,6 +131,24 @@ impl Renderer for SoftwareRenderer {
                 .start();
         }
 
+        self.stats = Default::default();
+
+        self.depth_tested_lines.clear();
+
+        self.transparent_triangles.clear();
+
+        self.depth_prepass_stage = DepthPrepassStage::Inactive;
+
+        self.shader_context.borrow_mut().fog = self.options.fog;
+
+        // Re-allocate our (supersampled) framebuffer if the factor changed.
+
+        if self.options.supersample_factor.max(1) != self.supersample_factor
+            && self.target_framebuffer.is_some()
+        {
+            self.bind_framebuffer(self.target_framebuffer.clone());
+        }
+
         // Clear the bound framebuffer.
 
         if let Some(rc) = &self.framebuffer {
@@ -156,14 +210,34 @@ impl Renderer for SoftwareRenderer {
                 self.do_ssao_pass();
             }
 
+            // Screen-space contact shadow pass.
+
+            if self
+                .options
+                .render_pass_flags
+                .contains(RenderPassFlag::ContactShadows)
+            {
+                self.do_contact_shadow_pass();
+            }
+
             // Deferred lighting pass.
 
             self.do_deferred_lighting_pass();
 
+            // Screen-space reflections pass.
+
+            if self.options.render_pass_flags.contains(RenderPassFlag::Ssr) {
+                self.do_ssr_pass();
+            }
+
             // Semi-transparent fragment pass.
 
             self.do_weighted_blended_pass();
 
+            // Sorted (back-to-front) transparent triangle pass.
+
+            self.do_sorted_transparency_pass();
+
             // Bloom pass (with or without dirt mask).
 
             if self
@@ -173,6 +247,10 @@ impl Renderer for SoftwareRenderer {
             {
                 self.do_bloom_pass();
             }
+        } else {
+            // Sorted (back-to-front) transparent triangle pass.
+
+            self.do_sorted_transparency_pass();
         }
 
         // Tone-mapping pass (or basic blit).
@@ -197,6 +275,10 @@ impl Renderer for SoftwareRenderer {
             }
         }
 
+        // Draw any depth-tested lines into the forward color buffer.
+
+        self.flush_depth_tested_lines();
+
         // Combine the forward and deferred (HDR) color buffers into the default
         // color buffer.
 
@@ -224,6 +306,10 @@ impl Renderer for SoftwareRenderer {
             }
         }
 
+        // Resolve supersampled color and depth into the bound framebuffer.
+
+        self.do_downsample_pass();
+
         #[cfg(feature = "debug_cycle_counts")]
         {
             self.cycle_counters
@@ -234,6 +320,14 @@ impl Renderer for SoftwareRenderer {
         }
     }
 
+    fn begin_depth_prepass(&mut self) {
+        self.depth_prepass_stage = DepthPrepassStage::WritingDepth;
+    }
+
+    fn end_depth_prepass(&mut self) {
+        self.depth_prepass_stage = DepthPrepassStage::Shading;
+    }
+
     fn render_point(
         &mut self,
         point_world_space: Vec3,
@@ -270,6 +364,10 @@ impl Renderer for SoftwareRenderer {
         self._render_camera(camera, color)
     }
 
+    fn render_camera_frustum(&mut self, camera: &Camera, color: Option<Color>) {
+        self._render_camera_frustum(camera, color)
+    }
+
     fn render_ambient_light(&mut self, transform: &Mat4, light: &AmbientLight) {
         self._render_ambient_light(transform, light)
     }
@@ -344,7 +442,15 @@ impl SoftwareRenderer {
         SoftwareRenderer {
             options,
             cycle_counters: Default::default(),
+            stats: Default::default(),
             framebuffer,
+            target_framebuffer: None,
+            supersample_factor: 1,
+            depth_tested_lines: vec![],
+            transparent_triangles: vec![],
+            is_active_material_transparent: false,
+            is_rendering_transparent_triangles: false,
+            depth_prepass_stage: Default::default(),
             viewport,
             g_buffer: None,
             ssao_buffer: None,
@@ -360,6 +466,9 @@ impl SoftwareRenderer {
             geometry_shader,
             shader_options,
             fragment_shader,
+            fragment_shader_name: None,
+            fragment_shader_override: None,
+            shader_registry: Default::default(),
         }
     }
 
@@ -373,9 +482,139 @@ impl SoftwareRenderer {
 
     pub fn set_fragment_shader(&mut self, shader: FragmentShaderFn) {
         self.fragment_shader = shader;
+
+        self.fragment_shader_name = None;
+    }
+
+    pub fn get_fragment_shader_name(&self) -> Option<&String> {
+        self.fragment_shader_name.as_ref()
+    }
+
+    // Binds the fragment shader registered (in `shader_registry`) as `name`.
+    pub fn set_fragment_shader_by_name(&mut self, name: &str) -> Result<(), String> {
+        self.fragment_shader = self.shader_registry.get_fragment_shader(name)?;
+
+        self.fragment_shader_name.replace(name.to_string());
+
+        Ok(())
+    }
+
+    // Returns the registry index of the material's own fragment shader, if it
+    // names one (unknown names fall back to the renderer's shader).
+    fn get_material_fragment_shader(&self, material: &Option<Handle>) -> Option<usize> {
+        let handle = material.as_ref()?;
+
+        let material_arena = self.scene_resources.material.borrow();
+
+        let name = material_arena
+            .get(handle)
+            .ok()?
+            .item
+            .fragment_shader
+            .clone()?;
+
+        self.shader_registry.get_fragment_shader_index(&name).ok()
+    }
+
+    // Sets the bound depth buffer's test method, returning the previous one.
+    fn swap_depth_test_method(&self, method: DepthTestMethod) -> Option<DepthTestMethod> {
+        let framebuffer = self.framebuffer.as_ref()?.borrow();
+
+        let mut depth_buffer = framebuffer.attachments.depth.as_ref()?.borrow_mut();
+
+        let original_method = *depth_buffer.get_depth_test_method();
+
+        depth_buffer.set_depth_test_method(method);
+
+        Some(original_method)
+    }
+
+    fn is_material_transparent(&self, material: &Option<Handle>) -> bool {
+        let Some(handle) = material.as_ref() else {
+            return false;
+        };
+
+        match self.scene_resources.material.borrow().get(handle) {
+            Ok(entry) => entry.item.is_transparent(),
+            Err(_) => false,
+        }
     }
 
     pub fn bind_framebuffer(&mut self, framebuffer_option: Option<Rc<RefCell<Framebuffer>>>) {
+        let supersample_factor = self.options.supersample_factor.max(1);
+
+        let render_framebuffer_option = match &framebuffer_option {
+            Some(target_rc) if supersample_factor > 1 => {
+                Some(self.get_supersampled_framebuffer(&target_rc.borrow(), supersample_factor))
+            }
+            _ => framebuffer_option.clone(),
+        };
+
+        self.target_framebuffer = framebuffer_option;
+
+        self.supersample_factor = supersample_factor;
+
+        self.bind_render_framebuffer(render_framebuffer_option);
+    }
+
+    // Returns a framebuffer `factor` times the size of `target`, reusing our
+    // current one when it already matches.
+    fn get_supersampled_framebuffer(
+        &self,
+        target: &Framebuffer,
+        factor: u32,
+    ) -> Rc<RefCell<Framebuffer>> {
+        let (width, height) = (target.width * factor, target.height * factor);
+
+        let (projection_z_near, projection_z_far) = match target.attachments.depth.as_ref() {
+            Some(depth_buffer_rc) => {
+                let depth_buffer = depth_buffer_rc.borrow();
+
+                (
+                    depth_buffer.get_projection_z_near(),
+                    depth_buffer.get_projection_z_far(),
+                )
+            }
+            None => (0.3, 100.0),
+        };
+
+        if let (Some(current_rc), Some(target_rc)) = (&self.framebuffer, &self.target_framebuffer) {
+            if !Rc::ptr_eq(current_rc, target_rc) {
+                let current = current_rc.borrow();
+
+                let is_matching_depth = match current.attachments.depth.as_ref() {
+                    Some(depth_buffer_rc) => {
+                        let depth_buffer = depth_buffer_rc.borrow();
+
+                        depth_buffer.get_projection_z_near() == projection_z_near
+                            && depth_buffer.get_projection_z_far() == projection_z_far
+                    }
+                    None => false,
+                };
+
+                if current.width == width && current.height == height && is_matching_depth {
+                    return current_rc.clone();
+                }
+            }
+        }
+
+        let mut framebuffer = Framebuffer::new(width, height);
+
+        framebuffer.complete(projection_z_near, projection_z_far);
+
+        if let (Some(source_rc), Some(target_rc)) = (
+            framebuffer.attachments.depth.as_ref(),
+            target.attachments.depth.as_ref(),
+        ) {
+            let method = *target_rc.borrow().get_depth_test_method();
+
+            source_rc.borrow_mut().set_depth_test_method(method);
+        }
+
+        Rc::new(RefCell::new(framebuffer))
+    }
+
+    fn bind_render_framebuffer(&mut self, framebuffer_option: Option<Rc<RefCell<Framebuffer>>>) {
         match &framebuffer_option {
             Some(framebuffer_rc) => {
                 let framebuffer = framebuffer_rc.borrow();
@@ -388,6 +627,10 @@ impl SoftwareRenderer {
 
                         self.viewport = RenderViewport::from_framebuffer(&framebuffer);
 
+                        self.shader_context
+                            .borrow_mut()
+                            .set_resolution(width, height);
+
                         let should_reallocate_g_buffer = match &self.g_buffer {
                             Some(g_buffer) => {
                                 g_buffer.0.width != width || g_buffer.0.height != height
@@ -436,6 +679,7 @@ impl SoftwareRenderer {
             }
             None => {
                 self.framebuffer = None;
+                self.target_framebuffer = None;
                 self.g_buffer = None;
                 self.ssao_buffer = None;
                 self.ssao_blur_buffer = None;
@@ -459,8 +703,19 @@ impl SoftwareRenderer {
 
         // Restore linear space interpolant.
 
+        let w = 1.0 / interpolant.position_projection_space.w;
+
         let mut linear_space_interpolant =
-            *interpolant * (1.0 / interpolant.position_projection_space.w);
+            match self.options.rasterizer_options.attribute_interpolation {
+                AttributeInterpolation::PerspectiveCorrect => *interpolant * w,
+                AttributeInterpolation::Affine => {
+                    let mut affine_interpolant = *interpolant;
+
+                    affine_interpolant.position_projection_space *= w;
+
+                    affine_interpolant
+                }
+            };
 
         let linear_space_z = linear_space_interpolant.position_projection_space.z;
 
@@ -475,19 +730,41 @@ impl SoftwareRenderer {
                 return;
             }
 
+            // Depth prepass (skips shading).
+
+            if self.depth_prepass_stage == DepthPrepassStage::WritingDepth {
+                depth_buffer.set(x, y, non_linear_z);
+
+                return;
+            }
+
+            self.stats.fragments_shaded += 1;
+
             // Geometry shader.
 
             linear_space_interpolant.depth = depth_buffer.get_normalized(linear_space_z);
 
-            if let Some(sample) = (self.geometry_shader)(
+            if let Some(mut sample) = (self.geometry_shader)(
                 &shader_context,
                 &self.scene_resources,
                 &self.shader_options,
                 &linear_space_interpolant,
             ) {
+                self.stats.fragments_written += 1;
+
+                // Two-sided lighting.
+
+                if self.should_light_both_sides() && linear_space_interpolant.back_facing > 0.5 {
+                    sample.flip_normal();
+                }
+
                 // Opaque vs. semi-transparent paths.
 
-                if sample.alpha > 1.0 - EPSILON {
+                if self.is_rendering_transparent_triangles {
+                    // Blend over the shaded opaque pass, skipping the depth buffer.
+
+                    self.blend_transparent_fragment(&framebuffer, &shader_context, x, y, &sample);
+                } else if sample.alpha > 1.0 - EPSILON {
                     // Write non-linear depth to the depth buffer.
 
                     depth_buffer.set(x, y, non_linear_z);
@@ -504,7 +781,7 @@ impl SoftwareRenderer {
                         .contains(RenderPassFlag::DeferredLighting)
                     {
                         if let Some(g_buffer) = self.g_buffer.as_mut() {
-                            g_buffer.set(x, y, sample);
+                            g_buffer.set(x, y, sample, self.fragment_shader_override);
                         }
                     } else if let Some(forward_buffer_rc) =
                         framebuffer.attachments.forward_ldr.as_ref()
@@ -515,6 +792,7 @@ impl SoftwareRenderer {
                             &shader_context,
                             &self.scene_resources,
                             &sample,
+                            self.fragment_shader_override,
                         );
 
                         let ldr_color = self.get_ldr_color(hdr_color);
@@ -559,8 +837,12 @@ impl SoftwareRenderer {
         shader_context: &ShaderContext,
         sample: GeometrySample,
     ) -> (Vec4, f32) {
-        let hdr_color =
-            self.get_hdr_color_for_sample(shader_context, &self.scene_resources, &sample);
+        let hdr_color = self.get_hdr_color_for_sample(
+            shader_context,
+            &self.scene_resources,
+            &sample,
+            self.fragment_shader_override,
+        );
 
         let alpha = sample.alpha;
 
@@ -575,6 +857,30 @@ impl SoftwareRenderer {
         (accumulation, revealage)
     }
 
+    // With tone mapping, the color buffer holds gamma-encoded (sRGB) colors;
+    // otherwise, it holds linear colors.
+    pub fn get_color_space(&self) -> ColorSpace {
+        if self
+            .options
+            .render_pass_flags
+            .contains(RenderPassFlag::ToneMapping)
+        {
+            ColorSpace::Srgb
+        } else {
+            ColorSpace::Linear
+        }
+    }
+
+    fn should_light_both_sides(&self) -> bool {
+        let rasterizer_options = &self.options.rasterizer_options;
+
+        rasterizer_options.two_sided_lighting
+            && matches!(
+                rasterizer_options.face_culling_strategy.reject,
+                FaceCullingReject::None
+            )
+    }
+
     fn get_ldr_color(&self, hdr_color: Vec3) -> Color {
         if self
             .options
@@ -592,11 +898,17 @@ impl SoftwareRenderer {
         shader_context: &ShaderContext,
         scene_resources: &SceneResources,
         sample: &GeometrySample,
+        fragment_shader_override: Option<usize>,
     ) -> Vec3 {
         let render_pass_flags = self.get_options().render_pass_flags;
 
         if render_pass_flags.contains(RenderPassFlag::Lighting) {
-            (self.fragment_shader)(shader_context, scene_resources, sample)
+            let fragment_shader = match fragment_shader_override {
+                Some(index) => self.shader_registry.get_fragment_shader_at(index),
+                None => self.fragment_shader,
+            };
+
+            fragment_shader(shader_context, scene_resources, sample)
         } else if render_pass_flags.contains(RenderPassFlag::Ssao) {
             sample.albedo * sample.ambient_factor
         } else {
@@ -614,3 +926,611 @@ impl SoftwareRenderer {
         Color::from_vec3(tone_mapped * 255.0)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        buffer::framebuffer::Framebuffer,
+        color::{self, Color},
+        material::Material,
+        matrix::Mat4,
+        mesh::primitive::{billboard, plane},
+        render::{culling::FaceCullingReject, options::RenderPassFlag, Renderer},
+        scene::{camera::Camera, resources::SceneResources},
+        shader::{context::ShaderContext, geometry::sample::GeometrySample},
+        shaders::default_vertex_shader::DEFAULT_VERTEX_SHADER,
+        software_renderer::zbuffer::MAX_DEPTH,
+        vec::{vec3::Vec3, vec4::Vec4},
+        vertex::default_vertex_out::DefaultVertexOut,
+    };
+
+    use super::SoftwareRenderer;
+
+    #[test]
+    fn switching_shaders_by_name_rebinds_fragment_shader() {
+        let shader_context: Rc<RefCell<ShaderContext>> = Default::default();
+        let scene_resources: Rc<SceneResources> = Default::default();
+
+        let mut renderer = SoftwareRenderer::new(
+            shader_context.clone(),
+            scene_resources.clone(),
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        renderer
+            .shader_registry
+            .register_fragment_shader("red", |_, _, _| Vec3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            });
+
+        renderer
+            .shader_registry
+            .register_fragment_shader("blue", |_, _, _| Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            });
+
+        let shade = |renderer: &SoftwareRenderer| -> Vec3 {
+            renderer.get_hdr_color_for_sample(
+                &shader_context.borrow(),
+                &scene_resources,
+                &GeometrySample::default(),
+                None,
+            )
+        };
+
+        renderer.set_fragment_shader_by_name("red").unwrap();
+
+        assert_eq!(shade(&renderer).x, 1.0);
+
+        renderer.set_fragment_shader_by_name("blue").unwrap();
+
+        assert_eq!(shade(&renderer).z, 1.0);
+        assert_eq!(renderer.get_fragment_shader_name().unwrap(), "blue");
+
+        assert!(renderer.set_fragment_shader_by_name("missing").is_err());
+    }
+
+    #[test]
+    fn entities_are_shaded_with_their_material_shaders() {
+        let red = Vec3 {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        };
+
+        let blue = Vec3 {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        };
+
+        let scene_resources: Rc<SceneResources> = Default::default();
+
+        let (toon_material, pbr_material) = {
+            let mut materials = scene_resources.material.borrow_mut();
+
+            let mut toon = Material::new("toon".to_string());
+            toon.fragment_shader = Some("toon".to_string());
+
+            let mut pbr = Material::new("pbr".to_string());
+            pbr.fragment_shader = Some("pbr".to_string());
+
+            (Some(materials.insert(toon)), Some(materials.insert(pbr)))
+        };
+
+        let mut renderer = SoftwareRenderer::new(
+            Default::default(),
+            scene_resources,
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        renderer
+            .shader_registry
+            .register_fragment_shader("toon", |_, _, _| Vec3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            });
+
+        renderer
+            .shader_registry
+            .register_fragment_shader("pbr", |_, _, _| Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            });
+
+        let mut framebuffer = Framebuffer::new(2, 1);
+
+        framebuffer.complete(0.3, 100.0);
+
+        let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+        renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+        // Each entity writes one G-buffer sample, tagged with its shader.
+
+        for (x, material) in [toon_material, pbr_material].iter().enumerate() {
+            let fragment_shader = renderer.get_material_fragment_shader(material);
+
+            renderer.g_buffer.as_mut().unwrap().set(
+                x as u32,
+                0,
+                GeometrySample::default(),
+                fragment_shader,
+            );
+        }
+
+        renderer.do_deferred_lighting_pass();
+
+        let framebuffer = framebuffer_rc.borrow();
+
+        let deferred_buffer = framebuffer
+            .attachments
+            .deferred_hdr
+            .as_ref()
+            .unwrap()
+            .borrow();
+
+        assert_eq!(*deferred_buffer.get(0, 0), red);
+        assert_eq!(*deferred_buffer.get(1, 0), blue);
+    }
+
+    #[test]
+    fn fragments_below_alpha_cutoff_are_discarded() {
+        let shader_context: Rc<RefCell<ShaderContext>> = Default::default();
+        let scene_resources: Rc<SceneResources> = Default::default();
+
+        let (cut_material, kept_material) = {
+            let mut materials = scene_resources.material.borrow_mut();
+
+            let mut cut = Material::new("cut".to_string());
+            cut.transparency = 0.8;
+            cut.alpha_cutoff = Some(0.5);
+
+            let mut kept = Material::new("kept".to_string());
+            kept.transparency = 0.3;
+            kept.alpha_cutoff = Some(0.5);
+
+            (Some(materials.insert(cut)), Some(materials.insert(kept)))
+        };
+
+        let mut renderer = SoftwareRenderer::new(
+            shader_context.clone(),
+            scene_resources,
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        let mut framebuffer = Framebuffer::new(2, 1);
+
+        framebuffer.complete(0.3, 100.0);
+
+        let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+        renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+        // Submits one fragment per material.
+
+        for (x, material) in [cut_material, kept_material].into_iter().enumerate() {
+            shader_context.borrow_mut().set_active_material(material);
+
+            let mut interpolant = DefaultVertexOut {
+                position_projection_space: Vec4::new(Vec3::ones(), 1.0),
+                ..Default::default()
+            };
+
+            renderer.submit_fragment(x as u32, 0, &mut interpolant);
+        }
+
+        let framebuffer = framebuffer_rc.borrow();
+
+        let depth_buffer = framebuffer.attachments.depth.as_ref().unwrap().borrow();
+
+        // The cut fragment left the depth buffer untouched, while the kept
+        // fragment was written as opaque.
+
+        assert_eq!(*depth_buffer.buffer.get(0, 0), MAX_DEPTH);
+        assert!(*depth_buffer.buffer.get(1, 0) < MAX_DEPTH);
+    }
+
+    #[test]
+    fn back_faces_are_lit_with_a_viewer_facing_normal_when_culling_is_disabled() {
+        let shader_context: Rc<RefCell<ShaderContext>> = Default::default();
+
+        // Viewer (and light) in front of the surface; the surface's normal
+        // faces away.
+
+        let view_position = Vec3 {
+            x: 0.0,
+            y: 0.0,
+            z: -5.0,
+        };
+
+        shader_context
+            .borrow_mut()
+            .set_view_position(Vec4::new(view_position, 1.0));
+
+        let direction_to_light = view_position.as_normal();
+
+        let back_facing_normal = -direction_to_light;
+
+        let mut renderer = SoftwareRenderer::new(
+            shader_context,
+            Default::default(),
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        let mut framebuffer = Framebuffer::new(1, 1);
+
+        framebuffer.complete(0.3, 100.0);
+
+        renderer.bind_framebuffer(Some(Rc::new(RefCell::new(framebuffer))));
+
+        let mut shade = |reject: FaceCullingReject, back_facing: f32| -> f32 {
+            renderer
+                .options
+                .rasterizer_options
+                .face_culling_strategy
+                .reject = reject;
+
+            renderer.begin_frame();
+
+            let mut interpolant = DefaultVertexOut {
+                position_projection_space: Vec4::new(Vec3::ones(), 1.0),
+                normal_world_space: back_facing_normal,
+                back_facing,
+                ..Default::default()
+            };
+
+            renderer.submit_fragment(0, 0, &mut interpolant);
+
+            let sample = renderer.g_buffer.as_ref().unwrap().0.get(0, 0);
+
+            sample.normal_world_space.dot(direction_to_light)
+        };
+
+        assert_eq!(shade(FaceCullingReject::None, 1.0), 1.0);
+
+        // With culling enabled, the normal is left untouched.
+
+        assert_eq!(shade(FaceCullingReject::Frontfaces, 1.0), -1.0);
+
+        // Facing follows the triangle's winding, not its (interpolated)
+        // normal, e.g., near a smooth-shaded silhouette.
+
+        assert_eq!(shade(FaceCullingReject::None, 0.0), -1.0);
+    }
+
+    #[test]
+    fn supersampled_frames_are_box_filtered_into_the_bound_framebuffer() {
+        let mut renderer = SoftwareRenderer::new(
+            Default::default(),
+            Default::default(),
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        renderer.options.supersample_factor = 2;
+
+        let mut framebuffer = Framebuffer::new(2, 1);
+
+        framebuffer.complete(0.3, 100.0);
+
+        let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+        renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+        // We rasterize into a framebuffer twice the size, which is reused
+        // when re-binding the same target.
+
+        let supersampled_rc = renderer.framebuffer.clone().unwrap();
+
+        assert_eq!(
+            (
+                supersampled_rc.borrow().width,
+                supersampled_rc.borrow().height
+            ),
+            (4, 2)
+        );
+
+        renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+        assert!(Rc::ptr_eq(
+            renderer.framebuffer.as_ref().unwrap(),
+            &supersampled_rc
+        ));
+
+        // Covers half of the first pixel's samples.
+
+        {
+            let supersampled = supersampled_rc.borrow();
+
+            let mut color_buffer = supersampled
+                .attachments
+                .color
+                .as_ref()
+                .unwrap()
+                .borrow_mut();
+
+            color_buffer.set(0, 0, color::WHITE.to_u32());
+            color_buffer.set(1, 1, color::WHITE.to_u32());
+
+            let mut depth_buffer = supersampled
+                .attachments
+                .depth
+                .as_ref()
+                .unwrap()
+                .borrow_mut();
+
+            depth_buffer.set(1, 1, 0.25);
+        }
+
+        renderer.do_downsample_pass();
+
+        let framebuffer = framebuffer_rc.borrow();
+
+        let color_buffer = framebuffer.attachments.color.as_ref().unwrap().borrow();
+
+        assert_eq!(
+            *color_buffer.get(0, 0),
+            Color::rgba(128, 128, 128, 128).to_u32()
+        );
+        assert_eq!(*color_buffer.get(1, 0), 0);
+
+        let depth_buffer = framebuffer.attachments.depth.as_ref().unwrap().borrow();
+
+        assert_eq!(*depth_buffer.buffer.get(0, 0), 0.25);
+        assert_eq!(*depth_buffer.buffer.get(1, 0), MAX_DEPTH);
+    }
+
+    #[test]
+    fn transparent_quads_are_blended_back_to_front() {
+        let camera = Camera::from_perspective(
+            Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            Default::default(),
+            75.0,
+            1.0,
+        );
+
+        let shader_context: Rc<RefCell<ShaderContext>> = Default::default();
+
+        camera.update_shader_context(&mut shader_context.borrow_mut());
+
+        let scene_resources: Rc<SceneResources> = Default::default();
+
+        let (near_material, far_material) = {
+            let mut materials = scene_resources.material.borrow_mut();
+
+            let mut near = Material::new("near".to_string());
+            near.albedo = Vec3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            };
+            near.transparency = 0.5;
+
+            let mut far = Material::new("far".to_string());
+            far.albedo = Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            };
+            far.transparency = 0.5;
+
+            (Some(materials.insert(near)), Some(materials.insert(far)))
+        };
+
+        let mut renderer = SoftwareRenderer::new(
+            shader_context,
+            scene_resources,
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        renderer.options.render_pass_flags =
+            RenderPassFlag::Rasterization | RenderPassFlag::DeferredLighting;
+
+        renderer
+            .options
+            .rasterizer_options
+            .face_culling_strategy
+            .reject = FaceCullingReject::None;
+
+        let mut framebuffer = Framebuffer::new(64, 64);
+
+        framebuffer.complete(0.3, 100.0);
+
+        let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+        renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+        // Two overlapping quads, submitted front-to-back.
+
+        let quad_at = |z: f32| {
+            billboard::generate(
+                Vec3 { x: 0.0, y: 0.0, z },
+                &camera.look_vector.get_position(),
+                2.0,
+                2.0,
+            )
+        };
+
+        let (near_quad, far_quad) = (quad_at(0.0), quad_at(2.0));
+
+        renderer.begin_frame();
+
+        renderer.render_entity(&Mat4::identity(), &None, &near_quad, &near_material);
+        renderer.render_entity(&Mat4::identity(), &None, &far_quad, &far_material);
+
+        renderer.end_frame();
+
+        let framebuffer = framebuffer_rc.borrow();
+
+        let deferred_buffer = framebuffer
+            .attachments
+            .deferred_hdr
+            .as_ref()
+            .unwrap()
+            .borrow();
+
+        // Blue (over black), then red over blue.
+
+        let blended = *deferred_buffer.get(32, 32);
+
+        assert!((blended.x - 0.5).abs() < 0.01);
+        assert!(blended.y.abs() < 0.01);
+        assert!((blended.z - 0.25).abs() < 0.01);
+
+        // Neither quad wrote depth.
+
+        let depth_buffer = framebuffer.attachments.depth.as_ref().unwrap().borrow();
+
+        assert_eq!(*depth_buffer.buffer.get(32, 32), MAX_DEPTH);
+    }
+
+    #[test]
+    fn ssr_pass_adds_reflections_to_a_reflective_floor() {
+        let camera = Camera::from_perspective(
+            Vec3 {
+                x: 0.0,
+                y: 1.0,
+                z: -8.0,
+            },
+            Default::default(),
+            75.0,
+            1.0,
+        );
+
+        let shader_context: Rc<RefCell<ShaderContext>> = Default::default();
+
+        camera.update_shader_context(&mut shader_context.borrow_mut());
+
+        let scene_resources: Rc<SceneResources> = Default::default();
+
+        let (floor_material, wall_material) = {
+            let mut materials = scene_resources.material.borrow_mut();
+
+            let mut floor = Material::new("floor".to_string());
+            floor.albedo = Vec3::ones() * 0.25;
+            floor.reflectivity = 0.5;
+
+            let mut wall = Material::new("wall".to_string());
+            wall.albedo = Vec3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            };
+
+            (Some(materials.insert(floor)), Some(materials.insert(wall)))
+        };
+
+        let mut renderer = SoftwareRenderer::new(
+            shader_context,
+            scene_resources,
+            DEFAULT_VERTEX_SHADER,
+            |_, _, sample| sample.albedo,
+            Default::default(),
+        );
+
+        renderer
+            .options
+            .rasterizer_options
+            .face_culling_strategy
+            .reject = FaceCullingReject::None;
+
+        let mut framebuffer = Framebuffer::new(64, 64);
+
+        framebuffer.complete(0.3, 100.0);
+
+        let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+        renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+        // A red wall, standing on a reflective floor.
+
+        let floor = plane::generate(10.0, 10.0, 1, 1);
+
+        let wall = billboard::generate(
+            Vec3 {
+                x: 0.0,
+                y: 1.0,
+                z: 2.0,
+            },
+            &camera.look_vector.get_position(),
+            2.0,
+            2.0,
+        );
+
+        let mut render = |render_pass_flags| {
+            renderer.options.render_pass_flags = render_pass_flags;
+
+            renderer.begin_frame();
+
+            renderer.render_entity(&Mat4::identity(), &None, &floor, &floor_material);
+            renderer.render_entity(&Mat4::identity(), &None, &wall, &wall_material);
+
+            renderer.end_frame();
+
+            let framebuffer = framebuffer_rc.borrow();
+
+            let deferred_buffer = framebuffer
+                .attachments
+                .deferred_hdr
+                .as_ref()
+                .unwrap()
+                .borrow();
+
+            deferred_buffer.clone()
+        };
+
+        let flags = RenderPassFlag::Rasterization | RenderPassFlag::DeferredLighting;
+
+        let without_ssr = render(flags);
+        let with_ssr = render(flags | RenderPassFlag::Ssr);
+
+        // Some floor pixels now reflect the (red) wall; nothing else changed.
+
+        let g_buffer = renderer.g_buffer.as_ref().unwrap();
+
+        let mut reflecting_pixel_count = 0;
+
+        for (index, (before, after)) in without_ssr
+            .get_all()
+            .iter()
+            .zip(with_ssr.get_all().iter())
+            .enumerate()
+        {
+            if before == after {
+                continue;
+            }
+
+            assert!(g_buffer.0.get_at(index).reflectivity > 0.0);
+
+            assert!(after.x > before.x);
+
+            reflecting_pixel_count += 1;
+        }
+
+        assert!(reflecting_pixel_count > 0);
+    }
+}
@@ -1,7 +1,6 @@
 use crate::{
     animation::lerp,
     buffer::Buffer2D,
-    color,
     texture::{
         map::{TextureBuffer, TextureMap},
         sample::{sample_bilinear_u8, sample_bilinear_vec3},
@@ -24,19 +23,28 @@ impl SoftwareRenderer {
                     let mut deferred_buffer = deferred_buffer_rc.borrow_mut();
                     let mut bloom_texture_map = bloom_texture_map_rc.borrow_mut();
 
-                    // Blit the HDR color buffer to the bloom buffer's largest mipmap (level 0).
+                    // Blit the bright portion of the HDR color buffer to the
+                    // bloom buffer's largest mipmap (level 0).
 
-                    bloom_texture_map.levels[0]
-                        .0
-                        .copy(deferred_buffer.data.as_slice());
+                    let threshold = self.options.bloom_threshold;
+
+                    for (index, hdr) in deferred_buffer.data.iter().enumerate() {
+                        bloom_texture_map.levels[0]
+                            .0
+                            .set_at(index, get_bloom_contribution(*hdr, threshold));
+                    }
 
                     // Blur the bloom buffer.
 
-                    do_physically_based_bloom(&mut bloom_texture_map);
+                    do_physically_based_bloom(
+                        &mut bloom_texture_map,
+                        self.options.bloom_mip_count as usize,
+                    );
 
                     // Blend our physically based bloom back into the color buffer.
 
-                    static BLOOM_STRENGTH: f32 = 0.07;
+                    let bloom_strength = self.options.bloom_intensity;
+
                     static DIRT_MASK_INTENSITY: f32 = 20.0;
 
                     let bloom_buffer = &bloom_texture_map.levels[0].0;
@@ -68,14 +76,14 @@ impl SoftwareRenderer {
                                         r as f32 / 255.0 * DIRT_MASK_INTENSITY
                                     };
 
-                                    let blended = lerp(hdr, bloom + bloom * dirt, BLOOM_STRENGTH);
+                                    let blended = lerp(hdr, bloom + bloom * dirt, bloom_strength);
 
                                     deferred_buffer.set(x, y, blended);
                                 }
                             }
                         }
                     } else {
-                        deferred_buffer.copy_lerp(bloom_buffer.data.as_slice(), BLOOM_STRENGTH);
+                        deferred_buffer.copy_lerp(bloom_buffer.data.as_slice(), bloom_strength);
                     }
                 }
             }
@@ -84,12 +92,24 @@ impl SoftwareRenderer {
     }
 }
 
+// Scales the color down by the fraction of its luminance that exceeds the
+// threshold, preserving its hue.
+fn get_bloom_contribution(hdr: Vec3, threshold: f32) -> Vec3 {
+    let luminance = hdr.luminance();
+
+    if luminance <= threshold {
+        return Default::default();
+    }
+
+    hdr * ((luminance - threshold) / luminance)
+}
+
 fn make_mipmaps(map: &mut TextureMap<Vec3>, levels: usize) {
     let (mut width, mut height) = (map.width, map.height);
 
     // Note: `levels` counts the number of mipmaps smaller than the source map.
 
-    for level in 0..levels {
+    for _ in 0..levels {
         width /= 2;
         height /= 2;
 
@@ -107,14 +127,7 @@ fn make_mipmaps(map: &mut TextureMap<Vec3>, levels: usize) {
             map.height
         );
 
-        let fill_value = match level {
-            0 | 3 => color::RED.to_vec3() / 255.0,
-            1 | 4 => color::GREEN.to_vec3() / 255.0,
-            2 => color::BLUE.to_vec3() / 255.0,
-            _ => panic!(),
-        };
-
-        let buffer = Buffer2D::new(width, height, Some(fill_value));
+        let buffer = Buffer2D::new(width, height, None);
 
         let mipmap = TextureBuffer(buffer);
 
@@ -122,15 +135,18 @@ fn make_mipmaps(map: &mut TextureMap<Vec3>, levels: usize) {
     }
 }
 
-fn do_physically_based_bloom(map: &mut TextureMap<Vec3>) {
-    // 1. Ensure that mipmaps are present.
+fn do_physically_based_bloom(map: &mut TextureMap<Vec3>, mip_count: usize) {
+    // 1. Ensure that the requested number of mipmaps are present, as far as the
+    // map's dimensions allow.
 
-    static MIPMAP_LEVELS: usize = 4;
+    let mip_count = mip_count.min(map.width.min(map.height).ilog2() as usize);
 
-    if map.levels.len() == 1 {
+    if map.levels.len() != mip_count + 1 {
         // Square dimensions are not required.
 
-        make_mipmaps(map, MIPMAP_LEVELS);
+        map.levels.truncate(1);
+
+        make_mipmaps(map, mip_count);
     }
 
     // 2. Downsample.
@@ -371,3 +387,90 @@ fn upsample(map: &mut TextureMap<Vec3>, from_mipmap_index: usize) {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        buffer::framebuffer::Framebuffer, shaders::default_vertex_shader::DEFAULT_VERTEX_SHADER,
+        software_renderer::SoftwareRenderer, vec::vec3::Vec3,
+    };
+
+    // Runs the bloom pass over a dim frame containing a few bright, emissive
+    // patches, returning the total energy in the bloom buffer.
+    fn get_bloom_energy(threshold: f32) -> f32 {
+        let mut renderer = SoftwareRenderer::new(
+            Default::default(),
+            Default::default(),
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        renderer.options.bloom_threshold = threshold;
+
+        let mut framebuffer = Framebuffer::new(64, 64);
+
+        framebuffer.complete(0.3, 100.0);
+
+        let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+        renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+        {
+            let framebuffer = framebuffer_rc.borrow();
+
+            let mut deferred_buffer = framebuffer
+                .attachments
+                .deferred_hdr
+                .as_ref()
+                .unwrap()
+                .borrow_mut();
+
+            for y in 0..64 {
+                for x in 0..64 {
+                    let brightness = if (x / 16 + y / 16) % 2 == 0 {
+                        (1 + x / 16) as f32
+                    } else {
+                        0.3
+                    };
+
+                    deferred_buffer.set(x, y, Vec3::ones() * brightness);
+                }
+            }
+        }
+
+        renderer.do_bloom_pass();
+
+        let framebuffer = framebuffer_rc.borrow();
+
+        let bloom_texture_map = framebuffer.attachments.bloom.as_ref().unwrap().borrow();
+
+        assert_eq!(
+            bloom_texture_map.levels.len(),
+            renderer.options.bloom_mip_count as usize + 1
+        );
+
+        bloom_texture_map.levels[0]
+            .0
+            .data
+            .iter()
+            .map(|color| color.x + color.y + color.z)
+            .sum()
+    }
+
+    #[test]
+    fn lower_bloom_thresholds_produce_more_bloom() {
+        // The brightest patches have a luminance of 4.0.
+
+        let energies: Vec<f32> = [5.0, 3.5, 2.0, 0.5, 0.0]
+            .into_iter()
+            .map(get_bloom_energy)
+            .collect();
+
+        for pair in energies.windows(2) {
+            assert!(pair[1] > pair[0], "{:?}", energies);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{
+    matrix::Mat4,
+    software_renderer::{gbuffer::GBuffer, SoftwareRenderer},
+    vec::{vec3::Vec3, vec4::Vec4},
+};
+
+// Offset (in view space) used to avoid self-shadowing.
+static CONTACT_SHADOW_BIAS: f32 = 0.01;
+
+impl SoftwareRenderer {
+    pub(in crate::software_renderer) fn do_contact_shadow_pass(&mut self) {
+        let shader_context = self.shader_context.borrow();
+
+        // Contact shadows are only cast by the scene's directional light.
+
+        let direction_to_light_world_space = match &shader_context.directional_light {
+            Some(handle) => match self.scene_resources.directional_light.borrow().get(handle) {
+                Ok(entry) => (*entry.item.get_direction() * -1.0).as_normal(),
+                Err(_) => return,
+            },
+            None => return,
+        };
+
+        let direction_to_light_view_space = (Vec4 {
+            w: 0.0,
+            ..direction_to_light_world_space
+        } * shader_context.view_inverse_transform)
+            .to_vec3()
+            .as_normal();
+
+        let projection = shader_context.get_projection();
+
+        let (steps, length) = (
+            self.options.contact_shadow_steps,
+            self.options.contact_shadow_length,
+        );
+
+        if let Some(g_buffer) = self.g_buffer.as_mut() {
+            // Marches every sample first, so that writes don't affect reads.
+
+            let shadows: Vec<f32> = g_buffer
+                .iter()
+                .map(|sample| {
+                    if !sample.stencil {
+                        return 0.0;
+                    }
+
+                    march_contact_shadow(
+                        g_buffer,
+                        projection,
+                        sample.position_view_space,
+                        direction_to_light_view_space,
+                        steps,
+                        length,
+                    )
+                })
+                .collect();
+
+            for (index, shadow) in shadows.into_iter().enumerate() {
+                // Keeps any shadowing from the geometry pass (e.g., parallax
+                // self-shadowing).
+
+                let sample = g_buffer.0.get_at_mut(index);
+
+                sample.contact_shadow = sample.contact_shadow.max(shadow);
+            }
+        }
+    }
+}
+
+pub(in crate::software_renderer) fn view_space_to_pixel(
+    g_buffer: &GBuffer,
+    projection: Mat4,
+    position_view_space: Vec3,
+) -> Option<(u32, u32)> {
+    let position_projection_space = Vec4::new(position_view_space, 1.0) * projection;
+
+    if position_projection_space.w <= 0.0 {
+        return None;
+    }
+
+    let position_ndc_space = position_projection_space.to_vec3() / position_projection_space.w;
+
+    let uv = position_ndc_space.ndc_to_uv();
+
+    if !(0.0..=1.0).contains(&uv.x) || !(0.0..=1.0).contains(&uv.y) {
+        return None;
+    }
+
+    let x = (uv.x * (g_buffer.0.width - 1) as f32) as u32;
+    let y = ((1.0 - uv.y) * (g_buffer.0.height - 1) as f32) as u32;
+
+    Some((x, y))
+}
+
+// Steps from a (view-space) position towards the light, returning 1.0 if any
+// step lands behind a nearby surface in the G-buffer, or 0.0 otherwise.
+fn march_contact_shadow(
+    g_buffer: &GBuffer,
+    projection: Mat4,
+    position_view_space: Vec3,
+    direction_to_light_view_space: Vec3,
+    steps: u32,
+    length: f32,
+) -> f32 {
+    let step_length = length / steps.max(1) as f32;
+
+    for step in 1..=steps {
+        let ray_position_view_space =
+            position_view_space + direction_to_light_view_space * (step_length * step as f32);
+
+        let Some((x, y)) = view_space_to_pixel(g_buffer, projection, ray_position_view_space)
+        else {
+            break;
+        };
+
+        let sample = g_buffer.get(x, y);
+
+        if !sample.stencil {
+            continue;
+        }
+
+        // Treats the surface as an occluder only if the ray passes just
+        // behind it; surfaces far in front of the ray can't cast a contact
+        // shadow.
+
+        let depth_delta = ray_position_view_space.z - sample.position_view_space.z;
+
+        if depth_delta > CONTACT_SHADOW_BIAS && depth_delta < length {
+            return 1.0;
+        }
+    }
+
+    0.0
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        matrix::Mat4,
+        shader::geometry::sample::GeometrySample,
+        software_renderer::gbuffer::GBuffer,
+        vec::vec3::{self, Vec3},
+    };
+
+    use super::{march_contact_shadow, view_space_to_pixel};
+
+    fn make_g_buffer_with_occluder_at(
+        projection: Mat4,
+        occluder_position_view_space: Vec3,
+    ) -> GBuffer {
+        let mut g_buffer = GBuffer::new(64, 64);
+
+        // A floor-like background surface, 10 units away from the camera.
+
+        for y in 0..64 {
+            for x in 0..64 {
+                let sample = GeometrySample {
+                    position_view_space: Vec3 {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 10.0,
+                    },
+                    ..Default::default()
+                };
+
+                g_buffer.set(x, y, sample, None);
+            }
+        }
+
+        // A small occluder, slightly closer to the camera.
+
+        let (occluder_x, occluder_y) =
+            view_space_to_pixel(&g_buffer, projection, occluder_position_view_space).unwrap();
+
+        for y in occluder_y - 1..=occluder_y + 1 {
+            for x in occluder_x - 1..=occluder_x + 1 {
+                let sample = GeometrySample {
+                    position_view_space: Vec3 {
+                        z: 9.9,
+                        ..occluder_position_view_space
+                    },
+                    ..Default::default()
+                };
+
+                g_buffer.set(x, y, sample, None);
+            }
+        }
+
+        g_buffer
+    }
+
+    #[test]
+    fn march_detects_occluders_within_range() {
+        let projection = Mat4::perspective_for_fov(75.0, 1.0, 0.3, 100.0);
+
+        let position_view_space = Vec3 {
+            x: 0.0,
+            y: 0.0,
+            z: 10.0,
+        };
+
+        let (steps, length) = (8, 1.0);
+
+        let shadow_for_occluder_at = |x: f32| {
+            let g_buffer = make_g_buffer_with_occluder_at(
+                projection,
+                Vec3 {
+                    x,
+                    ..position_view_space
+                },
+            );
+
+            march_contact_shadow(
+                &g_buffer,
+                projection,
+                position_view_space,
+                vec3::RIGHT,
+                steps,
+                length,
+            )
+        };
+
+        // An occluder along the ray, within the march length.
+
+        assert_eq!(shadow_for_occluder_at(0.5), 1.0);
+
+        // An occluder along the ray, beyond the march length.
+
+        assert_eq!(shadow_for_occluder_at(3.0), 0.0);
+    }
+}
@@ -15,12 +15,15 @@ impl SoftwareRenderer {
                 // Call the active fragment shader on every G-buffer sample that was
                 // written to by the rasterizer.
 
-                for (index, sample) in self.g_buffer.as_ref().unwrap().iter().enumerate() {
+                let g_buffer = self.g_buffer.as_ref().unwrap();
+
+                for (index, sample) in g_buffer.iter().enumerate() {
                     if sample.stencil {
                         let hdr_color = self.get_hdr_color_for_sample(
                             &shader_context,
                             &self.scene_resources,
                             sample,
+                            g_buffer.get_fragment_shader_at(index),
                         );
 
                         deferred_buffer.set_at(index, hdr_color);
@@ -0,0 +1,96 @@
+use std::rc::Rc;
+
+use crate::{buffer::Buffer2D, software_renderer::zbuffer::ZBuffer};
+
+use super::SoftwareRenderer;
+
+impl SoftwareRenderer {
+    // Box-filters the (supersampled) color and depth that we rendered into the
+    // bound framebuffer; does nothing when we render into it directly.
+    pub(in crate::software_renderer) fn do_downsample_pass(&mut self) {
+        let (Some(source_rc), Some(target_rc)) = (&self.framebuffer, &self.target_framebuffer)
+        else {
+            return;
+        };
+
+        if Rc::ptr_eq(source_rc, target_rc) {
+            return;
+        }
+
+        let (source, target) = (source_rc.borrow(), target_rc.borrow());
+
+        let factor = self.supersample_factor;
+
+        if let (Some(source_color_rc), Some(target_color_rc)) = (
+            source.attachments.color.as_ref(),
+            target.attachments.color.as_ref(),
+        ) {
+            downsample_color(
+                &source_color_rc.borrow(),
+                &mut target_color_rc.borrow_mut(),
+                factor,
+            );
+        }
+
+        if let (Some(source_depth_rc), Some(target_depth_rc)) = (
+            source.attachments.depth.as_ref(),
+            target.attachments.depth.as_ref(),
+        ) {
+            downsample_depth(
+                &source_depth_rc.borrow(),
+                &mut target_depth_rc.borrow_mut(),
+                factor,
+            );
+        }
+    }
+}
+
+fn downsample_color(source: &Buffer2D, target: &mut Buffer2D, factor: u32) {
+    let sample_count = factor * factor;
+
+    for y in 0..target.height {
+        for x in 0..target.width {
+            // Averages each (8-bit) channel over the block of samples.
+
+            let mut sums = [0_u32; 4];
+
+            for sample_y in 0..factor {
+                for sample_x in 0..factor {
+                    let sample = *source.get(x * factor + sample_x, y * factor + sample_y);
+
+                    for (channel, sum) in sums.iter_mut().enumerate() {
+                        *sum += (sample >> (channel * 8)) & 0xFF;
+                    }
+                }
+            }
+
+            let averaged = sums.iter().enumerate().fold(0, |color, (channel, sum)| {
+                color | ((sum + sample_count / 2) / sample_count) << (channel * 8)
+            });
+
+            target.set(x, y, averaged);
+        }
+    }
+}
+
+fn downsample_depth(source: &ZBuffer, target: &mut ZBuffer, factor: u32) {
+    // Keeps the nearest depth in each block of samples.
+
+    for y in 0..target.buffer.height {
+        for x in 0..target.buffer.width {
+            let mut nearest = f32::MAX;
+
+            for sample_y in 0..factor {
+                for sample_x in 0..factor {
+                    nearest = nearest.min(
+                        *source
+                            .buffer
+                            .get(x * factor + sample_x, y * factor + sample_y),
+                    );
+                }
+            }
+
+            target.set(x, y, nearest);
+        }
+    }
+}
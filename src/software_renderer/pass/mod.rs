@@ -1,7 +1,11 @@
 use super::SoftwareRenderer;
 
 pub mod bloom_pass;
+pub mod contact_shadow_pass;
 pub mod deferred_lighting_pass;
+pub mod downsample_pass;
+pub mod sorted_transparency_pass;
 pub mod ssao_pass;
+pub mod ssr_pass;
 pub mod tone_mapping;
 pub mod weighted_blended_pass;
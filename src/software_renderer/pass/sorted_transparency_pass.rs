@@ -0,0 +1,135 @@
+use std::cmp::Ordering;
+
+use crate::{
+    buffer::framebuffer::Framebuffer,
+    color::Color,
+    render::options::RenderPassFlag,
+    resource::handle::Handle,
+    shader::{context::ShaderContext, geometry::sample::GeometrySample},
+    software_renderer::primitive::triangle::Triangle,
+    vec::vec4::Vec4,
+    vertex::default_vertex_out::DefaultVertexOut,
+};
+
+use super::SoftwareRenderer;
+
+#[derive(Debug, Copy, Clone)]
+pub(in crate::software_renderer) struct TransparentTriangle {
+    triangle: Triangle<DefaultVertexOut>,
+    material: Option<Handle>,
+    fragment_shader_override: Option<usize>,
+    // View-space depth of the triangle's centroid.
+    depth: f32,
+}
+
+impl SoftwareRenderer {
+    pub(in crate::software_renderer) fn defer_transparent_triangle(
+        &mut self,
+        triangle: &Triangle<DefaultVertexOut>,
+    ) {
+        let shader_context = self.shader_context.borrow();
+
+        let centroid_world_space = (triangle.v0.position_world_space
+            + triangle.v1.position_world_space
+            + triangle.v2.position_world_space)
+            / 3.0;
+
+        let centroid_view_space =
+            Vec4::new(centroid_world_space, 1.0) * shader_context.view_inverse_transform;
+
+        self.transparent_triangles.push(TransparentTriangle {
+            triangle: *triangle,
+            material: shader_context.active_material,
+            fragment_shader_override: self.fragment_shader_override,
+            depth: centroid_view_space.z,
+        });
+    }
+
+    // Rasterizes the frame's transparent triangles from back to front, blending
+    // each fragment over the (shaded) opaque pass.
+    pub(in crate::software_renderer) fn do_sorted_transparency_pass(&mut self) {
+        if self.transparent_triangles.is_empty() {
+            return;
+        }
+
+        let mut triangles = std::mem::take(&mut self.transparent_triangles);
+
+        // Farthest first; ties keep their submission order.
+
+        triangles.sort_by(|a, b| b.depth.partial_cmp(&a.depth).unwrap_or(Ordering::Equal));
+
+        let original_material = self.shader_context.borrow().active_material;
+
+        self.is_rendering_transparent_triangles = true;
+
+        for transparent in &triangles {
+            self.shader_context
+                .borrow_mut()
+                .set_active_material(transparent.material);
+
+            self.fragment_shader_override = transparent.fragment_shader_override;
+
+            self.process_triangle(&transparent.triangle);
+        }
+
+        self.is_rendering_transparent_triangles = false;
+
+        self.fragment_shader_override = None;
+
+        self.shader_context
+            .borrow_mut()
+            .set_active_material(original_material);
+    }
+
+    pub(in crate::software_renderer) fn blend_transparent_fragment(
+        &self,
+        framebuffer: &Framebuffer,
+        shader_context: &ShaderContext,
+        x: u32,
+        y: u32,
+        sample: &GeometrySample,
+    ) {
+        let hdr_color = self.get_hdr_color_for_sample(
+            shader_context,
+            &self.scene_resources,
+            sample,
+            self.fragment_shader_override,
+        );
+
+        // Source: GL_SRC_ALPHA, dest: GL_ONE_MINUS_SRC_ALPHA
+
+        let alpha = sample.alpha.clamp(0.0, 1.0);
+
+        if self
+            .options
+            .render_pass_flags
+            .contains(RenderPassFlag::DeferredLighting)
+        {
+            if let Some(deferred_buffer_rc) = framebuffer.attachments.deferred_hdr.as_ref() {
+                let mut deferred_buffer = deferred_buffer_rc.borrow_mut();
+
+                let dest = *deferred_buffer.get(x, y);
+
+                deferred_buffer.set(x, y, dest * (1.0 - alpha) + hdr_color * alpha);
+            }
+        } else if let Some(forward_buffer_rc) = framebuffer.attachments.forward_ldr.as_ref() {
+            let mut forward_buffer = forward_buffer_rc.borrow_mut();
+
+            let mut dest = Color::from_u32(*forward_buffer.get(x, y));
+
+            // Pixels that the forward pass didn't write show the color buffer.
+
+            if dest.a == 0.0 {
+                if let Some(color_buffer_rc) = framebuffer.attachments.color.as_ref() {
+                    dest = Color::from_u32(*color_buffer_rc.borrow().get(x, y));
+                }
+            }
+
+            let src = self.get_ldr_color(hdr_color);
+
+            let blended = dest.to_vec3() * (1.0 - alpha) + src.to_vec3() * alpha;
+
+            forward_buffer.set(x, y, Color::from_vec3(blended).to_u32());
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    animation::lerp,
+    matrix::Mat4,
+    software_renderer::{gbuffer::GBuffer, SoftwareRenderer},
+    vec::{vec3::Vec3, vec4::Vec4},
+};
+
+use super::contact_shadow_pass::view_space_to_pixel;
+
+static SSR_MAX_STEPS: u32 = 64;
+
+// Distance (in view space) covered by each step of the march.
+static SSR_STEP_LENGTH: f32 = 0.1;
+
+// How far behind a surface the ray may pass and still count as a hit.
+static SSR_THICKNESS: f32 = 0.5;
+
+impl SoftwareRenderer {
+    pub(in crate::software_renderer) fn do_ssr_pass(&mut self) {
+        if let (Some(g_buffer), Some(framebuffer_rc)) =
+            (self.g_buffer.as_ref(), self.framebuffer.as_ref())
+        {
+            let framebuffer = framebuffer_rc.borrow();
+
+            let Some(deferred_buffer_rc) = framebuffer.attachments.deferred_hdr.as_ref() else {
+                return;
+            };
+
+            let mut deferred_buffer = deferred_buffer_rc.borrow_mut();
+
+            // Reflections sample the lit frame as it was before this pass.
+
+            let lit_buffer = deferred_buffer.clone();
+
+            let shader_context = self.shader_context.borrow();
+
+            let projection = shader_context.get_projection();
+
+            let cubemap_vec3_arena = self.scene_resources.cubemap_vec3.borrow();
+
+            let radiance_map = shader_context
+                .ambient_radiance_map
+                .and_then(|handle| cubemap_vec3_arena.get(&handle).ok())
+                .map(|entry| &entry.item);
+
+            for (index, sample) in g_buffer.iter().enumerate() {
+                if !sample.stencil || sample.reflectivity <= 0.0 {
+                    continue;
+                }
+
+                // 1. Trace the reflected view ray through the G-buffer.
+
+                let normal_view_space =
+                    (sample.normal_world_space * shader_context.view_inverse_transform).as_normal();
+
+                let reflected_ray_view_space = (-sample.position_view_space)
+                    .as_normal()
+                    .reflect(normal_view_space);
+
+                let reflected_color = match march_reflected_ray(
+                    g_buffer,
+                    projection,
+                    sample.position_view_space,
+                    reflected_ray_view_space,
+                ) {
+                    Some((x, y)) => Some(*lit_buffer.get(x, y)),
+                    None => {
+                        // 2. Fall back to the environment (skybox) when the
+                        // ray leaves the screen without hitting anything.
+
+                        radiance_map.map(|map| {
+                            let fragment_to_view = (shader_context.view_position.to_vec3()
+                                - sample.position_world_space)
+                                .as_normal();
+
+                            let mut direction =
+                                Vec4::new(fragment_to_view.reflect(sample.normal_world_space), 0.0);
+
+                            if let Some(transform) = shader_context.skybox_transform {
+                                direction *= transform;
+                            }
+
+                            map.sample_nearest(&direction, None)
+                        })
+                    }
+                };
+
+                if let Some(reflected_color) = reflected_color {
+                    let color = lit_buffer.get_at(index);
+
+                    deferred_buffer.set_at(
+                        index,
+                        lerp(*color, reflected_color, sample.reflectivity.min(1.0)),
+                    );
+                }
+            }
+        }
+    }
+}
+
+// Steps along a (view-space) reflected ray, returning the first pixel whose
+// surface the ray passes just behind, if any.
+fn march_reflected_ray(
+    g_buffer: &GBuffer,
+    projection: Mat4,
+    position_view_space: Vec3,
+    direction_view_space: Vec3,
+) -> Option<(u32, u32)> {
+    let start_pixel = view_space_to_pixel(g_buffer, projection, position_view_space);
+
+    for step in 1..=SSR_MAX_STEPS {
+        let ray_position_view_space =
+            position_view_space + direction_view_space * (SSR_STEP_LENGTH * step as f32);
+
+        // The ray left the screen (or passed behind the camera).
+
+        let (x, y) = view_space_to_pixel(g_buffer, projection, ray_position_view_space)?;
+
+        if Some((x, y)) == start_pixel {
+            continue;
+        }
+
+        let sample = g_buffer.get(x, y);
+
+        if !sample.stencil {
+            continue;
+        }
+
+        let depth_delta = ray_position_view_space.z - sample.position_view_space.z;
+
+        if depth_delta > 0.0 && depth_delta < SSR_THICKNESS {
+            return Some((x, y));
+        }
+    }
+
+    None
+}
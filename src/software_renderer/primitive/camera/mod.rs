@@ -11,22 +11,36 @@ impl SoftwareRenderer {
         camera: &Camera,
         color: Option<Color>,
     ) {
-        // World space view volume.
-
-        let frustum = camera.get_frustum();
-
-        // View volume
-
-        self.render_frustum(frustum, color);
+        self.render_camera_frustum(camera, color);
 
         // Target
 
+        let frustum = camera.get_frustum();
+
         self.render_line(
             (frustum.near[0] + frustum.near[2]) / 2.0,
             (frustum.far[0] + frustum.far[2]) / 2.0,
             color::WHITE,
         );
+    }
+
+    pub(in crate::software_renderer) fn _render_camera_frustum(
+        &mut self,
+        camera: &Camera,
+        color: Option<Color>,
+    ) {
+        // World space view volume.
+
+        self.render_frustum(camera.get_frustum(), color);
+
+        // Camera's local axes, at its position.
+
+        let look_vector = &camera.look_vector;
+
+        let position = look_vector.get_position();
 
-        self.render_axes(None, Some(5.0));
+        self.render_line(position, position + look_vector.get_right(), color::RED);
+        self.render_line(position, position + look_vector.get_up(), color::BLUE);
+        self.render_line(position, position + look_vector.get_forward(), color::GREEN);
     }
 }
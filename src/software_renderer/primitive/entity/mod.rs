@@ -1,6 +1,11 @@
 use crate::{
-    geometry::primitives::aabb::AABB, matrix::Mat4, mesh::Mesh, resource::handle::Handle,
-    scene::camera::frustum::Frustum, software_renderer::SoftwareRenderer, vec::vec4::Vec4,
+    geometry::primitives::aabb::AABB,
+    matrix::Mat4,
+    mesh::Mesh,
+    resource::handle::Handle,
+    scene::camera::frustum::Frustum,
+    software_renderer::{zbuffer::DepthTestMethod, DepthPrepassStage, SoftwareRenderer},
+    vec::vec4::Vec4,
 };
 
 impl SoftwareRenderer {
@@ -11,11 +16,23 @@ impl SoftwareRenderer {
         entity_mesh: &Mesh,
         entity_material: &Option<Handle>,
     ) -> bool {
+        let is_transparent = self.is_material_transparent(entity_material);
+
+        let is_depth_prepass = self.depth_prepass_stage == DepthPrepassStage::WritingDepth;
+
+        // Transparent entities never write depth.
+
+        if is_depth_prepass && is_transparent {
+            return false;
+        }
+
         let mut should_cull = false;
 
-        if let Some(frustum) = clipping_camera_frustum.as_ref() {
-            if should_cull_aabb(*world_transform, frustum, &entity_mesh.aabb) {
-                should_cull = true;
+        if self.options.entity_frustum_culling {
+            if let Some(frustum) = clipping_camera_frustum.as_ref() {
+                if should_cull_aabb(*world_transform, frustum, &entity_mesh.aabb) {
+                    should_cull = true;
+                }
             }
         }
 
@@ -35,8 +52,32 @@ impl SoftwareRenderer {
                 }
             }
 
+            // Shade this entity with its material's shader, if any.
+
+            self.fragment_shader_override = self.get_material_fragment_shader(entity_material);
+
+            self.is_active_material_transparent = is_transparent;
+
+            // After a depth prepass, only shade opaque fragments that match
+            // the prepass depth.
+
+            let original_depth_test_method =
+                if self.depth_prepass_stage == DepthPrepassStage::Shading && !is_transparent {
+                    self.swap_depth_test_method(DepthTestMethod::Equal)
+                } else {
+                    None
+                };
+
             self.render_entity_mesh(entity_mesh, world_transform);
 
+            if let Some(method) = original_depth_test_method {
+                self.swap_depth_test_method(method);
+            }
+
+            self.fragment_shader_override = None;
+
+            self.is_active_material_transparent = false;
+
             if did_set_active_material {
                 // Reset the shader context's original active material.
 
@@ -46,25 +87,79 @@ impl SoftwareRenderer {
             }
         }
 
+        // Entities are counted once, when they're shaded.
+
+        if !is_depth_prepass {
+            if should_cull {
+                self.stats.entities_culled += 1;
+            } else {
+                self.stats.entities_rendered += 1;
+            }
+        }
+
         !should_cull
     }
 }
 
 fn should_cull_aabb(world_transform: Mat4, clipping_camera_frustum: &Frustum, aabb: &AABB) -> bool {
-    // Cull the entire entity, if possible, based on its bounds.
+    // Cull the entire entity, if possible, based on its bounds; the entity is
+    // outside of the frustum if all corners of its (world-space) bounding box
+    // lie behind any one of the frustum's planes.
 
-    let bounding_sphere_position = (Vec4::new(aabb.center(), 1.0) * world_transform).to_vec3();
+    let corners = aabb
+        .get_vertices()
+        .map(|corner| (Vec4::new(corner, 1.0) * world_transform).to_vec3());
 
     // @TODO Generate planes once per frame, not once per entity draw.
     let culling_planes = clipping_camera_frustum.get_planes();
 
-    let radius = aabb.bounding_sphere_radius;
-
     // @TODO Verify the sign of the top plane and bottom plane normals.
-    !culling_planes[0].is_on_or_in_front_of(&bounding_sphere_position, radius)
-        || !culling_planes[1].is_on_or_in_front_of(&bounding_sphere_position, radius)
-        || !culling_planes[2].is_on_or_in_front_of(&bounding_sphere_position, radius)
-        || !culling_planes[3].is_on_or_in_front_of(&bounding_sphere_position, radius)
-        || !culling_planes[4].is_on_or_in_front_of(&bounding_sphere_position, radius)
-        || !culling_planes[5].is_on_or_in_front_of(&bounding_sphere_position, radius)
+    culling_planes.iter().any(|plane| {
+        corners
+            .iter()
+            .all(|corner| plane.get_signed_distance(corner) < 0.0)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        geometry::primitives::aabb::AABB,
+        matrix::Mat4,
+        scene::camera::Camera,
+        vec::vec3::{self, Vec3},
+    };
+
+    use super::should_cull_aabb;
+
+    #[test]
+    fn bounds_outside_of_the_frustum_are_culled() {
+        let camera = Camera::from_perspective(Default::default(), vec3::FORWARD, 75.0, 1.0);
+
+        let frustum = camera.get_frustum();
+
+        let aabb = AABB::from_min_max(-vec3::ONES, vec3::ONES);
+
+        let at = |x: f32, y: f32, z: f32| Mat4::translation(Vec3 { x, y, z });
+
+        // In front of the camera.
+
+        assert!(!should_cull_aabb(at(0.0, 0.0, 10.0), frustum, &aabb));
+
+        // Behind the camera, beyond the far plane, and far off to one side.
+
+        assert!(should_cull_aabb(at(0.0, 0.0, -10.0), frustum, &aabb));
+        assert!(should_cull_aabb(at(0.0, 0.0, 2000.0), frustum, &aabb));
+        assert!(should_cull_aabb(at(100.0, 0.0, 10.0), frustum, &aabb));
+
+        // Bounds that straddle a plane aren't culled.
+
+        assert!(!should_cull_aabb(at(0.0, 0.0, 0.5), frustum, &aabb));
+
+        // Bounds are scaled along with the entity.
+
+        let scaled = Mat4::scale([40.0, 40.0, 40.0, 1.0]) * at(0.0, 0.0, -30.0);
+
+        assert!(!should_cull_aabb(scaled, frustum, &aabb));
+    }
 }
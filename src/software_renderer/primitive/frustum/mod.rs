@@ -11,43 +11,17 @@ impl SoftwareRenderer {
         frustum: &Frustum,
         color: Option<Color>,
     ) {
-        // Draw near plane (red).
+        // Draw the near plane (red), the far plane (blue), and the edges
+        // connecting the 2 planes (yellow).
 
-        for (index, _point) in frustum.near.as_slice().iter().enumerate() {
-            self.render_line(
-                frustum.near[index],
-                frustum.near[if index == 3 { 0 } else { index + 1 }],
-                match color {
-                    Some(color) => color,
-                    None => color::RED,
-                },
-            );
-        }
-
-        // Draw far plane (blue).
-
-        for (index, _point) in frustum.far.as_slice().iter().enumerate() {
-            self.render_line(
-                frustum.far[index],
-                frustum.far[if index == 3 { 0 } else { index + 1 }],
-                match color {
-                    Some(color) => color,
-                    None => color::BLUE,
-                },
-            );
-        }
-
-        // Connect the 2 planes.
+        for (index, (start, end)) in frustum.get_edges().into_iter().enumerate() {
+            let default_color = match index / 4 {
+                0 => color::RED,
+                1 => color::BLUE,
+                _ => color::YELLOW,
+            };
 
-        for i in 0..4 {
-            self.render_line(
-                frustum.near[i],
-                frustum.far[i],
-                match color {
-                    Some(color) => color,
-                    None => color::YELLOW,
-                },
-            );
+            self.render_line(start, end, color.unwrap_or(default_color));
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::{
+    animation::lerp,
     color::{self, Color},
     graphics::Graphics,
     render::Renderer,
@@ -6,6 +7,9 @@ use crate::{
     vec::vec3::{self, Vec3},
 };
 
+// Lets lines lying on a surface (e.g., wireframe edges) pass the depth test.
+static DEPTH_TESTED_LINE_BIAS: f32 = 0.0005;
+
 impl SoftwareRenderer {
     pub(in crate::software_renderer) fn _render_line(
         &mut self,
@@ -25,6 +29,35 @@ impl SoftwareRenderer {
         self.render_line_from_ndc_space_vecs(&start_ndc_space, &end_ndc_space, color);
     }
 
+    // Queues a line to be drawn at the end of the frame, wherever it isn't
+    // hidden by the frame's (opaque) geometry.
+    pub(in crate::software_renderer) fn render_depth_tested_line(
+        &mut self,
+        start_world_space: Vec3,
+        end_world_space: Vec3,
+        color: Color,
+    ) {
+        let (start_ndc_space, end_ndc_space) = {
+            let shader_context = self.shader_context.borrow();
+
+            (
+                shader_context.to_ndc_space(start_world_space),
+                shader_context.to_ndc_space(end_world_space),
+            )
+        };
+
+        self.depth_tested_lines
+            .push((start_ndc_space, end_ndc_space, color));
+    }
+
+    pub(in crate::software_renderer) fn flush_depth_tested_lines(&mut self) {
+        let lines = std::mem::take(&mut self.depth_tested_lines);
+
+        for (start, end, color) in &lines {
+            self.render_depth_tested_line_from_ndc_space_vecs(start, end, *color);
+        }
+    }
+
     pub(in crate::software_renderer) fn render_line_loop(
         &mut self,
         positions_world_space: &[Vec3],
@@ -97,6 +130,67 @@ impl SoftwareRenderer {
         }
     }
 
+    fn render_depth_tested_line_from_ndc_space_vecs(
+        &mut self,
+        start: &Vec3,
+        end: &Vec3,
+        color: Color,
+    ) {
+        // Cull lines that are completely in front of our near plane.
+
+        if start.z <= 0.0 && end.z <= 0.0 {
+            return;
+        }
+
+        let framebuffer = match &self.framebuffer {
+            Some(rc) => rc.borrow(),
+            None => panic!(
+                "Called SoftwareRenderer::render_depth_tested_line_from_ndc_space_vecs() with no bound framebuffer!"
+            ),
+        };
+
+        let (Some(forward_ldr_rc), Some(depth_rc)) = (
+            framebuffer.attachments.forward_ldr.as_ref(),
+            framebuffer.attachments.depth.as_ref(),
+        ) else {
+            panic!("Called SoftwareRenderer::render_depth_tested_line_from_ndc_space_vecs() with no forward (LDR) or depth framebuffer attachment!");
+        };
+
+        let mut forward_buffer = forward_ldr_rc.borrow_mut();
+
+        let depth_buffer = depth_rc.borrow();
+
+        let color_u32 = color.to_u32();
+
+        let (width, height) = (self.viewport.width as f32, self.viewport.height as f32);
+
+        let (x1, y1) = (start.x * width, start.y * height);
+        let (x2, y2) = (end.x * width, end.y * height);
+
+        // Steps through the line one pixel at a time; NDC depth varies
+        // linearly in screen space.
+
+        let steps = (x2 - x1).abs().max((y2 - y1).abs()).ceil().max(1.0) as u32;
+
+        for step in 0..steps + 1 {
+            let alpha = step as f32 / steps as f32;
+
+            let (x, y) = (lerp(x1, x2, alpha), lerp(y1, y2, alpha));
+
+            if x < 0.0 || y < 0.0 || x >= width || y >= height {
+                continue;
+            }
+
+            let (x, y) = (x as u32, y as u32);
+
+            let z = lerp(start.z, end.z, alpha);
+
+            if z <= *depth_buffer.buffer.get(x, y) + DEPTH_TESTED_LINE_BIAS {
+                forward_buffer.set(x, y, color_u32);
+            }
+        }
+    }
+
     fn render_line_from_ndc_space_vecs(&mut self, start: &Vec3, end: &Vec3, color: Color) {
         // Cull lines that are completely in front of our near plane
         // (z1 <= 0 and z2 <= 0).
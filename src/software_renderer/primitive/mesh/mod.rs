@@ -99,6 +99,7 @@ fn get_vertices_in(geometry: &MeshGeometry, face: &Face) -> [DefaultVertexIn; 3]
         position: v0,
         normal: normal0,
         uv: uv0,
+        lightmap_uv: face.lightmap_uvs[0],
         tangent: tangent0,
         bitangent: bitangent0,
         color: WHITE,
@@ -108,6 +109,7 @@ fn get_vertices_in(geometry: &MeshGeometry, face: &Face) -> [DefaultVertexIn; 3]
         position: v1,
         normal: normal1,
         uv: uv1,
+        lightmap_uv: face.lightmap_uvs[1],
         tangent: tangent1,
         bitangent: bitangent1,
         color: WHITE,
@@ -117,6 +119,7 @@ fn get_vertices_in(geometry: &MeshGeometry, face: &Face) -> [DefaultVertexIn; 3]
         position: v2,
         normal: normal2,
         uv: uv2,
+        lightmap_uv: face.lightmap_uvs[2],
         tangent: tangent2,
         bitangent: bitangent2,
         color: WHITE,
@@ -101,7 +101,11 @@ impl SoftwareRenderer {
 
                         // Sample the cubemap using our world-space direction-offset.
 
-                        let skybox_hdr_color = skybox_hdr.sample_nearest(&normal, None);
+                        let skybox_hdr_color = if self.shader_options.bilinear_active {
+                            skybox_hdr.sample_bilinear(&normal, None)
+                        } else {
+                            skybox_hdr.sample_nearest(&normal, None)
+                        };
 
                         let skybox_color = self.get_tone_mapped_color_from_hdr(skybox_hdr_color);
 
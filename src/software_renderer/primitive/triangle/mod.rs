@@ -1,20 +1,24 @@
 use crate::{
     animation::lerp,
     color::{self, Color},
+    graphics::Graphics,
     mesh::face::Face,
     render::{
+        clip::{clip_polygon_against_frustum, clip_polygon_against_guard_band, FRUSTUM_PLANES},
         culling::{FaceCullingReject, FaceCullingWindingOrder},
-        options::RenderPassFlag,
+        options::{
+            rasterizer::{AttributeInterpolation, MAX_SUBPIXEL_PRECISION_BITS},
+            RenderPassFlag,
+        },
         Renderer,
     },
     software_renderer::SoftwareRenderer,
-    vec::vec4::Vec4,
+    vec::{vec3::Vec3, vec4::Vec4},
     vertex::default_vertex_out::DefaultVertexOut,
 };
 
-pub(in crate::software_renderer) mod clip;
-
-use self::clip::clip_by_all_planes;
+// A non-horizontal triangle edge, as its top and bottom vertices.
+type ScanlineEdge<'a> = (&'a DefaultVertexOut, &'a DefaultVertexOut);
 
 #[derive(Default, Debug, Copy, Clone)]
 pub struct Triangle<T> {
@@ -52,6 +56,15 @@ impl SoftwareRenderer {
                 }
             }
 
+            // Facing is decided once per triangle, by its (screen-space)
+            // winding, and passed along to each of its fragments.
+
+            let is_backface = self.is_backface(
+                v0.position_projection_space,
+                v1.position_projection_space,
+                v2.position_projection_space,
+            );
+
             match self.options.rasterizer_options.face_culling_strategy.reject {
                 FaceCullingReject::None => {
                     // Render all faces.
@@ -59,27 +72,25 @@ impl SoftwareRenderer {
                 FaceCullingReject::Backfaces => {
                     // Reject backfaces.
 
-                    if self.is_backface(
-                        v0.position_projection_space,
-                        v1.position_projection_space,
-                        v2.position_projection_space,
-                    ) {
+                    if is_backface {
                         continue;
                     }
                 }
                 FaceCullingReject::Frontfaces => {
                     // Reject frontfaces.
 
-                    if !self.is_backface(
-                        v0.position_projection_space,
-                        v1.position_projection_space,
-                        v2.position_projection_space,
-                    ) {
+                    if !is_backface {
                         continue;
                     }
                 }
             }
 
+            if is_backface {
+                for vertex in [&mut v0, &mut v1, &mut v2] {
+                    vertex.back_facing = 1.0;
+                }
+            }
+
             self.process_triangle(&Triangle { v0, v1, v2 });
         }
     }
@@ -128,9 +139,32 @@ impl SoftwareRenderer {
 
         let mut ndc_space_vertices = projection_space_vertices;
 
-        ndc_space_vertices[0].projection_space_to_viewport_space(&self.viewport);
-        ndc_space_vertices[1].projection_space_to_viewport_space(&self.viewport);
-        ndc_space_vertices[2].projection_space_to_viewport_space(&self.viewport);
+        let interpolation = self.options.rasterizer_options.attribute_interpolation;
+
+        let snap_resolution = self.options.rasterizer_options.vertex_snap_resolution;
+
+        for vertex in ndc_space_vertices.iter_mut() {
+            vertex.projection_space_to_viewport_space(&self.viewport, interpolation);
+
+            if let Some(resolution) = snap_resolution {
+                vertex.snap_to_viewport_grid(&self.viewport, resolution);
+            }
+        }
+
+        // Ratio of the triangle's UV-space area to its viewport-space area,
+        // for mipmap level selection; (perspective-correct) attributes are
+        // pre-scaled by their vertex's inverse-W.
+
+        let uv_footprint = get_uv_footprint(&projection_space_vertices, &ndc_space_vertices);
+
+        for vertex in ndc_space_vertices.iter_mut() {
+            vertex.uv_footprint = match interpolation {
+                AttributeInterpolation::PerspectiveCorrect => {
+                    uv_footprint * vertex.position_projection_space.w
+                }
+                AttributeInterpolation::Affine => uv_footprint,
+            };
+        }
 
         // Rasterize triangle in viewport space.
 
@@ -139,51 +173,72 @@ impl SoftwareRenderer {
             .render_pass_flags
             .contains(RenderPassFlag::Rasterization)
         {
-            self.triangle_fill(
-                ndc_space_vertices[0],
-                ndc_space_vertices[1],
-                ndc_space_vertices[2],
-            );
+            let [v0, v1, v2] = ndc_space_vertices;
+
+            match self.options.rasterizer_options.subpixel_precision_bits {
+                Some(bits) => self.fixed_point_triangle_fill(v0, v1, v2, bits),
+                None => self.triangle_fill(v0, v1, v2),
+            }
         }
 
         if self.options.draw_wireframe {
             let wireframe_color = Color::from_vec3(self.options.wireframe_color * 255.0);
 
             for i in 0..3 {
-                self.render_line(
+                let (start, end) = (
                     projection_space_vertices[i].position_world_space,
                     projection_space_vertices[if i == 2 { 0 } else { i + 1 }].position_world_space,
-                    wireframe_color,
                 );
+
+                if self.options.wireframe_depth_tested {
+                    self.render_depth_tested_line(start, end, wireframe_color);
+                } else {
+                    self.render_line(start, end, wireframe_color);
+                }
             }
         }
 
-        if self.options.draw_normals {
-            for vertex in &projection_space_vertices {
-                self.render_line(
-                    vertex.position_world_space,
-                    vertex.position_world_space
-                        + vertex.normal_world_space * self.options.draw_normals_scale,
-                    color::BLUE,
-                );
-
-                self.render_line(
-                    vertex.position_world_space,
-                    vertex.position_world_space
-                        + vertex.tangent_world_space * self.options.draw_normals_scale,
-                    color::RED,
-                );
-
-                self.render_line(
-                    vertex.position_world_space,
-                    vertex.position_world_space
-                        + vertex.bitangent_world_space * self.options.draw_normals_scale,
-                    color::GREEN,
-                );
+        for vertex in &projection_space_vertices {
+            for (start, end, color) in self.get_vertex_frame_segments(vertex) {
+                self.render_line(start, end, color);
             }
         }
     }
 
+    // Line segments (in world space) for whichever of the vertex's normal,
+    // tangent, and bitangent are enabled for debug drawing.
+    fn get_vertex_frame_segments(&self, vertex: &DefaultVertexOut) -> Vec<(Vec3, Vec3, Color)> {
+        let scale = self.options.draw_normals_scale;
+
+        [
+            (
+                self.options.draw_normals,
+                vertex.normal_world_space,
+                color::BLUE,
+            ),
+            (
+                self.options.draw_tangents,
+                vertex.tangent_world_space,
+                color::RED,
+            ),
+            (
+                self.options.draw_bitangents,
+                vertex.bitangent_world_space,
+                color::GREEN,
+            ),
+        ]
+        .into_iter()
+        .filter(|(enabled, _, _)| *enabled)
+        .map(|(_, direction, color)| {
+            (
+                vertex.position_world_space,
+                vertex.position_world_space + direction * scale,
+                color,
+            )
+        })
+        .collect()
+    }
+
     fn is_backface(&mut self, v0: Vec4, v1: Vec4, v2: Vec4) -> bool {
         // Computes a hard surface normal for the face (ignores smooth normals);
 
@@ -194,17 +249,46 @@ impl SoftwareRenderer {
         similarity_to_view_direction > 0.0
     }
 
-    fn process_triangle(&mut self, triangle: &Triangle<DefaultVertexOut>) {
+    pub(in crate::software_renderer) fn process_triangle(
+        &mut self,
+        triangle: &Triangle<DefaultVertexOut>,
+    ) {
         // @TODO(mzalla) Geometry shader?
 
         if self.should_cull_from_homogeneous_space(triangle) {
             return;
         }
 
-        let clipped_triangles = clip_by_all_planes(triangle);
+        // Defer transparent triangles until the end of the frame.
+
+        if self.options.sorted_transparency
+            && self.is_active_material_transparent
+            && !self.is_rendering_transparent_triangles
+        {
+            self.defer_transparent_triangle(triangle);
 
-        for clipped in &clipped_triangles {
-            self.post_process_triangle_vertices(clipped);
+            return;
+        }
+
+        self.stats.triangles_submitted += 1;
+
+        let vertices = [triangle.v0, triangle.v1, triangle.v2];
+
+        let clipped_polygon = match self.options.rasterizer_options.guard_band {
+            Some(guard_band) => {
+                clip_polygon_against_guard_band(&vertices, guard_band, &self.viewport)
+            }
+            None => clip_polygon_against_frustum(&vertices, &FRUSTUM_PLANES),
+        };
+
+        // Re-triangulate the (convex) clipped polygon, as a fan.
+
+        for index in 1..clipped_polygon.len().saturating_sub(1) {
+            self.post_process_triangle_vertices(&Triangle {
+                v0: clipped_polygon[0],
+                v1: clipped_polygon[index],
+                v2: clipped_polygon[index + 1],
+            });
         }
     }
 
@@ -233,7 +317,13 @@ impl SoftwareRenderer {
                 tri.swap(0, 1);
             }
 
-            self.flat_top_triangle_fill(tri[0], tri[1], tri[2]);
+            self.flat_top_triangle_fill(
+                tri[0],
+                tri[1],
+                tri[2],
+                (&tri[0], &tri[2]),
+                (&tri[1], &tri[2]),
+            );
         } else if tri[1].position_projection_space.y == tri[2].position_projection_space.y {
             // Flat-bottom (horizontal line is tri[1]-to-tri[2]);
 
@@ -244,7 +334,13 @@ impl SoftwareRenderer {
                 tri.swap(1, 2);
             }
 
-            self.flat_bottom_triangle_fill(tri[0], tri[1], tri[2]);
+            self.flat_bottom_triangle_fill(
+                tri[0],
+                tri[1],
+                tri[2],
+                (&tri[0], &tri[1]),
+                (&tri[0], &tri[2]),
+            );
         } else {
             // Find splitting vertex
 
@@ -252,7 +348,16 @@ impl SoftwareRenderer {
                 - tri[0].position_projection_space.y)
                 / (tri[2].position_projection_space.y - tri[0].position_projection_space.y);
 
-            let split_vertex = lerp(tri[0], tri[2], alpha_split);
+            let mut split_vertex = lerp(tri[0], tri[2], alpha_split);
+
+            // Ensures that both halves agree on the scanline that divides them.
+
+            split_vertex.position_projection_space.y = tri[1].position_projection_space.y;
+
+            // Both halves rasterize along the original (major) edge, rather
+            // than along the edges that meet at the split vertex.
+
+            let major_edge = (&tri[0], &tri[2]);
 
             if tri[1].position_projection_space.x < split_vertex.position_projection_space.x {
                 // Major right
@@ -261,24 +366,89 @@ impl SoftwareRenderer {
                 // split_point cannot have the same x-value; therefore, sort tri[1]
                 // and split_point by x-value;
 
-                self.flat_bottom_triangle_fill(tri[0], tri[1], split_vertex);
+                self.flat_bottom_triangle_fill(
+                    tri[0],
+                    tri[1],
+                    split_vertex,
+                    (&tri[0], &tri[1]),
+                    major_edge,
+                );
 
-                self.flat_top_triangle_fill(tri[1], split_vertex, tri[2]);
+                self.flat_top_triangle_fill(
+                    tri[1],
+                    split_vertex,
+                    tri[2],
+                    (&tri[1], &tri[2]),
+                    major_edge,
+                );
             } else {
                 // Major left
 
-                self.flat_bottom_triangle_fill(tri[0], split_vertex, tri[1]);
+                self.flat_bottom_triangle_fill(
+                    tri[0],
+                    split_vertex,
+                    tri[1],
+                    major_edge,
+                    (&tri[0], &tri[1]),
+                );
 
-                self.flat_top_triangle_fill(split_vertex, tri[1], tri[2]);
+                self.flat_top_triangle_fill(
+                    split_vertex,
+                    tri[1],
+                    tri[2],
+                    major_edge,
+                    (&tri[1], &tri[2]),
+                );
             }
         }
     }
 
+    fn fixed_point_triangle_fill(
+        &mut self,
+        v0: DefaultVertexOut,
+        v1: DefaultVertexOut,
+        v2: DefaultVertexOut,
+        subpixel_precision_bits: u32,
+    ) {
+        let bits = subpixel_precision_bits.min(MAX_SUBPIXEL_PRECISION_BITS);
+
+        let scale = (1_i64 << bits) as f32;
+
+        let to_fixed = |v: &DefaultVertexOut| -> (i64, i64) {
+            (
+                (v.position_projection_space.x * scale).round() as i64,
+                (v.position_projection_space.y * scale).round() as i64,
+            )
+        };
+
+        let points = [to_fixed(&v0), to_fixed(&v1), to_fixed(&v2)];
+
+        let (width, height) = (self.viewport.width, self.viewport.height);
+
+        Graphics::for_each_fixed_point_triangle_pixel(
+            width,
+            height,
+            points,
+            bits,
+            |x, y, weights, area| {
+                let area_inverse = 1.0 / area as f32;
+
+                let mut interpolant = v0 * (weights[0] as f32 * area_inverse)
+                    + v1 * (weights[1] as f32 * area_inverse)
+                    + v2 * (weights[2] as f32 * area_inverse);
+
+                self.submit_fragment(x, y, &mut interpolant);
+            },
+        );
+    }
+
     fn flat_top_triangle_fill(
         &mut self,
         top_left: DefaultVertexOut,
         top_right: DefaultVertexOut,
         bottom: DefaultVertexOut,
+        left_edge: ScanlineEdge,
+        right_edge: ScanlineEdge,
     ) {
         let delta_y = bottom.position_projection_space.y - top_left.position_projection_space.y;
 
@@ -296,6 +466,8 @@ impl SoftwareRenderer {
             &top_left_step,
             &top_right_step,
             &mut right_edge_interpolant,
+            left_edge,
+            right_edge,
         );
     }
 
@@ -304,6 +476,8 @@ impl SoftwareRenderer {
         top: DefaultVertexOut,
         bottom_left: DefaultVertexOut,
         bottom_right: DefaultVertexOut,
+        left_edge: ScanlineEdge,
+        right_edge: ScanlineEdge,
     ) {
         let delta_y = bottom_right.position_projection_space.y - top.position_projection_space.y;
 
@@ -321,6 +495,8 @@ impl SoftwareRenderer {
             &bottom_left_step,
             &bottom_right_step,
             &mut right_edge_interpolant,
+            left_edge,
+            right_edge,
         );
     }
 
@@ -331,6 +507,8 @@ impl SoftwareRenderer {
         left_step: &DefaultVertexOut,
         right_step: &DefaultVertexOut,
         right_edge_interpolant: &mut DefaultVertexOut,
+        left_edge: ScanlineEdge,
+        right_edge: ScanlineEdge,
     ) {
         // it0 will always be a top vertex.
         // it1 is either a top or a bottom vertex.
@@ -351,7 +529,7 @@ impl SoftwareRenderer {
         let y_start: u32 = u32::max((it0.position_projection_space.y - 0.5).ceil() as u32, 0);
         let y_end: u32 = u32::min(
             (it2.position_projection_space.y - 0.5).ceil() as u32,
-            self.viewport.height - 1,
+            self.viewport.height,
         );
 
         // Adjust both interpolants to account for us snapping y-start and y-end
@@ -364,15 +542,18 @@ impl SoftwareRenderer {
         // Rasterization loop
         for y in y_start..y_end {
             // Calculate our start and end X (end here is non-inclusive), such
-            // that they are non-fractional screen coordinates.
+            // that they are non-fractional screen coordinates. Evaluating each
+            // edge directly (rather than stepping it) means that two triangles
+            // sharing an edge agree exactly on where it crosses the scanline,
+            // so that its pixels are covered by exactly one of them.
             let x_start = u32::max(
-                (left_edge_interpolant.position_projection_space.x - 0.5).ceil() as u32,
+                (get_edge_x_at(left_edge, y as f32 + 0.5) - 0.5).ceil() as u32,
                 0,
             );
 
             let x_end = u32::min(
-                (right_edge_interpolant.position_projection_space.x - 0.5).ceil() as u32,
-                self.viewport.width - 1,
+                (get_edge_x_at(right_edge, y as f32 + 0.5) - 0.5).ceil() as u32,
+                self.viewport.width,
             );
 
             // Create an interpolant that we can move across our horizontal
@@ -403,3 +584,647 @@ impl SoftwareRenderer {
         }
     }
 }
+
+// The x-coordinate at which a (top-to-bottom) edge crosses the given scanline.
+fn get_edge_x_at((top, bottom): ScanlineEdge, y: f32) -> f32 {
+    let (top, bottom) = (
+        top.position_projection_space,
+        bottom.position_projection_space,
+    );
+
+    top.x + (bottom.x - top.x) * ((y - top.y) / (bottom.y - top.y))
+}
+
+// UV-space area per unit of viewport-space area, for a triangle (given its
+// original vertices, and those same vertices in viewport space).
+fn get_uv_footprint(
+    projection_space_vertices: &[DefaultVertexOut; 3],
+    viewport_space_vertices: &[DefaultVertexOut; 3],
+) -> f32 {
+    let [uv0, uv1, uv2] = projection_space_vertices.map(|vertex| vertex.uv);
+
+    let [p0, p1, p2] = viewport_space_vertices.map(|vertex| vertex.position_projection_space);
+
+    let uv_area = ((uv1.x - uv0.x) * (uv2.y - uv0.y) - (uv1.y - uv0.y) * (uv2.x - uv0.x)).abs();
+
+    let viewport_area = ((p1.x - p0.x) * (p2.y - p0.y) - (p1.y - p0.y) * (p2.x - p0.x)).abs();
+
+    if viewport_area == 0.0 {
+        0.0
+    } else {
+        uv_area / viewport_area
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, f32::consts::TAU, rc::Rc};
+
+    use crate::{
+        buffer::framebuffer::Framebuffer,
+        color::Color,
+        render::{
+            clip::{clip_polygon_against_frustum, clip_polygon_against_guard_band, FRUSTUM_PLANES},
+            options::rasterizer::AttributeInterpolation,
+            viewport::RenderViewport,
+            Renderer,
+        },
+        scene::camera::Camera,
+        shader::context::ShaderContext,
+        shaders::default_vertex_shader::DEFAULT_VERTEX_SHADER,
+        software_renderer::{
+            zbuffer::{DepthTestMethod, MAX_DEPTH},
+            SoftwareRenderer,
+        },
+        vec::{vec2::Vec2, vec3::Vec3, vec4::Vec4},
+        vertex::default_vertex_out::DefaultVertexOut,
+    };
+
+    use super::Triangle;
+
+    fn get_vertex(ndc_x: f32, ndc_y: f32, w: f32, u: f32, v: f32) -> DefaultVertexOut {
+        DefaultVertexOut {
+            position_projection_space: Vec4::new(
+                Vec3 {
+                    x: ndc_x * w,
+                    y: ndc_y * w,
+                    z: 0.5 * w,
+                },
+                w,
+            ),
+            uv: Vec2 { x: u, y: v, z: 0.0 },
+            ..Default::default()
+        }
+    }
+
+    fn get_uv_at(interpolation: AttributeInterpolation, x: u32, y: u32) -> Vec2 {
+        let mut renderer = SoftwareRenderer::new(
+            Default::default(),
+            Default::default(),
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        renderer.options.rasterizer_options.attribute_interpolation = interpolation;
+
+        let mut framebuffer = Framebuffer::new(64, 64);
+
+        framebuffer.complete(0.3, 100.0);
+
+        renderer.bind_framebuffer(Some(Rc::new(RefCell::new(framebuffer))));
+
+        // The right-hand vertex is much farther away than the other two.
+
+        renderer.process_triangle(&Triangle {
+            v0: get_vertex(-0.8, -0.8, 1.0, 0.0, 0.0),
+            v1: get_vertex(0.8, -0.8, 4.0, 1.0, 0.0),
+            v2: get_vertex(-0.8, 0.8, 1.0, 0.0, 1.0),
+        });
+
+        renderer.g_buffer.as_ref().unwrap().0.get(x, y).uv
+    }
+
+    #[test]
+    fn affine_interpolation_ignores_perspective() {
+        let (x, y) = (24, 40);
+
+        // Screen-space barycentric coordinates of the pixel center, relative
+        // to the (viewport-space) vertices (6.4, 57.6), (57.6, 57.6) and
+        // (6.4, 6.4).
+
+        let b1 = (x as f32 + 0.5 - 6.4) / 51.2;
+        let b2 = (57.6 - (y as f32 + 0.5)) / 51.2;
+        let b0 = 1.0 - b1 - b2;
+
+        let affine = get_uv_at(AttributeInterpolation::Affine, x, y);
+
+        assert!((affine.x - b1).abs() < 0.001);
+        assert!((affine.y - b2).abs() < 0.001);
+
+        // Perspective-correct interpolation weights each vertex by 1/w.
+
+        let (w0, w1, w2) = (1.0, 0.25, 1.0);
+
+        let w_sum = b0 * w0 + b1 * w1 + b2 * w2;
+
+        let perspective = get_uv_at(AttributeInterpolation::PerspectiveCorrect, x, y);
+
+        assert!((perspective.x - b1 * w1 / w_sum).abs() < 0.001);
+        assert!((perspective.y - b2 * w2 / w_sum).abs() < 0.001);
+
+        assert!((affine.x - perspective.x).abs() > 0.05);
+    }
+
+    #[test]
+    fn depth_tested_wireframe_edges_carry_the_wireframe_color() {
+        let camera = Camera::from_perspective(
+            Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            Default::default(),
+            75.0,
+            1.0,
+        );
+
+        let shader_context: Rc<RefCell<ShaderContext>> = Default::default();
+
+        camera.update_shader_context(&mut shader_context.borrow_mut());
+
+        let mut renderer = SoftwareRenderer::new(
+            shader_context.clone(),
+            Default::default(),
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        renderer.options.draw_wireframe = true;
+        renderer.options.wireframe_depth_tested = true;
+        renderer.options.wireframe_color = Vec3 {
+            x: 1.0,
+            y: 0.0,
+            z: 1.0,
+        };
+
+        let mut framebuffer = Framebuffer::new(64, 64);
+
+        framebuffer.complete(0.3, 100.0);
+
+        let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+        renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+        let get_triangle = |positions: [Vec3; 3]| {
+            let view_projection = camera.get_view_inverse_transform() * camera.get_projection();
+
+            let [v0, v1, v2] = positions.map(|position| DefaultVertexOut {
+                position_world_space: position,
+                position_projection_space: Vec4::new(position, 1.0) * view_projection,
+                ..Default::default()
+            });
+
+            Triangle { v0, v1, v2 }
+        };
+
+        let visible = [
+            Vec3 {
+                x: -1.0,
+                y: -1.0,
+                z: 0.0,
+            },
+            Vec3 {
+                x: 1.0,
+                y: -1.0,
+                z: 0.0,
+            },
+            Vec3 {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+        ];
+
+        // A smaller triangle, hidden behind the first.
+
+        let hidden = visible.map(|position| {
+            position * 0.5
+                + Vec3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 2.0,
+                }
+        });
+
+        renderer.process_triangle(&get_triangle(hidden));
+        renderer.process_triangle(&get_triangle(visible));
+
+        renderer.flush_depth_tested_lines();
+
+        let wireframe_color = Color::rgb(255, 0, 255).to_u32();
+
+        let framebuffer = framebuffer_rc.borrow();
+
+        let forward_buffer = framebuffer
+            .attachments
+            .forward_ldr
+            .as_ref()
+            .unwrap()
+            .borrow();
+
+        let get_pixel = |position: Vec3| -> u32 {
+            let ndc = shader_context.borrow().to_ndc_space(position);
+
+            *forward_buffer.get((ndc.x * 64.0) as u32, (ndc.y * 64.0) as u32)
+        };
+
+        for position in visible {
+            assert_eq!(get_pixel(position), wireframe_color);
+        }
+
+        for position in hidden {
+            assert_ne!(get_pixel(position), wireframe_color);
+        }
+    }
+
+    #[test]
+    fn fixed_point_rasterization_covers_shared_edges_exactly_once() {
+        let mut renderer = SoftwareRenderer::new(
+            Default::default(),
+            Default::default(),
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        renderer.options.rasterizer_options.subpixel_precision_bits = Some(4);
+
+        let mut framebuffer = Framebuffer::new(64, 64);
+
+        framebuffer.complete(0.3, 100.0);
+
+        // Counts overlapping fragments (at equal depths) as shaded.
+
+        framebuffer
+            .attachments
+            .depth
+            .as_ref()
+            .unwrap()
+            .borrow_mut()
+            .set_depth_test_method(DepthTestMethod::LessThanOrEqual);
+
+        renderer.bind_framebuffer(Some(Rc::new(RefCell::new(framebuffer))));
+
+        // A quad, split along its diagonal, with corners at fractional
+        // (viewport-space) pixel coordinates.
+
+        let vertex_at = |x: f32, y: f32| get_vertex(x / 32.0 - 1.0, 1.0 - y / 32.0, 1.0, 0.0, 0.0);
+
+        let (top_left, top_right, bottom_left, bottom_right) = (
+            vertex_at(10.3, 9.7),
+            vertex_at(50.6, 9.7),
+            vertex_at(10.3, 52.2),
+            vertex_at(50.6, 52.2),
+        );
+
+        renderer.process_triangle(&Triangle {
+            v0: top_left,
+            v1: bottom_left,
+            v2: bottom_right,
+        });
+
+        renderer.process_triangle(&Triangle {
+            v0: top_left,
+            v1: bottom_right,
+            v2: top_right,
+        });
+
+        // Pixels whose centers lie within the quad: columns 10 through 50, and
+        // rows 10 through 51.
+
+        let g_buffer = renderer.g_buffer.as_ref().unwrap();
+
+        for y in 10..=51 {
+            for x in 10..=50 {
+                assert!(g_buffer.get(x, y).stencil, "({}, {}) was not written", x, y);
+            }
+        }
+
+        assert_eq!(renderer.stats.fragments_shaded, 41 * 42);
+    }
+
+    // Rasterizes each of the given triangles (in viewport space) into its own
+    // frame, returning the number of fragments written to each pixel.
+    fn count_fragments_per_pixel(
+        subpixel_precision_bits: Option<u32>,
+        triangles: &[[(f32, f32); 3]],
+    ) -> Vec<u32> {
+        let mut renderer = SoftwareRenderer::new(
+            Default::default(),
+            Default::default(),
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        renderer.options.rasterizer_options.subpixel_precision_bits = subpixel_precision_bits;
+
+        let mut framebuffer = Framebuffer::new(64, 64);
+
+        framebuffer.complete(0.3, 100.0);
+
+        let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+        renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+        let vertex_at =
+            |(x, y): (f32, f32)| get_vertex(x / 32.0 - 1.0, 1.0 - y / 32.0, 1.0, 0.0, 0.0);
+
+        let mut write_counts = vec![0; 64 * 64];
+
+        for [a, b, c] in triangles {
+            // Clears the depth buffer, so that every fragment is counted.
+
+            renderer.begin_frame();
+
+            renderer.process_triangle(&Triangle {
+                v0: vertex_at(*a),
+                v1: vertex_at(*b),
+                v2: vertex_at(*c),
+            });
+
+            let framebuffer = framebuffer_rc.borrow();
+
+            let depth_buffer = framebuffer.attachments.depth.as_ref().unwrap().borrow();
+
+            for (index, count) in write_counts.iter_mut().enumerate() {
+                if *depth_buffer.buffer.get_at(index) != MAX_DEPTH {
+                    *count += 1;
+                }
+            }
+        }
+
+        write_counts
+    }
+
+    #[test]
+    fn triangle_fans_write_each_pixel_exactly_once() {
+        // A fan of triangles sharing a center vertex, and an edge with each
+        // neighbor.
+
+        let (center, triangle_count) = ((32.3, 31.7), 12);
+
+        let rim: Vec<(f32, f32)> = (0..triangle_count)
+            .map(|i| {
+                let theta = TAU * (i as f32 + 0.25) / triangle_count as f32;
+
+                (center.0 + 24.1 * theta.cos(), center.1 + 23.6 * theta.sin())
+            })
+            .collect();
+
+        let triangles: Vec<[(f32, f32); 3]> = (0..triangle_count)
+            .map(|i| [center, rim[i], rim[(i + 1) % triangle_count]])
+            .collect();
+
+        // Whether each pixel's center lies (unambiguously) inside the fan.
+
+        let is_inside = |x: usize, y: usize| {
+            let p = (x as f32 + 0.5, y as f32 + 0.5);
+
+            triangles.iter().all(|[_, a, b]| {
+                let cross = (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+
+                let length = ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt();
+
+                cross / length > 0.05
+            })
+        };
+
+        for subpixel_precision_bits in [None, Some(4), Some(8)] {
+            let write_counts = count_fragments_per_pixel(subpixel_precision_bits, &triangles);
+
+            for (index, count) in write_counts.into_iter().enumerate() {
+                let (x, y) = (index % 64, index / 64);
+
+                assert!(
+                    count <= 1,
+                    "{:?}: ({}, {}) was written {} times",
+                    subpixel_precision_bits,
+                    x,
+                    y,
+                    count
+                );
+
+                if is_inside(x, y) {
+                    assert_eq!(
+                        count, 1,
+                        "{:?}: ({}, {}) was not written",
+                        subpixel_precision_bits, x, y
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn triangle_meshes_covering_the_viewport_write_each_pixel_exactly_once() {
+        // An 8x8 grid of quads (each split into two triangles), whose interior
+        // vertices are jittered by up to 2.5 pixels.
+
+        let n = 8;
+
+        let grid_point = |i: usize, j: usize| -> (f32, f32) {
+            let (x, y) = (i as f32 * 8.0, j as f32 * 8.0);
+
+            if i == 0 || j == 0 || i == n || j == n {
+                return (x, y);
+            }
+
+            let jitter_x = ((i * 7919 + j * 104729 + 899) % 1000) as f32 / 1000.0 - 0.5;
+            let jitter_y = ((i * 15485863 + j * 32452843 + 493) % 1000) as f32 / 1000.0 - 0.5;
+
+            (x + jitter_x * 5.0, y + jitter_y * 5.0)
+        };
+
+        let mut triangles = vec![];
+
+        for j in 0..n {
+            for i in 0..n {
+                let (top_left, top_right, bottom_left, bottom_right) = (
+                    grid_point(i, j),
+                    grid_point(i + 1, j),
+                    grid_point(i, j + 1),
+                    grid_point(i + 1, j + 1),
+                );
+
+                triangles.push([top_left, bottom_left, bottom_right]);
+                triangles.push([top_left, bottom_right, top_right]);
+            }
+        }
+
+        for subpixel_precision_bits in [None, Some(4), Some(8)] {
+            let write_counts = count_fragments_per_pixel(subpixel_precision_bits, &triangles);
+
+            for (index, count) in write_counts.into_iter().enumerate() {
+                assert_eq!(
+                    count,
+                    1,
+                    "{:?}: ({}, {}) was written {} times",
+                    subpixel_precision_bits,
+                    index % 64,
+                    index / 64,
+                    count
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn vertex_frame_segments_extend_from_the_vertex_by_the_scale() {
+        let mut renderer = SoftwareRenderer::new(
+            Default::default(),
+            Default::default(),
+            DEFAULT_VERTEX_SHADER,
+            |_, _, _| Vec3::ones(),
+            Default::default(),
+        );
+
+        let vertex = DefaultVertexOut {
+            position_world_space: Vec3 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            },
+            normal_world_space: Vec3 {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            tangent_world_space: Vec3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            bitangent_world_space: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            ..Default::default()
+        };
+
+        // Nothing is drawn by default.
+
+        assert!(renderer.get_vertex_frame_segments(&vertex).is_empty());
+
+        renderer.options.draw_normals = true;
+        renderer.options.draw_normals_scale = 0.5;
+
+        let segments = renderer.get_vertex_frame_segments(&vertex);
+
+        assert_eq!(segments.len(), 1);
+
+        let (start, end, _) = segments[0];
+
+        assert_eq!(start, vertex.position_world_space);
+        assert_eq!(
+            end,
+            vertex.position_world_space + vertex.normal_world_space * 0.5
+        );
+
+        // Full TBN frames.
+
+        renderer.options.draw_tangents = true;
+        renderer.options.draw_bitangents = true;
+
+        let ends: Vec<Vec3> = renderer
+            .get_vertex_frame_segments(&vertex)
+            .into_iter()
+            .map(|(_, end, _)| end)
+            .collect();
+
+        assert_eq!(
+            ends,
+            vec![
+                vertex.position_world_space + vertex.normal_world_space * 0.5,
+                vertex.position_world_space + vertex.tangent_world_space * 0.5,
+                vertex.position_world_space + vertex.bitangent_world_space * 0.5,
+            ]
+        );
+    }
+
+    #[test]
+    fn triangles_within_the_guard_band_skip_clipping_but_rasterize_on_screen() {
+        // Crosses the left edge of the screen, but lies within a 2x guard band;
+        // its long edge crosses that screen edge at a whole pixel coordinate,
+        // so that clipping introduces no (sub-pixel) rounding of its own.
+
+        let triangle = Triangle {
+            v0: get_vertex(-1.5, -0.5, 1.0, 0.0, 0.0),
+            v1: get_vertex(0.5, -0.5, 1.0, 0.0, 0.0),
+            v2: get_vertex(-1.5, 0.5, 1.0, 0.0, 0.0),
+        };
+
+        let vertices = [triangle.v0, triangle.v1, triangle.v2];
+
+        let positions = |polygon: &[DefaultVertexOut]| -> Vec<Vec4> {
+            polygon
+                .iter()
+                .map(|vertex| vertex.position_projection_space)
+                .collect()
+        };
+
+        assert_eq!(
+            positions(&clip_polygon_against_guard_band(
+                &vertices,
+                2.0,
+                &RenderViewport {
+                    width: 64,
+                    width_over_2: 32.0,
+                    height: 64,
+                    height_over_2: 32.0,
+                }
+            )),
+            positions(&vertices)
+        );
+
+        assert_ne!(
+            positions(&clip_polygon_against_frustum(&vertices, &FRUSTUM_PLANES)),
+            positions(&vertices)
+        );
+
+        // Either way, the same (on-screen) pixels are written.
+
+        let get_written_pixels = |guard_band: Option<f32>, subpixel_precision_bits: Option<u32>| {
+            let mut renderer = SoftwareRenderer::new(
+                Default::default(),
+                Default::default(),
+                DEFAULT_VERTEX_SHADER,
+                |_, _, _| Vec3::ones(),
+                Default::default(),
+            );
+
+            renderer.options.rasterizer_options.guard_band = guard_band;
+            renderer.options.rasterizer_options.subpixel_precision_bits = subpixel_precision_bits;
+
+            let mut framebuffer = Framebuffer::new(64, 64);
+
+            framebuffer.complete(0.3, 100.0);
+
+            let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+            renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+            renderer.process_triangle(&triangle);
+
+            let framebuffer = framebuffer_rc.borrow();
+
+            let depth_buffer = framebuffer.attachments.depth.as_ref().unwrap().borrow();
+
+            (0..64 * 64)
+                .map(|index| *depth_buffer.buffer.get_at(index) != MAX_DEPTH)
+                .collect::<Vec<bool>>()
+        };
+
+        for subpixel_precision_bits in [None, Some(4)] {
+            let clipped = get_written_pixels(None, subpixel_precision_bits);
+            let guard_banded = get_written_pixels(Some(2.0), subpixel_precision_bits);
+
+            // Includes pixels along the left edge of the screen.
+
+            assert!(guard_banded[32 * 64]);
+
+            assert_eq!(clipped, guard_banded);
+        }
+
+        // An oversized guard band is clamped, rather than overflowing the
+        // fixed-point edge functions.
+
+        assert_eq!(
+            get_written_pixels(None, Some(16)),
+            get_written_pixels(Some(f32::MAX), Some(16))
+        );
+    }
+}
@@ -0,0 +1,464 @@
+use std::{
+    cell::RefCell,
+    fmt, fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use sdl2::{
+    image::{LoadSurface, SaveSurface},
+    pixels::PixelFormatEnum,
+    surface::Surface,
+};
+
+use crate::{
+    buffer::{framebuffer::Framebuffer, Buffer2D},
+    color::Color,
+    matrix::Mat4,
+    render::Renderer,
+    scene::{
+        context::{utils::make_cube_scene, SceneContext},
+        graph::SceneGraph,
+        node::{SceneNode, SceneNodeGlobalTraversalMethod, SceneNodeType},
+        resources::SceneResources,
+    },
+    shader::context::ShaderContext,
+    shaders::{
+        default_fragment_shader::DEFAULT_FRAGMENT_SHADER,
+        default_vertex_shader::DEFAULT_VERTEX_SHADER,
+    },
+    software_renderer::SoftwareRenderer,
+    vec::vec4::Vec4,
+};
+
+use uuid::Uuid;
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct ImageDiff {
+    pub max_difference: u8,
+    pub mean_difference: f32,
+    pub differing_pixels: usize,
+}
+
+impl fmt::Display for ImageDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ImageDiff (max_difference={}, mean_difference={:.4}, differing_pixels={})",
+            self.max_difference, self.mean_difference, self.differing_pixels
+        )
+    }
+}
+
+impl ImageDiff {
+    pub fn is_within(&self, tolerance: u8) -> bool {
+        self.max_difference <= tolerance
+    }
+}
+
+// Compares two color buffers channel-by-channel (RGBA).
+pub fn diff_images(actual: &Buffer2D, expected: &Buffer2D) -> Result<ImageDiff, String> {
+    if actual.width != expected.width || actual.height != expected.height {
+        return Err(format!(
+            "Image dimensions differ: actual is {}x{}, expected is {}x{}.",
+            actual.width, actual.height, expected.width, expected.height
+        ));
+    }
+
+    let mut diff = ImageDiff::default();
+
+    let mut total_difference = 0_u64;
+
+    for (a, b) in actual.data.iter().zip(expected.data.iter()) {
+        let pixel_difference = a
+            .to_le_bytes()
+            .iter()
+            .zip(b.to_le_bytes().iter())
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap();
+
+        if pixel_difference > 0 {
+            diff.differing_pixels += 1;
+        }
+
+        diff.max_difference = diff.max_difference.max(pixel_difference);
+
+        total_difference += pixel_difference as u64;
+    }
+
+    diff.mean_difference = total_difference as f32 / actual.data.len().max(1) as f32;
+
+    Ok(diff)
+}
+
+// A unique path under the system's temp directory; the file or directory at
+// this path (if any) is removed when the `TempPath` is dropped. The name is
+// kept as a suffix, so that any file extension is preserved.
+pub struct TempPath {
+    path: PathBuf,
+}
+
+impl TempPath {
+    pub fn new(name: &str) -> Self {
+        Self {
+            path: std::env::temp_dir().join(format!("cairo-{}-{}", Uuid::new_v4(), name)),
+        }
+    }
+
+    pub fn new_dir(name: &str) -> Self {
+        let temp_path = Self::new(name);
+
+        fs::create_dir_all(&temp_path.path).unwrap();
+
+        temp_path
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TempPath {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        // Cleanup is best-effort; a test may never have written to this path.
+
+        if self.path.is_dir() {
+            fs::remove_dir_all(&self.path).ok();
+        } else {
+            fs::remove_file(&self.path).ok();
+        }
+    }
+}
+
+// Set this environment variable to (re-)write golden images from the actual
+// rendered images, rather than comparing against them.
+pub static UPDATE_GOLDENS_VAR: &str = "UPDATE_GOLDENS";
+
+// Compares a rendered image against a stored golden PNG. A missing golden
+// image is an error, unless `UPDATE_GOLDENS` is set.
+pub fn assert_matches_golden(
+    actual: &Buffer2D,
+    golden_path: &Path,
+    tolerance: u8,
+) -> Result<ImageDiff, String> {
+    if std::env::var_os(UPDATE_GOLDENS_VAR).is_some() {
+        save_png(actual, golden_path)?;
+
+        return Ok(Default::default());
+    }
+
+    if !golden_path.exists() {
+        return Err(format!(
+            "Missing golden image '{}' (set {} to write it).",
+            golden_path.display(),
+            UPDATE_GOLDENS_VAR
+        ));
+    }
+
+    let expected = load_png(golden_path)?;
+
+    let diff = diff_images(actual, &expected)?;
+
+    if diff.is_within(tolerance) {
+        Ok(diff)
+    } else {
+        Err(format!(
+            "Image does not match golden image '{}' (tolerance {}): {}",
+            golden_path.display(),
+            tolerance,
+            diff
+        ))
+    }
+}
+
+pub fn load_png(path: &Path) -> Result<Buffer2D, String> {
+    let surface = Surface::from_file(path)?.convert_format(PixelFormatEnum::RGBA32)?;
+
+    let (width, height, pitch) = (surface.width(), surface.height(), surface.pitch());
+
+    let mut buffer = Buffer2D::new(width, height, None);
+
+    surface.with_lock(|pixels| {
+        for y in 0..height {
+            for x in 0..width {
+                let index = (y * pitch + x * 4) as usize;
+
+                let color = Color::rgba(
+                    pixels[index],
+                    pixels[index + 1],
+                    pixels[index + 2],
+                    pixels[index + 3],
+                );
+
+                buffer.set(x, y, color.to_u32());
+            }
+        }
+    });
+
+    Ok(buffer)
+}
+
+pub fn save_png(buffer: &Buffer2D, path: &Path) -> Result<(), String> {
+    let mut bytes: Vec<u8> = buffer
+        .data
+        .iter()
+        .flat_map(|pixel| pixel.to_le_bytes())
+        .collect();
+
+    let surface = Surface::from_data(
+        &mut bytes,
+        buffer.width,
+        buffer.height,
+        buffer.width * 4,
+        PixelFormatEnum::RGBA32,
+    )?;
+
+    surface.save(path)
+}
+
+// Renders the first scene in `scene_context` without a window, returning the
+// framebuffer's final color attachment.
+pub fn render_headless(
+    scene_context: &SceneContext,
+    mut shader_context: ShaderContext,
+    width: u32,
+    height: u32,
+) -> Result<Buffer2D, String> {
+    let scenes = scene_context.scenes.borrow();
+
+    let scene = scenes
+        .first()
+        .ok_or("Scene context contains no scenes to render.")?;
+
+    bind_scene_to_shader_context(scene, &scene_context.resources, &mut shader_context)?;
+
+    let mut framebuffer = Framebuffer::new(width, height);
+
+    framebuffer.complete(0.3, 100.0);
+
+    let framebuffer_rc = Rc::new(RefCell::new(framebuffer));
+
+    let mut renderer = SoftwareRenderer::new(
+        Rc::new(RefCell::new(shader_context)),
+        scene_context.resources.clone(),
+        DEFAULT_VERTEX_SHADER,
+        DEFAULT_FRAGMENT_SHADER,
+        Default::default(),
+    );
+
+    renderer.bind_framebuffer(Some(framebuffer_rc.clone()));
+
+    let renderer_rc = RefCell::new(renderer);
+
+    renderer_rc.borrow_mut().begin_frame();
+
+    scene.render(&scene_context.resources, &renderer_rc, None)?;
+
+    renderer_rc.borrow_mut().end_frame();
+
+    let framebuffer = framebuffer_rc.borrow();
+
+    match framebuffer.attachments.color.as_ref() {
+        Some(color_buffer_rc) => Ok(color_buffer_rc.borrow().clone()),
+        None => Err("Framebuffer has no color attachment.".to_string()),
+    }
+}
+
+// Without an `App`, we can't run `SceneGraph::update()`; instead, we bind the
+// scene's active camera and lights to the shader context directly.
+fn bind_scene_to_shader_context(
+    scene: &SceneGraph,
+    resources: &SceneResources,
+    shader_context: &mut ShaderContext,
+) -> Result<(), String> {
+    scene.root.visit(
+        SceneNodeGlobalTraversalMethod::DepthFirst,
+        None,
+        &mut |_current_depth: usize, current_world_transform: Mat4, node: &SceneNode| {
+            let handle = match node.get_handle() {
+                Some(handle) => *handle,
+                None => return Ok(()),
+            };
+
+            match node.get_type() {
+                SceneNodeType::Camera => {
+                    let mut camera_arena = resources.camera.borrow_mut();
+
+                    let camera = &mut camera_arena.get_mut(&handle)?.item;
+
+                    if camera.is_active {
+                        camera.recompute_world_space_frustum();
+
+                        camera.update_shader_context(shader_context);
+                    }
+                }
+                SceneNodeType::AmbientLight => {
+                    shader_context.set_ambient_light(Some(handle));
+                }
+                SceneNodeType::DirectionalLight => {
+                    shader_context.set_directional_light(Some(handle));
+                }
+                SceneNodeType::PointLight => {
+                    let mut point_light_arena = resources.point_light.borrow_mut();
+
+                    let point_light = &mut point_light_arena.get_mut(&handle)?.item;
+
+                    point_light.position =
+                        (Vec4::new(Default::default(), 1.0) * current_world_transform).to_vec3();
+
+                    shader_context.get_point_lights_mut().push(handle);
+                }
+                _ => (),
+            }
+
+            Ok(())
+        },
+    )
+}
+
+// A fixed scene (the default cube scene) for golden-image tests.
+pub fn render_cube_scene(width: u32, height: u32) -> Result<Buffer2D, String> {
+    let scene_context = SceneContext::default();
+
+    let shader_context = {
+        let resources = &scene_context.resources;
+
+        let (scene, shader_context) = make_cube_scene(
+            &mut resources.camera.borrow_mut(),
+            width as f32 / height as f32,
+            &mut resources.environment.borrow_mut(),
+            &mut resources.ambient_light.borrow_mut(),
+            &mut resources.directional_light.borrow_mut(),
+            &mut resources.mesh.borrow_mut(),
+            &mut resources.material.borrow_mut(),
+            &mut resources.entity.borrow_mut(),
+        )?;
+
+        // Aim the scene's camera at the cube.
+
+        for entry in resources.camera.borrow_mut().entries.iter_mut().flatten() {
+            entry.item.look_vector.set_target(Default::default());
+        }
+
+        scene_context.scenes.borrow_mut().push(scene);
+
+        shader_context
+    };
+
+    render_headless(&scene_context, shader_context, width, height)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{buffer::Buffer2D, color::Color};
+
+    use super::{
+        assert_matches_golden, diff_images, load_png, render_cube_scene, save_png, TempPath,
+    };
+
+    #[test]
+    fn identical_image_passes_and_shifted_image_fails() {
+        let (width, height) = (64, 48);
+
+        let golden = render_cube_scene(width, height).unwrap();
+
+        // Rendering is deterministic.
+
+        let identical = render_cube_scene(width, height).unwrap();
+
+        let diff = diff_images(&identical, &golden).unwrap();
+
+        assert!(diff.is_within(0));
+        assert_eq!(diff.differing_pixels, 0);
+
+        // Shift the image right by 4 pixels.
+
+        let mut shifted = Buffer2D::new(width, height, Some(golden.data[0]));
+
+        for y in 0..height {
+            for x in 4..width {
+                shifted.set(x, y, *golden.get(x - 4, y));
+            }
+        }
+
+        let diff = diff_images(&shifted, &golden).unwrap();
+
+        assert!(!diff.is_within(8));
+        assert!(diff.mean_difference > 0.0);
+    }
+
+    #[test]
+    fn temp_path_is_unique_and_removed_on_drop() {
+        let (file, directory) = (TempPath::new("a.txt"), TempPath::new_dir("a"));
+
+        assert_ne!(file.path(), TempPath::new("a.txt").path());
+        assert_eq!(file.extension().unwrap(), "txt");
+
+        std::fs::write(&file, "a").unwrap();
+        std::fs::write(directory.join("b.txt"), "b").unwrap();
+
+        let (file_path, directory_path) = (file.to_path_buf(), directory.to_path_buf());
+
+        drop(file);
+        drop(directory);
+
+        assert!(!file_path.exists());
+        assert!(!directory_path.exists());
+    }
+
+    #[test]
+    fn saved_png_round_trips_and_matches_as_a_golden_image() {
+        let mut image = Buffer2D::new(8, 4, None);
+
+        for y in 0..4 {
+            for x in 0..8 {
+                image.set(
+                    x,
+                    y,
+                    Color::rgba(x as u8 * 32, y as u8 * 64, 200, 255).to_u32(),
+                );
+            }
+        }
+
+        let golden_path = TempPath::new("golden.png");
+
+        // A missing golden image is an error.
+
+        assert!(assert_matches_golden(&image, &golden_path, 0).is_err());
+
+        save_png(&image, &golden_path).unwrap();
+
+        assert_eq!(load_png(&golden_path).unwrap().data, image.data);
+
+        let diff = assert_matches_golden(&image, &golden_path, 0).unwrap();
+
+        assert_eq!(diff.differing_pixels, 0);
+
+        // A single pixel, off by 10 in each color channel.
+
+        image.set(3, 2, Color::rgba(106, 138, 210, 255).to_u32());
+
+        assert!(assert_matches_golden(&image, &golden_path, 8).is_err());
+
+        let diff = assert_matches_golden(&image, &golden_path, 10).unwrap();
+
+        assert_eq!(diff.max_difference, 10);
+        assert_eq!(diff.differing_pixels, 1);
+    }
+}
@@ -0,0 +1,217 @@
+use super::{
+    get_level_dimensions, get_level_length, get_max_level_count, TextureContainer,
+    TextureContainerFormat,
+};
+
+// Reference:
+// https://learn.microsoft.com/en-us/windows/win32/direct3ddds/dds-header
+
+pub static DDS_MAGIC: &[u8] = b"DDS ";
+
+// Magic (4 bytes), and header (124 bytes).
+static DDS_DATA_OFFSET: usize = 128;
+
+// Additional header, following the standard header, for "DX10" files.
+static DDS_DX10_HEADER_SIZE: usize = 20;
+
+static DDPF_ALPHAPIXELS: u32 = 0x1;
+static DDPF_FOURCC: u32 = 0x4;
+static DDPF_RGB: u32 = 0x40;
+static DDPF_LUMINANCE: u32 = 0x20000;
+
+// How each pixel's data is laid out, before conversion.
+enum DdsPixelLayout {
+    // Stored as one of our formats, as-is.
+    Direct(TextureContainerFormat),
+    // 8-bit channels, packed into 1, 3, or 4 bytes by the given bit masks (red,
+    // green, blue, alpha).
+    Masked(usize, [u32; 4]),
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| "Unexpected end of DDS data.".to_string())
+}
+
+// DXGI formats (from a "DX10" header) that we can read.
+fn get_dxgi_format(dxgi_format: u32) -> Result<TextureContainerFormat, String> {
+    match dxgi_format {
+        // DXGI_FORMAT_R32G32B32A32_FLOAT
+        2 => Ok(TextureContainerFormat::RGBA32F),
+        // DXGI_FORMAT_R32G32B32_FLOAT
+        6 => Ok(TextureContainerFormat::RGB32F),
+        // DXGI_FORMAT_R16G16B16A16_FLOAT
+        10 => Ok(TextureContainerFormat::RGBA16F),
+        // DXGI_FORMAT_R8G8B8A8_UNORM, DXGI_FORMAT_R8G8B8A8_UNORM_SRGB
+        28 | 29 => Ok(TextureContainerFormat::RGBA8),
+        // DXGI_FORMAT_R8_UNORM
+        61 => Ok(TextureContainerFormat::R8),
+        // BC1-BC5, and BC6H-BC7
+        70..=84 | 94..=99 => Err(format!(
+            "Block-compressed DDS format (DXGI format {}) is not supported.",
+            dxgi_format
+        )),
+        _ => Err(format!(
+            "Unsupported DDS format (DXGI format {}).",
+            dxgi_format
+        )),
+    }
+}
+
+fn get_pixel_layout(bytes: &[u8]) -> Result<(DdsPixelLayout, usize), String> {
+    // Pixel format (DDS_PIXELFORMAT) fields.
+
+    let flags = read_u32(bytes, 80)?;
+    let four_cc = read_u32(bytes, 84)?;
+    let bit_count = read_u32(bytes, 88)?;
+
+    if flags & DDPF_FOURCC != 0 {
+        return match &four_cc.to_le_bytes() {
+            b"DX10" => Ok((
+                DdsPixelLayout::Direct(get_dxgi_format(read_u32(bytes, DDS_DATA_OFFSET)?)?),
+                DDS_DATA_OFFSET + DDS_DX10_HEADER_SIZE,
+            )),
+            b"DXT1" | b"DXT2" | b"DXT3" | b"DXT4" | b"DXT5" | b"ATI1" | b"ATI2" | b"BC4U"
+            | b"BC5U" => Err(format!(
+                "Block-compressed DDS format ('{}') is not supported.",
+                String::from_utf8_lossy(&four_cc.to_le_bytes())
+            )),
+            _ => match four_cc {
+                // D3DFMT_A16B16G16R16F
+                113 => Ok((
+                    DdsPixelLayout::Direct(TextureContainerFormat::RGBA16F),
+                    DDS_DATA_OFFSET,
+                )),
+                // D3DFMT_A32B32G32R32F
+                116 => Ok((
+                    DdsPixelLayout::Direct(TextureContainerFormat::RGBA32F),
+                    DDS_DATA_OFFSET,
+                )),
+                _ => Err(format!("Unsupported DDS format (FourCC {}).", four_cc)),
+            },
+        };
+    }
+
+    if flags & (DDPF_RGB | DDPF_LUMINANCE) != 0 && matches!(bit_count, 8 | 24 | 32) {
+        let mut masks = [
+            read_u32(bytes, 92)?,
+            read_u32(bytes, 96)?,
+            read_u32(bytes, 100)?,
+            0,
+        ];
+
+        if flags & DDPF_ALPHAPIXELS != 0 {
+            masks[3] = read_u32(bytes, 104)?;
+        }
+
+        return Ok((
+            DdsPixelLayout::Masked(bit_count as usize / 8, masks),
+            DDS_DATA_OFFSET,
+        ));
+    }
+
+    Err(format!(
+        "Unsupported DDS pixel format (flags {:#x}, {} bits per pixel).",
+        flags, bit_count
+    ))
+}
+
+fn get_masked_channel(pixel: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 255;
+    }
+
+    ((pixel & mask) >> mask.trailing_zeros()) as u8
+}
+
+pub fn parse_dds(bytes: &[u8]) -> Result<TextureContainer, String> {
+    if !bytes.starts_with(DDS_MAGIC) {
+        return Err("Missing DDS magic number.".to_string());
+    }
+
+    let height = read_u32(bytes, 12)?;
+    let width = read_u32(bytes, 16)?;
+
+    if width == 0 || height == 0 {
+        return Err(format!("Invalid DDS dimensions ({}x{}).", width, height));
+    }
+
+    let level_count =
+        (read_u32(bytes, 28)?.max(1) as usize).min(get_max_level_count(width, height));
+
+    let (layout, data_offset) = get_pixel_layout(bytes)?;
+
+    // Luminance-only data is read as a single channel.
+
+    let format = match &layout {
+        DdsPixelLayout::Direct(format) => *format,
+        DdsPixelLayout::Masked(1, _) => TextureContainerFormat::R8,
+        DdsPixelLayout::Masked(_, masks) => {
+            if masks[3] != 0 {
+                TextureContainerFormat::RGBA8
+            } else {
+                TextureContainerFormat::RGB8
+            }
+        }
+    };
+
+    let mut levels = Vec::with_capacity(level_count);
+
+    let mut offset = data_offset;
+
+    for level_index in 0..level_count {
+        let (level_width, level_height) = get_level_dimensions(width, height, level_index);
+
+        let pixel_count = level_width as usize * level_height as usize;
+
+        let source_bytes_per_pixel = match &layout {
+            DdsPixelLayout::Direct(format) => format.get_bytes_per_pixel(),
+            DdsPixelLayout::Masked(bytes_per_pixel, _) => *bytes_per_pixel,
+        };
+
+        let level_length = get_level_length(level_width, level_height, source_bytes_per_pixel)?;
+
+        let level = offset
+            .checked_add(level_length)
+            .and_then(|end| bytes.get(offset..end))
+            .ok_or_else(|| format!("DDS level {} lies outside of the file.", level_index))?;
+
+        offset += level_length;
+
+        match &layout {
+            DdsPixelLayout::Direct(_) => levels.push(level.to_vec()),
+            DdsPixelLayout::Masked(bytes_per_pixel, masks) => {
+                // Re-orders channels (e.g., BGRA) to match our formats.
+
+                let mut data = Vec::with_capacity(pixel_count * format.get_bytes_per_pixel());
+
+                for pixel_bytes in level.chunks_exact(*bytes_per_pixel) {
+                    let mut word = [0_u8; 4];
+
+                    word[..*bytes_per_pixel].copy_from_slice(pixel_bytes);
+
+                    let pixel = u32::from_le_bytes(word);
+
+                    let channels = masks.map(|mask| get_masked_channel(pixel, mask));
+
+                    data.extend_from_slice(&channels[..format.get_bytes_per_pixel()]);
+                }
+
+                levels.push(data);
+            }
+        }
+    }
+
+    let container = TextureContainer {
+        width,
+        height,
+        format,
+        levels,
+    };
+
+    container.validate()?;
+
+    Ok(container)
+}
@@ -0,0 +1,236 @@
+use super::{get_max_level_count, TextureContainer, TextureContainerFormat};
+
+// Reference:
+// https://registry.khronos.org/KTX/specs/2.0/ktxspec.v2.html
+
+pub static KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+// Identifier, header (9 x u32), and index (4 x u32, 2 x u64).
+static KTX2_LEVEL_INDEX_OFFSET: usize = 80;
+
+static KTX2_LEVEL_INDEX_ENTRY_SIZE: usize = 24;
+
+// Vulkan formats (`VkFormat`) that we can read.
+fn get_format(vk_format: u32) -> Result<TextureContainerFormat, String> {
+    match vk_format {
+        // VK_FORMAT_R8_UNORM, VK_FORMAT_R8_SRGB
+        9 | 15 => Ok(TextureContainerFormat::R8),
+        // VK_FORMAT_R8G8B8_UNORM, VK_FORMAT_R8G8B8_SRGB
+        23 | 29 => Ok(TextureContainerFormat::RGB8),
+        // VK_FORMAT_R8G8B8A8_UNORM, VK_FORMAT_R8G8B8A8_SRGB
+        37 | 43 => Ok(TextureContainerFormat::RGBA8),
+        // VK_FORMAT_R16G16B16_SFLOAT
+        90 => Ok(TextureContainerFormat::RGB16F),
+        // VK_FORMAT_R16G16B16A16_SFLOAT
+        97 => Ok(TextureContainerFormat::RGBA16F),
+        // VK_FORMAT_R32G32B32_SFLOAT
+        106 => Ok(TextureContainerFormat::RGB32F),
+        // VK_FORMAT_R32G32B32A32_SFLOAT
+        109 => Ok(TextureContainerFormat::RGBA32F),
+        // BC1-BC7, ETC2, EAC, and ASTC
+        131..=184 => Err(format!(
+            "Block-compressed KTX2 format (VkFormat {}) is not supported.",
+            vk_format
+        )),
+        _ => Err(format!("Unsupported KTX2 format (VkFormat {}).", vk_format)),
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| "Unexpected end of KTX2 data.".to_string())
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, String> {
+    Ok(read_u32(bytes, offset)? as u64 | ((read_u32(bytes, offset + 4)? as u64) << 32))
+}
+
+pub fn parse_ktx2(bytes: &[u8]) -> Result<TextureContainer, String> {
+    if !bytes.starts_with(&KTX2_IDENTIFIER) {
+        return Err("Missing KTX2 file identifier.".to_string());
+    }
+
+    let header = |index: usize| read_u32(bytes, KTX2_IDENTIFIER.len() + index * 4);
+
+    let vk_format = header(0)?;
+    let width = header(2)?;
+    let height = header(3)?.max(1);
+    let depth = header(4)?;
+    let layer_count = header(5)?;
+    let face_count = header(6)?;
+    let level_count = header(7)?.max(1);
+    let supercompression_scheme = header(8)?;
+
+    if supercompression_scheme != 0 {
+        return Err(format!(
+            "Supercompressed KTX2 data (scheme {}) is not supported.",
+            supercompression_scheme
+        ));
+    }
+
+    if depth > 1 || layer_count > 1 || face_count != 1 {
+        return Err("Only single 2D KTX2 images are supported.".to_string());
+    }
+
+    if width == 0 {
+        return Err("Invalid KTX2 width (0).".to_string());
+    }
+
+    let format = get_format(vk_format)?;
+
+    let level_count = (level_count as usize).min(get_max_level_count(width, height));
+
+    let mut levels = Vec::with_capacity(level_count);
+
+    for level_index in 0..level_count {
+        let entry_offset = KTX2_LEVEL_INDEX_OFFSET + level_index * KTX2_LEVEL_INDEX_ENTRY_SIZE;
+
+        let byte_offset = read_u64(bytes, entry_offset)?;
+        let byte_length = read_u64(bytes, entry_offset + 8)?;
+
+        let level = byte_offset
+            .checked_add(byte_length)
+            .and_then(|end| {
+                let (start, end) = (
+                    usize::try_from(byte_offset).ok()?,
+                    usize::try_from(end).ok()?,
+                );
+
+                bytes.get(start..end)
+            })
+            .ok_or_else(|| format!("KTX2 level {} lies outside of the file.", level_index))?;
+
+        levels.push(level.to_vec());
+    }
+
+    let container = TextureContainer {
+        width,
+        height,
+        format,
+        levels,
+    };
+
+    container.validate()?;
+
+    Ok(container)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        testing::TempPath,
+        texture::{container::get_level_dimensions, map::TextureMap},
+        vec::vec3::Vec3,
+    };
+
+    use super::{parse_ktx2, KTX2_IDENTIFIER, KTX2_LEVEL_INDEX_OFFSET};
+
+    // VK_FORMAT_R32G32B32_SFLOAT
+    static RGB32F: u32 = 106;
+
+    fn make_ktx2(width: u32, height: u32, levels: &[Vec<Vec3>]) -> Vec<u8> {
+        let mut bytes = KTX2_IDENTIFIER.to_vec();
+
+        let header = [RGB32F, 4, width, height, 0, 0, 1, levels.len() as u32, 0];
+
+        for value in header.iter().chain(&[0_u32; 4]) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        // Supercompression global data (none).
+
+        bytes.extend_from_slice(&[0; 16]);
+
+        let level_data: Vec<Vec<u8>> = levels
+            .iter()
+            .map(|level| {
+                level
+                    .iter()
+                    .flat_map(|v| [v.x, v.y, v.z])
+                    .flat_map(f32::to_le_bytes)
+                    .collect()
+            })
+            .collect();
+
+        let mut offset = bytes.len() + levels.len() * 24;
+
+        for data in &level_data {
+            for value in [offset, data.len(), data.len()] {
+                bytes.extend_from_slice(&(value as u64).to_le_bytes());
+            }
+
+            offset += data.len();
+        }
+
+        for data in level_data {
+            bytes.extend(data);
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn loads_both_levels_of_an_uncompressed_float_ktx2() {
+        let red = Vec3 {
+            x: 2.0,
+            y: 0.0,
+            z: 0.0,
+        };
+
+        let blue = Vec3 {
+            x: 0.0,
+            y: 0.0,
+            z: 4.0,
+        };
+
+        let bytes = make_ktx2(2, 2, &[vec![red; 4], vec![blue]]);
+
+        let filepath = TempPath::new("loads_both_levels.ktx2");
+
+        std::fs::write(&filepath, bytes).unwrap();
+
+        let mut map = TextureMap::<Vec3>::new(filepath.to_str().unwrap(), Default::default());
+
+        let result = map.load_float();
+
+        result.unwrap();
+
+        assert!(map.is_loaded && map.has_mipmaps_generated);
+        assert_eq!((map.width, map.height), (2, 2));
+
+        assert_eq!(map.levels.len(), 2);
+        assert_eq!(map.levels[0].0.data, vec![red; 4]);
+        assert_eq!(map.levels[1].0.data, vec![blue]);
+    }
+
+    #[test]
+    fn rejects_corrupt_headers() {
+        let bytes = make_ktx2(
+            2,
+            2,
+            &[vec![Default::default(); 4], vec![Default::default()]],
+        );
+
+        // A huge level count is clamped to the length of a full mip chain.
+
+        let mut corrupt = bytes.clone();
+
+        corrupt[KTX2_IDENTIFIER.len() + 7 * 4..][..4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        assert_eq!(parse_ktx2(&corrupt).unwrap().levels.len(), 2);
+
+        // A level whose offset and length overflow.
+
+        let mut corrupt = bytes.clone();
+
+        corrupt[KTX2_LEVEL_INDEX_OFFSET..][..8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        assert!(parse_ktx2(&corrupt).is_err());
+
+        assert_eq!(get_level_dimensions(1024, 512, 40), (1, 1));
+    }
+}
@@ -0,0 +1,250 @@
+use std::{fs, path::Path};
+
+use crate::{buffer::Buffer2D, vec::vec3::Vec3};
+
+use super::map::{TextureBuffer, TextureMapStorageFormat};
+
+pub mod dds;
+pub mod ktx2;
+
+// Uncompressed pixel formats that we can read from a texture container.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TextureContainerFormat {
+    R8,
+    RGB8,
+    RGBA8,
+    RGB16F,
+    RGBA16F,
+    RGB32F,
+    RGBA32F,
+}
+
+impl TextureContainerFormat {
+    pub fn get_bytes_per_pixel(&self) -> usize {
+        match self {
+            Self::R8 => 1,
+            Self::RGB8 => 3,
+            Self::RGBA8 => 4,
+            Self::RGB16F => 6,
+            Self::RGBA16F => 8,
+            Self::RGB32F => 12,
+            Self::RGBA32F => 16,
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        !matches!(self, Self::R8 | Self::RGB8 | Self::RGBA8)
+    }
+}
+
+// Pixel data read from a DDS or KTX2 file, including any mipmap levels stored
+// with it (largest first), so that the data needn't be re-decoded or
+// re-filtered at load time.
+#[derive(Debug, Clone)]
+pub struct TextureContainer {
+    pub width: u32,
+    pub height: u32,
+    pub format: TextureContainerFormat,
+    pub levels: Vec<Vec<u8>>,
+}
+
+pub fn is_texture_container_path(filepath: &str) -> bool {
+    let extension = Path::new(filepath)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase());
+
+    matches!(extension.as_deref(), Some("dds") | Some("ktx2"))
+}
+
+// Number of levels in a full mipmap chain, down to 1x1; containers may claim
+// more levels than this, but we never read past it.
+pub fn get_max_level_count(width: u32, height: u32) -> usize {
+    (u32::BITS - width.max(height).max(1).leading_zeros()) as usize
+}
+
+pub fn get_level_dimensions(width: u32, height: u32, level_index: usize) -> (u32, u32) {
+    let shift = |size: u32| {
+        u32::try_from(level_index)
+            .ok()
+            .and_then(|level_index| size.checked_shr(level_index))
+            .unwrap_or(0)
+            .max(1)
+    };
+
+    (shift(width), shift(height))
+}
+
+// Number of bytes in a level of the given dimensions, or an error if it can't
+// be represented.
+pub fn get_level_length(width: u32, height: u32, bytes_per_pixel: usize) -> Result<usize, String> {
+    (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixel_count| pixel_count.checked_mul(bytes_per_pixel))
+        .ok_or_else(|| format!("Texture level of {}x{} pixels is too large.", width, height))
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+
+    let mantissa = (bits & 0x3ff) as f32;
+
+    match exponent {
+        // Subnormal
+        0 => sign * mantissa * 2.0_f32.powi(-24),
+        31 => {
+            if mantissa == 0.0 {
+                sign * f32::INFINITY
+            } else {
+                f32::NAN
+            }
+        }
+        _ => sign * (1.0 + mantissa / 1024.0) * 2.0_f32.powi(exponent - 15),
+    }
+}
+
+impl TextureContainer {
+    pub fn load(filepath: &Path) -> Result<Self, String> {
+        let bytes = fs::read(filepath)
+            .map_err(|e| format!("Failed to read '{}': {}", filepath.display(), e))?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.starts_with(&ktx2::KTX2_IDENTIFIER) {
+            ktx2::parse_ktx2(bytes)
+        } else if bytes.starts_with(dds::DDS_MAGIC) {
+            dds::parse_dds(bytes)
+        } else {
+            Err("Unrecognized texture container (expected DDS or KTX2 data).".to_string())
+        }
+    }
+
+    // Checks that each level holds as many bytes as its dimensions require.
+    pub(in crate::texture) fn validate(&self) -> Result<(), String> {
+        if self.levels.is_empty() {
+            return Err("Texture container holds no levels.".to_string());
+        }
+
+        for (level_index, level) in self.levels.iter().enumerate() {
+            let (width, height) = get_level_dimensions(self.width, self.height, level_index);
+
+            let expected = get_level_length(width, height, self.format.get_bytes_per_pixel())?;
+
+            if level.len() < expected {
+                return Err(format!(
+                    "Level {} holds {} bytes; expected {} ({}x{} {:?}).",
+                    level_index,
+                    level.len(),
+                    expected,
+                    width,
+                    height,
+                    self.format
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn to_u8_levels(
+        &self,
+        storage_format: TextureMapStorageFormat,
+    ) -> Result<Vec<TextureBuffer>, String> {
+        if self.format.is_float() {
+            return Err(format!(
+                "Can't read {:?} (float) texture data into an 8-bit texture map.",
+                self.format
+            ));
+        }
+
+        let source_bytes_per_pixel = self.format.get_bytes_per_pixel();
+
+        let mut levels = Vec::with_capacity(self.levels.len());
+
+        for (level_index, level) in self.levels.iter().enumerate() {
+            let (width, height) = get_level_dimensions(self.width, self.height, level_index);
+
+            let pixel_count = width as usize * height as usize;
+
+            let mut data =
+                Vec::with_capacity(pixel_count * storage_format.get_buffer_samples_per_pixel());
+
+            for pixel in level.chunks_exact(source_bytes_per_pixel).take(pixel_count) {
+                let rgba = match self.format {
+                    TextureContainerFormat::R8 => [pixel[0], pixel[0], pixel[0], 255],
+                    TextureContainerFormat::RGB8 => [pixel[0], pixel[1], pixel[2], 255],
+                    _ => [pixel[0], pixel[1], pixel[2], pixel[3]],
+                };
+
+                match storage_format {
+                    TextureMapStorageFormat::RGBA32 => data.extend_from_slice(&rgba),
+                    TextureMapStorageFormat::RGB24 => data.extend_from_slice(&rgba[..3]),
+                    TextureMapStorageFormat::Index8(target_channel) => {
+                        // Single-channel data is copied as-is.
+
+                        data.push(if source_bytes_per_pixel == 1 {
+                            pixel[0]
+                        } else {
+                            rgba[target_channel.min(3)]
+                        })
+                    }
+                }
+            }
+
+            levels.push(TextureBuffer(Buffer2D::from_data(width, height, data)));
+        }
+
+        Ok(levels)
+    }
+
+    pub fn to_vec3_levels(&self) -> Result<Vec<TextureBuffer<Vec3>>, String> {
+        let (channel_count, channel_size) = match self.format {
+            TextureContainerFormat::RGB16F => (3, 2),
+            TextureContainerFormat::RGBA16F => (4, 2),
+            TextureContainerFormat::RGB32F => (3, 4),
+            TextureContainerFormat::RGBA32F => (4, 4),
+            _ => {
+                return Err(format!(
+                    "Can't read {:?} (8-bit) texture data into a float texture map.",
+                    self.format
+                ))
+            }
+        };
+
+        let read_channel = |bytes: &[u8]| -> f32 {
+            if channel_size == 2 {
+                f16_to_f32(u16::from_le_bytes([bytes[0], bytes[1]]))
+            } else {
+                f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+            }
+        };
+
+        let mut levels = Vec::with_capacity(self.levels.len());
+
+        for (level_index, level) in self.levels.iter().enumerate() {
+            let (width, height) = get_level_dimensions(self.width, self.height, level_index);
+
+            let pixel_count = width as usize * height as usize;
+
+            // Any alpha channel is dropped.
+
+            let data: Vec<Vec3> = level
+                .chunks_exact(channel_count * channel_size)
+                .take(pixel_count)
+                .map(|pixel| Vec3 {
+                    x: read_channel(&pixel[0..]),
+                    y: read_channel(&pixel[channel_size..]),
+                    z: read_channel(&pixel[channel_size * 2..]),
+                })
+                .collect();
+
+            levels.push(TextureBuffer(Buffer2D::from_data(width, height, data)));
+        }
+
+        Ok(levels)
+    }
+}
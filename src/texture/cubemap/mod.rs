@@ -8,6 +8,7 @@ use std::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    animation::lerp,
     app::context::ApplicationRenderingContext,
     buffer::{framebuffer::Framebuffer, Buffer2D},
     color::{self, Color},
@@ -25,7 +26,9 @@ use crate::{
 
 use super::{
     map::{TextureMap, TextureMapStorageFormat},
-    sample::{sample_bilinear_u8, sample_nearest_f32, sample_nearest_vec3, sample_trilinear_vec3},
+    sample::{
+        sample_from_texel_u8, sample_from_texel_vec3, sample_nearest_f32, sample_nearest_vec3,
+    },
 };
 
 static SIDES: usize = 6;
@@ -298,6 +301,94 @@ impl<
 
         (side, uv)
     }
+
+    // Inverse of `get_uv_for_direction()`; UVs beyond [0, 1] give directions
+    // that point into the neighboring sides.
+    pub fn get_direction_for_uv(side: Side, uv: Vec2) -> Vec3 {
+        let s = uv.x * 2.0 - 1.0;
+        let t = uv.y * 2.0 - 1.0;
+
+        let (x, y, z) = match side {
+            Side::Forward => (s, t, 1.0),
+            Side::Backward => (-s, t, -1.0),
+            Side::Up => (s, 1.0, -t),
+            Side::Down => (s, -1.0, t),
+            Side::Left => (-1.0, t, s),
+            Side::Right => (1.0, t, -s),
+        };
+
+        Vec3 { x, y, z }.as_normal()
+    }
+
+    // Returns the 4 texels (and their weights) to blend for a bilinear sample
+    // in `direction`; taps that fall off of the edge of the direction's side
+    // are fetched from the adjacent side (with that side's orientation), so
+    // that filtering is seamless across edges.
+    fn get_seamless_bilinear_taps(
+        &self,
+        direction: &Vec4,
+        level_index: Option<usize>,
+    ) -> [(Side, (f32, f32), f32); 4] {
+        let (side, uv) = self.get_uv_for_direction(direction);
+
+        let size = {
+            let map = &self.sides[side as usize];
+
+            match level_index {
+                Some(index) => map.levels[index].0.width,
+                None => map.width,
+            }
+        };
+
+        let size_f32 = size as f32;
+
+        // Texel centers lie at (i + 0.5) / size.
+
+        let x = uv.x * size_f32 - 0.5;
+        let y = (1.0 - uv.y) * size_f32 - 0.5;
+
+        let (x0, y0) = (x.floor(), y.floor());
+
+        let (alpha_x, alpha_y) = (x - x0, y - y0);
+
+        let get_tap = |texel_x: f32, texel_y: f32| -> (Side, (f32, f32)) {
+            if (0.0..size_f32).contains(&texel_x) && (0.0..size_f32).contains(&texel_y) {
+                return (side, (texel_x, texel_y));
+            }
+
+            let tap_uv = Vec2 {
+                x: (texel_x + 0.5) / size_f32,
+                y: 1.0 - (texel_y + 0.5) / size_f32,
+                z: 0.0,
+            };
+
+            let tap_direction = Self::get_direction_for_uv(side, tap_uv);
+
+            let (adjacent_side, adjacent_uv) =
+                self.get_uv_for_direction(&Vec4::new(tap_direction, 0.0));
+
+            let max_texel = (size - 1) as f32;
+
+            (
+                adjacent_side,
+                (
+                    (adjacent_uv.x * size_f32).floor().clamp(0.0, max_texel),
+                    ((1.0 - adjacent_uv.y) * size_f32)
+                        .floor()
+                        .clamp(0.0, max_texel),
+                ),
+            )
+        };
+
+        let with_weight = |(side, texel): (Side, (f32, f32)), weight: f32| (side, texel, weight);
+
+        [
+            with_weight(get_tap(x0, y0), (1.0 - alpha_x) * (1.0 - alpha_y)),
+            with_weight(get_tap(x0 + 1.0, y0), alpha_x * (1.0 - alpha_y)),
+            with_weight(get_tap(x0, y0 + 1.0), (1.0 - alpha_x) * alpha_y),
+            with_weight(get_tap(x0 + 1.0, y0 + 1.0), alpha_x * alpha_y),
+        ]
+    }
 }
 
 impl CubeMap<f32> {
@@ -327,6 +418,28 @@ impl CubeMap<Vec3> {
         sample_nearest_vec3(uv, map, level_index)
     }
 
+    pub fn sample_bilinear(&self, direction: &Vec4, level_index: Option<usize>) -> Vec3 {
+        let (side, _uv) = self.get_uv_for_direction(direction);
+
+        if !self.sides[side as usize].is_loaded {
+            return CUBEMAP_SIDE_COLORS[side as usize].to_vec3();
+        }
+
+        self.get_seamless_bilinear_taps(direction, level_index)
+            .into_iter()
+            .fold(Vec3::default(), |sum, (side, texel, weight)| {
+                let map = &self.sides[side as usize];
+
+                let color = if map.is_loaded {
+                    sample_from_texel_vec3(texel, map, level_index)
+                } else {
+                    CUBEMAP_SIDE_COLORS[side as usize].to_vec3()
+                };
+
+                sum + color * weight
+            })
+    }
+
     pub fn sample_trilinear(
         &self,
         direction: &Vec4,
@@ -334,15 +447,18 @@ impl CubeMap<Vec3> {
         far_level_index: usize,
         alpha: f32,
     ) -> Vec3 {
-        let (side, uv) = self.get_uv_for_direction(direction);
+        // Blends (seamless) bilinear samples from both mipmaps.
 
-        let map = &self.sides[side as usize];
+        if (near_level_index == far_level_index) || alpha == 0.0 {
+            self.sample_bilinear(direction, Some(near_level_index))
+        } else if alpha >= 1.0 {
+            self.sample_bilinear(direction, Some(far_level_index))
+        } else {
+            let near = self.sample_bilinear(direction, Some(near_level_index));
+            let far = self.sample_bilinear(direction, Some(far_level_index));
 
-        if !map.is_loaded {
-            return CUBEMAP_SIDE_COLORS[side as usize].to_vec3();
+            lerp(near, far, alpha)
         }
-
-        sample_trilinear_vec3(uv, map, near_level_index, far_level_index, alpha)
     }
 
     pub fn render_scene(
@@ -514,16 +630,77 @@ impl CubeMap {
     }
 
     pub fn sample_bilinear(&self, direction: &Vec4, level_index: Option<usize>) -> Color {
-        let (side, uv) = self.get_uv_for_direction(direction);
-
-        let map = &self.sides[side as usize];
+        let (side, _uv) = self.get_uv_for_direction(direction);
 
-        if !map.is_loaded {
+        if !self.sides[side as usize].is_loaded {
             return CUBEMAP_SIDE_COLORS[side as usize];
         }
 
-        let (r, g, b) = sample_bilinear_u8(uv, map, level_index);
+        let color = self
+            .get_seamless_bilinear_taps(direction, level_index)
+            .into_iter()
+            .fold(Vec3::default(), |sum, (side, texel, weight)| {
+                let map = &self.sides[side as usize];
 
-        Color::rgb(r, g, b)
+                let color = if map.is_loaded {
+                    let (r, g, b) = sample_from_texel_u8(texel, map, level_index);
+
+                    Color::rgb(r, g, b).to_vec3()
+                } else {
+                    CUBEMAP_SIDE_COLORS[side as usize].to_vec3()
+                };
+
+                sum + color * weight
+            });
+
+        Color::from_vec3(color)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::Buffer2D,
+        texture::map::TextureMap,
+        vec::{vec3::Vec3, vec4::Vec4},
+    };
+
+    use super::{CubeMap, Side, CUBE_MAP_SIDES};
+
+    #[test]
+    fn bilinear_samples_near_an_edge_blend_the_adjacent_sides() {
+        let get_side_color = |side: Side| {
+            let mut color = Vec3::default();
+
+            match side.get_index() {
+                0 => color.x = 1.0,
+                5 => color.y = 1.0,
+                _ => color.z = 1.0,
+            }
+
+            color
+        };
+
+        let cubemap = CubeMap::from_textures(CUBE_MAP_SIDES.map(|side| {
+            TextureMap::from_buffer(4, 4, Buffer2D::new(4, 4, Some(get_side_color(side))))
+        }));
+
+        // Forward is red, and Right is green.
+
+        let center = cubemap.sample_bilinear(&Vec4::new(Side::Forward.get_direction(), 0.0), None);
+
+        assert_eq!(center, get_side_color(Side::Forward));
+
+        let near_edge = Vec3 {
+            x: 0.97,
+            y: 0.0,
+            z: 1.0,
+        }
+        .as_normal();
+
+        let blended = cubemap.sample_bilinear(&Vec4::new(near_edge, 0.0), None);
+
+        assert!(blended.x > 0.25 && blended.y > 0.25, "{}", blended);
+        assert!(blended.z == 0.0 && (blended.x + blended.y - 1.0).abs() < 0.0001);
     }
 }
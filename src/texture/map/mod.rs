@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, path::Path};
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -8,11 +8,12 @@ use sdl2::pixels::PixelFormatEnum;
 use sdl2::render::TextureAccess;
 
 use crate::{
-    app::context::ApplicationRenderingContext, buffer::Buffer2D, color::Color,
+    app::context::ApplicationRenderingContext, buffer::Buffer2D, color::Color, hdr::load::load_hdr,
     serde::PostDeserialize, vec::vec2::Vec2, vec::vec3::Vec3,
 };
 
 use super::{
+    container::{is_texture_container_path, TextureContainer},
     get_half_scaled_u8, get_half_scaled_vec3,
     sample::{sample_bilinear_u8, sample_nearest_u8, sample_trilinear_u8, TextureSamplingMethod},
 };
@@ -70,6 +71,10 @@ pub struct TextureMap<T: Default + Debug + Copy + PartialEq = u8> {
     pub height: u32,
     #[serde(skip, default)]
     pub levels: Vec<TextureBuffer<T>>,
+    // Encoded (e.g., PNG) image data to decode on load, in place of reading
+    // `info.filepath`; for images embedded in other files.
+    #[serde(skip, default)]
+    pub encoded_bytes: Option<Vec<u8>>,
     pub sampling_options: TextureMapSamplingOptions,
 }
 
@@ -91,6 +96,7 @@ impl<T: Default + Debug + Copy + PartialEq> TextureMap<T> {
             width: 0,
             height: 0,
             levels: vec![],
+            encoded_bytes: None,
             sampling_options: Default::default(),
         }
     }
@@ -129,6 +135,7 @@ impl<T: Default + Debug + Copy + PartialEq> TextureMap<T> {
             width,
             height,
             levels: vec![TextureBuffer(buffer)],
+            encoded_bytes: None,
             sampling_options: Default::default(),
         }
     }
@@ -137,6 +144,19 @@ impl<T: Default + Debug + Copy + PartialEq> TextureMap<T> {
         self.info.storage_format.get_buffer_samples_per_pixel()
     }
 
+    // Takes `levels` (largest first) as this map's pixel data; any levels past
+    // the first are used as its mipmaps.
+    fn set_loaded_levels(&mut self, levels: Vec<TextureBuffer<T>>) {
+        self.width = levels[0].0.width;
+        self.height = levels[0].0.height;
+
+        self.has_mipmaps_generated = levels.len() > 1;
+
+        self.levels = levels;
+
+        self.is_loaded = true;
+    }
+
     pub fn validate_for_mipmapping(&mut self) -> Result<(), String> {
         if !self.is_loaded {
             return Err(String::from(
@@ -231,20 +251,47 @@ impl TextureMap {
         Self::new(filepath, TextureMapStorageFormat::Index8(target_channel))
     }
 
+    // `name` identifies the image (e.g., in place of a filepath).
+    pub fn from_encoded_bytes(
+        name: &str,
+        bytes: Vec<u8>,
+        storage_format: TextureMapStorageFormat,
+    ) -> Self {
+        Self {
+            encoded_bytes: Some(bytes),
+            ..Self::new(name, storage_format)
+        }
+    }
+
     pub fn load(&mut self, rendering_context: &ApplicationRenderingContext) -> Result<(), String> {
         if self.is_loaded {
             return Ok(());
         }
 
+        // DDS and KTX2 files store their (uncompressed) levels directly.
+
+        if self.encoded_bytes.is_none() && is_texture_container_path(&self.info.filepath) {
+            let container = TextureContainer::load(Path::new(&self.info.filepath))?;
+
+            let levels = container.to_u8_levels(self.info.storage_format)?;
+
+            self.set_loaded_levels(levels);
+
+            return Ok(());
+        }
+
         // Load the map's native-sized pixel data on-demand.
 
         let mut canvas = rendering_context.canvas.borrow_mut();
 
         let texture_creator = canvas.texture_creator();
 
-        let static_texture = texture_creator
-            .load_texture(self.info.filepath.clone())
-            .unwrap();
+        let static_texture = match self.encoded_bytes.take() {
+            Some(bytes) => texture_creator.load_texture_bytes(&bytes).unwrap(),
+            None => texture_creator
+                .load_texture(self.info.filepath.clone())
+                .unwrap(),
+        };
 
         let texture_attrs = static_texture.query();
 
@@ -538,6 +585,32 @@ impl TextureMap {
 }
 
 impl TextureMap<Vec3> {
+    // Loads float (e.g., HDR or IBL) pixel data from a Radiance (.hdr), DDS, or
+    // KTX2 file; DDS and KTX2 files may include their own mipmaps.
+    pub fn load_float(&mut self) -> Result<(), String> {
+        if self.is_loaded {
+            return Ok(());
+        }
+
+        let filepath = Path::new(&self.info.filepath);
+
+        let levels = if is_texture_container_path(&self.info.filepath) {
+            TextureContainer::load(filepath)?.to_vec3_levels()?
+        } else {
+            let hdr = load_hdr(filepath).map_err(|e| e.to_string())?;
+
+            vec![TextureBuffer(hdr.to_buffer())]
+        };
+
+        // One (Vec3) sample per pixel.
+
+        self.info.storage_format = TextureMapStorageFormat::Index8(0);
+
+        self.set_loaded_levels(levels);
+
+        Ok(())
+    }
+
     pub fn generate_mipmaps(&mut self) -> Result<(), String> {
         self.validate_for_mipmapping()?;
 
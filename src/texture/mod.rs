@@ -1,5 +1,6 @@
 use crate::{buffer::Buffer2D, vec::vec3::Vec3};
 
+pub mod container;
 pub mod cubemap;
 pub mod map;
 pub mod sample;
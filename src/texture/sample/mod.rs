@@ -491,6 +491,41 @@ pub fn sample_trilinear_u8(
     (color.x as u8, color.y as u8, color.z as u8)
 }
 
+// Continuous level of detail for a sample whose pixel covers `uv_footprint` (an
+// area, in UV space) of the map; level 0 is the full-size map.
+pub fn get_mipmap_lod<T: Default + Debug + Copy + PartialEq>(
+    uv_footprint: f32,
+    map: &TextureMap<T>,
+) -> f32 {
+    let texel_footprint = uv_footprint * (map.width * map.height) as f32;
+
+    if texel_footprint <= 1.0 {
+        0.0
+    } else {
+        0.5 * texel_footprint.log2()
+    }
+}
+
+// Blends bilinear samples from the 2 mipmap levels nearest to `lod`.
+pub fn sample_trilinear_lod_u8(uv: Vec2, map: &TextureMap, lod: f32) -> (u8, u8, u8) {
+    if !map.has_mipmaps_generated || map.levels.len() < 2 {
+        return sample_bilinear_u8(uv, map, None);
+    }
+
+    let lod = lod.clamp(0.0, (map.levels.len() - 1) as f32);
+
+    let near_level_index = lod.floor() as usize;
+    let far_level_index = (near_level_index + 1).min(map.levels.len() - 1);
+
+    sample_trilinear_u8(
+        uv,
+        map,
+        near_level_index,
+        far_level_index,
+        lod - near_level_index as f32,
+    )
+}
+
 pub fn sample_trilinear_vec3(
     uv: Vec2,
     map: &TextureMap<Vec3>,
@@ -525,7 +560,7 @@ fn get_texel_color_index<T: Default + Debug + Copy + PartialEq>(
     map.get_buffer_samples_per_pixel() * (texel.1 as u32 * level_width + texel.0 as u32) as usize
 }
 
-fn sample_from_texel_u8(
+pub fn sample_from_texel_u8(
     texel: (f32, f32),
     map: &TextureMap,
     level_index: Option<usize>,
@@ -596,7 +631,7 @@ fn sample_from_texel_vec2(
     buffer.0.data[texel_color_index]
 }
 
-fn sample_from_texel_vec3(
+pub fn sample_from_texel_vec3(
     texel: (f32, f32),
     map: &TextureMap<Vec3>,
     level_index: Option<usize>,
@@ -733,3 +768,38 @@ pub fn get_neighbors(
         ),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::Buffer2D,
+        texture::map::{TextureBuffer, TextureMap},
+        vec::vec2::Vec2,
+    };
+
+    use super::sample_trilinear_lod_u8;
+
+    #[test]
+    fn trilinear_sampling_blends_adjacent_mipmap_levels() {
+        let mut map = TextureMap::from_buffer(4, 4, Buffer2D::new(4, 4, Some(0_u8)));
+
+        map.levels
+            .push(TextureBuffer(Buffer2D::new(2, 2, Some(200_u8))));
+
+        map.has_mipmaps_generated = true;
+
+        let uv = Vec2 {
+            x: 0.5,
+            y: 0.5,
+            z: 0.0,
+        };
+
+        assert_eq!(sample_trilinear_lod_u8(uv, &map, 0.0).0, 0);
+        assert_eq!(sample_trilinear_lod_u8(uv, &map, 0.5).0, 100);
+        assert_eq!(sample_trilinear_lod_u8(uv, &map, 1.0).0, 200);
+
+        // Levels beyond the last are clamped.
+
+        assert_eq!(sample_trilinear_lod_u8(uv, &map, 3.0).0, 200);
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    fmt,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        mpsc::{channel, Receiver, Sender, TryRecvError},
+        Arc, Mutex,
+    },
+    thread::{self, available_parallelism, JoinHandle},
+};
+
+type ThreadPoolJob = Box<dyn FnOnce() + Send + 'static>;
+
+// A fixed set of worker threads, shared by subsystems (e.g., the resource
+// loader; see `ResourceLoaderQueue`) so that they don't oversubscribe the CPU.
+pub struct ThreadPool {
+    sender: Option<Sender<ThreadPoolJob>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl fmt::Debug for ThreadPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadPool")
+            .field("thread_count", &self.workers.len())
+            .finish()
+    }
+}
+
+impl Default for ThreadPool {
+    fn default() -> Self {
+        Self::new(get_default_thread_count())
+    }
+}
+
+pub fn get_default_thread_count() -> usize {
+    available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+}
+
+impl ThreadPool {
+    pub fn new(thread_count: usize) -> Self {
+        let (sender, receiver) = channel::<ThreadPoolJob>();
+
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..thread_count.max(1))
+            .map(|index| {
+                let receiver = receiver.clone();
+
+                thread::Builder::new()
+                    .name(format!("thread-pool-{}", index))
+                    .spawn(move || loop {
+                        let job = receiver.lock().unwrap().recv();
+
+                        match job {
+                            Ok(job) => {
+                                // Keeps this worker alive if a job panics; the
+                                // job's task handle reports the failure.
+
+                                catch_unwind(AssertUnwindSafe(job)).ok();
+                            }
+                            Err(_) => {
+                                // The pool was dropped.
+                                return;
+                            }
+                        }
+                    })
+                    .unwrap()
+            })
+            .collect();
+
+        Self {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    pub fn get_thread_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    pub fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if let Some(sender) = &self.sender {
+            sender.send(Box::new(job)).unwrap();
+        }
+    }
+
+    pub fn submit<T, F>(&self, job: F) -> ThreadPoolTask<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (sender, receiver) = channel();
+
+        self.execute(move || {
+            sender.send(job()).ok();
+        });
+
+        ThreadPoolTask { receiver }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // Closing the channel lets each worker finish its queued jobs and exit.
+
+        self.sender.take();
+
+        for worker in self.workers.drain(..) {
+            worker.join().ok();
+        }
+    }
+}
+
+// A handle to the result of a job submitted to a `ThreadPool`.
+#[derive(Debug)]
+pub struct ThreadPoolTask<T> {
+    receiver: Receiver<T>,
+}
+
+impl<T> ThreadPoolTask<T> {
+    pub fn wait(self) -> Result<T, String> {
+        self.receiver
+            .recv()
+            .map_err(|_| "Thread pool task panicked before returning a result.".to_string())
+    }
+
+    pub fn try_get(&self) -> Result<Option<T>, String> {
+        match self.receiver.try_recv() {
+            Ok(result) => Ok(Some(result)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => {
+                Err("Thread pool task panicked before returning a result.".to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ThreadPool;
+
+    #[test]
+    fn two_thread_pool_runs_every_submitted_task() {
+        let pool = ThreadPool::new(2);
+
+        assert_eq!(pool.get_thread_count(), 2);
+
+        let tasks: Vec<_> = (0..64_u64).map(|n| pool.submit(move || n * n)).collect();
+
+        let results: Vec<u64> = tasks.into_iter().map(|task| task.wait().unwrap()).collect();
+
+        assert_eq!(results, (0..64_u64).map(|n| n * n).collect::<Vec<u64>>());
+
+        // A panicking task reports an error without taking down its worker.
+
+        let failed = pool.submit(|| -> u64 { panic!("Task failed!") });
+
+        assert!(failed.wait().is_err());
+
+        assert_eq!(pool.submit(|| 7).wait(), Ok(7));
+    }
+}
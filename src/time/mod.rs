@@ -1,6 +1,8 @@
 use std::fmt;
 
-#[derive(Debug, Copy, Clone, Default)]
+pub static TIME_SCALES: [f32; 4] = [1.0, 0.5, 0.25, 0.1];
+
+#[derive(Debug, Copy, Clone)]
 pub struct TimingInfo {
     pub uptime_seconds: f32,
     pub current_frame_index: u32,
@@ -8,7 +10,29 @@ pub struct TimingInfo {
     pub unused_seconds: f32,
     pub unused_milliseconds: f32,
     pub milliseconds_slept: f32,
+    // Scaled (simulation) time since the previous update.
     pub seconds_since_last_update: f32,
+    pub time_scale: f32,
+    pub is_paused: bool,
+    // Lets the next update advance while paused.
+    pub should_step: bool,
+}
+
+impl Default for TimingInfo {
+    fn default() -> Self {
+        Self {
+            uptime_seconds: 0.0,
+            current_frame_index: 0,
+            frames_per_second: 0.0,
+            unused_seconds: 0.0,
+            unused_milliseconds: 0.0,
+            milliseconds_slept: 0.0,
+            seconds_since_last_update: 0.0,
+            time_scale: 1.0,
+            is_paused: false,
+            should_step: false,
+        }
+    }
 }
 
 impl fmt::Display for TimingInfo {
@@ -20,3 +44,91 @@ impl fmt::Display for TimingInfo {
         )
     }
 }
+
+impl TimingInfo {
+    pub fn cycle_time_scale(&mut self) {
+        let current_index = TIME_SCALES
+            .iter()
+            .position(|scale| *scale == self.time_scale)
+            .unwrap_or(0);
+
+        self.time_scale = TIME_SCALES[(current_index + 1) % TIME_SCALES.len()];
+    }
+
+    pub fn pause(&mut self) {
+        self.is_paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.is_paused = false;
+    }
+
+    pub fn toggle_paused(&mut self) {
+        self.is_paused = !self.is_paused;
+    }
+
+    // While paused, lets the next update advance by a single (scaled) frame.
+    pub fn step(&mut self) {
+        self.should_step = true;
+    }
+
+    // Advances simulation time by the (real) time elapsed since the previous
+    // update, scaled by our time scale; nothing elapses while paused.
+    pub fn advance(&mut self, elapsed_seconds: f32) {
+        self.seconds_since_last_update = if !self.is_paused || self.should_step {
+            elapsed_seconds * self.time_scale.max(0.0)
+        } else {
+            0.0
+        };
+
+        self.should_step = false;
+
+        self.uptime_seconds += self.seconds_since_last_update;
+    }
+
+    // Advances simulation time by an exact delta (e.g., one replayed from an
+    // input recording), ignoring our time scale and pause state.
+    pub fn advance_exactly(&mut self, seconds: f32) {
+        self.seconds_since_last_update = seconds;
+
+        self.should_step = false;
+
+        self.uptime_seconds += seconds;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimingInfo;
+
+    #[test]
+    fn time_scale_and_pausing_affect_the_update_delta() {
+        let mut timing_info = TimingInfo::default();
+
+        timing_info.time_scale = 0.5;
+
+        timing_info.advance(0.02);
+
+        assert_eq!(timing_info.seconds_since_last_update, 0.01);
+
+        // Paused updates report no elapsed time, except for a single step.
+
+        timing_info.pause();
+
+        timing_info.advance(0.02);
+
+        assert_eq!(timing_info.seconds_since_last_update, 0.0);
+
+        timing_info.step();
+
+        timing_info.advance(0.02);
+
+        assert_eq!(timing_info.seconds_since_last_update, 0.01);
+
+        timing_info.advance(0.02);
+
+        assert_eq!(timing_info.seconds_since_last_update, 0.0);
+
+        assert_eq!(timing_info.uptime_seconds, 0.02);
+    }
+}
@@ -52,6 +52,14 @@ impl Transform3D {
         self.recompute_transform();
     }
 
+    pub fn euler_rotation(&self) -> Vec3 {
+        self.rotation.to_euler()
+    }
+
+    pub fn set_euler_rotation(&mut self, euler: Vec3) {
+        self.set_rotation(Quaternion::from_euler(euler));
+    }
+
     pub fn scale(&self) -> &Vec3 {
         &self.scale
     }
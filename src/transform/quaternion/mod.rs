@@ -130,6 +130,38 @@ impl Quaternion {
         Self::new(-vec3::FORWARD, theta)
     }
 
+    // Builds a rotation from Euler angles (radians) about the X, Y, and Z
+    // axes, applied in Z-Y-X order.
+    pub fn from_euler(euler: Vec3) -> Self {
+        let (sin_x, cos_x) = (euler.x / 2.0).sin_cos();
+        let (sin_y, cos_y) = (euler.y / 2.0).sin_cos();
+        let (sin_z, cos_z) = (euler.z / 2.0).sin_cos();
+
+        let s = cos_x * cos_y * cos_z + sin_x * sin_y * sin_z;
+
+        let u = Vec3 {
+            x: sin_x * cos_y * cos_z - cos_x * sin_y * sin_z,
+            y: cos_x * sin_y * cos_z + sin_x * cos_y * sin_z,
+            z: cos_x * cos_y * sin_z - sin_x * sin_y * cos_z,
+        };
+
+        Self::from_raw(s, u)
+    }
+
+    // Recovers Euler angles (radians) for this rotation; inverse of
+    // `from_euler()`.
+    pub fn to_euler(&self) -> Vec3 {
+        let (s, u) = (self.s, self.u);
+
+        let sin_y = (2.0 * (s * u.y - u.z * u.x)).clamp(-1.0, 1.0);
+
+        Vec3 {
+            x: (2.0 * (s * u.x + u.y * u.z)).atan2(1.0 - 2.0 * (u.x * u.x + u.y * u.y)),
+            y: sin_y.asin(),
+            z: (2.0 * (s * u.z + u.x * u.y)).atan2(1.0 - 2.0 * (u.y * u.y + u.z * u.z)),
+        }
+    }
+
     #[allow(unused)]
     pub fn conjugate(&self) -> Self {
         let s = self.s;
@@ -215,3 +247,44 @@ fn quaternion_to_mat4(s: f32, x: f32, y: f32, z: f32) -> Mat4 {
         [0.0, 0.0, 0.0, 1.0],
     ])
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        matrix::Mat4,
+        vec::vec3::{self, Vec3},
+    };
+
+    use super::Quaternion;
+
+    static EULER: Vec3 = Vec3 {
+        x: 0.3,
+        y: -0.5,
+        z: 0.9,
+    };
+
+    #[test]
+    fn euler_angles_round_trip() {
+        let euler = Quaternion::from_euler(EULER).to_euler();
+
+        assert!((euler - EULER).mag() < 1e-5);
+    }
+
+    #[test]
+    fn euler_angles_match_axis_rotations() {
+        let quaternion = Quaternion::from_euler(EULER);
+
+        // Pins the axis order: `mat()` is the transpose of the (row-vector)
+        // product of `Mat4` rotations about X, then Y, then Z.
+
+        let rotation =
+            (Mat4::rotation_x(EULER.x) * Mat4::rotation_y(EULER.y) * Mat4::rotation_z(EULER.z))
+                .transposed();
+
+        for axis in [vec3::RIGHT, vec3::UP, vec3::FORWARD] {
+            let expected = axis * rotation;
+
+            assert!((axis * *quaternion.mat() - expected).mag() < 1e-5);
+        }
+    }
+}
@@ -15,7 +15,12 @@ use crate::{
     time::TimingInfo,
 };
 
-use super::ui_box::{key::UIKey, styles::UIBoxStylesMap, UIBox};
+use super::{
+    context_menu::OpenContextMenu,
+    drag_drop::DragDropState,
+    tooltip::TooltipState,
+    ui_box::{key::UIKey, styles::UIBoxStylesMap, UIBox},
+};
 
 use theme::UITheme;
 
@@ -63,12 +68,18 @@ pub struct UIContext<'a> {
     pub text_cache: RefCell<TextCache>,
     pub image_arena: RefCell<Option<Rc<RefCell<Arena<TextureMap>>>>>,
     pub theme: RefCell<UITheme>,
+    pub content_scale: RefCell<f32>,
     pub styles: RefCell<UIBoxStylesContext>,
     pub global_offset: RefCell<(u32, u32)>,
     pub cache: RefCell<HashMap<UIKey, UIBox>>,
     pub input_events: RefCell<UIInputEvents>,
     pub timing_info: RefCell<TimingInfo>,
     pub cursor_kind: RefCell<MouseCursorKind>,
+    pub drag_drop: RefCell<DragDropState>,
+    // Unsnapped value of the slider being dragged, if any.
+    pub slider_drag: RefCell<Option<(UIKey, f32)>>,
+    pub context_menu: RefCell<Option<OpenContextMenu>>,
+    pub tooltip: RefCell<TooltipState<UIKey>>,
     #[cfg(debug_assertions)]
     pub debug: RefCell<UIContextDebugOptions>,
 }
@@ -91,12 +102,17 @@ impl<'a> Default for UIContext<'a> {
             text_cache: Default::default(),
             image_arena: Default::default(),
             theme: RefCell::new(default_theme),
+            content_scale: RefCell::new(1.0),
             styles: RefCell::new(styles),
             global_offset: Default::default(),
             cache: Default::default(),
             input_events: Default::default(),
             timing_info: Default::default(),
             cursor_kind: Default::default(),
+            drag_drop: Default::default(),
+            slider_drag: Default::default(),
+            context_menu: Default::default(),
+            tooltip: Default::default(),
             #[cfg(debug_assertions)]
             debug: Default::default(),
         }
@@ -138,11 +154,44 @@ impl<'a> UIContext<'a> {
             .borrow_mut()
             .replace(FontCache::new(app.context.ttf_context));
 
-        {
-            let mut font_info = self.font_info.borrow_mut();
+        self.font_info.borrow_mut().filepath = font_path;
+
+        self.theme.borrow_mut().font_point_size = point_size;
+
+        // Defaults to the content scale of the primary display.
+
+        self.set_content_scale(get_display_content_scale(app));
+    }
+
+    pub fn set_content_scale(&self, scale: f32) {
+        *self.content_scale.borrow_mut() = scale.max(0.25);
+
+        self.update_font_point_size();
+    }
+
+    pub fn get_scaled_pixels(&self, pixels: u32) -> u32 {
+        (pixels as f32 * *self.content_scale.borrow()).round() as u32
+    }
+
+    fn update_font_point_size(&self) {
+        let point_size = self.theme.borrow().font_point_size as u32;
 
-            font_info.filepath = font_path;
-            font_info.point_size = point_size;
+        self.font_info.borrow_mut().point_size = self.get_scaled_pixels(point_size) as u16;
+    }
+
+    pub fn set_theme(&self, theme: UITheme) {
+        // The bottom of the text color stack is the theme's default text color.
+
+        if let Some(color) = self.styles.borrow_mut().text_color.stack.first_mut() {
+            *color = theme.text;
+        }
+
+        *self.theme.borrow_mut() = theme;
+
+        // Text is re-rendered at the theme's point size, once a font is loaded.
+
+        if self.font_cache.borrow().is_some() {
+            self.update_font_point_size();
         }
     }
 
@@ -161,6 +210,8 @@ impl<'a> UIContext<'a> {
         input_events.keyboard = keyboard_state.clone();
         input_events.mouse = mouse_state.clone();
         input_events.game_controller = *game_controller_state;
+
+        self.drag_drop.borrow_mut().update(mouse_state);
     }
 
     pub fn set_timing_info(&self, timing_info: &TimingInfo) {
@@ -174,6 +225,81 @@ impl<'a> UIContext<'a> {
     }
 }
 
+// SDL reports 96 DPI for a display with no scaling applied.
+static REFERENCE_DISPLAY_DPI: f32 = 96.0;
+
+pub fn get_display_content_scale(app: &App) -> f32 {
+    let dpi = app
+        .context
+        .sdl_context
+        .video()
+        .and_then(|video| video.display_dpi(0));
+
+    match dpi {
+        Ok((_diagonal, horizontal, _vertical)) => (horizontal / REFERENCE_DISPLAY_DPI).max(1.0),
+        Err(_) => 1.0,
+    }
+}
+
 thread_local! {
     pub static GLOBAL_UI_CONTEXT: UIContext<'static> = Default::default();
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::Buffer2D,
+        color::{space::ColorSpace, Color},
+        ui::{
+            extent::ScreenExtent,
+            plot::{draw_line_plot, LinePlotOptions},
+        },
+    };
+
+    use super::UITheme;
+
+    #[test]
+    fn ui_colors_present_identically_over_either_color_space() {
+        let extent = ScreenExtent::new((2, 2), (28, 12));
+
+        let values = [0.0, 3.0, 1.0, 4.0];
+
+        // A scene color that's exactly representable in both color spaces.
+
+        let scene_srgb = Color::rgb(128, 128, 128);
+
+        let scene_linear = ColorSpace::Linear.from_srgb(scene_srgb);
+
+        // Linear frames correspond to the renderer's non-HDR path; they're
+        // presented before the UI is drawn over them.
+
+        let draw_over_frame = |frame_color_space: ColorSpace, line_color: Color| -> Buffer2D {
+            let scene_color = match frame_color_space {
+                ColorSpace::Srgb => scene_srgb,
+                ColorSpace::Linear => scene_linear,
+            };
+
+            let mut target = Buffer2D::new(32, 16, Some(scene_color.to_u32()));
+
+            frame_color_space.present(&mut target);
+
+            let options = LinePlotOptions {
+                line_color,
+                ..Default::default()
+            };
+
+            draw_line_plot(&mut target, &extent, &values, &options);
+
+            target
+        };
+
+        for line_color in [UITheme::default().accent, Color::rgb(6, 9, 12)] {
+            let srgb = draw_over_frame(ColorSpace::Srgb, line_color);
+            let linear = draw_over_frame(ColorSpace::Linear, line_color);
+
+            assert!(srgb.data.contains(&line_color.to_u32()));
+
+            assert_eq!(srgb.data, linear.data);
+        }
+    }
+}
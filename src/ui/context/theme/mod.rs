@@ -1,6 +1,6 @@
 use crate::color::{self, Color};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct UITheme {
     // Text
     pub text: Color,
@@ -8,6 +8,9 @@ pub struct UITheme {
     pub text_pressed: Color,
     pub text_focus: Color,
 
+    // Accent
+    pub accent: Color,
+
     // Panel
     pub panel_background: Color,
     pub panel_border: Color,
@@ -32,11 +35,24 @@ pub struct UITheme {
 
     // Separator
     pub separator: Color,
+
+    // Metrics
+    pub padding: u32,
+    pub gap: u32,
+
+    // Fonts
+    pub font_point_size: u16,
 }
 
 impl Default for UITheme {
     fn default() -> Self {
-        // The default theme.
+        Self::dark()
+    }
+}
+
+impl UITheme {
+    // The default theme.
+    pub fn dark() -> Self {
         Self {
             // Text
             text: color::WHITE,
@@ -44,6 +60,9 @@ impl Default for UITheme {
             text_pressed: color::GREEN,
             text_focus: color::WHITE,
 
+            // Accent
+            accent: Color::rgb(71, 114, 179),
+
             // Panel
             panel_background: Color::rgba(14, 14, 14, 220),
             panel_border: Color::rgba(35, 35, 35, 220),
@@ -68,6 +87,54 @@ impl Default for UITheme {
 
             // Separator
             separator: color::WHITE,
+
+            // Metrics
+            padding: 4,
+            gap: 6,
+
+            // Fonts
+            font_point_size: 14,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            // Text
+            text: Color::rgb(20, 20, 20),
+            text_hover: Color::rgb(20, 20, 20),
+            text_pressed: Color::rgb(30, 120, 30),
+            text_focus: Color::rgb(20, 20, 20),
+
+            // Accent
+            accent: Color::rgb(56, 110, 200),
+
+            // Panel
+            panel_background: Color::rgba(238, 238, 238, 235),
+            panel_border: Color::rgba(190, 190, 190, 235),
+
+            // Selection state
+            background_selected: Color::rgb(150, 185, 235),
+
+            // Button
+            button_background: Color::rgb(218, 218, 218),
+
+            // Text input (?)
+            input_background: color::WHITE,
+            input_background_slider_alpha: Color::rgb(205, 205, 205),
+            input_text: Color::rgb(20, 20, 20),
+            input_cursor: Color::rgb(90, 90, 90),
+
+            // Checkbox
+            checkbox_background: Color::rgb(200, 200, 200),
+
+            // Dropdown
+            dropdown_background: Color::rgb(228, 228, 228),
+
+            // Separator
+            separator: Color::rgb(160, 160, 160),
+
+            // Metrics and fonts are shared with the dark theme.
+            ..Self::dark()
         }
     }
 }
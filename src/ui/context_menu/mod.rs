@@ -0,0 +1,581 @@
+use sdl2::mouse::MouseButton;
+
+use crate::{
+    buffer::Buffer2D,
+    device::mouse::{MouseEventKind, MouseState},
+    graphics::{text::TextOperation, Graphics},
+};
+
+use super::{
+    context::GLOBAL_UI_CONTEXT,
+    ui_box::{interaction::UIBoxInteraction, key::UIKey},
+};
+
+static CONTEXT_MENU_ITEM_HORIZONTAL_PADDING: u32 = 8;
+
+static CONTEXT_MENU_SUBMENU_INDICATOR: &str = ">";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextMenuItem {
+    Action(String),
+    Separator,
+    Submenu(String, Vec<ContextMenuItem>),
+}
+
+impl ContextMenuItem {
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            ContextMenuItem::Action(label) | ContextMenuItem::Submenu(label, _) => Some(label),
+            ContextMenuItem::Separator => None,
+        }
+    }
+}
+
+// Pixel dimensions used to lay out menu items.
+#[derive(Debug, Copy, Clone)]
+pub struct ContextMenuMetrics {
+    pub item_width: u32,
+    pub item_height: u32,
+    pub separator_height: u32,
+}
+
+impl Default for ContextMenuMetrics {
+    fn default() -> Self {
+        Self {
+            item_width: 160,
+            item_height: 20,
+            separator_height: 7,
+        }
+    }
+}
+
+impl ContextMenuMetrics {
+    pub fn get_height(&self, item: &ContextMenuItem) -> u32 {
+        match item {
+            ContextMenuItem::Separator => self.separator_height,
+            _ => self.item_height,
+        }
+    }
+}
+
+// A menu (or submenu) panel that's currently on screen.
+#[derive(Debug, Clone)]
+pub struct ContextMenuPanel<'a> {
+    pub position: (i32, i32),
+    pub items: &'a [ContextMenuItem],
+    // Index of the item (in `items`) whose submenu is open, if any.
+    pub open_index: Option<usize>,
+}
+
+impl<'a> ContextMenuPanel<'a> {
+    pub fn get_item_top(&self, index: usize, metrics: &ContextMenuMetrics) -> i32 {
+        self.position.1
+            + self.items[..index]
+                .iter()
+                .map(|item| metrics.get_height(item) as i32)
+                .sum::<i32>()
+    }
+
+    pub fn get_item_at(&self, position: (i32, i32), metrics: &ContextMenuMetrics) -> Option<usize> {
+        let (x, y) = position;
+
+        if x < self.position.0 || x >= self.position.0 + metrics.item_width as i32 {
+            return None;
+        }
+
+        let mut top = self.position.1;
+
+        for (index, item) in self.items.iter().enumerate() {
+            let bottom = top + metrics.get_height(item) as i32;
+
+            if y >= top && y < bottom {
+                return Some(index);
+            }
+
+            top = bottom;
+        }
+
+        None
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ContextMenuState {
+    pub position: (i32, i32),
+    // Path of item indices through the currently open submenus.
+    pub open_submenus: Vec<usize>,
+}
+
+impl ContextMenuState {
+    pub fn new(position: (i32, i32)) -> Self {
+        Self {
+            position,
+            open_submenus: vec![],
+        }
+    }
+
+    // Returns the on-screen panels, from the root menu to the deepest open
+    // submenu.
+    pub fn get_panels<'a>(
+        &self,
+        items: &'a [ContextMenuItem],
+        metrics: &ContextMenuMetrics,
+    ) -> Vec<ContextMenuPanel<'a>> {
+        let mut panels = vec![ContextMenuPanel {
+            position: self.position,
+            items,
+            open_index: None,
+        }];
+
+        for index in &self.open_submenus {
+            let panel = panels.last_mut().unwrap();
+
+            let Some(ContextMenuItem::Submenu(_, children)) = panel.items.get(*index) else {
+                break;
+            };
+
+            panel.open_index.replace(*index);
+
+            let position = (
+                panel.position.0 + metrics.item_width as i32,
+                panel.get_item_top(*index, metrics),
+            );
+
+            panels.push(ContextMenuPanel {
+                position,
+                items: children,
+                open_index: None,
+            });
+        }
+
+        panels
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ContextMenuResult {
+    // Index path of the chosen action, e.g., `[2, 0]` for the first item in
+    // the third item's submenu.
+    pub selected: Option<Vec<usize>>,
+    pub is_open: bool,
+}
+
+// Advances the menu's state for one frame of mouse input. A right-click inside
+// the trigger region opens the menu at the mouse position; hovering a submenu
+// opens it; a left-click on an action selects it and closes the menu, and a
+// click anywhere outside of the menu closes it.
+pub fn update_context_menu(
+    state: &mut Option<ContextMenuState>,
+    items: &[ContextMenuItem],
+    metrics: &ContextMenuMetrics,
+    mouse_state: &MouseState,
+    is_trigger_hovered: bool,
+) -> ContextMenuResult {
+    let mut result = ContextMenuResult::default();
+
+    let position = mouse_state.position;
+
+    // Hit-test the open panels, deepest first.
+
+    let hit = state.as_ref().and_then(|menu| {
+        let panels = menu.get_panels(items, metrics);
+
+        panels.iter().enumerate().rev().find_map(|(depth, panel)| {
+            panel
+                .get_item_at(position, metrics)
+                .map(|index| (depth, index, panel.items[index].clone()))
+        })
+    });
+
+    if let Some(menu) = state.as_mut() {
+        if let Some((depth, index, ContextMenuItem::Submenu(_, _))) = &hit {
+            menu.open_submenus.truncate(*depth);
+            menu.open_submenus.push(*index);
+        }
+    }
+
+    if let Some(event) = mouse_state.button_event {
+        if let MouseEventKind::Down = event.kind {
+            match (event.button, &hit) {
+                (MouseButton::Left, Some((depth, index, ContextMenuItem::Action(_)))) => {
+                    let menu = state.take().unwrap();
+
+                    let mut path = menu.open_submenus[..*depth].to_vec();
+
+                    path.push(*index);
+
+                    result.selected.replace(path);
+                }
+                (MouseButton::Right, None) if is_trigger_hovered => {
+                    state.replace(ContextMenuState::new(position));
+                }
+                (_, None) => {
+                    // Click-away.
+
+                    state.take();
+                }
+                _ => (),
+            }
+        }
+    }
+
+    result.is_open = state.is_some();
+
+    result
+}
+
+// The context menu that's currently open (if any), along with the key of the
+// widget that opened it.
+#[derive(Debug, Clone)]
+pub struct OpenContextMenu {
+    pub owner: UIKey,
+    pub state: ContextMenuState,
+    pub items: Vec<ContextMenuItem>,
+}
+
+// Opens a context menu at the mouse when the user right-clicks the `owner`
+// widget; should be called once per frame, after the owner is pushed. Only one
+// menu is open at a time, and it's drawn above all windows by
+// `render_context_menu()`.
+pub fn do_context_menu(
+    owner: &UIKey,
+    interaction: &UIBoxInteraction,
+    items: &[ContextMenuItem],
+) -> ContextMenuResult {
+    GLOBAL_UI_CONTEXT.with(|ctx| {
+        let mut open_menu = ctx.context_menu.borrow_mut();
+
+        if open_menu.as_ref().is_some_and(|menu| &menu.owner != owner) {
+            return ContextMenuResult::default();
+        }
+
+        let mut state = open_menu.take().map(|menu| menu.state);
+
+        let result = update_context_menu(
+            &mut state,
+            items,
+            &ContextMenuMetrics::default(),
+            &ctx.input_events.borrow().mouse,
+            interaction.mouse_interaction_in_bounds.is_hovering,
+        );
+
+        *open_menu = state.map(|state| OpenContextMenu {
+            owner: owner.clone(),
+            state,
+            items: items.to_vec(),
+        });
+
+        result
+    })
+}
+
+// Draws the open context menu (and its open submenus), if any.
+pub fn render_context_menu(framebuffer: &mut Buffer2D) -> Result<(), String> {
+    GLOBAL_UI_CONTEXT.with(|ctx| {
+        let open_menu = ctx.context_menu.borrow();
+
+        let Some(menu) = open_menu.as_ref() else {
+            return Ok(());
+        };
+
+        let metrics = ContextMenuMetrics::default();
+
+        let mouse_position = ctx.input_events.borrow().mouse.position;
+
+        let theme = ctx.theme.borrow();
+
+        let font_info = ctx.font_info.borrow();
+        let mut text_cache = ctx.text_cache.borrow_mut();
+        let mut font_cache_rc = ctx.font_cache.borrow_mut();
+
+        let Some(font_cache) = font_cache_rc.as_mut() else {
+            return Ok(());
+        };
+
+        for panel in menu.state.get_panels(&menu.items, &metrics) {
+            let (panel_x, panel_y) = (
+                panel.position.0.max(0) as u32,
+                panel.position.1.max(0) as u32,
+            );
+
+            let panel_height: u32 = panel
+                .items
+                .iter()
+                .map(|item| metrics.get_height(item))
+                .sum();
+
+            Graphics::rectangle(
+                framebuffer,
+                panel_x,
+                panel_y,
+                metrics.item_width,
+                panel_height,
+                Some(theme.dropdown_background.to_u32()),
+                Some(theme.panel_border.to_u32()),
+            );
+
+            let hovered_index = panel.get_item_at(mouse_position, &metrics);
+
+            for (index, item) in panel.items.iter().enumerate() {
+                let item_y = panel.get_item_top(index, &metrics).max(0) as u32;
+
+                let label = match item {
+                    ContextMenuItem::Separator => {
+                        // Draws a line through the middle of the separator's row.
+
+                        let separator_y = (item_y + metrics.separator_height / 2) as i32;
+
+                        Graphics::line(
+                            framebuffer,
+                            (panel_x + CONTEXT_MENU_ITEM_HORIZONTAL_PADDING) as i32,
+                            separator_y,
+                            (panel_x + metrics.item_width - CONTEXT_MENU_ITEM_HORIZONTAL_PADDING)
+                                as i32,
+                            separator_y,
+                            theme.separator.to_u32(),
+                        );
+
+                        continue;
+                    }
+                    ContextMenuItem::Action(label) | ContextMenuItem::Submenu(label, _) => label,
+                };
+
+                // Highlights the hovered item, or the item whose submenu is open.
+
+                if hovered_index == Some(index) || panel.open_index == Some(index) {
+                    Graphics::rectangle(
+                        framebuffer,
+                        panel_x + 1,
+                        item_y + 1,
+                        metrics.item_width - 2,
+                        metrics.item_height - 2,
+                        Some(theme.background_selected.to_u32()),
+                        None,
+                    );
+                }
+
+                Graphics::text(
+                    framebuffer,
+                    font_cache,
+                    Some(&mut text_cache),
+                    &font_info,
+                    &TextOperation {
+                        text: label,
+                        x: panel_x + CONTEXT_MENU_ITEM_HORIZONTAL_PADDING,
+                        y: item_y + 1,
+                        color: theme.text,
+                    },
+                )?;
+
+                if let ContextMenuItem::Submenu(_, _) = item {
+                    Graphics::text(
+                        framebuffer,
+                        font_cache,
+                        Some(&mut text_cache),
+                        &font_info,
+                        &TextOperation {
+                            text: &CONTEXT_MENU_SUBMENU_INDICATOR.to_string(),
+                            x: panel_x + metrics.item_width
+                                - CONTEXT_MENU_ITEM_HORIZONTAL_PADDING * 2,
+                            y: item_y + 1,
+                            color: theme.text,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use sdl2::mouse::MouseButton;
+
+    use crate::{
+        device::mouse::{MouseEvent, MouseEventKind, MouseState},
+        ui::{
+            context::GLOBAL_UI_CONTEXT,
+            ui_box::{interaction::UIBoxInteraction, key::UIKey},
+        },
+    };
+
+    use super::{do_context_menu, update_context_menu, ContextMenuItem, ContextMenuMetrics};
+
+    fn click(button: MouseButton, position: (i32, i32)) -> MouseState {
+        MouseState {
+            position,
+            button_event: Some(MouseEvent {
+                button,
+                kind: MouseEventKind::Down,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn hover(position: (i32, i32)) -> MouseState {
+        MouseState {
+            position,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn right_click_opens_and_selection_closes() {
+        let items = vec![
+            ContextMenuItem::Action("Rename".to_string()),
+            ContextMenuItem::Separator,
+            ContextMenuItem::Action("Delete".to_string()),
+        ];
+
+        let metrics = ContextMenuMetrics::default();
+
+        let mut state = None;
+
+        let result = update_context_menu(
+            &mut state,
+            &items,
+            &metrics,
+            &click(MouseButton::Right, (100, 50)),
+            true,
+        );
+
+        assert!(result.is_open);
+        assert_eq!(state.as_ref().unwrap().position, (100, 50));
+
+        // Click "Delete" (below the first item and the separator).
+
+        let y = 50 + (metrics.item_height + metrics.separator_height) as i32 + 1;
+
+        let result = update_context_menu(
+            &mut state,
+            &items,
+            &metrics,
+            &click(MouseButton::Left, (110, y)),
+            true,
+        );
+
+        assert_eq!(result.selected, Some(vec![2]));
+        assert!(!result.is_open);
+        assert!(state.is_none());
+    }
+
+    #[test]
+    fn hovering_opens_submenu_and_click_away_closes() {
+        let items = vec![ContextMenuItem::Submenu(
+            "Add".to_string(),
+            vec![
+                ContextMenuItem::Action("Cube".to_string()),
+                ContextMenuItem::Action("Sphere".to_string()),
+            ],
+        )];
+
+        let metrics = ContextMenuMetrics::default();
+
+        let mut state = None;
+
+        update_context_menu(
+            &mut state,
+            &items,
+            &metrics,
+            &click(MouseButton::Right, (0, 0)),
+            true,
+        );
+
+        update_context_menu(&mut state, &items, &metrics, &hover((5, 5)), false);
+
+        assert_eq!(state.as_ref().unwrap().open_submenus, vec![0]);
+
+        let submenu_x = metrics.item_width as i32 + 5;
+        let sphere_y = metrics.item_height as i32 + 5;
+
+        let result = update_context_menu(
+            &mut state,
+            &items,
+            &metrics,
+            &click(MouseButton::Left, (submenu_x, sphere_y)),
+            false,
+        );
+
+        assert_eq!(result.selected, Some(vec![0, 1]));
+
+        // Re-open, then click away.
+
+        update_context_menu(
+            &mut state,
+            &items,
+            &metrics,
+            &click(MouseButton::Right, (0, 0)),
+            true,
+        );
+
+        let result = update_context_menu(
+            &mut state,
+            &items,
+            &metrics,
+            &click(MouseButton::Left, (500, 500)),
+            true,
+        );
+
+        assert!(!result.is_open);
+        assert_eq!(result.selected, None);
+    }
+
+    #[test]
+    fn do_context_menu_keeps_its_state_in_the_ui_context() {
+        let owner = UIKey::from_string("Outline".to_string());
+
+        let items = vec![
+            ContextMenuItem::Action("Rename".to_string()),
+            ContextMenuItem::Separator,
+            ContextMenuItem::Action("Delete".to_string()),
+        ];
+
+        let metrics = ContextMenuMetrics::default();
+
+        let mut hovered = UIBoxInteraction::default();
+
+        hovered.mouse_interaction_in_bounds.is_hovering = true;
+
+        let set_mouse = |mouse_state: MouseState| {
+            GLOBAL_UI_CONTEXT.with(|ctx| ctx.input_events.borrow_mut().mouse = mouse_state);
+        };
+
+        // Right-clicking the owner opens its menu at the mouse.
+
+        set_mouse(click(MouseButton::Right, (100, 50)));
+
+        let result = do_context_menu(&owner, &hovered, &items);
+
+        assert!(result.is_open);
+
+        GLOBAL_UI_CONTEXT.with(|ctx| {
+            let open_menu = ctx.context_menu.borrow();
+
+            let menu = open_menu.as_ref().unwrap();
+
+            assert_eq!(menu.owner, owner);
+            assert_eq!(menu.state.position, (100, 50));
+        });
+
+        // Other widgets don't see the menu.
+
+        let other = UIKey::from_string("Inspector".to_string());
+
+        assert!(!do_context_menu(&other, &hovered, &items).is_open);
+
+        // Selecting "Delete" reports its index, and closes the menu.
+
+        let y = 50 + (metrics.item_height + metrics.separator_height) as i32 + 1;
+
+        set_mouse(click(MouseButton::Left, (110, y)));
+
+        let result = do_context_menu(&owner, &UIBoxInteraction::default(), &items);
+
+        assert_eq!(result.selected, Some(vec![2]));
+        assert!(!result.is_open);
+
+        GLOBAL_UI_CONTEXT.with(|ctx| assert!(ctx.context_menu.borrow().is_none()));
+    }
+}
@@ -0,0 +1,319 @@
+use std::{any::Any, fmt, rc::Rc};
+
+use sdl2::mouse::MouseButton;
+
+use crate::{
+    buffer::Buffer2D,
+    device::mouse::{MouseEventKind, MouseState},
+    graphics::{
+        text::{
+            cache::{cache_text, TextCacheKey},
+            TextOperation,
+        },
+        Graphics,
+    },
+};
+
+use super::{
+    context::GLOBAL_UI_CONTEXT,
+    ui_box::{interaction::UIBoxInteraction, key::UIKey},
+};
+
+static DRAG_PREVIEW_CURSOR_OFFSET: i32 = 12;
+
+#[derive(Clone)]
+struct ActiveDrag {
+    source: UIKey,
+    payload: Rc<dyn Any>,
+    preview_label: String,
+}
+
+impl fmt::Debug for ActiveDrag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActiveDrag")
+            .field("source", &self.source)
+            .field("payload", &"Rc<dyn Any>")
+            .field("preview_label", &self.preview_label)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragDropDelivery<T> {
+    pub source: UIKey,
+    pub target: UIKey,
+    pub payload: T,
+}
+
+// Tracks a drag (started by some drag-source widget) that carries a typed
+// payload, until the payload is dropped onto a drop target, or cancelled.
+#[derive(Default, Debug, Clone)]
+pub struct DragDropState {
+    active: Option<ActiveDrag>,
+    is_dropping: bool,
+    mouse_position: (i32, i32),
+}
+
+impl DragDropState {
+    pub fn begin_drag<T: 'static>(&mut self, source: UIKey, payload: T, preview_label: String) {
+        self.active.replace(ActiveDrag {
+            source,
+            payload: Rc::new(payload),
+            preview_label,
+        });
+
+        self.is_dropping = false;
+    }
+
+    pub fn cancel(&mut self) {
+        self.active.take();
+
+        self.is_dropping = false;
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.active.is_some()
+    }
+
+    pub fn get_source(&self) -> Option<&UIKey> {
+        self.active.as_ref().map(|drag| &drag.source)
+    }
+
+    // Returns the current payload, if one is being dragged and it has type `T`.
+    pub fn peek_payload<T: 'static>(&self) -> Option<&T> {
+        self.active
+            .as_ref()
+            .and_then(|drag| drag.payload.downcast_ref::<T>())
+    }
+
+    // Called once per frame with the latest mouse state. Releasing the left
+    // mouse button drops the payload; if no target accepts it during that
+    // frame, the drag is cancelled on the next update.
+    pub fn update(&mut self, mouse_state: &MouseState) {
+        if self.is_dropping {
+            self.cancel();
+        }
+
+        self.mouse_position = mouse_state.position;
+
+        if let (Some(_), Some(event)) = (&self.active, mouse_state.button_event) {
+            if matches!(
+                (event.button, event.kind),
+                (MouseButton::Left, MouseEventKind::Up)
+            ) {
+                self.is_dropping = true;
+            }
+        }
+    }
+
+    // Delivers the payload to `target` if it was dropped while the target was
+    // hovered, and the payload has type `T`.
+    pub fn accept_drop<T: Clone + 'static>(
+        &mut self,
+        target: &UIKey,
+        is_hovering: bool,
+    ) -> Option<DragDropDelivery<T>> {
+        if !self.is_dropping || !is_hovering {
+            return None;
+        }
+
+        let payload = self.peek_payload::<T>()?.clone();
+
+        let drag = self.active.take().unwrap();
+
+        self.is_dropping = false;
+
+        Some(DragDropDelivery {
+            source: drag.source,
+            target: target.clone(),
+            payload,
+        })
+    }
+
+    // Returns the drag preview's label and screen position, if dragging.
+    pub fn get_preview(&self) -> Option<(&str, (i32, i32))> {
+        self.active.as_ref().map(|drag| {
+            (
+                drag.preview_label.as_str(),
+                (
+                    self.mouse_position.0 + DRAG_PREVIEW_CURSOR_OFFSET,
+                    self.mouse_position.1 + DRAG_PREVIEW_CURSOR_OFFSET,
+                ),
+            )
+        })
+    }
+}
+
+// Begins a drag from `source` when the user starts dragging it with the left
+// mouse button.
+pub fn drag_source<T, F>(
+    source: &UIKey,
+    interaction: &UIBoxInteraction,
+    preview_label: String,
+    get_payload: F,
+) where
+    T: 'static,
+    F: FnOnce() -> T,
+{
+    let mouse_interaction = &interaction.mouse_interaction_in_bounds;
+
+    if !(mouse_interaction.is_left_down && mouse_interaction.drag_event.is_some()) {
+        return;
+    }
+
+    GLOBAL_UI_CONTEXT.with(|ctx| {
+        let mut drag_drop = ctx.drag_drop.borrow_mut();
+
+        if !drag_drop.is_dragging() {
+            drag_drop.begin_drag(source.clone(), get_payload(), preview_label);
+        }
+    });
+}
+
+// Returns the payload dropped onto `target` this frame, if any.
+pub fn drop_target<T: Clone + 'static>(
+    target: &UIKey,
+    interaction: &UIBoxInteraction,
+) -> Option<DragDropDelivery<T>> {
+    GLOBAL_UI_CONTEXT.with(|ctx| {
+        ctx.drag_drop
+            .borrow_mut()
+            .accept_drop(target, interaction.mouse_interaction_in_bounds.is_hovering)
+    })
+}
+
+// Draws the active drag's preview label next to the mouse cursor.
+pub fn render_drag_preview(framebuffer: &mut Buffer2D) {
+    GLOBAL_UI_CONTEXT.with(|ctx| {
+        let drag_drop = ctx.drag_drop.borrow();
+
+        let Some((label, (x, y))) = drag_drop.get_preview() else {
+            return;
+        };
+
+        let font_info = ctx.font_info.borrow();
+        let mut text_cache = ctx.text_cache.borrow_mut();
+        let mut font_cache_rc = ctx.font_cache.borrow_mut();
+
+        let Some(font_cache) = font_cache_rc.as_mut() else {
+            return;
+        };
+
+        let (text_width, text_height) = cache_text(font_cache, &mut text_cache, &font_info, label);
+
+        let theme = ctx.theme.borrow();
+
+        let padding = theme.padding;
+
+        let (width, height) = (text_width + padding * 2, text_height + padding * 2);
+
+        let x = (x.max(0) as u32).min(framebuffer.width.saturating_sub(width));
+        let y = (y.max(0) as u32).min(framebuffer.height.saturating_sub(height));
+
+        Graphics::rectangle(
+            framebuffer,
+            x,
+            y,
+            width,
+            height,
+            Some(theme.dropdown_background.to_u32()),
+            Some(theme.panel_border.to_u32()),
+        );
+
+        let key = TextCacheKey {
+            font_info: font_info.clone(),
+            text: label.to_string(),
+        };
+
+        if let Some(texture) = text_cache.get(&key) {
+            Graphics::blit_text_from_mask(
+                texture,
+                &TextOperation {
+                    text: &key.text,
+                    x: x + padding,
+                    y: y + padding,
+                    color: theme.text,
+                },
+                framebuffer,
+                None,
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use sdl2::mouse::MouseButton;
+
+    use crate::{
+        device::mouse::{MouseEvent, MouseEventKind, MouseState},
+        ui::ui_box::key::UIKey,
+    };
+
+    use super::DragDropState;
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    struct AssetHandle(u32);
+
+    fn mouse_at(position: (i32, i32), released: bool) -> MouseState {
+        MouseState {
+            position,
+            button_event: if released {
+                Some(MouseEvent {
+                    button: MouseButton::Left,
+                    kind: MouseEventKind::Up,
+                })
+            } else {
+                None
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn drag_from_source_delivers_payload_to_target() {
+        let source = UIKey::from_string("AssetBrowser.item_3".to_string());
+        let target = UIKey::from_string("Viewport".to_string());
+
+        let mut state = DragDropState::default();
+
+        state.begin_drag(source.clone(), AssetHandle(3), "crate.obj".to_string());
+
+        // Move across the viewport.
+
+        state.update(&mouse_at((100, 100), false));
+
+        assert_eq!(state.get_preview().unwrap().0, "crate.obj");
+
+        assert!(state.accept_drop::<AssetHandle>(&target, true).is_none());
+
+        // Release over the viewport.
+
+        state.update(&mouse_at((120, 110), true));
+
+        // Targets that aren't hovered, or expect another type, don't receive it.
+
+        assert!(state.accept_drop::<AssetHandle>(&source, false).is_none());
+        assert!(state.accept_drop::<String>(&target, true).is_none());
+
+        let delivery = state.accept_drop::<AssetHandle>(&target, true).unwrap();
+
+        assert_eq!(delivery.source, source);
+        assert_eq!(delivery.target, target);
+        assert_eq!(delivery.payload, AssetHandle(3));
+
+        assert!(!state.is_dragging());
+    }
+
+    #[test]
+    fn unaccepted_drop_is_cancelled() {
+        let mut state = DragDropState::default();
+
+        state.begin_drag(UIKey::from_string("a".to_string()), 1_u32, "1".to_string());
+
+        state.update(&mouse_at((0, 0), true));
+        state.update(&mouse_at((0, 0), false));
+
+        assert!(!state.is_dragging());
+    }
+}
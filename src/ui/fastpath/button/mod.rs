@@ -1,4 +1,5 @@
 use crate::ui::{
+    context::GLOBAL_UI_CONTEXT,
     ui_box::{UIBox, UIBoxFeatureFlag, UILayoutDirection},
     UISize, UISizeWithStrictness,
 };
@@ -37,5 +38,37 @@ pub fn button(
 
     button_box.text_content = Some(label);
 
+    // Falls back to the theme's button color when no fill color is pushed.
+
+    if button_box.styles.fill_color.is_none() {
+        button_box.styles.fill_color =
+            Some(GLOBAL_UI_CONTEXT.with(|ctx| ctx.theme.borrow().button_background));
+    }
+
     button_box
 }
+
+#[cfg(test)]
+mod test {
+    use crate::ui::context::{theme::UITheme, GLOBAL_UI_CONTEXT};
+
+    use super::button;
+
+    #[test]
+    fn switching_themes_changes_button_background() {
+        let dark = button("ok".to_string(), "OK".to_string(), None);
+
+        GLOBAL_UI_CONTEXT.with(|ctx| ctx.set_theme(UITheme::light()));
+
+        let light = button("ok".to_string(), "OK".to_string(), None);
+
+        let (dark_fill, light_fill) = (
+            dark.styles.fill_color.unwrap().to_u32(),
+            light.styles.fill_color.unwrap().to_u32(),
+        );
+
+        assert_eq!(dark_fill, UITheme::dark().button_background.to_u32());
+        assert_eq!(light_fill, UITheme::light().button_background.to_u32());
+        assert_ne!(dark_fill, light_fill);
+    }
+}
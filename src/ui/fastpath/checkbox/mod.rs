@@ -140,7 +140,7 @@ pub fn checkbox(
                     let theme = ctx.theme.borrow();
 
                     let fill_color = if item.is_checked {
-                        theme.background_selected
+                        theme.accent
                     } else {
                         theme.checkbox_background
                     };
@@ -168,7 +168,9 @@ pub fn checkbox(
                 item.label.to_string(),
             );
 
-            tree.push(spacer(6))?;
+            let gap = GLOBAL_UI_CONTEXT.with(|ctx| ctx.theme.borrow().gap);
+
+            tree.push(spacer(gap))?;
 
             tree.push(checkbox_label)?;
 
@@ -0,0 +1,69 @@
+use crate::{
+    device::cpu::CpuCapabilities,
+    time::TimingInfo,
+    ui::{
+        context::GLOBAL_UI_CONTEXT,
+        ui_box::{tree::UIBoxTree, UILayoutDirection},
+    },
+};
+
+use super::{container::container, text::text};
+
+// Lists the current frame rate and the host CPU's capabilities, one per line.
+pub fn debug_info(id: String, tree: &mut UIBoxTree) -> Result<(), String> {
+    let timing_info = GLOBAL_UI_CONTEXT.with(|ctx| *ctx.timing_info.borrow());
+
+    let lines = get_debug_info_lines(&timing_info, CpuCapabilities::get());
+
+    tree.with_parent(
+        container(
+            format!("{}_debug_info_container", id),
+            UILayoutDirection::TopToBottom,
+            None,
+        ),
+        |tree| -> Result<(), String> {
+            for (index, line) in lines.into_iter().enumerate() {
+                tree.push(text(format!("{}.debug_info_line_{}", id, index), line))?;
+            }
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+fn get_debug_info_lines(
+    timing_info: &TimingInfo,
+    cpu_capabilities: &CpuCapabilities,
+) -> Vec<String> {
+    vec![
+        format!("{:.0} FPS", timing_info.frames_per_second),
+        cpu_capabilities.to_string(),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{device::cpu::CpuCapabilities, time::TimingInfo};
+
+    use super::get_debug_info_lines;
+
+    #[test]
+    fn debug_info_reports_cpu_capabilities() {
+        let timing_info = TimingInfo {
+            frames_per_second: 59.6,
+            ..Default::default()
+        };
+
+        let cpu_capabilities = CpuCapabilities::get();
+
+        let lines = get_debug_info_lines(&timing_info, cpu_capabilities);
+
+        assert_eq!(lines[0], "60 FPS");
+
+        assert_eq!(lines[1], cpu_capabilities.to_string());
+
+        assert!(lines[1].contains(&cpu_capabilities.simd_level.to_string()));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::ui::{
+    context::GLOBAL_UI_CONTEXT,
+    ui_box::{key::UIKey, tree::UIBoxTree, UILayoutDirection},
+};
+
+use super::{button::button, container::container};
+
+// Shows the selected item as a button; clicking it expands the list of items
+// (inline, below the button). Returns the index of a newly-selected item.
+pub fn dropdown(
+    id: String,
+    items: &[String],
+    selected_index: usize,
+    tree: &mut UIBoxTree,
+) -> Result<Option<usize>, String> {
+    let container_id = format!("{}_dropdown_container", id);
+
+    let ui_key = UIKey::from_string(container_id.clone());
+
+    let was_expanded = GLOBAL_UI_CONTEXT.with(|ctx| {
+        let cache = ctx.cache.borrow();
+
+        match cache.get(&ui_key) {
+            Some(previous_frame) => previous_frame.expanded,
+            None => false,
+        }
+    });
+
+    let wrapper = container(container_id, UILayoutDirection::TopToBottom, None);
+
+    let mut was_toggled = false;
+
+    let mut result = None;
+
+    tree.with_parent(wrapper, |tree| -> Result<(), String> {
+        let selected_label = match items.get(selected_index) {
+            Some(item) => item.clone(),
+            None => "-".to_string(),
+        };
+
+        let toggle_label = format!(
+            "{} {}",
+            selected_label,
+            if was_expanded { "^" } else { "v" }
+        );
+
+        let interaction_result = tree.push(button(
+            format!("{}.dropdown_toggle", id),
+            toggle_label,
+            None,
+        ))?;
+
+        if interaction_result
+            .mouse_interaction_in_bounds
+            .was_left_pressed
+        {
+            was_toggled = true;
+        }
+
+        if was_expanded {
+            for (index, item) in items.iter().enumerate() {
+                let interaction_result = tree.push(button(
+                    format!("{}.dropdown_item_{}", id, index),
+                    item.clone(),
+                    None,
+                ))?;
+
+                if interaction_result
+                    .mouse_interaction_in_bounds
+                    .was_left_pressed
+                {
+                    result = Some(index);
+
+                    was_toggled = true;
+                }
+            }
+        }
+
+        Ok(())
+    })?;
+
+    if let Some(current_rc) = tree.get_current() {
+        let parent_node = current_rc.borrow_mut();
+
+        let wrapper_node_rc = &parent_node.children[parent_node.children.len() - 1];
+        let mut wrapper_node = wrapper_node_rc.borrow_mut();
+
+        wrapper_node.data.expanded = was_expanded != was_toggled;
+    }
+
+    Ok(result)
+}
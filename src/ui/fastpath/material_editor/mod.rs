@@ -0,0 +1,272 @@
+use crate::{
+    material::Material,
+    resource::{arena::Arena, handle::Handle},
+    texture::map::TextureMap,
+    ui::ui_box::{tree::UIBoxTree, UILayoutDirection},
+    vec::vec3::{self, Vec3},
+};
+
+use super::{
+    color::color_picker,
+    container::container,
+    dropdown::dropdown,
+    slider::{slider, SliderOptions},
+    text::text,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MaterialMap {
+    Albedo,
+    Normal,
+    Roughness,
+    Metallic,
+    Emissive,
+}
+
+impl MaterialMap {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MaterialMap::Albedo => "Albedo map",
+            MaterialMap::Normal => "Normal map",
+            MaterialMap::Roughness => "Roughness map",
+            MaterialMap::Metallic => "Metallic map",
+            MaterialMap::Emissive => "Emissive map",
+        }
+    }
+
+    fn get_handle_mut<'a>(&self, material: &'a mut Material) -> &'a mut Option<Handle> {
+        match self {
+            MaterialMap::Albedo => &mut material.albedo_map,
+            MaterialMap::Normal => &mut material.normal_map,
+            MaterialMap::Roughness => &mut material.roughness_map,
+            MaterialMap::Metallic => &mut material.metallic_map,
+            MaterialMap::Emissive => &mut material.emissive_color_map,
+        }
+    }
+}
+
+static MATERIAL_MAPS: [MaterialMap; 5] = [
+    MaterialMap::Albedo,
+    MaterialMap::Normal,
+    MaterialMap::Roughness,
+    MaterialMap::Metallic,
+    MaterialMap::Emissive,
+];
+
+#[derive(Debug, Copy, Clone)]
+pub enum MaterialEdit {
+    Albedo(Vec3),
+    Metallic(f32),
+    Roughness(f32),
+    Shininess(f32),
+    EmissiveIntensity(f32),
+    Map(MaterialMap, Option<Handle>),
+}
+
+// A slider's label, current value and options, and the edit it produces.
+type ScalarField<'a> = (&'a str, f32, SliderOptions, fn(f32) -> MaterialEdit);
+
+// The emissive intensity is the largest component of the emissive color.
+pub fn get_emissive_intensity(material: &Material) -> f32 {
+    let color = material.emissive_color;
+
+    color.x.max(color.y).max(color.z)
+}
+
+pub fn apply_material_edit(material: &mut Material, edit: MaterialEdit) {
+    match edit {
+        MaterialEdit::Albedo(albedo) => material.albedo = albedo,
+        MaterialEdit::Metallic(metallic) => material.metallic = metallic,
+        MaterialEdit::Roughness(roughness) => material.roughness = roughness,
+        MaterialEdit::Shininess(exponent) => {
+            material.specular_exponent = exponent.round().clamp(1.0, 255.0) as u8
+        }
+        MaterialEdit::EmissiveIntensity(intensity) => {
+            // Scales the emissive color, preserving its hue.
+
+            let current = get_emissive_intensity(material);
+
+            material.emissive_color = if current > 0.0 {
+                material.emissive_color * (intensity / current)
+            } else {
+                vec3::ONES * intensity
+            };
+        }
+        MaterialEdit::Map(map, handle) => *map.get_handle_mut(material) = handle,
+    }
+}
+
+// Edits a material's surface properties and texture map assignments; returns
+// `true` if the material changed.
+pub fn material_editor(
+    id: String,
+    material: &mut Material,
+    texture_arena: &Arena<TextureMap>,
+    tree: &mut UIBoxTree,
+) -> Result<bool, String> {
+    let mut edits: Vec<MaterialEdit> = vec![];
+
+    // Map assignment options ("None", followed by each texture in the arena).
+
+    let mut map_handles: Vec<Option<Handle>> = vec![None];
+    let mut map_labels: Vec<String> = vec!["None".to_string()];
+
+    for (index, entry) in texture_arena.entries.iter().enumerate() {
+        if let Some(entry) = entry {
+            map_handles.push(Some(Handle {
+                index,
+                uuid: entry.uuid,
+            }));
+
+            map_labels.push(entry.item.info.filepath.clone());
+        }
+    }
+
+    tree.with_parent(
+        container(
+            format!("{}_material_editor", id),
+            UILayoutDirection::TopToBottom,
+            None,
+        ),
+        |tree| -> Result<(), String> {
+            let unit_range = SliderOptions::default();
+
+            tree.push(text(
+                format!("{}_material_editor_albedo_label", id),
+                "Albedo".to_string(),
+            ))?;
+
+            if let Some(albedo) = color_picker(
+                format!("{}_material_editor_albedo", id),
+                material.albedo,
+                unit_range,
+                tree,
+            )? {
+                edits.push(MaterialEdit::Albedo(albedo));
+            }
+
+            let scalar_fields: [ScalarField; 4] = [
+                (
+                    "Metallic",
+                    material.metallic,
+                    unit_range,
+                    MaterialEdit::Metallic,
+                ),
+                (
+                    "Roughness",
+                    material.roughness,
+                    unit_range,
+                    MaterialEdit::Roughness,
+                ),
+                (
+                    "Shininess",
+                    material.specular_exponent as f32,
+                    SliderOptions {
+                        min: 1.0,
+                        max: 255.0,
+                        decimals: 0,
+                        ..Default::default()
+                    },
+                    MaterialEdit::Shininess,
+                ),
+                (
+                    "Emissive intensity",
+                    get_emissive_intensity(material),
+                    SliderOptions {
+                        min: 0.0,
+                        max: 10.0,
+                        ..Default::default()
+                    },
+                    MaterialEdit::EmissiveIntensity,
+                ),
+            ];
+
+            for (label, value, options, to_edit) in scalar_fields {
+                tree.push(text(
+                    format!("{}_material_editor_{}_label", id, label),
+                    label.to_string(),
+                ))?;
+
+                if let Some(new_value) = slider(
+                    format!("{}_material_editor_{}", id, label),
+                    value,
+                    options,
+                    tree,
+                )? {
+                    edits.push(to_edit(new_value));
+                }
+            }
+
+            for map in MATERIAL_MAPS.iter() {
+                tree.push(text(
+                    format!("{}_material_editor_{}_label", id, map.label()),
+                    map.label().to_string(),
+                ))?;
+
+                let current = *map.get_handle_mut(material);
+
+                let selected_index = map_handles
+                    .iter()
+                    .position(|handle| *handle == current)
+                    .unwrap_or(0);
+
+                if let Some(index) = dropdown(
+                    format!("{}_material_editor_{}", id, map.label()),
+                    &map_labels,
+                    selected_index,
+                    tree,
+                )? {
+                    edits.push(MaterialEdit::Map(*map, map_handles[index]));
+                }
+            }
+
+            Ok(())
+        },
+    )?;
+
+    let did_edit = !edits.is_empty();
+
+    for edit in edits {
+        apply_material_edit(material, edit);
+    }
+
+    Ok(did_edit)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{material::Material, resource::handle::Handle, vec::vec3::Vec3};
+
+    use super::{apply_material_edit, get_emissive_intensity, MaterialEdit, MaterialMap};
+
+    #[test]
+    fn albedo_edit_updates_material() {
+        let mut material = Material::new("brick".to_string());
+
+        let albedo = Vec3 {
+            x: 0.8,
+            y: 0.2,
+            z: 0.1,
+        };
+
+        apply_material_edit(&mut material, MaterialEdit::Albedo(albedo));
+
+        assert_eq!(
+            (material.albedo.x, material.albedo.y, material.albedo.z),
+            (0.8, 0.2, 0.1)
+        );
+
+        apply_material_edit(&mut material, MaterialEdit::EmissiveIntensity(2.0));
+
+        assert_eq!(get_emissive_intensity(&material), 2.0);
+
+        let handle = Handle::default();
+
+        apply_material_edit(
+            &mut material,
+            MaterialEdit::Map(MaterialMap::Normal, Some(handle)),
+        );
+
+        assert_eq!(material.normal_map, Some(handle));
+    }
+}
@@ -2,7 +2,10 @@ pub mod button;
 pub mod checkbox;
 pub mod color;
 pub mod container;
+pub mod debug_info;
+pub mod dropdown;
 pub mod image;
+pub mod material_editor;
 pub mod radio;
 pub mod slider;
 pub mod spacer;
@@ -10,3 +13,5 @@ pub mod stack;
 pub mod tab_selector;
 pub mod text;
 pub mod text_input;
+pub mod transform_editor;
+pub mod virtual_list;
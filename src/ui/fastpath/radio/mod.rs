@@ -70,15 +70,15 @@ fn render_selected_indicator(
     GLOBAL_UI_CONTEXT.with(|ctx| {
         let theme = ctx.theme.borrow();
 
-        let background_selected_u32 = theme.background_selected.to_u32();
+        let accent_u32 = theme.accent.to_u32();
 
         Graphics::circle(
             target,
             (extent.left + 8) as i32,
             (extent.top + 8) as i32,
             5,
-            Some(background_selected_u32),
-            Some(background_selected_u32),
+            Some(accent_u32),
+            Some(accent_u32),
         );
     });
 
@@ -111,13 +111,13 @@ pub fn radio(
                     let theme = ctx.theme.borrow();
 
                     let fill_color = if is_selected {
-                        theme.background_selected
+                        theme.accent
                     } else {
                         theme.checkbox_background
                     };
 
                     let border_color = if is_selected {
-                        theme.background_selected
+                        theme.accent
                     } else {
                         theme.checkbox_background
                     };
@@ -147,7 +147,9 @@ pub fn radio(
                 option.label.to_string(),
             );
 
-            tree.push(spacer(6))?;
+            let gap = GLOBAL_UI_CONTEXT.with(|ctx| ctx.theme.borrow().gap);
+
+            tree.push(spacer(gap))?;
 
             tree.push(radio_option_label)?;
 
@@ -1,3 +1,5 @@
+use sdl2::keyboard::Mod;
+
 use crate::{
     device::mouse::cursor::MouseCursorKind,
     ui::{
@@ -12,12 +14,16 @@ use crate::{
 
 use super::{spacer::greedy_spacer, text::text};
 
+pub static SLIDER_FINE_DRAG_FACTOR: f32 = 0.1;
+
 #[derive(Debug, Copy, Clone)]
 pub struct SliderOptions {
     pub min: f32,
     pub max: f32,
     pub decimals: usize,
     pub is_vertical: bool,
+    // Increment used when snapping (ctrl-drag); defaults to 1/10th of the range.
+    pub step: Option<f32>,
 }
 
 impl Default for SliderOptions {
@@ -27,10 +33,84 @@ impl Default for SliderOptions {
             max: 1.0,
             decimals: 2,
             is_vertical: false,
+            step: None,
+        }
+    }
+}
+
+impl SliderOptions {
+    pub fn get_step(&self) -> f32 {
+        self.step.unwrap_or((self.max - self.min) / 10.0)
+    }
+}
+
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub enum SliderDragMode {
+    #[default]
+    Normal,
+    Fine,
+    Snap,
+}
+
+impl SliderDragMode {
+    pub fn from_modifiers(modifiers: Mod) -> Self {
+        if modifiers.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD) {
+            Self::Fine
+        } else if modifiers.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD) {
+            Self::Snap
+        } else {
+            Self::Normal
         }
     }
 }
 
+// Returns the (unsnapped) drag value after a drag covering `drag_alpha` of the
+// slider's extent; callers should carry this value across the whole drag, and
+// pass it through `get_committed_value()` before displaying or storing it.
+pub fn get_dragged_value(
+    drag_value: f32,
+    drag_alpha: f32,
+    options: &SliderOptions,
+    mode: SliderDragMode,
+) -> f32 {
+    let value_delta = (options.max - options.min) * drag_alpha;
+
+    let new_value = match mode {
+        SliderDragMode::Normal | SliderDragMode::Snap => drag_value + value_delta,
+        SliderDragMode::Fine => drag_value + value_delta * SLIDER_FINE_DRAG_FACTOR,
+    };
+
+    new_value.max(options.min).min(options.max)
+}
+
+// Returns the value to display (or store) for a given drag value; snapping
+// happens here, so that many small drags can still accumulate into a step.
+// The result always lies within [min, max].
+pub fn get_committed_value(drag_value: f32, options: &SliderOptions, mode: SliderDragMode) -> f32 {
+    let step = options.get_step();
+
+    let value = if mode == SliderDragMode::Snap && step.is_finite() && step > 0.0 {
+        options.min + ((drag_value - options.min) / step).round() * step
+    } else {
+        drag_value
+    };
+
+    // Note that `f32::max()` and `f32::min()` discard a NaN operand.
+
+    let value = if value.is_finite() { value } else { drag_value };
+
+    value.max(options.min).min(options.max)
+}
+
+// Parses a typed slider value, clamping it to the slider's range.
+pub fn parse_slider_entry(text: &str, options: &SliderOptions) -> Option<f32> {
+    text.trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .map(|value| value.max(options.min).min(options.max))
+}
+
 pub fn slider(
     id: String,
     value: f32,
@@ -105,6 +185,16 @@ pub fn slider(
     let slider_result: Option<f32> = GLOBAL_UI_CONTEXT.with(|ctx| -> Option<f32> {
         let cache = ctx.cache.borrow();
 
+        let mut slider_drag = ctx.slider_drag.borrow_mut();
+
+        // Forget the drag value once a drag on this slider has ended.
+
+        if !interaction_result.was_active
+            && matches!(slider_drag.as_ref(), Some((key, _)) if *key == ui_key)
+        {
+            slider_drag.take();
+        }
+
         match (
             interaction_result.mouse_interaction_in_bounds.drag_event,
             cache.get(&ui_key),
@@ -118,11 +208,22 @@ pub fn slider(
 
                 let drag_alpha = local_drag_delta / extent;
 
-                let value_delta = (options.max - options.min) * drag_alpha;
+                let mode =
+                    SliderDragMode::from_modifiers(ctx.input_events.borrow().keyboard.modifiers);
+
+                // Continues from the unsnapped value carried over from earlier
+                // frames of this drag, if any.
+
+                let drag_value = match slider_drag.as_ref() {
+                    Some((key, drag_value)) if *key == ui_key => *drag_value,
+                    _ => value,
+                };
+
+                let drag_value = get_dragged_value(drag_value, drag_alpha, &options, mode);
 
-                let new_value = (value + value_delta).max(options.min).min(options.max);
+                slider_drag.replace((ui_key.clone(), drag_value));
 
-                Some(new_value)
+                Some(get_committed_value(drag_value, &options, mode))
             }
             _ => None,
         }
@@ -130,3 +231,109 @@ pub fn slider(
 
     Ok(slider_result)
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        get_committed_value, get_dragged_value, parse_slider_entry, SliderDragMode, SliderOptions,
+        SLIDER_FINE_DRAG_FACTOR,
+    };
+
+    #[test]
+    fn shift_drag_is_finer_than_normal_drag() {
+        let options = SliderOptions {
+            min: 0.0,
+            max: 10.0,
+            ..Default::default()
+        };
+
+        let normal = get_dragged_value(5.0, 0.1, &options, SliderDragMode::Normal);
+        let fine = get_dragged_value(5.0, 0.1, &options, SliderDragMode::Fine);
+
+        assert!((normal - 6.0).abs() < 1e-5);
+        assert!((fine - 5.0 - (normal - 5.0) * SLIDER_FINE_DRAG_FACTOR).abs() < 1e-5);
+    }
+
+    #[test]
+    fn snap_drag_and_typed_entry() {
+        let options = SliderOptions {
+            min: 0.0,
+            max: 10.0,
+            step: Some(0.5),
+            ..Default::default()
+        };
+
+        // A slow drag never moves a full step in a single frame, but still
+        // leaves its starting step once enough small drags accumulate.
+
+        let mut drag_value = 1.0;
+
+        let mut committed = vec![];
+
+        for _ in 0..5 {
+            drag_value = get_dragged_value(drag_value, 0.013, &options, SliderDragMode::Snap);
+
+            committed.push(get_committed_value(
+                drag_value,
+                &options,
+                SliderDragMode::Snap,
+            ));
+        }
+
+        assert_eq!(committed, vec![1.0, 1.5, 1.5, 1.5, 1.5]);
+
+        assert_eq!(
+            get_committed_value(1.4, &options, SliderDragMode::Normal),
+            1.4
+        );
+
+        assert_eq!(parse_slider_entry(" 2.25 ", &options), Some(2.25));
+        assert_eq!(parse_slider_entry("42", &options), Some(10.0));
+        assert_eq!(parse_slider_entry("abc", &options), None);
+    }
+
+    #[test]
+    fn invalid_steps_never_commit_non_finite_values() {
+        for step in [Some(f32::NAN), Some(f32::INFINITY), Some(0.0), Some(-1.0)] {
+            let options = SliderOptions {
+                min: 0.0,
+                max: 10.0,
+                step,
+                ..Default::default()
+            };
+
+            assert_eq!(
+                get_committed_value(1.4, &options, SliderDragMode::Snap),
+                1.4
+            );
+        }
+
+        // An infinite range yields an infinite default step.
+
+        let options = SliderOptions {
+            min: f32::MIN,
+            max: f32::MAX,
+            ..Default::default()
+        };
+
+        assert!(options.get_step().is_infinite());
+
+        assert_eq!(
+            get_committed_value(1.4, &options, SliderDragMode::Snap),
+            1.4
+        );
+
+        // Values outside of the range are clamped.
+
+        let options = SliderOptions::default();
+
+        assert_eq!(
+            get_committed_value(2.0, &options, SliderDragMode::Normal),
+            1.0
+        );
+        assert_eq!(
+            get_committed_value(f32::NAN, &options, SliderDragMode::Snap),
+            0.0
+        );
+    }
+}
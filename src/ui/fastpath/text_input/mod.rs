@@ -9,13 +9,44 @@ use crate::{
     },
 };
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextInputNavigation {
+    // Tab
+    Complete,
+    // Up arrow
+    Previous,
+    // Down arrow
+    Next,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct TextInputResult {
+    pub value: Option<String>,
+    pub was_submitted: bool,
+    pub navigation: Option<TextInputNavigation>,
+}
+
 pub fn text_input(
     id: String,
     value: &String,
     tree: &mut UIBoxTree,
 ) -> Result<Option<String>, String> {
+    text_input_with_submit(id, value, tree).map(|result| result.value)
+}
+
+// Like `text_input()`, but also reports when Enter, Tab, or the up or down
+// arrow keys were pressed.
+pub fn text_input_with_submit(
+    id: String,
+    value: &String,
+    tree: &mut UIBoxTree,
+) -> Result<TextInputResult, String> {
     let mut result: Option<String> = None;
 
+    let mut was_submitted = false;
+
+    let mut navigation = None;
+
     let mut text_input_box = GLOBAL_UI_CONTEXT.with(|ctx| -> Result<UIBox, String> {
         let theme = ctx.theme.borrow();
 
@@ -57,6 +88,18 @@ pub fn text_input(
 
             for keycode in pressed_keys {
                 match *keycode {
+                    Keycode::RETURN | Keycode::KP_ENTER => {
+                        was_submitted = true;
+                    }
+                    Keycode::TAB => {
+                        navigation = Some(TextInputNavigation::Complete);
+                    }
+                    Keycode::UP => {
+                        navigation = Some(TextInputNavigation::Previous);
+                    }
+                    Keycode::DOWN => {
+                        navigation = Some(TextInputNavigation::Next);
+                    }
                     Keycode::BACKSPACE | Keycode::Delete => {
                         // Remove one character from the model value, if possible.
 
@@ -86,5 +129,9 @@ pub fn text_input(
         });
     }
 
-    Ok(result)
+    Ok(TextInputResult {
+        value: result,
+        was_submitted,
+        navigation,
+    })
 }
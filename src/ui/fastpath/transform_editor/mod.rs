@@ -0,0 +1,205 @@
+use crate::{
+    transform::Transform3D,
+    ui::{
+        context::GLOBAL_UI_CONTEXT,
+        ui_box::{tree::UIBoxTree, UILayoutDirection},
+    },
+    vec::vec3::Vec3,
+};
+
+use super::{
+    container::container,
+    slider::{slider, SliderOptions},
+    spacer::spacer,
+    text::text,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TransformComponent {
+    Translation,
+    Rotation,
+    Scale,
+}
+
+impl TransformComponent {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TransformComponent::Translation => "Translation",
+            TransformComponent::Rotation => "Rotation",
+            TransformComponent::Scale => "Scale",
+        }
+    }
+
+    pub fn get_slider_options(&self) -> SliderOptions {
+        match self {
+            TransformComponent::Translation => SliderOptions {
+                min: -100.0,
+                max: 100.0,
+                ..Default::default()
+            },
+            TransformComponent::Rotation => SliderOptions {
+                min: -180.0,
+                max: 180.0,
+                decimals: 1,
+                step: Some(15.0),
+                ..Default::default()
+            },
+            TransformComponent::Scale => SliderOptions {
+                min: 0.01,
+                max: 10.0,
+                ..Default::default()
+            },
+        }
+    }
+
+    // Returns the component's value as shown in the editor (rotations are
+    // shown as Euler angles, in degrees).
+    pub fn get_value(&self, transform: &Transform3D) -> Vec3 {
+        match self {
+            TransformComponent::Translation => *transform.translation(),
+            TransformComponent::Rotation => {
+                let euler = transform.euler_rotation();
+
+                Vec3 {
+                    x: euler.x.to_degrees(),
+                    y: euler.y.to_degrees(),
+                    z: euler.z.to_degrees(),
+                }
+            }
+            TransformComponent::Scale => *transform.scale(),
+        }
+    }
+}
+
+static TRANSFORM_COMPONENTS: [TransformComponent; 3] = [
+    TransformComponent::Translation,
+    TransformComponent::Rotation,
+    TransformComponent::Scale,
+];
+
+// Writes one edited field (axis 0, 1 or 2) back to the transform, recomposing
+// the transform from its (possibly Euler) components.
+pub fn apply_transform_edit(
+    transform: &mut Transform3D,
+    component: TransformComponent,
+    axis: usize,
+    value: f32,
+) {
+    let mut components = component.get_value(transform);
+
+    match axis {
+        0 => components.x = value,
+        1 => components.y = value,
+        _ => components.z = value,
+    }
+
+    match component {
+        TransformComponent::Translation => transform.set_translation(components),
+        TransformComponent::Rotation => transform.set_euler_rotation(Vec3 {
+            x: components.x.to_radians(),
+            y: components.y.to_radians(),
+            z: components.z.to_radians(),
+        }),
+        TransformComponent::Scale => transform.set_scale(components),
+    }
+}
+
+// Edits a transform's translation, rotation and scale with number sliders;
+// returns `true` if the transform changed (i.e., its owner is now dirty).
+pub fn transform_editor(
+    id: String,
+    transform: &mut Transform3D,
+    tree: &mut UIBoxTree,
+) -> Result<bool, String> {
+    let mut did_edit = false;
+
+    let gap = GLOBAL_UI_CONTEXT.with(|ctx| ctx.theme.borrow().gap);
+
+    tree.with_parent(
+        container(
+            format!("{}_transform_editor", id),
+            UILayoutDirection::TopToBottom,
+            None,
+        ),
+        |tree| -> Result<(), String> {
+            for component in TRANSFORM_COMPONENTS.iter() {
+                let label = component.label();
+
+                tree.push(text(
+                    format!("{}_transform_editor_{}_label", id, label),
+                    label.to_string(),
+                ))?;
+
+                let value = component.get_value(transform);
+
+                let row = container(
+                    format!("{}_transform_editor_{}_row", id, label),
+                    UILayoutDirection::LeftToRight,
+                    None,
+                );
+
+                tree.with_parent(row, |tree| -> Result<(), String> {
+                    for (axis, axis_value) in [value.x, value.y, value.z].into_iter().enumerate() {
+                        if axis > 0 {
+                            tree.push(spacer(gap))?;
+                        }
+
+                        if let Some(new_value) = slider(
+                            format!("{}_transform_editor_{}_{}", id, label, axis),
+                            axis_value,
+                            component.get_slider_options(),
+                            tree,
+                        )? {
+                            apply_transform_edit(transform, *component, axis, new_value);
+
+                            did_edit = true;
+                        }
+                    }
+
+                    Ok(())
+                })?;
+            }
+
+            Ok(())
+        },
+    )?;
+
+    Ok(did_edit)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{transform::Transform3D, vec::vec3::Vec3};
+
+    use super::{apply_transform_edit, TransformComponent};
+
+    #[test]
+    fn editing_y_translation_updates_transform() {
+        let mut transform = Transform3D::default();
+
+        transform.set_translation(Vec3 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        });
+
+        apply_transform_edit(&mut transform, TransformComponent::Translation, 1, 5.0);
+
+        let translation = transform.translation();
+
+        assert_eq!(
+            (translation.x, translation.y, translation.z),
+            (1.0, 5.0, 3.0)
+        );
+
+        // Rotation fields round-trip through Euler angles (in degrees).
+
+        apply_transform_edit(&mut transform, TransformComponent::Rotation, 1, 30.0);
+
+        let rotation = TransformComponent::Rotation.get_value(&transform);
+
+        assert!(rotation.x.abs() < 1e-3);
+        assert!((rotation.y - 30.0).abs() < 1e-3);
+        assert!(rotation.z.abs() < 1e-3);
+    }
+}
@@ -0,0 +1,227 @@
+use std::ops::Range;
+
+use sdl2::mouse::MouseWheelDirection;
+
+use crate::ui::{
+    context::GLOBAL_UI_CONTEXT,
+    ui_box::{key::UIKey, tree::UIBoxTree, UILayoutDirection},
+    UISize, UISizeWithStrictness,
+};
+
+use super::container::container;
+
+#[derive(Debug, Copy, Clone)]
+pub struct VirtualListOptions {
+    pub row_height: u32,
+    pub viewport_height: u32,
+    // Extra rows to build beyond the bottom of the viewport (e.g., so that
+    // rows can prefetch their data before they scroll into view).
+    pub overscan: usize,
+}
+
+impl Default for VirtualListOptions {
+    fn default() -> Self {
+        Self {
+            row_height: 20,
+            viewport_height: 200,
+            overscan: 0,
+        }
+    }
+}
+
+impl VirtualListOptions {
+    // Counts a partially visible last row.
+    pub fn get_visible_row_count(&self) -> usize {
+        self.viewport_height.div_ceil(self.row_height.max(1)) as usize
+    }
+
+    pub fn get_fully_visible_row_count(&self) -> usize {
+        (self.viewport_height / self.row_height.max(1)) as usize
+    }
+}
+
+// Returns the range of rows to build, given the first visible row.
+pub fn get_visible_row_range(
+    item_count: usize,
+    first_row: usize,
+    options: &VirtualListOptions,
+) -> Range<usize> {
+    let start = first_row.min(item_count);
+
+    let end = (start + options.get_visible_row_count() + options.overscan).min(item_count);
+
+    start..end
+}
+
+// Builds UI boxes for only those rows that are (or are about to be) visible,
+// scrolling a whole row at a time with the mouse wheel. `render_row` is called
+// with each row's index.
+pub fn virtual_list<C>(
+    id: String,
+    item_count: usize,
+    options: VirtualListOptions,
+    tree: &mut UIBoxTree,
+    mut render_row: C,
+) -> Result<Range<usize>, String>
+where
+    C: FnMut(usize, &mut UIBoxTree) -> Result<(), String>,
+{
+    let container_id = format!("{}_virtual_list_container", id);
+
+    let ui_key = UIKey::from_string(container_id.clone());
+
+    // Lets the last row scroll fully into view.
+
+    let max_first_row = item_count.saturating_sub(options.get_fully_visible_row_count());
+
+    // Applies any mouse-wheel scrolling over the list's previous-frame bounds.
+
+    let first_row = GLOBAL_UI_CONTEXT.with(|ctx| {
+        let cache = ctx.cache.borrow();
+
+        let Some(previous_frame) = cache.get(&ui_key) else {
+            return 0;
+        };
+
+        let mut first_row = previous_frame.scroll_offset as i32;
+
+        let mouse = &ctx.input_events.borrow().mouse;
+
+        let is_hovering = previous_frame.global_bounds.contains(
+            mouse.position.0.max(0) as u32,
+            mouse.position.1.max(0) as u32,
+        );
+
+        if let (true, Some(event)) = (is_hovering, &mouse.wheel_event) {
+            first_row += match event.direction {
+                MouseWheelDirection::Flipped => event.delta,
+                _ => -event.delta,
+            };
+        }
+
+        first_row.max(0) as usize
+    });
+
+    let first_row = first_row.min(max_first_row);
+
+    let range = get_visible_row_range(item_count, first_row, &options);
+
+    let wrapper = container(
+        container_id,
+        UILayoutDirection::TopToBottom,
+        Some([
+            UISizeWithStrictness {
+                size: UISize::PercentOfParent(1.0),
+                strictness: 0.0,
+            },
+            UISizeWithStrictness {
+                size: UISize::Pixels(options.viewport_height),
+                strictness: 1.0,
+            },
+        ]),
+    );
+
+    tree.with_parent(wrapper, |tree| -> Result<(), String> {
+        for index in range.clone() {
+            let row = container(
+                format!("{}_virtual_list_row_{}", id, index),
+                UILayoutDirection::LeftToRight,
+                Some([
+                    UISizeWithStrictness {
+                        size: UISize::PercentOfParent(1.0),
+                        strictness: 0.0,
+                    },
+                    UISizeWithStrictness {
+                        size: UISize::Pixels(options.row_height),
+                        strictness: 1.0,
+                    },
+                ]),
+            );
+
+            tree.with_parent(row, |tree| render_row(index, tree))?;
+        }
+
+        Ok(())
+    })?;
+
+    if let Some(current_rc) = tree.get_current() {
+        let parent_node = current_rc.borrow_mut();
+
+        let wrapper_node_rc = &parent_node.children[parent_node.children.len() - 1];
+        let mut wrapper_node = wrapper_node_rc.borrow_mut();
+
+        wrapper_node.data.scroll_offset = first_row;
+    }
+
+    Ok(range)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ui::{
+        fastpath::{container::container, spacer::spacer},
+        ui_box::{tree::UIBoxTree, UILayoutDirection},
+    };
+
+    use super::{get_visible_row_range, virtual_list, VirtualListOptions};
+
+    #[test]
+    fn only_visible_rows_are_rendered() {
+        let options = VirtualListOptions {
+            row_height: 20,
+            viewport_height: 100,
+            overscan: 2,
+        };
+
+        let mut tree = UIBoxTree::default();
+
+        tree.push_parent(container(
+            "root".to_string(),
+            UILayoutDirection::TopToBottom,
+            None,
+        ))
+        .unwrap();
+
+        let mut rendered = vec![];
+
+        let range = virtual_list(
+            "assets".to_string(),
+            10_000,
+            options,
+            &mut tree,
+            |index, tree| {
+                rendered.push(index);
+
+                tree.push(spacer(1))?;
+
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        // 5 visible rows, plus 2 rows of overscan.
+
+        assert_eq!(range, 0..7);
+        assert_eq!(rendered, (0..7).collect::<Vec<usize>>());
+
+        // Scrolled near the end of the list.
+
+        assert_eq!(
+            get_visible_row_range(10_000, 9_997, &options),
+            9_997..10_000
+        );
+        assert_eq!(get_visible_row_range(3, 0, &options), 0..3);
+
+        // A partially visible last row is still rendered.
+
+        let options = VirtualListOptions {
+            viewport_height: 110,
+            overscan: 0,
+            ..options
+        };
+
+        assert_eq!(options.get_visible_row_count(), 6);
+        assert_eq!(options.get_fully_visible_row_count(), 5);
+        assert_eq!(get_visible_row_range(10_000, 0, &options), 0..6);
+    }
+}
@@ -3,9 +3,14 @@ use core::fmt;
 use serde::{Deserialize, Serialize};
 
 pub mod context;
+pub mod context_menu;
+pub mod drag_drop;
 pub mod extent;
 pub mod fastpath;
 pub mod panel;
+pub mod plot;
+pub mod text_edit;
+pub mod tooltip;
 pub mod ui_box;
 pub mod window;
 
@@ -9,7 +9,7 @@ use crate::resource::handle::Handle;
 use crate::color::Color;
 
 use super::{
-    fastpath::text::text,
+    fastpath::{tab_selector::tab_selector, text::text},
     ui_box::{
         interaction::UIBoxInteraction, tree::UIBoxTree, UIBox, UIBoxCustomRenderCallback,
         UIBoxFeatureFlag, UILayoutDirection,
@@ -60,6 +60,13 @@ impl fmt::Display for PanelInstanceData {
     }
 }
 
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PanelTab {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_data: Option<PanelInstanceData>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Panel {
     pub path: String,
@@ -68,6 +75,10 @@ pub struct Panel {
     pub alpha_split: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instance_data: Option<PanelInstanceData>,
+    // Tabbed leaf panels show one tab's instance data (in `instance_data`) at
+    // a time.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tabs: Vec<PanelTab>,
     // For child panels.
     pub layout_direction: UILayoutDirection,
 }
@@ -79,6 +90,7 @@ impl Default for Panel {
             resizable: true,
             alpha_split: 1.0,
             instance_data: None,
+            tabs: vec![],
             layout_direction: Default::default(),
         }
     }
@@ -101,6 +113,7 @@ impl Panel {
             resizable: true,
             alpha_split,
             instance_data,
+            tabs: vec![],
             layout_direction,
         }
     }
@@ -170,10 +183,20 @@ impl Panel {
     }
 
     pub fn render_leaf_panel_contents(
-        &self,
+        &mut self,
         ui_box_tree: &mut UIBoxTree,
         #[allow(unused)] panel_interaction_result: &UIBoxInteraction,
     ) -> Result<(), String> {
+        if !self.tabs.is_empty() {
+            let titles = self.tabs.iter().map(|tab| tab.title.as_str()).collect();
+
+            let selected_index = tab_selector(self.get_panel_ui_box_id(), titles, ui_box_tree)?;
+
+            if let Some(tab) = self.tabs.get(selected_index) {
+                self.instance_data.clone_from(&tab.instance_data);
+            }
+        }
+
         match &self.instance_data {
             Some(instance_data) => match &instance_data.render {
                 Some(render) => render(&instance_data.panel_instance, ui_box_tree),
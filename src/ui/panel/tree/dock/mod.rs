@@ -0,0 +1,392 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    collections::tree::node::Node,
+    ui::{
+        context::GLOBAL_UI_CONTEXT,
+        extent::ScreenExtent,
+        panel::PanelTab,
+        ui_box::{key::UIKey, UILayoutDirection},
+    },
+};
+
+use super::{Panel, PanelTree};
+
+// Fraction of a panel's width (or height) treated as an edge drop zone.
+pub static DOCK_ZONE_EDGE_FRACTION: f32 = 0.25;
+
+type PanelNodeRc<'a> = Rc<RefCell<Node<'a, Panel>>>;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DockZone {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Center,
+}
+
+impl DockZone {
+    pub fn from_position(bounds: &ScreenExtent, position: (u32, u32)) -> Option<Self> {
+        let (x, y) = position;
+
+        if !bounds.contains(x, y) {
+            return None;
+        }
+
+        let width = (bounds.right - bounds.left).max(1) as f32;
+        let height = (bounds.bottom - bounds.top).max(1) as f32;
+
+        let alpha_x = (x - bounds.left) as f32 / width;
+        let alpha_y = (y - bounds.top) as f32 / height;
+
+        // Pick the nearest edge, if it's within the edge zone.
+
+        let candidates = [
+            (alpha_x, DockZone::Left),
+            (1.0 - alpha_x, DockZone::Right),
+            (alpha_y, DockZone::Top),
+            (1.0 - alpha_y, DockZone::Bottom),
+        ];
+
+        let (distance, zone) = candidates
+            .into_iter()
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap();
+
+        if distance < DOCK_ZONE_EDGE_FRACTION {
+            Some(zone)
+        } else {
+            Some(DockZone::Center)
+        }
+    }
+
+    // Returns the region of `bounds` that a window dropped in this zone would
+    // occupy.
+    pub fn get_preview_extent(&self, bounds: &ScreenExtent) -> ScreenExtent {
+        let center_x = bounds.left + (bounds.right - bounds.left) / 2;
+        let center_y = bounds.top + (bounds.bottom - bounds.top) / 2;
+
+        match self {
+            DockZone::Top => ScreenExtent {
+                bottom: center_y,
+                ..*bounds
+            },
+            DockZone::Bottom => ScreenExtent {
+                top: center_y,
+                ..*bounds
+            },
+            DockZone::Left => ScreenExtent {
+                right: center_x,
+                ..*bounds
+            },
+            DockZone::Right => ScreenExtent {
+                left: center_x,
+                ..*bounds
+            },
+            DockZone::Center => *bounds,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DockTarget {
+    pub panel_path: String,
+    pub zone: DockZone,
+    pub bounds: ScreenExtent,
+}
+
+impl<'a> PanelTree<'a> {
+    // Returns the leaf panel (and drop zone) beneath `position`, based on the
+    // panels' bounds from the previous frame.
+    pub fn get_dock_target(&self, position: (u32, u32)) -> Option<DockTarget> {
+        let mut target = None;
+
+        let root = self.tree.root.as_ref()?;
+
+        visit_leaves(root, &mut |panel| {
+            if target.is_some() {
+                return;
+            }
+
+            let key = UIKey::from_string(panel.get_panel_ui_box_id());
+
+            let bounds = GLOBAL_UI_CONTEXT.with(|ctx| {
+                ctx.cache
+                    .borrow()
+                    .get(&key)
+                    .map(|ui_box| ui_box.global_bounds)
+            });
+
+            if let Some(bounds) = bounds {
+                if let Some(zone) = DockZone::from_position(&bounds, position) {
+                    target.replace(DockTarget {
+                        panel_path: panel.path.clone(),
+                        zone,
+                        bounds,
+                    });
+                }
+            }
+        });
+
+        target
+    }
+
+    // Docks a copy of `source` into the panel at `target_path`. Edge zones
+    // split the target panel in two; the center zone adds `source`'s leaf
+    // panels to the target as tabs.
+    pub fn dock(
+        &mut self,
+        target_path: &str,
+        zone: DockZone,
+        title: &str,
+        source: &PanelTree<'a>,
+    ) -> Result<(), String> {
+        let root = self
+            .tree
+            .root
+            .as_ref()
+            .ok_or("Called PanelTree::dock() on an empty tree!")?;
+
+        let target_rc = find_node(root, target_path)
+            .ok_or(format!("No panel found with path '{}'.", target_path))?;
+
+        let source_root = source
+            .tree
+            .root
+            .as_ref()
+            .ok_or("Called PanelTree::dock() with an empty source tree!")?;
+
+        if zone == DockZone::Center {
+            return dock_as_tabs(&target_rc, title, source_root);
+        }
+
+        // Move the target's current contents into a new child, beside a copy
+        // of the source tree.
+
+        let existing_rc = copy_subtree(
+            &target_rc,
+            Some(target_rc.clone()),
+            format!("{} docked_existing", target_path),
+        );
+
+        let dropped_rc = copy_subtree(
+            source_root,
+            Some(target_rc.clone()),
+            format!("{} docked_{}", target_path, get_path_id(title)),
+        );
+
+        for child_rc in [&existing_rc, &dropped_rc] {
+            let mut child = child_rc.borrow_mut();
+
+            child.data.alpha_split = 0.5;
+            child.data.resizable = true;
+        }
+
+        let mut target = target_rc.borrow_mut();
+
+        target.data.instance_data = None;
+        target.data.tabs.clear();
+
+        target.data.layout_direction = match zone {
+            DockZone::Left | DockZone::Right => UILayoutDirection::LeftToRight,
+            _ => UILayoutDirection::TopToBottom,
+        };
+
+        target.children = match zone {
+            DockZone::Left | DockZone::Top => vec![dropped_rc, existing_rc],
+            _ => vec![existing_rc, dropped_rc],
+        };
+
+        Ok(())
+    }
+}
+
+fn dock_as_tabs(
+    target_rc: &PanelNodeRc,
+    title: &str,
+    source_root: &PanelNodeRc,
+) -> Result<(), String> {
+    let mut target = target_rc.borrow_mut();
+
+    if !target.children.is_empty() {
+        return Err(format!(
+            "Can't add tabs to non-leaf panel '{}'.",
+            target.data.path
+        ));
+    }
+
+    let panel = &mut target.data;
+
+    if panel.tabs.is_empty() {
+        let existing_title = panel
+            .path
+            .split(' ')
+            .next_back()
+            .unwrap_or_default()
+            .to_string();
+
+        panel.tabs.push(PanelTab {
+            title: existing_title,
+            instance_data: panel.instance_data.clone(),
+        });
+    }
+
+    visit_leaves(source_root, &mut |leaf| {
+        panel.tabs.push(PanelTab {
+            title: title.to_string(),
+            instance_data: leaf.instance_data.clone(),
+        });
+    });
+
+    Ok(())
+}
+
+fn get_path_id(title: &str) -> String {
+    title.split_whitespace().collect::<Vec<&str>>().join("_")
+}
+
+fn visit_leaves<'a, C>(node_rc: &PanelNodeRc<'a>, visit: &mut C)
+where
+    C: FnMut(&Panel),
+{
+    let node = node_rc.borrow();
+
+    if node.children.is_empty() {
+        visit(&node.data);
+    }
+
+    for child in &node.children {
+        visit_leaves(child, visit);
+    }
+}
+
+fn find_node<'a>(node_rc: &PanelNodeRc<'a>, path: &str) -> Option<PanelNodeRc<'a>> {
+    let node = node_rc.borrow();
+
+    if node.data.path == path {
+        return Some(node_rc.clone());
+    }
+
+    node.children
+        .iter()
+        .find_map(|child| find_node(child, path))
+}
+
+// Deep-copies the subtree at `node_rc`, re-rooting panel paths at `path`.
+fn copy_subtree<'a>(
+    node_rc: &PanelNodeRc<'a>,
+    parent: Option<PanelNodeRc<'a>>,
+    path: String,
+) -> PanelNodeRc<'a> {
+    let node = node_rc.borrow();
+
+    let mut data = node.data.clone();
+
+    data.path.clone_from(&path);
+
+    let copy_rc = Rc::new(RefCell::new(Node {
+        data,
+        children: vec![],
+        parent,
+    }));
+
+    let children = node
+        .children
+        .iter()
+        .enumerate()
+        .map(|(index, child_rc)| {
+            let child_id = child_rc
+                .borrow()
+                .data
+                .path
+                .split(' ')
+                .next_back()
+                .map(|id| id.to_string())
+                .unwrap_or(index.to_string());
+
+            copy_subtree(
+                child_rc,
+                Some(copy_rc.clone()),
+                format!("{} {}", path, child_id),
+            )
+        })
+        .collect();
+
+    copy_rc.borrow_mut().children = children;
+
+    copy_rc
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ui::{
+        extent::ScreenExtent,
+        panel::{tree::PanelTree, Panel},
+        ui_box::UILayoutDirection,
+    };
+
+    use super::DockZone;
+
+    #[test]
+    fn left_zone_drop_splits_horizontally() {
+        let mut tree = PanelTree::with_root(Panel {
+            path: "Viewport".to_string(),
+            ..Default::default()
+        });
+
+        let source = PanelTree::with_root(Panel {
+            path: "Outline".to_string(),
+            ..Default::default()
+        });
+
+        let bounds = ScreenExtent::new((0, 0), (400, 300));
+
+        let zone = DockZone::from_position(&bounds, (20, 150)).unwrap();
+
+        assert_eq!(zone, DockZone::Left);
+
+        tree.dock("Viewport", zone, "Outline", &source).unwrap();
+
+        let root = tree.tree.root.as_ref().unwrap().borrow();
+
+        assert!(matches!(
+            root.data.layout_direction,
+            UILayoutDirection::LeftToRight
+        ));
+
+        assert_eq!(root.children.len(), 2);
+
+        let left = &root.children[0].borrow().data;
+        let right = &root.children[1].borrow().data;
+
+        assert_eq!(left.path, "Viewport docked_Outline");
+        assert_eq!(right.path, "Viewport docked_existing");
+        assert_eq!(left.alpha_split, 0.5);
+    }
+
+    #[test]
+    fn center_zone_drop_adds_tabs() {
+        let mut tree = PanelTree::with_root(Panel {
+            path: "Root Properties".to_string(),
+            ..Default::default()
+        });
+
+        let source = PanelTree::with_root(Panel::default());
+
+        let bounds = ScreenExtent::new((0, 0), (400, 300));
+
+        let zone = DockZone::from_position(&bounds, (200, 150)).unwrap();
+
+        assert_eq!(zone, DockZone::Center);
+
+        tree.dock("Root Properties", zone, "Materials", &source)
+            .unwrap();
+
+        let root = tree.tree.root.as_ref().unwrap().borrow();
+
+        let titles: Vec<&str> = root.data.tabs.iter().map(|t| t.title.as_str()).collect();
+
+        assert_eq!(titles, vec!["Properties", "Materials"]);
+    }
+}
@@ -15,6 +15,8 @@ use crate::{
 
 use super::Panel;
 
+pub mod dock;
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct PanelTree<'a> {
     #[serde(flatten)]
@@ -135,23 +137,21 @@ impl<'a> PanelTree<'a> {
                                         cache.get(&ui_box_node.data.key).unwrap();
 
                                     let prev_frame_parent_size = match drag_handle {
-                                        UIBoxDragHandle::Left | UIBoxDragHandle::Right => {
-                                            prev_frame_parent.global_bounds.right
-                                                - prev_frame_parent.global_bounds.left
-                                        }
                                         UIBoxDragHandle::Top | UIBoxDragHandle::Bottom => {
                                             prev_frame_parent.global_bounds.bottom
                                                 - prev_frame_parent.global_bounds.top
                                         }
+                                        _ => {
+                                            prev_frame_parent.global_bounds.right
+                                                - prev_frame_parent.global_bounds.left
+                                        }
                                     };
 
                                     let drag_pixels = match drag_handle {
-                                        UIBoxDragHandle::Left | UIBoxDragHandle::Right => {
-                                            pixel_delta.0 as f32
-                                        }
                                         UIBoxDragHandle::Top | UIBoxDragHandle::Bottom => {
                                             pixel_delta.1 as f32
                                         }
+                                        _ => pixel_delta.0 as f32,
                                     };
 
                                     let drag_alpha = drag_pixels / prev_frame_parent_size as f32;
@@ -0,0 +1,184 @@
+use crate::{
+    buffer::Buffer2D,
+    color::{self, Color},
+    graphics::Graphics,
+    vec::vec2::Vec2,
+};
+
+use super::extent::ScreenExtent;
+
+#[derive(Default, Debug, Copy, Clone)]
+pub enum PlotRange {
+    #[default]
+    Auto,
+    Fixed {
+        min: f32,
+        max: f32,
+    },
+}
+
+impl PlotRange {
+    pub fn resolve(&self, values: &[f32]) -> (f32, f32) {
+        let (min, max) = match self {
+            PlotRange::Fixed { min, max } => (*min, *max),
+            PlotRange::Auto => {
+                if values.is_empty() {
+                    return (0.0, 1.0);
+                }
+
+                values.iter().fold((f32::MAX, f32::MIN), |(min, max), v| {
+                    (min.min(*v), max.max(*v))
+                })
+            }
+        };
+
+        // Avoids a zero-height range (e.g., when all values are equal).
+
+        if max - min > f32::EPSILON {
+            (min, max)
+        } else {
+            (min - 0.5, min + 0.5)
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct LinePlotOptions {
+    pub range: PlotRange,
+    pub grid_rows: u32,
+    pub grid_columns: u32,
+    pub line_color: Color,
+    pub axis_color: Color,
+    pub grid_color: Color,
+}
+
+impl Default for LinePlotOptions {
+    fn default() -> Self {
+        Self {
+            range: Default::default(),
+            grid_rows: 4,
+            grid_columns: 8,
+            line_color: color::GREEN,
+            axis_color: color::LIGHT_GRAY,
+            grid_color: color::DARK_GRAY,
+        }
+    }
+}
+
+// Maps each value to a point inside of the plot rect; values are spread evenly
+// from left to right, with `min` at the bottom edge and `max` at the top edge.
+pub fn get_plot_points(values: &[f32], range: (f32, f32), extent: &ScreenExtent) -> Vec<Vec2> {
+    let (min, max) = range;
+
+    let width = (extent.right - extent.left) as f32;
+    let height = (extent.bottom - extent.top) as f32;
+
+    let step = if values.len() > 1 {
+        width / (values.len() - 1) as f32
+    } else {
+        0.0
+    };
+
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let alpha = ((value - min) / (max - min)).clamp(0.0, 1.0);
+
+            Vec2 {
+                x: extent.left as f32 + step * index as f32,
+                y: extent.bottom as f32 - height * alpha,
+                z: 0.0,
+            }
+        })
+        .collect()
+}
+
+pub fn draw_line_plot(
+    target: &mut Buffer2D,
+    extent: &ScreenExtent,
+    values: &[f32],
+    options: &LinePlotOptions,
+) {
+    let (left, right, top, bottom) = (
+        extent.left as i32,
+        extent.right as i32,
+        extent.top as i32,
+        extent.bottom as i32,
+    );
+
+    // Draw the grid.
+
+    let grid_color = options.grid_color.to_u32();
+
+    for row in 1..options.grid_rows {
+        let y = top + (bottom - top) * row as i32 / options.grid_rows as i32;
+
+        Graphics::line(target, left, y, right, y, grid_color);
+    }
+
+    for column in 1..options.grid_columns {
+        let x = left + (right - left) * column as i32 / options.grid_columns as i32;
+
+        Graphics::line(target, x, top, x, bottom, grid_color);
+    }
+
+    // Draw the axes.
+
+    let axis_color = options.axis_color.to_u32();
+
+    Graphics::line(target, left, top, left, bottom, axis_color);
+    Graphics::line(target, left, bottom, right, bottom, axis_color);
+
+    // Draw the data.
+
+    let range = options.range.resolve(values);
+
+    let points = get_plot_points(values, range, extent);
+
+    if points.len() > 1 {
+        Graphics::poly_line(target, &points, false, options.line_color.to_u32());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ui::extent::ScreenExtent;
+
+    use super::{get_plot_points, PlotRange};
+
+    #[test]
+    fn data_points_map_into_plot_rect() {
+        let extent = ScreenExtent::new((10, 20), (100, 50));
+
+        let values = [0.0, 5.0, 10.0, 20.0];
+
+        let range = PlotRange::Fixed {
+            min: 0.0,
+            max: 10.0,
+        }
+        .resolve(&values);
+
+        let points: Vec<(f32, f32)> = get_plot_points(&values, range, &extent)
+            .iter()
+            .map(|p| (p.x, p.y))
+            .collect();
+
+        // Values outside of a fixed range are clamped to the plot rect.
+
+        assert_eq!(
+            points,
+            vec![
+                (10.0, 70.0),
+                (10.0 + 100.0 / 3.0, 45.0),
+                (10.0 + 200.0 / 3.0, 20.0),
+                (110.0, 20.0)
+            ]
+        );
+
+        // An automatic range spans the data.
+
+        assert_eq!(PlotRange::Auto.resolve(&values), (0.0, 20.0));
+        assert_eq!(PlotRange::Auto.resolve(&[3.0, 3.0]), (2.5, 3.5));
+    }
+}
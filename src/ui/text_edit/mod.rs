@@ -0,0 +1,747 @@
+use std::fmt;
+
+use sdl2::{
+    clipboard::ClipboardUtil,
+    keyboard::{Keycode, Mod},
+};
+
+use crate::device::keyboard::{keycode::to_ascii_char, KeyboardState};
+
+pub static TEXT_MASK_CHAR: char = '\u{2022}';
+
+pub trait Clipboard: fmt::Debug {
+    fn get_text(&self) -> Option<String>;
+    fn set_text(&mut self, text: &str) -> Result<(), String>;
+}
+
+// A process-local clipboard, for use when no system clipboard is available.
+#[derive(Default, Debug, Clone)]
+pub struct InMemoryClipboard {
+    text: Option<String>,
+}
+
+impl Clipboard for InMemoryClipboard {
+    fn get_text(&self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), String> {
+        self.text.replace(text.to_string());
+
+        Ok(())
+    }
+}
+
+pub struct SystemClipboard {
+    util: ClipboardUtil,
+}
+
+impl fmt::Debug for SystemClipboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SystemClipboard")
+            .field("util", &"Unknown")
+            .finish()
+    }
+}
+
+impl SystemClipboard {
+    pub fn new(util: ClipboardUtil) -> Self {
+        Self { util }
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn get_text(&self) -> Option<String> {
+        if !self.util.has_clipboard_text() {
+            return None;
+        }
+
+        self.util.clipboard_text().ok()
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), String> {
+        self.util
+            .set_clipboard_text(text)
+            .map_err(|err| format!("Failed to set clipboard text: {}", err))
+    }
+}
+
+// Caret and selection state for an editable string. Positions are measured in
+// characters (not bytes).
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct TextEditState {
+    pub cursor: usize,
+    // The fixed end of the current selection, if any; the other end is `cursor`.
+    pub anchor: Option<usize>,
+}
+
+impl TextEditState {
+    pub fn at_end(text: &str) -> Self {
+        Self {
+            cursor: text.chars().count(),
+            anchor: None,
+        }
+    }
+
+    pub fn has_selection(&self) -> bool {
+        matches!(self.anchor, Some(anchor) if anchor != self.cursor)
+    }
+
+    // Returns the selected character range, ordered as (start, end).
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        match self.anchor {
+            Some(anchor) if anchor != self.cursor => {
+                Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn selected_text(&self, text: &str) -> Option<String> {
+        self.selection()
+            .map(|(start, end)| text.chars().skip(start).take(end - start).collect())
+    }
+
+    pub fn clamp(&mut self, text: &str) {
+        let len = text.chars().count();
+
+        self.cursor = self.cursor.min(len);
+
+        if let Some(anchor) = self.anchor.as_mut() {
+            *anchor = (*anchor).min(len);
+        }
+    }
+
+    pub fn select_all(&mut self, text: &str) {
+        self.anchor.replace(0);
+
+        self.cursor = text.chars().count();
+    }
+
+    // Moves the caret to `position`, either extending the selection or
+    // collapsing it.
+    pub fn move_to(&mut self, position: usize, select: bool) {
+        if select {
+            if self.anchor.is_none() {
+                self.anchor.replace(self.cursor);
+            }
+        } else {
+            self.anchor.take();
+        }
+
+        self.cursor = position;
+    }
+
+    pub fn move_left(&mut self, select: bool) {
+        match (self.selection(), select) {
+            (Some((start, _)), false) => self.move_to(start, false),
+            _ => self.move_to(self.cursor.saturating_sub(1), select),
+        }
+    }
+
+    pub fn move_right(&mut self, text: &str, select: bool) {
+        match (self.selection(), select) {
+            (Some((_, end)), false) => self.move_to(end, false),
+            _ => self.move_to((self.cursor + 1).min(text.chars().count()), select),
+        }
+    }
+
+    pub fn move_home(&mut self, text: &str, select: bool) {
+        self.move_to(get_line_start(text, self.cursor), select);
+    }
+
+    pub fn move_end(&mut self, text: &str, select: bool) {
+        self.move_to(get_line_end(text, self.cursor), select);
+    }
+
+    // Moves the caret to the same column on the previous line, if any.
+    pub fn move_up(&mut self, text: &str, select: bool) {
+        let line_start = get_line_start(text, self.cursor);
+
+        if line_start == 0 {
+            self.move_to(0, select);
+
+            return;
+        }
+
+        let column = self.cursor - line_start;
+
+        let previous_line_start = get_line_start(text, line_start - 1);
+
+        let previous_line_end = line_start - 1;
+
+        self.move_to(
+            (previous_line_start + column).min(previous_line_end),
+            select,
+        );
+    }
+
+    // Moves the caret to the same column on the next line, if any.
+    pub fn move_down(&mut self, text: &str, select: bool) {
+        let line_end = get_line_end(text, self.cursor);
+
+        let len = text.chars().count();
+
+        if line_end == len {
+            self.move_to(len, select);
+
+            return;
+        }
+
+        let column = self.cursor - get_line_start(text, self.cursor);
+
+        let next_line_start = line_end + 1;
+
+        let next_line_end = get_line_end(text, next_line_start);
+
+        self.move_to((next_line_start + column).min(next_line_end), select);
+    }
+
+    // Replaces the current selection (if any) with `insertion`.
+    pub fn insert(&mut self, text: &mut String, insertion: &str) {
+        self.delete_selection(text);
+
+        let offset = get_byte_offset(text, self.cursor);
+
+        text.insert_str(offset, insertion);
+
+        self.cursor += insertion.chars().count();
+    }
+
+    pub fn delete_selection(&mut self, text: &mut String) -> bool {
+        match self.selection() {
+            Some((start, end)) => {
+                let (start_offset, end_offset) =
+                    (get_byte_offset(text, start), get_byte_offset(text, end));
+
+                text.replace_range(start_offset..end_offset, "");
+
+                self.cursor = start;
+
+                self.anchor.take();
+
+                true
+            }
+            None => {
+                self.anchor.take();
+
+                false
+            }
+        }
+    }
+
+    pub fn backspace(&mut self, text: &mut String, whole_word: bool) -> bool {
+        if self.delete_selection(text) {
+            return true;
+        }
+
+        if self.cursor == 0 {
+            return false;
+        }
+
+        let start = if whole_word {
+            get_previous_word_start(text, self.cursor)
+        } else {
+            self.cursor - 1
+        };
+
+        self.anchor.replace(start);
+
+        self.delete_selection(text)
+    }
+
+    pub fn delete(&mut self, text: &mut String) -> bool {
+        if self.delete_selection(text) {
+            return true;
+        }
+
+        if self.cursor >= text.chars().count() {
+            return false;
+        }
+
+        self.anchor.replace(self.cursor + 1);
+
+        self.delete_selection(text)
+    }
+
+    // Returns true if the selection was written to the clipboard.
+    pub fn copy(&self, text: &str, clipboard: &mut dyn Clipboard) -> bool {
+        match self.selected_text(text) {
+            Some(selected) => clipboard.set_text(&selected).is_ok(),
+            None => false,
+        }
+    }
+
+    // Leaves the selection in place if it couldn't be copied.
+    pub fn cut(&mut self, text: &mut String, clipboard: &mut dyn Clipboard) -> bool {
+        self.copy(text, clipboard) && self.delete_selection(text)
+    }
+
+    pub fn paste(&mut self, text: &mut String, clipboard: &dyn Clipboard) -> bool {
+        match clipboard.get_text() {
+            Some(pasted) if !pasted.is_empty() => {
+                self.insert(text, &pasted);
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Applies this frame's keyboard input to `text`; returns true if `text`
+    // was modified.
+    pub fn handle_keyboard(
+        &mut self,
+        text: &mut String,
+        keyboard_state: &KeyboardState,
+        clipboard: &mut dyn Clipboard,
+    ) -> bool {
+        self.handle_keyboard_impl(text, keyboard_state, clipboard, false, false)
+    }
+
+    // Like `handle_keyboard()`, for a masked (e.g., password) field: copy and
+    // cut do nothing, so that the text never reaches the clipboard.
+    pub fn handle_keyboard_masked(
+        &mut self,
+        text: &mut String,
+        keyboard_state: &KeyboardState,
+        clipboard: &mut dyn Clipboard,
+    ) -> bool {
+        self.handle_keyboard_impl(text, keyboard_state, clipboard, false, true)
+    }
+
+    // Like `handle_keyboard()`, but Enter inserts a newline, and Up/Down move
+    // the caret between lines.
+    pub fn handle_keyboard_multiline(
+        &mut self,
+        text: &mut String,
+        keyboard_state: &KeyboardState,
+        clipboard: &mut dyn Clipboard,
+    ) -> bool {
+        self.handle_keyboard_impl(text, keyboard_state, clipboard, true, false)
+    }
+
+    fn handle_keyboard_impl(
+        &mut self,
+        text: &mut String,
+        keyboard_state: &KeyboardState,
+        clipboard: &mut dyn Clipboard,
+        multiline: bool,
+        masked: bool,
+    ) -> bool {
+        self.clamp(text);
+
+        let modifiers = keyboard_state.modifiers;
+
+        let shift = modifiers.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+
+        let command =
+            modifiers.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD | Mod::LGUIMOD | Mod::RGUIMOD);
+
+        let mut did_edit = false;
+
+        for keycode in &keyboard_state.newly_pressed_keycodes {
+            match (*keycode, command) {
+                (Keycode::LEFT, _) => self.move_left(shift),
+                (Keycode::RIGHT, _) => self.move_right(text, shift),
+                (Keycode::HOME, _) => self.move_home(text, shift),
+                (Keycode::END, _) => self.move_end(text, shift),
+                (Keycode::UP, _) if multiline => self.move_up(text, shift),
+                (Keycode::DOWN, _) if multiline => self.move_down(text, shift),
+                (Keycode::RETURN | Keycode::KP_ENTER, _) if multiline => {
+                    self.insert(text, "\n");
+
+                    did_edit = true;
+                }
+                (Keycode::BACKSPACE, _) => did_edit |= self.backspace(text, command),
+                (Keycode::DELETE, _) => did_edit |= self.delete(text),
+                (Keycode::A, true) => self.select_all(text),
+                (Keycode::C, true) if !masked => {
+                    self.copy(text, clipboard);
+                }
+                (Keycode::X, true) if !masked => did_edit |= self.cut(text, clipboard),
+                (Keycode::V, true) => did_edit |= self.paste(text, clipboard),
+                (_, false) => {
+                    if let Some(c) = to_ascii_char(keycode, &keyboard_state.pressed_keycodes) {
+                        self.insert(text, &c.to_string());
+
+                        did_edit = true;
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        did_edit
+    }
+}
+
+// Returns the string to display for `text`; masked text shows one bullet per
+// character, so caret and selection indices carry over unchanged.
+pub fn get_display_text(text: &str, masked: bool) -> String {
+    if masked {
+        text.chars().map(|_| TEXT_MASK_CHAR).collect()
+    } else {
+        text.to_string()
+    }
+}
+
+// A visual line of wrapped text, as a range of character indices (excluding
+// any trailing newline).
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct TextLine {
+    pub start: usize,
+    pub end: usize,
+}
+
+// Breaks `text` into visual lines at newlines and, where possible, at word
+// boundaries so that no line's measured width exceeds `max_width`. Words wider
+// than `max_width` are left on a line of their own.
+pub fn wrap_lines<F>(text: &str, max_width: u32, measure: F) -> Vec<TextLine>
+where
+    F: Fn(&str) -> u32,
+{
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut lines = vec![];
+
+    let mut hard_line_start = 0;
+
+    for hard_line_end in (0..=chars.len()).filter(|i| *i == chars.len() || chars[*i] == '\n') {
+        let mut line_start = hard_line_start;
+
+        // The most recent position at which we could break the current line.
+
+        let mut last_break: Option<usize> = None;
+
+        let mut position = hard_line_start;
+
+        while position < hard_line_end {
+            if chars[position] == ' ' {
+                last_break.replace(position + 1);
+            }
+
+            let candidate: String = chars[line_start..=position].iter().collect();
+
+            if chars[position] != ' ' && measure(&candidate) > max_width {
+                if let Some(break_at) = last_break.take() {
+                    lines.push(TextLine {
+                        start: line_start,
+                        end: break_at,
+                    });
+
+                    line_start = break_at;
+                }
+            }
+
+            position += 1;
+        }
+
+        lines.push(TextLine {
+            start: line_start,
+            end: hard_line_end,
+        });
+
+        hard_line_start = hard_line_end + 1;
+    }
+
+    lines
+}
+
+// Returns the index of the visual line containing the caret.
+pub fn get_line_index(lines: &[TextLine], cursor: usize) -> usize {
+    lines
+        .iter()
+        .rposition(|line| line.start <= cursor)
+        .unwrap_or(0)
+}
+
+pub fn get_byte_offset(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map(|(offset, _)| offset)
+        .unwrap_or(text.len())
+}
+
+pub fn get_line_start(text: &str, cursor: usize) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut position = cursor.min(chars.len());
+
+    while position > 0 && chars[position - 1] != '\n' {
+        position -= 1;
+    }
+
+    position
+}
+
+pub fn get_line_end(text: &str, cursor: usize) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut position = cursor.min(chars.len());
+
+    while position < chars.len() && chars[position] != '\n' {
+        position += 1;
+    }
+
+    position
+}
+
+fn get_previous_word_start(text: &str, cursor: usize) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut position = cursor.min(chars.len());
+
+    // Skip any whitespace immediately before the cursor, then the word itself.
+
+    while position > 0 && chars[position - 1].is_whitespace() {
+        position -= 1;
+    }
+
+    while position > 0 && !chars[position - 1].is_whitespace() {
+        position -= 1;
+    }
+
+    position
+}
+
+#[cfg(test)]
+mod test {
+    use sdl2::keyboard::{Keycode, Mod};
+
+    use crate::device::keyboard::KeyboardState;
+
+    use super::{
+        get_display_text, get_line_index, wrap_lines, Clipboard, InMemoryClipboard, TextEditState,
+        TEXT_MASK_CHAR,
+    };
+
+    fn press(keycode: Keycode, modifiers: Mod) -> KeyboardState {
+        let mut keyboard_state = KeyboardState {
+            modifiers,
+            ..Default::default()
+        };
+
+        keyboard_state.pressed_keycodes.insert(keycode);
+        keyboard_state.newly_pressed_keycodes.insert(keycode);
+
+        keyboard_state
+    }
+
+    #[test]
+    fn clipboard_text_round_trips() {
+        let mut clipboard: Box<dyn Clipboard> = Box::<InMemoryClipboard>::default();
+
+        assert_eq!(clipboard.get_text(), None);
+
+        let text = "hello world".to_string();
+
+        let state = TextEditState {
+            cursor: 11,
+            anchor: Some(6),
+        };
+
+        assert!(state.copy(&text, clipboard.as_mut()));
+
+        assert_eq!(clipboard.get_text().as_deref(), Some("world"));
+
+        clipboard.set_text("scene.json").unwrap();
+
+        assert_eq!(clipboard.get_text().as_deref(), Some("scene.json"));
+    }
+
+    #[test]
+    fn cut_keeps_the_selection_when_the_clipboard_rejects_it() {
+        #[derive(Debug)]
+        struct UnavailableClipboard;
+
+        impl Clipboard for UnavailableClipboard {
+            fn get_text(&self) -> Option<String> {
+                None
+            }
+
+            fn set_text(&mut self, _text: &str) -> Result<(), String> {
+                Err("No clipboard available.".to_string())
+            }
+        }
+
+        let mut text = "hello world".to_string();
+
+        let mut state = TextEditState {
+            cursor: 11,
+            anchor: Some(6),
+        };
+
+        assert!(!state.cut(&mut text, &mut UnavailableClipboard));
+
+        assert_eq!(text, "hello world");
+        assert_eq!(state.selection(), Some((6, 11)));
+    }
+
+    #[test]
+    fn paste_inserts_at_cursor() {
+        let mut text = "hello world".to_string();
+
+        let mut state = TextEditState {
+            cursor: 5,
+            anchor: None,
+        };
+
+        let mut clipboard = InMemoryClipboard::default();
+
+        clipboard.set_text(", big").unwrap();
+
+        let did_edit =
+            state.handle_keyboard(&mut text, &press(Keycode::V, Mod::LCTRLMOD), &mut clipboard);
+
+        assert!(did_edit);
+        assert_eq!(text, "hello, big world");
+        assert_eq!(state.cursor, 10);
+    }
+
+    #[test]
+    fn shift_home_selects_to_line_start() {
+        let mut text = "first line\nsecond line".to_string();
+
+        let mut state = TextEditState::at_end(&text);
+
+        let mut clipboard = InMemoryClipboard::default();
+
+        state.handle_keyboard(
+            &mut text,
+            &press(Keycode::HOME, Mod::LSHIFTMOD),
+            &mut clipboard,
+        );
+
+        assert_eq!(state.selected_text(&text).unwrap(), "second line");
+
+        // Copy, then cut the selection.
+
+        state.handle_keyboard(&mut text, &press(Keycode::X, Mod::LCTRLMOD), &mut clipboard);
+
+        assert_eq!(text, "first line\n");
+        assert_eq!(clipboard.get_text().unwrap(), "second line");
+    }
+
+    #[test]
+    fn masked_display_text_hides_model_value() {
+        let mut text = String::new();
+
+        let mut state = TextEditState::default();
+
+        let mut clipboard = InMemoryClipboard::default();
+
+        for keycode in [Keycode::P, Keycode::A, Keycode::S, Keycode::S] {
+            state.handle_keyboard(&mut text, &press(keycode, Mod::NOMOD), &mut clipboard);
+        }
+
+        let display_text = get_display_text(&text, true);
+
+        assert_eq!(text, "pass");
+        assert_eq!(display_text.chars().count(), 4);
+        assert!(display_text.chars().all(|c| c == TEXT_MASK_CHAR));
+
+        // Selections index the masked string the same way as the model.
+
+        state.move_home(&text, true);
+
+        assert_eq!(state.selection(), Some((0, 4)));
+        assert_eq!(
+            state.selected_text(&display_text).unwrap(),
+            display_text.as_str()
+        );
+    }
+
+    #[test]
+    fn masked_copy_and_cut_leave_clipboard_and_text_unchanged() {
+        let mut text = "hunter2".to_string();
+
+        let mut state = TextEditState::default();
+
+        state.select_all(&text);
+
+        let mut clipboard = InMemoryClipboard::default();
+
+        for keycode in [Keycode::C, Keycode::X] {
+            let did_edit = state.handle_keyboard_masked(
+                &mut text,
+                &press(keycode, Mod::LCTRLMOD),
+                &mut clipboard,
+            );
+
+            assert!(!did_edit);
+        }
+
+        assert_eq!(clipboard.get_text(), None);
+        assert_eq!(text, "hunter2");
+        assert_eq!(state.selection(), Some((0, 7)));
+    }
+
+    #[test]
+    fn enter_inserts_newline_and_moves_caret_down() {
+        let mut text = "first".to_string();
+
+        let mut state = TextEditState::at_end(&text);
+
+        let mut clipboard = InMemoryClipboard::default();
+
+        let did_edit = state.handle_keyboard_multiline(
+            &mut text,
+            &press(Keycode::RETURN, Mod::NOMOD),
+            &mut clipboard,
+        );
+
+        assert!(did_edit);
+        assert_eq!(text, "first\n");
+        assert_eq!(state.cursor, 6);
+
+        let lines = wrap_lines(&text, 100, |s| s.chars().count() as u32);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(get_line_index(&lines, state.cursor), 1);
+
+        // Single-line editing ignores Enter.
+
+        let mut single = "first".to_string();
+
+        assert!(!TextEditState::at_end(&single).handle_keyboard(
+            &mut single,
+            &press(Keycode::RETURN, Mod::NOMOD),
+            &mut clipboard,
+        ));
+    }
+
+    #[test]
+    fn wrap_lines_breaks_at_words() {
+        let text = "the quick brown fox";
+
+        let lines = wrap_lines(text, 10, |s| s.chars().count() as u32);
+
+        let wrapped: Vec<String> = lines
+            .iter()
+            .map(|line| {
+                text.chars()
+                    .skip(line.start)
+                    .take(line.end - line.start)
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(wrapped, vec!["the quick ", "brown fox"]);
+    }
+
+    #[test]
+    fn ctrl_backspace_deletes_word() {
+        let mut text = "one two  three".to_string();
+
+        let mut state = TextEditState::at_end(&text);
+
+        state.backspace(&mut text, true);
+
+        assert_eq!(text, "one two  ");
+
+        state.backspace(&mut text, true);
+
+        assert_eq!(text, "one ");
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{
+    buffer::Buffer2D,
+    graphics::{
+        text::{cache::cache_text, TextOperation},
+        Graphics,
+    },
+};
+
+use super::context::GLOBAL_UI_CONTEXT;
+
+pub static TOOLTIP_DELAY_SECONDS: f32 = 0.6;
+
+static TOOLTIP_PADDING: u32 = 4;
+
+static TOOLTIP_CURSOR_OFFSET: u32 = 16;
+
+// Tracks how long the current hover target has been hovered, so that its
+// tooltip can be shown after a delay. Widgets call `request()` while they're
+// hovered; `update()` runs once per frame after all widgets.
+#[derive(Debug, Clone)]
+pub struct TooltipState<K> {
+    pub delay_seconds: f32,
+    requested: Option<(K, String)>,
+    target: Option<(K, String)>,
+    hover_started_at: f32,
+    uptime_seconds: f32,
+}
+
+impl<K> Default for TooltipState<K> {
+    fn default() -> Self {
+        Self {
+            delay_seconds: TOOLTIP_DELAY_SECONDS,
+            requested: None,
+            target: None,
+            hover_started_at: 0.0,
+            uptime_seconds: 0.0,
+        }
+    }
+}
+
+impl<K: PartialEq> TooltipState<K> {
+    pub fn request(&mut self, id: K, text: &str) {
+        self.requested.replace((id, text.to_string()));
+    }
+
+    pub fn update(&mut self, uptime_seconds: f32) {
+        let requested = self.requested.take();
+
+        let is_same_target = match (&self.target, &requested) {
+            (Some((current, _)), Some((next, _))) => current == next,
+            _ => false,
+        };
+
+        if !is_same_target {
+            self.hover_started_at = uptime_seconds;
+        }
+
+        self.target = requested;
+
+        self.uptime_seconds = uptime_seconds;
+    }
+
+    // Returns the tooltip text to display, if the hover delay has elapsed.
+    pub fn get_visible(&self) -> Option<(&K, &str)> {
+        match &self.target {
+            Some((id, text))
+                if self.uptime_seconds - self.hover_started_at >= self.delay_seconds =>
+            {
+                Some((id, text.as_str()))
+            }
+            _ => None,
+        }
+    }
+}
+
+// Draws the visible tooltip (if any) below and to the right of the mouse
+// cursor, keeping it inside of the framebuffer.
+pub fn render_tooltip(framebuffer: &mut Buffer2D) -> Result<(), String> {
+    GLOBAL_UI_CONTEXT.with(|ctx| {
+        let tooltip = ctx.tooltip.borrow();
+
+        let Some((_key, text)) = tooltip.get_visible() else {
+            return Ok(());
+        };
+
+        let font_info = ctx.font_info.borrow();
+        let mut text_cache = ctx.text_cache.borrow_mut();
+        let mut font_cache_rc = ctx.font_cache.borrow_mut();
+
+        let Some(font_cache) = font_cache_rc.as_mut() else {
+            return Ok(());
+        };
+
+        let (text_width, text_height) = cache_text(font_cache, &mut text_cache, &font_info, text);
+
+        let (width, height) = (
+            text_width + TOOLTIP_PADDING * 2,
+            text_height + TOOLTIP_PADDING * 2,
+        );
+
+        let mouse_position = ctx.input_events.borrow().mouse.position;
+
+        let x = (mouse_position.0.max(0) as u32 + TOOLTIP_CURSOR_OFFSET)
+            .min(framebuffer.width.saturating_sub(width));
+
+        let y = (mouse_position.1.max(0) as u32 + TOOLTIP_CURSOR_OFFSET)
+            .min(framebuffer.height.saturating_sub(height));
+
+        let theme = ctx.theme.borrow();
+
+        Graphics::rectangle(
+            framebuffer,
+            x,
+            y,
+            width,
+            height,
+            Some(theme.dropdown_background.to_u32()),
+            Some(theme.panel_border.to_u32()),
+        );
+
+        Graphics::text(
+            framebuffer,
+            font_cache,
+            Some(&mut text_cache),
+            &font_info,
+            &TextOperation {
+                text: &text.to_string(),
+                x: x + TOOLTIP_PADDING,
+                y: y + TOOLTIP_PADDING,
+                color: theme.text,
+            },
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::Buffer2D,
+        ui::{
+            context::GLOBAL_UI_CONTEXT,
+            fastpath::container::container,
+            ui_box::{key::UIKey, tree::UIBoxTree, UIBox, UIBoxFeatureFlag, UILayoutDirection},
+            UISize, UISizeWithStrictness,
+        },
+    };
+
+    use super::{TooltipState, TOOLTIP_DELAY_SECONDS};
+
+    #[test]
+    fn tooltip_appears_after_hover_delay() {
+        let mut state = TooltipState::<u32>::default();
+
+        let mut uptime = 10.0;
+
+        state.request(7, "Resets the camera");
+        state.update(uptime);
+
+        assert!(state.get_visible().is_none());
+
+        // Keep hovering the same widget until the delay elapses.
+
+        while uptime < 10.0 + TOOLTIP_DELAY_SECONDS {
+            uptime += 1.0 / 60.0;
+
+            state.request(7, "Resets the camera");
+            state.update(uptime);
+        }
+
+        assert_eq!(state.get_visible(), Some((&7, "Resets the camera")));
+
+        // Hovering a different widget restarts the delay.
+
+        state.request(8, "Other");
+        state.update(uptime + 0.01);
+
+        assert!(state.get_visible().is_none());
+
+        // Leaving all widgets hides the tooltip.
+
+        state.update(uptime + 5.0);
+
+        assert!(state.get_visible().is_none());
+    }
+
+    // Builds, lays out and renders one frame with a single hoverable box, then
+    // updates the context's tooltip.
+    fn build_frame(frame_index: u32, uptime_seconds: f32) {
+        let sizes = |width: u32, height: u32| {
+            [
+                UISizeWithStrictness {
+                    size: UISize::Pixels(width),
+                    strictness: 1.0,
+                },
+                UISizeWithStrictness {
+                    size: UISize::Pixels(height),
+                    strictness: 1.0,
+                },
+            ]
+        };
+
+        let mut tree = UIBoxTree::default();
+
+        tree.push_parent(container(
+            "root".to_string(),
+            UILayoutDirection::LeftToRight,
+            Some(sizes(100, 100)),
+        ))
+        .unwrap();
+
+        let mut reset_button = UIBox::new(
+            "reset_camera".to_string(),
+            UIBoxFeatureFlag::DrawFill | UIBoxFeatureFlag::Hoverable,
+            UILayoutDirection::LeftToRight,
+            sizes(80, 20),
+            None,
+        );
+
+        reset_button.tooltip = Some("Resets the camera".to_string());
+
+        tree.push(reset_button).unwrap();
+
+        tree.commit_frame().unwrap();
+
+        tree.render_frame(frame_index, &mut Buffer2D::new(100, 100, None))
+            .unwrap();
+
+        GLOBAL_UI_CONTEXT.with(|ctx| ctx.tooltip.borrow_mut().update(uptime_seconds));
+    }
+
+    #[test]
+    fn hovered_widget_shows_its_tooltip_after_the_delay() {
+        GLOBAL_UI_CONTEXT.with(|ctx| ctx.input_events.borrow_mut().mouse.position = (10, 10));
+
+        let key = UIKey::from_string("reset_camera".to_string());
+
+        let mut uptime = 0.0;
+
+        for frame_index in 0..3 {
+            build_frame(frame_index, uptime);
+
+            GLOBAL_UI_CONTEXT.with(|ctx| assert!(ctx.tooltip.borrow().get_visible().is_none()));
+
+            uptime += 1.0 / 60.0;
+        }
+
+        build_frame(3, uptime + TOOLTIP_DELAY_SECONDS);
+
+        GLOBAL_UI_CONTEXT.with(|ctx| {
+            assert_eq!(
+                ctx.tooltip.borrow().get_visible(),
+                Some((&key, "Resets the camera"))
+            );
+        });
+    }
+}
@@ -32,22 +32,28 @@ impl UIBox {
             },
         };
 
-        match &handle {
-            Some(handle) => match handle {
-                UIBoxDragHandle::Top => {
+        if let Some(handle) = &handle {
+            // Corner handles highlight both of their edges.
+
+            match handle.get_vertical_edge() {
+                Some(UIBoxDragHandle::Top) => {
                     target.horizontal_line_unsafe(x1 as u32, x2 as u32, y1 as u32, color)
                 }
-                UIBoxDragHandle::Bottom => {
+                Some(UIBoxDragHandle::Bottom) => {
                     target.horizontal_line_unsafe(x1 as u32, x2 as u32, y2 as u32, color)
                 }
-                UIBoxDragHandle::Left => {
+                _ => (),
+            }
+
+            match handle.get_horizontal_edge() {
+                Some(UIBoxDragHandle::Left) => {
                     target.vertical_line_unsafe(x1 as u32, y1 as u32, y2 as u32, color)
                 }
-                UIBoxDragHandle::Right => {
+                Some(UIBoxDragHandle::Right) => {
                     target.vertical_line_unsafe(x2 as u32, y1 as u32, y2 as u32, color)
                 }
-            },
-            None => (),
+                _ => (),
+            }
         }
     }
 }
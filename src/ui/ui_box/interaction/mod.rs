@@ -227,7 +227,8 @@ impl UIBoxInteraction {
                     ),
                 };
 
-                if features.contains(UIBoxFeatureFlag::ResizableMinExtentOnPrimaryAxis)
+                let primary_handle = if features
+                    .contains(UIBoxFeatureFlag::ResizableMinExtentOnPrimaryAxis)
                     && within_epsilon(mouse_primary, min_primary)
                     && (min_secondary..max_secondary + 1).contains(&mouse_secondary)
                 {
@@ -237,7 +238,12 @@ impl UIBoxInteraction {
                     && (min_secondary..max_secondary + 1).contains(&mouse_secondary)
                 {
                     Some(drag_handle_max_primary)
-                } else if features.contains(UIBoxFeatureFlag::ResizableMinExtentOnSecondaryAxis)
+                } else {
+                    None
+                };
+
+                let secondary_handle = if features
+                    .contains(UIBoxFeatureFlag::ResizableMinExtentOnSecondaryAxis)
                     && within_epsilon(mouse_secondary, min_secondary)
                     && (min_primary..max_primary + 1).contains(&mouse_primary)
                 {
@@ -249,6 +255,14 @@ impl UIBoxInteraction {
                     Some(drag_handle_max_secondary)
                 } else {
                     None
+                };
+
+                // Hovering near two adjacent edges selects the corner between them.
+
+                match (primary_handle, secondary_handle) {
+                    (Some(a), Some(b)) => Some(UIBoxDragHandle::corner(a, b)),
+                    (Some(handle), None) | (None, Some(handle)) => Some(handle),
+                    (None, None) => None,
                 }
             }
         };
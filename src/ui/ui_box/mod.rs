@@ -88,6 +88,40 @@ pub enum UIBoxDragHandle {
     Top,
     Bottom,
     Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl UIBoxDragHandle {
+    pub fn corner(a: Self, b: Self) -> Self {
+        // Combines a horizontal and a vertical edge into a corner handle.
+
+        match (a, b) {
+            (Self::Left, Self::Top) | (Self::Top, Self::Left) => Self::TopLeft,
+            (Self::Right, Self::Top) | (Self::Top, Self::Right) => Self::TopRight,
+            (Self::Left, Self::Bottom) | (Self::Bottom, Self::Left) => Self::BottomLeft,
+            (Self::Right, Self::Bottom) | (Self::Bottom, Self::Right) => Self::BottomRight,
+            _ => a,
+        }
+    }
+
+    pub fn get_horizontal_edge(&self) -> Option<Self> {
+        match self {
+            Self::Left | Self::TopLeft | Self::BottomLeft => Some(Self::Left),
+            Self::Right | Self::TopRight | Self::BottomRight => Some(Self::Right),
+            Self::Top | Self::Bottom => None,
+        }
+    }
+
+    pub fn get_vertical_edge(&self) -> Option<Self> {
+        match self {
+            Self::Top | Self::TopLeft | Self::TopRight => Some(Self::Top),
+            Self::Bottom | Self::BottomLeft | Self::BottomRight => Some(Self::Bottom),
+            Self::Left | Self::Right => None,
+        }
+    }
 }
 
 pub type UIBoxCustomRenderCallback =
@@ -111,6 +145,9 @@ pub struct UIBox {
     pub styles: UIBoxStyles,
     pub expanded: bool,
     pub selected_item_index: usize,
+    pub scroll_offset: usize,
+    // Shown near the mouse cursor after this box is hovered for a while.
+    pub tooltip: Option<String>,
     #[serde(skip)]
     pub computed_relative_position: [f32; UI_2D_AXIS_COUNT], // Position relative to parent, in pixels.
     #[serde(skip)]
@@ -150,6 +187,8 @@ impl Debug for UIBox {
             .field("styles", &self.styles)
             .field("expanded", &self.expanded)
             .field("selected_item_index", &self.selected_item_index)
+            .field("scroll_offset", &self.scroll_offset)
+            .field("tooltip", &self.tooltip)
             .field(
                 "computed_relative_position",
                 &self.computed_relative_position,
@@ -501,6 +540,12 @@ impl UIBox {
                         UIBoxDragHandle::Top | UIBoxDragHandle::Bottom => {
                             MouseCursorKind::DragUpDown
                         }
+                        UIBoxDragHandle::TopLeft | UIBoxDragHandle::BottomRight => {
+                            MouseCursorKind::DraftDiagonalRightDown
+                        }
+                        UIBoxDragHandle::TopRight | UIBoxDragHandle::BottomLeft => {
+                            MouseCursorKind::DragDiagonalLeftDown
+                        }
                     };
                 });
             }
@@ -563,6 +608,7 @@ impl UIBox {
             cached.global_bounds = self.global_bounds;
             cached.expanded = self.expanded;
             cached.selected_item_index = self.selected_item_index;
+            cached.scroll_offset = self.scroll_offset;
             cached.hot = self.hot;
             cached.hot_transition = self.hot_transition;
             cached.active = self.active;
@@ -121,6 +121,16 @@ impl<'a> UIBoxTree<'a> {
                 *ctx.cursor_kind.borrow_mut() = MouseCursorKind::Hand;
             }
 
+            // Requests this box's tooltip (if any) while it's hovered.
+
+            if interaction_result.mouse_interaction_in_bounds.is_hovering {
+                if let Some(tooltip) = &ui_box.tooltip {
+                    ctx.tooltip
+                        .borrow_mut()
+                        .request(ui_box.key.clone(), tooltip);
+                }
+            }
+
             ui_box.hot_drag_handle = interaction_result
                 .mouse_interaction_in_bounds
                 .hot_drag_handle;
@@ -249,6 +259,8 @@ impl<'a> UIBoxTree<'a> {
 
         ui_debug_print!(">\n> (Standalone sizes pass...)\n>");
 
+        let content_scale = GLOBAL_UI_CONTEXT.with(|ctx| *ctx.content_scale.borrow());
+
         #[allow(unused)]
         self.tree.visit_root_dfs_mut(
             &NodeLocalTraversalMethod::PreOrder,
@@ -274,7 +286,12 @@ impl<'a> UIBoxTree<'a> {
                                 format!("{}: Pixel size for {} axis: {}", ui_box.id, axis, pixels)
                             );
 
-                            ui_box.computed_size[screen_axis_index] = pixels as f32;
+                            // A tree's root is sized to its window (or screen),
+                            // whose size is already in physical pixels.
+
+                            let scale = if parent_data.is_some() { content_scale } else { 1.0 };
+
+                            ui_box.computed_size[screen_axis_index] = (pixels as f32 * scale).round();
                         }
                         UISize::TextContent => {
                             let (texture_width, texture_height) = GLOBAL_UI_CONTEXT.with(|ctx| {
@@ -991,3 +1008,71 @@ impl<'a> UIBoxTree<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::ui::{
+        context::GLOBAL_UI_CONTEXT,
+        fastpath::{button::button, container::container},
+        ui_box::UILayoutDirection,
+        UISize, UISizeWithStrictness,
+    };
+
+    use super::UIBoxTree;
+
+    // Returns the (computed) heights of the tree's root and button.
+    fn get_heights(content_scale: f32) -> (f32, f32) {
+        GLOBAL_UI_CONTEXT.with(|ctx| ctx.set_content_scale(content_scale));
+
+        let mut tree = UIBoxTree::default();
+
+        let sizes = |width: u32, height: u32| {
+            [
+                UISizeWithStrictness {
+                    size: UISize::Pixels(width),
+                    strictness: 1.0,
+                },
+                UISizeWithStrictness {
+                    size: UISize::Pixels(height),
+                    strictness: 1.0,
+                },
+            ]
+        };
+
+        tree.push_parent(container(
+            "root".to_string(),
+            UILayoutDirection::LeftToRight,
+            Some(sizes(400, 400)),
+        ))
+        .unwrap();
+
+        tree.push(button(
+            "button".to_string(),
+            "Button".to_string(),
+            Some(sizes(80, 20)),
+        ))
+        .unwrap();
+
+        tree.commit_frame().unwrap();
+
+        let root = tree.tree.root.as_ref().unwrap().borrow();
+
+        let button_node = root.children[0].borrow();
+
+        (
+            root.data.computed_size[1],
+            button_node.data.computed_size[1],
+        )
+    }
+
+    #[test]
+    fn doubling_content_scale_doubles_button_height() {
+        let (root_height, button_height) = get_heights(1.0);
+
+        assert_eq!((root_height, button_height), (400.0, 20.0));
+
+        // The root keeps its (window) size.
+
+        assert_eq!(get_heights(2.0), (root_height, button_height * 2.0));
+    }
+}
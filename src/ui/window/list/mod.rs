@@ -1,17 +1,28 @@
-use sdl2::mouse::MouseButton;
+use sdl2::{keyboard::Mod, mouse::MouseButton};
 
 use crate::{
-    app::resolution::Resolution, buffer::Buffer2D, device::mouse::MouseEventKind,
-    mem::linked_list::LinkedList, ui::context::GLOBAL_UI_CONTEXT,
+    app::resolution::Resolution,
+    buffer::Buffer2D,
+    device::mouse::MouseEventKind,
+    graphics::Graphics,
+    mem::linked_list::LinkedList,
+    ui::{
+        context::GLOBAL_UI_CONTEXT, context_menu::render_context_menu,
+        drag_drop::render_drag_preview, tooltip::render_tooltip,
+    },
 };
 
 use super::Window;
 
+// Modifier keys that must be held while dragging a floating window, for it to
+// dock (rather than simply move) when released over a docked window's panel.
+pub static DOCK_MODIFIERS: Mod = Mod::LCTRLMOD.union(Mod::RCTRLMOD);
+
 #[derive(Default, Debug, Clone)]
 pub struct WindowList<'a>(pub LinkedList<Window<'a>>);
 
 impl<'a> WindowList<'a> {
-    pub fn rebuild_ui_trees(&mut self, resolution: Resolution) {
+    pub fn rebuild_ui_trees(&mut self, resolution: Resolution) -> Result<(), String> {
         let mut focused_window = None;
 
         {
@@ -71,7 +82,91 @@ impl<'a> WindowList<'a> {
             self.0.push_back(window);
         }
 
+        // Every window's widgets have requested their tooltips by now.
+
+        GLOBAL_UI_CONTEXT.with(|ctx| {
+            let uptime_seconds = ctx.timing_info.borrow().uptime_seconds;
+
+            ctx.tooltip.borrow_mut().update(uptime_seconds);
+        });
+
+        let result = self.update_docking();
+
         self.0.retain(|window| window.active);
+
+        result
+    }
+
+    // While a floating window is dragged over a docked window's panel (with
+    // `DOCK_MODIFIERS` held), records the drop zone beneath the mouse; on
+    // release, docks the floating window's panel tree into that panel.
+    fn update_docking(&mut self) -> Result<(), String> {
+        let (mouse_position, is_docking) = GLOBAL_UI_CONTEXT.with(|ctx| {
+            let input_events = ctx.input_events.borrow();
+
+            let position = input_events.mouse.position;
+
+            (
+                (position.0.max(0) as u32, position.1.max(0) as u32),
+                input_events.keyboard.modifiers.intersects(DOCK_MODIFIERS),
+            )
+        });
+
+        let hovered_target = self
+            .0
+            .iter()
+            .filter(|window| is_docking && window.docked && window.active)
+            .find_map(|window| {
+                window
+                    .panel_tree
+                    .borrow()
+                    .get_dock_target(mouse_position)
+                    .map(|target| (window.id.clone(), target))
+            });
+
+        let mut dropped = None;
+
+        for window in self.0.iter_mut() {
+            if window.docked {
+                continue;
+            }
+
+            if window.dragging {
+                window.dock_target = hovered_target.as_ref().map(|(_, target)| target.clone());
+            } else if let Some(target) = window.dock_target.take() {
+                // The window was released above a drop zone.
+
+                if let Some((target_window_id, _)) = &hovered_target {
+                    dropped.replace((
+                        window.id.clone(),
+                        window.title.clone(),
+                        window.panel_tree.borrow().clone(),
+                        target_window_id.clone(),
+                        target,
+                    ));
+                }
+            }
+        }
+
+        if let Some((source_id, title, source_tree, target_window_id, target)) = dropped {
+            for window in self.0.iter_mut() {
+                if window.id == target_window_id {
+                    let mut panel_tree = window.panel_tree.borrow_mut();
+
+                    panel_tree
+                        .dock(&target.panel_path, target.zone, &title, &source_tree)
+                        .map_err(|err| format!("Failed to dock window '{}': {}", source_id, err))?;
+                }
+            }
+
+            for window in self.0.iter_mut() {
+                if window.id == source_id {
+                    window.active = false;
+                }
+            }
+        }
+
+        Ok(())
     }
 
     pub fn render(&mut self, frame_index: u32, framebuffer: &mut Buffer2D) -> Result<(), String> {
@@ -79,6 +174,35 @@ impl<'a> WindowList<'a> {
             window.render(frame_index, framebuffer)?;
         }
 
-        Ok(())
+        // Highlights the drop zone for any window being dragged for docking.
+
+        for window in self.0.iter() {
+            if let Some(target) = &window.dock_target {
+                let preview = target.zone.get_preview_extent(&target.bounds);
+
+                let color = GLOBAL_UI_CONTEXT.with(|ctx| ctx.theme.borrow().background_selected);
+
+                Graphics::rectangle(
+                    framebuffer,
+                    preview.left,
+                    preview.top,
+                    preview.right - preview.left,
+                    preview.bottom - preview.top,
+                    None,
+                    Some(color.to_u32()),
+                );
+            }
+        }
+
+        // Draws any open context menu, and drag-and-drop preview, above all
+        // windows.
+
+        render_context_menu(framebuffer)?;
+
+        render_drag_preview(framebuffer);
+
+        // Draws the hovered widget's tooltip above everything else.
+
+        render_tooltip(framebuffer)
     }
 }
@@ -4,17 +4,13 @@ use serde::{Deserialize, Serialize};
 
 use sdl2::mouse::MouseButton;
 
-use crate::{
-    app::resolution::Resolution,
-    buffer::Buffer2D,
-    device::mouse::{MouseEventKind, MouseState},
-};
+use crate::{app::resolution::Resolution, buffer::Buffer2D, device::mouse::MouseEventKind};
 
 use super::{
     context::{UIContext, GLOBAL_UI_CONTEXT},
     extent::ScreenExtent,
     fastpath::{button::button, container::container, spacer::greedy_spacer, text::text},
-    panel::tree::PanelTree,
+    panel::tree::{dock::DockTarget, PanelTree},
     ui_box::{
         interaction::UIBoxInteraction,
         tree::{UIBoxTree, UIBoxTreeRenderCallback},
@@ -53,12 +49,20 @@ pub struct Window<'a> {
     pub size: (u32, u32),
     pub extent: ScreenExtent,
     pub with_titlebar: bool,
+    pub resizable: bool,
+    pub minimum_size: (u32, u32),
+    pub maximized: bool,
+    // Position and size to return to when a maximized window is restored.
+    pub restore_position_and_size: Option<((u32, u32), (u32, u32))>,
     #[serde(skip)]
     pub render_header_callback: Option<UIBoxTreeRenderCallback>,
     #[serde(skip)]
     pub panel_tree: RefCell<PanelTree<'a>>,
     #[serde(skip)]
     pub ui_trees: WindowUITrees<'a>,
+    // Where this (floating) window would dock if dropped now.
+    #[serde(skip)]
+    pub dock_target: Option<DockTarget>,
 }
 
 impl<'a> fmt::Debug for Window<'a> {
@@ -73,6 +77,10 @@ impl<'a> fmt::Debug for Window<'a> {
             .field("size", &self.size)
             .field("extent", &self.extent)
             .field("with_titlebar", &self.with_titlebar)
+            .field("resizable", &self.resizable)
+            .field("minimum_size", &self.minimum_size)
+            .field("maximized", &self.maximized)
+            .field("restore_position_and_size", &self.restore_position_and_size)
             .field(
                 "render_callback",
                 match self.render_header_callback {
@@ -82,6 +90,7 @@ impl<'a> fmt::Debug for Window<'a> {
             )
             .field("panel_tree", &self.panel_tree)
             .field("ui_trees", &self.ui_trees)
+            .field("dock_target", &self.dock_target)
             .finish()
     }
 }
@@ -90,8 +99,10 @@ impl<'a> fmt::Debug for Window<'a> {
 pub struct WindowOptions {
     pub docked: bool,
     pub with_titlebar: bool,
+    pub resizable: bool,
     pub position: (u32, u32),
     pub size: (u32, u32),
+    pub minimum_size: (u32, u32),
 }
 
 impl WindowOptions {
@@ -99,8 +110,10 @@ impl WindowOptions {
         Self {
             docked: true,
             with_titlebar: false,
+            resizable: false,
             position: (0, 0),
             size: (resolution.width, resolution.height),
+            minimum_size: (0, 0),
         }
     }
 }
@@ -110,8 +123,10 @@ impl Default for WindowOptions {
         Self {
             docked: false,
             with_titlebar: true,
+            resizable: true,
             position: (8, 8),
             size: (200, 200),
+            minimum_size: (64, 64),
         }
     }
 }
@@ -125,6 +140,7 @@ pub struct WindowRenderTitlebarResult {
     pub did_start_dragging: bool,
     pub did_stop_dragging: bool,
     pub position_delta: Option<(i32, i32)>,
+    pub should_toggle_maximized: bool,
     pub should_close: bool,
 }
 
@@ -142,6 +158,8 @@ impl<'a> Window<'a> {
             docked: options.docked,
             active: true,
             with_titlebar: options.with_titlebar,
+            resizable: options.resizable,
+            minimum_size: options.minimum_size,
             position: options.position,
             size: options.size,
             extent: ScreenExtent::new(options.position, options.size),
@@ -166,11 +184,16 @@ impl<'a> Window<'a> {
 
         // Rebuilds the UI tree root based on the current window resolution.
 
-        if self.docked {
+        if self.docked || self.maximized {
             self.size.0 = main_window_bounds.width;
             self.size.1 = main_window_bounds.height;
         }
 
+        if self.maximized {
+            self.position = (0, 0);
+            self.extent = ScreenExtent::new(self.position, self.size);
+        }
+
         let mut root_ui_box: UIBox = Default::default();
 
         let mut root_ui_box_result: UIBoxInteraction = Default::default();
@@ -184,7 +207,7 @@ impl<'a> Window<'a> {
                     UIBoxFeatureMask::none()
                         | UIBoxFeatureFlag::DrawFill
                         | UIBoxFeatureFlag::DrawChildDividers
-                        | if self.docked {
+                        | if self.docked || self.maximized || !self.resizable {
                             UIBoxFeatureMask::none()
                         } else {
                             UIBoxFeatureFlag::ResizableMinExtentOnPrimaryAxis
@@ -225,6 +248,7 @@ impl<'a> Window<'a> {
                     &self.id,
                     &self.title,
                     self.dragging,
+                    self.maximized,
                     &root_ui_box_result
                         .mouse_interaction_in_bounds
                         .active_drag_handle,
@@ -267,8 +291,8 @@ impl<'a> Window<'a> {
             Some(handle) => {
                 let mouse = &ctx.input_events.borrow().mouse;
 
-                if mouse.drag_events.contains_key(&MouseButton::Left) {
-                    self.apply_resize_event(mouse, handle, main_window_bounds);
+                if let Some(drag_event) = mouse.drag_events.get(&MouseButton::Left) {
+                    self.apply_resize_delta(handle, drag_event.delta, main_window_bounds);
                 }
             }
             None => (),
@@ -287,7 +311,11 @@ impl<'a> Window<'a> {
                 self.dragging = false;
             }
 
-            if self.dragging {
+            if result.should_toggle_maximized {
+                self.toggle_maximized(main_window_bounds);
+            }
+
+            if self.dragging && !self.maximized {
                 if let Some(delta) = result.position_delta {
                     self.apply_position_delta(delta, main_window_bounds);
                 }
@@ -321,6 +349,54 @@ impl<'a> Window<'a> {
         Ok(())
     }
 
+    pub fn toggle_maximized(&mut self, main_window_bounds: &Resolution) {
+        if self.maximized {
+            self.restore(main_window_bounds);
+        } else {
+            self.maximize(main_window_bounds);
+        }
+    }
+
+    pub fn maximize(&mut self, main_window_bounds: &Resolution) {
+        if self.docked || self.maximized {
+            return;
+        }
+
+        self.restore_position_and_size = Some((self.position, self.size));
+
+        self.maximized = true;
+        self.dragging = false;
+
+        self.position = (0, 0);
+        self.size = (main_window_bounds.width, main_window_bounds.height);
+
+        self.extent = ScreenExtent::new(self.position, self.size);
+    }
+
+    pub fn restore(&mut self, main_window_bounds: &Resolution) {
+        if !self.maximized {
+            return;
+        }
+
+        self.maximized = false;
+
+        if let Some((position, size)) = self.restore_position_and_size.take() {
+            // The main window may have shrunk while we were maximized.
+
+            self.size = (
+                size.0.min(main_window_bounds.width),
+                size.1.min(main_window_bounds.height),
+            );
+
+            self.position = (
+                position.0.min(main_window_bounds.width - self.size.0),
+                position.1.min(main_window_bounds.height - self.size.1),
+            );
+        }
+
+        self.extent = ScreenExtent::new(self.position, self.size);
+    }
+
     fn get_computed_size_of_root_child(&self, index: usize) -> Option<(u32, u32)> {
         let base_ui_tree = self.ui_trees.base.borrow();
         let root_rc_option = &base_ui_tree.tree.root;
@@ -363,9 +439,11 @@ impl<'a> Window<'a> {
 
         let panel_tree_computed_size = self.get_computed_size_of_panel_tree().unwrap_or_default();
 
+        // Never shrink past the window's configured minimum size.
+
         (
-            panel_tree_computed_size.0,
-            titlebar_computed_size.1 + panel_tree_computed_size.1,
+            panel_tree_computed_size.0.max(self.minimum_size.0),
+            (titlebar_computed_size.1 + panel_tree_computed_size.1).max(self.minimum_size.1),
         )
     }
 
@@ -379,16 +457,29 @@ impl<'a> Window<'a> {
         self.extent = ScreenExtent::new(self.position, self.size);
     }
 
-    fn apply_resize_event(
+    fn apply_resize_delta(
         &mut self,
-        mouse_state: &MouseState,
         handle: &UIBoxDragHandle,
+        delta: (i32, i32),
         main_window_bounds: &Resolution,
     ) {
-        let drag_event = mouse_state.drag_events.get(&MouseButton::Left);
+        // Corner handles resize along both axes, one edge at a time.
 
-        let mut delta = drag_event.as_ref().unwrap().delta;
+        if let Some(edge) = handle.get_horizontal_edge() {
+            self.apply_edge_resize_delta(&edge, delta.0, main_window_bounds);
+        }
 
+        if let Some(edge) = handle.get_vertical_edge() {
+            self.apply_edge_resize_delta(&edge, delta.1, main_window_bounds);
+        }
+    }
+
+    fn apply_edge_resize_delta(
+        &mut self,
+        edge: &UIBoxDragHandle,
+        mut delta: i32,
+        main_window_bounds: &Resolution,
+    ) {
         // Restricts the resize deltas to protect the minimum size needed for
         // the window's inner content.
 
@@ -399,51 +490,50 @@ impl<'a> Window<'a> {
             self.size.1.max(minimum_size.1) as i32 - minimum_size.1 as i32,
         );
 
-        match &handle {
+        match edge {
             UIBoxDragHandle::Left => {
-                if delta.0 > 0 {
-                    delta.0 = delta.0.min(buffer.0);
+                if delta > 0 {
+                    delta = delta.min(buffer.0);
                 } else {
-                    delta.0 = delta.0.max(-(self.extent.left as i32));
+                    delta = delta.max(-(self.extent.left as i32));
                 }
 
-                self.size.0 = (self.size.0 as i32 - delta.0) as u32;
+                self.size.0 = (self.size.0 as i32 - delta) as u32;
 
-                self.apply_position_delta((delta.0, 0), main_window_bounds);
+                self.apply_position_delta((delta, 0), main_window_bounds);
             }
             UIBoxDragHandle::Right => {
-                if delta.0 < 0 {
-                    delta.0 = delta.0.max(-buffer.0);
+                if delta < 0 {
+                    delta = delta.max(-buffer.0);
                 } else {
-                    delta.0 = delta
-                        .0
-                        .min(main_window_bounds.width as i32 - 1 - self.extent.right as i32);
+                    delta =
+                        delta.min(main_window_bounds.width as i32 - 1 - self.extent.right as i32);
                 }
 
-                self.size.0 = (self.size.0 as i32 + delta.0) as u32;
+                self.size.0 = (self.size.0 as i32 + delta) as u32;
             }
             UIBoxDragHandle::Top => {
-                if delta.1 > 0 {
-                    delta.1 = delta.1.min(buffer.1);
+                if delta > 0 {
+                    delta = delta.min(buffer.1);
                 } else {
-                    delta.1 = delta.1.max(-(self.extent.top as i32));
+                    delta = delta.max(-(self.extent.top as i32));
                 }
 
-                self.size.1 = (self.size.1 as i32 - delta.1) as u32;
+                self.size.1 = (self.size.1 as i32 - delta) as u32;
 
-                self.apply_position_delta((0, delta.1), main_window_bounds);
+                self.apply_position_delta((0, delta), main_window_bounds);
             }
             UIBoxDragHandle::Bottom => {
-                if delta.1 < 0 {
-                    delta.1 = delta.1.max(-buffer.1);
+                if delta < 0 {
+                    delta = delta.max(-buffer.1);
                 } else {
-                    delta.1 = delta
-                        .1
-                        .min(main_window_bounds.height as i32 - 1 - self.extent.bottom as i32);
+                    delta =
+                        delta.min(main_window_bounds.height as i32 - 1 - self.extent.bottom as i32);
                 }
 
-                self.size.1 = (self.size.1 as i32 + delta.1) as u32;
+                self.size.1 = (self.size.1 as i32 + delta) as u32;
             }
+            _ => (),
         }
 
         self.extent = ScreenExtent::new(self.position, self.size);
@@ -454,6 +544,7 @@ fn render_titlebar(
     id: &str,
     title: &str,
     was_dragging: bool,
+    is_maximized: bool,
     active_drag_handle: &Option<UIBoxDragHandle>,
     tree: &mut UIBoxTree,
 ) -> Result<WindowRenderTitlebarResult, String> {
@@ -461,6 +552,7 @@ fn render_titlebar(
         did_start_dragging: false,
         did_stop_dragging: false,
         position_delta: None,
+        should_toggle_maximized: false,
         should_close: false,
     };
 
@@ -490,6 +582,7 @@ fn render_titlebar(
 
             if active_drag_handle.is_none()
                 && !was_dragging
+                && !is_maximized
                 && container_box_result
                     .mouse_interaction_in_bounds
                     .was_left_pressed
@@ -525,6 +618,24 @@ fn render_titlebar(
 
             tree.push(greedy_spacer())?;
 
+            let mut maximize_button = button(
+                format!("{}_WindowTitleBarMaximize", id),
+                if is_maximized { "Restore" } else { "Maximize" }.to_string(),
+                None,
+            );
+
+            maximize_button.features ^=
+                UIBoxFeatureFlag::DrawFill | UIBoxFeatureFlag::EmbossAndDeboss;
+
+            let maximize_button_interaction = tree.push(maximize_button)?;
+
+            if maximize_button_interaction
+                .mouse_interaction_in_bounds
+                .was_left_pressed
+            {
+                result.should_toggle_maximized = true;
+            }
+
             let mut close_button = button(
                 format!("{}_WindowTitleBarClose", id),
                 "Close".to_string(),
@@ -548,3 +659,73 @@ fn render_titlebar(
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{app::resolution::Resolution, ui::ui_box::UIBoxDragHandle};
+
+    use super::{Window, WindowOptions};
+
+    fn make_window() -> Window<'static> {
+        Window::new(
+            "TestWindow".to_string(),
+            "Test Window".to_string(),
+            WindowOptions {
+                position: (10, 10),
+                size: (200, 150),
+                minimum_size: (120, 80),
+                ..Default::default()
+            },
+            None,
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn dragging_the_right_edge_resizes_by_the_drag_delta() {
+        let bounds = Resolution {
+            width: 800,
+            height: 600,
+        };
+
+        let mut window = make_window();
+
+        window.apply_resize_delta(&UIBoxDragHandle::Right, (50, 0), &bounds);
+
+        assert_eq!(window.size, (250, 150));
+        assert_eq!(window.position, (10, 10));
+        assert_eq!(window.extent.right, 260);
+
+        // Shrinking past the minimum size clamps to the minimum.
+
+        window.apply_resize_delta(&UIBoxDragHandle::Right, (-500, 0), &bounds);
+
+        assert_eq!(window.size, (120, 150));
+
+        // Corner handles resize along both axes.
+
+        window.apply_resize_delta(&UIBoxDragHandle::BottomRight, (30, 20), &bounds);
+
+        assert_eq!(window.size, (150, 170));
+    }
+
+    #[test]
+    fn maximized_window_restores_its_previous_extent() {
+        let bounds = Resolution {
+            width: 800,
+            height: 600,
+        };
+
+        let mut window = make_window();
+
+        window.toggle_maximized(&bounds);
+
+        assert!(window.maximized);
+        assert_eq!((window.position, window.size), ((0, 0), (800, 600)));
+
+        window.toggle_maximized(&bounds);
+
+        assert!(!window.maximized);
+        assert_eq!((window.position, window.size), ((10, 10), (200, 150)));
+    }
+}
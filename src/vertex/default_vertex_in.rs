@@ -9,6 +9,7 @@ pub struct DefaultVertexIn {
     pub tangent: Vec3,
     pub bitangent: Vec3,
     pub uv: Vec2,
+    pub lightmap_uv: Vec2,
     pub color: Vec3,
 }
 
@@ -21,6 +22,7 @@ impl Add<DefaultVertexIn> for DefaultVertexIn {
             tangent: self.tangent + rhs.tangent,
             bitangent: self.bitangent + rhs.bitangent,
             uv: self.uv + rhs.uv,
+            lightmap_uv: self.lightmap_uv + rhs.lightmap_uv,
             color: self.color + rhs.color,
         }
     }
@@ -35,6 +37,7 @@ impl Sub<DefaultVertexIn> for DefaultVertexIn {
             tangent: self.tangent - rhs.tangent,
             bitangent: self.bitangent - rhs.bitangent,
             uv: self.uv - rhs.uv,
+            lightmap_uv: self.lightmap_uv - rhs.lightmap_uv,
             color: self.color - rhs.color,
         }
     }
@@ -49,6 +52,7 @@ impl Mul<f32> for DefaultVertexIn {
             tangent: self.tangent * scalar,
             bitangent: self.bitangent * scalar,
             uv: self.uv * scalar,
+            lightmap_uv: self.lightmap_uv * scalar,
             color: self.color * scalar,
         }
     }
@@ -63,6 +67,7 @@ impl Div<f32> for DefaultVertexIn {
             tangent: self.tangent / scalar,
             bitangent: self.bitangent / scalar,
             uv: self.uv / scalar,
+            lightmap_uv: self.lightmap_uv / scalar,
             color: self.color / scalar,
         }
     }
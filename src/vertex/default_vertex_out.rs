@@ -2,7 +2,7 @@ use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub};
 
 use crate::{
     matrix::Mat4,
-    render::viewport::RenderViewport,
+    render::{options::rasterizer::AttributeInterpolation, viewport::RenderViewport},
     vec::{vec2::Vec2, vec3::Vec3, vec4::Vec4},
 };
 
@@ -98,6 +98,13 @@ pub struct DefaultVertexOut {
     pub tangent_space_info: TangentSpaceInfo,
     pub color: Vec3,
     pub uv: Vec2,
+    pub lightmap_uv: Vec2,
+    // UV-space area covered by one viewport pixel, across this vertex's
+    // triangle; selects mipmap levels.
+    pub uv_footprint: f32,
+    // 1.0 if this vertex's triangle winds away from the viewer (in screen
+    // space), else 0.0; constant across the triangle.
+    pub back_facing: f32,
     pub depth: f32,
 }
 
@@ -106,10 +113,19 @@ impl DefaultVertexOut {
         Default::default()
     }
 
-    pub fn projection_space_to_viewport_space(&mut self, viewport: &RenderViewport) {
+    pub fn projection_space_to_viewport_space(
+        &mut self,
+        viewport: &RenderViewport,
+        interpolation: AttributeInterpolation,
+    ) {
         let w_inverse = 1.0 / self.position_projection_space.w;
 
-        *self *= w_inverse;
+        // Affine interpolation leaves the remaining attributes in linear space.
+
+        match interpolation {
+            AttributeInterpolation::PerspectiveCorrect => *self *= w_inverse,
+            AttributeInterpolation::Affine => self.position_projection_space *= w_inverse,
+        }
 
         self.position_projection_space.x =
             (self.position_projection_space.x + 1.0) * viewport.width_over_2;
@@ -119,6 +135,18 @@ impl DefaultVertexOut {
 
         self.position_projection_space.w = w_inverse;
     }
+
+    pub fn snap_to_viewport_grid(&mut self, viewport: &RenderViewport, resolution: (u32, u32)) {
+        // Size of one grid cell, in viewport pixels.
+
+        let cell_width = viewport.width as f32 / resolution.0.max(1) as f32;
+        let cell_height = viewport.height as f32 / resolution.1.max(1) as f32;
+
+        let position = &mut self.position_projection_space;
+
+        position.x = (position.x / cell_width).round() * cell_width;
+        position.y = (position.y / cell_height).round() * cell_height;
+    }
 }
 
 impl Add<DefaultVertexOut> for DefaultVertexOut {
@@ -135,6 +163,9 @@ impl Add<DefaultVertexOut> for DefaultVertexOut {
             tangent_space_info: self.tangent_space_info + rhs.tangent_space_info,
             color: self.color + rhs.color,
             uv: self.uv + rhs.uv,
+            lightmap_uv: self.lightmap_uv + rhs.lightmap_uv,
+            uv_footprint: self.uv_footprint + rhs.uv_footprint,
+            back_facing: self.back_facing + rhs.back_facing,
             depth: self.depth + rhs.depth,
         }
     }
@@ -151,6 +182,9 @@ impl AddAssign<DefaultVertexOut> for DefaultVertexOut {
         self.tangent_space_info += rhs.tangent_space_info;
         self.color += rhs.color;
         self.uv += rhs.uv;
+        self.lightmap_uv += rhs.lightmap_uv;
+        self.uv_footprint += rhs.uv_footprint;
+        self.back_facing += rhs.back_facing;
         self.depth += rhs.depth;
     }
 }
@@ -169,6 +203,9 @@ impl Sub<DefaultVertexOut> for DefaultVertexOut {
             tangent_space_info: self.tangent_space_info - rhs.tangent_space_info,
             color: self.color - rhs.color,
             uv: self.uv - rhs.uv,
+            lightmap_uv: self.lightmap_uv - rhs.lightmap_uv,
+            uv_footprint: self.uv_footprint - rhs.uv_footprint,
+            back_facing: self.back_facing - rhs.back_facing,
             depth: self.depth - rhs.depth,
         }
     }
@@ -187,6 +224,9 @@ impl Mul<f32> for DefaultVertexOut {
             tangent_space_info: self.tangent_space_info * scalar,
             color: self.color * scalar,
             uv: self.uv * scalar,
+            lightmap_uv: self.lightmap_uv * scalar,
+            uv_footprint: self.uv_footprint * scalar,
+            back_facing: self.back_facing * scalar,
             depth: self.depth * scalar,
         }
     }
@@ -203,6 +243,9 @@ impl MulAssign<f32> for DefaultVertexOut {
         self.tangent_space_info *= scalar;
         self.color *= scalar;
         self.uv *= scalar;
+        self.lightmap_uv *= scalar;
+        self.uv_footprint *= scalar;
+        self.back_facing *= scalar;
         self.depth *= scalar;
     }
 }
@@ -220,7 +263,53 @@ impl Div<f32> for DefaultVertexOut {
             tangent_space_info: self.tangent_space_info / scalar,
             color: self.color / scalar,
             uv: self.uv / scalar,
+            lightmap_uv: self.lightmap_uv / scalar,
+            uv_footprint: self.uv_footprint / scalar,
+            back_facing: self.back_facing / scalar,
             depth: self.depth / scalar,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        render::viewport::RenderViewport,
+        vec::{vec3::Vec3, vec4::Vec4},
+    };
+
+    use super::DefaultVertexOut;
+
+    #[test]
+    fn snapped_vertex_lands_on_nearest_grid_increment() {
+        let viewport = RenderViewport {
+            width: 640,
+            width_over_2: 320.0,
+            height: 480,
+            height_over_2: 240.0,
+        };
+
+        let mut vertex = DefaultVertexOut {
+            position_projection_space: Vec4::new(
+                Vec3 {
+                    x: 101.3,
+                    y: 76.9,
+                    z: 0.5,
+                },
+                1.0,
+            ),
+            ..Default::default()
+        };
+
+        // A 320x240 grid gives cells of 2x2 viewport pixels.
+
+        vertex.snap_to_viewport_grid(&viewport, (320, 240));
+
+        assert_eq!(vertex.position_projection_space.x, 102.0);
+        assert_eq!(vertex.position_projection_space.y, 76.0);
+
+        // Depth is unaffected.
+
+        assert_eq!(vertex.position_projection_space.z, 0.5);
+    }
+}